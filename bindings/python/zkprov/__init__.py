@@ -89,6 +89,12 @@ _LIB.zkp_verify.argtypes = [
     POINTER(c_char_p),
 ]
 
+# int32_t zkp_list_commitments(const char* backend_id, char** out_json);
+# ABI 2+: absent from older libraries, so only declared when exported.
+if hasattr(_LIB, "zkp_list_commitments"):
+    _LIB.zkp_list_commitments.restype = c_int
+    _LIB.zkp_list_commitments.argtypes = [c_char_p, POINTER(c_char_p)]
+
 # void zkp_free(void*);
 _LIB.zkp_free.restype = None
 _LIB.zkp_free.argtypes = [c_void_p]
@@ -139,6 +145,17 @@ def list_profiles() -> dict:
     return payload
 
 
+def list_commitments(backend_id: str) -> dict:
+    if not hasattr(_LIB, "zkp_list_commitments"):
+        raise RuntimeError("zkp_list_commitments needs libzkprov ABI 2 or newer")
+    out = c_char_p()
+    code = _LIB.zkp_list_commitments(backend_id.encode("utf-8"), ctypes.byref(out))
+    payload = _decode_json(out)
+    if code != 0:
+        _err(code, payload)
+    return payload
+
+
 class ProveConfig(ctypes.Structure):
     # plain Python object is fine; this is informational only
     pass
@@ -0,0 +1,120 @@
+import 'dart:async';
+import 'dart:convert';
+import 'dart:io';
+import 'dart:isolate';
+import 'dart:typed_data';
+
+import 'package:zkprov_flutter/zkprov_ffi.dart' as ffi;
+
+import 'errors.dart';
+import 'models.dart';
+
+/// Stage of a tracked [ZkdClient] operation.
+enum ZkdStage {
+  /// Accepted; the background isolate is being spawned.
+  queued,
+
+  /// The native call is running.
+  running,
+
+  /// Finished; [ZkdProgress.result] is set.
+  done,
+}
+
+/// One event of a [ZkdClient.proveWithProgress] or
+/// [ZkdClient.verifyWithProgress] stream.
+class ZkdProgress<T> {
+  const ZkdProgress(this.stage, this.elapsed, [this.result]);
+
+  final ZkdStage stage;
+
+  /// Time since the operation was requested.
+  final Duration elapsed;
+
+  /// The outcome, on the [ZkdStage.done] event only.
+  final T? result;
+}
+
+/// High-level client over the `zkp_*` C ABI.
+///
+/// Listing calls are cheap and run on the calling isolate. [prove] and
+/// [verify] run on a background isolate by default, so a Flutter UI keeps
+/// rendering while a proof is produced. Every failure is thrown as a
+/// [ZkdException] subclass chosen by its `ZKP_ERR_*` code.
+class ZkdClient {
+  const ZkdClient({this.background = true});
+
+  /// Run [prove] and [verify] on a background isolate.
+  final bool background;
+
+  Future<List<BackendInfo>> listBackends() => _guard(() async {
+        final json = await ffi.listBackends();
+        return _items(json).map(BackendInfo.fromJson).toList();
+      });
+
+  Future<List<Profile>> listProfiles() => _guard(() async {
+        final json = await ffi.listProfiles();
+        return _items(json).map(Profile.fromJson).toList();
+      });
+
+  /// Prove `cfg.airPath` over `cfg.publicInputsJson`.
+  Future<ffi.ZkProvProveResult> prove(ffi.ZkProvConfig cfg) =>
+      _run(() => ffi.prove(cfg));
+
+  /// Verify [proof]. A proof that does not verify completes with
+  /// `verified == false`; corrupt proofs and bad configs throw.
+  Future<ffi.ZkProvVerifyResult> verify(
+    ffi.ZkProvConfig cfg,
+    Uint8List proof,
+  ) =>
+      _run(() => ffi.verify(cfg, proof));
+
+  /// [prove], reporting each [ZkdStage]. Failures are stream errors.
+  Stream<ZkdProgress<ffi.ZkProvProveResult>> proveWithProgress(
+    ffi.ZkProvConfig cfg,
+  ) =>
+      _track(() => prove(cfg));
+
+  /// [verify], reporting each [ZkdStage]. Failures are stream errors.
+  Stream<ZkdProgress<ffi.ZkProvVerifyResult>> verifyWithProgress(
+    ffi.ZkProvConfig cfg,
+    Uint8List proof,
+  ) =>
+      _track(() => verify(cfg, proof));
+
+  /// Parse a report written by `zkd validate`.
+  Future<ValidationReport> readValidationReport(String path) async {
+    final json = jsonDecode(await File(path).readAsString());
+    return ValidationReport.fromJson((json as Map).cast<String, dynamic>());
+  }
+
+  Future<T> _run<T>(Future<T> Function() task) {
+    if (!background) {
+      return _guard(task);
+    }
+    // Map inside the isolate: the typed exception is what crosses back.
+    return Isolate.run(() => _guard(task));
+  }
+}
+
+Stream<ZkdProgress<T>> _track<T>(Future<T> Function() task) async* {
+  final watch = Stopwatch()..start();
+  yield ZkdProgress<T>(ZkdStage.queued, watch.elapsed);
+  final pending = task();
+  yield ZkdProgress<T>(ZkdStage.running, watch.elapsed);
+  final result = await pending;
+  yield ZkdProgress<T>(ZkdStage.done, watch.elapsed, result);
+}
+
+Future<T> _guard<T>(Future<T> Function() task) async {
+  try {
+    return await task();
+  } on ffi.ZkProvException catch (e) {
+    throw ZkdException.fromFfi(e);
+  }
+}
+
+/// `zkp_list_*` return JSON arrays, which the FFI layer wraps as `items`.
+Iterable<Map<String, dynamic>> _items(Map<String, dynamic> json) =>
+    (json['items'] as List? ?? const <dynamic>[])
+        .map((e) => (e as Map).cast<String, dynamic>());
@@ -0,0 +1,104 @@
+import 'package:zkprov_flutter/zkprov_ffi.dart';
+
+/// `ZKP_ERR_*` codes returned by the C ABI (`crates/ffi-types/src/error.rs`).
+enum ZkdErrorCode {
+  invalidArg(1),
+  backend(2),
+  profile(3),
+  proofCorrupt(4),
+  verifyFail(5),
+  internal(6);
+
+  const ZkdErrorCode(this.value);
+
+  final int value;
+
+  /// The code for [value]; unknown codes map to [internal].
+  static ZkdErrorCode fromValue(int value) => ZkdErrorCode.values.firstWhere(
+        (c) => c.value == value,
+        orElse: () => ZkdErrorCode.internal,
+      );
+}
+
+/// Base class of every error thrown by [ZkdClient].
+sealed class ZkdException implements Exception {
+  const ZkdException(this.operation, this.message, {this.detail});
+
+  /// Maps a low-level [ZkProvException] to its typed subclass.
+  factory ZkdException.fromFfi(ZkProvException e) {
+    final op = e.operation;
+    final msg = e.msg;
+    final detail = e.detail;
+    return switch (ZkdErrorCode.fromValue(e.code)) {
+      ZkdErrorCode.invalidArg => ZkdInvalidArgument(op, msg, detail: detail),
+      ZkdErrorCode.backend => ZkdBackendUnavailable(op, msg, detail: detail),
+      ZkdErrorCode.profile => ZkdProfileError(op, msg, detail: detail),
+      ZkdErrorCode.proofCorrupt => ZkdProofCorrupt(op, msg, detail: detail),
+      ZkdErrorCode.verifyFail => ZkdVerifyFailed(op, msg, detail: detail),
+      ZkdErrorCode.internal => ZkdInternalError(op, msg, detail: detail),
+    };
+  }
+
+  /// FFI function that failed, e.g. `zkp_prove`.
+  final String operation;
+  final String message;
+  final String? detail;
+
+  ZkdErrorCode get code;
+
+  @override
+  String toString() {
+    final suffix =
+        detail == null || detail!.isEmpty ? '' : ' [detail: $detail]';
+    return '$runtimeType($operation, ${code.name}): $message$suffix';
+  }
+}
+
+/// Malformed request: bad inputs JSON, null pointers, invalid options.
+final class ZkdInvalidArgument extends ZkdException {
+  const ZkdInvalidArgument(super.operation, super.message, {super.detail});
+
+  @override
+  ZkdErrorCode get code => ZkdErrorCode.invalidArg;
+}
+
+/// Unknown backend, or one lacking the requested field, hash or arity.
+final class ZkdBackendUnavailable extends ZkdException {
+  const ZkdBackendUnavailable(super.operation, super.message, {super.detail});
+
+  @override
+  ZkdErrorCode get code => ZkdErrorCode.backend;
+}
+
+/// Unknown, invalid or unsigned profile.
+final class ZkdProfileError extends ZkdException {
+  const ZkdProfileError(super.operation, super.message, {super.detail});
+
+  @override
+  ZkdErrorCode get code => ZkdErrorCode.profile;
+}
+
+/// Proof bytes that do not decode.
+final class ZkdProofCorrupt extends ZkdException {
+  const ZkdProofCorrupt(super.operation, super.message, {super.detail});
+
+  @override
+  ZkdErrorCode get code => ZkdErrorCode.proofCorrupt;
+}
+
+/// A well-formed proof that does not verify. [ZkdClient.verify] reports
+/// this as `verified == false` instead of throwing.
+final class ZkdVerifyFailed extends ZkdException {
+  const ZkdVerifyFailed(super.operation, super.message, {super.detail});
+
+  @override
+  ZkdErrorCode get code => ZkdErrorCode.verifyFail;
+}
+
+/// Anything else, including codes this package does not know.
+final class ZkdInternalError extends ZkdException {
+  const ZkdInternalError(super.operation, super.message, {super.detail});
+
+  @override
+  ZkdErrorCode get code => ZkdErrorCode.internal;
+}
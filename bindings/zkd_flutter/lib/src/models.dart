@@ -0,0 +1,171 @@
+// Typed views of the JSON documents returned by the C ABI. Parsers accept
+// unknown fields, since `zkp-ffi/1` may add payload fields.
+
+/// Scheduled removal of a deprecated backend.
+class BackendDeprecation {
+  const BackendDeprecation({required this.sunset, this.replacement});
+
+  factory BackendDeprecation.fromJson(Map<String, dynamic> json) =>
+      BackendDeprecation(
+        sunset: json['sunset'] as String,
+        replacement: json['replacement'] as String?,
+      );
+
+  /// Date (YYYY-MM-DD) after which the backend may be removed.
+  final String sunset;
+  final String? replacement;
+}
+
+/// One entry of `zkp_list_backends`.
+class BackendInfo {
+  const BackendInfo({
+    required this.id,
+    required this.recursion,
+    this.aliases = const <String>[],
+    this.deprecated,
+  });
+
+  factory BackendInfo.fromJson(Map<String, dynamic> json) {
+    final deprecated = json['deprecated'];
+    return BackendInfo(
+      id: json['id'] as String,
+      recursion: json['recursion'] as bool? ?? false,
+      aliases: _strings(json['aliases']),
+      deprecated: deprecated is Map
+          ? BackendDeprecation.fromJson(deprecated.cast<String, dynamic>())
+          : null,
+    );
+  }
+
+  final String id;
+  final bool recursion;
+  final List<String> aliases;
+  final BackendDeprecation? deprecated;
+}
+
+/// One entry of `zkp_list_profiles`.
+class Profile {
+  const Profile({
+    required this.id,
+    required this.lambdaBits,
+    this.friBlowup,
+    this.friQueries,
+    this.grindBits,
+    this.merkleArity,
+    this.constColLimit,
+    this.rowsMax,
+    this.owner,
+    this.signature,
+  });
+
+  factory Profile.fromJson(Map<String, dynamic> json) => Profile(
+        id: json['id'] as String,
+        lambdaBits: json['lambda_bits'] as int,
+        friBlowup: json['fri_blowup'] as int?,
+        friQueries: json['fri_queries'] as int?,
+        grindBits: json['grind_bits'] as int?,
+        merkleArity: json['merkle_arity'] as int?,
+        constColLimit: json['const_col_limit'] as int?,
+        rowsMax: json['rows_max'] as int?,
+        owner: json['owner'] as String?,
+        signature: json['signature'] as String?,
+      );
+
+  final String id;
+  final int lambdaBits;
+  final int? friBlowup;
+  final int? friQueries;
+  final int? grindBits;
+  final int? merkleArity;
+  final int? constColLimit;
+  final int? rowsMax;
+
+  /// Tenant namespace of an `<owner>/<name>` profile.
+  final String? owner;
+
+  /// Hex Ed25519 signature over the profile, if signed.
+  final String? signature;
+}
+
+/// An error or warning in a [ValidationReport].
+class ValidationIssue {
+  const ValidationIssue({
+    required this.code,
+    required this.message,
+    this.context,
+  });
+
+  factory ValidationIssue.fromJson(Map<String, dynamic> json) =>
+      ValidationIssue(
+        code: json['code'] as String,
+        message: json['msg'] as String,
+        context: json['context'],
+      );
+
+  /// E.g. `BlindingReuse` for errors, a free-form code for warnings.
+  final String code;
+  final String message;
+  final Object? context;
+}
+
+/// Report metadata: what was validated and how long it took.
+class ValidationMeta {
+  const ValidationMeta({
+    required this.backendId,
+    required this.profileId,
+    required this.hashId,
+    required this.timeMs,
+    this.curve,
+  });
+
+  factory ValidationMeta.fromJson(Map<String, dynamic> json) => ValidationMeta(
+        backendId: json['backend_id'] as String,
+        profileId: json['profile_id'] as String,
+        hashId: json['hash_id'] as String,
+        timeMs: json['time_ms'] as int,
+        curve: json['curve'] as String?,
+      );
+
+  final String backendId;
+  final String profileId;
+  final String hashId;
+  final int timeMs;
+  final String? curve;
+}
+
+/// `corelib::validation::ValidationReport`, as written by `zkd validate`.
+class ValidationReport {
+  const ValidationReport({
+    required this.ok,
+    required this.commitPassed,
+    required this.errors,
+    required this.warnings,
+    required this.meta,
+  });
+
+  factory ValidationReport.fromJson(Map<String, dynamic> json) =>
+      ValidationReport(
+        ok: json['ok'] as bool,
+        commitPassed: json['commit_passed'] as bool,
+        errors: _issues(json['errors']),
+        warnings: _issues(json['warnings']),
+        meta: ValidationMeta.fromJson(
+          (json['meta'] as Map).cast<String, dynamic>(),
+        ),
+      );
+
+  final bool ok;
+  final bool commitPassed;
+  final List<ValidationIssue> errors;
+  final List<ValidationIssue> warnings;
+  final ValidationMeta meta;
+}
+
+List<String> _strings(Object? value) =>
+    value is List ? value.cast<String>().toList() : const <String>[];
+
+List<ValidationIssue> _issues(Object? value) => value is List
+    ? value
+        .map((e) => ValidationIssue.fromJson((e as Map).cast<String, dynamic>()))
+        .toList()
+    : const <ValidationIssue>[];
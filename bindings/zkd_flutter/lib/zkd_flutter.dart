@@ -0,0 +1,10 @@
+/// High-level ZKProv API: [ZkdClient], typed models for the FFI JSON, and
+/// [ZkdException]s mapped from `ZKP_ERR_*` codes.
+library;
+
+export 'package:zkprov_flutter/zkprov_ffi.dart'
+    show ZkProvConfig, ZkProvProveResult, ZkProvVerifyResult;
+
+export 'src/client.dart';
+export 'src/errors.dart';
+export 'src/models.dart';
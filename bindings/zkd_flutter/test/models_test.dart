@@ -0,0 +1,60 @@
+import 'dart:convert';
+
+import 'package:flutter_test/flutter_test.dart';
+import 'package:zkd_flutter/zkd_flutter.dart';
+import 'package:zkprov_flutter/zkprov_ffi.dart' show ZkProvException;
+
+Map<String, dynamic> _json(String s) => jsonDecode(s) as Map<String, dynamic>;
+
+void main() {
+  test('parses backend and profile listings', () {
+    final backend = BackendInfo.fromJson(_json(
+      '{"id":"native@0.0","recursion":false,"aliases":["native"],'
+      '"deprecated":{"sunset":"2027-01-01","replacement":"native@0.1"},'
+      '"future_field":1}',
+    ));
+    expect(backend.id, 'native@0.0');
+    expect(backend.aliases, ['native']);
+    expect(backend.deprecated?.replacement, 'native@0.1');
+    final bare = BackendInfo.fromJson(_json('{"id":"x","recursion":true}'));
+    expect(bare.aliases, isEmpty);
+
+    final profile = Profile.fromJson(_json(
+      '{"id":"acme/fast","lambda_bits":80,"fri_queries":30,"owner":"acme"}',
+    ));
+    expect(profile.lambdaBits, 80);
+    expect(profile.friQueries, 30);
+    expect(profile.rowsMax, isNull);
+    expect(profile.owner, 'acme');
+  });
+
+  test('parses validation reports', () {
+    final report = ValidationReport.fromJson(_json('''
+{"ok":false,"commit_passed":false,
+ "errors":[{"code":"BlindingReuse","msg":"blind reused","context":{"index":2}}],
+ "warnings":[{"code":"slow","msg":"large trace","context":null}],
+ "meta":{"backend_id":"native@0.0","profile_id":"balanced","hash_id":"blake3",
+         "curve":null,"time_ms":12}}
+'''));
+    expect(report.ok, isFalse);
+    expect(report.errors.single.code, 'BlindingReuse');
+    expect(report.errors.single.context, {'index': 2});
+    expect(report.warnings.single.message, 'large trace');
+    expect(report.meta.curve, isNull);
+    expect(report.meta.timeMs, 12);
+  });
+
+  test('maps ZKP_ERR codes to typed exceptions', () {
+    ZkdException map(int code) => ZkdException.fromFfi(
+          ZkProvException(operation: 'zkp_prove', code: code, msg: 'm'),
+        );
+    expect(map(1), isA<ZkdInvalidArgument>());
+    expect(map(2), isA<ZkdBackendUnavailable>());
+    expect(map(3), isA<ZkdProfileError>());
+    expect(map(4), isA<ZkdProofCorrupt>());
+    expect(map(5), isA<ZkdVerifyFailed>());
+    expect(map(6), isA<ZkdInternalError>());
+    expect(map(99).code, ZkdErrorCode.internal);
+    expect(map(2).operation, 'zkp_prove');
+  });
+}
@@ -0,0 +1,478 @@
+//! Conformance checks for backend adapters, meant to run in the adapter's
+//! own test suite:
+//!
+//! ```ignore
+//! use zkd_backend_testkit::{Case, Kit};
+//!
+//! #[test]
+//! fn my_backend_conforms() {
+//!     my_backend::register();
+//!     let config = Config::new("mine@0.1", "Goldilocks", "blake3", 2, false, "balanced");
+//!     let case = Case::load("fib", "air/fib.air".as_ref(), "air/fib.inputs.json".as_ref())
+//!         .unwrap();
+//!     Kit::new(config).run(&[case]).unwrap();
+//! }
+//! ```
+//!
+//! [`Kit::run`] runs, per backend and then per case:
+//!
+//! - [`check_capabilities`]: the registered backend reports ids and
+//!   capabilities corelib understands (known fields and hashes, FRI arities
+//!   that are powers of two, known nondeterminism sources, …).
+//! - [`Kit::round_trip`]: prove, verify, and prove again to the same bytes;
+//!   the header binds the backend and config.
+//! - [`Kit::digest_parity`]: the header fields EVM verifiers read
+//!   ([`DigestManifest`]) are bound the same way `native@0.0` binds them.
+//! - [`Kit::rejections`]: every entry of [`corruptions`] (the matrix of
+//!   `examples/conformance/suite.json`, plus the profile and config hashes)
+//!   fails with the error class the reference backend fails with.
+//!
+//! Each returns an error naming the case and the check that failed.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, ensure, Context, Result};
+
+use zkprov_backend_native::ensure_native_registered;
+use zkprov_corelib as core;
+use zkprov_corelib::air::{parse_air_file, AirIr};
+use zkprov_corelib::backend::{Capabilities, PublicInputs, NONDETERMINISM_SOURCES};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::crypto::field::field_by_id;
+use zkprov_corelib::crypto::registry::canonical_hash_id;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::proof::{hash64, split_proof, ProofHeader, ProofView};
+use zkprov_corelib::registry;
+use zkprov_corelib::trace::Trace;
+use zkprov_ffi_types::ErrorCode;
+
+/// Backend the digest parity check compares against.
+pub const REFERENCE_BACKEND: &str = "native@0.0";
+
+/// `recursion` values a backend may report.
+const RECURSION: &[&str] = &["none", "stark-in-stark", "snark-wrapper"];
+
+/// An AIR with public inputs and, for backends that prove from a witness,
+/// a trace.
+#[derive(Debug, Clone)]
+pub struct Case {
+    pub name: String,
+    pub ir: AirIr,
+    /// Public inputs JSON text.
+    pub inputs: String,
+    pub trace: Option<Trace>,
+}
+
+impl Case {
+    pub fn new(name: impl Into<String>, ir: AirIr, inputs: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ir,
+            inputs: inputs.into(),
+            trace: None,
+        }
+    }
+
+    pub fn with_trace(mut self, trace: Trace) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// A case from an AIR file and an inputs JSON file.
+    pub fn load(name: &str, air: &Path, inputs: &Path) -> Result<Self> {
+        let ir = parse_air_file(air).with_context(|| format!("case '{}'", name))?;
+        let inputs = std::fs::read_to_string(inputs)
+            .with_context(|| format!("case '{}': reading {}", name, inputs.display()))?;
+        Ok(Self::new(name, ir, inputs))
+    }
+
+    /// [`load`](Self::load) plus a trace JSON file (`zkd trace-dump` format).
+    pub fn load_with_trace(name: &str, air: &Path, inputs: &Path, trace: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(trace)
+            .with_context(|| format!("case '{}': reading {}", name, trace.display()))?;
+        let trace = Trace::from_json(&serde_json::from_str(&text)?)
+            .with_context(|| format!("case '{}': {}", name, trace.display()))?;
+        Ok(Self::load(name, air, inputs)?.with_trace(trace))
+    }
+}
+
+/// The Prime254 example AIRs pinned in `examples/conformance/suite.json`.
+/// Only available where the zkd source tree is, i.e. in this workspace.
+pub fn example_cases() -> Result<Vec<Case>> {
+    let air = |name: &str| examples_dir().join("air").join(format!("{}.air", name));
+    [
+        ("toy", r#"{"demo":true,"n":7}"#),
+        ("selector_counter", "{}"),
+        ("range_check", r#"{"amount":1234567890}"#),
+    ]
+    .into_iter()
+    .map(|(name, inputs)| {
+        let ir = parse_air_file(&air(name)).with_context(|| format!("case '{}'", name))?;
+        Ok(Case::new(name, ir, inputs))
+    })
+    .collect()
+}
+
+fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples")
+}
+
+/// A proof header as an EVM verifier sees it: the bound hashes and the
+/// digest `D` over header and body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestManifest {
+    pub backend_id_hash: u64,
+    pub profile_id_hash: u64,
+    pub pubio_hash: u64,
+    pub config_hash: u64,
+    pub body_len: u64,
+    pub digest: [u8; 32],
+}
+
+impl DigestManifest {
+    pub fn from_proof(bytes: &[u8]) -> Result<Self> {
+        let (header, body) = split_proof(bytes)?;
+        Ok(Self::new(&header, body))
+    }
+
+    pub fn new(header: &ProofHeader, body: &[u8]) -> Self {
+        Self {
+            backend_id_hash: header.backend_id_hash,
+            profile_id_hash: header.profile_id_hash,
+            pubio_hash: header.pubio_hash,
+            config_hash: header.config_hash,
+            body_len: header.body_len,
+            digest: digest_D(header, body),
+        }
+    }
+}
+
+/// One entry of the corruption matrix.
+#[derive(Debug, Clone, Copy)]
+pub struct Corruption {
+    pub name: &'static str,
+    pub mutation: Mutation,
+    /// Error class verification must fail with; [`ErrorCode::Ok`] when the
+    /// change must be ignored.
+    pub expect: ErrorCode,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Mutation {
+    /// XOR `0x01` into the byte at this offset; negative counts from the end.
+    Flip(isize),
+    /// Keep only this many bytes.
+    Truncate(usize),
+}
+
+impl Corruption {
+    pub fn apply(&self, proof: &[u8]) -> Vec<u8> {
+        let mut out = proof.to_vec();
+        match self.mutation {
+            Mutation::Flip(offset) => {
+                let i = if offset < 0 {
+                    proof.len() - offset.unsigned_abs()
+                } else {
+                    offset as usize
+                };
+                out[i] ^= 1;
+            }
+            Mutation::Truncate(len) => out.truncate(len),
+        }
+        out
+    }
+}
+
+/// The header and body corruptions of `examples/conformance/suite.json`,
+/// plus flips of `profile_id_hash` and `config_hash`.
+pub fn corruptions() -> Vec<Corruption> {
+    use ErrorCode::{Ok, ProofCorrupt, VerifyFail};
+    use Mutation::{Flip, Truncate};
+    let c = |name, mutation, expect| Corruption {
+        name,
+        mutation,
+        expect,
+    };
+    vec![
+        c("magic", Flip(0), ProofCorrupt),
+        c("version", Flip(4), ProofCorrupt),
+        c("header_len", Flip(8), ProofCorrupt),
+        c("byte_order", Flip(10), ProofCorrupt),
+        c("critical_flag", Flip(12), ProofCorrupt),
+        c("noncritical_flag", Flip(14), ProofCorrupt),
+        c("backend_id", Flip(16), VerifyFail),
+        c("profile_id", Flip(24), VerifyFail),
+        c("pubio_hash", Flip(32), VerifyFail),
+        c("body_len", Flip(40), ProofCorrupt),
+        c("config_hash", Flip(48), VerifyFail),
+        c("reserved", Flip(56), Ok),
+        c("header_truncated", Truncate(60), ProofCorrupt),
+        c("body_last_byte", Flip(-1), VerifyFail),
+    ]
+}
+
+/// Check that `backend_id` is registered and reports capabilities corelib
+/// can act on. Returns them.
+pub fn check_capabilities(backend_id: &str) -> Result<Capabilities> {
+    registry::ensure_builtins_registered();
+    let id = registry::resolve_backend_id(backend_id)?;
+    let backend = registry::get_backend(id)?;
+    ensure!(
+        backend.prover.id() == id,
+        "prover id '{}' differs from the registered id '{}'",
+        backend.prover.id(),
+        id
+    );
+    let caps = backend.prover.capabilities();
+    ensure!(
+        caps == registry::get_backend_capabilities(id)?,
+        "capabilities change between calls"
+    );
+    ensure!(!caps.fields.is_empty(), "no fields");
+    for field in &caps.fields {
+        ensure!(field_by_id(field).is_some(), "unknown field '{}'", field);
+    }
+    ensure!(!caps.hashes.is_empty(), "no hashes");
+    for hash in &caps.hashes {
+        ensure!(
+            canonical_hash_id(hash) == Some(*hash),
+            "hash '{}' is not a canonical hash id",
+            hash
+        );
+    }
+    ensure!(!caps.fri_arities.is_empty(), "no FRI arities");
+    for arity in &caps.fri_arities {
+        ensure!(
+            *arity >= 2 && arity.is_power_of_two(),
+            "FRI arity {} is not a power of two above 1",
+            arity
+        );
+    }
+    ensure!(
+        RECURSION.contains(&caps.recursion),
+        "unknown recursion '{}' (expected one of {})",
+        caps.recursion,
+        RECURSION.join(", ")
+    );
+    ensure!(
+        !caps.pedersen || !caps.curves.is_empty(),
+        "pedersen is supported but no curves are listed"
+    );
+    ensure!(caps.max_rows != Some(0), "max_rows is 0");
+    for source in &caps.nondeterminism {
+        ensure!(
+            NONDETERMINISM_SOURCES.contains(source),
+            "unknown nondeterminism source '{}' (expected one of {})",
+            source,
+            NONDETERMINISM_SOURCES.join(", ")
+        );
+    }
+    Ok(caps)
+}
+
+/// The per-case checks for one backend configuration.
+#[derive(Debug, Clone)]
+pub struct Kit {
+    config: Config,
+}
+
+impl Kit {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// [`check_capabilities`], then every check on every case.
+    pub fn run(&self, cases: &[Case]) -> Result<()> {
+        check_capabilities(&self.config.backend_id)
+            .with_context(|| format!("capabilities of '{}'", self.config.backend_id))?;
+        for case in cases {
+            let proof = self.round_trip(case)?;
+            self.digest_parity(case, &proof)?;
+            self.rejections(case, &proof)?;
+        }
+        Ok(())
+    }
+
+    /// Prove `case` with the configured backend.
+    pub fn prove(&self, case: &Case) -> Result<Vec<u8>> {
+        prove_case(&self.config, case)
+    }
+
+    /// Verify `proof` of `case`, mapping the outcome to its error class.
+    pub fn verify(&self, case: &Case, proof: &[u8]) -> ErrorCode {
+        let result = PublicInputs::from_json(&case.inputs)
+            .map_err(|e| core::errors::VerifyError::Backend(e.into()))
+            .and_then(|inputs| {
+                let view = ProofView::from_bytes(proof)?;
+                core::verify(&self.config, &case.ir, &inputs, &view)
+            });
+        match result {
+            Ok(()) => ErrorCode::Ok,
+            Err(e) => ErrorCode::from(&e),
+        }
+    }
+
+    /// Prove, verify and prove again: the proof verifies, proving is
+    /// deterministic, and the header binds the backend and config.
+    /// Returns the proof.
+    pub fn round_trip(&self, case: &Case) -> Result<Vec<u8>> {
+        let what = |check: &str| format!("case '{}': {}", case.name, check);
+        let proof = self.prove(case).with_context(|| what("prove"))?;
+        let inputs = PublicInputs::from_json(&case.inputs)?;
+        let view = ProofView::from_bytes(&proof).with_context(|| what("decode"))?;
+        core::verify(&self.config, &case.ir, &inputs, &view).with_context(|| what("verify"))?;
+        ensure!(
+            self.prove(case).with_context(|| what("prove again"))? == proof,
+            "{}",
+            what("proving twice gave different proofs")
+        );
+
+        let header = view.header;
+        let id = registry::resolve_backend_id(&self.config.backend_id)?;
+        let expected = [
+            (
+                "backend_id_hash",
+                header.backend_id_hash,
+                hash64("BACKEND", id.as_bytes()),
+            ),
+            ("config_hash", header.config_hash, self.config.config_hash()),
+        ];
+        for (field, actual, expected) in expected {
+            ensure!(
+                actual == expected,
+                "{}",
+                what(&format!(
+                    "header {} is 0x{:016x}, expected 0x{:016x}",
+                    field, actual, expected
+                ))
+            );
+        }
+        Ok(proof)
+    }
+
+    /// Compare `proof`'s [`DigestManifest`] with a [`REFERENCE_BACKEND`]
+    /// proof of the same case (in the reference backend's own field): the
+    /// profile and public inputs must be bound identically, while the
+    /// backend id, and so `D`, must differ.
+    pub fn digest_parity(&self, case: &Case, proof: &[u8]) -> Result<DigestManifest> {
+        let what = |check: &str| format!("case '{}': {}", case.name, check);
+        let ours = DigestManifest::from_proof(proof).with_context(|| what("digest manifest"))?;
+        let (header, body) = split_proof(proof)?;
+        ensure!(
+            ours.body_len == body.len() as u64,
+            "{}",
+            what("header body_len does not match the body")
+        );
+        ensure!(
+            ours.digest == digest_D(&header, body),
+            "{}",
+            what("digest is not reproducible")
+        );
+
+        ensure_native_registered();
+        let reference = reference_config(&self.config)?;
+        let mut ir = case.ir.clone();
+        ir.meta.field = reference.field.clone();
+        let reference_case = Case {
+            ir,
+            trace: None,
+            ..case.clone()
+        };
+        let theirs = prove_case(&reference, &reference_case)
+            .and_then(|p| DigestManifest::from_proof(&p))
+            .with_context(|| what(&format!("proving with {}", REFERENCE_BACKEND)))?;
+        ensure!(
+            ours.pubio_hash == theirs.pubio_hash,
+            "{}",
+            what(&format!(
+                "pubio_hash 0x{:016x} differs from {}'s 0x{:016x}",
+                ours.pubio_hash, REFERENCE_BACKEND, theirs.pubio_hash
+            ))
+        );
+        ensure!(
+            ours.profile_id_hash == theirs.profile_id_hash,
+            "{}",
+            what(&format!(
+                "profile_id_hash differs from {}'s",
+                REFERENCE_BACKEND
+            ))
+        );
+        if registry::resolve_backend_id(&self.config.backend_id)? != REFERENCE_BACKEND {
+            ensure!(
+                ours.backend_id_hash != theirs.backend_id_hash && ours.digest != theirs.digest,
+                "{}",
+                what(&format!(
+                    "digest does not bind the backend: same as {}'s",
+                    REFERENCE_BACKEND
+                ))
+            );
+        }
+        Ok(ours)
+    }
+
+    /// Verify every [`corruptions`] entry applied to `proof` and check the
+    /// error class; reports every mismatch at once.
+    pub fn rejections(&self, case: &Case, proof: &[u8]) -> Result<()> {
+        let failures: Vec<String> = corruptions()
+            .iter()
+            .filter_map(|c| {
+                let got = self.verify(case, &c.apply(proof));
+                (got != c.expect).then(|| {
+                    format!(
+                        "{}: expected {}, got {}",
+                        c.name,
+                        c.expect.as_str(),
+                        got.as_str()
+                    )
+                })
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "case '{}': corrupted proofs: {}",
+                case.name,
+                failures.join("; ")
+            ))
+        }
+    }
+}
+
+fn prove_case(config: &Config, case: &Case) -> Result<Vec<u8>> {
+    let inputs = PublicInputs::from_json(&case.inputs)?;
+    let proof = match &case.trace {
+        Some(trace) => core::prove_with_trace(config, &case.ir, &inputs, trace)?,
+        None => core::prove(config, &case.ir, &inputs)?,
+    };
+    Ok(proof.to_bytes())
+}
+
+/// `config` on [`REFERENCE_BACKEND`], in its first field and with the hash
+/// and FRI arity kept when it supports them.
+fn reference_config(config: &Config) -> Result<Config> {
+    let caps = registry::get_backend_capabilities(REFERENCE_BACKEND)?;
+    let hash = canonical_hash_id(&config.hash)
+        .filter(|h| caps.hashes.contains(h))
+        .unwrap_or(caps.hashes[0]);
+    let fri_arity = if caps.fri_arities.contains(&config.fri_arity) {
+        config.fri_arity
+    } else {
+        caps.fri_arities[0]
+    };
+    let mut reference = Config::new(
+        REFERENCE_BACKEND,
+        caps.fields[0],
+        hash,
+        fri_arity,
+        false,
+        &config.profile_id,
+    )
+    .with_profile_overrides(config.profile_overrides.clone());
+    reference.pubio_encoding = config.pubio_encoding;
+    Ok(reference)
+}
@@ -0,0 +1,28 @@
+use std::path::Path;
+
+use zkd_backend_testkit::{Case, Kit};
+use zkprov_backend_ministark::{ensure_ministark_registered, BACKEND_ID};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::profile::parse_overrides;
+
+fn case(name: &str) -> Case {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../backends/ministark/air");
+    let file = |ext: &str| dir.join(format!("{name}.{ext}"));
+    Case::load_with_trace(
+        name,
+        &file("air"),
+        &file("inputs.json"),
+        &file("trace.json"),
+    )
+    .unwrap()
+}
+
+#[test]
+fn ministark_passes_the_kit() {
+    ensure_ministark_registered();
+    let config = Config::new(BACKEND_ID, "Goldilocks", "blake3", 2, false, "dev-fast")
+        .with_profile_overrides(parse_overrides(&["fri_queries=26", "grind_bits=4"]).unwrap());
+    Kit::new(config)
+        .run(&[case("fib"), case("merkle_path")])
+        .unwrap();
+}
@@ -0,0 +1,60 @@
+use zkd_backend_testkit::{check_capabilities, corruptions, example_cases, Kit};
+use zkprov_backend_native::ensure_native_registered;
+use zkprov_corelib::config::Config;
+use zkprov_ffi_types::ErrorCode;
+
+fn kit() -> Kit {
+    ensure_native_registered();
+    Kit::new(Config::new(
+        "native@0.0",
+        "Prime254",
+        "blake3",
+        2,
+        false,
+        "balanced",
+    ))
+}
+
+#[test]
+fn native_passes_the_kit() {
+    kit().run(&example_cases().unwrap()).unwrap();
+}
+
+#[test]
+fn native_capabilities_are_sane() {
+    ensure_native_registered();
+    let caps = check_capabilities("native@latest").unwrap();
+    assert!(caps.fields.contains(&"Prime254"));
+    assert!(check_capabilities("nope@0.0").is_err());
+}
+
+#[test]
+fn rejection_matrix_reports_every_mismatch() {
+    let kit = kit();
+    let case = &example_cases().unwrap()[0];
+    let proof = kit.prove(case).unwrap();
+    kit.rejections(case, &proof).unwrap();
+
+    // Proofs of other inputs verify as corrupted proofs of this case would not.
+    let mut other = case.clone();
+    other.inputs = r#"{"demo":true,"n":8}"#.into();
+    assert_eq!(kit.verify(&other, &proof), ErrorCode::VerifyFail);
+    let err = kit.rejections(&other, &proof).unwrap_err().to_string();
+    for c in corruptions() {
+        if c.expect == ErrorCode::Ok {
+            assert!(
+                err.contains(&format!("{}: expected ok, got verify_fail", c.name)),
+                "{err}"
+            );
+        }
+    }
+}
+
+#[test]
+fn digest_parity_with_itself_keeps_the_digest() {
+    let kit = kit();
+    let case = &example_cases().unwrap()[1];
+    let proof = kit.round_trip(case).unwrap();
+    let manifest = kit.digest_parity(case, &proof).unwrap();
+    assert_eq!(manifest.body_len as usize, proof.len() - 64);
+}
@@ -0,0 +1,151 @@
+//! The AIR as the STARK sees it: lowered transition constraints evaluated
+//! over Goldilocks, and boundary assertions resolved against the public
+//! inputs.
+
+use anyhow::{bail, Result};
+use num_bigint::BigUint;
+
+use zkprov_corelib::air::expr::Expr;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::PublicInputs;
+
+use crate::field::{Felt, P};
+use crate::transcript::Transcript;
+
+/// One boundary assertion `trace[row][column] = value`.
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub column: usize,
+    pub row: u32,
+    pub value: Felt,
+}
+
+/// Constraints of an AIR instantiated for a trace of `rows` rows.
+pub struct Constraints {
+    pub width: usize,
+    pub rows: u32,
+    pub transitions: Vec<Expr>,
+    pub assertions: Vec<Assertion>,
+    /// Rendered constraints, absorbed into the transcript.
+    rendered: Vec<String>,
+}
+
+impl Constraints {
+    pub fn new(ir: &AirIr, inputs: &PublicInputs, rows: u32) -> Result<Self> {
+        if ir.meta.field != "Goldilocks" {
+            bail!(
+                "ministark proves Goldilocks AIRs, '{}' declares '{}'",
+                ir.meta.name,
+                ir.meta.field
+            );
+        }
+        let transitions = ir.transition_constraints()?;
+        let values = inputs.value();
+        let p = BigUint::from(P);
+        let mut rendered: Vec<String> = transitions.iter().map(|c| c.to_string()).collect();
+        let mut assertions = Vec::new();
+        for a in ir.boundary_assertions(rows)? {
+            if a.row >= rows {
+                bail!("boundary {} targets row {} of {}", a, a.row, rows);
+            }
+            assertions.push(Assertion {
+                column: a.column,
+                row: a.row,
+                value: Felt::from_biguint(&a.resolve(&values, &p)?),
+            });
+            rendered.push(a.to_string());
+        }
+        Ok(Self {
+            width: ir.columns.trace_cols as usize,
+            rows,
+            transitions: transitions.into_iter().map(|c| c.expr).collect(),
+            assertions,
+            rendered,
+        })
+    }
+
+    /// Highest transition constraint degree (0 without transitions).
+    pub fn max_degree(&self) -> u32 {
+        self.transitions.iter().map(Expr::degree).max().unwrap_or(0)
+    }
+
+    /// How many times `rows` the composition degree bound is: transition
+    /// quotients have degree below `(d - 1) * rows`, rounded up to a power
+    /// of two for FRI.
+    pub fn quotient_factor(&self) -> usize {
+        (self.max_degree().saturating_sub(1).max(1) as usize).next_power_of_two()
+    }
+
+    /// Absorb the statement: AIR, rendered constraints and public inputs.
+    pub fn absorb(&self, ir: &AirIr, inputs: &PublicInputs, transcript: &mut Transcript) {
+        transcript.absorb("AIR.NAME", ir.meta.name.as_bytes());
+        transcript.absorb("AIR.COLS", &(self.width as u64).to_le_bytes());
+        transcript.absorb("AIR.CONSTRAINTS", self.rendered.join("\n").as_bytes());
+        transcript.absorb("PUBIO", inputs.binding());
+    }
+}
+
+/// Evaluate `expr` on a row pair.
+pub fn eval(expr: &Expr, cur: &[Felt], next: &[Felt]) -> Felt {
+    match expr {
+        Expr::Const(c) => Felt::new(*c),
+        Expr::Cur(i) => cur[*i],
+        Expr::Next(i) => next[*i],
+        Expr::Add(a, b) => eval(a, cur, next) + eval(b, cur, next),
+        Expr::Sub(a, b) => eval(a, cur, next) - eval(b, cur, next),
+        Expr::Mul(a, b) => eval(a, cur, next) * eval(b, cur, next),
+        Expr::Neg(a) => -eval(a, cur, next),
+    }
+}
+
+/// Random coefficients for the composition polynomial.
+pub struct Coefficients {
+    pub transitions: Vec<Felt>,
+    pub assertions: Vec<Felt>,
+}
+
+impl Coefficients {
+    pub fn draw(constraints: &Constraints, transcript: &mut Transcript) -> Self {
+        Self {
+            transitions: (0..constraints.transitions.len())
+                .map(|_| transcript.draw_felt())
+                .collect(),
+            assertions: (0..constraints.assertions.len())
+                .map(|_| transcript.draw_felt())
+                .collect(),
+        }
+    }
+}
+
+/// Composition value at `x` from the trace rows at `x` and `ω·x`:
+///
+/// ```text
+/// C(x) = Σ α_j · T_j(cur, next) · (x - ω^(n-1)) / (x^n - 1)
+///      + Σ β_k · (cur[c_k] - v_k) / (x - ω^(r_k))
+/// ```
+///
+/// `x` must lie outside the trace domain.
+pub fn composition_at(
+    constraints: &Constraints,
+    coeffs: &Coefficients,
+    x: Felt,
+    cur: &[Felt],
+    next: &[Felt],
+) -> Felt {
+    let n = constraints.rows as u64;
+    let omega = Felt::root_of_unity(n as usize);
+    let mut acc = Felt::ZERO;
+    if !constraints.transitions.is_empty() {
+        let mut sum = Felt::ZERO;
+        for (expr, alpha) in constraints.transitions.iter().zip(&coeffs.transitions) {
+            sum = sum + *alpha * eval(expr, cur, next);
+        }
+        let last = omega.pow(n - 1);
+        acc = sum * (x - last) * (x.pow(n) - Felt::ONE).inverse();
+    }
+    for (a, beta) in constraints.assertions.iter().zip(&coeffs.assertions) {
+        let root = omega.pow(a.row as u64);
+        acc = acc + *beta * (cur[a.column] - a.value) * (x - root).inverse();
+    }
+    acc
+}
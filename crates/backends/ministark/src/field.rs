@@ -0,0 +1,156 @@
+//! Goldilocks field `p = 2^64 - 2^32 + 1`.
+//!
+//! Elements are kept canonical (`< p`). Multiplication reduces through
+//! `u128`; nothing here is constant-time, which is fine for a prover whose
+//! witness is not secret and a verifier that only handles public data.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+/// The Goldilocks prime.
+pub const P: u64 = zkprov_corelib::crypto::field::GOLDILOCKS_MODULUS;
+
+/// Generator of the multiplicative group; also the coset shift of the LDE
+/// domain.
+pub const GENERATOR: Felt = Felt(7);
+
+/// `p - 1 = 2^32 * (2^32 - 1)`: subgroups of order up to `2^32`.
+pub const TWO_ADICITY: u32 = 32;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Felt(u64);
+
+impl Felt {
+    pub const ZERO: Felt = Felt(0);
+    pub const ONE: Felt = Felt(1);
+
+    /// Reduce `v` modulo `p`.
+    pub const fn new(v: u64) -> Self {
+        Felt(if v >= P { v - P } else { v })
+    }
+
+    /// `v mod p` for values of any size, e.g. trace cells.
+    pub fn from_biguint(v: &BigUint) -> Self {
+        let r = v % P;
+        Felt(r.iter_u64_digits().next().unwrap_or(0))
+    }
+
+    /// Canonical representative, which is also the wire encoding.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// `None` unless `v` is canonical, so encodings are unique.
+    pub fn from_canonical(v: u64) -> Option<Self> {
+        (v < P).then_some(Felt(v))
+    }
+
+    pub fn pow(self, mut e: u64) -> Self {
+        let mut base = self;
+        let mut acc = Felt::ONE;
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            e >>= 1;
+        }
+        acc
+    }
+
+    /// Multiplicative inverse; `0` maps to `0`.
+    pub fn inverse(self) -> Self {
+        self.pow(P - 2)
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Primitive `n`-th root of unity; `n` must be a power of two no larger
+    /// than `2^TWO_ADICITY`.
+    pub fn root_of_unity(n: usize) -> Self {
+        assert!(n.is_power_of_two(), "domain size {n} is not a power of two");
+        let log_n = n.trailing_zeros();
+        assert!(log_n <= TWO_ADICITY, "domain size 2^{log_n} exceeds 2^32");
+        GENERATOR
+            .pow((P - 1) >> TWO_ADICITY)
+            .pow(1u64 << (TWO_ADICITY - log_n))
+    }
+
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+}
+
+impl Add for Felt {
+    type Output = Felt;
+    fn add(self, rhs: Felt) -> Felt {
+        let (sum, carry) = self.0.overflowing_add(rhs.0);
+        // 2^64 = 2^32 - 1 (mod p)
+        let (sum, carry2) = if carry {
+            sum.overflowing_add(0xFFFF_FFFF)
+        } else {
+            (sum, false)
+        };
+        debug_assert!(!carry2);
+        Felt::new(sum)
+    }
+}
+
+impl Sub for Felt {
+    type Output = Felt;
+    fn sub(self, rhs: Felt) -> Felt {
+        self + (-rhs)
+    }
+}
+
+impl Neg for Felt {
+    type Output = Felt;
+    fn neg(self) -> Felt {
+        if self.0 == 0 {
+            self
+        } else {
+            Felt(P - self.0)
+        }
+    }
+}
+
+impl Mul for Felt {
+    type Output = Felt;
+    fn mul(self, rhs: Felt) -> Felt {
+        Felt(((self.0 as u128 * rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl fmt::Debug for Felt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Felt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Invert every element with one field inversion (Montgomery's trick).
+/// All inputs must be non-zero.
+pub fn batch_inverse(values: &[Felt]) -> Vec<Felt> {
+    let mut prefix = Vec::with_capacity(values.len());
+    let mut acc = Felt::ONE;
+    for &v in values {
+        prefix.push(acc);
+        acc = acc * v;
+    }
+    let mut inv = acc.inverse();
+    let mut out = vec![Felt::ZERO; values.len()];
+    for i in (0..values.len()).rev() {
+        out[i] = prefix[i] * inv;
+        inv = inv * values[i];
+    }
+    out
+}
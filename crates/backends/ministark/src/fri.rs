@@ -0,0 +1,101 @@
+//! FRI with folding factor 2.
+//!
+//! Layer `l` holds evaluations of `f_l` on the coset `s_l·⟨ω_l⟩` of size
+//! `N_l`. Its Merkle leaves are the pairs `(f_l(x_i), f_l(-x_i))`, i.e.
+//! positions `i` and `i + N_l/2`, so one opening serves both. After the
+//! layer root is absorbed, the verifier's challenge `β` folds it into
+//!
+//! ```text
+//! f_{l+1}(x^2) = (f_l(x) + f_l(-x)) / 2 + β · (f_l(x) - f_l(-x)) / (2x)
+//! ```
+//!
+//! on `s_l^2·⟨ω_l^2⟩`, halving the degree bound. Folding stops at degree
+//! bound 1, where an honest layer is constant; that constant (the
+//! remainder) is sent in the clear.
+
+use crate::field::{batch_inverse, Felt};
+use crate::merkle::{Digest, MerkleTree};
+use crate::proof_body::FriOpening;
+use crate::transcript::Transcript;
+
+/// One committed layer.
+pub struct FriLayer {
+    values: Vec<Felt>,
+    tree: MerkleTree,
+}
+
+/// Number of committed layers for a degree bound (a power of two).
+pub fn num_layers(degree_bound: usize) -> usize {
+    degree_bound.trailing_zeros() as usize
+}
+
+/// `f(x)` from the opened pair at `x` (the first slot's point) and `β`.
+pub fn fold(pair: [Felt; 2], beta: Felt, x: Felt) -> Felt {
+    let half = Felt::new(2).inverse();
+    let [a, b] = pair;
+    (a + b) * half + beta * (a - b) * half * x.inverse()
+}
+
+/// Commit to `values` (evaluations on `shift·⟨ω⟩`) of degree below
+/// `degree_bound`, absorbing each layer root and drawing its `β`.
+pub fn commit(
+    mut values: Vec<Felt>,
+    mut shift: Felt,
+    degree_bound: usize,
+    transcript: &mut Transcript,
+) -> (Vec<FriLayer>, Felt) {
+    let mut layers = Vec::new();
+    for _ in 0..num_layers(degree_bound) {
+        let half = values.len() / 2;
+        let leaves: Vec<Vec<Felt>> = (0..half)
+            .map(|i| vec![values[i], values[i + half]])
+            .collect();
+        let tree = MerkleTree::new(&leaves);
+        transcript.absorb("FRI.ROOT", &tree.root());
+        let beta = transcript.draw_felt();
+
+        let omega = Felt::root_of_unity(values.len());
+        let mut x = shift;
+        let xs: Vec<Felt> = (0..half)
+            .map(|_| {
+                let cur = x;
+                x = x * omega;
+                cur
+            })
+            .collect();
+        let two_inv = Felt::new(2).inverse();
+        let folded = batch_inverse(&xs)
+            .into_iter()
+            .enumerate()
+            .map(|(i, x_inv)| {
+                let (a, b) = (values[i], values[i + half]);
+                (a + b) * two_inv + beta * (a - b) * two_inv * x_inv
+            })
+            .collect();
+        layers.push(FriLayer { values, tree });
+        values = folded;
+        shift = shift * shift;
+    }
+    (layers, values[0])
+}
+
+impl FriLayer {
+    pub fn root(&self) -> Digest {
+        self.tree.root()
+    }
+
+    /// Size of the layer's evaluation domain.
+    pub fn domain_size(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Opening of the pair containing position `pos`.
+    pub fn open(&self, pos: usize) -> FriOpening {
+        let half = self.values.len() / 2;
+        let i = pos % half;
+        FriOpening {
+            pair: [self.values[i], self.values[i + half]],
+            path: self.tree.open(i),
+        }
+    }
+}
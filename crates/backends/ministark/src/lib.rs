@@ -0,0 +1,173 @@
+//! `ministark@0.1`: a small, self-contained STARK over Goldilocks.
+//!
+//! Unlike the native stub this backend produces real proofs: the trace is
+//! low-degree extended and Merkle-committed, constraints are folded into a
+//! composition polynomial, and FRI (arity 2) tests it for low degree, with
+//! BLAKE3 for commitments and Fiat–Shamir. It is written for reading rather
+//! than speed (no zero-knowledge blinding, single-threaded, `u128`
+//! reductions) and serves as a reference to diff production backends
+//! against. See `docs/ministark.md` for the protocol.
+//!
+//! Proving needs a trace (`prove_with_trace`); AIRs must declare
+//! `field = "Goldilocks"`.
+
+use std::sync::Once;
+
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::errors::{ProveError, RegistryError, VerifyError};
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{self, Proof, ProofView};
+use zkprov_corelib::registry::register_backend;
+use zkprov_corelib::trace::{eval, Trace};
+
+pub mod constraints;
+pub mod field;
+pub mod fri;
+pub mod merkle;
+pub mod poly;
+pub mod proof_body;
+pub mod stark;
+pub mod transcript;
+
+pub use proof_body::StarkProof;
+
+pub const BACKEND_ID: &str = "ministark@0.1";
+
+#[derive(Debug, Default)]
+pub struct MiniStarkBackend;
+
+impl ProverBackend for MiniStarkBackend {
+    fn id(&self) -> &'static str {
+        BACKEND_ID
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            fields: vec!["Goldilocks"],
+            hashes: vec!["blake3"],
+            fri_arities: vec![2],
+            recursion: "none",
+            lookups: false,
+            curves: vec![],
+            pedersen: false,
+            max_rows: Some(1 << 20),
+            incremental: false,
+            nondeterminism: Vec::new(),
+        }
+    }
+
+    fn prove(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        Err(ProveError::Unsupported {
+            backend_id: self.id().to_string(),
+            feature: "proving without a trace (pass --trace)".to_string(),
+        })
+    }
+
+    fn prove_with_trace(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        trace: &Trace,
+    ) -> Result<Proof, ProveError> {
+        check_trace(trace, ir, inputs)?;
+        let body = stark::prove(ir, profile, ctx, inputs, trace)
+            .map_err(|e| {
+                e.downcast::<ProveError>()
+                    .unwrap_or_else(|e| ProveError::InvalidInput(format!("{e:#}")))
+            })?
+            .to_bytes();
+        Ok(Proof::new(header_for(profile, ctx, inputs), body))
+    }
+}
+
+impl VerifierBackend for MiniStarkBackend {
+    fn verify(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        proof: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        if proof.body.len() as u64 != proof.header.body_len {
+            return Err(VerifyError::BodyLength {
+                expected: proof.header.body_len,
+                actual: proof.body.len() as u64,
+            });
+        }
+        proof
+            .header
+            .check_bindings(&header_for(profile, ctx, inputs))?;
+        let body = StarkProof::from_bytes(proof.body)
+            .map_err(|e| VerifyError::Rejected(format!("malformed ministark body: {e:#}")))?;
+        stark::verify(ir, profile, ctx, inputs, &body)
+    }
+}
+
+/// Register the ministark adapter with the corelib registry.
+pub fn register_ministark_backend() -> Result<(), RegistryError> {
+    register_backend(Box::new(MiniStarkBackend), Box::new(MiniStarkBackend))
+}
+
+/// Idempotent [`register_ministark_backend`] plus the corelib builtins.
+pub fn ensure_ministark_registered() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let _ = register_ministark_backend(); // already registered by the host is fine
+    });
+    zkprov_corelib::registry::ensure_builtins_registered();
+}
+
+fn header_for(profile: &Profile, ctx: &CallContext, inputs: &PublicInputs) -> proof::ProofHeader {
+    proof::ProofHeader {
+        backend_id_hash: proof::hash64("BACKEND", BACKEND_ID.as_bytes()),
+        profile_id_hash: proof::hash64("PROFILE", profile.id.as_bytes()),
+        pubio_hash: proof::pubio::pubio_hash(inputs),
+        body_len: 0,
+        config_hash: ctx.config_hash,
+        flags: 0,
+    }
+}
+
+/// Reject a trace that does not fit or satisfy the AIR before doing any
+/// STARK work, with the same errors as the native backend.
+fn check_trace(trace: &Trace, ir: &AirIr, inputs: &PublicInputs) -> Result<(), ProveError> {
+    let invalid = |e: anyhow::Error| ProveError::InvalidInput(format!("{e:#}"));
+    trace.check_shape(ir).map_err(invalid)?;
+    if let Some(v) = eval::violations(trace, ir, Some(1))
+        .map_err(invalid)?
+        .into_iter()
+        .next()
+    {
+        return Err(ProveError::Unsatisfied {
+            constraint: v.constraint,
+            row: v.row,
+            columns: v.columns(),
+            display: v.display,
+        });
+    }
+    if let Some(v) = eval::boundary_violations(trace, ir, &inputs.value())
+        .map_err(invalid)?
+        .into_iter()
+        .next()
+    {
+        return Err(ProveError::BoundaryUnsatisfied {
+            display: v.display,
+            row: v.row,
+            expected: v.expected.to_string(),
+            actual: v.actual.to_string(),
+        });
+    }
+    Ok(())
+}
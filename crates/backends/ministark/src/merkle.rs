@@ -0,0 +1,70 @@
+//! Binary Merkle commitments over rows of field elements, hashed with the
+//! corelib BLAKE3 leaf/node domains (see `zkprov_corelib::crypto::merkle`).
+
+use zkprov_corelib::crypto::blake3::Blake3;
+use zkprov_corelib::crypto::merkle::{leaf_hash, node2_hash};
+
+use crate::field::Felt;
+
+pub type Digest = [u8; 32];
+
+/// Leaf bytes of a row: each element as 8 little-endian bytes.
+pub fn leaf_bytes(row: &[Felt]) -> Vec<u8> {
+    row.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// A fully materialized tree over a power-of-two number of leaves.
+pub struct MerkleTree {
+    /// `layers[0]` holds the leaf hashes, the last layer the root.
+    layers: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+    pub fn new(rows: &[Vec<Felt>]) -> Self {
+        assert!(rows.len().is_power_of_two(), "leaf count must be 2^k");
+        let mut layers = vec![rows
+            .iter()
+            .map(|r| leaf_hash::<Blake3>(&leaf_bytes(r)))
+            .collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| node2_hash::<Blake3>(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> Digest {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Sibling hashes from leaf `index` up to the root.
+    pub fn open(&self, index: usize) -> Vec<Digest> {
+        let mut idx = index;
+        let mut path = Vec::with_capacity(self.layers.len() - 1);
+        for layer in &self.layers[..self.layers.len() - 1] {
+            path.push(layer[idx ^ 1]);
+            idx >>= 1;
+        }
+        path
+    }
+}
+
+/// Root implied by `row` at leaf `index` and its sibling `path`.
+pub fn root_from_path(row: &[Felt], index: usize, path: &[Digest]) -> Digest {
+    let mut acc = leaf_hash::<Blake3>(&leaf_bytes(row));
+    let mut idx = index;
+    for sibling in path {
+        acc = if idx & 1 == 1 {
+            node2_hash::<Blake3>(sibling, &acc)
+        } else {
+            node2_hash::<Blake3>(&acc, sibling)
+        };
+        idx >>= 1;
+    }
+    acc
+}
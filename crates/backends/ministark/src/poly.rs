@@ -0,0 +1,58 @@
+//! Radix-2 FFTs over power-of-two subgroups and their cosets.
+
+use crate::field::Felt;
+
+/// Evaluate the polynomial with coefficients `values` on `⟨ω⟩` in place,
+/// where `ω` is a primitive `values.len()`-th root of unity.
+pub fn fft(values: &mut [Felt], omega: Felt) {
+    let n = values.len();
+    assert!(n.is_power_of_two(), "fft size {n} is not a power of two");
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let w_len = omega.pow((n / len) as u64);
+        for chunk in values.chunks_mut(len) {
+            let mut w = Felt::ONE;
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (a, b) in lo.iter_mut().zip(hi) {
+                let t = *b * w;
+                *b = *a - t;
+                *a = *a + t;
+                w = w * w_len;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Inverse of [`fft`]: coefficients from evaluations on `⟨ω⟩`.
+pub fn ifft(values: &mut [Felt], omega: Felt) {
+    fft(values, omega.inverse());
+    let n_inv = Felt::new(values.len() as u64).inverse();
+    for v in values.iter_mut() {
+        *v = *v * n_inv;
+    }
+}
+
+/// Interpolate `column` (evaluations on the size-`n` subgroup) and evaluate
+/// it on the coset `shift·⟨ω_N⟩` of size `n * blowup`.
+pub fn low_degree_extend(column: &[Felt], blowup: usize, shift: Felt) -> Vec<Felt> {
+    let n = column.len();
+    let mut coeffs = column.to_vec();
+    ifft(&mut coeffs, Felt::root_of_unity(n));
+    let big = n * blowup;
+    coeffs.resize(big, Felt::ZERO);
+    let mut s = Felt::ONE;
+    for c in coeffs.iter_mut().take(n) {
+        *c = *c * s;
+        s = s * shift;
+    }
+    fft(&mut coeffs, Felt::root_of_unity(big));
+    coeffs
+}
@@ -0,0 +1,178 @@
+//! Proof body layout (little endian):
+//!
+//! ```text
+//! rows u32 | trace_root [32] | fri_roots: u8 count, [32] each
+//! remainder u64 | pow_nonce u64 | queries: u32 count, then per query
+//!   row: felts, path | next row: felts, path | per FRI layer: pair [2 felts], path
+//! ```
+//!
+//! Felt lists carry a `u16` count, paths a `u8` count; field elements must
+//! be canonical so every proof has exactly one encoding.
+
+use anyhow::{bail, Result};
+
+use crate::field::Felt;
+use crate::merkle::Digest;
+
+/// A trace row with its Merkle path.
+#[derive(Debug, Clone)]
+pub struct RowOpening {
+    pub values: Vec<Felt>,
+    pub path: Vec<Digest>,
+}
+
+/// A FRI leaf `(f(x), f(-x))` with its Merkle path.
+#[derive(Debug, Clone)]
+pub struct FriOpening {
+    pub pair: [Felt; 2],
+    pub path: Vec<Digest>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryProof {
+    pub row: RowOpening,
+    pub next: RowOpening,
+    pub fri: Vec<FriOpening>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StarkProof {
+    pub rows: u32,
+    pub trace_root: Digest,
+    pub fri_roots: Vec<Digest>,
+    pub remainder: Felt,
+    pub pow_nonce: u64,
+    pub queries: Vec<QueryProof>,
+}
+
+impl StarkProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&self.rows.to_le_bytes());
+        out.extend_from_slice(&self.trace_root);
+        put_digests(&mut out, &self.fri_roots);
+        out.extend_from_slice(&self.remainder.to_le_bytes());
+        out.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        out.extend_from_slice(&(self.queries.len() as u32).to_le_bytes());
+        for q in &self.queries {
+            for row in [&q.row, &q.next] {
+                put_felts(&mut out, &row.values);
+                put_digests(&mut out, &row.path);
+            }
+            out.push(q.fri.len() as u8);
+            for layer in &q.fri {
+                out.extend_from_slice(&layer.pair[0].to_le_bytes());
+                out.extend_from_slice(&layer.pair[1].to_le_bytes());
+                put_digests(&mut out, &layer.path);
+            }
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut r = Reader { bytes, pos: 0 };
+        let rows = r.u32()?;
+        let trace_root = r.digest()?;
+        let fri_roots = r.digests()?;
+        let remainder = r.felt()?;
+        let pow_nonce = r.u64()?;
+        let count = r.u32()? as usize;
+        let mut queries = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            let row = RowOpening {
+                values: r.felts()?,
+                path: r.digests()?,
+            };
+            let next = RowOpening {
+                values: r.felts()?,
+                path: r.digests()?,
+            };
+            let layers = r.u8()? as usize;
+            let mut fri = Vec::with_capacity(layers);
+            for _ in 0..layers {
+                fri.push(FriOpening {
+                    pair: [r.felt()?, r.felt()?],
+                    path: r.digests()?,
+                });
+            }
+            queries.push(QueryProof { row, next, fri });
+        }
+        if r.pos != bytes.len() {
+            bail!("{} trailing bytes", bytes.len() - r.pos);
+        }
+        Ok(Self {
+            rows,
+            trace_root,
+            fri_roots,
+            remainder,
+            pow_nonce,
+            queries,
+        })
+    }
+}
+
+fn put_felts(out: &mut Vec<u8>, values: &[Felt]) {
+    out.extend_from_slice(&(values.len() as u16).to_le_bytes());
+    for v in values {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+}
+
+fn put_digests(out: &mut Vec<u8>, digests: &[Digest]) {
+    out.push(digests.len() as u8);
+    for d in digests {
+        out.extend_from_slice(d);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8]> {
+        let end = self.pos + n;
+        if end > self.bytes.len() {
+            bail!("truncated at byte {}", self.pos);
+        }
+        let s = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(s)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn felt(&mut self) -> Result<Felt> {
+        let at = self.pos;
+        let v = self.u64()?;
+        match Felt::from_canonical(v) {
+            Some(f) => Ok(f),
+            None => bail!("non-canonical field element at byte {}", at),
+        }
+    }
+
+    fn felts(&mut self) -> Result<Vec<Felt>> {
+        let n = u16::from_le_bytes(self.take(2)?.try_into().unwrap());
+        (0..n).map(|_| self.felt()).collect()
+    }
+
+    fn digest(&mut self) -> Result<Digest> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    fn digests(&mut self) -> Result<Vec<Digest>> {
+        let n = self.u8()?;
+        (0..n).map(|_| self.digest()).collect()
+    }
+}
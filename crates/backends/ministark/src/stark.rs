@@ -0,0 +1,382 @@
+//! Proving and verification.
+//!
+//! The trace (`n` rows, a power of two) is interpolated column by column and
+//! extended to the coset `g·⟨ω_N⟩` with `N = n · blowup`, where row `i`'s
+//! successor sits `blowup` positions later. The extended rows are committed
+//! in one Merkle tree; the composition polynomial (see
+//! [`constraints::composition_at`]) is evaluated on the same coset and
+//! proven low-degree with FRI. Each query opens trace rows `i` and
+//! `i + blowup`, recomputes the composition there and follows it through
+//! every FRI layer.
+
+use anyhow::{bail, Result};
+
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{CallContext, PublicInputs};
+use zkprov_corelib::deadline::Phases;
+use zkprov_corelib::errors::VerifyError;
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::trace::memory::DEFAULT_BLOWUP;
+use zkprov_corelib::trace::Trace;
+
+use crate::constraints::{composition_at, Coefficients, Constraints};
+use crate::field::{batch_inverse, Felt, GENERATOR};
+use crate::fri::{self, FriLayer};
+use crate::merkle::{root_from_path, Digest, MerkleTree};
+use crate::poly::low_degree_extend;
+use crate::proof_body::{QueryProof, RowOpening, StarkProof};
+use crate::transcript::Transcript;
+
+/// Queries drawn when the profile leaves `fri_queries` unset.
+pub const DEFAULT_QUERIES: u32 = 30;
+/// Largest accepted `grind_bits`.
+pub const MAX_GRIND_BITS: u32 = 32;
+const TRANSCRIPT_LABEL: &str = "zkprov/ministark/v1";
+
+/// Protocol parameters for one proof.
+#[derive(Debug, Clone, Copy)]
+pub struct Params {
+    pub rows: usize,
+    pub blowup: usize,
+    pub queries: usize,
+    pub grind_bits: u32,
+    /// Composition degree bound over `rows`.
+    pub quotient_factor: usize,
+}
+
+impl Params {
+    pub fn new(profile: &Profile, rows: u32, constraints: &Constraints) -> Result<Self> {
+        let blowup = profile.fri_blowup.unwrap_or(DEFAULT_BLOWUP) as usize;
+        let queries = profile.fri_queries.unwrap_or(DEFAULT_QUERIES) as usize;
+        let grind_bits = profile.grind_bits.unwrap_or(0);
+        let quotient_factor = constraints.quotient_factor();
+        if rows < 2 || !rows.is_power_of_two() {
+            bail!("trace rows must be a power of two >= 2, got {}", rows);
+        }
+        if !blowup.is_power_of_two() || blowup < 2 * quotient_factor {
+            bail!(
+                "fri_blowup {} too small for degree-{} constraints (need a power of two >= {})",
+                blowup,
+                constraints.max_degree(),
+                2 * quotient_factor
+            );
+        }
+        let lde = rows as u64 * blowup as u64;
+        if lde > 1 << 32 {
+            bail!("evaluation domain 2^{} exceeds 2^32", lde.trailing_zeros());
+        }
+        if queries == 0 || queries > u16::MAX as usize {
+            bail!("fri_queries must be in 1..=65535, got {}", queries);
+        }
+        if grind_bits > MAX_GRIND_BITS {
+            bail!("grind_bits {} exceeds {}", grind_bits, MAX_GRIND_BITS);
+        }
+        if constraints.width > u16::MAX as usize {
+            bail!("trace width {} exceeds 65535", constraints.width);
+        }
+        Ok(Self {
+            rows: rows as usize,
+            blowup,
+            queries,
+            grind_bits,
+            quotient_factor,
+        })
+    }
+
+    pub fn lde_size(&self) -> usize {
+        self.rows * self.blowup
+    }
+
+    pub fn degree_bound(&self) -> usize {
+        self.rows * self.quotient_factor
+    }
+
+    fn absorb(&self, transcript: &mut Transcript) {
+        for (label, v) in [
+            ("ROWS", self.rows),
+            ("BLOWUP", self.blowup),
+            ("QUERIES", self.queries),
+            ("GRIND", self.grind_bits as usize),
+        ] {
+            transcript.absorb(label, &(v as u64).to_le_bytes());
+        }
+    }
+}
+
+/// The `i`-th point of the evaluation coset.
+fn lde_point(params: &Params, i: usize) -> Felt {
+    GENERATOR * Felt::root_of_unity(params.lde_size()).pow(i as u64)
+}
+
+/// Prove that `trace` satisfies `ir` under `inputs`. The trace must already
+/// have been checked against the AIR. `ctx.deadline` is checked after
+/// the trace commitment, the composition and FRI; an expired one fails with
+/// `ProveError::TimedOut` (downcast it from the returned error).
+pub fn prove(
+    ir: &AirIr,
+    profile: &Profile,
+    ctx: &CallContext,
+    inputs: &PublicInputs,
+    trace: &Trace,
+) -> Result<StarkProof> {
+    let constraints = Constraints::new(ir, inputs, trace.rows)?;
+    let params = Params::new(profile, trace.rows, &constraints)?;
+    let (n, big) = (params.rows, params.lde_size());
+    let mut phases = Phases::new(ctx.deadline);
+
+    let lde_cols: Vec<Vec<Felt>> = (0..trace.cols())
+        .map(|col| {
+            let column: Vec<Felt> = (0..trace.rows)
+                .map(|row| Felt::from_biguint(&trace.get(row, col).unwrap_or_default()))
+                .collect();
+            low_degree_extend(&column, params.blowup, GENERATOR)
+        })
+        .collect();
+    let lde_rows: Vec<Vec<Felt>> = (0..big)
+        .map(|i| lde_cols.iter().map(|c| c[i]).collect())
+        .collect();
+    let trace_tree = MerkleTree::new(&lde_rows);
+    phases.finish("trace")?;
+
+    let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+    transcript.absorb("CONFIG", &ctx.config_hash.to_le_bytes());
+    constraints.absorb(ir, inputs, &mut transcript);
+    params.absorb(&mut transcript);
+    transcript.absorb("TRACE.ROOT", &trace_tree.root());
+    let coeffs = Coefficients::draw(&constraints, &mut transcript);
+
+    // Composition over the coset; the divisions are batched.
+    let omega_n = Felt::root_of_unity(n);
+    let omega_big = Felt::root_of_unity(big);
+    let mut x = GENERATOR;
+    let points: Vec<Felt> = (0..big)
+        .map(|_| {
+            let cur = x;
+            x = x * omega_big;
+            cur
+        })
+        .collect();
+    let last = omega_n.pow(n as u64 - 1);
+    // x^n - 1 repeats with period `blowup` on the coset.
+    let vanishing_inv = batch_inverse(
+        &points[..params.blowup]
+            .iter()
+            .map(|x| x.pow(n as u64) - Felt::ONE)
+            .collect::<Vec<_>>(),
+    );
+    let mut boundary_inv: Vec<(u32, Vec<Felt>)> = Vec::new();
+    for a in &constraints.assertions {
+        if boundary_inv.iter().all(|(row, _)| *row != a.row) {
+            let root = omega_n.pow(a.row as u64);
+            let diffs: Vec<Felt> = points.iter().map(|&x| x - root).collect();
+            boundary_inv.push((a.row, batch_inverse(&diffs)));
+        }
+    }
+    let composition: Vec<Felt> = (0..big)
+        .map(|i| {
+            let (cur, next) = (&lde_rows[i], &lde_rows[(i + params.blowup) % big]);
+            let mut acc = Felt::ZERO;
+            if !constraints.transitions.is_empty() {
+                let mut sum = Felt::ZERO;
+                for (expr, alpha) in constraints.transitions.iter().zip(&coeffs.transitions) {
+                    sum = sum + *alpha * crate::constraints::eval(expr, cur, next);
+                }
+                acc = sum * (points[i] - last) * vanishing_inv[i % params.blowup];
+            }
+            for (a, beta) in constraints.assertions.iter().zip(&coeffs.assertions) {
+                let inv = &boundary_inv
+                    .iter()
+                    .find(|(row, _)| *row == a.row)
+                    .unwrap()
+                    .1;
+                acc = acc + *beta * (cur[a.column] - a.value) * inv[i];
+            }
+            acc
+        })
+        .collect();
+    phases.finish("composition")?;
+
+    let (layers, remainder) = fri::commit(
+        composition,
+        GENERATOR,
+        params.degree_bound(),
+        &mut transcript,
+    );
+    transcript.absorb("FRI.REMAINDER", &remainder.to_le_bytes());
+    let pow_nonce = transcript.grind(params.grind_bits);
+    transcript.absorb("POW", &pow_nonce.to_le_bytes());
+    phases.finish("fri")?;
+
+    let queries = (0..params.queries)
+        .map(|_| {
+            let pos = transcript.draw_index(big);
+            let next = (pos + params.blowup) % big;
+            QueryProof {
+                row: RowOpening {
+                    values: lde_rows[pos].clone(),
+                    path: trace_tree.open(pos),
+                },
+                next: RowOpening {
+                    values: lde_rows[next].clone(),
+                    path: trace_tree.open(next),
+                },
+                fri: open_layers(&layers, pos),
+            }
+        })
+        .collect();
+
+    Ok(StarkProof {
+        rows: trace.rows,
+        trace_root: trace_tree.root(),
+        fri_roots: layers.iter().map(FriLayer::root).collect(),
+        remainder,
+        pow_nonce,
+        queries,
+    })
+}
+
+fn open_layers(layers: &[FriLayer], mut pos: usize) -> Vec<crate::proof_body::FriOpening> {
+    layers
+        .iter()
+        .map(|layer| {
+            let opening = layer.open(pos);
+            pos %= layer.domain_size() / 2;
+            opening
+        })
+        .collect()
+}
+
+fn hex(d: &Digest) -> String {
+    format!("0x{}", blake3::Hash::from_bytes(*d).to_hex())
+}
+
+fn rejected(msg: impl Into<String>) -> VerifyError {
+    VerifyError::Rejected(msg.into())
+}
+
+/// Check `proof` for `ir` under `inputs` and the profile's parameters.
+/// `ctx.config_hash` is absorbed like at prove time, so a proof made for
+/// another config fails its Fiat–Shamir checks.
+pub fn verify(
+    ir: &AirIr,
+    profile: &Profile,
+    ctx: &CallContext,
+    inputs: &PublicInputs,
+    proof: &StarkProof,
+) -> Result<(), VerifyError> {
+    let constraints = Constraints::new(ir, inputs, proof.rows).map_err(VerifyError::Backend)?;
+    let params =
+        Params::new(profile, proof.rows, &constraints).map_err(|e| rejected(e.to_string()))?;
+    let big = params.lde_size();
+    let log_big = big.trailing_zeros() as usize;
+    let layers = fri::num_layers(params.degree_bound());
+    if proof.fri_roots.len() != layers {
+        return Err(rejected(format!(
+            "expected {} FRI layers, proof has {}",
+            layers,
+            proof.fri_roots.len()
+        )));
+    }
+    if proof.queries.len() != params.queries {
+        return Err(rejected(format!(
+            "expected {} queries, proof has {}",
+            params.queries,
+            proof.queries.len()
+        )));
+    }
+
+    let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+    transcript.absorb("CONFIG", &ctx.config_hash.to_le_bytes());
+    constraints.absorb(ir, inputs, &mut transcript);
+    params.absorb(&mut transcript);
+    transcript.absorb("TRACE.ROOT", &proof.trace_root);
+    let coeffs = Coefficients::draw(&constraints, &mut transcript);
+    let betas: Vec<Felt> = proof
+        .fri_roots
+        .iter()
+        .map(|root| {
+            transcript.absorb("FRI.ROOT", root);
+            transcript.draw_felt()
+        })
+        .collect();
+    transcript.absorb("FRI.REMAINDER", &proof.remainder.to_le_bytes());
+    if !transcript.check_grind(proof.pow_nonce, params.grind_bits) {
+        return Err(rejected(format!(
+            "proof-of-work nonce does not meet {} bits",
+            params.grind_bits
+        )));
+    }
+    transcript.absorb("POW", &proof.pow_nonce.to_le_bytes());
+
+    for (k, query) in proof.queries.iter().enumerate() {
+        let pos = transcript.draw_index(big);
+        let next = (pos + params.blowup) % big;
+        for (row, index) in [(&query.row, pos), (&query.next, next)] {
+            if row.values.len() != constraints.width || row.path.len() != log_big {
+                return Err(rejected(format!("query {k}: malformed trace opening")));
+            }
+            let root = root_from_path(&row.values, index, &row.path);
+            if root != proof.trace_root {
+                return Err(VerifyError::RootMismatch {
+                    what: format!("trace Merkle path (query {k}, row {index})"),
+                    expected: hex(&proof.trace_root),
+                    actual: hex(&root),
+                });
+            }
+        }
+        if query.fri.len() != layers {
+            return Err(rejected(format!("query {k}: malformed FRI openings")));
+        }
+
+        let x = lde_point(&params, pos);
+        let mut value = composition_at(
+            &constraints,
+            &coeffs,
+            x,
+            &query.row.values,
+            &query.next.values,
+        );
+        let (mut pos, mut size, mut shift) = (pos, big, GENERATOR);
+        for (l, (opening, (root, beta))) in query
+            .fri
+            .iter()
+            .zip(proof.fri_roots.iter().zip(&betas))
+            .enumerate()
+        {
+            let half = size / 2;
+            let i = pos % half;
+            if opening.path.len() != half.trailing_zeros() as usize {
+                return Err(rejected(format!(
+                    "query {k}: malformed FRI layer {l} opening"
+                )));
+            }
+            let got = root_from_path(&opening.pair, i, &opening.path);
+            if got != *root {
+                return Err(VerifyError::RootMismatch {
+                    what: format!("FRI layer {l} Merkle path (query {k})"),
+                    expected: hex(root),
+                    actual: hex(&got),
+                });
+            }
+            if opening.pair[usize::from(pos >= half)] != value {
+                return Err(rejected(format!(
+                    "query {k}: FRI layer {l} is inconsistent with {}",
+                    if l == 0 {
+                        "the trace"
+                    } else {
+                        "the previous layer"
+                    }
+                )));
+            }
+            let x = shift * Felt::root_of_unity(size).pow(i as u64);
+            value = fri::fold(opening.pair, *beta, x);
+            (pos, size, shift) = (i, half, shift * shift);
+        }
+        if value != proof.remainder {
+            return Err(rejected(format!(
+                "query {k}: FRI remainder mismatch (composition is not low-degree)"
+            )));
+        }
+    }
+    Ok(())
+}
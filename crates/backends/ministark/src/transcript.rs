@@ -0,0 +1,80 @@
+//! Fiat–Shamir transcript: a BLAKE3 hash chain over everything the prover
+//! has sent so far.
+
+use crate::field::{Felt, P};
+use crate::merkle::Digest;
+
+pub struct Transcript {
+    state: Digest,
+    /// Challenges drawn since the last absorb, so repeated draws differ.
+    counter: u64,
+}
+
+impl Transcript {
+    pub fn new(label: &str) -> Self {
+        Self {
+            state: *blake3::hash(label.as_bytes()).as_bytes(),
+            counter: 0,
+        }
+    }
+
+    /// Absorb `bytes` under `label`.
+    pub fn absorb(&mut self, label: &str, bytes: &[u8]) {
+        let mut h = blake3::Hasher::new();
+        h.update(&self.state);
+        h.update(&(label.len() as u64).to_le_bytes());
+        h.update(label.as_bytes());
+        h.update(&(bytes.len() as u64).to_le_bytes());
+        h.update(bytes);
+        self.state = *h.finalize().as_bytes();
+        self.counter = 0;
+    }
+
+    fn draw_bytes(&mut self) -> Digest {
+        let mut h = blake3::Hasher::new();
+        h.update(&self.state);
+        h.update(b"DRAW");
+        h.update(&self.counter.to_le_bytes());
+        self.counter += 1;
+        *h.finalize().as_bytes()
+    }
+
+    fn draw_u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.draw_bytes()[..8].try_into().unwrap())
+    }
+
+    /// A uniformly random field element (rejection sampled).
+    pub fn draw_felt(&mut self) -> Felt {
+        loop {
+            let v = self.draw_u64();
+            if v < P {
+                return Felt::new(v);
+            }
+        }
+    }
+
+    /// A uniformly random index below `bound`, a power of two.
+    pub fn draw_index(&mut self, bound: usize) -> usize {
+        debug_assert!(bound.is_power_of_two());
+        (self.draw_u64() as usize) & (bound - 1)
+    }
+
+    /// Leading zero bits of the hash of the current state and `nonce`.
+    fn work(&self, nonce: u64) -> u32 {
+        let mut h = blake3::Hasher::new();
+        h.update(&self.state);
+        h.update(b"GRIND");
+        h.update(&nonce.to_le_bytes());
+        let d = h.finalize();
+        u64::from_be_bytes(d.as_bytes()[..8].try_into().unwrap()).leading_zeros()
+    }
+
+    /// Smallest nonce whose proof of work has `bits` leading zero bits.
+    pub fn grind(&self, bits: u32) -> u64 {
+        (0..).find(|&nonce| self.work(nonce) >= bits).unwrap()
+    }
+
+    pub fn check_grind(&self, nonce: u64, bits: u32) -> bool {
+        self.work(nonce) >= bits
+    }
+}
@@ -0,0 +1,242 @@
+use std::path::Path;
+use std::time::Duration;
+
+use zkprov_backend_ministark::field::{Felt, P};
+use zkprov_backend_ministark::poly::{fft, ifft};
+use zkprov_backend_ministark::{
+    ensure_ministark_registered, stark, MiniStarkBackend, StarkProof, BACKEND_ID,
+};
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::air::rows::RowsHint;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{CallContext, PublicInputs, VerifierBackend};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::deadline::Deadline;
+use zkprov_corelib::errors::{ProveError, VerifyError};
+use zkprov_corelib::profile::{parse_overrides, resolve_profile};
+use zkprov_corelib::proof::ProofView;
+use zkprov_corelib::trace::Trace;
+
+const AIR_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/air");
+
+struct Fixture {
+    ir: AirIr,
+    inputs: String,
+    trace: Trace,
+}
+
+fn fixture(name: &str) -> Fixture {
+    let dir = Path::new(AIR_DIR);
+    let read = |ext: &str| std::fs::read_to_string(dir.join(format!("{name}.{ext}"))).unwrap();
+    let trace = serde_json::from_str(&read("trace.json")).unwrap();
+    Fixture {
+        ir: parse_air_file(&dir.join(format!("{name}.air"))).unwrap(),
+        inputs: read("inputs.json"),
+        trace: Trace::from_json(&trace).unwrap(),
+    }
+}
+
+/// dev-fast (80 bits, blowup 8) with cheap grinding: 26 · 3 + 4 = 82 bits.
+fn config() -> Config {
+    Config::new(BACKEND_ID, "Goldilocks", "blake3", 2, false, "dev-fast")
+        .with_profile_overrides(parse_overrides(&["fri_queries=26", "grind_bits=4"]).unwrap())
+}
+
+fn prove(f: &Fixture) -> Vec<u8> {
+    ensure_ministark_registered();
+    let inputs = PublicInputs::from_json(f.inputs.clone()).unwrap();
+    zkprov_corelib::prove_with_trace(&config(), &f.ir, &inputs, &f.trace)
+        .expect("prove")
+        .to_bytes()
+}
+
+fn verify(f: &Fixture, inputs: &str, proof: &[u8]) -> Result<(), VerifyError> {
+    let inputs = PublicInputs::from_json(inputs).unwrap();
+    let view = ProofView::from_bytes(proof).map_err(|e| VerifyError::Malformed(e.to_string()))?;
+    zkprov_corelib::verify(&config(), &f.ir, &inputs, &view)
+}
+
+#[test]
+fn fft_roundtrips_over_goldilocks() {
+    let omega = Felt::root_of_unity(16);
+    assert_eq!(omega.pow(16), Felt::ONE);
+    assert_ne!(omega.pow(8), Felt::ONE);
+    assert_eq!(Felt::new(P - 1) + Felt::new(2), Felt::ONE);
+    assert_eq!(Felt::new(3) * Felt::new(3).inverse(), Felt::ONE);
+
+    let coeffs: Vec<Felt> = (0..16).map(|i| Felt::new(i * i + 1)).collect();
+    let mut evals = coeffs.clone();
+    fft(&mut evals, omega);
+    // Spot-check against direct evaluation at ω^3.
+    let x = omega.pow(3);
+    let direct = coeffs.iter().rev().fold(Felt::ZERO, |acc, &c| acc * x + c);
+    assert_eq!(evals[3], direct);
+    ifft(&mut evals, omega);
+    assert_eq!(evals, coeffs);
+}
+
+#[test]
+fn fib_and_merkle_path_prove_and_verify() {
+    for name in ["fib", "merkle_path"] {
+        let f = fixture(name);
+        let proof = prove(&f);
+        verify(&f, &f.inputs, &proof).unwrap_or_else(|e| panic!("{name}: {e}"));
+        // Deterministic: same trace, same proof.
+        assert_eq!(prove(&f), proof, "{name}");
+    }
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let f = fixture("merkle_path");
+    let proof = prove(&f);
+
+    // Any flipped body byte is caught: Merkle paths, FRI consistency or
+    // decoding, depending on where it lands.
+    for offset in [48 + 4, 48 + 40, proof.len() / 2, proof.len() - 1] {
+        let mut bad = proof.clone();
+        bad[offset] ^= 1;
+        assert!(verify(&f, &f.inputs, &bad).is_err(), "offset {offset}");
+    }
+    // The last bytes are a sibling on the final FRI layer's Merkle path.
+    let mut bad = proof.clone();
+    *bad.last_mut().unwrap() ^= 1;
+    let err = verify(&f, &f.inputs, &bad).unwrap_err();
+    assert!(
+        matches!(err, VerifyError::RootMismatch { ref what, .. } if what.starts_with("FRI layer")),
+        "{err}"
+    );
+
+    // A different root is a different statement.
+    let err = verify(&f, r#"{"leaf":42,"root":7}"#, &proof).unwrap_err();
+    assert!(matches!(err, VerifyError::HeaderBinding { .. }), "{err}");
+}
+
+#[test]
+fn rewritten_config_hash_fails_the_transcript() {
+    let f = fixture("fib");
+    let proof = prove(&f);
+    let other = config().config_hash() ^ 1;
+    let mut forged = proof.clone();
+    forged[48..56].copy_from_slice(&other.to_le_bytes());
+
+    let cfg = config();
+    let inputs = PublicInputs::from_json(f.inputs.clone())
+        .unwrap()
+        .bind(cfg.pubio_encoding, &f.ir, cfg.undeclared_inputs)
+        .unwrap();
+    let view = ProofView::from_bytes(&forged).unwrap();
+    assert_eq!(view.header.config_hash, other);
+    let profile = resolve_profile("dev-fast", &cfg.profile_overrides).unwrap();
+    let err = MiniStarkBackend
+        .verify(&f.ir, &profile, &CallContext::new(other), &inputs, &view)
+        .unwrap_err();
+    assert!(!matches!(err, VerifyError::HeaderBinding { .. }), "{err}");
+}
+
+#[test]
+fn composition_of_a_bad_trace_fails_low_degree_test() {
+    // Bypass the prover's trace checks: the STARK itself must catch it.
+    let mut f = fixture("fib");
+    let mut cols: Vec<Vec<u64>> = (0..2)
+        .map(|c| {
+            (0..f.trace.rows)
+                .map(|r| f.trace.get(r, c).unwrap().try_into().unwrap())
+                .collect()
+        })
+        .collect();
+    cols[1][5] += 1;
+    f.trace = Trace::from_u64_columns("Goldilocks", &cols).unwrap();
+    let profile = resolve_profile(
+        "dev-fast",
+        &parse_overrides(&["fri_queries=27", "grind_bits=0"]).unwrap(),
+    )
+    .unwrap();
+    let inputs = PublicInputs::from_json(f.inputs.clone()).unwrap();
+    let proof = stark::prove(&f.ir, &profile, &CallContext::new(0), &inputs, &f.trace).unwrap();
+    let bytes = proof.to_bytes();
+    let back = StarkProof::from_bytes(&bytes).unwrap();
+    let err = stark::verify(&f.ir, &profile, &CallContext::new(0), &inputs, &back).unwrap_err();
+    assert!(
+        matches!(err, VerifyError::Rejected(ref m) if m.contains("FRI")),
+        "{err}"
+    );
+}
+
+#[test]
+fn prover_rejects_unsupported_requests() {
+    ensure_ministark_registered();
+    let f = fixture("fib");
+    let inputs = PublicInputs::from_json(f.inputs.clone()).unwrap();
+    let err = zkprov_corelib::prove(&config(), &f.ir, &inputs).unwrap_err();
+    assert!(matches!(err, ProveError::Unsupported { .. }), "{err}");
+
+    // Unsatisfied traces are refused with the failing constraint.
+    let cols: Vec<Vec<u64>> = vec![vec![1; 16], vec![1; 16]];
+    let bad = Trace::from_u64_columns("Goldilocks", &cols).unwrap();
+    let err = zkprov_corelib::prove_with_trace(&config(), &f.ir, &inputs, &bad).unwrap_err();
+    assert!(matches!(err, ProveError::Unsatisfied { .. }), "{err}");
+
+    // The degree-3 Merkle AIR needs a blowup of at least 4.
+    let m = fixture("merkle_path");
+    let inputs = PublicInputs::from_json(m.inputs.clone()).unwrap();
+    let cfg = config()
+        .with_profile_overrides(parse_overrides(&["fri_blowup=2", "fri_queries=76"]).unwrap());
+    let err = zkprov_corelib::prove_with_trace(&cfg, &m.ir, &inputs, &m.trace).unwrap_err();
+    assert!(err.to_string().contains("fri_blowup 2 too small"), "{err}");
+}
+
+#[test]
+fn expired_deadline_stops_at_the_first_phase_boundary() {
+    ensure_ministark_registered();
+    let f = fixture("fib");
+    let inputs = PublicInputs::from_json(f.inputs.clone()).unwrap();
+    let cfg = config().with_deadline(Deadline::after(Duration::ZERO));
+    let err = zkprov_corelib::prove_with_trace(&cfg, &f.ir, &inputs, &f.trace).unwrap_err();
+    let ProveError::TimedOut {
+        phase,
+        budget_ms,
+        phases,
+        ..
+    } = err
+    else {
+        panic!("expected TimedOut, got {err}");
+    };
+    assert_eq!((phase.as_str(), budget_ms), ("trace", 0));
+    assert_eq!(phases.len(), 1);
+
+    // A generous budget changes nothing.
+    let cfg = config().with_deadline(Deadline::after(Duration::from_secs(3600)));
+    let proof = zkprov_corelib::prove_with_trace(&cfg, &f.ir, &inputs, &f.trace).unwrap();
+    assert_eq!(proof.to_bytes(), prove(&f));
+}
+
+#[test]
+fn rows_hint_expressions_are_pinned_and_bounded_by_max_rows() {
+    ensure_ministark_registered();
+    let f = fixture("fib");
+    let inputs = PublicInputs::from_json(f.inputs.clone()).unwrap();
+    let with_hint = |expr: &str| AirIr {
+        rows_hint: Some(RowsHint::Expr(expr.to_string())),
+        ..f.ir.clone()
+    };
+
+    // next_pow2(1597) / 128 = 16 rows, the fixture's fixed hint.
+    let ir = with_hint("next_pow2(result) / 128");
+    let proof = zkprov_corelib::prove_with_trace(&config(), &ir, &inputs, &f.trace).unwrap();
+    assert_eq!(proof.to_bytes(), prove(&f));
+    assert_eq!(
+        zkprov_corelib::resolve_rows(&config(), &ir, &inputs).unwrap(),
+        Some(16)
+    );
+
+    // 2^21 rows fit the AIR range but not ministark's max_rows.
+    let ir = with_hint("next_pow2(result) * 1024");
+    let err = zkprov_corelib::prove_with_trace(&config(), &ir, &inputs, &f.trace).unwrap_err();
+    assert!(matches!(err, ProveError::InvalidInput(_)), "{err}");
+    assert!(
+        err.to_string()
+            .contains("rows_hint evaluates to 2097152, above the backend max_rows 1048576"),
+        "{err}"
+    );
+}
@@ -1,16 +1,22 @@
 //! Native backend adapter with AIR-aware stub proving and hash selection.
 
-use zkprov_corelib::air::AirProgram;
-use zkprov_corelib::backend::{Capabilities, ProverBackend, VerifierBackend};
+use std::path::Path;
+use std::sync::Once;
+
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::config::Config;
 use zkprov_corelib::crypto::registry::hash64_by_id;
-use zkprov_corelib::errors::RegistryError;
+use zkprov_corelib::deadline::Phases;
+use zkprov_corelib::errors::{ProveError, RegistryError, VerifyError};
+use zkprov_corelib::evm::pubio::PubIoEncoding;
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{self, Proof, ProofView};
 use zkprov_corelib::registry::register_backend;
-use zkprov_corelib::trace::TraceShape;
-use zkprov_corelib::{
-    config::Config,
-    proof,
-    validate::{validate_air_against_backend, validate_config},
-};
+use zkprov_corelib::trace::{eval, Reuse, Trace, TraceCache, TraceShape};
 
 #[derive(Debug, Default)]
 pub struct NativeBackend;
@@ -28,24 +34,105 @@ impl ProverBackend for NativeBackend {
             lookups: false,
             curves: vec!["placeholder"],
             pedersen: true,
+            max_rows: Some(1 << 22),
+            incremental: true,
+            nondeterminism: Vec::new(),
         }
     }
-    fn prove_stub(&self) -> Vec<u8> {
-        b"PROOF\0".to_vec()
+    fn prove(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        prove_inner(self.id(), ir, profile, ctx, inputs, None, None).map(|(proof, _)| proof)
+    }
+    fn prove_with_trace(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        trace: &Trace,
+    ) -> Result<Proof, ProveError> {
+        prove_inner(self.id(), ir, profile, ctx, inputs, Some(trace), None).map(|(proof, _)| proof)
+    }
+    fn prove_incremental(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        trace: &Trace,
+        cache: &mut TraceCache,
+    ) -> Result<(Proof, Reuse), ProveError> {
+        prove_inner(
+            self.id(),
+            ir,
+            profile,
+            ctx,
+            inputs,
+            Some(trace),
+            Some(cache),
+        )
+    }
+    /// Knobs a real prover would take; the stub validates but ignores them.
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "partition_size": { "type": "integer", "minimum": 1 },
+                "batching": { "enum": ["linear", "algebraic"] }
+            },
+            "additionalProperties": false
+        }))
     }
 }
+
 impl VerifierBackend for NativeBackend {
-    fn verify_stub(&self, proof: &[u8]) -> bool {
-        proof == b"PROOF\0"
+    fn verify(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        proof: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        verify_inner(ProverBackend::id(self), ir, profile, ctx, inputs, proof)
     }
 }
 
+/// Register the native adapter, replacing the capabilities-only placeholder
+/// that `zkprov_corelib::registry::ensure_builtins_registered` installs.
 pub fn register_native_backend() -> Result<(), RegistryError> {
     register_backend(Box::new(NativeBackend), Box::new(NativeBackend))
 }
 
-/// Deterministic root over AIR+Trace+Inputs using selected hash (64-bit).
-fn fake_trace_root_u64(air: &AirProgram, inputs_json: &str, hash_id: &str) -> anyhow::Result<u64> {
+/// Idempotent [`register_native_backend`] plus the corelib builtins/aliases.
+pub fn ensure_native_registered() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let _ = register_native_backend(); // already registered by the host is fine
+    });
+    zkprov_corelib::registry::ensure_builtins_registered();
+}
+
+/// Hash id used for the fake root: the run's selection, else the AIR default.
+fn selected_hash<'a>(ir: &'a AirIr, ctx: &'a CallContext) -> &'a str {
+    ctx.hash.as_deref().unwrap_or(ir.meta.hash.as_str())
+}
+
+/// Deterministic root over Config+AIR+Trace+Inputs using selected hash
+/// (64-bit). When proving from a trace file, its commitment is mixed in as
+/// well.
+fn fake_trace_root_u64(
+    config_hash: u64,
+    ir: &AirIr,
+    inputs: &PublicInputs,
+    hash_id: &str,
+    trace_commitment: Option<&[u8]>,
+) -> anyhow::Result<u64> {
     // Mix in salient fields; order matters (stable).
     let mut accum = 0u64;
     let mix = |acc: &mut u64, label: &str, bytes: &[u8]| -> anyhow::Result<()> {
@@ -54,89 +141,247 @@ fn fake_trace_root_u64(air: &AirProgram, inputs_json: &str, hash_id: &str) -> an
         *acc ^= h.rotate_left(13) ^ h.wrapping_mul(0x9e3779b97f4a7c15);
         Ok(())
     };
-    let shape = TraceShape::from_air(air);
+    let shape = TraceShape::from_ir(ir);
 
-    mix(&mut accum, "AIR.NAME", air.meta.name.as_bytes())?;
-    mix(&mut accum, "AIR.FIELD", air.meta.field.as_bytes())?;
+    mix(&mut accum, "CONFIG", &config_hash.to_le_bytes())?;
+    mix(&mut accum, "AIR.NAME", ir.meta.name.as_bytes())?;
+    mix(&mut accum, "AIR.FIELD", ir.meta.field.as_bytes())?;
     mix(&mut accum, "TRACE.ROWS", &shape.rows.to_le_bytes())?;
     mix(&mut accum, "TRACE.COLS", &shape.cols.to_le_bytes())?;
-    mix(&mut accum, "IO.JSON", inputs_json.as_bytes())?;
+    let io_label = match inputs.encoding() {
+        PubIoEncoding::Json => "IO.JSON",
+        PubIoEncoding::Typed => "IO.TYPED",
+    };
+    mix(&mut accum, io_label, inputs.binding())?;
+    let transitions = ir.transition_constraints()?;
+    if !transitions.is_empty() {
+        let rendered: Vec<String> = transitions.iter().map(|c| c.to_string()).collect();
+        mix(
+            &mut accum,
+            "AIR.TRANSITIONS",
+            rendered.join("\n").as_bytes(),
+        )?;
+    }
+    let boundary = ir.boundary_assertions(shape.rows)?;
+    if !boundary.is_empty() {
+        let rendered: Vec<String> = boundary.iter().map(|a| a.to_string()).collect();
+        mix(&mut accum, "AIR.BOUNDARY", rendered.join("\n").as_bytes())?;
+    }
+    if let Some(fixed) = ir.columns.fixed_commitment() {
+        mix(&mut accum, "AIR.FIXED", &fixed)?;
+    }
+    if let Some(commitment) = trace_commitment {
+        mix(&mut accum, "TRACE.COMMIT", commitment)?;
+    }
 
     Ok(accum)
 }
 
-/// Prove: AIR-aware deterministic proof.
+/// Load `air_path` and prove through the registry; `config.backend_id`
+/// selects the backend (normally `native@0.0` or an alias of it).
 pub fn native_prove(
     config: &Config,
     public_inputs_json: &str,
     air_path: &str,
 ) -> anyhow::Result<Vec<u8>> {
-    zkprov_corelib::registry::ensure_builtins_registered();
-    validate_config(config)?;
-
-    // Load and validate AIR
-    let air = AirProgram::load_from_file(air_path)?;
-    validate_air_against_backend(&air, &config.backend_id)?;
-
-    // Header identifiers
-    let backend_id_hash = proof::hash64("BACKEND", config.backend_id.as_bytes());
-    let profile_id_hash = proof::hash64("PROFILE", config.profile_id.as_bytes());
-    let pubio_hash = proof::hash64("PUBIO", public_inputs_json.as_bytes());
-
-    // Body = fake trace root as 8 bytes, using user-selected hash
-    let root = fake_trace_root_u64(&air, public_inputs_json, &config.hash)?;
-    let body = root.to_le_bytes();
-
-    let header = proof::ProofHeader {
-        backend_id_hash,
-        profile_id_hash,
-        pubio_hash,
-        body_len: body.len() as u64,
-    };
-    Ok(proof::assemble_proof(&header, &body))
+    ensure_native_registered();
+    let ir = parse_air_file(Path::new(air_path))?;
+    let inputs = PublicInputs::from_json(public_inputs_json)?;
+    Ok(zkprov_corelib::prove(config, &ir, &inputs)?.to_bytes())
+}
+
+/// Prove from an externally generated trace (see `zkprov_corelib::trace::Trace`).
+/// The trace must match the AIR field and column count; its commitment is
+/// appended to the body so verification does not need the trace itself.
+pub fn native_prove_with_trace(
+    config: &Config,
+    public_inputs_json: &str,
+    air_path: &str,
+    trace: &Trace,
+) -> anyhow::Result<Vec<u8>> {
+    ensure_native_registered();
+    let ir = parse_air_file(Path::new(air_path))?;
+    let inputs = PublicInputs::from_json(public_inputs_json)?;
+    Ok(zkprov_corelib::prove_with_trace(config, &ir, &inputs, trace)?.to_bytes())
 }
 
-/// Verify: recompute fake root and compare bytes.
+/// Load `air_path` and verify `proof_bytes` through the registry.
 pub fn native_verify(
     config: &Config,
     public_inputs_json: &str,
     air_path: &str,
     proof_bytes: &[u8],
 ) -> anyhow::Result<bool> {
-    zkprov_corelib::registry::ensure_builtins_registered();
-    validate_config(config)?;
+    ensure_native_registered();
+    let ir = parse_air_file(Path::new(air_path))?;
+    let inputs = PublicInputs::from_json(public_inputs_json)?;
+    let proof = ProofView::from_bytes(proof_bytes)?;
+    zkprov_corelib::verify(config, &ir, &inputs, &proof)?;
+    Ok(true)
+}
 
-    let air = AirProgram::load_from_file(air_path)?;
-    validate_air_against_backend(&air, &config.backend_id)?;
+/// Reference interpreter: reject the trace at the first transition
+/// constraint that does not hold (see `zkprov_corelib::trace::eval`).
+fn check_transitions(trace: &Trace, ir: &AirIr) -> Result<(), ProveError> {
+    let first = eval::violations(trace, ir, Some(1))
+        .map_err(|e| ProveError::InvalidInput(format!("{e:#}")))?;
+    match first.into_iter().next() {
+        Some(v) => Err(ProveError::Unsatisfied {
+            constraint: v.constraint,
+            row: v.row,
+            columns: v.columns(),
+            display: v.display,
+        }),
+        None => Ok(()),
+    }
+}
 
-    if proof_bytes.len() < 40 {
-        anyhow::bail!("proof too short");
+/// Reject the trace at the first `[[boundary]]` assertion it violates.
+fn check_boundaries(trace: &Trace, ir: &AirIr, inputs: &PublicInputs) -> Result<(), ProveError> {
+    let first = eval::boundary_violations(trace, ir, &inputs.value())
+        .map_err(|e| ProveError::InvalidInput(format!("{e:#}")))?;
+    match first.into_iter().next() {
+        Some(v) => Err(ProveError::BoundaryUnsatisfied {
+            display: v.display,
+            row: v.row,
+            expected: v.expected.to_string(),
+            actual: v.actual.to_string(),
+        }),
+        None => Ok(()),
     }
-    let header = proof::ProofHeader::decode(&proof_bytes[0..40])?;
-    let body = &proof_bytes[40..];
+}
 
-    if body.len() as u64 != header.body_len {
-        anyhow::bail!("body length mismatch");
+fn header_for(
+    backend_id: &str,
+    profile: &Profile,
+    ctx: &CallContext,
+    inputs: &PublicInputs,
+) -> proof::ProofHeader {
+    proof::ProofHeader {
+        backend_id_hash: proof::hash64("BACKEND", backend_id.as_bytes()),
+        profile_id_hash: proof::hash64("PROFILE", profile.id.as_bytes()),
+        pubio_hash: proof::pubio::pubio_hash(inputs),
+        body_len: 0,
+        config_hash: ctx.config_hash,
+        flags: 0,
     }
+}
 
-    // Check header bindings
-    let expect_backend = proof::hash64("BACKEND", config.backend_id.as_bytes());
-    if expect_backend != header.backend_id_hash {
-        anyhow::bail!("backend id hash mismatch");
+fn prove_inner(
+    backend_id: &str,
+    ir: &AirIr,
+    profile: &Profile,
+    ctx: &CallContext,
+    inputs: &PublicInputs,
+    trace: Option<&Trace>,
+    cache: Option<&mut TraceCache>,
+) -> Result<(Proof, Reuse), ProveError> {
+    let hash = selected_hash(ir, ctx);
+    let mut phases = Phases::new(ctx.deadline);
+    // Public inputs referenced by boundary constraints must be present.
+    if !ir.boundary.is_empty() {
+        let values = inputs.value();
+        eval::field_modulus(&ir.meta.field)
+            .and_then(|p| {
+                ir.boundary_assertions(TraceShape::from_ir(ir).rows)?
+                    .iter()
+                    .try_for_each(|a| a.resolve(&values, &p).map(drop))
+            })
+            .map_err(|e| ProveError::InvalidInput(format!("{e:#}")))?;
     }
-    let expect_profile = proof::hash64("PROFILE", config.profile_id.as_bytes());
-    if expect_profile != header.profile_id_hash {
-        anyhow::bail!("profile id hash mismatch");
+
+    // Body = fake trace root as 8 bytes, using user-selected hash,
+    // followed by the 32-byte trace commitment when proving from a trace
+    let mut reuse = Reuse::default();
+    let commitment = match trace {
+        Some(t) => {
+            t.check_shape(ir)
+                .map_err(|e| ProveError::InvalidInput(format!("{e:#}")))?;
+            check_transitions(t, ir)?;
+            check_boundaries(t, ir, inputs)?;
+            phases.finish("trace")?;
+            let commitment = match cache {
+                Some(cache) => {
+                    let (commitment, r) = cache.commit(t, hash)?;
+                    reuse = r;
+                    commitment
+                }
+                None => {
+                    reuse = Reuse::none(t.cols());
+                    t.commitment(hash)?
+                }
+            };
+            // The cache already holds the new commitments, so a caller that
+            // saves it after a timeout resumes from here.
+            phases.finish("commit")?;
+            Some(commitment)
+        }
+        None => None,
+    };
+    let root = fake_trace_root_u64(
+        ctx.config_hash,
+        ir,
+        inputs,
+        hash,
+        commitment.as_ref().map(|c| c.as_slice()),
+    )?;
+    let mut body = root.to_le_bytes().to_vec();
+    if let Some(c) = commitment {
+        body.extend_from_slice(&c);
     }
-    let expect_pubio = proof::hash64("PUBIO", public_inputs_json.as_bytes());
-    if expect_pubio != header.pubio_hash {
-        anyhow::bail!("public io hash mismatch");
+
+    Ok((
+        Proof::new(header_for(backend_id, profile, ctx, inputs), body),
+        reuse,
+    ))
+}
+
+/// Verify: check header bindings, then recompute the fake root.
+fn verify_inner(
+    backend_id: &str,
+    ir: &AirIr,
+    profile: &Profile,
+    ctx: &CallContext,
+    inputs: &PublicInputs,
+    proof: &ProofView<'_>,
+) -> Result<(), VerifyError> {
+    if proof.body.len() as u64 != proof.header.body_len {
+        return Err(VerifyError::BodyLength {
+            expected: proof.header.body_len,
+            actual: proof.body.len() as u64,
+        });
     }
 
+    proof
+        .header
+        .check_bindings(&header_for(backend_id, profile, ctx, inputs))?;
+
     // Check fake root derived from selected hash
-    let expect_root = fake_trace_root_u64(&air, public_inputs_json, &config.hash)?.to_le_bytes();
-    if body != expect_root {
-        anyhow::bail!("fake trace root mismatch");
+    let body = proof.body;
+    let (root, commitment) = match body.len() {
+        8 => (body, None),
+        40 => (&body[..8], Some(&body[8..])),
+        n => {
+            return Err(VerifyError::Rejected(format!(
+                "unexpected proof body length {} (native bodies are 8 or 40 bytes)",
+                n
+            )))
+        }
+    };
+    let expect_root = fake_trace_root_u64(
+        ctx.config_hash,
+        ir,
+        inputs,
+        selected_hash(ir, ctx),
+        commitment,
+    )?;
+    let root = u64::from_le_bytes(root.try_into().expect("8-byte root"));
+    if root != expect_root {
+        return Err(VerifyError::RootMismatch {
+            what: "fake trace root".to_string(),
+            expected: format!("0x{:016x}", expect_root),
+            actual: format!("0x{:016x}", root),
+        });
     }
-    Ok(true)
+    Ok(())
 }
@@ -1,5 +1,6 @@
 use zkprov_backend_native::{native_prove, native_verify};
 use zkprov_corelib::config::Config;
+use zkprov_corelib::proof::HEADER_LEN;
 
 const AIR_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../examples/air/toy.air");
 
@@ -12,7 +13,7 @@ fn blake3_and_keccak_yield_different_bodies() {
     let proof_b3 = native_prove(&cfg_b3, inputs, AIR_PATH).unwrap();
     let proof_kc = native_prove(&cfg_kc, inputs, AIR_PATH).unwrap();
 
-    assert_ne!(&proof_b3[40..], &proof_kc[40..]);
+    assert_ne!(&proof_b3[HEADER_LEN..], &proof_kc[HEADER_LEN..]);
 
     assert!(native_verify(&cfg_b3, inputs, AIR_PATH, &proof_b3).unwrap());
     assert!(native_verify(&cfg_kc, inputs, AIR_PATH, &proof_kc).unwrap());
@@ -30,3 +31,21 @@ fn poseidon2_and_rescue_placeholders_work() {
         assert!(native_verify(&cfg, inputs, AIR_PATH, &proof).unwrap());
     }
 }
+
+#[test]
+fn keccak_air_hash_and_alias_match_keccak256() {
+    let air = std::fs::read_to_string(AIR_PATH)
+        .unwrap()
+        .replace("hash = \"blake3\"", "hash = \"keccak\"");
+    let tmp = tempfile::tempdir().unwrap();
+    let keccak_air = tmp.path().join("toy_keccak.air");
+    std::fs::write(&keccak_air, air).unwrap();
+    let keccak_air = keccak_air.to_str().unwrap();
+    let inputs = r#"{"x":1}"#;
+
+    let canonical = Config::new("native@0.0", "Prime254", "keccak256", 2, false, "balanced");
+    let alias = Config::new("native@0.0", "Prime254", "keccak", 2, false, "balanced");
+    let proof = native_prove(&alias, inputs, keccak_air).unwrap();
+    assert_eq!(proof, native_prove(&canonical, inputs, AIR_PATH).unwrap());
+    assert!(native_verify(&canonical, inputs, keccak_air, &proof).unwrap());
+}
@@ -1,6 +1,9 @@
 use zkprov_backend_native::{native_prove, native_verify};
+use zkprov_corelib::air::parser::parse_air_file;
 use zkprov_corelib::config::Config;
-use zkprov_corelib::proof::{hash64, ProofHeader};
+use zkprov_corelib::evm::pubio::{encode_typed, PubIoEncoding};
+use zkprov_corelib::profile::parse_overrides;
+use zkprov_corelib::proof::{hash64, ProofHeader, HEADER_LEN};
 
 const AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../examples/air/toy.air");
 
@@ -12,7 +15,7 @@ fn prove_verify_with_air() {
     assert!(native_verify(&cfg, inputs, AIR, &proof).expect("verify"));
 
     // Header sanity
-    let hdr = ProofHeader::decode(&proof[0..40]).unwrap();
+    let hdr = ProofHeader::decode(&proof[..HEADER_LEN]).unwrap();
     assert_eq!(hdr.backend_id_hash, hash64("BACKEND", b"native@0.0"));
     assert_eq!(hdr.profile_id_hash, hash64("PROFILE", b"balanced"));
 }
@@ -48,3 +51,92 @@ boundary_count = 2
     let ok = native_verify(&cfg, inputs, tmp.path().to_str().unwrap(), &proof);
     assert!(ok.is_err(), "verify must fail when AIR changes");
 }
+
+#[test]
+fn verify_fails_when_periodic_column_data_differs() {
+    let src = std::fs::read_to_string(AIR).unwrap();
+    let write = |periodic: &str| {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let air = src.replace(
+            "periodic_cols = 1\n",
+            &format!("periodic_cols = 1\nconstants = [3]\nperiodic = [{periodic}]\n"),
+        );
+        std::fs::write(tmp.path(), air).unwrap();
+        tmp
+    };
+    let declared = write("[1, 0, 0, 0]");
+    let substituted = write("[0, 0, 0, 1]");
+
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"a":1}"#;
+    let proof = native_prove(&cfg, inputs, declared.path().to_str().unwrap()).unwrap();
+    assert!(native_verify(&cfg, inputs, declared.path().to_str().unwrap(), &proof).unwrap());
+    assert!(native_verify(&cfg, inputs, substituted.path().to_str().unwrap(), &proof).is_err());
+    // Declaring the data changes the proof versus leaving it implicit.
+    assert!(native_verify(&cfg, inputs, AIR, &proof).is_err());
+}
+
+#[test]
+fn typed_pubio_binds_declared_inputs_not_json_text() {
+    const DEMO: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../../examples/air/commit_demo.air"
+    );
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced")
+        .with_pubio_encoding(PubIoEncoding::Typed);
+    let inputs = r#"{"x":1,"y":"2","acc":"0xbeef","digest":7}"#;
+    let reformatted = r#"{ "digest": 7, "acc": "beef", "y": 2, "x": "0x1" }"#;
+
+    let proof = native_prove(&cfg, inputs, DEMO).expect("prove");
+    let hdr = ProofHeader::decode(&proof[..HEADER_LEN]).unwrap();
+    let ir = parse_air_file(DEMO.as_ref()).unwrap();
+    let value: serde_json::Value = serde_json::from_str(inputs).unwrap();
+    assert_eq!(
+        hdr.pubio_hash,
+        hash64("PUBIO", &encode_typed(&ir, &value).unwrap())
+    );
+    assert!(native_verify(&cfg, reformatted, DEMO, &proof).expect("verify"));
+
+    // Different values, or the JSON binding, do not verify.
+    let other = r#"{"x":1,"y":2,"acc":"0xbeef","digest":8}"#;
+    assert!(native_verify(&cfg, other, DEMO, &proof).is_err());
+    let json_cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    assert!(native_verify(&json_cfg, inputs, DEMO, &proof).is_err());
+
+    // Undeclared inputs cannot be bound by the typed encoding.
+    let extra = r#"{"x":1,"y":2,"acc":"0x","digest":7,"z":0}"#;
+    assert!(native_prove(&cfg, extra, DEMO).is_err());
+}
+
+#[test]
+fn header_binds_every_config_parameter() {
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"a":1}"#;
+    let proof = native_prove(&cfg, inputs, AIR).unwrap();
+    let hdr = ProofHeader::decode(&proof[..HEADER_LEN]).unwrap();
+    assert_eq!(hdr.config_hash, cfg.config_hash());
+    assert!(native_verify(&cfg, inputs, AIR, &proof).unwrap());
+
+    // Same backend and profile ids, different FRI arity or profile parameters.
+    for other in [
+        Config::new("native@0.0", "Prime254", "blake3", 4, false, "balanced"),
+        cfg.clone()
+            .with_profile_overrides(parse_overrides(&["fri_queries=40"]).unwrap()),
+    ] {
+        let err = native_verify(&other, inputs, AIR, &proof).unwrap_err();
+        assert!(err.to_string().contains("config_hash mismatch"), "{err}");
+    }
+
+    // Rewriting the header's config_hash does not move the proof to another
+    // config: the body root binds the config it was proved under.
+    for other in [
+        Config::new("native@0.0", "Prime254", "blake3", 4, false, "balanced"),
+        cfg.clone()
+            .with_profile_overrides(parse_overrides(&["fri_queries=40"]).unwrap()),
+    ] {
+        let mut forged = proof.clone();
+        forged[48..56].copy_from_slice(&other.config_hash().to_le_bytes());
+        let err = native_verify(&other, inputs, AIR, &forged).unwrap_err();
+        assert!(err.to_string().contains("fake trace root"), "{err}");
+    }
+}
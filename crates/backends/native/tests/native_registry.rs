@@ -0,0 +1,31 @@
+use zkprov_backend_native::register_native_backend;
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::{ProveError, RegistryError};
+use zkprov_corelib::registry::ensure_builtins_registered;
+
+const AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../examples/air/toy.air");
+
+#[test]
+fn native_adapter_replaces_builtin_placeholder() {
+    ensure_builtins_registered();
+    let ir = parse_air_file(AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json(r#"{"a":1}"#).unwrap();
+    let cfg = Config::new("native@latest", "Prime254", "blake3", 2, false, "balanced");
+
+    assert!(matches!(
+        zkprov_corelib::prove(&cfg, &ir, &inputs),
+        Err(ProveError::Unsupported { .. })
+    ));
+
+    register_native_backend().expect("replaces placeholder");
+    assert!(matches!(
+        register_native_backend(),
+        Err(RegistryError::DuplicateBackend(_))
+    ));
+
+    let proof = zkprov_corelib::prove(&cfg, &ir, &inputs).expect("prove");
+    assert_eq!(proof.body.len(), 8);
+    zkprov_corelib::verify(&cfg, &ir, &inputs, &proof.view()).expect("verify");
+}
@@ -0,0 +1,254 @@
+use zkprov_backend_native::{
+    ensure_native_registered, native_prove, native_prove_with_trace, native_verify,
+};
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::ProveError;
+use zkprov_corelib::trace::{Trace, TraceCache, TraceCompression};
+
+const AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../examples/air/toy.air");
+
+fn toy_trace(seed: u64) -> Trace {
+    let columns: Vec<Vec<u64>> = (0..4)
+        .map(|c| (0..8).map(|r| seed + c * 8 + r).collect())
+        .collect();
+    Trace::from_u64_columns("Prime254", &columns).unwrap()
+}
+
+#[test]
+fn prove_from_trace_verifies_and_binds_trace() {
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"a":1}"#;
+
+    // Round-trip through the binary format, as an external generator would.
+    let bytes = toy_trace(0).encode(TraceCompression::Rle).unwrap();
+    let trace = Trace::decode(&bytes).unwrap();
+
+    let proof = native_prove_with_trace(&cfg, inputs, AIR, &trace).expect("prove");
+    assert!(native_verify(&cfg, inputs, AIR, &proof).expect("verify"));
+
+    let other = native_prove_with_trace(&cfg, inputs, AIR, &toy_trace(1)).unwrap();
+    assert_ne!(proof, other, "different traces must yield different proofs");
+
+    let plain = native_prove(&cfg, inputs, AIR).unwrap();
+    assert_ne!(proof.len(), plain.len());
+
+    // Tampering with the embedded trace commitment breaks verification.
+    let mut tampered = proof.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(native_verify(&cfg, inputs, AIR, &tampered).is_err());
+}
+
+#[test]
+fn incremental_prove_reuses_unchanged_columns() {
+    ensure_native_registered();
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let ir = parse_air_file(AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json(r#"{"a":1}"#).unwrap();
+    let mut cache = TraceCache::new();
+
+    let first = toy_trace(0);
+    let (_, reuse) = zkprov_corelib::prove_incremental(&cfg, &ir, &inputs, &first, &mut cache)
+        .expect("first prove");
+    assert_eq!(reuse.reused, 0);
+
+    // Only column 2 changes; the proof must match a full prove exactly.
+    let mut second = first.clone();
+    second.columns[2][0] ^= 1;
+    let (proof, reuse) =
+        zkprov_corelib::prove_incremental(&cfg, &ir, &inputs, &second, &mut cache).unwrap();
+    assert_eq!((reuse.columns, reuse.reused), (4, 3));
+    assert_eq!(reuse.percent(), 75.0);
+    let full = native_prove_with_trace(&cfg, r#"{"a":1}"#, AIR, &second).unwrap();
+    assert_eq!(proof.to_bytes(), full);
+    assert!(native_verify(&cfg, r#"{"a":1}"#, AIR, &full).unwrap());
+}
+
+#[test]
+fn prove_from_trace_rejects_shape_mismatch() {
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"a":1}"#;
+
+    let narrow = Trace::from_u64_columns("Prime254", &[vec![1; 8], vec![2; 8]]).unwrap();
+    let err = native_prove_with_trace(&cfg, inputs, AIR, &narrow).unwrap_err();
+    assert!(err.to_string().contains("columns"), "{err}");
+
+    let odd_rows = Trace::from_u64_columns("Prime254", &vec![vec![0; 6]; 4]).unwrap();
+    assert!(native_prove_with_trace(&cfg, inputs, AIR, &odd_rows).is_err());
+
+    let wrong_field = Trace::from_u64_columns("Goldilocks", &vec![vec![0; 8]; 4]).unwrap();
+    assert!(native_prove_with_trace(&cfg, inputs, AIR, &wrong_field).is_err());
+}
+
+#[test]
+fn selector_columns_must_be_boolean() {
+    const SELECTOR_AIR: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../../examples/air/selector_counter.air"
+    );
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"a":1}"#;
+
+    let ok = Trace::from_u64_columns(
+        "Prime254",
+        &[vec![1, 2, 4, 5], vec![1, 0, 1, 0], vec![0, 1, 0, 1]],
+    )
+    .unwrap();
+    let proof = native_prove_with_trace(&cfg, inputs, SELECTOR_AIR, &ok).expect("prove");
+    assert!(native_verify(&cfg, inputs, SELECTOR_AIR, &proof).unwrap());
+
+    let bad = Trace::from_u64_columns(
+        "Prime254",
+        &[vec![1, 2, 4, 5], vec![1, 0, 2, 0], vec![0, 1, 0, 1]],
+    )
+    .unwrap();
+    let err = native_prove_with_trace(&cfg, inputs, SELECTOR_AIR, &bad).unwrap_err();
+    assert!(err.to_string().contains("s_inc"), "{err}");
+}
+
+#[test]
+fn unsatisfied_transition_reports_constraint_and_row() {
+    const SELECTOR_AIR: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../../examples/air/selector_counter.air"
+    );
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"a":1}"#;
+
+    // Row 1 doubles 2 into 5 instead of 4.
+    let bad = Trace::from_u64_columns(
+        "Prime254",
+        &[vec![1, 2, 5, 6], vec![1, 0, 1, 0], vec![0, 1, 0, 1]],
+    )
+    .unwrap();
+    let err = native_prove_with_trace(&cfg, inputs, SELECTOR_AIR, &bad).unwrap_err();
+    match err.downcast_ref::<ProveError>() {
+        Some(ProveError::Unsatisfied {
+            constraint,
+            row,
+            display,
+            columns,
+        }) => {
+            assert_eq!((*constraint, *row), (1, 1));
+            assert_eq!(display, "(cur.s_dbl * (next.a - (cur.a * 2)))");
+            assert_eq!(columns, &["s_dbl", "a"]);
+        }
+        other => panic!("expected Unsatisfied, got {other:?} ({err})"),
+    }
+    assert!(err.to_string().contains("#1"), "{err}");
+    assert!(err.to_string().contains("row 1"), "{err}");
+    assert!(
+        err.to_string().ends_with("on columns `s_dbl`, `a`"),
+        "{err}"
+    );
+
+    // Field arithmetic wraps: decrementing below zero is still checked mod p.
+    let tmp = tempfile::tempdir().unwrap();
+    let air = tmp.path().join("dec.air");
+    std::fs::write(
+        &air,
+        r#"[meta]
+name = "dec"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 1
+names = ["a"]
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+transitions = ["next.a + 1 = cur.a"]
+"#,
+    )
+    .unwrap();
+    let air = air.to_str().unwrap();
+    let p_minus_1 = zkprov_corelib::crypto::field::prime254_modulus() - 1u32;
+    let wraps = Trace::from_biguint_columns(
+        "Prime254",
+        &[vec![
+            1u32.into(),
+            0u32.into(),
+            p_minus_1.clone(),
+            p_minus_1 - 1u32,
+        ]],
+    )
+    .unwrap();
+    native_prove_with_trace(&cfg, inputs, air, &wraps).expect("wrapping trace satisfies");
+}
+
+#[test]
+fn boundary_constraints_are_checked_and_bound_into_proofs() {
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let src = r#"rows_hint = 8
+
+[meta]
+name = "running_sum"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 1
+names = ["acc"]
+
+[constraints]
+transition_count = 1
+boundary_count = 2
+transitions = ["next.acc = cur.acc + 2"]
+
+[[public_inputs]]
+name = "total"
+type = "u64"
+
+[[boundary]]
+column = "acc"
+row = "first"
+value = 1
+
+[[boundary]]
+column = "acc"
+row = "last"
+public = "total"
+"#;
+    let tmp = tempfile::tempdir().unwrap();
+    let air = tmp.path().join("running_sum.air");
+    std::fs::write(&air, src).unwrap();
+    let air = air.to_str().unwrap();
+    let trace = |start: u64| {
+        Trace::from_u64_columns("Prime254", &[(0..8).map(|r| start + 2 * r).collect()]).unwrap()
+    };
+
+    let proof = native_prove_with_trace(&cfg, r#"{"total":15}"#, air, &trace(1)).expect("prove");
+    assert!(native_verify(&cfg, r#"{"total":15}"#, air, &proof).unwrap());
+
+    // The transitions hold from any start, but acc[first] must be 1.
+    let err = native_prove_with_trace(&cfg, r#"{"total":17}"#, air, &trace(3)).unwrap_err();
+    match err.downcast_ref::<ProveError>() {
+        Some(ProveError::BoundaryUnsatisfied {
+            display,
+            row,
+            expected,
+            actual,
+        }) => {
+            assert_eq!(display, "acc[first] = 1");
+            assert_eq!((*row, expected.as_str(), actual.as_str()), (0, "1", "3"));
+        }
+        other => panic!("expected BoundaryUnsatisfied, got {other:?} ({err})"),
+    }
+    let err = native_prove_with_trace(&cfg, r#"{"total":16}"#, air, &trace(1)).unwrap_err();
+    assert!(
+        err.to_string().contains("acc[last] = public.total"),
+        "{err}"
+    );
+    let err = native_prove(&cfg, r#"{"sum":15}"#, air).unwrap_err();
+    assert!(err.to_string().contains("'total' is missing"), "{err}");
+
+    // The declared boundary is part of what the proof commits to.
+    let plain = native_prove(&cfg, r#"{"total":15}"#, air).unwrap();
+    let moved = tmp.path().join("moved.air");
+    std::fs::write(&moved, src.replace("value = 1", "value = 3")).unwrap();
+    assert!(native_verify(&cfg, r#"{"total":15}"#, moved.to_str().unwrap(), &plain).is_err());
+}
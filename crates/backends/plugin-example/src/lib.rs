@@ -0,0 +1,143 @@
+//! `example@0.1`: the smallest backend `ZKD_BACKEND_PATH` can load, written
+//! against the C ABI in `include/zkd_backend.h` (see
+//! `zkprov_corelib::plugin`) without linking corelib.
+//!
+//! It proves nothing: the body is a BLAKE3 digest of the program name,
+//! profile id and canonical inputs, which `verify` recomputes. Build it with
+//! `cargo build -p zkd-backend-plugin-example`, copy
+//! `libzkd_backend_plugin_example.so` (`.dylib`, `.dll`) into a directory of
+//! its own and point `ZKD_BACKEND_PATH` at that directory.
+
+use std::ffi::{c_char, CStr};
+
+use serde_json::Value;
+
+const ABI_VERSION: u32 = 1;
+
+const OK: i32 = 0;
+const ERR_INVALID_INPUT: i32 = 1;
+const ERR_REJECTED: i32 = 3;
+const ERR_ABI: i32 = 5;
+
+const ID: &CStr = c"example@0.1";
+const CAPABILITIES: &CStr = cr#"{
+    "fields": ["Prime254"],
+    "hashes": ["blake3"],
+    "fri_arities": [2],
+    "recursion": "none",
+    "lookups": false,
+    "curves": ["placeholder"],
+    "pedersen": true
+}"#;
+
+/// `zkd_buf`.
+#[repr(C)]
+pub struct Buf {
+    pub ptr: *mut u8,
+    pub len: u64,
+}
+
+/// `zkd_backend_v1`.
+#[repr(C)]
+pub struct BackendV1 {
+    pub abi_version: u32,
+    pub id: *const c_char,
+    pub capabilities_json: *const c_char,
+    pub prove: unsafe extern "C" fn(*const u8, u64, *mut Buf) -> i32,
+    pub verify: unsafe extern "C" fn(*const u8, u64, *const u8, u64, *mut Buf) -> i32,
+    pub free_buf: unsafe extern "C" fn(Buf),
+}
+
+struct Table(BackendV1);
+
+// SAFETY: the table is immutable and points at static data only.
+unsafe impl Sync for Table {}
+
+static TABLE: Table = Table(BackendV1 {
+    abi_version: ABI_VERSION,
+    id: ID.as_ptr(),
+    capabilities_json: CAPABILITIES.as_ptr(),
+    prove,
+    verify,
+    free_buf,
+});
+
+/// Answer the host's ABI range with version 1, the only one spoken here.
+///
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn zkd_register_backend(
+    host_min: u32,
+    host_max: u32,
+    out: *mut *const BackendV1,
+) -> i32 {
+    if out.is_null() {
+        return ERR_INVALID_INPUT;
+    }
+    if !(host_min..=host_max).contains(&ABI_VERSION) {
+        return ERR_ABI;
+    }
+    *out = &TABLE.0;
+    OK
+}
+
+unsafe extern "C" fn prove(request: *const u8, request_len: u64, out: *mut Buf) -> i32 {
+    let request = std::slice::from_raw_parts(request, request_len as usize);
+    match digest(request) {
+        Ok(body) => respond(out, OK, body.to_vec()),
+        Err(e) => respond(out, ERR_INVALID_INPUT, e.into_bytes()),
+    }
+}
+
+unsafe extern "C" fn verify(
+    request: *const u8,
+    request_len: u64,
+    body: *const u8,
+    body_len: u64,
+    out: *mut Buf,
+) -> i32 {
+    let request = std::slice::from_raw_parts(request, request_len as usize);
+    let body = std::slice::from_raw_parts(body, body_len as usize);
+    match digest(request) {
+        Ok(expected) if expected.as_slice() == body => OK,
+        Ok(_) => respond(out, ERR_REJECTED, b"digest mismatch".to_vec()),
+        Err(e) => respond(out, ERR_INVALID_INPUT, e.into_bytes()),
+    }
+}
+
+unsafe extern "C" fn free_buf(buf: Buf) {
+    if !buf.ptr.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buf.ptr,
+            buf.len as usize,
+        )));
+    }
+}
+
+unsafe fn respond(out: *mut Buf, status: i32, bytes: Vec<u8>) -> i32 {
+    let bytes = Box::into_raw(bytes.into_boxed_slice());
+    *out = Buf {
+        ptr: bytes as *mut u8,
+        len: bytes.len() as u64,
+    };
+    status
+}
+
+fn digest(request: &[u8]) -> Result<[u8; 32], String> {
+    let request: Value =
+        serde_json::from_slice(request).map_err(|e| format!("bad request: {e}"))?;
+    let program = request["air"]["meta"]["name"].as_str().unwrap_or("");
+    let profile = request["profile"]["id"].as_str().unwrap_or("");
+    if !request["inputs"].is_object() {
+        return Err("inputs must be a JSON object".into());
+    }
+    let mut h = blake3::Hasher::new();
+    h.update(b"ZKD.PLUGIN.EXAMPLE");
+    for part in [program, profile, &request["inputs"].to_string()] {
+        h.update(&(part.len() as u64).to_le_bytes());
+        h.update(part.as_bytes());
+    }
+    Ok(*h.finalize().as_bytes())
+}
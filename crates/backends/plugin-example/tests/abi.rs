@@ -0,0 +1,104 @@
+use zkd_backend_plugin_example::zkd_register_backend;
+use zkprov_corelib::errors::PluginError;
+use zkprov_corelib::plugin::{self, BackendV1, Buf, RegisterFn, ABI_VERSION, MIN_ABI_VERSION};
+
+unsafe extern "C" fn too_new(min: u32, max: u32, out: *mut *const BackendV1) -> i32 {
+    assert_eq!((min, max), (MIN_ABI_VERSION, ABI_VERSION));
+    let _ = out;
+    plugin::ERR_ABI
+}
+
+unsafe extern "C" fn no_prove(_: *const u8, _: u64, _: *mut Buf) -> i32 {
+    plugin::ERR_INTERNAL
+}
+unsafe extern "C" fn no_verify(_: *const u8, _: u64, _: *const u8, _: u64, _: *mut Buf) -> i32 {
+    plugin::ERR_INTERNAL
+}
+unsafe extern "C" fn no_free(_: Buf) {}
+
+struct Table(BackendV1);
+unsafe impl Sync for Table {}
+
+static FUTURE: Table = Table(BackendV1 {
+    abi_version: 9,
+    id: c"future@1.0".as_ptr(),
+    capabilities_json: c"{}".as_ptr(),
+    prove: no_prove,
+    verify: no_verify,
+    free_buf: no_free,
+});
+
+static BAD_CAPS: Table = Table(BackendV1 {
+    abi_version: 1,
+    id: c"badcaps@1.0".as_ptr(),
+    capabilities_json: cr#"{"fields":["Prime254"],"hashes":["md5"],"fri_arities":[2],
+        "recursion":"none","lookups":false,"curves":[],"pedersen":false}"#
+        .as_ptr(),
+    prove: no_prove,
+    verify: no_verify,
+    free_buf: no_free,
+});
+
+static BAD_ID: Table = Table(BackendV1 {
+    abi_version: 1,
+    id: c"noversion".as_ptr(),
+    capabilities_json: c"{}".as_ptr(),
+    prove: no_prove,
+    verify: no_verify,
+    free_buf: no_free,
+});
+
+unsafe extern "C" fn answers_future(_: u32, _: u32, out: *mut *const BackendV1) -> i32 {
+    *out = &FUTURE.0;
+    plugin::OK
+}
+unsafe extern "C" fn answers_bad_caps(_: u32, _: u32, out: *mut *const BackendV1) -> i32 {
+    *out = &BAD_CAPS.0;
+    plugin::OK
+}
+unsafe extern "C" fn answers_bad_id(_: u32, _: u32, out: *mut *const BackendV1) -> i32 {
+    *out = &BAD_ID.0;
+    plugin::OK
+}
+
+fn register(entry: RegisterFn) -> Result<&'static str, PluginError> {
+    unsafe { plugin::register_entry(entry) }
+}
+
+#[test]
+fn abi_versions_are_negotiated() {
+    assert!(matches!(
+        register(too_new),
+        Err(PluginError::AbiMismatch { plugin: None, .. })
+    ));
+    let err = register(answers_future).unwrap_err();
+    assert!(matches!(
+        err,
+        PluginError::AbiMismatch {
+            plugin: Some(9),
+            ..
+        }
+    ));
+    assert!(err.to_string().ends_with("plugin answered 9)"), "{err}");
+
+    // The example answers a range that includes version 1 and refuses others.
+    let mut table = std::ptr::null();
+    unsafe {
+        assert_eq!(zkd_register_backend(2, 3, &mut table), plugin::ERR_ABI);
+        assert!(table.is_null());
+        assert_eq!(zkd_register_backend(1, 3, &mut table), plugin::OK);
+        assert_eq!((*table).abi_version, 1);
+    }
+}
+
+#[test]
+fn invalid_backends_are_refused() {
+    let err = register(answers_bad_caps).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unknown or non-canonical hash 'md5'"),
+        "{err}"
+    );
+    let err = register(answers_bad_id).unwrap_err();
+    assert!(err.to_string().contains("not name@version"), "{err}");
+}
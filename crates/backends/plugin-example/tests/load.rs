@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::PathBuf;
+
+use zkprov_corelib::air::parse_air_file;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::{PluginError, RegistryError, VerifyError};
+use zkprov_corelib::plugin;
+use zkprov_corelib::registry::{ensure_builtins_registered, snapshot};
+
+const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../examples/air/toy.air");
+
+/// The cdylib cargo built next to this test binary (in `deps/`, and uplifted
+/// one level up).
+fn built_plugin() -> PathBuf {
+    let name = format!(
+        "{}zkd_backend_plugin_example.{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_EXTENSION
+    );
+    let exe = std::env::current_exe().unwrap();
+    let deps = exe.parent().unwrap();
+    [deps.join(&name), deps.parent().unwrap().join(&name)]
+        .into_iter()
+        .find(|p| p.exists())
+        .expect("plugin cdylib is built with the tests")
+}
+
+#[test]
+fn plugins_load_from_backend_path_and_prove() {
+    let dir = tempfile::tempdir().unwrap();
+    let lib = dir.path().join(built_plugin().file_name().unwrap());
+    fs::copy(built_plugin(), &lib).unwrap();
+    let junk = dir
+        .path()
+        .join(format!("junk.{}", std::env::consts::DLL_EXTENSION));
+    fs::write(&junk, b"not a library").unwrap();
+    std::env::set_var(plugin::PATH_VAR, dir.path());
+    // Registering the builtins never loads plugins; only the explicit call does.
+    ensure_builtins_registered();
+    assert!(!snapshot()
+        .backends
+        .iter()
+        .any(|b| b.info.id == "example@0.1"));
+    assert_eq!(plugin::load_from_env(), ["example@0.1"]);
+    assert!(plugin::load_from_env().is_empty(), "loads only once");
+
+    let snap = snapshot();
+    let entry = snap
+        .backends
+        .iter()
+        .find(|b| b.info.id == "example@0.1")
+        .expect("plugin registered");
+    let info = entry.plugin.as_ref().expect("plugin origin");
+    assert_eq!(info.abi_version, plugin::ABI_VERSION);
+    assert_eq!(info.path.as_deref(), Some(lib.to_str().unwrap()));
+    assert_eq!(entry.capabilities.hashes, ["blake3"]);
+    assert_eq!(snap.plugin_errors.len(), 1, "{:?}", snap.plugin_errors);
+    assert_eq!(snap.plugin_errors[0].path, junk.display().to_string());
+    assert!(snap.plugin_errors[0].error.starts_with("cannot load"));
+
+    let config = Config::new("example@0.1", "Prime254", "blake3", 2, false, "balanced");
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json(r#"{"b": 2, "a": 1}"#).unwrap();
+    let proof = zkprov_corelib::prove(&config, &ir, &inputs).unwrap();
+    assert_eq!(proof.body.len(), 32);
+    zkprov_corelib::verify(&config, &ir, &inputs, &proof.view()).unwrap();
+    // Same inputs, other spelling: the plugin sees the canonical form.
+    let respelled = PublicInputs::from_json(r#"{"a":1.0,"b":2}"#).unwrap();
+    zkprov_corelib::verify(&config, &ir, &respelled, &proof.view()).unwrap();
+
+    let mut tampered = proof.clone();
+    tampered.body[0] ^= 1;
+    assert!(matches!(
+        zkprov_corelib::verify(&config, &ir, &inputs, &tampered.view()),
+        Err(VerifyError::Rejected(m)) if m == "digest mismatch"
+    ));
+    let other = PublicInputs::from_json(r#"{"a":2}"#).unwrap();
+    assert!(matches!(
+        zkprov_corelib::verify(&config, &ir, &other, &proof.view()),
+        Err(VerifyError::HeaderBinding { .. })
+    ));
+    let not_object = PublicInputs::from_json("[1]").unwrap();
+    assert!(zkprov_corelib::prove(&config, &ir, &not_object)
+        .unwrap_err()
+        .to_string()
+        .contains("inputs must be a JSON object"));
+
+    assert!(matches!(
+        plugin::load_library(&lib),
+        Err(PluginError::Registry(RegistryError::DuplicateBackend(id))) if id == "example@0.1"
+    ));
+}
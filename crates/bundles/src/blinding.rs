@@ -0,0 +1,67 @@
+//! BlindingSource: fresh Pedersen blinds from the OS CSPRNG, or reproducible
+//! blinds derived with HKDF-SHA256 from a master secret and a label
+//! (e.g. "orders/42/amount"). Derived blinds let a wallet re-open commitments
+//! without storing every blind; labels must then be unique per commitment.
+
+use crate::errors::PrivacyError;
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// Blind length in bytes.
+pub const BLIND_LEN: usize = 32;
+/// Minimum master secret length accepted for derivation.
+pub const MIN_MASTER_LEN: usize = 32;
+
+const HKDF_SALT: &[u8] = b"zkprov/blinding/v1";
+
+pub enum BlindingSource {
+    /// OS CSPRNG; the label is ignored.
+    Os,
+    /// HKDF-SHA256(master, salt = "zkprov/blinding/v1", info = label).
+    Derived(Hkdf<Sha256>),
+}
+
+impl std::fmt::Debug for BlindingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlindingSource::Os => write!(f, "BlindingSource::Os"),
+            BlindingSource::Derived(_) => write!(f, "BlindingSource::Derived(<redacted>)"),
+        }
+    }
+}
+
+impl BlindingSource {
+    pub fn os() -> Self {
+        BlindingSource::Os
+    }
+
+    pub fn from_master(master: &[u8]) -> Result<Self, PrivacyError> {
+        if master.len() < MIN_MASTER_LEN {
+            return Err(PrivacyError::WeakMasterSecret);
+        }
+        Ok(BlindingSource::Derived(Hkdf::<Sha256>::new(
+            Some(HKDF_SALT),
+            master,
+        )))
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        matches!(self, BlindingSource::Derived(_))
+    }
+
+    /// Produce a blind for `label`. Use [`PedersenCtx::commit_with_source`]
+    /// to also record it in a session's tracker.
+    ///
+    /// [`PedersenCtx::commit_with_source`]: crate::PedersenCtx::commit_with_source
+    pub fn blind(&self, label: &str) -> Result<[u8; BLIND_LEN], PrivacyError> {
+        let mut out = [0u8; BLIND_LEN];
+        match self {
+            BlindingSource::Os => getrandom::getrandom(&mut out)
+                .map_err(|e| PrivacyError::Internal(format!("os rng: {e}")))?,
+            BlindingSource::Derived(hk) => hk
+                .expand(label.as_bytes(), &mut out)
+                .map_err(|e| PrivacyError::Internal(format!("hkdf: {e}")))?,
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,52 @@
+//! CommitEquality: two commitments, possibly under different blinds or
+//! commitment hashes, open to the same message.
+//!
+//! Checked host-side from both openings for now; the AIR fragment that proves
+//! it without revealing the message is still to come (TASKLIST Task 0.7).
+
+use crate::errors::PrivacyError;
+use crate::pedersen::{BlindingTracker, PedersenCommit, PedersenCtx};
+
+pub struct CommitEquality;
+
+impl CommitEquality {
+    /// Commit to `msg` under `a` with `r_a` and under `b` with `r_b`. Both
+    /// blinds go through the tracker, so under `no_r_reuse` they must differ
+    /// from each other and from any blind used before.
+    pub fn run(
+        a: &PedersenCtx,
+        b: &PedersenCtx,
+        tracker: &mut BlindingTracker,
+        msg: &[u8],
+        r_a: &[u8],
+        r_b: &[u8],
+    ) -> Result<(PedersenCommit, PedersenCommit), PrivacyError> {
+        let ca = a.commit(tracker, msg, r_a)?;
+        let cb = b.commit(tracker, msg, r_b)?;
+        Ok((ca, cb))
+    }
+
+    /// Check that `ca` (under `a`) and `cb` (under `b`) both open to `msg`
+    /// with blinds `r_a` and `r_b`. Either one failing to open is
+    /// `CommitmentMismatch`; other gadget errors pass through.
+    pub fn verify(
+        a: &PedersenCtx,
+        ca: &PedersenCommit,
+        r_a: &[u8],
+        b: &PedersenCtx,
+        cb: &PedersenCommit,
+        r_b: &[u8],
+        msg: &[u8],
+    ) -> Result<(), PrivacyError> {
+        for (ctx, c, r) in [(a, ca, r_a), (b, cb, r_b)] {
+            match ctx.open(msg, r, &c.cx, &c.cy) {
+                Ok(_) => {}
+                Err(PrivacyError::InvalidCurvePoint) => {
+                    return Err(PrivacyError::CommitmentMismatch)
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
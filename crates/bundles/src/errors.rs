@@ -6,7 +6,10 @@ pub enum PrivacyError {
     InvalidCurvePoint,
     BlindingReuse,
     RangeCheckOverflow,
+    /// Two commitments do not open to the same message.
+    CommitmentMismatch,
     UnsupportedCurve, // helpful internal; not required by DoD but used in messages
+    WeakMasterSecret, // blinding derivation master shorter than MIN_MASTER_LEN
     Internal(String),
 }
 
@@ -17,7 +20,9 @@ impl std::fmt::Display for PrivacyError {
             InvalidCurvePoint => write!(f, "InvalidCurvePoint"),
             BlindingReuse => write!(f, "BlindingReuse"),
             RangeCheckOverflow => write!(f, "RangeCheckOverflow"),
+            CommitmentMismatch => write!(f, "CommitmentMismatch"),
             UnsupportedCurve => write!(f, "UnsupportedCurve"),
+            WeakMasterSecret => write!(f, "WeakMasterSecret"),
             Internal(s) => write!(f, "Internal({})", s),
         }
     }
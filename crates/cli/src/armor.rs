@@ -0,0 +1,286 @@
+//! Text armor for proof blobs (`zkd proof-encode` / `zkd proof-decode`).
+//!
+//! Proofs are streamed through in fixed-size chunks, so neither direction
+//! holds the whole proof in memory. Both directions check the stream
+//! against the embedded header: it must decode, and the byte count must
+//! equal the header length plus `body_len`. Decoding ignores ASCII whitespace, so
+//! line-wrapped armor round-trips.
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use zkprov_corelib::proof::{ProofHeader, HEADER_LEN};
+
+use super::{ProofDecodeArgs, ProofEncodeArgs};
+
+/// Longest possible header (`header_len` is a u16); only this much of the
+/// stream is kept for the length check.
+const MAX_HEADER_LEN: usize = u16::MAX as usize;
+
+const CHUNK: usize = 48 * 1024;
+const B64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Armor {
+    /// RFC 4648 base64 with padding
+    Base64,
+    /// Lowercase hex (uppercase accepted when decoding)
+    Hex,
+}
+
+impl Armor {
+    /// Raw bytes per encoded group, and symbols per group.
+    fn group(self) -> (usize, usize) {
+        match self {
+            Armor::Base64 => (3, 4),
+            Armor::Hex => (1, 2),
+        }
+    }
+
+    fn encode_group(self, bytes: &[u8], out: &mut Vec<u8>) {
+        match self {
+            Armor::Hex => {
+                for &b in bytes {
+                    out.push(HEX[(b >> 4) as usize]);
+                    out.push(HEX[(b & 0x0f) as usize]);
+                }
+            }
+            Armor::Base64 => {
+                let n = (bytes[0] as u32) << 16
+                    | (*bytes.get(1).unwrap_or(&0) as u32) << 8
+                    | *bytes.get(2).unwrap_or(&0) as u32;
+                for i in 0..4 {
+                    if i <= bytes.len() {
+                        out.push(B64[(n >> (18 - 6 * i) & 0x3f) as usize]);
+                    } else {
+                        out.push(b'=');
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decode one full group; `Ok(true)` when it was padded (must be last).
+    fn decode_group(self, symbols: &[u8], out: &mut Vec<u8>) -> Result<bool> {
+        match self {
+            Armor::Hex => {
+                out.push(hex_val(symbols[0])? << 4 | hex_val(symbols[1])?);
+                Ok(false)
+            }
+            Armor::Base64 => {
+                let pad = symbols.iter().rev().take_while(|&&s| s == b'=').count();
+                if pad > 2 || symbols[..4 - pad].contains(&b'=') {
+                    bail!("misplaced base64 padding");
+                }
+                let mut n = 0u32;
+                for &s in &symbols[..4 - pad] {
+                    n = n << 6 | b64_val(s)?;
+                }
+                n <<= 6 * pad as u32;
+                out.extend_from_slice(&n.to_be_bytes()[1..4 - pad]);
+                Ok(pad > 0)
+            }
+        }
+    }
+}
+
+fn hex_val(s: u8) -> Result<u8> {
+    (s as char)
+        .to_digit(16)
+        .map(|d| d as u8)
+        .ok_or_else(|| anyhow!("invalid hex character '{}'", s.escape_ascii()))
+}
+
+fn b64_val(s: u8) -> Result<u32> {
+    B64.iter()
+        .position(|&c| c == s)
+        .map(|i| i as u32)
+        .ok_or_else(|| anyhow!("invalid base64 character '{}'", s.escape_ascii()))
+}
+
+/// Tracks the decoded proof bytes against the header's `body_len`.
+#[derive(Default)]
+struct LengthCheck {
+    head: Vec<u8>,
+    total: u64,
+}
+
+impl LengthCheck {
+    fn feed(&mut self, bytes: &[u8]) {
+        let want = MAX_HEADER_LEN
+            .saturating_sub(self.head.len())
+            .min(bytes.len());
+        self.head.extend_from_slice(&bytes[..want]);
+        self.total += bytes.len() as u64;
+    }
+
+    fn finish(&self) -> Result<u64> {
+        let (header, header_len) = ProofHeader::decode_prefix(&self.head).map_err(|e| {
+            if self.head.len() < HEADER_LEN {
+                anyhow!(
+                    "proof is too short for header ({} bytes, need {})",
+                    self.total,
+                    HEADER_LEN
+                )
+            } else {
+                e
+            }
+        })?;
+        let expected = header_len as u64 + header.body_len;
+        if self.total != expected {
+            bail!(
+                "proof length ({}) does not match header body_len {} (expected {})",
+                self.total,
+                header.body_len,
+                expected
+            );
+        }
+        Ok(self.total)
+    }
+}
+
+/// Read until `buf` is full or EOF; returns the number of bytes read.
+fn fill(r: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut n = 0;
+    while n < buf.len() {
+        match r.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(k) => n += k,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e).context("reading proof"),
+        }
+    }
+    Ok(n)
+}
+
+/// Armor the binary proof read from `r` onto `w`; returns the proof length.
+pub fn encode(armor: Armor, mut r: impl Read, mut w: impl Write) -> Result<u64> {
+    let (bytes_per, _) = armor.group();
+    let mut check = LengthCheck::default();
+    let mut buf = vec![0u8; CHUNK - CHUNK % bytes_per];
+    let mut out = Vec::with_capacity(buf.len() * 2);
+    loop {
+        let n = fill(&mut r, &mut buf)?;
+        check.feed(&buf[..n]);
+        out.clear();
+        for group in buf[..n].chunks(bytes_per) {
+            armor.encode_group(group, &mut out);
+        }
+        w.write_all(&out).context("writing armor")?;
+        if n < buf.len() {
+            break;
+        }
+    }
+    let total = check.finish()?;
+    w.write_all(b"\n").context("writing armor")?;
+    w.flush()?;
+    Ok(total)
+}
+
+/// Decode armor read from `r` into the binary proof on `w`; returns the
+/// proof length. Fails if the result is not a proof of its declared length.
+pub fn decode(armor: Armor, mut r: impl Read, mut w: impl Write) -> Result<u64> {
+    let (_, symbols_per) = armor.group();
+    let mut check = LengthCheck::default();
+    let mut buf = vec![0u8; CHUNK];
+    let mut pending: Vec<u8> = Vec::with_capacity(symbols_per);
+    let mut out = Vec::with_capacity(CHUNK);
+    let mut padded = false;
+    loop {
+        let n = fill(&mut r, &mut buf)?;
+        out.clear();
+        for &s in buf[..n].iter().filter(|s| !s.is_ascii_whitespace()) {
+            if padded {
+                bail!("data after base64 padding");
+            }
+            pending.push(s);
+            if pending.len() == symbols_per {
+                padded = armor.decode_group(&pending, &mut out)?;
+                pending.clear();
+            }
+        }
+        check.feed(&out);
+        w.write_all(&out).context("writing proof")?;
+        if n < buf.len() {
+            break;
+        }
+    }
+    if !pending.is_empty() {
+        bail!("armor is truncated ({} trailing symbols)", pending.len());
+    }
+    let total = check.finish()?;
+    w.flush()?;
+    Ok(total)
+}
+
+/// `zkd proof-encode`.
+pub fn proof_encode(args: ProofEncodeArgs) -> Result<()> {
+    let ProofEncodeArgs {
+        armor: kind,
+        proof_path,
+        output,
+    } = args;
+    let input = open_input(&proof_path)?;
+    match output {
+        Some(path) => {
+            let len = with_output_file(&path, |w| encode(kind, input, w))?;
+            eprintln!("Wrote: {} (proof {} bytes)", path, len);
+        }
+        None => {
+            encode(kind, input, std::io::stdout().lock())?;
+        }
+    }
+    Ok(())
+}
+
+/// `zkd proof-decode`.
+pub fn proof_decode(args: ProofDecodeArgs) -> Result<()> {
+    let ProofDecodeArgs {
+        armor: kind,
+        input,
+        output,
+    } = args;
+    let input = open_input(&input)?;
+    if output == "-" {
+        decode(kind, input, std::io::stdout().lock())?;
+    } else {
+        let len = with_output_file(&output, |w| decode(kind, input, w))?;
+        eprintln!("Wrote: {} (proof {} bytes)", output, len);
+    }
+    Ok(())
+}
+
+/// Buffered reader over `path`, or stdin for `-`.
+fn open_input(path: &str) -> Result<Box<dyn std::io::Read>> {
+    if path == "-" {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+    let file = fs::File::open(path).with_context(|| format!("failed to open '{}'", path))?;
+    Ok(Box::new(std::io::BufReader::new(file)))
+}
+
+/// Stream into a new file at `path`, removing it again if `f` fails so no
+/// partial output is left behind.
+fn with_output_file<T>(
+    path: &str,
+    f: impl FnOnce(&mut std::io::BufWriter<fs::File>) -> Result<T>,
+) -> Result<T> {
+    if let Some(dir) = Path::new(path).parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create dir '{}'", dir.display()))?;
+        }
+    }
+    let file = fs::File::create(path).with_context(|| format!("failed to create '{}'", path))?;
+    let mut w = std::io::BufWriter::new(file);
+    let result = f(&mut w);
+    if result.is_err() {
+        drop(w);
+        let _ = fs::remove_file(path);
+    }
+    result
+}
@@ -0,0 +1,136 @@
+//! Audit records for `zkd prove` and `zkd verify` (see
+//! [`zkprov_corelib::audit`]).
+//!
+//! The log is `--audit-log PATH`, else `$ZKD_AUDIT_LOG`; without either,
+//! nothing is recorded. The actor is `$ZKD_AUDIT_ACTOR`, else `$USER`.
+
+use anyhow::{Context, Result};
+use std::fmt::Display;
+use std::path::Path;
+use std::time::Instant;
+
+use zkprov_corelib::audit::{verify_log, AuditLog, AuditOp, AuditRecord, AuditResult};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::evm::typed::program_hash;
+
+use super::{bytes_to_hex, output, AuditCmd};
+
+pub struct Auditor {
+    log: AuditLog,
+    op: AuditOp,
+    started: Instant,
+    program_hash: String,
+    inputs_hash: String,
+    backend: String,
+    profile: String,
+}
+
+impl Auditor {
+    /// An auditor for one operation, or `None` when no log is configured.
+    pub fn open(
+        path: Option<&str>,
+        op: AuditOp,
+        config: &Config,
+        program_path: &str,
+        inputs_json: &str,
+    ) -> Result<Option<Self>> {
+        let Some(path) = path
+            .map(str::to_string)
+            .or_else(|| std::env::var("ZKD_AUDIT_LOG").ok())
+            .filter(|p| !p.is_empty())
+        else {
+            return Ok(None);
+        };
+        let air_source = std::fs::read(program_path)
+            .with_context(|| format!("failed to read '{}'", program_path))?;
+        Ok(Some(Self {
+            log: AuditLog::new(path),
+            op,
+            started: Instant::now(),
+            program_hash: format!("0x{}", bytes_to_hex(&program_hash(&air_source))),
+            inputs_hash: format!(
+                "0x{}",
+                bytes_to_hex(blake3::hash(inputs_json.as_bytes()).as_bytes())
+            ),
+            backend: config.backend_id.clone(),
+            profile: config.profile_id.clone(),
+        }))
+    }
+
+    fn record(&self, digest: Option<&[u8; 32]>, error: Option<String>) -> Result<()> {
+        let record = AuditRecord {
+            actor: actor(),
+            op: self.op,
+            program_hash: self.program_hash.clone(),
+            inputs_hash: self.inputs_hash.clone(),
+            backend: self.backend.clone(),
+            profile: self.profile.clone(),
+            digest: digest.map(|d| format!("0x{}", bytes_to_hex(d))),
+            result: match error {
+                None => AuditResult::Ok,
+                Some(_) => AuditResult::Failed,
+            },
+            error,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+        };
+        self.log.append(record).map(|_| ())
+    }
+
+    /// Record success. An operation that cannot be audited fails.
+    pub fn ok(&self, digest: &[u8; 32]) -> Result<()> {
+        self.record(Some(digest), None)
+            .context("the operation succeeded but could not be audited")
+    }
+
+    /// Record a failure; the caller is already exiting, so a log that
+    /// cannot be written is only reported.
+    pub fn failed(&self, digest: Option<&[u8; 32]>, error: &dyn Display) {
+        if let Err(e) = self.record(digest, Some(format!("{:#}", error))) {
+            eprintln!("⚠️ audit log '{}': {:#}", self.log.path().display(), e);
+        }
+    }
+}
+
+/// Record `error` with `auditor`, when there is one.
+pub fn failed(auditor: &Option<Auditor>, error: &dyn Display) {
+    if let Some(a) = auditor {
+        a.failed(None, error);
+    }
+}
+
+fn actor() -> String {
+    ["ZKD_AUDIT_ACTOR", "USER"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// `zkd audit …`.
+pub fn audit_cmd(action: AuditCmd) -> Result<()> {
+    match action {
+        AuditCmd::Verify { log, json } => match verify_log(Path::new(&log)) {
+            Ok(report) if json => output::data_line(
+                &serde_json::json!({"ok": true, "entries": report.entries, "head": report.head}),
+            )?,
+            Ok(report) => {
+                info!(
+                    "✅ AuditChainIntact entries={} head={}",
+                    report.entries, report.head
+                );
+                output::result("ok");
+            }
+            Err(e) => {
+                if json {
+                    output::data_line(
+                        &serde_json::json!({"ok": false, "error": format!("{e:#}")}),
+                    )?;
+                } else {
+                    eprintln!("❌ AuditChainBroken {:#}", e);
+                    output::on_failure("fail");
+                }
+                output::exit(1);
+            }
+        },
+    }
+    Ok(())
+}
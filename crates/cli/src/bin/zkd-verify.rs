@@ -0,0 +1,10 @@
+//! Verification-only build of `zkd`: verify, digest and inspection commands,
+//! without provers or gadget bundles. Build with
+//! `cargo build -p zkd --release --no-default-features --features verifier --bin zkd-verify`.
+
+#[path = "../main.rs"]
+mod zkd;
+
+fn main() -> anyhow::Result<()> {
+    zkd::main()
+}
@@ -0,0 +1,433 @@
+//! `zkd conformance`: a JSON-driven suite that any zkd implementation or
+//! binding can run to certify that it proves and verifies like zkd.
+//!
+//! A suite (`examples/conformance/suite.json`) lists cases, each an AIR
+//! (relative to the suite file), public inputs, a pinned configuration and
+//! the expected EVM digest `D`, plus corruptions applied to every proof with
+//! the error class verification must fail with:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "corruptions": [{ "name": "magic", "flip": 0, "error": "proof_corrupt" }],
+//!   "cases": [{ "name": "toy", "air": "../air/toy.air", "inputs": {"n": 7},
+//!               "backend": "native@0.0", "field": "Prime254", "hash": "blake3",
+//!               "fri_arity": 2, "profile": "balanced", "digest": "0x…" }]
+//! }
+//! ```
+//!
+//! `flip` XORs `0x01` into the byte at that offset (negative offsets count
+//! from the end) and `truncate` keeps that many leading bytes. Error classes
+//! are the snake-case `ZKP_ERR_*` names (`proof_corrupt`, `verify_fail`, …),
+//! or `ok` for a change verifiers must ignore.
+//!
+//! Without an adapter the runner checks zkd itself. After `--`, it runs the
+//! given command once per request: one [`Request`] JSON object on stdin, one
+//! [`Reply`] on stdout. `zkd conformance --adapter` is the reference adapter.
+//!
+//! Prints one `PASS <case>/<check>` or `FAIL <case>/<check>: <why>` line per
+//! check; `--bless` rewrites the suite's digests from zkd itself.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use zkprov_corelib as core;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::proof::ProofView;
+use zkprov_corelib::registry;
+
+use super::{output, ConformanceArgs};
+use zkprov_ffi_types::ErrorCode;
+
+pub const SUITE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    pub version: u32,
+    #[serde(default)]
+    pub corruptions: Vec<Corruption>,
+    pub cases: Vec<Case>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Case {
+    pub name: String,
+    /// AIR path relative to the suite file.
+    pub air: String,
+    pub inputs: serde_json::Value,
+    pub backend: String,
+    pub field: String,
+    pub hash: String,
+    pub fri_arity: u32,
+    pub profile: String,
+    /// `0x`-prefixed EVM digest `D` of the proof.
+    pub digest: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Corruption {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flip: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<usize>,
+    /// Expected error class, e.g. `proof_corrupt`.
+    pub error: String,
+}
+
+impl Corruption {
+    fn apply(&self, proof: &[u8]) -> Result<Vec<u8>> {
+        match (self.flip, self.truncate) {
+            (Some(offset), None) => {
+                let len = proof.len() as i64;
+                let at = if offset < 0 { len + offset } else { offset };
+                ensure!(
+                    (0..len).contains(&at),
+                    "offset {} is outside a {}-byte proof",
+                    offset,
+                    len
+                );
+                let mut out = proof.to_vec();
+                out[at as usize] ^= 0x01;
+                Ok(out)
+            }
+            (None, Some(len)) => Ok(proof[..len.min(proof.len())].to_vec()),
+            _ => bail!(
+                "corruption '{}' needs exactly one of flip / truncate",
+                self.name
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Op {
+    Prove,
+    Verify,
+}
+
+/// One adapter request. `inputs` is the public inputs JSON text and
+/// `proof` (verify only) is hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Request {
+    pub op: Op,
+    /// Absolute AIR path.
+    pub air: String,
+    pub inputs: String,
+    pub backend: String,
+    pub field: String,
+    pub hash: String,
+    pub fri_arity: u32,
+    pub profile: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+}
+
+/// Adapter reply: a `ZKP_ERR_*` code (`0` on success) and, for a
+/// successful prove, the hex proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reply {
+    pub code: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
+}
+
+impl Reply {
+    fn err(code: ErrorCode, msg: impl std::fmt::Display) -> Self {
+        Self {
+            code: code.code(),
+            proof: None,
+            msg: Some(msg.to_string()),
+        }
+    }
+}
+
+/// Outcome of one check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckResult {
+    pub case: String,
+    pub check: String,
+    pub pass: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    ensure!(s.len().is_multiple_of(2), "odd-length hex");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|e| anyhow!("bad hex: {e}")))
+        .collect()
+}
+
+fn class_name(code: i32) -> String {
+    ErrorCode::from_code(code)
+        .map(|c| c.as_str().to_string())
+        .unwrap_or_else(|| format!("code {}", code))
+}
+
+/// Answer `request` with zkd's own prover and verifier.
+pub fn reference(request: &Request) -> Reply {
+    registry::ensure_builtins_registered();
+    let config = Config::new(
+        &request.backend,
+        &request.field,
+        &request.hash,
+        request.fri_arity,
+        false,
+        &request.profile,
+    );
+    let parsed = core::air::parse_air_file(Path::new(&request.air))
+        .and_then(|ir| Ok((ir, PublicInputs::from_json(&request.inputs)?)));
+    let (ir, inputs) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return Reply::err(ErrorCode::InvalidArg, format!("{e:#}")),
+    };
+    match request.op {
+        Op::Prove => match core::prove(&config, &ir, &inputs) {
+            Ok(proof) => Reply {
+                code: ErrorCode::Ok.code(),
+                proof: Some(to_hex(&proof.to_bytes())),
+                msg: None,
+            },
+            Err(e) => Reply::err(ErrorCode::from(&e), &e),
+        },
+        Op::Verify => {
+            let bytes = match request.proof.as_deref().map(from_hex) {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(e)) => return Reply::err(ErrorCode::InvalidArg, e),
+                None => return Reply::err(ErrorCode::InvalidArg, "verify needs a proof"),
+            };
+            let result = ProofView::from_bytes(&bytes)
+                .and_then(|proof| core::verify(&config, &ir, &inputs, &proof));
+            match result {
+                Ok(()) => Reply {
+                    code: ErrorCode::Ok.code(),
+                    proof: None,
+                    msg: None,
+                },
+                Err(e) => Reply::err(ErrorCode::from(&e), &e),
+            }
+        }
+    }
+}
+
+/// `zkd conformance --adapter`: answer one request read from stdin.
+pub fn serve_adapter() -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let request: Request = serde_json::from_str(&input).context("parsing request")?;
+    output::own_stdout();
+    println!("{}", serde_json::to_string(&reference(&request))?);
+    Ok(())
+}
+
+fn call_adapter(command: &[String], request: &Request) -> Result<Reply> {
+    let (program, args) = command.split_first().expect("non-empty adapter command");
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("running adapter '{}'", program))?;
+    let mut stdin = child.stdin.take().expect("piped stdin");
+    writeln!(stdin, "{}", serde_json::to_string(request)?)?;
+    drop(stdin);
+    let out = child.wait_with_output()?;
+    ensure!(
+        out.status.success(),
+        "adapter exited with {}: {}",
+        out.status,
+        String::from_utf8_lossy(&out.stderr).trim()
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    serde_json::from_str(stdout.trim())
+        .with_context(|| format!("adapter reply is not a Reply object: {}", stdout.trim()))
+}
+
+/// Run `suite_path` against zkd (empty `adapter`) or an adapter command.
+/// With `bless`, rewrite the suite's digests instead of comparing them.
+/// Returns every check's outcome; `print` writes the result lines as well.
+pub fn run(
+    suite_path: &Path,
+    adapter: &[String],
+    bless: bool,
+    print: bool,
+) -> Result<Vec<CheckResult>> {
+    ensure!(
+        !bless || adapter.is_empty(),
+        "--bless records zkd's own digests; drop the adapter command"
+    );
+    let text =
+        fs::read_to_string(suite_path).with_context(|| format!("read {}", suite_path.display()))?;
+    let mut suite: Suite =
+        serde_json::from_str(&text).with_context(|| format!("parse {}", suite_path.display()))?;
+    ensure!(
+        suite.version == SUITE_VERSION,
+        "unsupported suite version {} (expected {})",
+        suite.version,
+        SUITE_VERSION
+    );
+    let base = suite_path.parent().unwrap_or(Path::new("."));
+    let call = |request: &Request| -> Result<Reply> {
+        if adapter.is_empty() {
+            Ok(reference(request))
+        } else {
+            call_adapter(adapter, request)
+        }
+    };
+
+    let mut results = Vec::new();
+    let mut record = |case: &str, check: String, outcome: Result<()>| {
+        let result = CheckResult {
+            case: case.to_string(),
+            check,
+            pass: outcome.is_ok(),
+            detail: outcome.err().map(|e| format!("{e:#}")),
+        };
+        match &result.detail {
+            _ if !print => {}
+            None => out!("PASS {}/{}", result.case, result.check),
+            Some(why) => out!("FAIL {}/{}: {}", result.case, result.check, why),
+        }
+        results.push(result);
+    };
+    for case in &mut suite.cases {
+        let air = base.join(&case.air);
+        let air = fs::canonicalize(&air).with_context(|| format!("AIR {}", air.display()))?;
+        let request = |op, proof: Option<&[u8]>| Request {
+            op,
+            air: air.to_string_lossy().into_owned(),
+            inputs: case.inputs.to_string(),
+            backend: case.backend.clone(),
+            field: case.field.clone(),
+            hash: case.hash.clone(),
+            fri_arity: case.fri_arity,
+            profile: case.profile.clone(),
+            proof: proof.map(to_hex),
+        };
+
+        let proved = call(&request(Op::Prove, None)).and_then(|reply| {
+            ensure!(
+                reply.code == 0,
+                "prove failed with {}: {}",
+                class_name(reply.code),
+                reply.msg.unwrap_or_default()
+            );
+            let proof = from_hex(reply.proof.as_deref().unwrap_or_default())?;
+            let view = ProofView::from_bytes(&proof)?;
+            let digest = format!("0x{}", to_hex(&digest_D(&view.header, view.body)));
+            Ok((proof, digest))
+        });
+        let proof = match proved {
+            Ok((proof, digest)) if bless => {
+                if print {
+                    out!("BLESS {} {}", case.name, digest);
+                }
+                case.digest = digest;
+                proof
+            }
+            Ok((proof, digest)) => {
+                let outcome = if digest == case.digest {
+                    Ok(())
+                } else {
+                    Err(anyhow!("digest {} != expected {}", digest, case.digest))
+                };
+                record(&case.name, "prove".into(), outcome);
+                proof
+            }
+            Err(e) => {
+                record(&case.name, "prove".into(), Err(e));
+                continue;
+            }
+        };
+
+        let outcome = call(&request(Op::Verify, Some(&proof))).and_then(|reply| {
+            ensure!(
+                reply.code == 0,
+                "valid proof rejected with {}: {}",
+                class_name(reply.code),
+                reply.msg.unwrap_or_default()
+            );
+            Ok(())
+        });
+        record(&case.name, "verify".into(), outcome);
+
+        for corruption in &suite.corruptions {
+            let outcome = corruption.apply(&proof).and_then(|bad| {
+                let reply = call(&request(Op::Verify, Some(&bad)))?;
+                let got = class_name(reply.code);
+                ensure!(
+                    got == corruption.error,
+                    "expected {}, got {}",
+                    corruption.error,
+                    if reply.code == 0 {
+                        "a verified proof".into()
+                    } else {
+                        got
+                    }
+                );
+                Ok(())
+            });
+            record(&case.name, format!("corrupt:{}", corruption.name), outcome);
+        }
+    }
+
+    if bless && results.iter().all(|r| r.pass) {
+        let mut text = serde_json::to_string_pretty(&suite)?;
+        text.push('\n');
+        fs::write(suite_path, text).with_context(|| format!("write {}", suite_path.display()))?;
+        if print {
+            out!("Wrote: {}", suite_path.display());
+        }
+    }
+    if print {
+        let failed = results.iter().filter(|r| !r.pass).count();
+        out!(
+            "conformance: {} passed, {} failed",
+            results.len() - failed,
+            failed
+        );
+    }
+    Ok(results)
+}
+
+/// `zkd conformance`.
+pub fn conformance_cmd(args: ConformanceArgs) -> Result<()> {
+    let ConformanceArgs {
+        suite,
+        bless,
+        adapter,
+        json,
+        command,
+    } = args;
+    if adapter {
+        return serve_adapter();
+    }
+    let results = run(Path::new(&suite), &command, bless, !json)?;
+    if json {
+        output::data(&results)?;
+    }
+    if results.iter().any(|r| !r.pass) {
+        output::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Commitments, blinds and test vectors: `zkd commit`, `zkd open-commit`,
+//! `zkd blind-new` and `zkd vectors`.
+
+#[cfg(feature = "prover")]
+use anyhow::Context;
+use anyhow::{anyhow, Result};
+#[cfg(feature = "prover")]
+use std::fs;
+#[cfg(feature = "prover")]
+use zkprov_bundles::BlindingSource;
+use zkprov_corelib::crypto::vectors;
+use zkprov_corelib::gadgets::commitment::{Comm32, CommitKind, CommitmentScheme32, Witness};
+use zkprov_corelib::registry;
+
+#[cfg(feature = "prover")]
+use super::{bytes_to_hex, BlindNewArgs, CommitArgs};
+use super::{hex_to_bytes, output, write_bytes, OpenCommitArgs, VectorsArgs};
+
+/// `zkd commit`.
+#[cfg(feature = "prover")]
+pub fn commit(args: CommitArgs) -> Result<()> {
+    let CommitArgs {
+        kind,
+        hash_id,
+        msg_hex,
+        blind_hex,
+    } = args;
+    registry::ensure_builtins_registered();
+    let kind: CommitKind = kind.parse()?;
+    let msg = hex_to_bytes(&msg_hex)?;
+    let blind = hex_to_bytes(&blind_hex)?;
+    let scheme = commit_scheme(kind, hash_id.as_deref())?;
+    let commitment = scheme.commit(&Witness {
+        msg: &msg,
+        blind: &blind,
+    })?;
+    output::line("commitment", kind.format(&commitment));
+    Ok(())
+}
+
+/// `zkd open-commit`.
+pub fn open_commit(args: OpenCommitArgs) -> Result<()> {
+    let OpenCommitArgs {
+        kind,
+        hash_id,
+        msg_hex,
+        blind_hex,
+        commit_hex,
+    } = args;
+    registry::ensure_builtins_registered();
+    let kind: CommitKind = kind.parse()?;
+    let msg = hex_to_bytes(&msg_hex)?;
+    let blind = hex_to_bytes(&blind_hex)?;
+    let cbytes = hex_to_bytes(commit_hex.strip_prefix("0x").unwrap_or(&commit_hex))?;
+    if cbytes.len() != 32 {
+        return Err(anyhow!("commit-hex must be 32 bytes (64 hex chars)"));
+    }
+    let mut c32 = [0u8; 32];
+    c32.copy_from_slice(&cbytes);
+    let scheme = commit_scheme(kind, hash_id.as_deref())?;
+    let opened = scheme.open(
+        &Witness {
+            msg: &msg,
+            blind: &blind,
+        },
+        &Comm32(c32),
+    )?;
+    if opened {
+        info!("✅ Opened");
+        output::result("ok");
+    } else {
+        info!("❌ Invalid opening");
+        output::on_failure("fail");
+        output::exit(1);
+    }
+    Ok(())
+}
+
+/// `zkd blind-new`.
+#[cfg(feature = "prover")]
+pub fn blind_new(args: BlindNewArgs) -> Result<()> {
+    let BlindNewArgs { label, master_file } = args;
+    let source = match &master_file {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("reading master secret {}", path))?;
+            let master = hex_to_bytes(text.trim())
+                .with_context(|| format!("master secret in {} is not hex", path))?;
+            BlindingSource::from_master(&master).map_err(|e| anyhow!("{e}"))?
+        }
+        None => BlindingSource::os(),
+    };
+    let blind = source
+        .blind(label.as_deref().unwrap_or(""))
+        .map_err(|e| anyhow!("{e}"))?;
+    output::line("blind", bytes_to_hex(&blind));
+    Ok(())
+}
+
+/// `zkd vectors`.
+pub fn vectors(args: VectorsArgs) -> Result<()> {
+    let VectorsArgs { output, pretty } = args;
+    let vectors = vectors::generate();
+    let mut json = if pretty {
+        serde_json::to_string_pretty(&vectors)?
+    } else {
+        serde_json::to_string(&vectors)?
+    };
+    json.push('\n');
+    match &output {
+        Some(path) => {
+            write_bytes(path, json.as_bytes())?;
+            info!("Wrote: {}", path);
+        }
+        None if output::is_json() => output::data(&vectors)?,
+        None => print!("{json}"),
+    }
+    Ok(())
+}
+
+/// The scheme behind `commit`/`open-commit --kind`.
+fn commit_scheme(kind: CommitKind, hash_id: Option<&str>) -> Result<Box<dyn CommitmentScheme32>> {
+    if kind == CommitKind::Pedersen && hash_id.is_none() {
+        return Err(anyhow!("--hash is required for --kind pedersen"));
+    }
+    kind.scheme(hash_id)
+}
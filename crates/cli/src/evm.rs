@@ -0,0 +1,171 @@
+//! EVM interop: `zkd evm-pubio`, `zkd evm-digest`, `zkd evm-fixtures` and
+//! `zkd evm-typed-hash` (see [`zkprov_corelib::evm`]).
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+use zkprov_corelib as core;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::evm::aggregate::Aggregate;
+use zkprov_corelib::evm::digest::{digest_D, keccak256_bytes};
+use zkprov_corelib::evm::typed::{self, TypedDomain, TypedSubmission};
+use zkprov_corelib::evm::{fixtures, pubio};
+use zkprov_corelib::proof::{split_proof, ProofHeader};
+
+use super::{
+    bytes_to_hex, hex_to_bytes, open_proof, output, read_to_bytes, read_to_string, write_bytes,
+    EvmDigestArgs, EvmFixturesArgs, EvmPubioArgs, EvmTypedHashArgs,
+};
+
+/// `zkd evm-pubio`.
+pub fn evm_pubio(args: EvmPubioArgs) -> Result<()> {
+    let EvmPubioArgs {
+        program_path,
+        inputs_path,
+        encoding,
+        abi_out,
+    } = args;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let inputs_json = fs::read_to_string(&inputs_path)
+        .with_context(|| format!("reading inputs {}", inputs_path))?;
+    let inputs = PublicInputs::from_json(inputs_json)?;
+    let encoding: pubio::PubIoEncoding = encoding.parse()?;
+    let abi = pubio::encode(encoding, &ir, &inputs)?;
+    if let Some(path) = &abi_out {
+        write_bytes(path, &abi)?;
+    }
+    let out = serde_json::json!({
+        "encoding": encoding.as_str(),
+        "digest": format!("0x{}", bytes_to_hex(&keccak256_bytes(&abi))),
+        "abi_len": abi.len(),
+    });
+    output::data(&out)?;
+    Ok(())
+}
+
+/// `zkd evm-digest`.
+pub fn evm_digest(args: EvmDigestArgs) -> Result<()> {
+    let EvmDigestArgs {
+        proof_paths,
+        aggregate,
+        with_leaves,
+        abi_out,
+    } = args;
+    let digests = proof_paths
+        .iter()
+        .map(|path| {
+            let (header, body) = read_proof_parts(path)?;
+            Ok(digest_D(&header, &body))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !aggregate {
+        if digests.len() != 1 {
+            return Err(anyhow!("multiple proofs require --aggregate"));
+        }
+        output::line("digest", format_args!("0x{}", bytes_to_hex(&digests[0])));
+        return Ok(());
+    }
+    let agg = Aggregate::from_digests(&digests, with_leaves)?;
+    let mut out = serde_json::json!({
+        "root": format!("0x{}", bytes_to_hex(&agg.root)),
+        "count": agg.count,
+    });
+    if let Some(leaves) = &agg.leaves {
+        out["leaves"] = leaves
+            .iter()
+            .map(|l| format!("0x{}", bytes_to_hex(l)))
+            .collect();
+    }
+    if let Some(path) = abi_out {
+        write_bytes(&path, &agg.encode())?;
+        out["abi"] = serde_json::Value::from(path);
+    }
+    output::data(&out)?;
+    Ok(())
+}
+
+/// `zkd evm-fixtures`.
+pub fn evm_fixtures(args: EvmFixturesArgs) -> Result<()> {
+    let EvmFixturesArgs {
+        proof_path,
+        inputs_path,
+        out_dir,
+        pubio_encoding,
+        program_path,
+    } = args;
+    let (header, body) = read_proof_parts(&proof_path)?;
+    let mut inputs = PublicInputs::from_json(read_to_string(&inputs_path)?)?;
+    if let Some(program) = &program_path {
+        let ir = core::air::parse_air_file(Path::new(program))?;
+        inputs = inputs.with_encoding(pubio_encoding.parse()?, &ir)?;
+    }
+    for path in fixtures::write_all(Path::new(&out_dir), &header, &body, &inputs)? {
+        info!("Wrote: {}", path.display());
+    }
+    Ok(())
+}
+
+/// `zkd evm-typed-hash`.
+pub fn evm_typed_hash(args: EvmTypedHashArgs) -> Result<()> {
+    let EvmTypedHashArgs {
+        proof_path,
+        program_path,
+        inputs_path,
+        chain_id,
+        verifying_contract,
+        context,
+        json,
+    } = args;
+    let (header, body) = read_proof_parts(&proof_path)?;
+    let air_source = read_to_bytes(&program_path)?;
+    let inputs = read_to_string(&inputs_path)?;
+    let domain = TypedDomain {
+        chain_id,
+        verifying_contract: hex_to_array(&verifying_contract, "verifying-contract")?,
+    };
+    let submission = TypedSubmission {
+        digest: digest_D(&header, &body),
+        program_hash: typed::program_hash(&air_source),
+        public_inputs_hash: typed::public_inputs_hash(&inputs),
+        context: match context {
+            Some(c) => hex_to_array(&c, "context")?,
+            None => [0u8; 32],
+        },
+    };
+    let hash = format!("0x{}", bytes_to_hex(&submission.signing_hash(domain)));
+    if json {
+        let out = serde_json::json!({
+            "typed_hash": hash,
+            "typed_data": submission.typed_data_json(domain),
+        });
+        output::data(&out)?;
+    } else {
+        output::line("typed_hash", hash);
+    }
+    Ok(())
+}
+
+/// Read a proof file and split it into header and body, checking body_len.
+fn read_proof_parts(proof_path: &str) -> Result<(ProofHeader, Vec<u8>)> {
+    let (proof, _) = open_proof(read_to_bytes(proof_path)?, proof_path)?;
+    let (header, body) = split_proof(&proof)
+        .with_context(|| format!("proof '{}' has no valid header", proof_path))?;
+    if body.len() as u64 != header.body_len {
+        return Err(anyhow!(
+            "proof '{}' length ({}) does not match header body_len {}",
+            proof_path,
+            proof.len(),
+            header.body_len
+        ));
+    }
+    let body = body.to_vec();
+    Ok((header, body))
+}
+
+/// Decode `0x`-optional hex into a fixed-size array, naming `what` on error.
+fn hex_to_array<const N: usize>(s: &str, what: &str) -> Result<[u8; N]> {
+    let bytes = hex_to_bytes(s.strip_prefix("0x").unwrap_or(s))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("{} must be {} bytes ({} hex chars)", what, N, N * 2))
+}
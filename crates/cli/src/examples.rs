@@ -0,0 +1,366 @@
+//! `zkd examples run`: headless example runner for CI and local checks.
+//!
+//! Replaces the bash-only paths of `scripts/run_examples.sh` so the C, Flutter
+//! and EVM examples run the same way on Linux, macOS and Windows:
+//! - `c`       builds `zkprov-ffi-c` (release), compiles `examples/c/roundtrip.c`
+//!   with the platform C compiler picked by the `cc` crate, and runs it
+//! - `flutter` builds the FFI library and the Flutter APK against it
+//! - `evm`     re-derives the `examples/evm_verifier/testdata` fixtures and runs
+//!   `forge test` when Foundry is installed
+//!
+//! Each example prints one `PASS <label>`, `SKIP <label>: <why>` or
+//! `FAIL <label>: <error>` line; any failure makes the command exit non-zero.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use clap::ValueEnum;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use zkprov_corelib::buildinfo;
+use zkprov_corelib::evm::abi::{decode_body, decode_meta, encode_body, encode_meta};
+use zkprov_corelib::evm::aggregate::Aggregate;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::evm::fixtures;
+
+use super::{golden, output, ExamplesCmd};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Example {
+    /// C FFI roundtrip (examples/c/roundtrip.c)
+    C,
+    /// Flutter demo APK (examples/flutter_app)
+    Flutter,
+    /// EVM verifier fixtures (examples/evm_verifier)
+    Evm,
+}
+
+impl Example {
+    pub const ALL: [Example; 3] = [Example::C, Example::Flutter, Example::Evm];
+
+    fn label(self) -> &'static str {
+        match self {
+            Example::C => "C roundtrip",
+            Example::Flutter => "Flutter demo",
+            Example::Evm => "EVM fixtures",
+        }
+    }
+}
+
+/// Result of one example run.
+#[derive(Debug)]
+pub enum Outcome {
+    Pass,
+    /// A required external tool is missing; not counted as a failure.
+    Skip(String),
+}
+
+/// Repository layout and toolchain used by the runner.
+pub struct Runner {
+    root: PathBuf,
+    target_dir: PathBuf,
+    cargo: OsString,
+    ffi_built: bool,
+}
+
+impl Runner {
+    /// `root` must be the workspace root (the directory holding `examples/`).
+    pub fn new(root: &Path) -> Result<Self> {
+        ensure!(
+            root.join("examples").is_dir() && root.join("Cargo.toml").is_file(),
+            "'{}' is not the zkprov workspace root (pass --root)",
+            root.display()
+        );
+        let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| root.join("target"));
+        Ok(Self {
+            root: root.to_path_buf(),
+            target_dir,
+            cargo: std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()),
+            ffi_built: false,
+        })
+    }
+
+    /// Run `examples` in order, printing one status line each. Returns whether
+    /// all of them passed or were skipped.
+    pub fn run_all(&mut self, examples: &[Example]) -> bool {
+        let mut ok = true;
+        for &example in examples {
+            match self.run(example) {
+                Ok(Outcome::Pass) => out!("PASS {}", example.label()),
+                Ok(Outcome::Skip(why)) => out!("SKIP {}: {}", example.label(), why),
+                Err(e) => {
+                    out!("FAIL {}: {:#}", example.label(), e);
+                    ok = false;
+                }
+            }
+        }
+        ok
+    }
+
+    pub fn run(&mut self, example: Example) -> Result<Outcome> {
+        match example {
+            Example::C => self.run_c(),
+            Example::Flutter => self.run_flutter(),
+            Example::Evm => self.run_evm(),
+        }
+    }
+
+    fn info(&self, msg: &str) {
+        eprintln!("[examples] {}", msg);
+    }
+
+    fn release_dir(&self) -> PathBuf {
+        self.target_dir.join("release")
+    }
+
+    fn build_ffi(&mut self) -> Result<()> {
+        if self.ffi_built {
+            self.info("Reusing cached release artifacts.");
+            return Ok(());
+        }
+        self.info("Building zkprov-ffi-c (release).");
+        run_checked(
+            Command::new(&self.cargo)
+                .args(["build", "-p", "zkprov-ffi-c", "--release"])
+                .current_dir(&self.root),
+        )?;
+        self.ffi_built = true;
+        Ok(())
+    }
+
+    fn shared_lib(&self) -> Result<PathBuf> {
+        let name = format!(
+            "{}zkprov{}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_SUFFIX
+        );
+        let path = self.release_dir().join(name);
+        ensure!(path.is_file(), "artifact not found: {}", path.display());
+        Ok(path)
+    }
+
+    fn run_c(&mut self) -> Result<Outcome> {
+        self.build_ffi()?;
+        let release_dir = self.release_dir();
+        let build_dir = self.target_dir.join("examples");
+        fs::create_dir_all(&build_dir)
+            .with_context(|| format!("create {}", build_dir.display()))?;
+        let exe = build_dir.join(format!("roundtrip_c{}", std::env::consts::EXE_SUFFIX));
+
+        let compiler = cc::Build::new()
+            .cargo_metadata(false)
+            .target(buildinfo::TARGET)
+            .host(buildinfo::TARGET)
+            .opt_level(0)
+            .debug(false)
+            .try_get_compiler()
+            .map_err(|e| anyhow!("no C compiler available: {}", e))?;
+        self.info(&format!(
+            "Compiling C harness with {}.",
+            compiler.path().display()
+        ));
+        let mut cmd = compiler.to_command();
+        let include = self.root.join("include");
+        let src = self.root.join("examples/c/roundtrip.c");
+        if compiler.is_like_msvc() {
+            cmd.arg("/nologo")
+                .arg(format!("/I{}", include.display()))
+                .arg(&src)
+                .arg(format!("/Fe:{}", exe.display()))
+                .arg("/link")
+                .arg(format!("/LIBPATH:{}", release_dir.display()))
+                .args(["zkprov.lib", "ws2_32.lib", "userenv.lib", "ntdll.lib"]);
+        } else {
+            cmd.arg(format!("-I{}", include.display()))
+                .arg(&src)
+                .arg(format!("-L{}", release_dir.display()))
+                .arg("-lzkprov");
+            if cfg!(windows) {
+                cmd.args(["-lws2_32", "-luserenv", "-lntdll"]);
+            } else {
+                cmd.arg(format!("-Wl,-rpath,{}", release_dir.display()));
+            }
+            cmd.arg("-o").arg(&exe);
+        }
+        run_checked(cmd.current_dir(&build_dir))?;
+
+        self.info("Running C harness.");
+        let (var, value) = library_search_path(&release_dir)?;
+        run_checked(Command::new(&exe).env(var, value).current_dir(&self.root))?;
+        Ok(Outcome::Pass)
+    }
+
+    fn run_flutter(&mut self) -> Result<Outcome> {
+        let flutter = if cfg!(windows) {
+            "flutter.bat"
+        } else {
+            "flutter"
+        };
+        if !tool_available(flutter) {
+            return Ok(Outcome::Skip("Flutter SDK not found on PATH".to_string()));
+        }
+        self.build_ffi()?;
+        let shared_lib = self.shared_lib()?;
+        let app = self.root.join("examples/flutter_app");
+        self.info("Resolving Flutter dependencies.");
+        run_checked(
+            Command::new(flutter)
+                .args(["--suppress-analytics", "pub", "get"])
+                .current_dir(&app),
+        )?;
+        self.info("Building Flutter APK (release).");
+        run_checked(
+            Command::new(flutter)
+                .args(["--suppress-analytics", "build", "apk"])
+                .env("ZKPROV_LIBRARY_PATH", &shared_lib)
+                .current_dir(&app),
+        )?;
+        Ok(Outcome::Pass)
+    }
+
+    fn run_evm(&mut self) -> Result<Outcome> {
+        let dir = self.root.join("examples/evm_verifier");
+        self.info("Checking EVM fixtures.");
+        check_evm_fixtures(&dir.join("testdata"))?;
+        if !tool_available("forge") {
+            return Ok(Outcome::Skip(
+                "fixtures consistent; Foundry (forge) not found on PATH".to_string(),
+            ));
+        }
+        self.info("Running forge test.");
+        run_checked(Command::new("forge").arg("test").current_dir(&dir))?;
+        Ok(Outcome::Pass)
+    }
+}
+
+/// The fixtures the Solidity tests read must agree with corelib: the digest of
+/// `meta.json` + `body.bin`, their ABI encodings, and the aggregate root.
+pub fn check_evm_fixtures(testdata: &Path) -> Result<()> {
+    let read = |name: &str| -> Result<Vec<u8>> {
+        let path = testdata.join(name);
+        fs::read(&path).with_context(|| format!("read {}", path.display()))
+    };
+    let meta: serde_json::Value = serde_json::from_slice(&read("meta.json")?)?;
+    let header = fixtures::parse_meta_json(&meta)?;
+    let body = read("body.bin")?;
+    ensure!(
+        body.len() as u64 == header.body_len,
+        "body.bin is {} bytes, meta.json says {}",
+        body.len(),
+        header.body_len
+    );
+
+    let digest: String = digest_D(&header, &body)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let expected = String::from_utf8(read("digest.hex")?)?;
+    let expected = expected.trim().trim_start_matches("0x");
+    ensure!(
+        digest == expected,
+        "digest.hex is {}, fixtures hash to {}",
+        expected,
+        digest
+    );
+
+    let meta_abi = read("meta.abi")?;
+    ensure!(
+        meta_abi == encode_meta(&header) && decode_meta(&meta_abi)? == header,
+        "meta.abi does not match meta.json"
+    );
+    let body_abi = read("body.abi")?;
+    ensure!(
+        body_abi == encode_body(&body) && decode_body(&body_abi)? == body,
+        "body.abi does not match body.bin"
+    );
+
+    let agg = Aggregate::decode(&read("aggregate.abi")?)?;
+    if !agg.verify()? {
+        bail!("aggregate.abi leaves do not hash to its root");
+    }
+    let agg_json: serde_json::Value = serde_json::from_slice(&read("aggregate.json")?)?;
+    let root = format!(
+        "0x{}",
+        agg.root
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    );
+    ensure!(
+        agg_json["root"].as_str() == Some(root.as_str())
+            && agg_json["count"].as_u64() == Some(agg.count),
+        "aggregate.json does not match aggregate.abi"
+    );
+    Ok(())
+}
+
+fn tool_available(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .output()
+        .map(|out| out.status.success())
+        .unwrap_or(false)
+}
+
+/// Environment variable and value that put `dir` on the dynamic loader path.
+fn library_search_path(dir: &Path) -> Result<(&'static str, OsString)> {
+    let var = if cfg!(windows) {
+        "PATH"
+    } else if cfg!(target_os = "macos") {
+        "DYLD_LIBRARY_PATH"
+    } else {
+        "LD_LIBRARY_PATH"
+    };
+    let mut paths = vec![dir.to_path_buf()];
+    if let Some(existing) = std::env::var_os(var) {
+        paths.extend(std::env::split_paths(&existing));
+    }
+    Ok((var, std::env::join_paths(paths)?))
+}
+
+fn run_checked(cmd: &mut Command) -> Result<()> {
+    let status = cmd
+        .status()
+        .with_context(|| format!("spawn {:?}", cmd.get_program()))?;
+    ensure!(
+        status.success(),
+        "{:?} exited with {}",
+        cmd.get_program(),
+        status
+    );
+    Ok(())
+}
+
+/// `zkd examples …`.
+pub fn examples_cmd(action: ExamplesCmd) -> Result<()> {
+    match action {
+        ExamplesCmd::Run { examples, root } => {
+            let root = match root {
+                Some(root) => std::path::PathBuf::from(root),
+                None => std::env::current_dir()?,
+            };
+            let mut runner = Runner::new(&root)?;
+            let selected = if examples.is_empty() {
+                Example::ALL.to_vec()
+            } else {
+                examples
+            };
+            if !runner.run_all(&selected) {
+                output::exit(1);
+            }
+        }
+        ExamplesCmd::VerifyGolden { bless, root } => {
+            let root = match root {
+                Some(root) => std::path::PathBuf::from(root),
+                None => std::env::current_dir()?,
+            };
+            if !golden::verify_golden(&root, bless)? {
+                output::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
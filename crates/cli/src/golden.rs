@@ -0,0 +1,207 @@
+//! `zkd examples verify-golden`: proves every AIR under `examples/` with the
+//! configuration pinned in `examples/golden.lock` and compares the EVM digest
+//! `D` against the recorded one.
+//!
+//! Each lock entry pins backend, field, hash, FRI arity, profile and the
+//! public inputs, so a digest change means the prover (or the AIR) changed,
+//! not the environment. `--bless` rewrites the digests, keeping existing pins
+//! and pinning new AIRs from their `[meta]` (backend and profile fall back to
+//! `native@0.0` / `balanced`). Entries whose AIR no longer exists are dropped
+//! on bless and reported as stale otherwise.
+//!
+//! Prints one `PASS <air>`, `FAIL <air>: <why>` or `BLESS <air> <digest>` line
+//! per AIR, paths relative to `examples/`.
+
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use zkprov_corelib as core;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::proof::split_proof;
+use zkprov_corelib::registry;
+
+use super::output;
+
+pub const LOCK_FILE: &str = "golden.lock";
+const DEFAULT_BACKEND: &str = "native@0.0";
+const DEFAULT_PROFILE: &str = "balanced";
+const DEFAULT_FRI_ARITY: u32 = 2;
+/// Directories never searched for AIRs (build output, package caches).
+const SKIP_DIRS: &[&str] = &["build", "node_modules", "target"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenEntry {
+    /// AIR path relative to `examples/`, with `/` separators.
+    pub air: String,
+    pub backend: String,
+    pub field: String,
+    pub hash: String,
+    pub fri_arity: u32,
+    pub profile: String,
+    /// Public inputs JSON.
+    pub inputs: String,
+    /// `0x`-prefixed EVM digest `D`.
+    pub digest: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenLock {
+    #[serde(default, rename = "example")]
+    pub examples: Vec<GoldenEntry>,
+}
+
+impl GoldenLock {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let body = toml::to_string_pretty(self)?;
+        let text = format!(
+            "# Generated by `zkd examples verify-golden --bless`; edit pins, not digests.\n\n{}",
+            body
+        );
+        fs::write(path, text).with_context(|| format!("write {}", path.display()))
+    }
+}
+
+/// Every `.air` file below `examples`, as sorted `/`-separated relative paths.
+pub fn discover(examples: &Path) -> Result<Vec<String>> {
+    fn walk(dir: &Path, base: &Path, out: &mut Vec<String>) -> Result<()> {
+        for entry in fs::read_dir(dir).with_context(|| format!("read {}", dir.display()))? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if path.is_dir() {
+                if !name.starts_with('.') && !SKIP_DIRS.contains(&name) {
+                    walk(&path, base, out)?;
+                }
+            } else if path.extension().is_some_and(|e| e == "air") {
+                let rel = path.strip_prefix(base)?;
+                let parts: Vec<_> = rel.iter().map(|p| p.to_string_lossy()).collect();
+                out.push(parts.join("/"));
+            }
+        }
+        Ok(())
+    }
+    let mut out = Vec::new();
+    walk(examples, examples, &mut out)?;
+    out.sort();
+    Ok(out)
+}
+
+fn pin_from_meta(examples: &Path, air: &str) -> Result<GoldenEntry> {
+    let ir = core::air::parse_air_file(&examples.join(air))?;
+    Ok(GoldenEntry {
+        air: air.to_string(),
+        backend: ir
+            .meta
+            .backend
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BACKEND.to_string()),
+        field: ir.meta.field.clone(),
+        hash: ir.meta.hash.as_str().to_string(),
+        fri_arity: DEFAULT_FRI_ARITY,
+        profile: ir
+            .meta
+            .profile
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+        inputs: "{}".to_string(),
+        digest: String::new(),
+    })
+}
+
+/// Prove `entry.air` with the entry's pins and return its digest.
+pub fn prove_digest(examples: &Path, entry: &GoldenEntry) -> Result<String> {
+    let config = Config::new(
+        &entry.backend,
+        &entry.field,
+        &entry.hash,
+        entry.fri_arity,
+        false,
+        &entry.profile,
+    );
+    let ir = core::air::parse_air_file(&examples.join(&entry.air))?;
+    let inputs = PublicInputs::from_json(&entry.inputs)?;
+    let proof = core::prove(&config, &ir, &inputs)?.to_bytes();
+    let (header, body) = split_proof(&proof)?;
+    let digest = digest_D(&header, body);
+    Ok(format!(
+        "0x{}",
+        digest
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    ))
+}
+
+/// Check (or with `bless`, rewrite) `<root>/examples/golden.lock`. Returns
+/// whether every AIR matched.
+pub fn verify_golden(root: &Path, bless: bool) -> Result<bool> {
+    let examples: PathBuf = root.join("examples");
+    ensure!(
+        examples.is_dir(),
+        "'{}' has no examples/ directory (pass --root)",
+        root.display()
+    );
+    registry::ensure_builtins_registered();
+    let lock_path = examples.join(LOCK_FILE);
+    let lock = GoldenLock::load(&lock_path)?;
+    let mut pinned: BTreeMap<String, GoldenEntry> = lock
+        .examples
+        .into_iter()
+        .map(|e| (e.air.clone(), e))
+        .collect();
+
+    let mut ok = true;
+    let mut blessed = Vec::new();
+    for air in discover(&examples)? {
+        let entry = match pinned.remove(&air) {
+            Some(entry) => entry,
+            None if bless => pin_from_meta(&examples, &air)?,
+            None => {
+                out!("FAIL {}: not in {} (run with --bless)", air, LOCK_FILE);
+                ok = false;
+                continue;
+            }
+        };
+        let digest = match prove_digest(&examples, &entry) {
+            Ok(digest) => digest,
+            Err(e) => {
+                out!("FAIL {}: {:#}", air, e);
+                ok = false;
+                continue;
+            }
+        };
+        if bless {
+            out!("BLESS {} {}", air, digest);
+            blessed.push(GoldenEntry { digest, ..entry });
+        } else if digest == entry.digest {
+            out!("PASS {}", air);
+        } else {
+            out!("FAIL {}: digest {} != golden {}", air, digest, entry.digest);
+            ok = false;
+        }
+    }
+    if bless {
+        if ok {
+            GoldenLock { examples: blessed }.save(&lock_path)?;
+            out!("Wrote: {}", lock_path.display());
+        }
+    } else {
+        for stale in pinned.keys() {
+            out!("FAIL {}: AIR missing (stale {} entry)", stale, LOCK_FILE);
+            ok = false;
+        }
+    }
+    Ok(ok)
+}
@@ -0,0 +1,289 @@
+//! Registry, profile and AIR inspection: `zkd backend-ls`,
+//! `zkd profile-check`, `zkd air-diff`, `zkd io-schema` and `zkd io-check`.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use zkprov_corelib as core;
+use zkprov_corelib::air::diff::diff_air;
+use zkprov_corelib::air::types::CommitmentKind;
+use zkprov_corelib::air::{AirIr, AirProgram};
+use zkprov_corelib::air_bindings::Bindings;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::evm::pubio;
+use zkprov_corelib::io::TypedInputs;
+use zkprov_corelib::proof::pubio::pubio_hash;
+use zkprov_corelib::registry;
+use zkprov_corelib::trace::TraceShape;
+
+use super::{
+    output, read_to_string, AirDiffArgs, BackendLsArgs, IoCheckArgs, IoSchemaArgs, ProfileCheckArgs,
+};
+
+/// `zkd backend-ls`.
+pub fn backend_ls(args: BackendLsArgs) -> Result<()> {
+    let BackendLsArgs { verbose, json } = args;
+    let infos = core::list_backends();
+    if json {
+        output::data(&registry::snapshot())?;
+    } else if !verbose {
+        for b in infos {
+            match &b.deprecated {
+                Some(dep) => out!(
+                    "{}  recursion={}  deprecated(sunset={})",
+                    b.id,
+                    b.recursion,
+                    dep.sunset
+                ),
+                None => out!("{}  recursion={}", b.id, b.recursion),
+            }
+        }
+        for (alias, _) in registry::list_aliases() {
+            out!("{}", registry::alias_chain(&alias).join(" -> "));
+        }
+    } else {
+        for b in infos {
+            let caps = registry::get_backend_capabilities(b.id).expect("backend disappeared");
+            out!("{}", b.id);
+            if !b.aliases.is_empty() {
+                out!("  aliases: {}", b.aliases.join(", "));
+            }
+            if let Some(dep) = &b.deprecated {
+                match &dep.replacement {
+                    Some(r) => {
+                        out!("  deprecated: sunset {} (replacement: {})", dep.sunset, r)
+                    }
+                    None => out!("  deprecated: sunset {}", dep.sunset),
+                }
+            }
+            out!("  recursion: {}", caps.recursion);
+            out!("  lookups: {}", caps.lookups);
+            out!("  fields: {}", caps.fields.join(", "));
+            out!("  hashes: {}", caps.hashes.join(", "));
+            let arities = caps
+                .fri_arities
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            out!("  fri_arities: {}", arities);
+            if let Some(max) = caps.max_rows {
+                out!("  max_rows: {}", max);
+            }
+            if let Some(plugin) = core::plugin::info(b.id) {
+                out!(
+                    "  plugin: {} (abi v{})",
+                    plugin.path.as_deref().unwrap_or("<static>"),
+                    plugin.abi_version
+                );
+            }
+        }
+    }
+    if !json {
+        for failure in core::plugin::failures() {
+            eprintln!("⚠️ plugin {}: {}", failure.path, failure.error);
+        }
+    }
+    Ok(())
+}
+
+/// `zkd profile-check`.
+pub fn profile_check(args: ProfileCheckArgs) -> Result<()> {
+    let ProfileCheckArgs { json } = args;
+    let matrix = core::validate::profile_compat_matrix()?;
+    if json {
+        output::data(&matrix)?;
+    } else {
+        output::document(&render_compat_matrix(&matrix));
+    }
+    Ok(())
+}
+
+/// `zkd air-diff`.
+pub fn air_diff(args: AirDiffArgs) -> Result<()> {
+    let AirDiffArgs {
+        old,
+        new,
+        json,
+        fail_on_breaking,
+    } = args;
+    let before = core::air::parse_air_file(Path::new(&old))?;
+    let after = core::air::parse_air_file(Path::new(&new))?;
+    let diff = diff_air(&before, &after);
+    if json {
+        let mut out = serde_json::to_value(&diff)?;
+        out["breaking"] = diff.is_breaking().into();
+        output::data(&out)?;
+    } else if diff.is_empty() {
+        info!("✅ no semantic changes");
+    } else {
+        output::document(&diff.to_string());
+    }
+    if fail_on_breaking && diff.is_breaking() {
+        output::exit(1);
+    }
+    Ok(())
+}
+
+/// `zkd io-schema`.
+pub fn io_schema(args: IoSchemaArgs) -> Result<()> {
+    let IoSchemaArgs {
+        program_path,
+        pretty,
+        solidity,
+    } = args;
+    let air = AirProgram::load_from_file(&program_path)?;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    if solidity {
+        output::document(&pubio::solidity_struct(&ir));
+        return Ok(());
+    }
+    let schema = public_io_schema(&air, &ir);
+    if pretty {
+        output::data(&schema)?;
+    } else {
+        output::data_line(&schema)?;
+    }
+    Ok(())
+}
+
+/// `zkd io-check`.
+pub fn io_check(args: IoCheckArgs) -> Result<()> {
+    let IoCheckArgs {
+        program_path,
+        inputs_path,
+        undeclared_inputs,
+        allow_missing,
+    } = args;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let text = read_to_string(&inputs_path)?;
+    let value: serde_json::Value = serde_json::from_str(&text)
+        .with_context(|| format!("invalid inputs JSON '{}'", inputs_path))?;
+    let value =
+        core::air::public::resolve_bytes_refs(&ir.public_inputs, &value, ir.meta.hash.as_str())?
+            .unwrap_or(value);
+    let undeclared = undeclared_inputs.parse()?;
+    let typed = TypedInputs::parse(&ir.public_inputs, &value, undeclared)?;
+    if !allow_missing {
+        typed.require_all()?;
+    }
+    let bound = PublicInputs::from_json(value.to_string())?.bind(
+        pubio::PubIoEncoding::Json,
+        &ir,
+        undeclared,
+    )?;
+    output::data_line(&typed.canonical_json())?;
+    info!(
+        "✅ InputsValid declared={} supplied={} pubio_hash=0x{:016x}",
+        typed.len(),
+        typed.len() - typed.missing().len(),
+        pubio_hash(&bound)
+    );
+    Ok(())
+}
+
+/// Public I/O schema for integrators, derived from the parsed AIR and its
+/// commitment bindings.
+fn public_io_schema(air: &AirProgram, ir: &AirIr) -> serde_json::Value {
+    let shape = TraceShape::from_air(air);
+    let policy = Bindings::from_air(air).commitments;
+
+    let mut curves: Vec<&str> = Vec::new();
+    let bindings: Vec<serde_json::Value> = ir
+        .commitments
+        .iter()
+        .map(|binding| {
+            let mut entry = match &binding.kind {
+                CommitmentKind::Pedersen { curve } => {
+                    if !curves.contains(&curve.as_str()) {
+                        curves.push(curve);
+                    }
+                    serde_json::json!({ "kind": "pedersen", "curve": curve })
+                }
+                CommitmentKind::PoseidonCommit => serde_json::json!({ "kind": "poseidon_commit" }),
+                CommitmentKind::KeccakCommit => serde_json::json!({ "kind": "keccak_commit" }),
+            };
+            entry["public_inputs"] = serde_json::json!(binding.public_inputs);
+            entry
+        })
+        .collect();
+    let pedersen = ir
+        .commitments
+        .iter()
+        .any(|b| matches!(b.kind, CommitmentKind::Pedersen { .. }));
+
+    serde_json::json!({
+        "program": air.meta.name,
+        "field": air.meta.field,
+        "hash": format!("{:?}", air.meta.hash).to_lowercase(),
+        "degree_hint": ir.degree_hint,
+        "rows_hint": air.rows_hint,
+        "trace": {
+            "rows": shape.rows,
+            "cols": shape.cols,
+            "const_cols": shape.const_cols,
+            "periodic_cols": shape.periodic_cols
+        },
+        "public_inputs": {
+            "kind": "json",
+            "binding": "raw",
+            "declared": ir.public_inputs,
+        },
+        "commitments": {
+            "pedersen": pedersen,
+            "curves": curves,
+            "no_r_reuse": policy.no_r_reuse.unwrap_or(false),
+            "bindings": bindings,
+        }
+    })
+}
+
+/// Profiles as rows, backends as columns, then one line per incompatibility.
+fn render_compat_matrix(matrix: &[core::validate::ProfileCompat]) -> String {
+    let mut backends: Vec<&str> = Vec::new();
+    let mut profiles: Vec<&str> = Vec::new();
+    for cell in matrix {
+        if !backends.contains(&cell.backend_id.as_str()) {
+            backends.push(&cell.backend_id);
+        }
+        if !profiles.contains(&cell.profile_id.as_str()) {
+            profiles.push(&cell.profile_id);
+        }
+    }
+    let first = profiles
+        .iter()
+        .map(|p| p.len())
+        .chain(["profile".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{:<first$}", "profile");
+    for b in &backends {
+        out.push_str(&format!("  {}", b));
+    }
+    out.push('\n');
+    for p in &profiles {
+        let mut row = format!("{:<first$}", p);
+        for b in &backends {
+            let ok = matrix
+                .iter()
+                .find(|c| c.profile_id == *p && c.backend_id == *b)
+                .is_some_and(|c| c.compatible);
+            row.push_str(&format!(
+                "  {:<width$}",
+                if ok { "ok" } else { "✗" },
+                width = b.len()
+            ));
+        }
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    for cell in matrix.iter().filter(|c| !c.compatible) {
+        for r in &cell.reasons {
+            out.push_str(&format!(
+                "✗ {} on {} [{}]: {}\n",
+                cell.profile_id, cell.backend_id, r.check, r.reason
+            ));
+        }
+    }
+    out
+}
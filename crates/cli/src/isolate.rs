@@ -0,0 +1,252 @@
+//! Isolated proving: run the prover in a child `zkd` process under memory and
+//! CPU rlimits so a backend OOM, panic or hang surfaces as a structured error.
+//!
+//! Pipe protocol (one JSON document each way):
+//! parent -> child stdin:  `WorkerRequest`
+//! child  -> parent stdout: `WorkerResponse`
+//! A child that exits without a well-formed response is classified from its
+//! exit status (panic, signal, rlimit) by the parent.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use zkprov_corelib::config::Config;
+
+/// Hidden subcommand the parent uses to re-invoke itself as a worker.
+pub const WORKER_SUBCOMMAND: &str = "prove-worker";
+
+/// Exit status of a Rust process that panicked.
+const PANIC_EXIT_CODE: i32 = 101;
+
+#[derive(Debug, Clone, Copy)]
+pub struct IsolateLimits {
+    /// Address-space limit for the worker, in MiB.
+    pub mem_mb: u64,
+    /// CPU-time limit (rlimit) and wall-clock deadline, in seconds.
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkerRequest {
+    pub config: Config,
+    pub inputs: String,
+    pub program_path: String,
+    pub trace_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum WorkerResponse {
+    Ok { proof_hex: String },
+    Err { message: String },
+}
+
+/// Why an isolated prove did not produce a proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IsolateFailure {
+    /// The prover ran to completion and reported an error.
+    Prover(String),
+    /// The worker panicked.
+    Panic(String),
+    /// The worker was killed by a signal (OOM abort, SIGXCPU, ...).
+    Signal(i32),
+    /// The worker exceeded the wall-clock deadline and was killed.
+    Timeout(u64),
+    /// The worker could not be started or spoke an invalid protocol.
+    Protocol(String),
+}
+
+impl IsolateFailure {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            IsolateFailure::Prover(_) => "prover",
+            IsolateFailure::Panic(_) => "panic",
+            IsolateFailure::Signal(_) => "signal",
+            IsolateFailure::Timeout(_) => "timeout",
+            IsolateFailure::Protocol(_) => "protocol",
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "kind": self.kind(), "detail": self.to_string() })
+    }
+}
+
+impl fmt::Display for IsolateFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IsolateFailure::Prover(msg) => write!(f, "prover error: {}", msg),
+            IsolateFailure::Panic(msg) => write!(f, "worker panicked: {}", msg),
+            IsolateFailure::Signal(sig) => write!(f, "worker killed by signal {}", sig),
+            IsolateFailure::Timeout(secs) => write!(f, "worker exceeded {}s deadline", secs),
+            IsolateFailure::Protocol(msg) => write!(f, "worker protocol error: {}", msg),
+        }
+    }
+}
+
+/// Parent side: spawn the worker, send the request, and collect the proof.
+pub fn prove_isolated(
+    req: &WorkerRequest,
+    limits: IsolateLimits,
+) -> std::result::Result<Vec<u8>, IsolateFailure> {
+    let exe = std::env::current_exe()
+        .map_err(|e| IsolateFailure::Protocol(format!("cannot locate zkd binary: {}", e)))?;
+    let mut cmd = Command::new(exe);
+    cmd.arg(WORKER_SUBCOMMAND)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    apply_rlimits(&mut cmd, limits);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| IsolateFailure::Protocol(format!("failed to spawn worker: {}", e)))?;
+
+    let payload = serde_json::to_vec(req).map_err(|e| IsolateFailure::Protocol(e.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A worker that dies early closes the pipe; its exit status explains why.
+        let _ = stdin.write_all(&payload);
+    }
+    let stdout = spawn_reader(child.stdout.take());
+    let stderr = spawn_reader(child.stderr.take());
+
+    let deadline = Instant::now() + Duration::from_secs(limits.timeout_secs);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(IsolateFailure::Timeout(limits.timeout_secs));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(e) => return Err(IsolateFailure::Protocol(e.to_string())),
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    match serde_json::from_slice::<WorkerResponse>(&stdout) {
+        Ok(WorkerResponse::Ok { proof_hex }) => super::hex_to_bytes(&proof_hex)
+            .map_err(|e| IsolateFailure::Protocol(format!("bad proof encoding: {}", e))),
+        Ok(WorkerResponse::Err { message }) => Err(IsolateFailure::Prover(message)),
+        Err(_) => Err(classify_exit(status, &stderr)),
+    }
+}
+
+fn spawn_reader<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+fn classify_exit(status: ExitStatus, stderr: &[u8]) -> IsolateFailure {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(sig) = status.signal() {
+            return IsolateFailure::Signal(sig);
+        }
+    }
+    let tail = String::from_utf8_lossy(stderr).trim().to_string();
+    match status.code() {
+        Some(PANIC_EXIT_CODE) => IsolateFailure::Panic(tail),
+        Some(code) => IsolateFailure::Protocol(format!("worker exited with {}: {}", code, tail)),
+        None => IsolateFailure::Protocol("worker exited without status".to_string()),
+    }
+}
+
+#[cfg(unix)]
+fn apply_rlimits(cmd: &mut Command, limits: IsolateLimits) {
+    use std::os::unix::process::CommandExt;
+    let mem = rlimit(limits.mem_mb.saturating_mul(1024 * 1024));
+    let cpu = rlimit(limits.timeout_secs);
+    // SAFETY: the closure runs between fork and exec and only calls
+    // async-signal-safe `setrlimit` on stack-local structs.
+    unsafe {
+        cmd.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &mem) != 0
+                || libc::setrlimit(libc::RLIMIT_CPU, &cpu) != 0
+            {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(unix)]
+fn rlimit(value: u64) -> libc::rlimit {
+    libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    }
+}
+
+#[cfg(not(unix))]
+fn apply_rlimits(_cmd: &mut Command, _limits: IsolateLimits) {
+    // rlimits are unix-only; the wall-clock deadline still applies.
+}
+
+/// Memory the kernel reports as available for new allocations
+/// (`MemAvailable` in `/proc/meminfo`); `None` where that is not readable.
+pub fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemAvailable:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib.saturating_mul(1024))
+}
+
+/// Worker side: read one request from stdin, prove, and write one response.
+pub fn run_worker() -> Result<()> {
+    let mut input = Vec::new();
+    std::io::stdin().read_to_end(&mut input)?;
+    let req: WorkerRequest =
+        serde_json::from_slice(&input).map_err(|e| anyhow!("invalid worker request: {}", e))?;
+
+    inject_fault();
+
+    let result = match &req.trace_path {
+        Some(path) => super::trace::read_trace(path).and_then(|t| {
+            super::prove::prove_with_registry(&req.config, &req.program_path, &req.inputs, Some(&t))
+        }),
+        None => {
+            super::prove::prove_with_registry(&req.config, &req.program_path, &req.inputs, None)
+        }
+    };
+    let resp = match result {
+        Ok(proof) => WorkerResponse::Ok {
+            proof_hex: super::bytes_to_hex(&proof),
+        },
+        Err(e) => WorkerResponse::Err {
+            message: format!("{:#}", e),
+        },
+    };
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer(&mut stdout, &resp)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Debug builds honour `ZKD_ISOLATE_FAULT=panic|abort|hang` so tests can
+/// exercise crash containment.
+fn inject_fault() {
+    #[cfg(debug_assertions)]
+    match std::env::var("ZKD_ISOLATE_FAULT").as_deref() {
+        Ok("panic") => panic!("injected worker fault"),
+        Ok("abort") => std::process::abort(),
+        Ok("hang") => loop {
+            thread::sleep(Duration::from_secs(1));
+        },
+        _ => {}
+    }
+}
@@ -1,38 +1,82 @@
 use anyhow::{anyhow, Context, Result};
-use clap::{Args, Parser, Subcommand};
-use std::convert::TryFrom;
+use clap::builder::PossibleValuesParser;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
 use std::fs;
 use std::path::Path;
-use std::process;
-use zkprov_backend_native::{native_prove, native_verify};
+#[cfg(feature = "prover")]
+use std::time::Duration;
+use zkprov_backend_ministark::ensure_ministark_registered;
+use zkprov_backend_native::ensure_native_registered;
 use zkprov_corelib as core;
-use zkprov_corelib::air::AirProgram;
-use zkprov_corelib::air_bindings::Bindings;
-use zkprov_corelib::config::Config;
-use zkprov_corelib::evm::digest::digest_D;
-use zkprov_corelib::gadgets::commitment::{
-    Comm32, CommitmentScheme32, PedersenParams, PedersenPlaceholder, Witness,
-};
-use zkprov_corelib::proof::ProofHeader;
+use zkprov_corelib::air::rows::RowsHint;
+use zkprov_corelib::air::{AirIr, AirProgram};
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::buildinfo;
+use zkprov_corelib::config::{AirHints, Config};
+#[cfg(feature = "prover")]
+use zkprov_corelib::deadline::parse_duration;
+use zkprov_corelib::profile::parse_overrides;
+use zkprov_corelib::proof::container::{is_container, ProofContainer};
 use zkprov_corelib::registry;
 use zkprov_corelib::trace::TraceShape;
-use zkprov_corelib::validate::{validate_air_against_backend, validate_config};
-use zkprov_corelib::validation::Validator;
+use zkprov_corelib::validation::ValidationWarning;
+use zkprov_ffi_types::{Envelope, VersionMeta, VersionReport};
 
+// First, so every module sees its macros.
+#[macro_use]
+mod output;
+
+mod armor;
+mod audit;
+#[cfg(feature = "prover")]
+mod conformance;
+mod crypto;
+mod evm;
+#[cfg(feature = "prover")]
+mod examples;
+#[cfg(feature = "prover")]
+mod golden;
+mod inspect;
+#[cfg(feature = "prover")]
+mod isolate;
+mod pkg;
+#[cfg(feature = "prover")]
+mod prove;
+mod qr;
+mod storage;
+mod trace;
+mod tsa;
+mod verify;
+
+#[cfg(feature = "prover")]
+const EXIT_INVALID_CONFIG: i32 = 2;
 const EXIT_CORRUPT_PROOF: i32 = 4;
+#[cfg(feature = "prover")]
+const EXIT_ISOLATE_FAILURE: i32 = 5;
+#[cfg(feature = "prover")]
+const EXIT_TIMED_OUT: i32 = 6;
 
 #[derive(Parser)]
-#[command(name = "zkd", version, about = "ZKProv CLI")]
+#[command(name = env!("CARGO_BIN_NAME"), version, about = "ZKProv CLI")]
 struct Cli {
+    /// Print only the command's result line on stdout (digest, `ok`/`fail`, hex)
+    #[arg(short = 'q', long = "quiet", global = true)]
+    quiet: bool,
+    /// Output format: text, or one JSON envelope per command on stdout
+    #[arg(long = "format", global = true, value_enum, default_value = "text")]
+    format: output::Format,
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Args, Debug, Clone)]
 struct CommonCfg {
-    /// Backend id, e.g. native@0.0
-    #[arg(long = "backend")]
-    backend_id: String,
+    /// Backend id, e.g. native@0.0, or `auto` for the first backend supporting
+    /// the requested field, hash and FRI arity. Optional with
+    /// --respect-air-hints when the AIR names one
+    #[arg(long = "backend", required_unless_present = "air_hints")]
+    backend_id: Option<String>,
     /// Field id, e.g. Prime254
     #[arg(long = "field")]
     field: String,
@@ -45,106 +89,778 @@ struct CommonCfg {
     /// Require recursion capability (fails if backend doesn't support)
     #[arg(long = "need-recursion", default_value_t = false)]
     need_recursion: bool,
-    /// Profile id, e.g. balanced
-    #[arg(long = "profile")]
-    profile_id: String,
+    /// Profile id, e.g. balanced. Optional with --respect-air-hints when the
+    /// AIR names one
+    #[arg(long = "profile", required_unless_present = "air_hints")]
+    profile_id: Option<String>,
+    /// Override a profile parameter (repeatable), e.g. fri_queries=40
+    #[arg(long = "profile-override", value_name = "KEY=VALUE")]
+    profile_overrides: Vec<String>,
+    /// Public IO encoding bound by the proof: json (v1) or typed (ABI v2)
+    #[arg(long = "pubio-encoding", default_value = "json", value_parser = ["json", "typed"])]
+    pubio_encoding: String,
+    /// Input keys the AIR does not declare as public inputs: ignore (left
+    /// out of the binding) or reject
+    #[arg(long = "undeclared-inputs", default_value = "ignore", value_parser = ["ignore", "reject"])]
+    undeclared_inputs: String,
+    /// Backend-specific options as a JSON object, checked against the
+    /// backend's options schema, e.g. '{"batching":"linear"}'
+    #[arg(long = "backend-options", value_name = "JSON")]
+    backend_options: Option<String>,
+    /// Take the backend and profile the AIR's meta names when they are not
+    /// given; `=strict` also refuses --backend/--profile that contradict it
+    #[arg(
+        long = "respect-air-hints",
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "respect",
+        value_parser = ["respect", "strict"]
+    )]
+    air_hints: Option<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// List available backends
-    BackendLs {
-        /// Show full capability matrix
-        #[arg(short, long)]
-        verbose: bool,
-    },
+    BackendLs(BackendLsArgs),
     /// List available profiles
     ProfileLs,
+    /// Cross-check every profile against every backend (arity, rows_max, hash)
+    ProfileCheck(ProfileCheckArgs),
+    /// Semantic diff of two AIRs, marking digest-breaking vs. cosmetic changes
+    AirDiff(AirDiffArgs),
     /// Print the public I/O schema derived from the program AIR
-    IoSchema {
-        /// Program AIR path (.air TOML)
-        #[arg(short = 'p', long = "program")]
-        program_path: String,
-        /// Emit JSON (default) or pretty JSON
-        #[arg(long = "pretty", default_value_t = false)]
-        pretty: bool,
-    },
+    IoSchema(IoSchemaArgs),
+    /// Check an inputs JSON against the program's declared public inputs and
+    /// print it in canonical form
+    IoCheck(IoCheckArgs),
+    #[cfg(feature = "prover")]
     /// Prove: read inputs JSON, produce proof blob
-    Prove {
-        /// Program AIR path (.air TOML)
-        #[arg(short = 'p', long = "program")]
-        program_path: String,
-        /// Inputs JSON path
-        #[arg(short = 'i', long = "inputs")]
-        inputs_path: String,
-        /// Output proof file path
-        #[arg(short = 'o', long = "output")]
-        proof_out: String,
-        /// Print stats row/col/body_len after success
-        #[arg(long = "stats", default_value_t = false)]
-        stats: bool,
-        #[command(flatten)]
-        cfg: CommonCfg,
-    },
+    Prove(ProveArgs),
     /// Verify: read inputs JSON and proof blob, return success/failure
+    Verify(VerifyArgs),
+    /// Localize what is inconsistent in a rejected proof: magic, version,
+    /// each header binding, body length and body content
+    Triage(TriageArgs),
+    /// Validate: verify the proof, run header and commitment checks and emit a structured report
+    Validate(ValidateArgs),
+    #[cfg(feature = "prover")]
+    /// Compute a commitment of an AIR commitment kind for msg/blind (hex).
+    Commit(CommitArgs),
+    /// Verify opening against a commitment (all hex).
+    OpenCommit(OpenCommitArgs),
+    #[cfg(feature = "prover")]
+    /// Generate a fresh 32-byte blinding factor (hex) for commit/open-commit.
+    BlindNew(BlindNewArgs),
+    /// Print deterministic hash, commitment and Merkle test vectors as JSON
+    /// for checking other-language implementations
+    Vectors(VectorsArgs),
+    /// ABI-encode public inputs for on-chain use and print their Keccak digest.
+    EvmPubio(EvmPubioArgs),
+    /// Compute the Keccak digest (D) used by the EVM verifier from a proof blob.
+    EvmDigest(EvmDigestArgs),
+    /// Write the Solidity verifier fixtures (meta.json, body.bin, digest.hex,
+    /// meta.abi, body.abi) for a proof into a directory.
+    EvmFixtures(EvmFixturesArgs),
+    /// Armor a binary proof as base64 or hex text, checking it against the
+    /// header's body_len
+    ProofEncode(ProofEncodeArgs),
+    /// Decode base64 or hex armor back into a binary proof, checking it
+    /// against the header's body_len
+    ProofDecode(ProofDecodeArgs),
+    /// Split a proof into a numbered sequence of QR codes (SVG plus scanned
+    /// text) for air-gapped transfer
+    ProofQr(ProofQrArgs),
+    /// Rebuild a proof from scanned proof-qr chunk texts, in any order
+    ProofQrAssemble(ProofQrAssembleArgs),
+    #[cfg(feature = "prover")]
+    /// Convert a JSON trace into the binary columnar format (.zkt)
+    TraceImport(TraceImportArgs),
+    #[cfg(feature = "prover")]
+    /// Convert a binary trace (.zkt) back to JSON
+    TraceExport(TraceExportArgs),
+    /// Pretty-print trace rows for a program and flag transition constraint violations
+    TraceDump(TraceDumpArgs),
+    /// Evaluate every transition and boundary constraint on a trace without
+    /// proving; exits 1 and lists the first violations when any fail
+    Check(CheckArgs),
+    /// Store or fetch proofs in the artifact store configured in zkd.toml
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveCmd,
+    },
+    /// Check the hash chain of a prove/verify audit log
+    Audit {
+        #[command(subcommand)]
+        action: AuditCmd,
+    },
+    /// Build, install and list `.zkpkg` program packages
+    Pkg {
+        #[command(subcommand)]
+        action: PkgCmd,
+    },
+    /// Print the zkd version; --verbose adds commit, target, profile, rustc and features
+    Version {
+        /// Include build provenance
+        #[arg(short = 'v', long = "verbose", default_value_t = false)]
+        verbose: bool,
+        /// Emit build info as JSON (implies --verbose)
+        #[arg(long = "json", default_value_t = false)]
+        json: bool,
+    },
+    #[cfg(feature = "prover")]
+    /// Build and run the bundled examples headlessly (C, Flutter, EVM fixtures)
+    Examples {
+        #[command(subcommand)]
+        action: ExamplesCmd,
+    },
+    #[cfg(feature = "prover")]
+    /// Run a JSON conformance suite against zkd or, after `--`, an adapter command
+    Conformance(ConformanceArgs),
+    #[cfg(feature = "prover")]
+    /// Report nondeterminism sources of a backend and this build, then prove
+    /// under several thread counts and check that every digest matches
+    DeterminismAudit(DeterminismAuditArgs),
+    #[cfg(feature = "prover")]
+    /// Internal: isolated prove worker speaking the --isolate pipe protocol
+    #[command(name = "prove-worker", hide = true)]
+    ProveWorker,
+    /// Print a shell completion script (backends/profiles enumerated at generation time)
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Generate man pages for zkd and its subcommands
+    Man {
+        /// Output directory for the generated `.1` pages
+        #[arg(short = 'o', long = "out-dir")]
+        out_dir: String,
+    },
+    /// Compute the EIP-712 typed-data hash wallets sign to submit a proof on-chain.
+    EvmTypedHash(EvmTypedHashArgs),
+}
+
+#[derive(Args)]
+struct BackendLsArgs {
+    /// Show full capability matrix
+    #[arg(short, long)]
+    verbose: bool,
+    /// Emit the whole registry (capabilities, aliases, deprecations) as JSON
+    #[arg(long = "json", default_value_t = false, conflicts_with = "verbose")]
+    json: bool,
+}
+
+#[derive(Args)]
+struct ProfileCheckArgs {
+    /// Emit the matrix as JSON
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct AirDiffArgs {
+    /// AIR before the change
+    old: String,
+    /// AIR after the change
+    new: String,
+    /// Emit the diff as JSON
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+    /// Exit 1 when any change is digest-breaking
+    #[arg(long = "fail-on-breaking", default_value_t = false)]
+    fail_on_breaking: bool,
+}
+
+#[derive(Args)]
+struct IoSchemaArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Emit JSON (default) or pretty JSON
+    #[arg(long = "pretty", default_value_t = false)]
+    pretty: bool,
+    /// Print the Solidity struct for the typed public IO encoding instead
+    #[arg(long = "solidity", default_value_t = false, conflicts_with = "pretty")]
+    solidity: bool,
+}
+
+#[derive(Args)]
+struct IoCheckArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Keys the AIR does not declare: ignore or reject
+    #[arg(long = "undeclared-inputs", default_value = "ignore", value_parser = ["ignore", "reject"])]
+    undeclared_inputs: String,
+    /// Accept inputs that leave declared inputs out (bound as absent)
+    #[arg(long = "allow-missing", default_value_t = false)]
+    allow_missing: bool,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct ProveArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Output proof file path
+    #[arg(short = 'o', long = "output")]
+    proof_out: String,
+    /// Print stats row/col/body_len after success
+    #[arg(long = "stats", default_value_t = false)]
+    stats: bool,
+    /// Prove from an externally generated trace (.zkt, or JSON as taken
+    /// by trace-import)
+    #[arg(long = "trace")]
+    trace_path: Option<String>,
+    /// Experimental: reuse the trace cached at this path by the previous
+    /// run, recommitting only changed columns, and update the cache
+    #[arg(
+        long = "incremental-cache",
+        value_name = "PATH",
+        requires = "trace_path",
+        conflicts_with = "isolate"
+    )]
+    incremental_cache: Option<String>,
+    /// Run the prover in a child process under memory/CPU limits
+    #[arg(long = "isolate", default_value_t = false)]
+    isolate: bool,
+    /// Wall-clock budget (e.g. 90s, 1500ms, 5m); past it the prover stops
+    /// at the next phase boundary and exits 6 with the phases completed
+    #[arg(
+        long = "deadline",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        conflicts_with = "isolate"
+    )]
+    deadline: Option<Duration>,
+    /// Record build provenance (commit, target, features) in the manifest
+    #[arg(long = "build-info", default_value_t = false)]
+    build_info: bool,
+    /// Abort before proving if the estimated memory exceeds this many MiB
+    /// (default: available RAM, or the --isolate limit)
+    #[arg(long = "max-mem", value_name = "MiB")]
+    max_mem_mb: Option<u64>,
+    /// Obtain an RFC 3161 timestamp over SHA-256(D) from this TSA and
+    /// store the reply next to the proof as `<proof>.tsr`
+    #[arg(long = "timestamp-url", value_name = "URL")]
+    timestamp_url: Option<String>,
+    /// Also write the backend-native proof (the body without the zkd
+    /// header) to this path, for the backend's own verifiers
+    #[arg(long = "emit-native-proof", value_name = "PATH")]
+    native_out: Option<String>,
+    /// Upload the proof and its sidecars to the artifact store configured
+    /// in zkd.toml, keyed by the proof digest
+    #[arg(long = "archive", default_value_t = false)]
+    archive: bool,
+    /// zkd.toml path for --archive (default: $ZKD_CONFIG, else ./zkd.toml)
+    #[arg(long = "config", value_name = "PATH", requires = "archive")]
+    config_path: Option<String>,
+    /// Append a hash-chained audit entry to this log (default: $ZKD_AUDIT_LOG)
+    #[arg(long = "audit-log", value_name = "PATH")]
+    audit_log: Option<String>,
+    /// Memory limit for --isolate, in MiB
+    #[arg(long = "isolate-mem-mb", default_value_t = 4096, requires = "isolate")]
+    isolate_mem_mb: u64,
+    /// CPU and wall-clock limit for --isolate, in seconds
+    #[arg(
+        long = "isolate-timeout-secs",
+        default_value_t = 600,
+        requires = "isolate"
+    )]
+    isolate_timeout_secs: u64,
+    /// Choose fri_queries / grind_bits for the profile's lambda_bits
+    /// within a grinding budget, recorded in the manifest
+    #[arg(long = "adaptive-queries", default_value_t = false)]
+    adaptive_queries: bool,
+    /// Grinding time budget for --adaptive-queries (e.g. 250ms, 2s)
+    #[arg(
+        long = "grind-budget",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        requires = "adaptive_queries"
+    )]
+    grind_budget: Option<Duration>,
+    /// Time grinding on this host for --adaptive-queries instead of the
+    /// fixed reference cost; the choice is then not reproducible
+    #[arg(
+        long = "measure-grind",
+        default_value_t = false,
+        requires = "adaptive_queries"
+    )]
+    measure_grind: bool,
+    /// Write a proof container carrying the canonical public inputs, so
+    /// `verify` needs no -i
+    #[arg(long = "embed-inputs", default_value_t = false)]
+    embed_inputs: bool,
+    #[command(flatten)]
+    cfg: CommonCfg,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path (default: the inputs embedded in the proof)
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: Option<String>,
+    /// Proof file path; repeat with --quorum to verify several proofs
+    #[arg(short = 'P', long = "proof", required = true)]
+    proof_in: Vec<String>,
+    /// Succeed when at least K of the given proofs verify and agree,
+    /// each with the backend and profile named in its header
+    #[arg(
+        long = "quorum",
+        value_name = "K",
+        conflicts_with_all = ["stats", "check_timestamp", "policy"]
+    )]
+    quorum: Option<usize>,
+    /// Print stats row/col/body_len after success
+    #[arg(long = "stats", default_value_t = false)]
+    stats: bool,
+    /// Print the result (or failure diagnosis) as JSON on stdout
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+    /// Also require a valid `<proof>.tsr` timestamp over this proof's
+    /// digest, signed under --tsa-ca
+    #[arg(long = "check-timestamp", default_value_t = false, requires = "tsa_ca")]
+    check_timestamp: bool,
+    /// Trusted TSA certificates or CAs (PEM) that `<proof>.tsr` must be
+    /// signed under, for --check-timestamp and a policy's max_proof_age
+    #[arg(long = "tsa-ca", value_name = "PEM", conflicts_with = "quorum")]
+    tsa_ca: Option<String>,
+    /// With --stats, also time ABI-encoding the proof for the EVM verifier
+    #[arg(long = "stats-abi", default_value_t = false, requires = "stats")]
+    stats_abi: bool,
+    /// Also require the proof to satisfy a relying-party policy file
+    /// (accepted programs, backends, profile strength, signers, age)
+    #[arg(long = "policy", value_name = "POLICY.toml")]
+    policy: Option<String>,
+    /// Append a hash-chained audit entry to this log (default: $ZKD_AUDIT_LOG)
+    #[arg(long = "audit-log", value_name = "PATH", conflicts_with = "quorum")]
+    audit_log: Option<String>,
+    /// Determinism manifest written by `zkd prove`: its profile overrides
+    /// are applied, adaptive query choices are checked against the
+    /// resulting config's profile, and its digest must match the proof
+    #[arg(long = "manifest", value_name = "PATH", conflicts_with = "quorum")]
+    manifest: Option<String>,
+    #[command(flatten)]
+    cfg: CommonCfg,
+}
+
+#[derive(Args)]
+struct TriageArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path the proof should bind
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Proof file path
+    #[arg(short = 'P', long = "proof")]
+    proof_in: String,
+    /// Print the per-region report as JSON
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+    #[command(flatten)]
+    cfg: CommonCfg,
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Proof file path
+    #[arg(short = 'P', long = "proof")]
+    proof_in: String,
+    /// Output directory for validation reports
+    #[arg(short = 'o', long = "output")]
+    output_dir: String,
+    /// Previously used blinds, one hex per line (`#` comments); turns on
+    /// no_r_reuse and fails the commitment check on a listed blind
+    #[arg(long = "known-blinds", value_name = "FILE")]
+    known_blinds: Option<String>,
+    #[command(flatten)]
+    cfg: CommonCfg,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct CommitArgs {
+    /// pedersen (placeholder; bare hex), poseidon_commit (0x Prime254
+    /// element) or keccak_commit (0x bytes32, 32-byte blind)
+    #[arg(long = "kind", default_value = "pedersen", value_parser = ["pedersen", "poseidon_commit", "keccak_commit"])]
+    kind: String,
+    /// Commitment hash; required for pedersen, implied by the other kinds
+    #[arg(long = "hash")]
+    hash_id: Option<String>,
+    #[arg(long = "msg-hex")]
+    msg_hex: String,
+    #[arg(long = "blind-hex")]
+    blind_hex: String,
+}
+
+#[derive(Args)]
+struct OpenCommitArgs {
+    #[arg(long = "kind", default_value = "pedersen", value_parser = ["pedersen", "poseidon_commit", "keccak_commit"])]
+    kind: String,
+    #[arg(long = "hash")]
+    hash_id: Option<String>,
+    #[arg(long = "msg-hex")]
+    msg_hex: String,
+    #[arg(long = "blind-hex")]
+    blind_hex: String,
+    #[arg(long = "commit-hex")]
+    commit_hex: String,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct BlindNewArgs {
+    /// Derivation label (e.g. "orders/42/amount"); only used with --master-file
+    #[arg(long = "label", requires = "master_file")]
+    label: Option<String>,
+    /// File holding a hex master secret (>= 32 bytes) for reproducible HKDF
+    /// derivation; the OS CSPRNG is used when omitted
+    #[arg(long = "master-file")]
+    master_file: Option<String>,
+}
+
+#[derive(Args)]
+struct VectorsArgs {
+    /// Write to this file instead of stdout
+    #[arg(short = 'o', long = "output")]
+    output: Option<String>,
+    /// Pretty-print the JSON
+    #[arg(long = "pretty", default_value_t = false)]
+    pretty: bool,
+}
+
+#[derive(Args)]
+struct EvmPubioArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// json (v1: JSON text as bytes) or typed (v2: declared inputs as an ABI tuple)
+    #[arg(long = "encoding", default_value = "typed", value_parser = ["json", "typed"])]
+    encoding: String,
+    /// Write the ABI-encoded public IO to this path
+    #[arg(long = "abi-out")]
+    abi_out: Option<String>,
+}
+
+#[derive(Args)]
+struct EvmDigestArgs {
+    /// Proof file path (repeat with --aggregate to batch several proofs)
+    #[arg(short = 'P', long = "proof", required = true)]
+    proof_paths: Vec<String>,
+    /// Emit the Merkle root over all proof digests for the aggregator verifier
+    #[arg(long = "aggregate", default_value_t = false)]
+    aggregate: bool,
+    /// Include per-proof digests in the aggregate output
+    #[arg(long = "with-leaves", default_value_t = false, requires = "aggregate")]
+    with_leaves: bool,
+    /// Write the ABI-encoded aggregate calldata to this path
+    #[arg(long = "abi-out", requires = "aggregate")]
+    abi_out: Option<String>,
+}
+
+#[derive(Args)]
+struct EvmFixturesArgs {
+    /// Proof file path
+    #[arg(short = 'P', long = "proof")]
+    proof_path: String,
+    /// Inputs JSON the proof was made over
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Output directory (created if missing)
+    #[arg(short = 'o', long = "out-dir")]
+    out_dir: String,
+    /// Public IO encoding the proof binds: json (v1) or typed (ABI v2)
+    #[arg(long = "pubio-encoding", default_value = "json", value_parser = ["json", "typed"])]
+    pubio_encoding: String,
+    /// Program AIR path, needed to re-encode inputs for --pubio-encoding
+    /// typed, and to bind declared inputs under json
+    #[arg(
+        short = 'p',
+        long = "program",
+        required_if_eq("pubio_encoding", "typed")
+    )]
+    program_path: Option<String>,
+}
+
+#[derive(Args)]
+struct ProofEncodeArgs {
+    /// Text encoding
+    #[arg(long = "armor", value_enum)]
+    armor: armor::Armor,
+    /// Proof file path (`-` for stdin)
+    #[arg(short = 'P', long = "proof")]
+    proof_path: String,
+    /// Output path (stdout when omitted)
+    #[arg(short = 'o', long = "output")]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct ProofDecodeArgs {
+    /// Text encoding
+    #[arg(long = "armor", value_enum)]
+    armor: armor::Armor,
+    /// Armored input path (`-` for stdin)
+    #[arg(short = 'i', long = "input")]
+    input: String,
+    /// Output proof path (`-` for stdout)
+    #[arg(short = 'o', long = "output")]
+    output: String,
+}
+
+#[derive(Args)]
+struct ProofQrArgs {
+    /// Proof file path
+    #[arg(short = 'P', long = "proof")]
+    proof_path: String,
+    /// Output directory for chunk-NNN.svg / chunk-NNN.txt
+    #[arg(short = 'o', long = "out-dir")]
+    out_dir: String,
+    /// Base64 characters per QR code
+    #[arg(long = "chunk-size", default_value_t = 800)]
+    chunk_size: usize,
+}
+
+#[derive(Args)]
+struct ProofQrAssembleArgs {
+    /// Chunk text files or directories of `*.txt` (one chunk per line)
+    #[arg(required = true)]
+    inputs: Vec<String>,
+    /// Output proof path
+    #[arg(short = 'o', long = "output")]
+    output: String,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct TraceImportArgs {
+    /// Trace JSON path ({"field": .., "columns": [[..], ..]})
+    #[arg(short = 'i', long = "input")]
+    input: String,
+    /// Output .zkt path
+    #[arg(short = 'o', long = "output")]
+    output: String,
+    /// Column compression: none | rle
+    #[arg(long = "compress", default_value = "none")]
+    compress: String,
+    /// Check the trace's shape against this AIR before writing it
+    #[arg(short = 'p', long = "program")]
+    program_path: Option<String>,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct TraceExportArgs {
+    /// Trace .zkt path
+    #[arg(short = 'i', long = "input")]
+    input: String,
+    /// Output JSON path (stdout when omitted)
+    #[arg(short = 'o', long = "output")]
+    output: Option<String>,
+}
+
+#[derive(Args)]
+struct TraceDumpArgs {
+    /// Path to AIR program
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Trace path (.zkt or JSON)
+    #[arg(long = "trace")]
+    trace_path: String,
+    /// Row range START..END (end exclusive; either side may be omitted)
+    #[arg(long = "rows", default_value = "0..64")]
+    rows: String,
+    /// Also write the selected rows as CSV
+    #[arg(long = "csv")]
+    csv: Option<String>,
+}
+
+#[derive(Args)]
+struct CheckArgs {
+    /// Path to AIR program
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path (public inputs referenced by boundary constraints)
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Trace path (.zkt or JSON)
+    #[arg(long = "trace")]
+    trace_path: String,
+    /// Report at most this many violations
+    #[arg(long = "max-violations", default_value_t = 10)]
+    max_violations: usize,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct ConformanceArgs {
+    /// Suite file
+    #[arg(long = "suite", default_value = "examples/conformance/suite.json")]
+    suite: String,
+    /// Rewrite the suite's digests from zkd itself instead of comparing
+    #[arg(long = "bless", default_value_t = false, conflicts_with = "command")]
+    bless: bool,
+    /// Answer one adapter request from stdin with zkd (the reference adapter)
+    #[arg(long = "adapter", default_value_t = false, conflicts_with_all = ["bless", "command"])]
+    adapter: bool,
+    /// Print the check results as JSON
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+    /// Adapter command run once per request (request JSON on stdin, reply on stdout)
+    #[arg(last = true, value_name = "ADAPTER")]
+    command: Vec<String>,
+}
+
+#[cfg(feature = "prover")]
+#[derive(Args)]
+struct DeterminismAuditArgs {
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Concurrent proves per run, comma-separated
+    #[arg(long = "threads", value_delimiter = ',', default_value = "1,4")]
+    threads: Vec<usize>,
+    /// Print the audit report as JSON
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+    #[command(flatten)]
+    cfg: CommonCfg,
+}
+
+#[derive(Args)]
+struct EvmTypedHashArgs {
+    /// Proof file path
+    #[arg(short = 'P', long = "proof")]
+    proof_path: String,
+    /// Program AIR path (.air TOML)
+    #[arg(short = 'p', long = "program")]
+    program_path: String,
+    /// Inputs JSON path
+    #[arg(short = 'i', long = "inputs")]
+    inputs_path: String,
+    /// Chain id of the EIP-712 domain
+    #[arg(long = "chain-id")]
+    chain_id: u64,
+    /// Verifying contract address (20 bytes hex)
+    #[arg(long = "verifying-contract")]
+    verifying_contract: String,
+    /// Optional 32-byte context (hex), e.g. a session nonce
+    #[arg(long = "context")]
+    context: Option<String>,
+    /// Emit the full eth_signTypedData_v4 payload alongside the hash
+    #[arg(long = "json", default_value_t = false)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum AuditCmd {
+    /// Check every entry's hash, prev link and sequence number
     Verify {
-        /// Program AIR path (.air TOML)
-        #[arg(short = 'p', long = "program")]
-        program_path: String,
-        /// Inputs JSON path
-        #[arg(short = 'i', long = "inputs")]
-        inputs_path: String,
-        /// Proof file path
-        #[arg(short = 'P', long = "proof")]
-        proof_in: String,
-        /// Print stats row/col/body_len after success
-        #[arg(long = "stats", default_value_t = false)]
-        stats: bool,
-        #[command(flatten)]
-        cfg: CommonCfg,
+        /// Audit log (JSON lines) written by --audit-log
+        log: String,
+        /// Print the result as JSON
+        #[arg(long = "json", default_value_t = false)]
+        json: bool,
     },
-    /// Validate: derive commitment checks and emit a structured report
-    Validate {
-        /// Program AIR path (.air TOML)
-        #[arg(short = 'p', long = "program")]
-        program_path: String,
-        /// Inputs JSON path
-        #[arg(short = 'i', long = "inputs")]
-        inputs_path: String,
-        /// Proof file path
-        #[arg(short = 'P', long = "proof")]
-        proof_in: String,
-        /// Output directory for validation reports
+}
+
+#[derive(Subcommand)]
+enum PkgCmd {
+    #[cfg(feature = "prover")]
+    /// Build a .zkpkg from a zkpkg.toml manifest, reproducing its golden digests
+    Build {
+        /// Package manifest
+        #[arg(default_value = "zkpkg.toml")]
+        manifest: String,
+        /// Output package path (default: <name>.zkpkg)
         #[arg(short = 'o', long = "output")]
-        output_dir: String,
-        #[command(flatten)]
-        cfg: CommonCfg,
+        output: Option<String>,
     },
-    /// Compute a Pedersen (placeholder) commitment for msg/blind (hex).
-    Commit {
-        #[arg(long = "hash")]
-        hash_id: String,
-        #[arg(long = "msg-hex")]
-        msg_hex: String,
-        #[arg(long = "blind-hex")]
-        blind_hex: String,
+    /// Install a .zkpkg into $ZKD_PKG_DIR (default ~/.zkd/packages)
+    Install {
+        /// Package file
+        package: String,
     },
-    /// Verify opening against a commitment (all hex).
-    OpenCommit {
-        #[arg(long = "hash")]
-        hash_id: String,
-        #[arg(long = "msg-hex")]
-        msg_hex: String,
-        #[arg(long = "blind-hex")]
-        blind_hex: String,
-        #[arg(long = "commit-hex")]
-        commit_hex: String,
+    /// List installed packages
+    List {
+        /// Print the packages' metadata as JSON
+        #[arg(long = "json", default_value_t = false)]
+        json: bool,
     },
-    /// Compute the Keccak digest (D) used by the EVM verifier from a proof blob.
-    EvmDigest {
+}
+
+#[derive(Subcommand)]
+enum ArchiveCmd {
+    /// Upload a proof and its manifest / timestamp sidecars under its digest
+    Put {
         /// Proof file path
         #[arg(short = 'P', long = "proof")]
         proof_path: String,
+        /// zkd.toml path (default: $ZKD_CONFIG, else ./zkd.toml)
+        #[arg(long = "config", value_name = "PATH")]
+        config_path: Option<String>,
+    },
+    /// Download the proof (and any sidecars) stored under a digest
+    Get {
+        /// EVM digest D of the proof (hex, 0x optional)
+        #[arg(long = "digest")]
+        digest: String,
+        /// Output proof path; sidecars are written next to it
+        #[arg(short = 'o', long = "output")]
+        output: String,
+        /// zkd.toml path (default: $ZKD_CONFIG, else ./zkd.toml)
+        #[arg(long = "config", value_name = "PATH")]
+        config_path: Option<String>,
+    },
+}
+
+#[cfg(feature = "prover")]
+#[derive(Subcommand)]
+enum ExamplesCmd {
+    /// Run examples (all when none are named); prints PASS/SKIP/FAIL per example
+    Run {
+        /// Examples to run
+        #[arg(value_enum)]
+        examples: Vec<examples::Example>,
+        /// Workspace root holding examples/ (defaults to the current directory)
+        #[arg(long = "root")]
+        root: Option<String>,
+    },
+    /// Prove every AIR under examples/ with its pinned config and compare
+    /// digests against examples/golden.lock
+    VerifyGolden {
+        /// Rewrite golden.lock with the current digests instead of comparing
+        #[arg(long = "bless", default_value_t = false)]
+        bless: bool,
+        /// Workspace root holding examples/ (defaults to the current directory)
+        #[arg(long = "root")]
+        root: Option<String>,
     },
 }
 
@@ -169,21 +885,149 @@ fn write_bytes(path: &str, bytes: &[u8]) -> Result<()> {
     Ok(())
 }
 
-fn mk_config(c: &CommonCfg) -> Config {
-    Config::new(
-        &c.backend_id,
+/// Build the run's config; `--backend auto` also honours the `meta.requires`
+/// of the AIR at `program_path`.
+fn mk_config(c: &CommonCfg, program_path: &str) -> Result<Config> {
+    let overrides = parse_overrides(&c.profile_overrides)?;
+    let air_hints: AirHints = c.air_hints.as_deref().unwrap_or("ignore").parse()?;
+    let mut config = Config::new(
+        c.backend_id.as_deref().unwrap_or_default(),
         &c.field,
         &c.hash,
         c.fri_arity,
         c.need_recursion,
-        &c.profile_id,
+        c.profile_id.as_deref().unwrap_or_default(),
     )
+    .with_profile_overrides(overrides)
+    .with_pubio_encoding(c.pubio_encoding.parse()?)
+    .with_undeclared_inputs(c.undeclared_inputs.parse()?)
+    .with_options(match &c.backend_options {
+        Some(json) => serde_json::from_str(json).context("--backend-options is not valid JSON")?,
+        None => serde_json::Value::Null,
+    })
+    .with_air_hints(air_hints);
+    if air_hints.is_ignore() && config.backend_id != registry::AUTO_BACKEND {
+        return Ok(config);
+    }
+    registry::ensure_builtins_registered();
+    let air = AirProgram::load_from_file(program_path)?;
+    for what in config.apply_air_hints(&air.meta)? {
+        let id = match what {
+            "backend" => &config.backend_id,
+            _ => &config.profile_id,
+        };
+        eprintln!("AIR hint: {}={} (meta.{})", what, id, what);
+    }
+    if config.backend_id == registry::AUTO_BACKEND {
+        let query = registry::BackendQuery {
+            field: Some(c.field.clone()),
+            hash: Some(c.hash.clone()),
+            fri_arity: Some(c.fri_arity),
+            recursion: c.need_recursion.then_some(true),
+            requires: air.meta.requires,
+            ..Default::default()
+        };
+        config.backend_id = registry::select_backend(&query)?.to_string();
+    }
+    Ok(config)
+}
+
+/// Trace shape of `air` for one run: a `rows_hint` expression is evaluated
+/// against the public inputs and checked against the profile and backend
+/// row limits. Also returns the evaluated rows (`None` for fixed hints).
+fn run_shape(
+    config: &Config,
+    air: &AirProgram,
+    inputs_json: &str,
+) -> Result<(TraceShape, Option<u32>)> {
+    let mut shape = TraceShape::from_air(air);
+    if !air.rows_hint.as_ref().is_some_and(RowsHint::is_expr) {
+        return Ok((shape, None));
+    }
+    let ir = AirIr::from(air.clone());
+    let rows = core::resolve_rows(config, &ir, &PublicInputs::from_json(inputs_json)?)?;
+    if let Some(rows) = rows {
+        shape.rows = rows;
+    }
+    Ok((shape, rows))
+}
+
+fn print_warnings(warnings: &[ValidationWarning]) {
+    for w in warnings {
+        eprintln!("⚠️  {}: {}", w.code, w.msg);
+    }
 }
 
 /// Map verifier/proof parsing failures to the mandated exit code (4).
 fn exit_for_corrupt_proof(err: &anyhow::Error) -> ! {
     eprintln!("Error: {err}");
-    process::exit(EXIT_CORRUPT_PROOF);
+    output::exit(EXIT_CORRUPT_PROOF);
+}
+
+/// Subcommands whose `--backend`/`--profile` flags accept registry ids.
+const CONFIG_SUBCOMMANDS: &[&str] = &["prove", "verify", "validate"];
+
+/// Build the clap command with `--backend`/`--profile` possible values taken
+/// from the live registry and profile set. Used only for completions and man
+/// pages; parsing keeps accepting any id so unknown ids get precise errors.
+fn command_with_dynamic_values() -> clap::Command {
+    registry::ensure_builtins_registered();
+    let mut backends: Vec<String> = core::list_backends()
+        .into_iter()
+        .map(|b| b.id.to_string())
+        .collect();
+    backends.push(registry::AUTO_BACKEND.to_string());
+    backends.extend(registry::list_aliases().into_iter().map(|(alias, _)| alias));
+    let profiles: Vec<String> = core::list_profiles().into_iter().map(|p| p.id).collect();
+
+    let mut cmd = Cli::command();
+    for name in CONFIG_SUBCOMMANDS {
+        if cmd.find_subcommand(name).is_none() {
+            continue; // `prove` in verifier-only builds
+        }
+        let backends = backends.clone();
+        let profiles = profiles.clone();
+        cmd = cmd.mut_subcommand(*name, move |sub| {
+            sub.mut_arg("backend_id", |a| {
+                a.value_parser(PossibleValuesParser::new(backends))
+            })
+            .mut_arg("profile_id", |a| {
+                a.value_parser(PossibleValuesParser::new(profiles))
+            })
+        });
+    }
+    cmd
+}
+
+fn write_man_pages(out_dir: &str) -> Result<Vec<String>> {
+    fs::create_dir_all(out_dir).with_context(|| format!("failed to create dir '{}'", out_dir))?;
+    let cmd = command_with_dynamic_values();
+    let mut written = Vec::new();
+    let mut render = |name: String, page: clap::Command| -> Result<()> {
+        let mut buf = Vec::new();
+        clap_mangen::Man::new(page).render(&mut buf)?;
+        let path = Path::new(out_dir).join(format!("{name}.1"));
+        fs::write(&path, buf).with_context(|| format!("failed to write '{}'", path.display()))?;
+        written.push(path.display().to_string());
+        Ok(())
+    };
+    for sub in cmd.get_subcommands() {
+        let name = format!("{}-{}", env!("CARGO_BIN_NAME"), sub.get_name());
+        render(name.clone(), sub.clone().name(name))?;
+    }
+    render(env!("CARGO_BIN_NAME").to_string(), cmd)?;
+    Ok(written)
+}
+
+/// The bare proof in the bytes of a proof file, with the inputs embedded in
+/// it. A proof container (`prove --embed-inputs`) is checked and unwrapped.
+fn open_proof(bytes: Vec<u8>, proof_path: &str) -> Result<(Vec<u8>, Option<String>)> {
+    if !is_container(&bytes) {
+        return Ok((bytes, None));
+    }
+    let container = ProofContainer::decode(&bytes)
+        .with_context(|| format!("proof container '{}'", proof_path))?;
+    Ok((container.to_proof(), container.inputs))
 }
 
 // --- Hex helpers ---------------------------------------------------------
@@ -221,316 +1065,167 @@ fn bytes_to_hex(v: &[u8]) -> String {
     out
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-    match cli.command {
-        Some(Commands::BackendLs { verbose }) => {
-            let infos = core::list_backends();
-            if !verbose {
-                for b in infos {
-                    println!("{}  recursion={}", b.id, b.recursion);
-                }
-            } else {
-                for b in infos {
-                    let caps =
-                        registry::get_backend_capabilities(b.id).expect("backend disappeared");
-                    println!("{}", b.id);
-                    println!("  recursion: {}", caps.recursion);
-                    println!("  lookups: {}", caps.lookups);
-                    println!("  fields: {}", caps.fields.join(", "));
-                    println!("  hashes: {}", caps.hashes.join(", "));
-                    let arities = caps
-                        .fri_arities
-                        .iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    println!("  fri_arities: {}", arities);
-                }
-            }
+fn version_meta() -> VersionMeta {
+    VersionMeta {
+        version: buildinfo::VERSION.to_string(),
+        commit: buildinfo::git_commit().map(str::to_string),
+    }
+}
+
+pub fn main() -> Result<()> {
+    ensure_native_registered();
+    ensure_ministark_registered();
+    // Plugins run in-process; the verification-only binary never loads them.
+    #[cfg(feature = "plugins")]
+    if env!("CARGO_BIN_NAME") == "zkd" {
+        zkprov_corelib::plugin::load_from_env();
+    }
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    output::set_quiet(cli.quiet);
+    output::set_format(cli.format, &command_name(&matches));
+    if cli.format == output::Format::Json {
+        if let Some(command) = &mut cli.command {
+            request_json(command);
+        }
+    }
+    let result = run(cli.command);
+    if result.is_err() {
+        output::failed();
+    }
+    output::finish(result.as_ref().map(|_| ()));
+    result
+}
+
+/// `pkg build`: the subcommand path named in `--format json` envelopes.
+fn command_name(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    names.join(" ")
+}
+
+/// `--format json` implies the `--json` of commands that have one.
+fn request_json(command: &mut Commands) {
+    match command {
+        Commands::BackendLs(BackendLsArgs { json, .. })
+        | Commands::ProfileCheck(ProfileCheckArgs { json, .. })
+        | Commands::AirDiff(AirDiffArgs { json, .. })
+        | Commands::Verify(VerifyArgs { json, .. })
+        | Commands::Triage(TriageArgs { json, .. })
+        | Commands::Version { json, .. }
+        | Commands::EvmTypedHash(EvmTypedHashArgs { json, .. })
+        | Commands::Audit {
+            action: AuditCmd::Verify { json, .. },
         }
+        | Commands::Pkg {
+            action: PkgCmd::List { json, .. },
+        } => *json = true,
+        #[cfg(feature = "prover")]
+        Commands::Conformance(ConformanceArgs { json, .. })
+        | Commands::DeterminismAudit(DeterminismAuditArgs { json, .. }) => *json = true,
+        _ => {}
+    }
+}
+
+fn run(mut command: Option<Commands>) -> Result<()> {
+    pkg::resolve_package_program(&mut command)?;
+    match command {
+        Some(Commands::BackendLs(args)) => inspect::backend_ls(args)?,
         Some(Commands::ProfileLs) => {
             let profiles = core::list_profiles();
-            for p in profiles {
-                println!("{}  λ={} bits", p.id, p.lambda_bits);
+            for p in &profiles {
+                out!("{}  λ={} bits  target={}", p.id, p.lambda_bits, p.target);
             }
+            output::field("profiles", &profiles);
         }
-        Some(Commands::IoSchema {
-            program_path,
-            pretty,
-        }) => {
-            let air = AirProgram::load_from_file(&program_path)?;
-            let shape = TraceShape::from_air(&air);
-            // Minimal schema reflection for Phase-0 (public inputs remain free-form JSON)
-            let schema = serde_json::json!({
-                "program": air.meta.name,
-                "field": air.meta.field,
-                "hash": format!("{:?}", air.meta.hash).to_lowercase(),
-                "trace": { "rows": shape.rows, "cols": shape.cols, "const_cols": shape.const_cols, "periodic_cols": shape.periodic_cols },
-                "public_inputs": { "kind": "json", "binding": "raw" },
-                "commitments": {
-                    "pedersen": true,
-                    "curves": ["placeholder"],
-                    "no_r_reuse": false
+        Some(Commands::ProfileCheck(args)) => inspect::profile_check(args)?,
+        Some(Commands::AirDiff(args)) => inspect::air_diff(args)?,
+        Some(Commands::IoSchema(args)) => inspect::io_schema(args)?,
+        Some(Commands::IoCheck(args)) => inspect::io_check(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::Prove(args)) => prove::prove(args)?,
+        Some(Commands::Verify(args)) => verify::verify(args)?,
+        Some(Commands::Triage(args)) => verify::triage(args)?,
+        Some(Commands::Validate(args)) => verify::validate(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::Commit(args)) => crypto::commit(args)?,
+        Some(Commands::OpenCommit(args)) => crypto::open_commit(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::BlindNew(args)) => crypto::blind_new(args)?,
+        Some(Commands::Vectors(args)) => crypto::vectors(args)?,
+        Some(Commands::EvmPubio(args)) => evm::evm_pubio(args)?,
+        Some(Commands::EvmDigest(args)) => evm::evm_digest(args)?,
+        Some(Commands::EvmFixtures(args)) => evm::evm_fixtures(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::TraceImport(args)) => trace::trace_import(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::TraceExport(args)) => trace::trace_export(args)?,
+        Some(Commands::TraceDump(args)) => trace::trace_dump(args)?,
+        Some(Commands::ProofEncode(args)) => armor::proof_encode(args)?,
+        Some(Commands::ProofDecode(args)) => armor::proof_decode(args)?,
+        Some(Commands::ProofQr(args)) => qr::proof_qr(args)?,
+        Some(Commands::ProofQrAssemble(args)) => qr::proof_qr_assemble(args)?,
+        Some(Commands::Archive { action }) => storage::archive_cmd(action)?,
+        Some(Commands::Pkg { action }) => pkg::pkg_cmd(action)?,
+        Some(Commands::Audit { action }) => audit::audit_cmd(action)?,
+        Some(Commands::Check(args)) => trace::check(args)?,
+        Some(Commands::Version { verbose, json }) => {
+            let info = buildinfo::build_info();
+            if json {
+                let report = Envelope::ok(VersionReport {
+                    version: version_meta(),
+                    build: info,
+                });
+                output::data(&report)?;
+            } else if verbose {
+                out!("zkd {}", info.version);
+                for line in info.to_lines().iter().skip(1) {
+                    out!("  {}", line);
                 }
-            });
-            if pretty {
-                println!("{}", serde_json::to_string_pretty(&schema)?);
             } else {
-                println!("{}", serde_json::to_string(&schema)?);
+                out!("zkd {}", info.version);
             }
         }
-        Some(Commands::Prove {
-            program_path,
-            inputs_path,
-            proof_out,
-            stats,
-            cfg,
-        }) => {
-            registry::ensure_builtins_registered();
-            let config = mk_config(&cfg);
-            validate_config(&config).map_err(|e| anyhow!(e.to_string()))?;
-            let inputs = read_to_string(&inputs_path)?;
-
-            if config.backend_id == "native@0.0" {
-                let proof = native_prove(&config, &inputs, &program_path)?;
-                write_bytes(&proof_out, &proof)?;
-                let hdr = ProofHeader::decode(&proof[0..40])
-                    .unwrap_or_else(|e| exit_for_corrupt_proof(&e));
-                println!(
-                    "✅ ProofGenerated backend={} profile={} body_len={} pubio_hash=0x{:016x}",
-                    config.backend_id, config.profile_id, hdr.body_len, hdr.pubio_hash
-                );
-                if stats {
-                    let air = AirProgram::load_from_file(&program_path)?;
-                    let shape = TraceShape::from_air(&air);
-                    println!(
-                        "stats rows={} cols={} const={} periodic={}",
-                        shape.rows, shape.cols, shape.const_cols, shape.periodic_cols
-                    );
-                }
-                println!("Program: {}", program_path);
-                println!("Wrote: {}", proof_out);
-            } else {
-                return Err(anyhow!(
-                    "backend '{}' not implemented yet in CLI",
-                    config.backend_id
-                ));
-            }
-        }
-        Some(Commands::Verify {
-            program_path,
-            inputs_path,
-            proof_in,
-            stats,
-            cfg,
-        }) => {
-            registry::ensure_builtins_registered();
-            let config = mk_config(&cfg);
-            validate_config(&config).map_err(|e| anyhow!(e.to_string()))?;
-            let inputs = read_to_string(&inputs_path)?;
-            let proof = read_to_bytes(&proof_in)?;
-
-            if config.backend_id == "native@0.0" {
-                // First, attempt to decode header; any failure maps to exit code 4
-                let hdr = match ProofHeader::decode(proof.get(0..40).unwrap_or(&[])) {
-                    Ok(h) => h,
-                    Err(e) => exit_for_corrupt_proof(&e),
-                };
-                // Now run backend verify; any transcript/commit mismatch is also "corrupt proof"
-                match native_verify(&config, &inputs, &program_path, &proof) {
-                    Ok(true) => {
-                        println!(
-                            "✅ ProofVerified backend={} profile={} pubio_hash=0x{:016x}",
-                            config.backend_id, config.profile_id, hdr.pubio_hash
-                        );
-                        if stats {
-                            let air = AirProgram::load_from_file(&program_path)?;
-                            let shape = TraceShape::from_air(&air);
-                            println!(
-                                "stats rows={} cols={} const={} periodic={}",
-                                shape.rows, shape.cols, shape.const_cols, shape.periodic_cols
-                            );
-                        }
-                    }
-                    Ok(false) => {
-                        eprintln!("❌ Verification failed");
-                        process::exit(EXIT_CORRUPT_PROOF);
-                    }
-                    Err(e) => {
-                        // Treat mismatches and root/header problems as "corrupt proof"
-                        exit_for_corrupt_proof(&e);
-                    }
-                }
-            } else {
-                return Err(anyhow!(
-                    "backend '{}' not implemented yet in CLI",
-                    config.backend_id
-                ));
-            }
-        }
-        Some(Commands::Validate {
-            program_path,
-            inputs_path,
-            proof_in,
-            output_dir,
-            cfg,
-        }) => {
-            registry::ensure_builtins_registered();
-            let config = mk_config(&cfg);
-            validate_config(&config).map_err(|e| anyhow!(e.to_string()))?;
-            let air = AirProgram::load_from_file(&program_path)?;
-            validate_air_against_backend(&air, &config.backend_id)
-                .map_err(|e| anyhow!(e.to_string()))?;
-            let bindings = Bindings::from_air(&air);
-
-            let proof = read_to_bytes(&proof_in)?;
-            if proof.len() < 40 {
-                return Err(anyhow!(
-                    "proof '{}' is too short for header ({} bytes)",
-                    proof_in,
-                    proof.len()
-                ));
-            }
-            let header = ProofHeader::decode(&proof[0..40])
-                .map_err(|e| anyhow!("failed to decode proof header: {e}"))?;
-            let body = &proof[40..];
-            if body.len() as u64 != header.body_len {
-                return Err(anyhow!(
-                    "proof '{}' body length ({}) does not match header body_len {}",
-                    proof_in,
-                    body.len(),
-                    header.body_len
-                ));
-            }
-
-            let inputs_json = read_to_string(&inputs_path)?;
-            let mut validator = Validator::new(&bindings);
-
-            if bindings.commitments.pedersen {
-                let mut msg_bytes = inputs_json.into_bytes();
-                msg_bytes.extend_from_slice(body);
-                let mut blind_bytes = Vec::new();
-                blind_bytes.extend_from_slice(&header.pubio_hash.to_le_bytes());
-                blind_bytes.extend_from_slice(&header.backend_id_hash.to_le_bytes());
-                blind_bytes.extend_from_slice(&header.profile_id_hash.to_le_bytes());
-                validator.check_commit_point(&msg_bytes, &blind_bytes);
-            }
-            validator.check_range_u64(header.body_len, 64);
-
-            let mut report = validator.finalize();
-            report.meta.backend_id = config.backend_id.clone();
-            report.meta.profile_id = config.profile_id.clone();
-            report.meta.hash_id = bindings
-                .hash_id_for_commitments
-                .clone()
-                .unwrap_or_else(|| config.hash.clone());
-            report.meta.curve = bindings.commitments.curve.clone();
-
-            let report_path = report.write_pretty(&output_dir).with_context(|| {
-                format!("failed to write validation report under '{}'", output_dir)
-            })?;
-            println!(
-                "✅ Validation ok={} commit_passed={} report={}",
-                report.ok,
-                report.commit_passed,
-                report_path.display()
+        #[cfg(feature = "prover")]
+        Some(Commands::Examples { action }) => examples::examples_cmd(action)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::Conformance(args)) => conformance::conformance_cmd(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::DeterminismAudit(args)) => prove::determinism_audit(args)?,
+        #[cfg(feature = "prover")]
+        Some(Commands::ProveWorker) => isolate::run_worker()?,
+        Some(Commands::Completions { shell }) => {
+            let mut cmd = command_with_dynamic_values();
+            clap_complete::generate(
+                shell,
+                &mut cmd,
+                env!("CARGO_BIN_NAME"),
+                &mut std::io::stdout(),
             );
-            if !report.ok {
-                for err in &report.errors {
-                    eprintln!("❌ {:?}: {}", err.code, err.msg);
-                }
-                process::exit(1);
-            }
-        }
-        Some(Commands::Commit {
-            hash_id,
-            msg_hex,
-            blind_hex,
-        }) => {
-            registry::ensure_builtins_registered();
-            let msg = hex_to_bytes(&msg_hex)?;
-            let blind = hex_to_bytes(&blind_hex)?;
-            let ped = PedersenPlaceholder::new(PedersenParams { hash_id });
-            let commitment = ped.commit(&Witness {
-                msg: &msg,
-                blind: &blind,
-            })?;
-            println!("{}", bytes_to_hex(commitment.as_bytes()));
         }
-        Some(Commands::OpenCommit {
-            hash_id,
-            msg_hex,
-            blind_hex,
-            commit_hex,
-        }) => {
-            registry::ensure_builtins_registered();
-            let msg = hex_to_bytes(&msg_hex)?;
-            let blind = hex_to_bytes(&blind_hex)?;
-            let cbytes = hex_to_bytes(&commit_hex)?;
-            if cbytes.len() != 32 {
-                return Err(anyhow!("commit-hex must be 32 bytes (64 hex chars)"));
-            }
-            let mut c32 = [0u8; 32];
-            c32.copy_from_slice(&cbytes);
-            let ped = PedersenPlaceholder::new(PedersenParams { hash_id });
-            let opened = ped.open(
-                &Witness {
-                    msg: &msg,
-                    blind: &blind,
-                },
-                &Comm32(c32),
-            )?;
-            if opened {
-                println!("✅ Opened");
-            } else {
-                println!("❌ Invalid opening");
-                process::exit(1);
+        Some(Commands::Man { out_dir }) => {
+            for path in write_man_pages(&out_dir)? {
+                info!("Wrote: {}", path);
             }
         }
-        Some(Commands::EvmDigest { proof_path }) => {
-            let proof = read_to_bytes(&proof_path)?;
-            if proof.len() < 40 {
-                return Err(anyhow!(
-                    "proof '{}' is too short for header ({} bytes)",
-                    proof_path,
-                    proof.len()
-                ));
-            }
-            let header = ProofHeader::decode(&proof[0..40])?;
-            let body_len = usize::try_from(header.body_len).map_err(|_| {
-                anyhow!("header body_len {} does not fit in memory", header.body_len)
-            })?;
-            let expected_len = 40usize
-                .checked_add(body_len)
-                .ok_or_else(|| anyhow!("proof length overflow"))?;
-            if proof.len() != expected_len {
-                return Err(anyhow!(
-                    "proof '{}' length ({}) does not match header body_len {}",
-                    proof_path,
-                    proof.len(),
-                    header.body_len
-                ));
-            }
-            let body = &proof[40..expected_len];
-            let digest = digest_D(&header, body);
-            println!("0x{}", bytes_to_hex(&digest));
-        }
+        Some(Commands::EvmTypedHash(args)) => evm::evm_typed_hash(args)?,
         None => {
-            println!("zkd {} — ready", core::version());
-            println!("Try: `zkd backend-ls [-v]`, `zkd profile-ls`,");
-            println!("     `zkd io-schema -p <program.air>`,",);
-            println!("     `zkd commit --hash <id> --msg-hex <..> --blind-hex <..>`,",);
-            println!(
+            out!("zkd {} — ready", core::version());
+            out!("Try: `zkd backend-ls [-v]`, `zkd profile-ls`,");
+            out!("     `zkd io-schema -p <program.air>`,",);
+            out!("     `zkd commit --hash <id> --msg-hex <..> --blind-hex <..>`,",);
+            out!(
                 "     `zkd open-commit --hash <id> --msg-hex <..> --blind-hex <..> --commit-hex <..>`,",
             );
-            println!(
-                "     `zkd prove -p <program> -i <inputs> -o <proof> --profile ... [--stats]`,",
-            );
-            println!(
-                "     `zkd verify -p <program> -i <inputs> -P <proof> --profile ... [--stats]`",
+            out!(
+                "     `zkd prove -p <program> -i <inputs> -o <proof> --profile ... [--profile-override k=v] [--stats]`,",
             );
-            println!(
+            out!("     `zkd verify -p <program> -i <inputs> -P <proof> --profile ... [--stats]`",);
+            out!(
                 "     `zkd validate -p <program> -i <inputs> -P <proof> -o <reports> --profile ...`",
             );
         }
@@ -0,0 +1,265 @@
+//! Where command output goes, so every subcommand treats `--quiet` and
+//! `--format json` alike.
+//!
+//! * [`info!`] prints the human-readable status lines (`✅ ProofVerified …`,
+//!   `Wrote: …`). `--quiet` and `--format json` drop them.
+//! * [`result`] prints the one scriptable line a command reduces to (the
+//!   digest for `prove`, `ok` for `verify`, the hex for `commit`). It prints
+//!   only under `--quiet`.
+//! * Data a command was asked for goes through [`data`] / [`data_line`]
+//!   (JSON with `--json`), [`out!`] (listing lines), [`line`] (one value)
+//!   and [`document`] (schemas, tables, files written to stdout). It is
+//!   printed with or without `--quiet`.
+//! * [`field`] adds a structured value that only `--format json` reports.
+//! * Diagnostics, warnings and errors always go to stderr.
+//!
+//! A command that has a failure line (`fail` for `verify`) registers it with
+//! [`on_failure`]. [`exit`] and an error returned from `main` print it, on
+//! stdout and only under `--quiet`, before the process ends. So a quiet
+//! command prints exactly one stdout line when it has a result line, and
+//! nothing when its output is data only.
+//!
+//! Under `--format json` nothing above reaches stdout directly. The result
+//! line, data and fields are collected into one `zkp-ffi/1` envelope that
+//! [`finish`] or [`exit`] prints, as the C ABI reports results.
+
+use std::fmt::{Arguments, Display};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use zkprov_corelib::errors::{CapabilityError, ProveError, RegistryError, VerifyError};
+use zkprov_ffi_types::{Envelope, ErrorCode, RESERVED_FIELDS};
+
+/// `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static JSON: AtomicBool = AtomicBool::new(false);
+static FAILURE_LINE: Mutex<Option<&'static str>> = Mutex::new(None);
+/// Payload of the `--format json` envelope; `None` once it was printed.
+static PAYLOAD: Mutex<Option<Map<String, Value>>> = Mutex::new(None);
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Select the output format. `command` names the subcommand in the envelope.
+pub fn set_format(format: Format, command: &str) {
+    let json = format == Format::Json;
+    JSON.store(json, Ordering::Relaxed);
+    if json {
+        let mut payload = Map::new();
+        payload.insert("command".into(), command.into());
+        *PAYLOAD.lock().expect("payload lock") = Some(payload);
+    }
+}
+
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// `println!` unless `--quiet` or `--format json`.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        output::info_line(format_args!($($arg)*))
+    };
+}
+
+pub fn info_line(line: Arguments<'_>) {
+    if !is_quiet() && !is_json() {
+        println!("{}", line);
+    }
+}
+
+/// A line of listing data: printed in text mode, collected as `lines` by
+/// `--format json`.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        output::data_text_line(format_args!($($arg)*))
+    };
+}
+
+pub fn data_text_line(line: Arguments<'_>) {
+    if is_json() {
+        with_payload(|p| {
+            let lines = p.entry("lines").or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(lines) = lines {
+                lines.push(line.to_string().into());
+            }
+        });
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// A single value printed as a line (a hex commitment, a digest); `key`
+/// under `--format json`.
+pub fn line(key: &str, value: impl Display) {
+    if is_json() {
+        field(key, value.to_string());
+    } else {
+        println!("{}", value);
+    }
+}
+
+/// Text printed as is (a schema, a table, a file written to stdout);
+/// `document` under `--format json`.
+pub fn document(text: &str) {
+    if is_json() {
+        field("document", text);
+    } else {
+        print!("{}", text);
+    }
+}
+
+/// The command's scriptable result line, printed only under `--quiet`;
+/// `result` under `--format json`.
+pub fn result(line: impl Display) {
+    if is_json() {
+        field("result", line.to_string());
+    } else if is_quiet() {
+        println!("{}", line);
+    }
+}
+
+/// A value only `--format json` reports. Ignored in text mode.
+pub fn field(key: &str, value: impl Serialize) {
+    if is_json() {
+        let value = serde_json::to_value(value).expect("output fields serialize");
+        with_payload(|p| {
+            p.insert(key.to_string(), value);
+        });
+    }
+}
+
+/// JSON data the command was asked for (`--json`), pretty-printed. Under
+/// `--format json` an object's fields join the envelope (a value that is
+/// already an envelope replaces it) and anything else becomes `data`.
+pub fn data(value: &impl Serialize) -> serde_json::Result<()> {
+    if is_json() {
+        merge(serde_json::to_value(value)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    }
+    Ok(())
+}
+
+/// [`data`] on a single line.
+pub fn data_line(value: &impl Serialize) -> serde_json::Result<()> {
+    if is_json() {
+        merge(serde_json::to_value(value)?);
+    } else {
+        println!("{}", serde_json::to_string(value)?);
+    }
+    Ok(())
+}
+
+fn merge(value: Value) {
+    match value {
+        Value::Object(mut map) if map.contains_key("schema") => {
+            // Already an envelope (`verify --json`): print it instead.
+            if let Some(mut payload) = PAYLOAD.lock().expect("payload lock").take() {
+                if let Some(command) = payload.remove("command") {
+                    map.insert("command".into(), command);
+                }
+                println!("{}", Value::Object(map));
+            }
+        }
+        Value::Object(map) => with_payload(|p| {
+            for (key, value) in map {
+                if !RESERVED_FIELDS.contains(&key.as_str()) {
+                    p.insert(key, value);
+                }
+            }
+        }),
+        other => field("data", other),
+    }
+}
+
+/// The command speaks its own protocol on stdout; no envelope is printed.
+#[cfg(feature = "prover")]
+pub fn own_stdout() {
+    PAYLOAD.lock().expect("payload lock").take();
+}
+
+fn with_payload(f: impl FnOnce(&mut Map<String, Value>)) {
+    if let Some(payload) = PAYLOAD.lock().expect("payload lock").as_mut() {
+        f(payload);
+    }
+}
+
+/// Line [`exit`] and a failed `main` print under `--quiet`.
+pub fn on_failure(line: &'static str) {
+    *FAILURE_LINE.lock().expect("failure line lock") = Some(line);
+}
+
+/// Print the registered failure line, if any, under `--quiet`.
+pub fn failed() {
+    if let Some(line) = FAILURE_LINE.lock().expect("failure line lock").take() {
+        if !is_json() {
+            result(line);
+        }
+    }
+}
+
+/// Print the `--format json` envelope for the command's outcome: success,
+/// or the error `main` is about to return.
+pub fn finish(outcome: Result<(), &anyhow::Error>) {
+    let Some(payload) = PAYLOAD.lock().expect("payload lock").take() else {
+        return;
+    };
+    let envelope = match outcome {
+        Ok(()) => Envelope::ok(payload),
+        Err(e) => {
+            let mut payload = payload;
+            payload.insert("exit_code".into(), 1.into());
+            Envelope::err(error_code(e), format!("{e:#}"), payload)
+        }
+    };
+    println!("{}", envelope.to_json());
+}
+
+fn error_code(e: &anyhow::Error) -> ErrorCode {
+    if let Some(e) = e.downcast_ref::<VerifyError>() {
+        e.into()
+    } else if let Some(e) = e.downcast_ref::<ProveError>() {
+        e.into()
+    } else if let Some(e) = e.downcast_ref::<CapabilityError>() {
+        e.into()
+    } else if let Some(e) = e.downcast_ref::<RegistryError>() {
+        e.into()
+    } else {
+        ErrorCode::InvalidArg
+    }
+}
+
+/// [`failed`], then exit with `code`. Under `--format json` the envelope
+/// reports the exit code (see INTERFACES §1.3).
+pub fn exit(code: i32) -> ! {
+    failed();
+    if let Some(mut payload) = PAYLOAD.lock().expect("payload lock").take() {
+        let (status, msg) = match code {
+            1 => (ErrorCode::VerifyFail, "verification failed"),
+            2 => (ErrorCode::InvalidArg, "invalid configuration"),
+            3 => (ErrorCode::Backend, "backend capability mismatch"),
+            4 => (ErrorCode::ProofCorrupt, "proof rejected or unreadable"),
+            6 => (ErrorCode::Internal, "proving deadline exceeded"),
+            _ => (ErrorCode::Internal, "internal runtime error"),
+        };
+        payload.insert("exit_code".into(), code.into());
+        println!("{}", Envelope::err(status, msg, payload).to_json());
+    }
+    process::exit(code)
+}
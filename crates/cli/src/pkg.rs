@@ -0,0 +1,156 @@
+//! `zkd pkg` and `-p pkg:<name>` program paths (see
+//! [`zkprov_corelib::pkg`]).
+
+#[cfg(feature = "prover")]
+use anyhow::anyhow;
+use anyhow::Result;
+use std::path::Path;
+#[cfg(feature = "prover")]
+use zkprov_corelib as core;
+#[cfg(feature = "prover")]
+use zkprov_corelib::backend::PublicInputs;
+#[cfg(feature = "prover")]
+use zkprov_corelib::config::Config;
+#[cfg(feature = "prover")]
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::pkg::{Package, PackageStore, PKG_PREFIX};
+#[cfg(feature = "prover")]
+use zkprov_corelib::proof::split_proof;
+#[cfg(feature = "prover")]
+use zkprov_corelib::registry;
+
+#[cfg(feature = "prover")]
+use super::{bytes_to_hex, write_bytes, DeterminismAuditArgs, ProveArgs};
+use super::{
+    output, CheckArgs, Commands, EvmFixturesArgs, EvmPubioArgs, EvmTypedHashArgs, IoCheckArgs,
+    IoSchemaArgs, PkgCmd, TraceDumpArgs, TriageArgs, ValidateArgs, VerifyArgs,
+};
+
+/// Point a `-p pkg:<name>` program path at the installed package's AIR.
+pub fn resolve_package_program(command: &mut Option<Commands>) -> Result<()> {
+    let program_path = match command {
+        Some(
+            Commands::IoSchema(IoSchemaArgs { program_path, .. })
+            | Commands::IoCheck(IoCheckArgs { program_path, .. })
+            | Commands::Verify(VerifyArgs { program_path, .. })
+            | Commands::Triage(TriageArgs { program_path, .. })
+            | Commands::Validate(ValidateArgs { program_path, .. })
+            | Commands::EvmPubio(EvmPubioArgs { program_path, .. })
+            | Commands::EvmFixtures(EvmFixturesArgs {
+                program_path: Some(program_path),
+                ..
+            })
+            | Commands::TraceDump(TraceDumpArgs { program_path, .. })
+            | Commands::Check(CheckArgs { program_path, .. })
+            | Commands::EvmTypedHash(EvmTypedHashArgs { program_path, .. }),
+        ) => program_path,
+        #[cfg(feature = "prover")]
+        Some(
+            Commands::Prove(ProveArgs { program_path, .. })
+            | Commands::DeterminismAudit(DeterminismAuditArgs { program_path, .. }),
+        ) => program_path,
+        _ => return Ok(()),
+    };
+    if program_path.starts_with(PKG_PREFIX) {
+        let store = PackageStore::new(PackageStore::default_root()?);
+        *program_path = store.resolve(program_path)?.display().to_string();
+    }
+    Ok(())
+}
+
+/// `zkd pkg …`.
+pub fn pkg_cmd(action: PkgCmd) -> Result<()> {
+    match action {
+        #[cfg(feature = "prover")]
+        PkgCmd::Build { manifest, output } => {
+            let package = Package::from_manifest(Path::new(&manifest))?;
+            let profiles = core::profile::load_all_profiles_or_default();
+            for id in &package.profiles {
+                if !profiles.iter().any(|p| &p.id == id) {
+                    return Err(anyhow!("recommended profile '{}' not found", id));
+                }
+            }
+            registry::ensure_builtins_registered();
+            let ir = core::air::parse_air_str(&package.air)?;
+            for golden in &package.golden {
+                let config = Config::new(
+                    &golden.backend,
+                    &golden.field,
+                    &golden.hash,
+                    golden.fri_arity,
+                    false,
+                    &golden.profile,
+                );
+                let inputs = PublicInputs::from_json(&package.inputs[&golden.inputs])?;
+                let bytes = core::prove(&config, &ir, &inputs)?.to_bytes();
+                let (hdr, body) = split_proof(&bytes)?;
+                let actual = format!("0x{}", bytes_to_hex(&digest_D(&hdr, body)));
+                let label = format!("{}@{}/{}", golden.inputs, golden.backend, golden.profile);
+                if !actual.eq_ignore_ascii_case(&golden.digest) {
+                    return Err(anyhow!(
+                        "golden digest {} mismatch: expected {} got {}",
+                        label,
+                        golden.digest,
+                        actual
+                    ));
+                }
+                info!("PASS golden {} digest={}", label, actual);
+            }
+            let output = output.unwrap_or_else(|| format!("{}.zkpkg", package.name));
+            write_bytes(&output, &package.to_bytes())?;
+            info!(
+                "✅ PackageBuilt name={} version={} program_hash={} golden={}",
+                package.name,
+                package.version,
+                package.program_hash,
+                package.golden.len()
+            );
+            info!("Wrote: {}", output);
+        }
+        PkgCmd::Install { package } => {
+            let package = Package::load(Path::new(&package))?;
+            let store = PackageStore::new(PackageStore::default_root()?);
+            let air = store.install(&package)?;
+            info!(
+                "✅ PackageInstalled name={} version={} program={}{}",
+                package.name, package.version, PKG_PREFIX, package.name
+            );
+            info!("Installed: {}", air.display());
+        }
+        PkgCmd::List { json } => {
+            let store = PackageStore::new(PackageStore::default_root()?);
+            let packages = store.list()?;
+            if json {
+                let listing: Vec<serde_json::Value> = packages
+                    .iter()
+                    .map(|p| {
+                        serde_json::json!({
+                            "name": p.name,
+                            "version": p.version,
+                            "description": p.description,
+                            "program_hash": p.program_hash,
+                            "profiles": p.profiles,
+                            "inputs": p.inputs.keys().collect::<Vec<_>>(),
+                            "golden": p.golden,
+                        })
+                    })
+                    .collect();
+                output::data(&listing)?;
+            } else if packages.is_empty() {
+                out!("no packages installed in {}", store.root().display());
+            } else {
+                for p in &packages {
+                    out!(
+                        "{} {} program_hash={} profiles={} inputs={}",
+                        p.name,
+                        p.version,
+                        p.program_hash,
+                        p.profiles.join(","),
+                        p.inputs.keys().cloned().collect::<Vec<_>>().join(",")
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
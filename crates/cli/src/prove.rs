@@ -0,0 +1,424 @@
+//! `zkd prove` and `zkd determinism-audit`.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use std::time::Duration;
+use zkprov_bundles::BlindingSource;
+use zkprov_corelib as core;
+use zkprov_corelib::air::AirProgram;
+use zkprov_corelib::audit::AuditOp;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::buildinfo;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::deadline::Deadline;
+use zkprov_corelib::errors::ProveError;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::manifest::DeterminismManifest;
+use zkprov_corelib::manifest::DeterminismVector;
+use zkprov_corelib::proof::container::ProofContainer;
+use zkprov_corelib::proof::{split_proof, ProofView};
+use zkprov_corelib::registry;
+use zkprov_corelib::timestamp;
+use zkprov_corelib::trace::memory::check_memory_budget;
+use zkprov_corelib::trace::Trace;
+use zkprov_corelib::trace::{Reuse, TraceCache};
+use zkprov_corelib::validate::validate_config_with_warnings;
+
+use super::isolate::{self, IsolateLimits, WorkerRequest};
+use super::trace::read_trace;
+use super::{
+    audit, bytes_to_hex, exit_for_corrupt_proof, mk_config, output, print_warnings, read_to_string,
+    run_shape, storage, tsa, write_bytes, DeterminismAuditArgs, ProveArgs, EXIT_INVALID_CONFIG,
+    EXIT_ISOLATE_FAILURE, EXIT_TIMED_OUT,
+};
+
+/// `zkd prove`.
+pub fn prove(args: ProveArgs) -> Result<()> {
+    let ProveArgs {
+        program_path,
+        inputs_path,
+        proof_out,
+        stats,
+        trace_path,
+        incremental_cache,
+        isolate,
+        deadline,
+        build_info,
+        max_mem_mb,
+        timestamp_url,
+        native_out,
+        archive,
+        config_path,
+        audit_log,
+        isolate_mem_mb,
+        isolate_timeout_secs,
+        adaptive_queries,
+        grind_budget,
+        measure_grind,
+        embed_inputs,
+        cfg,
+    } = args;
+    registry::ensure_builtins_registered();
+    let mut config = mk_config(&cfg, &program_path)?;
+    if let Some(budget) = deadline {
+        config = config.with_deadline(Deadline::after(budget));
+    }
+    let adaptive = if adaptive_queries {
+        Some(choose_adaptive_queries(
+            &mut config,
+            grind_budget,
+            measure_grind,
+        )?)
+    } else {
+        None
+    };
+    let warnings = validate_config_with_warnings(&config).map_err(|e| anyhow!(e.to_string()))?;
+    print_warnings(&warnings);
+    let inputs = read_to_string(&inputs_path)?;
+    let air = AirProgram::load_from_file(&program_path)?;
+    let auditor = audit::Auditor::open(
+        audit_log.as_deref(),
+        AuditOp::Prove,
+        &config,
+        &program_path,
+        &inputs,
+    )?;
+
+    let trace = match &trace_path {
+        Some(path) => Some(read_trace(path)?),
+        None => None,
+    };
+    let (air_shape, evaluated_rows) = run_shape(&config, &air, &inputs)?;
+    let mut shape = air_shape;
+    if let Some(t) = &trace {
+        shape.rows = t.rows;
+    }
+    let budget = [max_mem_mb, isolate.then_some(isolate_mem_mb)]
+        .into_iter()
+        .flatten()
+        .min()
+        .map(|mb| mb.saturating_mul(1024 * 1024))
+        .or_else(isolate::available_memory_bytes);
+    if let Some(limit) = budget {
+        if let Err(e) = check_memory_budget(&config, &shape, limit) {
+            eprintln!("❌ MemoryBudgetExceeded {}", e);
+            output::exit(EXIT_INVALID_CONFIG);
+        }
+    }
+    let mut reuse = None;
+    let proof = if isolate {
+        let req = WorkerRequest {
+            config: config.clone(),
+            inputs: inputs.clone(),
+            program_path: program_path.clone(),
+            trace_path: trace_path.clone(),
+        };
+        let limits = IsolateLimits {
+            mem_mb: isolate_mem_mb,
+            timeout_secs: isolate_timeout_secs,
+        };
+        isolate::prove_isolated(&req, limits).unwrap_or_else(|failure| {
+            audit::failed(&auditor, &failure.to_json());
+            eprintln!("❌ IsolatedProveFailed {}", failure.to_json());
+            output::exit(EXIT_ISOLATE_FAILURE);
+        })
+    } else if let (Some(cache_path), Some(t)) = (&incremental_cache, &trace) {
+        let (proof, r) =
+            prove_incremental_with_registry(&config, &program_path, &inputs, t, cache_path)
+                .or_else(|e| {
+                    audit::failed(&auditor, &e);
+                    exit_if_timed_out(e, Some(cache_path))
+                })?;
+        reuse = Some(r);
+        proof
+    } else {
+        prove_with_registry(&config, &program_path, &inputs, trace.as_ref()).or_else(|e| {
+            audit::failed(&auditor, &e);
+            exit_if_timed_out(e, None)
+        })?
+    };
+    if embed_inputs {
+        let container = embed_inputs_container(&proof, &program_path, &inputs)?;
+        write_bytes(&proof_out, &container)?;
+    } else {
+        write_bytes(&proof_out, &proof)?;
+    }
+    let (hdr, body) = split_proof(&proof).unwrap_or_else(|e| exit_for_corrupt_proof(&e));
+    let digest = digest_D(&hdr, body);
+    if let Some(path) = &native_out {
+        let view =
+            ProofView::from_bytes(&proof).unwrap_or_else(|e| exit_for_corrupt_proof(&anyhow!(e)));
+        write_bytes(path, view.native_proof())?;
+    }
+    let manifest = DeterminismManifest {
+        program: air.meta.name.clone(),
+        digest: format!("0x{}", bytes_to_hex(&digest)),
+        determinism_vector: DeterminismVector::from_config(
+            &config,
+            format!("{:016x}", hdr.pubio_hash),
+        )
+        .with_fixed_columns(air.columns.fixed_commitment())
+        .with_rows(evaluated_rows)
+        .with_adaptive(adaptive),
+        build: build_info.then(buildinfo::build_info),
+    };
+    let manifest_out = manifest_path_for(&proof_out);
+    write_bytes(&manifest_out, manifest.to_json_pretty()?.as_bytes())?;
+    let stamped = match &timestamp_url {
+        Some(url) => {
+            let nonce_bytes = BlindingSource::os().blind("zkd.timestamp.nonce")?;
+            let mut nonce = [0u8; 8];
+            nonce.copy_from_slice(&nonce_bytes[..8]);
+            let client = tsa::HttpTsa { url: url.clone() };
+            let (token, info) = timestamp::stamp(&client, &digest, u64::from_be_bytes(nonce))?;
+            let token_out = tsa::token_path_for(&proof_out);
+            write_bytes(&token_out, &token)?;
+            Some((token_out, info))
+        }
+        None => None,
+    };
+    let archived = if archive {
+        let store = storage::open(config_path.as_deref())?;
+        storage::archive(store.as_ref(), &digest, &proof_out)?
+    } else {
+        Vec::new()
+    };
+    if let Some(auditor) = &auditor {
+        auditor.ok(&digest)?;
+    }
+    info!(
+        "✅ ProofGenerated backend={} profile={} body_len={} pubio_hash=0x{:016x}",
+        config.backend_id, config.profile_id, hdr.body_len, hdr.pubio_hash
+    );
+    for (key, value) in &config.profile_overrides {
+        info!("profile_override {}={}", key, value);
+    }
+    if let Some(a) = &adaptive {
+        info!(
+            "adaptive_queries fri_queries={} grind_bits={} security_bits={} lambda_bits={} grind_ns={} budget_ms={}",
+            a.fri_queries, a.grind_bits, a.security_bits, a.lambda_bits, a.grind_ns, a.budget_ms
+        );
+    }
+    if let (Some(path), Some(t)) = (&trace_path, &trace) {
+        info!("Trace: {} rows={} cols={}", path, t.rows, t.cols());
+    }
+    if stats {
+        let shape = air_shape;
+        info!(
+            "stats rows={} cols={} const={} periodic={}",
+            shape.rows, shape.cols, shape.const_cols, shape.periodic_cols
+        );
+        if let Some(r) = reuse {
+            info!(
+                "stats incremental reused_cols={}/{} reuse={:.1}%",
+                r.reused,
+                r.columns,
+                r.percent()
+            );
+        }
+    }
+    info!("Program: {}", program_path);
+    info!("Wrote: {}", proof_out);
+    info!("Manifest: {}", manifest_out);
+    if let Some(path) = &native_out {
+        info!("Native proof: {} ({} bytes)", path, hdr.body_len);
+    }
+    if let Some((token_out, info)) = stamped {
+        info!(
+            "Timestamp: {} time={} serial={}",
+            token_out, info.time, info.serial
+        );
+    }
+    for location in &archived {
+        info!("Archived: {}", location);
+    }
+    output::field("backend", &config.backend_id);
+    output::field("profile", &config.profile_id);
+    output::field("digest", &manifest.digest);
+    output::field("body_len", hdr.body_len);
+    output::field("pubio_hash", format!("0x{:016x}", hdr.pubio_hash));
+    output::field("proof", &proof_out);
+    output::field("manifest", &manifest_out);
+    if stats {
+        let mut stats = serde_json::json!({
+            "rows": air_shape.rows,
+            "cols": air_shape.cols,
+            "const": air_shape.const_cols,
+            "periodic": air_shape.periodic_cols,
+        });
+        if let Some(r) = reuse {
+            stats["reused_cols"] = r.reused.into();
+            stats["columns"] = r.columns.into();
+        }
+        output::field("stats", stats);
+    }
+    output::result(&manifest.digest);
+    Ok(())
+}
+
+/// `zkd determinism-audit`.
+pub fn determinism_audit(args: DeterminismAuditArgs) -> Result<()> {
+    let DeterminismAuditArgs {
+        program_path,
+        inputs_path,
+        threads,
+        json,
+        cfg,
+    } = args;
+    registry::ensure_builtins_registered();
+    let config = mk_config(&cfg, &program_path)?;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let inputs = PublicInputs::from_json(&read_to_string(&inputs_path)?)?;
+    let report = core::determinism::audit(&config, &ir, &inputs, &threads)?;
+    if json {
+        output::data(&report)?;
+    } else {
+        for f in &report.findings {
+            info!(
+                "{} {} ({}): {}",
+                f.severity.as_str(),
+                f.source,
+                f.origin,
+                f.detail
+            );
+        }
+        for run in &report.runs {
+            info!("threads={} digests={}", run.threads, run.digests.join(","));
+        }
+    }
+    let proofs: usize = report.runs.iter().map(|r| r.digests.len()).sum();
+    if report.deterministic {
+        if !json {
+            info!(
+                "✅ DeterminismAuditPassed backend={} profile={} proofs={} warnings={}",
+                report.backend,
+                report.profile,
+                proofs,
+                report.warnings()
+            );
+            output::result("ok");
+        }
+    } else {
+        if !json {
+            info!(
+                "❌ NondeterminismDetected backend={} profile={} proofs={}",
+                report.backend, report.profile, proofs
+            );
+            output::on_failure("fail");
+        }
+        output::exit(1);
+    }
+    Ok(())
+}
+
+/// `zkd prove --adaptive-queries`: pick `fri_queries` / `grind_bits` for the
+/// profile's `lambda_bits` and add them to the run's profile overrides. The
+/// reference cost model is used unless `measure` asks to time this host.
+fn choose_adaptive_queries(
+    config: &mut Config,
+    budget: Option<Duration>,
+    measure: bool,
+) -> Result<core::profile::adaptive::AdaptiveChoice> {
+    use core::profile::adaptive::{solve, CostModel, DEFAULT_GRIND_BUDGET};
+    for key in ["fri_queries", "grind_bits"] {
+        if config.profile_overrides.contains_key(key) {
+            return Err(anyhow!(
+                "--adaptive-queries chooses {}; drop its --profile-override",
+                key
+            ));
+        }
+    }
+    let profile = core::profile::resolve_profile(&config.profile_id, &config.profile_overrides)?;
+    let model = if measure {
+        CostModel::measure(&config.hash)?
+    } else {
+        CostModel::REFERENCE
+    };
+    let choice = solve(&profile, &model, budget.unwrap_or(DEFAULT_GRIND_BUDGET))?;
+    config
+        .profile_overrides
+        .insert("fri_queries".to_string(), choice.fri_queries);
+    config
+        .profile_overrides
+        .insert("grind_bits".to_string(), choice.grind_bits);
+    Ok(choice)
+}
+
+/// Path of the determinism manifest persisted next to a proof.
+fn manifest_path_for(proof_out: &str) -> String {
+    format!("{proof_out}.manifest.json")
+}
+
+/// `prove --embed-inputs`: `proof` in a container with `inputs`. Declared
+/// `bytes_ref` files are replaced by their digests so the copy stands alone.
+fn embed_inputs_container(proof: &[u8], program_path: &str, inputs: &str) -> Result<Vec<u8>> {
+    let ir = core::air::parse_air_file(Path::new(program_path))?;
+    let value: serde_json::Value = serde_json::from_str(inputs)?;
+    let value =
+        core::air::public::resolve_bytes_refs(&ir.public_inputs, &value, ir.meta.hash.as_str())?
+            .unwrap_or(value);
+    Ok(ProofContainer::from_proof(proof)?
+        .with_inputs(&value)
+        .encode())
+}
+
+/// Prove through the registry; `config.backend_id` picks the backend.
+pub fn prove_with_registry(
+    config: &Config,
+    program_path: &str,
+    inputs_json: &str,
+    trace: Option<&Trace>,
+) -> Result<Vec<u8>> {
+    let ir = core::air::parse_air_file(Path::new(program_path))?;
+    let inputs = PublicInputs::from_json(inputs_json)?;
+    let proof = match trace {
+        Some(t) => core::prove_with_trace(config, &ir, &inputs, t)?,
+        None => core::prove(config, &ir, &inputs)?,
+    };
+    Ok(proof.to_bytes())
+}
+
+/// [`prove_with_registry`] from a trace, reusing and then updating the
+/// incremental trace cache at `cache_path`.
+fn prove_incremental_with_registry(
+    config: &Config,
+    program_path: &str,
+    inputs_json: &str,
+    trace: &Trace,
+    cache_path: &str,
+) -> Result<(Vec<u8>, Reuse)> {
+    let ir = core::air::parse_air_file(Path::new(program_path))?;
+    let inputs = PublicInputs::from_json(inputs_json)?;
+    let mut cache = TraceCache::load(Path::new(cache_path))?;
+    let result = core::prove_incremental(config, &ir, &inputs, trace, &mut cache);
+    // Keep what a timed-out run already committed so the next run resumes.
+    if matches!(result, Ok(_) | Err(ProveError::TimedOut { .. })) {
+        cache.save(Path::new(cache_path))?;
+    }
+    let (proof, reuse) = result?;
+    Ok((proof.to_bytes(), reuse))
+}
+
+/// Exit with [`EXIT_TIMED_OUT`] and the partial phase stats if `err` is a
+/// deadline abort; otherwise hand it back. `checkpoint` is the incremental
+/// cache saved for resuming, if any.
+fn exit_if_timed_out<T>(err: anyhow::Error, checkpoint: Option<&String>) -> Result<T> {
+    if let Some(ProveError::TimedOut {
+        phase,
+        elapsed_ms,
+        budget_ms,
+        phases,
+    }) = err.downcast_ref::<ProveError>()
+    {
+        let report = serde_json::json!({
+            "phase": phase,
+            "elapsed_ms": elapsed_ms,
+            "budget_ms": budget_ms,
+            "phases": phases,
+            "checkpoint": checkpoint,
+        });
+        eprintln!("❌ TimedOut {}", report);
+        output::exit(EXIT_TIMED_OUT);
+    }
+    Err(err)
+}
@@ -0,0 +1,228 @@
+//! Chunked QR export of proofs for air-gapped transfer (`zkd proof-qr`,
+//! `zkd proof-qr-assemble`).
+//!
+//! The proof is base64-armored (see [`super::armor`]) and split into chunks,
+//! each carried by one QR code whose text is
+//!
+//! ```text
+//! ZKDQR1:<index>/<total>:<proof hash>:<base64 slice>
+//! ```
+//!
+//! `index` counts from 1 and `proof hash` is the first 16 hex digits of
+//! BLAKE3 over the whole proof, so chunks of different proofs cannot be
+//! mixed. Assembly accepts the scanned texts in any order, and rejects gaps,
+//! conflicting duplicates and a proof whose hash or `body_len` do not match.
+
+use anyhow::{anyhow, bail, Context, Result};
+use qrcodegen::{QrCode, QrCodeEcc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::armor::{self, Armor};
+use super::{output, read_to_bytes, write_bytes, ProofQrArgs, ProofQrAssembleArgs};
+
+const PREFIX: &str = "ZKDQR1";
+
+/// First 16 hex digits of BLAKE3(`proof`).
+fn proof_hash(proof: &[u8]) -> String {
+    blake3::hash(proof).to_hex()[..16].to_string()
+}
+
+/// Chunk texts for `proof`, each at most `chunk_chars` base64 characters.
+pub fn chunks(proof: &[u8], chunk_chars: usize) -> Result<Vec<String>> {
+    if chunk_chars == 0 {
+        bail!("chunk size must be positive");
+    }
+    let mut text = Vec::new();
+    armor::encode(Armor::Base64, proof, &mut text)?;
+    let text = String::from_utf8(text)?;
+    let hash = proof_hash(proof);
+    let parts: Vec<&[u8]> = text.trim_end().as_bytes().chunks(chunk_chars).collect();
+    let total = parts.len();
+    Ok(parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            format!(
+                "{}:{}/{}:{}:{}",
+                PREFIX,
+                i + 1,
+                total,
+                hash,
+                String::from_utf8_lossy(part)
+            )
+        })
+        .collect())
+}
+
+/// SVG rendering of `qr` with a 4-module quiet zone.
+fn to_svg(qr: &QrCode) -> String {
+    let border = 4;
+    let dim = qr.size() + 2 * border;
+    let mut path = String::new();
+    for y in 0..qr.size() {
+        for x in 0..qr.size() {
+            if qr.get_module(x, y) {
+                path.push_str(&format!("M{},{}h1v1h-1z", x + border, y + border));
+            }
+        }
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" viewBox=\"0 0 {dim} {dim}\" stroke=\"none\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\n\
+         <path d=\"{path}\" fill=\"#000000\"/>\n\
+         </svg>\n"
+    )
+}
+
+/// Write `chunk-NNN.svg` and its text `chunk-NNN.txt` for every chunk into
+/// `out_dir`; returns the number of chunks.
+pub fn write_chunks(proof: &[u8], chunk_chars: usize, out_dir: &Path) -> Result<usize> {
+    let chunks = chunks(proof, chunk_chars)?;
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create dir '{}'", out_dir.display()))?;
+    let width = chunks.len().to_string().len().max(3);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let qr = QrCode::encode_text(chunk, QrCodeEcc::Medium).map_err(|_| {
+            anyhow!(
+                "chunk of {} characters does not fit in a QR code; lower --chunk-size",
+                chunk.len()
+            )
+        })?;
+        let stem = out_dir.join(format!("chunk-{:0width$}", i + 1));
+        fs::write(stem.with_extension("svg"), to_svg(&qr))
+            .with_context(|| format!("failed to write '{}.svg'", stem.display()))?;
+        fs::write(stem.with_extension("txt"), format!("{}\n", chunk))
+            .with_context(|| format!("failed to write '{}.txt'", stem.display()))?;
+    }
+    Ok(chunks.len())
+}
+
+/// Rebuild the proof from scanned chunk texts, in any order.
+pub fn assemble<S: AsRef<str>>(texts: &[S]) -> Result<Vec<u8>> {
+    let mut hash: Option<String> = None;
+    let mut total: Option<usize> = None;
+    let mut parts: BTreeMap<usize, String> = BTreeMap::new();
+    for text in texts {
+        let text = text.as_ref().trim();
+        let mut fields = text.splitn(4, ':');
+        let (Some(PREFIX), Some(pos), Some(h), Some(data)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            bail!("not a {} chunk: '{}'", PREFIX, text);
+        };
+        let (index, count) = pos
+            .split_once('/')
+            .and_then(|(i, n)| Some((i.parse::<usize>().ok()?, n.parse::<usize>().ok()?)))
+            .filter(|&(i, n)| i >= 1 && i <= n)
+            .ok_or_else(|| anyhow!("invalid chunk position '{}'", pos))?;
+        if *hash.get_or_insert_with(|| h.to_string()) != h {
+            bail!(
+                "chunk {} belongs to proof {}, not {}",
+                index,
+                h,
+                hash.unwrap()
+            );
+        }
+        if *total.get_or_insert(count) != count {
+            bail!(
+                "chunk {} declares {} chunks, others {}",
+                index,
+                count,
+                total.unwrap()
+            );
+        }
+        if let Some(prev) = parts.insert(index, data.to_string()) {
+            if prev != data {
+                bail!("conflicting copies of chunk {}", index);
+            }
+        }
+    }
+    let total = total.ok_or_else(|| anyhow!("no chunks given"))?;
+    let missing: Vec<String> = (1..=total)
+        .filter(|i| !parts.contains_key(i))
+        .map(|i| i.to_string())
+        .collect();
+    if !missing.is_empty() {
+        bail!("missing chunks {} of {}", missing.join(", "), total);
+    }
+    let text: String = parts.into_values().collect();
+    let mut proof = Vec::new();
+    armor::decode(Armor::Base64, text.as_bytes(), &mut proof)?;
+    let expected = hash.unwrap_or_default();
+    if proof_hash(&proof) != expected {
+        bail!(
+            "assembled proof hash {} does not match chunk headers ({})",
+            proof_hash(&proof),
+            expected
+        );
+    }
+    Ok(proof)
+}
+
+/// Chunk texts from `paths`: directories contribute their `*.txt` files,
+/// files contribute each non-empty line.
+pub fn read_chunk_texts(paths: &[String]) -> Result<Vec<String>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        let path = Path::new(path);
+        if path.is_dir() {
+            let mut txt: Vec<PathBuf> = fs::read_dir(path)
+                .with_context(|| format!("failed to list '{}'", path.display()))?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+                .collect();
+            txt.sort();
+            files.extend(txt);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    let mut out = Vec::new();
+    for file in files {
+        let s = fs::read_to_string(&file)
+            .with_context(|| format!("failed to read '{}'", file.display()))?;
+        out.extend(
+            s.lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string),
+        );
+    }
+    Ok(out)
+}
+
+/// `zkd proof-qr`.
+pub fn proof_qr(args: ProofQrArgs) -> Result<()> {
+    let ProofQrArgs {
+        proof_path,
+        out_dir,
+        chunk_size,
+    } = args;
+    let proof = read_to_bytes(&proof_path)?;
+    let count = write_chunks(&proof, chunk_size, Path::new(&out_dir))?;
+    info!(
+        "✅ ProofQr chunks={} proof_bytes={} dir={}",
+        count,
+        proof.len(),
+        out_dir
+    );
+    Ok(())
+}
+
+/// `zkd proof-qr-assemble`.
+pub fn proof_qr_assemble(args: ProofQrAssembleArgs) -> Result<()> {
+    let ProofQrAssembleArgs { inputs, output } = args;
+    let texts = read_chunk_texts(&inputs)?;
+    let proof = assemble(&texts)?;
+    write_bytes(&output, &proof)?;
+    info!(
+        "✅ ProofQrAssembled chunks={} proof_bytes={}",
+        texts.len(),
+        proof.len()
+    );
+    info!("Wrote: {}", output);
+    Ok(())
+}
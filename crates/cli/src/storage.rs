@@ -0,0 +1,261 @@
+//! Artifact stores for `zkd archive` and `zkd prove --archive`.
+//!
+//! The store is taken from the `[storage]` table of `zkd.toml` (see
+//! [`zkprov_corelib::storage`]). With the `s3` feature, `kind = "s3"` talks
+//! to any S3-compatible endpoint through `curl --aws-sigv4`, so proxies and
+//! custom CAs follow the user's curl configuration; credentials are read
+//! from the environment and handed to curl on stdin, never on its command
+//! line.
+
+use anyhow::{anyhow, bail, Result};
+use std::path::{Path, PathBuf};
+
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::proof::split_proof;
+use zkprov_corelib::storage::{artifact_key, FsStorage, Storage, StorageConfig, ARTIFACTS};
+
+use super::{
+    bytes_to_hex, exit_for_corrupt_proof, hex_to_bytes, open_proof, output, read_to_bytes,
+    ArchiveCmd, EXIT_CORRUPT_PROOF,
+};
+
+/// Config file used when `--config` and `ZKD_CONFIG` are both unset.
+const DEFAULT_CONFIG: &str = "zkd.toml";
+
+/// Open the store configured in `config_path` (else `$ZKD_CONFIG`, else
+/// `./zkd.toml`). A relative `fs` root is resolved against the config
+/// file's directory.
+pub fn open(config_path: Option<&str>) -> Result<Box<dyn Storage>> {
+    let path = config_path
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("ZKD_CONFIG").map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG));
+    let config = StorageConfig::load(&path)?
+        .ok_or_else(|| anyhow!("{} has no [storage] table", path.display()))?;
+    match config {
+        StorageConfig::Fs { root } => {
+            let base = path.parent().unwrap_or(Path::new(""));
+            Ok(Box::new(FsStorage::new(base.join(root))))
+        }
+        #[cfg(feature = "s3")]
+        StorageConfig::S3(s3) => Ok(Box::new(s3::S3Storage::from_env(s3)?)),
+        #[cfg(not(feature = "s3"))]
+        StorageConfig::S3(_) => {
+            bail!("storage kind \"s3\" needs zkd built with the `s3` feature")
+        }
+    }
+}
+
+/// Fetch every stored artifact of `digest_d` next to `proof_out`; the proof
+/// itself is required, sidecars are fetched when present.
+pub fn fetch(store: &dyn Storage, digest_d: &[u8; 32], proof_out: &str) -> Result<Vec<String>> {
+    let mut written = Vec::new();
+    for (name, suffix) in ARTIFACTS {
+        let key = artifact_key(digest_d, name);
+        if !suffix.is_empty() && !store.exists(&key)? {
+            continue;
+        }
+        let dest = format!("{proof_out}{suffix}");
+        store.get(&key, Path::new(&dest))?;
+        written.push(dest);
+    }
+    Ok(written)
+}
+
+/// Upload `proof_path` and whichever sidecars exist next to it under
+/// `digest_d`; returns the locations written.
+pub fn archive(store: &dyn Storage, digest_d: &[u8; 32], proof_path: &str) -> Result<Vec<String>> {
+    let mut stored = Vec::new();
+    for (name, suffix) in ARTIFACTS {
+        let src = format!("{proof_path}{suffix}");
+        if !Path::new(&src).is_file() {
+            if suffix.is_empty() {
+                bail!("no proof at {}", src);
+            }
+            continue;
+        }
+        let key = artifact_key(digest_d, name);
+        store.put(&key, Path::new(&src))?;
+        stored.push(store.locate(&key));
+    }
+    Ok(stored)
+}
+
+#[cfg(feature = "s3")]
+mod s3 {
+    use anyhow::{anyhow, bail, Context, Result};
+    use std::io::Write;
+    use std::path::Path;
+    use std::process::{Command, Output, Stdio};
+
+    use zkprov_corelib::storage::{check_key, S3Config, Storage};
+
+    pub struct S3Storage {
+        config: S3Config,
+        access_key: String,
+        secret_key: String,
+        session_token: Option<String>,
+    }
+
+    impl S3Storage {
+        pub fn from_env(config: S3Config) -> Result<Self> {
+            let var = |name: &str| {
+                std::env::var(name).map_err(|_| anyhow!("S3 credentials: ${} is not set", name))
+            };
+            Ok(Self {
+                access_key: var(&config.access_key_env)?,
+                secret_key: var(&config.secret_key_env)?,
+                session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+                config,
+            })
+        }
+
+        fn url(&self, key: &str) -> Result<String> {
+            check_key(key)?;
+            Ok(format!(
+                "{}/{}/{}{}",
+                self.config.endpoint.trim_end_matches('/'),
+                self.config.bucket,
+                self.config.prefix,
+                key
+            ))
+        }
+
+        /// Run curl against `key` with SigV4 signing configured on stdin.
+        fn curl(&self, key: &str, args: &[&str]) -> Result<Output> {
+            let url = self.url(key)?;
+            let mut config = format!(
+                "user = \"{}:{}\"\naws-sigv4 = \"aws:amz:{}:s3\"\n",
+                quote(&self.access_key),
+                quote(&self.secret_key),
+                quote(&self.config.region)
+            );
+            if let Some(token) = &self.session_token {
+                config.push_str(&format!(
+                    "header = \"x-amz-security-token: {}\"\n",
+                    quote(token)
+                ));
+            }
+            let mut child = Command::new("curl")
+                .args(["--silent", "--show-error", "--config", "-"])
+                .args(args)
+                .arg(&url)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .context("failed to run curl")?;
+            child
+                .stdin
+                .take()
+                .ok_or_else(|| anyhow!("curl stdin unavailable"))?
+                .write_all(config.as_bytes())?;
+            Ok(child.wait_with_output()?)
+        }
+
+        fn check(&self, key: &str, out: &Output) -> Result<()> {
+            if !out.status.success() {
+                bail!(
+                    "{} returned {}: {}",
+                    self.locate(key),
+                    out.status,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// Escape `s` for a double-quoted curl config value.
+    fn quote(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    impl Storage for S3Storage {
+        fn put(&self, key: &str, src: &Path) -> Result<()> {
+            let src = src.to_str().ok_or_else(|| anyhow!("non-UTF-8 path"))?;
+            let out = self.curl(key, &["--fail", "--upload-file", src])?;
+            self.check(key, &out)
+        }
+
+        fn get(&self, key: &str, dest: &Path) -> Result<()> {
+            let dest_str = dest.to_str().ok_or_else(|| anyhow!("non-UTF-8 path"))?;
+            let out = self.curl(key, &["--fail", "--output", dest_str])?;
+            if let Err(e) = self.check(key, &out) {
+                let _ = std::fs::remove_file(dest);
+                return Err(e);
+            }
+            Ok(())
+        }
+
+        fn exists(&self, key: &str) -> Result<bool> {
+            let out = self.curl(key, &["--head", "--write-out", "\n%{http_code}"])?;
+            self.check(key, &out)?;
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            match stdout.lines().last().map(str::trim) {
+                Some("200") => Ok(true),
+                Some("404") => Ok(false),
+                other => bail!(
+                    "{}: unexpected HEAD status {}",
+                    self.locate(key),
+                    other.unwrap_or("(none)")
+                ),
+            }
+        }
+
+        fn locate(&self, key: &str) -> String {
+            self.url(key).unwrap_or_else(|_| key.to_string())
+        }
+    }
+}
+
+/// `zkd archive …`.
+pub fn archive_cmd(action: ArchiveCmd) -> Result<()> {
+    match action {
+        ArchiveCmd::Put {
+            proof_path,
+            config_path,
+        } => {
+            let (proof, _) = open_proof(read_to_bytes(&proof_path)?, &proof_path)
+                .unwrap_or_else(|e| exit_for_corrupt_proof(&e));
+            let (hdr, body) = split_proof(&proof).unwrap_or_else(|e| exit_for_corrupt_proof(&e));
+            let digest = digest_D(&hdr, body);
+            let store = open(config_path.as_deref())?;
+            let stored = archive(store.as_ref(), &digest, &proof_path)?;
+            info!("✅ Archived digest=0x{}", bytes_to_hex(&digest));
+            for location in &stored {
+                info!("Stored: {}", location);
+            }
+            output::result(format_args!("0x{}", bytes_to_hex(&digest)));
+        }
+        ArchiveCmd::Get {
+            digest,
+            output,
+            config_path,
+        } => {
+            let digest: [u8; 32] = hex_to_bytes(digest.trim().trim_start_matches("0x"))
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or_else(|| anyhow!("--digest must be 32 bytes of hex"))?;
+            let store = open(config_path.as_deref())?;
+            let written = fetch(store.as_ref(), &digest, &output)?;
+            // The key is only a claim; recompute D over what was fetched.
+            let (proof, _) = open_proof(read_to_bytes(&output)?, &output)
+                .unwrap_or_else(|e| exit_for_corrupt_proof(&e));
+            let (hdr, body) = split_proof(&proof).unwrap_or_else(|e| exit_for_corrupt_proof(&e));
+            if digest_D(&hdr, body) != digest {
+                eprintln!(
+                    "❌ fetched proof does not match digest 0x{}",
+                    bytes_to_hex(&digest)
+                );
+                output::exit(EXIT_CORRUPT_PROOF);
+            }
+            info!("✅ Fetched digest=0x{}", bytes_to_hex(&digest));
+            for path in &written {
+                info!("Wrote: {}", path);
+            }
+            output::result(format_args!("0x{}", bytes_to_hex(&digest)));
+        }
+    }
+    Ok(())
+}
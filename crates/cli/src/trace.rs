@@ -0,0 +1,295 @@
+//! Traces: `zkd trace-import`, `zkd trace-export`, `zkd trace-dump` and
+//! `zkd check`.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+use zkprov_corelib as core;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::trace::eval as trace_eval;
+use zkprov_corelib::trace::Trace;
+#[cfg(feature = "prover")]
+use zkprov_corelib::trace::TraceCompression;
+
+use super::{output, read_to_string, write_bytes, CheckArgs, TraceDumpArgs};
+#[cfg(feature = "prover")]
+use super::{TraceExportArgs, TraceImportArgs};
+
+/// `zkd trace-import`.
+#[cfg(feature = "prover")]
+pub fn trace_import(args: TraceImportArgs) -> Result<()> {
+    let TraceImportArgs {
+        input,
+        output,
+        compress,
+        program_path,
+    } = args;
+    let compression = TraceCompression::parse(&compress)?;
+    let value: serde_json::Value = serde_json::from_str(&read_to_string(&input)?)
+        .with_context(|| format!("failed to parse trace JSON '{}'", input))?;
+    let trace = Trace::from_json(&value)?;
+    if let Some(program) = &program_path {
+        let ir = core::air::parse_air_file(Path::new(program))?;
+        trace.check_shape(&ir)?;
+    }
+    let bytes = trace.encode(compression)?;
+    write_bytes(&output, &bytes)?;
+    info!(
+        "✅ TraceImported field={} rows={} cols={} elem_width={} bytes={}",
+        trace.field,
+        trace.rows,
+        trace.cols(),
+        trace.elem_width,
+        bytes.len()
+    );
+    info!("Wrote: {}", output);
+    Ok(())
+}
+
+/// `zkd trace-export`.
+#[cfg(feature = "prover")]
+pub fn trace_export(args: TraceExportArgs) -> Result<()> {
+    let TraceExportArgs { input, output } = args;
+    let trace = read_trace(&input)?;
+    let json = serde_json::to_string_pretty(&trace.to_json())?;
+    match output {
+        Some(path) => {
+            write_bytes(&path, json.as_bytes())?;
+            info!("Wrote: {}", path);
+        }
+        None if output::is_json() => output::data(&trace.to_json())?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// `zkd trace-dump`.
+pub fn trace_dump(args: TraceDumpArgs) -> Result<()> {
+    let TraceDumpArgs {
+        program_path,
+        trace_path,
+        rows,
+        csv,
+    } = args;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let trace = read_trace(&trace_path)?;
+    if trace.cols() != ir.columns.trace_cols {
+        return Err(anyhow!(
+            "trace has {} columns, AIR declares {}",
+            trace.cols(),
+            ir.columns.trace_cols
+        ));
+    }
+    let range = parse_row_range(&rows, trace.rows)?;
+    let violations = trace_eval::violations(&trace, &ir, None)?;
+    let dump = TraceDump::new(&ir, &trace, range, &violations);
+    output::document(&dump.render_table());
+    out!(
+        "rows {}..{} of {}  violations={}",
+        dump.range.start,
+        dump.range.end,
+        trace.rows,
+        violations.len()
+    );
+    for v in &violations {
+        out!(
+            "✗ #{} at row {} -> {}: {} = 0",
+            v.constraint,
+            v.row,
+            v.row + 1,
+            v.display
+        );
+    }
+    if let Some(path) = csv {
+        write_bytes(&path, dump.render_csv().as_bytes())?;
+        info!("Wrote: {}", path);
+    }
+    Ok(())
+}
+
+/// `zkd check`.
+pub fn check(args: CheckArgs) -> Result<()> {
+    let CheckArgs {
+        program_path,
+        inputs_path,
+        trace_path,
+        max_violations,
+    } = args;
+    let started = std::time::Instant::now();
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let inputs: serde_json::Value = serde_json::from_str(&read_to_string(&inputs_path)?)
+        .with_context(|| format!("invalid inputs JSON '{}'", inputs_path))?;
+    core::air::public::validate_public_inputs(&ir.public_inputs, &inputs)?;
+    let trace = read_trace(&trace_path)?;
+    trace.check_shape(&ir)?;
+    let constraints = ir.transition_constraints()?.len();
+    // One past the limit tells us whether the list was truncated.
+    let mut violations =
+        trace_eval::violations(&trace, &ir, Some(max_violations.saturating_add(1)))?;
+    let mut truncated = violations.len() > max_violations;
+    violations.truncate(max_violations);
+    let mut boundary = trace_eval::boundary_violations(&trace, &ir, &inputs)?;
+    let room = max_violations - violations.len();
+    truncated |= boundary.len() > room;
+    boundary.truncate(room);
+    for v in &violations {
+        out!(
+            "✗ #{} at row {} -> {}: {} = 0",
+            v.constraint,
+            v.row,
+            v.row + 1,
+            v.display
+        );
+        let cells: Vec<String> = v
+            .cells
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        out!("    {}", cells.join(" "));
+    }
+    for b in &boundary {
+        out!(
+            "✗ boundary {} at row {}: expected {}, found {}",
+            b.display,
+            b.row,
+            b.expected,
+            b.actual
+        );
+    }
+    let elapsed_ms = started.elapsed().as_millis();
+    if violations.is_empty() && boundary.is_empty() {
+        out!(
+            "✅ check ok: rows={} transitions={} boundary={} ({} ms)",
+            trace.rows,
+            constraints,
+            ir.boundary.len(),
+            elapsed_ms
+        );
+    } else {
+        out!(
+            "❌ check failed: violations={}{} ({} ms)",
+            violations.len() + boundary.len(),
+            if truncated { "+" } else { "" },
+            elapsed_ms
+        );
+        output::exit(1);
+    }
+    Ok(())
+}
+
+pub fn read_trace(path: &str) -> Result<Trace> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read '{}'", path))?;
+    Trace::parse(&bytes).with_context(|| format!("invalid trace file '{}'", path))
+}
+
+/// Parse `START..END` (end exclusive, either side optional), clamped to `rows`.
+fn parse_row_range(spec: &str, rows: u32) -> Result<std::ops::Range<u32>> {
+    let (start, end) = spec
+        .split_once("..")
+        .ok_or_else(|| anyhow!("row range '{}' must look like START..END", spec))?;
+    let bound = |s: &str, default: u32| -> Result<u32> {
+        match s.trim() {
+            "" => Ok(default),
+            n => n
+                .parse()
+                .with_context(|| format!("invalid row bound '{}' in '{}'", n, spec)),
+        }
+    };
+    let start = bound(start, 0)?;
+    let end = bound(end, rows)?.min(rows);
+    if start >= end {
+        return Err(anyhow!(
+            "row range '{}' selects no rows (trace has {})",
+            spec,
+            rows
+        ));
+    }
+    Ok(start..end)
+}
+
+/// Rows selected for `zkd trace-dump`, with per-row violation markers.
+struct TraceDump {
+    headers: Vec<String>,
+    range: std::ops::Range<u32>,
+    rows: Vec<(u32, Vec<String>, Vec<usize>)>,
+}
+
+impl TraceDump {
+    fn new(
+        ir: &AirIr,
+        trace: &Trace,
+        range: std::ops::Range<u32>,
+        violations: &[trace_eval::Violation],
+    ) -> Self {
+        let named: Vec<&String> = ir
+            .columns
+            .names
+            .iter()
+            .chain(&ir.columns.selectors)
+            .collect();
+        let headers = (0..trace.cols() as usize)
+            .map(|c| {
+                named
+                    .get(c)
+                    .map(|n| n.to_string())
+                    .unwrap_or(format!("c{}", c))
+            })
+            .collect();
+        let rows = range
+            .clone()
+            .map(|row| {
+                let values = (0..trace.cols())
+                    .map(|col| trace.get(row, col).unwrap_or_default().to_string())
+                    .collect();
+                let failing = violations
+                    .iter()
+                    .filter(|v| v.row == row)
+                    .map(|v| v.constraint)
+                    .collect();
+                (row, values, failing)
+            })
+            .collect();
+        Self {
+            headers,
+            range,
+            rows,
+        }
+    }
+
+    fn render_table(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.len()).collect();
+        for (_, values, _) in &self.rows {
+            for (w, v) in widths.iter_mut().zip(values) {
+                *w = (*w).max(v.len());
+            }
+        }
+        let row_w = self.range.end.to_string().len().max(3);
+        let mut out = format!("  {:>row_w$}", "row");
+        for (h, w) in self.headers.iter().zip(&widths) {
+            out.push_str(&format!("  {:>w$}", h));
+        }
+        out.push('\n');
+        for (row, values, failing) in &self.rows {
+            let mark = if failing.is_empty() { ' ' } else { '!' };
+            out.push_str(&format!("{} {:>row_w$}", mark, row));
+            for (v, w) in values.iter().zip(&widths) {
+                out.push_str(&format!("  {:>w$}", v));
+            }
+            if !failing.is_empty() {
+                let ids: Vec<String> = failing.iter().map(|i| format!("#{}", i)).collect();
+                out.push_str(&format!("  ✗ {}", ids.join(" ")));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn render_csv(&self) -> String {
+        let mut out = format!("row,{},violations\n", self.headers.join(","));
+        for (row, values, failing) in &self.rows {
+            let ids: Vec<String> = failing.iter().map(|i| i.to_string()).collect();
+            out.push_str(&format!("{},{},{}\n", row, values.join(","), ids.join(";")));
+        }
+        out
+    }
+}
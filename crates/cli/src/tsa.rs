@@ -0,0 +1,108 @@
+//! RFC 3161 time-stamping over HTTP for `zkd prove --timestamp-url`, and
+//! token authentication for `zkd verify --tsa-ca`.
+//!
+//! The request is POSTed with `curl` (so proxies, client certificates and
+//! custom CAs follow the user's curl configuration); only `SHA-256(D)` is
+//! sent, never the proof. Tokens are checked with `openssl ts -verify`.
+
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::bytes_to_hex;
+use zkprov_corelib::timestamp::{self, TokenVerifier};
+#[cfg(feature = "prover")]
+use zkprov_corelib::timestamp::{TimestampClient, TimestampRequest};
+
+#[cfg(feature = "prover")]
+pub struct HttpTsa {
+    pub url: String,
+}
+
+#[cfg(feature = "prover")]
+impl TimestampClient for HttpTsa {
+    fn submit(&self, request: &TimestampRequest) -> Result<Vec<u8>> {
+        use anyhow::anyhow;
+        use std::process::Stdio;
+
+        let mut child = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--header",
+                "Content-Type: application/timestamp-query",
+                "--data-binary",
+                "@-",
+            ])
+            .arg(&self.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("failed to run curl")?;
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("curl stdin unavailable"))?
+            .write_all(&request.to_der())?;
+        let out = child.wait_with_output()?;
+        if !out.status.success() {
+            bail!(
+                "{} returned {}: {}",
+                self.url,
+                out.status,
+                String::from_utf8_lossy(&out.stderr).trim()
+            );
+        }
+        Ok(out.stdout)
+    }
+}
+
+/// Authenticates tokens with `openssl ts -verify`: the CMS signature, the
+/// signer's `timeStamping` usage and its chain to `ca_file`, a PEM file of
+/// trusted TSA certificates or CAs.
+#[derive(Debug)]
+pub struct OpensslTsa {
+    pub ca_file: PathBuf,
+}
+
+impl TokenVerifier for OpensslTsa {
+    fn verify_token(&self, response: &[u8], imprint: &[u8; 32]) -> Result<()> {
+        let mut token = tempfile::NamedTempFile::new()?;
+        token.write_all(response)?;
+        let out = Command::new("openssl")
+            .args(["ts", "-verify", "-digest", &bytes_to_hex(imprint), "-in"])
+            .arg(token.path())
+            .arg("-CAfile")
+            .arg(&self.ca_file)
+            .output()
+            .context("failed to run openssl")?;
+        if !out.status.success() {
+            // OpenSSL error lines read `<id>:error:<code>:<lib>:<func>:<reason>:…`;
+            // chain failures end in `Verify error:<reason>`.
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            let last = stderr.lines().last().unwrap_or("openssl ts -verify failed");
+            let reason = match last.split_once("Verify error:") {
+                Some((_, reason)) => reason,
+                None => last.split(':').nth(5).unwrap_or(last),
+            };
+            bail!("not signed under {}: {}", self.ca_file.display(), reason);
+        }
+        Ok(())
+    }
+}
+
+/// `<proof>.tsr`, next to the proof.
+pub fn token_path_for(proof: &str) -> String {
+    format!("{}.{}", proof, timestamp::TOKEN_EXTENSION)
+}
+
+pub fn read_token(proof: &str) -> Result<Vec<u8>> {
+    let path = token_path_for(proof);
+    if !Path::new(&path).exists() {
+        bail!("no timestamp token at {}", path);
+    }
+    std::fs::read(&path).with_context(|| format!("reading {}", path))
+}
@@ -0,0 +1,631 @@
+//! `zkd verify`, `zkd triage` and `zkd validate`.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+use zkprov_corelib as core;
+use zkprov_corelib::air::AirProgram;
+use zkprov_corelib::air_bindings::Bindings;
+use zkprov_corelib::audit::AuditOp;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::VerifyError;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::manifest::DeterminismManifest;
+use zkprov_corelib::policy::{PolicyEvidence, VerifyPolicy};
+use zkprov_corelib::proof::triage::{self, RegionStatus};
+use zkprov_corelib::proof::{split_proof, ProofHeader, ProofView};
+use zkprov_corelib::registry;
+use zkprov_corelib::timestamp::{self, TokenVerifier};
+use zkprov_corelib::validate::{validate_air_against_backend, validate_config_with_warnings};
+use zkprov_corelib::validation::Validator;
+use zkprov_ffi_types::{Empty, Envelope, ErrorCode, VerifyMeta};
+
+use super::{
+    audit, bytes_to_hex, exit_for_corrupt_proof, mk_config, open_proof, output, print_warnings,
+    read_to_bytes, read_to_string, run_shape, tsa, version_meta, CommonCfg, TriageArgs,
+    ValidateArgs, VerifyArgs, EXIT_CORRUPT_PROOF,
+};
+
+/// `zkd verify`.
+pub fn verify(args: VerifyArgs) -> Result<()> {
+    let VerifyArgs {
+        program_path,
+        inputs_path,
+        mut proof_in,
+        quorum,
+        stats,
+        json,
+        check_timestamp,
+        tsa_ca,
+        stats_abi,
+        policy,
+        audit_log,
+        manifest,
+        cfg,
+    } = args;
+    if !json {
+        output::on_failure("fail");
+    }
+    if let Some(threshold) = quorum {
+        let inputs_path = inputs_path.ok_or_else(|| anyhow!("verify --quorum needs -i"))?;
+        return verify_quorum_cmd(
+            &cfg,
+            &program_path,
+            &inputs_path,
+            &proof_in,
+            threshold,
+            json,
+        );
+    }
+    if proof_in.len() > 1 {
+        return Err(anyhow!("several --proof files need --quorum K"));
+    }
+    let proof_in = proof_in.remove(0);
+    let started = Instant::now();
+    let mut timings = VerifyTimings::default();
+    registry::ensure_builtins_registered();
+    let t = Instant::now();
+    let mut config = mk_config(&cfg, &program_path)?;
+    let manifest = match &manifest {
+        Some(path) => {
+            let manifest = DeterminismManifest::from_json(&read_to_string(path)?)
+                .with_context(|| format!("failed to parse manifest '{}'", path))?;
+            if let Err(e) = apply_manifest(&mut config, &manifest) {
+                eprintln!("❌ ManifestRejected {:#}", e);
+                output::exit(EXIT_CORRUPT_PROOF);
+            }
+            Some(manifest)
+        }
+        None => None,
+    };
+    let warnings = validate_config_with_warnings(&config).map_err(|e| anyhow!(e.to_string()))?;
+    timings.config_validation = t.elapsed();
+    print_warnings(&warnings);
+    let policy = match &policy {
+        Some(path) => Some(VerifyPolicy::load(Path::new(path))?),
+        None => None,
+    };
+    let trusted_tsa = tsa_ca.map(|ca| tsa::OpensslTsa { ca_file: ca.into() });
+    let t = Instant::now();
+    let (proof, embedded) = match open_proof(read_to_bytes(&proof_in)?, &proof_in) {
+        Ok(opened) => opened,
+        Err(e) if json => {
+            output::data_line(&verify_failure_json(&e))?;
+            output::exit(EXIT_CORRUPT_PROOF);
+        }
+        Err(e) => exit_for_corrupt_proof(&e),
+    };
+    // Embedded inputs are only a claim: verification recomputes
+    // pubio_hash from them like from an -i file.
+    let (inputs, embedded_inputs) = match (&inputs_path, embedded) {
+        (Some(path), _) => (read_to_string(path)?, false),
+        (None, Some(inputs)) => (inputs, true),
+        (None, None) => {
+            return Err(anyhow!(
+                "proof '{}' embeds no inputs (prove --embed-inputs); pass -i",
+                proof_in
+            ))
+        }
+    };
+    timings.read = t.elapsed();
+    let auditor = audit::Auditor::open(
+        audit_log.as_deref(),
+        AuditOp::Verify,
+        &config,
+        &program_path,
+        &inputs,
+    )?;
+
+    // Header decode, binding, root and policy failures all map to exit code 4
+    let hdr = match verify_with_registry(
+        &config,
+        &program_path,
+        &inputs,
+        &proof,
+        policy.as_ref().map(|p| (p, proof_in.as_str())),
+        trusted_tsa.as_ref().map(|t| t as &dyn TokenVerifier),
+        embedded_inputs,
+        &mut timings,
+    ) {
+        Ok(hdr) => hdr,
+        Err(e) => {
+            audit::failed(&auditor, &e);
+            if json {
+                output::data_line(&verify_failure_json(&e))?;
+                output::exit(EXIT_CORRUPT_PROOF);
+            }
+            exit_for_corrupt_proof(&e)
+        }
+    };
+    // Decoding checked `body_len` against the bytes after the header.
+    let body = &proof[proof.len() - hdr.body_len as usize..];
+    let t = Instant::now();
+    let digest = digest_D(&hdr, body);
+    timings.digest = t.elapsed();
+    if let Some(manifest) = &manifest {
+        let expected = format!("0x{}", bytes_to_hex(&digest));
+        if !manifest.digest.eq_ignore_ascii_case(&expected) {
+            let e = anyhow!(
+                "manifest digest {} is not the proof's {}",
+                manifest.digest,
+                expected
+            );
+            audit::failed(&auditor, &e);
+            eprintln!("❌ ManifestRejected {:#}", e);
+            output::exit(EXIT_CORRUPT_PROOF);
+        }
+    }
+    if stats_abi {
+        let t = Instant::now();
+        let encoded = [
+            core::evm::abi::encode_meta(&hdr),
+            core::evm::abi::encode_body(body),
+            core::evm::abi::encode_public_io(&inputs),
+        ];
+        std::hint::black_box(encoded);
+        timings.abi_encode = Some(t.elapsed());
+    }
+    let stamp_info = if check_timestamp {
+        let trusted_tsa = trusted_tsa
+            .as_ref()
+            .expect("--check-timestamp requires --tsa-ca");
+        let checked = tsa::read_token(&proof_in)
+            .and_then(|token| timestamp::check_response(&token, &digest, trusted_tsa));
+        if let (Err(e), Some(auditor)) = (&checked, &auditor) {
+            auditor.failed(Some(&digest), e);
+        }
+        match checked {
+            Ok(info) => Some(info),
+            Err(e) if json => {
+                let diagnosis = serde_json::json!({
+                    "category": "timestamp",
+                    "message": format!("{e:#}"),
+                });
+                output::data_line(&verify_failure_envelope(ErrorCode::VerifyFail, diagnosis)?)?;
+                output::exit(EXIT_CORRUPT_PROOF);
+            }
+            Err(e) => {
+                eprintln!("❌ TimestampInvalid {:#}", e);
+                output::exit(EXIT_CORRUPT_PROOF);
+            }
+        }
+    } else {
+        None
+    };
+    let shape = if stats {
+        let air = AirProgram::load_from_file(&program_path)?;
+        Some(run_shape(&config, &air, &inputs)?.0)
+    } else {
+        None
+    };
+    timings.total = started.elapsed();
+    if let Some(auditor) = &auditor {
+        auditor.ok(&digest)?;
+    }
+    if json {
+        let mut out = serde_json::to_value(Envelope::ok(VerifyMeta {
+            verified: true,
+            digest: format!("0x{}", bytes_to_hex(&digest)),
+            version: version_meta(),
+        }))?;
+        out["backend"] = config.backend_id.clone().into();
+        out["profile"] = config.profile_id.clone().into();
+        out["pubio_hash"] = format!("0x{:016x}", hdr.pubio_hash).into();
+        if embedded_inputs {
+            out["embedded_inputs"] = true.into();
+        }
+        if let Some(info) = &stamp_info {
+            out["timestamp"] = serde_json::to_value(info)?;
+        }
+        if let Some(shape) = shape {
+            out["stats"] = serde_json::json!({
+                "rows": shape.rows,
+                "cols": shape.cols,
+                "const": shape.const_cols,
+                "periodic": shape.periodic_cols,
+                "timing_us": timings.to_json(),
+            });
+        }
+        output::data_line(&out)?;
+    } else {
+        info!(
+            "✅ ProofVerified backend={} profile={} pubio_hash=0x{:016x}",
+            config.backend_id, config.profile_id, hdr.pubio_hash
+        );
+        if embedded_inputs {
+            info!("Inputs: embedded in {}", proof_in);
+        }
+        if let Some(info) = &stamp_info {
+            info!("Timestamp: time={} serial={}", info.time, info.serial);
+        }
+        if let Some(shape) = shape {
+            info!(
+                "stats rows={} cols={} const={} periodic={}",
+                shape.rows, shape.cols, shape.const_cols, shape.periodic_cols
+            );
+            info!("{}", timings.render());
+        }
+        output::result("ok");
+    }
+    Ok(())
+}
+
+/// `zkd triage`.
+pub fn triage(args: TriageArgs) -> Result<()> {
+    let TriageArgs {
+        program_path,
+        inputs_path,
+        proof_in,
+        json,
+        cfg,
+    } = args;
+    registry::ensure_builtins_registered();
+    let config = mk_config(&cfg, &program_path)?;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let inputs = PublicInputs::from_json(&read_to_string(&inputs_path)?)?;
+    let proof = read_to_bytes(&proof_in)?;
+    let report = triage::triage(&config, &ir, &inputs, &proof)?;
+    if json {
+        let mut out = serde_json::to_value(&report)?;
+        out["consistent"] = report.is_consistent().into();
+        output::data(&out)?;
+    } else {
+        for r in &report.regions {
+            let mark = match r.status {
+                RegionStatus::Ok => "✓",
+                RegionStatus::Mismatch => "✗",
+                RegionStatus::Missing => "-",
+            };
+            let mut line = format!("{} {:<16} [{}..{})", mark, r.region, r.start, r.end);
+            if let (Some(expected), Some(actual)) = (&r.expected, &r.actual) {
+                line.push_str(&format!(" expected={} actual={}", expected, actual));
+            }
+            if let Some(detail) = &r.detail {
+                line.push_str(&format!(" ({})", detail));
+            }
+            out!("{}", line);
+        }
+        let bad: Vec<&str> = report.findings().map(|r| r.region).collect();
+        if bad.is_empty() {
+            info!("✅ proof consistent ({} bytes)", report.proof_len);
+        } else {
+            info!("❌ inconsistent regions: {}", bad.join(", "));
+        }
+    }
+    if !report.is_consistent() {
+        output::exit(EXIT_CORRUPT_PROOF);
+    }
+    Ok(())
+}
+
+/// `zkd validate`.
+pub fn validate(args: ValidateArgs) -> Result<()> {
+    let ValidateArgs {
+        program_path,
+        inputs_path,
+        proof_in,
+        output_dir,
+        known_blinds,
+        cfg,
+    } = args;
+    registry::ensure_builtins_registered();
+    let config = mk_config(&cfg, &program_path)?;
+    let warnings = validate_config_with_warnings(&config).map_err(|e| anyhow!(e.to_string()))?;
+    print_warnings(&warnings);
+    let air = AirProgram::load_from_file(&program_path)?;
+    validate_air_against_backend(&air, &config.backend_id).map_err(|e| anyhow!(e.to_string()))?;
+    let mut bindings = Bindings::from_air(&air);
+    if known_blinds.is_some() {
+        bindings.commitments.no_r_reuse = Some(true);
+    }
+
+    let (proof, _) = open_proof(read_to_bytes(&proof_in)?, &proof_in)?;
+    let (header, body) =
+        split_proof(&proof).map_err(|e| anyhow!("failed to decode proof header: {e}"))?;
+    if body.len() as u64 != header.body_len {
+        return Err(anyhow!(
+            "proof '{}' body length ({}) does not match header body_len {}",
+            proof_in,
+            body.len(),
+            header.body_len
+        ));
+    }
+
+    let inputs_json = read_to_string(&inputs_path)?;
+    let ir = core::air::parse_air_file(Path::new(&program_path))?;
+    let mut validator = Validator::new(&bindings);
+    if let Some(path) = &known_blinds {
+        let count = validator
+            .load_known_blinds(path)
+            .map_err(|e| anyhow!(e.to_string()))?;
+        info!("Known blinds: {} from {}", count, path);
+    }
+    let view = ProofView {
+        header: header.clone(),
+        body,
+    };
+    validator.check_proof(&config, &ir, &inputs_json, &view);
+    validator.check_header_bindings(&header, &config, &ir, &inputs_json);
+
+    if bindings.commitments.pedersen {
+        let mut msg_bytes = inputs_json.into_bytes();
+        msg_bytes.extend_from_slice(body);
+        let mut blind_bytes = Vec::new();
+        blind_bytes.extend_from_slice(&header.pubio_hash.to_le_bytes());
+        blind_bytes.extend_from_slice(&header.backend_id_hash.to_le_bytes());
+        blind_bytes.extend_from_slice(&header.profile_id_hash.to_le_bytes());
+        validator.check_commit_point(&msg_bytes, &blind_bytes);
+    }
+    validator.check_range_u64(header.body_len, 64);
+
+    let mut report = validator.finalize();
+    for warning in warnings {
+        report.push_warning(warning);
+    }
+    report.meta.backend_id = config.backend_id.clone();
+    report.meta.profile_id = config.profile_id.clone();
+    report.meta.hash_id = bindings
+        .hash_id_for_commitments
+        .clone()
+        .unwrap_or_else(|| config.hash.clone());
+    report.meta.curve = bindings.commitments.curve.clone();
+
+    let report_path = report
+        .write_pretty(&output_dir)
+        .with_context(|| format!("failed to write validation report under '{}'", output_dir))?;
+    let verified = report.checks.iter().any(|c| c.name == "verify" && c.passed);
+    info!(
+        "✅ Validation ok={} verified={} commit_passed={} report={}",
+        report.ok,
+        verified,
+        report.commit_passed,
+        report_path.display()
+    );
+    if !report.ok {
+        for err in &report.errors {
+            eprintln!("❌ {:?}: {}", err.code, err.msg);
+        }
+        output::exit(1);
+    }
+    Ok(())
+}
+
+/// `zkd verify --manifest`: check the manifest's seal, take its profile
+/// overrides (explicit `--profile-override`s must agree) and check a
+/// recorded adaptive choice against the verifier's profile.
+fn apply_manifest(config: &mut Config, manifest: &DeterminismManifest) -> Result<()> {
+    let vector = &manifest.determinism_vector;
+    vector.verify()?;
+    for (key, value) in &vector.profile_overrides {
+        match config.profile_overrides.get(key) {
+            Some(given) if given != value => {
+                return Err(anyhow!(
+                    "--profile-override {}={} contradicts the manifest's {}={}",
+                    key,
+                    given,
+                    key,
+                    value
+                ))
+            }
+            _ => {
+                config.profile_overrides.insert(key.clone(), *value);
+            }
+        }
+    }
+    if let Some(choice) = &vector.adaptive {
+        choice.check(config)?;
+    }
+    Ok(())
+}
+
+/// Verify through the registry. The proof is decoded before the program is
+/// loaded, so a corrupt file is reported as such even if the AIR is broken.
+#[allow(clippy::too_many_arguments)]
+fn verify_with_registry(
+    config: &Config,
+    program_path: &str,
+    inputs_json: &str,
+    proof_bytes: &[u8],
+    policy: Option<(&VerifyPolicy, &str)>,
+    tsa: Option<&dyn TokenVerifier>,
+    embedded_inputs: bool,
+    timings: &mut VerifyTimings,
+) -> Result<ProofHeader> {
+    let t = Instant::now();
+    let proof = ProofView::from_bytes(proof_bytes)?;
+    timings.header_decode = t.elapsed();
+    let t = Instant::now();
+    let ir = core::air::parse_air_file(Path::new(program_path))?;
+    let inputs = PublicInputs::from_json(inputs_json)?;
+    timings.program_parse = t.elapsed();
+    let t = Instant::now();
+    match policy {
+        Some((policy, proof_path)) => {
+            let program_source = read_to_bytes(program_path)?;
+            let token = tsa::read_token(proof_path).ok();
+            let evidence = PolicyEvidence {
+                program_source: &program_source,
+                timestamp: token.as_deref(),
+                tsa,
+                now: SystemTime::now(),
+                embedded_inputs,
+            };
+            core::verify_with_policy(config, &ir, &inputs, &proof, policy, &evidence)?;
+        }
+        None => core::verify(config, &ir, &inputs, &proof)?,
+    }
+    timings.backend_verify = t.elapsed();
+    Ok(proof.header)
+}
+
+/// `zkd verify --quorum K`: verify every proof and require K to agree.
+/// Prints the per-proof report and exits 4 when the quorum is not reached.
+fn verify_quorum_cmd(
+    cfg: &CommonCfg,
+    program_path: &str,
+    inputs_path: &str,
+    proof_paths: &[String],
+    threshold: usize,
+    json: bool,
+) -> Result<()> {
+    registry::ensure_builtins_registered();
+    let config = mk_config(cfg, program_path)?;
+    let ir = core::air::parse_air_file(Path::new(program_path))?;
+    let inputs = PublicInputs::from_json(read_to_string(inputs_path)?)?;
+    let proofs = proof_paths
+        .iter()
+        .map(|path| Ok((path.clone(), open_proof(read_to_bytes(path)?, path)?.0)))
+        .collect::<Result<Vec<_>>>()?;
+    let report = core::quorum::verify_quorum(&config, &ir, &inputs, &proofs, threshold)?;
+    if json {
+        output::data_line(&report)?;
+    } else {
+        for entry in &report.entries {
+            let detail = match (&entry.error, &entry.digest) {
+                (Some(error), _) => error.clone(),
+                (None, Some(digest)) => format!("digest={digest}"),
+                (None, None) => String::new(),
+            };
+            info!(
+                "{:<9} {}  {}/{}  {}",
+                entry.status.as_str(),
+                entry.label,
+                entry.backend,
+                entry.profile,
+                detail
+            );
+        }
+        let verdict = if report.reached {
+            "✅ QuorumReached"
+        } else {
+            "❌ QuorumNotReached"
+        };
+        info!(
+            "{} accepted={}/{} threshold={}",
+            verdict, report.accepted, report.total, report.threshold
+        );
+    }
+    if !report.reached {
+        output::exit(EXIT_CORRUPT_PROOF);
+    }
+    if !json {
+        output::result("ok");
+    }
+    Ok(())
+}
+
+/// Wall-clock phases of `zkd verify`, reported under `--stats`.
+#[derive(Debug, Default)]
+struct VerifyTimings {
+    /// Config construction and capability validation.
+    config_validation: Duration,
+    /// Reading the inputs and proof files.
+    read: Duration,
+    header_decode: Duration,
+    /// Parsing the AIR and public inputs.
+    program_parse: Duration,
+    backend_verify: Duration,
+    /// Computing the EVM digest `D`.
+    digest: Duration,
+    /// ABI-encoding meta, body and public IO (`--stats-abi` only).
+    abi_encode: Option<Duration>,
+    total: Duration,
+}
+
+impl VerifyTimings {
+    fn phases(&self) -> Vec<(&'static str, Duration)> {
+        let mut out = vec![
+            ("config_validation", self.config_validation),
+            ("read", self.read),
+            ("header_decode", self.header_decode),
+            ("program_parse", self.program_parse),
+            ("backend_verify", self.backend_verify),
+            ("digest", self.digest),
+        ];
+        if let Some(abi) = self.abi_encode {
+            out.push(("abi_encode", abi));
+        }
+        out.push(("total", self.total));
+        out
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        self.phases()
+            .into_iter()
+            .map(|(name, d)| (name.to_string(), (d.as_micros() as u64).into()))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    fn render(&self) -> String {
+        let phases: Vec<String> = self
+            .phases()
+            .into_iter()
+            .map(|(name, d)| format!("{}={}", name, d.as_micros()))
+            .collect();
+        format!("stats timing_us {}", phases.join(" "))
+    }
+}
+
+/// `zkd verify --json` failure: a failed FFI envelope plus the exit code and
+/// a diagnosis.
+fn verify_failure_envelope(
+    code: ErrorCode,
+    diagnosis: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let msg = diagnosis["message"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let mut out = serde_json::to_value(Envelope::err(code, msg, Empty {}))?;
+    out["verified"] = false.into();
+    out["exit_code"] = EXIT_CORRUPT_PROOF.into();
+    out["diagnosis"] = diagnosis;
+    Ok(out)
+}
+
+/// Machine-readable `zkd verify --json` failure report, keyed by the
+/// `VerifyError` category; other errors (unreadable AIR/inputs) are "input".
+fn verify_failure_json(err: &anyhow::Error) -> serde_json::Value {
+    let mut diagnosis = serde_json::json!({
+        "category": "input",
+        "message": format!("{err:#}"),
+    });
+    if let Some(e) = err.downcast_ref::<VerifyError>() {
+        diagnosis["category"] = e.category().into();
+        match e {
+            VerifyError::HeaderBinding {
+                field,
+                expected,
+                actual,
+            } => {
+                diagnosis["field"] = field.as_str().into();
+                diagnosis["expected"] = format!("0x{:016x}", expected).into();
+                diagnosis["actual"] = format!("0x{:016x}", actual).into();
+            }
+            VerifyError::BodyLength { expected, actual } => {
+                diagnosis["expected"] = (*expected).into();
+                diagnosis["actual"] = (*actual).into();
+            }
+            VerifyError::RootMismatch {
+                what,
+                expected,
+                actual,
+            } => {
+                diagnosis["field"] = what.as_str().into();
+                diagnosis["expected"] = expected.as_str().into();
+                diagnosis["actual"] = actual.as_str().into();
+            }
+            VerifyError::Policy(violations) => {
+                diagnosis["violations"] =
+                    serde_json::to_value(violations).expect("violations serialize");
+            }
+            _ => {}
+        }
+    }
+    let code = err
+        .downcast_ref::<VerifyError>()
+        .map_or(ErrorCode::InvalidArg, ErrorCode::from);
+    verify_failure_envelope(code, diagnosis).expect("failure envelope serializes")
+}
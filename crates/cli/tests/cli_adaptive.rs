@@ -0,0 +1,177 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::tempdir;
+
+use common::zkd_toy;
+
+fn prove_adaptive(dir: &Path) -> (PathBuf, serde_json::Value) {
+    fs::write(dir.join("inputs.json"), r#"{"a":1}"#).unwrap();
+    let out = zkd_toy(
+        "prove",
+        dir,
+        &["--adaptive-queries", "--grind-budget", "1ms"],
+    );
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("adaptive_queries fri_queries="), "{stdout}");
+
+    let path = dir.join("toy.proof.manifest.json");
+    let manifest = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    (path, manifest)
+}
+
+#[test]
+fn adaptive_choice_recorded_and_checked_by_verify() {
+    let dir = tempdir().unwrap();
+    let (path, manifest) = prove_adaptive(dir.path());
+    let vector = &manifest["determinism_vector"];
+    let adaptive = &vector["adaptive"];
+    assert_eq!(adaptive["lambda_bits"], 100);
+    assert_eq!(adaptive["fri_blowup"], 16);
+    // The fixed reference cost: 1ms grinds 8 bits next to 23 queries.
+    assert_eq!(adaptive["grind_ns"], 1000);
+    assert_eq!(
+        (
+            adaptive["fri_queries"].as_u64(),
+            adaptive["grind_bits"].as_u64()
+        ),
+        (Some(23), Some(8))
+    );
+    assert!(adaptive["security_bits"].as_u64().unwrap() >= 100);
+    assert_eq!(
+        vector["profile_overrides"]["fri_queries"],
+        adaptive["fri_queries"]
+    );
+    assert_eq!(
+        vector["profile_overrides"]["grind_bits"],
+        adaptive["grind_bits"]
+    );
+
+    // The manifest supplies the overrides; the verifier's config with them
+    // must match config_hash and carry the recorded choice.
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &["--manifest", path.to_str().unwrap()],
+    );
+    assert!(out.status.success(), "{:?}", out);
+    assert!(!zkd_toy("verify", dir.path(), &[]).status.success());
+
+    // Contradicting an override from the manifest is rejected.
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &[
+            "--manifest",
+            path.to_str().unwrap(),
+            "--profile-override",
+            "fri_queries=99",
+        ],
+    );
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+}
+
+#[test]
+fn verify_rejects_tampered_or_weak_adaptive_manifests() {
+    let dir = tempdir().unwrap();
+    let (path, manifest) = prove_adaptive(dir.path());
+
+    // Editing the choice breaks the seal.
+    let mut tampered = manifest.clone();
+    tampered["determinism_vector"]["adaptive"]["grind_bits"] = 0.into();
+    fs::write(&path, tampered.to_string()).unwrap();
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &["--manifest", path.to_str().unwrap()],
+    );
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("ManifestRejected"));
+
+    // A resealed choice below lambda_bits is rejected: its overrides do
+    // not resolve to a profile that keeps lambda_bits.
+    let mut weak: zkprov_corelib::manifest::DeterminismManifest =
+        serde_json::from_value(manifest.clone()).unwrap();
+    let mut vector = weak.determinism_vector.clone();
+    let mut choice = vector.adaptive.unwrap();
+    choice.fri_queries = 16;
+    choice.grind_bits = 0;
+    vector.profile_overrides.insert("fri_queries".into(), 16);
+    vector.profile_overrides.insert("grind_bits".into(), 0);
+    weak.determinism_vector = vector.with_adaptive(Some(choice));
+    fs::write(&path, weak.to_json_pretty().unwrap()).unwrap();
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &["--manifest", path.to_str().unwrap()],
+    );
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("below lambda_bits 100"));
+
+    // A resealed choice that the manifest's overrides do not run.
+    let mut stale: zkprov_corelib::manifest::DeterminismManifest =
+        serde_json::from_value(manifest.clone()).unwrap();
+    let vector = stale.determinism_vector.clone();
+    let mut choice = vector.adaptive.unwrap();
+    choice.fri_queries = 30;
+    choice.grind_bits = 0;
+    choice.security_bits = 120;
+    stale.determinism_vector = vector.with_adaptive(Some(choice));
+    fs::write(&path, stale.to_json_pretty().unwrap()).unwrap();
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &["--manifest", path.to_str().unwrap()],
+    );
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("not the recorded"));
+
+    // A manifest for another proof is rejected by its digest.
+    let mut other = manifest;
+    other["digest"] = format!("0x{}", "00".repeat(32)).into();
+    fs::write(&path, other.to_string()).unwrap();
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &["--manifest", path.to_str().unwrap()],
+    );
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+}
+
+#[test]
+fn measure_grind_is_opt_in() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+    let out = zkd_toy("prove", dir.path(), &["--measure-grind"]);
+    assert_eq!(out.status.code(), Some(2), "{:?}", out);
+
+    let out = zkd_toy(
+        "prove",
+        dir.path(),
+        &[
+            "--adaptive-queries",
+            "--grind-budget",
+            "1ms",
+            "--measure-grind",
+        ],
+    );
+    assert!(out.status.success(), "{:?}", out);
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(dir.path().join("toy.proof.manifest.json")).unwrap(),
+    )
+    .unwrap();
+    let adaptive = &manifest["determinism_vector"]["adaptive"];
+    assert!(adaptive["security_bits"].as_u64().unwrap() >= 100);
+    let out = zkd_toy(
+        "verify",
+        dir.path(),
+        &[
+            "--manifest",
+            dir.path().join("toy.proof.manifest.json").to_str().unwrap(),
+        ],
+    );
+    assert!(out.status.success(), "{:?}", out);
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use tempfile::tempdir;
+
+const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+
+/// `toy.air` with `meta.backend` and `meta.profile` hints.
+fn hinted_air(dir: &Path) -> PathBuf {
+    let toy = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/air/toy.air");
+    let src = fs::read_to_string(toy).expect("read toy.air");
+    let hinted = src.replacen(
+        "[meta]\n",
+        "[meta]\nbackend = \"native@0.0\"\nprofile = \"dev-fast\"\n",
+        1,
+    );
+    assert_ne!(src, hinted, "toy.air has a [meta] table");
+    let path = dir.join("hinted.air");
+    fs::write(&path, hinted).expect("write hinted.air");
+    path
+}
+
+fn zkd(cmd: &str, dir: &Path, extra: &[&str]) -> Output {
+    let inputs = dir.join("inputs.json");
+    fs::write(&inputs, r#"{"a":1,"b":[1,2,3]}"#).expect("write inputs");
+    let air = hinted_air(dir);
+    Command::new(BIN)
+        .arg(cmd)
+        .args(["-p", air.to_str().unwrap()])
+        .args(["-i", inputs.to_str().unwrap()])
+        .arg(if cmd == "prove" { "-o" } else { "-P" })
+        .arg(dir.join("hinted.proof"))
+        .args([
+            "--field",
+            "Prime254",
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+        ])
+        .args(extra)
+        .output()
+        .expect("run zkd")
+}
+
+#[test]
+fn respect_takes_backend_and_profile_from_air() {
+    let dir = tempdir().unwrap();
+    let out = zkd("prove", dir.path(), &["--respect-air-hints"]);
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("backend=native@0.0 profile=dev-fast"),
+        "{stdout}"
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("AIR hint: backend=native@0.0"), "{stderr}");
+
+    // An explicit profile wins over the hint in respect mode.
+    let out = zkd(
+        "verify",
+        dir.path(),
+        &["--respect-air-hints", "--profile", "dev-fast"],
+    );
+    assert!(out.status.success(), "{out:?}");
+}
+
+#[test]
+fn strict_rejects_flags_that_contradict_hints() {
+    let dir = tempdir().unwrap();
+    let out = zkd(
+        "prove",
+        dir.path(),
+        &["--respect-air-hints=strict", "--profile", "balanced"],
+    );
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("contradicts"), "{stderr}");
+    assert!(stderr.contains("meta.profile"), "{stderr}");
+}
+
+#[test]
+fn hints_are_ignored_without_the_flag() {
+    let dir = tempdir().unwrap();
+    let out = zkd("prove", dir.path(), &[]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("--backend"), "{stderr}");
+}
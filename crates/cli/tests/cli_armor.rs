@@ -0,0 +1,275 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tempfile::tempdir;
+
+const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+
+fn prove(dir: &std::path::Path) -> std::path::PathBuf {
+    let air = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/air/toy.air");
+    let inputs = dir.join("inputs.json");
+    let proof = dir.join("p.proof");
+    fs::write(&inputs, r#"{"demo":true,"n":7}"#).unwrap();
+    let status = Command::new(BIN)
+        .args([
+            "prove",
+            "-p",
+            air.to_str().unwrap(),
+            "-i",
+            inputs.to_str().unwrap(),
+            "-o",
+            proof.to_str().unwrap(),
+            "--backend",
+            "native@0.0",
+            "--field",
+            "Prime254",
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+            "--profile",
+            "balanced",
+        ])
+        .status()
+        .expect("run prove");
+    assert!(status.success());
+    proof
+}
+
+#[test]
+fn proof_armor_roundtrips_and_checks_body_len() {
+    let dir = tempdir().unwrap();
+    let proof = prove(dir.path());
+    let raw = fs::read(&proof).unwrap();
+
+    // hex to stdout is the plain lowercase encoding
+    let out = Command::new(BIN)
+        .args([
+            "proof-encode",
+            "--armor",
+            "hex",
+            "-P",
+            proof.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{:?}", out);
+    let hex: String = raw.iter().map(|b| format!("{:02x}", b)).collect();
+    assert_eq!(String::from_utf8(out.stdout).unwrap().trim(), hex);
+
+    // base64 to a file, line-wrapped, decoded back through stdin
+    let b64 = dir.path().join("p.b64");
+    let out = Command::new(BIN)
+        .args([
+            "proof-encode",
+            "--armor",
+            "base64",
+            "-P",
+            proof.to_str().unwrap(),
+            "-o",
+            b64.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{:?}", out);
+    let text = fs::read_to_string(&b64).unwrap();
+    assert_eq!(text.trim().len(), raw.len().div_ceil(3) * 4);
+    let wrapped: Vec<String> = text
+        .trim()
+        .as_bytes()
+        .chunks(64)
+        .map(|l| String::from_utf8(l.to_vec()).unwrap())
+        .collect();
+    let back = dir.path().join("back.proof");
+    let mut child = Command::new(BIN)
+        .args([
+            "proof-decode",
+            "--armor",
+            "base64",
+            "-i",
+            "-",
+            "-o",
+            back.to_str().unwrap(),
+        ])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(wrapped.join("\n").as_bytes())
+        .unwrap();
+    assert!(child.wait().unwrap().success());
+    assert_eq!(fs::read(&back).unwrap(), raw);
+
+    // Dropping the last 3 bytes keeps the armor well-formed but breaks body_len
+    let short = dir.path().join("short.b64");
+    let t = text.trim();
+    fs::write(&short, &t[..t.len() - 4]).unwrap();
+    let bad = dir.path().join("bad.proof");
+    let out = Command::new(BIN)
+        .args([
+            "proof-decode",
+            "--armor",
+            "base64",
+            "-i",
+            short.to_str().unwrap(),
+            "-o",
+            bad.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("does not match header body_len"),
+        "{:?}",
+        out
+    );
+    assert!(!bad.exists());
+
+    let junk = dir.path().join("junk.hex");
+    fs::write(&junk, "zz").unwrap();
+    let out = Command::new(BIN)
+        .args([
+            "proof-decode",
+            "--armor",
+            "hex",
+            "-i",
+            junk.to_str().unwrap(),
+            "-o",
+            bad.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("invalid hex character"));
+}
+
+#[test]
+fn proof_qr_chunks_reassemble_in_any_order() {
+    let dir = tempdir().unwrap();
+    let proof = prove(dir.path());
+    let raw = fs::read(&proof).unwrap();
+    let qr_dir = dir.path().join("qr");
+    let out = Command::new(BIN)
+        .args([
+            "proof-qr",
+            "-P",
+            proof.to_str().unwrap(),
+            "-o",
+            qr_dir.to_str().unwrap(),
+            "--chunk-size",
+            "20",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{:?}", out);
+    let chunks = (raw.len().div_ceil(3) * 4).div_ceil(20);
+    assert!(String::from_utf8_lossy(&out.stdout).contains(&format!("chunks={}", chunks)));
+    let svg = fs::read_to_string(qr_dir.join("chunk-001.svg")).unwrap();
+    assert!(svg.contains("<svg") && svg.contains("<path d=\"M"));
+    let first = fs::read_to_string(qr_dir.join("chunk-001.txt")).unwrap();
+    assert!(
+        first.starts_with(&format!("ZKDQR1:1/{}:", chunks)),
+        "{first}"
+    );
+
+    // Scans arrive out of order and with a repeat.
+    let scans = dir.path().join("scans.txt");
+    let mut lines: Vec<String> = (1..=chunks)
+        .rev()
+        .map(|i| fs::read_to_string(qr_dir.join(format!("chunk-{:03}.txt", i))).unwrap())
+        .collect();
+    lines.push(first.clone());
+    fs::write(&scans, lines.concat()).unwrap();
+    let assemble = |inputs: &[&std::path::Path]| {
+        let back = dir.path().join("back.proof");
+        let _ = fs::remove_file(&back);
+        let mut args = vec![
+            "proof-qr-assemble".to_string(),
+            "-o".to_string(),
+            back.to_str().unwrap().to_string(),
+        ];
+        args.extend(inputs.iter().map(|p| p.to_str().unwrap().to_string()));
+        let out = Command::new(BIN).args(&args).output().unwrap();
+        (out, fs::read(&back).ok())
+    };
+    let (out, back) = assemble(&[&scans]);
+    assert!(out.status.success(), "{:?}", out);
+    assert_eq!(back.unwrap(), raw);
+    let (out, back) = assemble(&[&qr_dir]);
+    assert!(out.status.success(), "{:?}", out);
+    assert_eq!(back.unwrap(), raw);
+
+    fs::remove_file(qr_dir.join("chunk-002.txt")).unwrap();
+    let (out, back) = assemble(&[&qr_dir]);
+    assert!(!out.status.success());
+    assert!(back.is_none());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains(&format!("missing chunks 2 of {}", chunks)),
+        "{:?}",
+        out
+    );
+
+    // A chunk from another proof cannot be mixed in.
+    let foreign = dir.path().join("foreign.txt");
+    let second = fs::read_to_string(qr_dir.join("chunk-003.txt")).unwrap();
+    let mut fields: Vec<&str> = second.splitn(4, ':').collect();
+    fields[2] = "0000000000000000";
+    let tampered = format!("{}{}", first, fields.join(":"));
+    fs::write(&foreign, tampered).unwrap();
+    let (out, _) = assemble(&[&foreign]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("belongs to proof"));
+}
+
+#[test]
+fn archive_put_and_get_through_fs_storage() {
+    let dir = tempdir().unwrap();
+    let proof = prove(dir.path());
+    let raw = fs::read(&proof).unwrap();
+    let config = dir.path().join("zkd.toml");
+    fs::write(&config, "[storage]\nkind = \"fs\"\nroot = \"archive\"\n").unwrap();
+
+    let out = Command::new(BIN)
+        .args(["archive", "put", "-P", proof.to_str().unwrap()])
+        .args(["--config", config.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let digest = stdout
+        .lines()
+        .find_map(|l| l.strip_prefix("✅ Archived digest=0x"))
+        .expect("digest line")
+        .to_string();
+    let stored = dir.path().join("archive").join(&digest);
+    assert_eq!(fs::read(stored.join("proof")).unwrap(), raw);
+    assert!(stored.join("manifest.json").is_file());
+    assert!(!stored.join("proof.tsr").exists());
+
+    // ZKD_CONFIG is honoured when --config is omitted
+    let back = dir.path().join("fetched/p.proof");
+    fs::create_dir_all(back.parent().unwrap()).unwrap();
+    let get = |digest: &str| {
+        Command::new(BIN)
+            .args(["archive", "get", "--digest", digest])
+            .args(["-o", back.to_str().unwrap()])
+            .env("ZKD_CONFIG", &config)
+            .output()
+            .unwrap()
+    };
+    let out = get(&format!("0x{digest}"));
+    assert!(out.status.success(), "{:?}", out);
+    assert_eq!(fs::read(&back).unwrap(), raw);
+    assert!(dir.path().join("fetched/p.proof.manifest.json").is_file());
+
+    // A stored object that does not hash to its key is refused
+    let mut tampered = raw.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    fs::write(stored.join("proof"), tampered).unwrap();
+    assert_eq!(get(&digest).status.code(), Some(4));
+    assert!(!get(&"00".repeat(32)).status.success());
+}
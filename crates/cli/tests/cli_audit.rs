@@ -0,0 +1,104 @@
+mod common;
+
+use std::fs;
+use std::process::Command;
+use tempfile::tempdir;
+
+use common::{air_path, BIN, CFG};
+
+fn zkd(args: &[&str]) -> std::process::Output {
+    Command::new(BIN)
+        .args(args)
+        .env("ZKD_AUDIT_ACTOR", "ci-bot")
+        .env_remove("ZKD_AUDIT_LOG")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn prove_and_verify_append_a_verifiable_chain() {
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    fs::write(&inputs, r#"{"a":1}"#).unwrap();
+    let proof = tmp.path().join("toy.proof");
+    let log = tmp.path().join("audit.jsonl");
+    let air = air_path();
+    let (inputs, proof_s, log_s) = (
+        inputs.to_str().unwrap(),
+        proof.to_str().unwrap(),
+        log.to_str().unwrap(),
+    );
+
+    let mut args = vec![
+        "prove",
+        "-p",
+        &air,
+        "-i",
+        inputs,
+        "-o",
+        proof_s,
+        "--audit-log",
+        log_s,
+    ];
+    args.extend(CFG);
+    let out = zkd(&args);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let verify = |proof: &str| {
+        let mut args = vec![
+            "verify",
+            "-p",
+            &air,
+            "-i",
+            inputs,
+            "-P",
+            proof,
+            "--audit-log",
+            log_s,
+        ];
+        args.extend(CFG);
+        zkd(&args)
+    };
+    assert!(verify(proof_s).status.success());
+
+    let mut bytes = fs::read(&proof).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+    let bad = tmp.path().join("bad.proof");
+    fs::write(&bad, &bytes).unwrap();
+    assert_eq!(verify(bad.to_str().unwrap()).status.code(), Some(4));
+
+    let entries: Vec<serde_json::Value> = fs::read_to_string(&log)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0]["op"], "prove");
+    assert_eq!(entries[0]["actor"], "ci-bot");
+    assert_eq!(entries[0]["result"], "ok");
+    assert_eq!(entries[1]["op"], "verify");
+    assert_eq!(entries[1]["digest"], entries[0]["digest"]);
+    assert_eq!(entries[1]["program_hash"], entries[0]["program_hash"]);
+    assert_eq!(entries[2]["result"], "failed");
+    assert!(entries[2]["error"].is_string());
+
+    let out = zkd(&["audit", "verify", log_s]);
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("AuditChainIntact entries=3"));
+
+    let text = fs::read_to_string(&log).unwrap();
+    fs::write(
+        &log,
+        text.replacen("\"result\":\"failed\"", "\"result\":\"ok\"", 1),
+    )
+    .unwrap();
+    let out = zkd(&["audit", "verify", log_s, "--json"]);
+    assert_eq!(out.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["ok"], false);
+    assert!(report["error"].as_str().unwrap().starts_with("line 3"));
+}
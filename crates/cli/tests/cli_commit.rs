@@ -69,3 +69,82 @@ fn open_fails_with_wrong_blind() {
     assert_ne!(code, 0, "open exit code should be non-zero");
     assert!(out.contains("❌"), "open output should show failure");
 }
+
+#[test]
+fn commit_kinds_roundtrip_with_their_output_formats() {
+    let blind = "aa".repeat(32);
+    for (kind, prefix) in [("poseidon_commit", "0x"), ("keccak_commit", "0x")] {
+        let (code, c_hex, err) = run(&[
+            "commit",
+            "--kind",
+            kind,
+            "--msg-hex",
+            "2a",
+            "--blind-hex",
+            &blind,
+        ]);
+        assert_eq!(code, 0, "{kind}: {err}");
+        let c_hex = c_hex.trim();
+        assert!(
+            c_hex.starts_with(prefix) && c_hex.len() == 66,
+            "{kind}: {c_hex}"
+        );
+
+        let (code, out, _err) = run(&[
+            "open-commit",
+            "--kind",
+            kind,
+            "--msg-hex",
+            "2a",
+            "--blind-hex",
+            &blind,
+            "--commit-hex",
+            c_hex,
+        ]);
+        assert_eq!(code, 0, "{kind} open exit code");
+        assert!(out.contains("✅"), "{kind} open output");
+    }
+
+    // Solidity: keccak256(abi.encodePacked(hex"2a", blind)).
+    let (_code, c_hex, _err) = run(&[
+        "commit",
+        "--kind",
+        "keccak_commit",
+        "--msg-hex",
+        "2a",
+        "--blind-hex",
+        &blind,
+    ]);
+    assert_eq!(
+        c_hex.trim(),
+        "0xdd7fb9c25f71f2df5fd9a59e3a228c8b22f201aa1d813700af94daaf594c2024"
+    );
+
+    for (args, needle) in [
+        (
+            vec!["--kind", "keccak_commit", "--blind-hex", "01"],
+            "keccak_commit blinds must be 32 bytes, got 1",
+        ),
+        (
+            vec![
+                "--kind",
+                "poseidon_commit",
+                "--hash",
+                "blake3",
+                "--blind-hex",
+                "01",
+            ],
+            "poseidon_commit commitments always use poseidon2, not 'blake3'",
+        ),
+        (
+            vec!["--blind-hex", "01"],
+            "--hash is required for --kind pedersen",
+        ),
+    ] {
+        let mut argv = vec!["commit", "--msg-hex", "2a"];
+        argv.extend(args);
+        let (code, _out, err) = run(&argv);
+        assert_ne!(code, 0);
+        assert!(err.contains(needle), "{err}");
+    }
+}
@@ -0,0 +1,32 @@
+use std::fs;
+use std::process::Command;
+use tempfile::tempdir;
+
+const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+
+#[test]
+fn completions_enumerate_backends_and_profiles() {
+    let out = Command::new(BIN)
+        .args(["completions", "bash"])
+        .output()
+        .expect("run completions");
+    assert!(out.status.success());
+    let script = String::from_utf8(out.stdout).unwrap();
+    assert!(script.contains("native@0.0"));
+    assert!(script.contains("native@latest"));
+    assert!(script.contains("balanced"));
+}
+
+#[test]
+fn man_pages_written_per_subcommand() {
+    let dir = tempdir().unwrap();
+    let out = Command::new(BIN)
+        .args(["man", "-o", dir.path().to_str().unwrap()])
+        .output()
+        .expect("run man");
+    assert!(out.status.success());
+    let root = fs::read_to_string(dir.path().join("zkd.1")).expect("zkd.1");
+    assert!(root.contains(".TH"));
+    let prove = fs::read_to_string(dir.path().join("zkd-prove.1")).expect("zkd-prove.1");
+    assert!(prove.contains("native@0.0"));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{zkd, BIN};
+
+fn suite() -> String {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    base.join("../../examples/conformance/suite.json")
+        .to_str()
+        .expect("utf8 path")
+        .to_owned()
+}
+
+#[test]
+fn reference_and_self_adapter_pass_the_suite() {
+    let suite = suite();
+    let out = zkd(&["conformance", "--suite", &suite]);
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("PASS toy/prove"));
+    assert!(stdout.contains("PASS commit_demo/corrupt:body_len"));
+    assert!(!stdout.contains("FAIL"));
+
+    let out = zkd(&[
+        "conformance",
+        "--suite",
+        &suite,
+        "--json",
+        "--",
+        BIN,
+        "conformance",
+        "--adapter",
+    ]);
+    assert!(out.status.success(), "{:?}", out);
+    let results: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let results = results.as_array().unwrap();
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|r| r["pass"] == true));
+}
+
+#[test]
+fn adapter_that_accepts_everything_fails_corruption_checks() {
+    // Proves with zkd but reports every proof as verified.
+    let script = format!(
+        r#"req=$(cat); case "$req" in *'"op":"verify"'*) echo '{{"code":0}}';; *) printf '%s' "$req" | '{BIN}' conformance --adapter;; esac"#
+    );
+    let out = zkd(&[
+        "conformance",
+        "--suite",
+        &suite(),
+        "--",
+        "sh",
+        "-c",
+        &script,
+    ]);
+    assert_eq!(out.status.code(), Some(1), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("PASS toy/prove"));
+    assert!(stdout.contains("PASS toy/verify"));
+    assert!(stdout.contains("FAIL toy/corrupt:magic: expected proof_corrupt, got a verified proof"));
+}
+
+#[test]
+fn bless_rejects_an_adapter() {
+    let out = zkd(&["conformance", "--bless", "--", "true"]);
+    assert!(!out.status.success());
+}
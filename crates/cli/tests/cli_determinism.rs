@@ -0,0 +1,73 @@
+mod common;
+
+use std::fs;
+use std::process::{Command, Output};
+
+use tempfile::tempdir;
+
+use common::{air_path, BIN};
+
+fn audit(extra: &[&str]) -> Output {
+    let dir = tempdir().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    fs::write(&inputs, r#"{"a":1}"#).unwrap();
+    let inputs = inputs.to_str().unwrap().to_owned();
+    let air = air_path();
+    let mut args = vec![
+        "determinism-audit",
+        "-p",
+        &air,
+        "-i",
+        &inputs,
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    args.extend_from_slice(extra);
+    Command::new(BIN).args(&args).output().expect("run zkd")
+}
+
+#[test]
+fn native_backend_passes_the_audit() {
+    let out = audit(&["--threads", "1,3"]);
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("platform-intrinsics (build)"), "{stdout}");
+    assert!(stdout.contains("threads=3 digests=0x"), "{stdout}");
+    assert!(
+        stdout.contains(
+            "✅ DeterminismAuditPassed backend=native@0.0 profile=balanced proofs=4 warnings=0"
+        ),
+        "{stdout}"
+    );
+
+    let quiet = audit(&["--threads", "2", "-q"]);
+    assert!(quiet.status.success(), "{:?}", quiet);
+    assert_eq!(String::from_utf8_lossy(&quiet.stdout), "ok\n");
+}
+
+#[test]
+fn json_report_lists_runs_and_findings() {
+    let out = audit(&["--json"]);
+    assert!(out.status.success(), "{:?}", out);
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["deterministic"], true);
+    assert_eq!(report["runs"][0]["threads"], 1);
+    assert_eq!(report["runs"][1]["digests"].as_array().unwrap().len(), 4);
+    assert_eq!(report["findings"][0]["severity"], "info");
+}
+
+#[test]
+fn a_single_proof_is_rejected() {
+    let out = audit(&["--threads", "1"]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("two proofs"), "{stderr}");
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::tempdir;
+
+use common::{air_path, BIN, CFG};
+
+/// Run `cmd` on the toy AIR and `dir/toy.proof`, passing `dir/inputs.json`
+/// only when `with_inputs`.
+fn zkd(cmd: &str, dir: &Path, with_inputs: bool, extra: &[&str]) -> Output {
+    let air = air_path();
+    let inputs = dir.join("inputs.json");
+    let proof = dir.join("toy.proof");
+    let mut args = vec![cmd, "-p", &air];
+    if with_inputs {
+        args.extend(["-i", inputs.to_str().unwrap()]);
+    }
+    args.extend([
+        if cmd == "prove" { "-o" } else { "-P" },
+        proof.to_str().unwrap(),
+    ]);
+    args.extend(CFG);
+    args.extend_from_slice(extra);
+    Command::new(BIN).args(&args).output().expect("run zkd")
+}
+
+fn prove_embedded(dir: &Path) {
+    fs::write(dir.join("inputs.json"), "{ \"b\": [2, 3], \"a\": 1.0 }").unwrap();
+    let out = zkd("prove", dir, true, &["--embed-inputs"]);
+    assert!(out.status.success(), "{:?}", out);
+}
+
+#[test]
+fn embedded_inputs_make_the_proof_self_contained() {
+    let dir = tempdir().unwrap();
+    prove_embedded(dir.path());
+    let proof = fs::read(dir.path().join("toy.proof")).unwrap();
+    assert!(proof.starts_with(b"ZKPC"));
+    let text = String::from_utf8_lossy(&proof);
+    assert!(text.contains(r#"{"a":1,"b":[2,3]}"#), "{text}");
+
+    let out = zkd("verify", dir.path(), false, &[]);
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with("✅ ProofVerified"), "{stdout}");
+    assert!(stdout.contains("Inputs: embedded in "), "{stdout}");
+
+    // An explicit -i still decides what the proof is checked against.
+    assert!(zkd("verify", dir.path(), true, &[]).status.success());
+    fs::write(dir.path().join("inputs.json"), r#"{"a":2,"b":[2,3]}"#).unwrap();
+    assert_eq!(zkd("verify", dir.path(), true, &[]).status.code(), Some(4));
+
+    let json = zkd("verify", dir.path(), false, &["--json"]);
+    assert!(json.status.success(), "{:?}", json);
+    let envelope: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(envelope["embedded_inputs"], true);
+}
+
+#[test]
+fn tampered_embedded_inputs_fail_verification() {
+    let dir = tempdir().unwrap();
+    prove_embedded(dir.path());
+    let path = dir.path().join("toy.proof");
+    let proof = fs::read(&path).unwrap();
+    let needle = br#"{"a":1,"#;
+    let at = proof
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .expect("embedded inputs");
+    let mut tampered = proof.clone();
+    tampered[at + 5] = b'2';
+    fs::write(&path, &tampered).unwrap();
+
+    let out = zkd("verify", dir.path(), false, &[]);
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+}
+
+#[test]
+fn bare_proofs_still_need_inputs() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+    assert!(zkd("prove", dir.path(), true, &[]).status.success());
+    let out = zkd("verify", dir.path(), false, &[]);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("embeds no inputs"), "{stderr}");
+}
+
+#[test]
+fn policy_can_refuse_embedded_inputs() {
+    let dir = tempdir().unwrap();
+    prove_embedded(dir.path());
+    let policy = dir.path().join("rp.toml");
+    fs::write(&policy, "allow_embedded_inputs = false\n").unwrap();
+    let policy = policy.to_str().unwrap();
+
+    let out = zkd("verify", dir.path(), false, &["--policy", policy]);
+    assert_eq!(out.status.code(), Some(4), "{:?}", out);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("allow_embedded_inputs"), "{stderr}");
+
+    let out = zkd("verify", dir.path(), true, &["--policy", policy]);
+    assert!(out.status.success(), "{:?}", out);
+}
@@ -0,0 +1,111 @@
+//! The C ABI and `zkd --json` emit the same `zkprov-ffi-types` envelopes.
+
+mod common;
+
+use std::collections::BTreeSet;
+use std::ffi::{c_char, CStr, CString};
+use std::process::Command;
+use std::ptr;
+use tempfile::tempdir;
+
+use serde_json::Value;
+use zkprov::{zkp_free, zkp_verify, zkp_version, ZKP_OK};
+use zkprov_ffi_types::{Envelope, VerifyMeta, VersionReport, SCHEMA};
+
+use common::{air_path, BIN, CFG};
+
+fn take_json(ptr: *mut c_char) -> String {
+    assert!(!ptr.is_null());
+    let json = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_owned();
+    zkp_free(ptr.cast());
+    json
+}
+
+fn keys(json: &str) -> BTreeSet<String> {
+    let value: Value = serde_json::from_str(json).unwrap();
+    value.as_object().unwrap().keys().cloned().collect()
+}
+
+#[test]
+fn version_envelopes_match_across_ffi_and_cli() {
+    let mut out: *mut c_char = ptr::null_mut();
+    assert_eq!(unsafe { zkp_version(&mut out) }, ZKP_OK);
+    let ffi: Envelope<VersionReport> = serde_json::from_str(&take_json(out)).unwrap();
+
+    let cli = Command::new(BIN)
+        .args(["version", "--json"])
+        .output()
+        .unwrap();
+    assert!(cli.status.success());
+    let cli: Envelope<VersionReport> = serde_json::from_slice(&cli.stdout).unwrap();
+
+    assert_eq!(ffi.schema, SCHEMA);
+    assert_eq!(ffi, cli);
+}
+
+#[test]
+fn verify_envelopes_match_across_ffi_and_cli() {
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    let proof = tmp.path().join("toy.proof");
+    std::fs::write(&inputs, r#"{"a":1}"#).unwrap();
+    let air = air_path();
+    let (inputs_s, proof_s) = (inputs.to_str().unwrap(), proof.to_str().unwrap());
+    let zkd = |args: &[&str]| Command::new(BIN).args(args).args(CFG).output().unwrap();
+
+    assert!(zkd(&["prove", "-p", &air, "-i", inputs_s, "-o", proof_s])
+        .status
+        .success());
+    let cli = zkd(&[
+        "verify", "-p", &air, "-i", inputs_s, "-P", proof_s, "--json",
+    ]);
+    assert!(cli.status.success());
+    let cli_json = String::from_utf8(cli.stdout).unwrap();
+
+    let bytes = std::fs::read(&proof).unwrap();
+    let c = |s: &str| CString::new(s).unwrap();
+    let mut meta: *mut c_char = ptr::null_mut();
+    let status = unsafe {
+        zkp_verify(
+            c("native@0.0").as_ptr(),
+            c("Prime254").as_ptr(),
+            c("blake3").as_ptr(),
+            2,
+            c("balanced").as_ptr(),
+            c(&air).as_ptr(),
+            c(r#"{"a":1}"#).as_ptr(),
+            bytes.as_ptr(),
+            bytes.len() as u64,
+            &mut meta,
+        )
+    };
+    assert_eq!(status, ZKP_OK);
+    let ffi_json = take_json(meta);
+
+    let ffi: Envelope<VerifyMeta> = serde_json::from_str(&ffi_json).unwrap();
+    let cli: Envelope<VerifyMeta> = serde_json::from_str(&cli_json).unwrap();
+    assert_eq!(ffi, cli);
+    // The CLI may add fields, but never drops or renames FFI ones.
+    assert!(keys(&ffi_json).is_subset(&keys(&cli_json)));
+
+    // Failures share the envelope too: `ok`, `code` and `msg` are set.
+    let tampered = tmp.path().join("bad.proof");
+    let mut bad = bytes.clone();
+    bad.truncate(bad.len() - 1);
+    std::fs::write(&tampered, bad).unwrap();
+    let out = zkd(&[
+        "verify",
+        "-p",
+        &air,
+        "-i",
+        inputs_s,
+        "-P",
+        tampered.to_str().unwrap(),
+        "--json",
+    ]);
+    let failed: Envelope = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(!failed.ok);
+    assert_eq!(failed.schema, SCHEMA);
+    assert_eq!(failed.code, zkprov::ZKP_ERR_PROOF_CORRUPT);
+    assert!(!failed.msg.is_empty());
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use std::fs;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+use tempfile::tempdir;
+
+use common::{toy_args, BIN};
+
+fn zkd_json(args: &[String]) -> Output {
+    Command::new(BIN)
+        .arg("--format")
+        .arg("json")
+        .args(args)
+        .output()
+        .expect("run zkd")
+}
+
+/// The one envelope a `--format json` command prints.
+fn envelope(out: &Output) -> Value {
+    let text = String::from_utf8(out.stdout.clone()).expect("utf8 stdout");
+    assert_eq!(text.lines().count(), 1, "{text}");
+    let envelope: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(envelope["schema"], "zkp-ffi/1");
+    envelope
+}
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn prove_and_verify_report_envelopes() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+
+    let mut prove = toy_args("prove", dir.path(), &[]);
+    prove.push("--stats".into());
+    let proved = zkd_json(&prove);
+    assert!(proved.status.success(), "{:?}", proved);
+    let proved = envelope(&proved);
+    assert_eq!(proved["ok"], true);
+    assert_eq!(proved["command"], "prove");
+    assert_eq!(proved["backend"], "native@0.0");
+    assert_eq!(proved["digest"], proved["result"]);
+    assert_eq!(proved["stats"]["cols"], 4);
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(proved["manifest"].as_str().unwrap()).unwrap())
+            .unwrap();
+    assert_eq!(proved["digest"], manifest["digest"]);
+
+    // `--format json` implies `verify --json`, and may follow the subcommand.
+    let mut verify = toy_args("verify", dir.path(), &[]);
+    verify.extend(strings(&["--format", "json"]));
+    let verified = Command::new(BIN).args(&verify).output().unwrap();
+    assert!(verified.status.success(), "{:?}", verified);
+    let verified = envelope(&verified);
+    assert_eq!(verified["verified"], true);
+    assert_eq!(verified["command"], "verify");
+    assert_eq!(verified["digest"], proved["digest"]);
+
+    let proof_path = dir.path().join("toy.proof");
+    let mut proof = fs::read(&proof_path).unwrap();
+    let last = proof.len() - 1;
+    proof[last] ^= 1;
+    fs::write(&proof_path, &proof).unwrap();
+    let tampered = zkd_json(&toy_args("verify", dir.path(), &[]));
+    assert_eq!(tampered.status.code(), Some(4));
+    let tampered = envelope(&tampered);
+    assert_eq!(tampered["ok"], false);
+    assert_eq!(tampered["verified"], false);
+    assert_eq!(tampered["exit_code"], 4);
+
+    let digest = zkd_json(&strings(&[
+        "evm-digest",
+        "-P",
+        proof_path.to_str().unwrap(),
+    ]));
+    assert!(envelope(&digest)["digest"]
+        .as_str()
+        .unwrap()
+        .starts_with("0x"));
+}
+
+#[test]
+fn listings_and_values_become_fields() {
+    let profiles = zkd_json(&strings(&["profile-ls"]));
+    assert!(profiles.status.success());
+    let profiles = envelope(&profiles);
+    assert_eq!(profiles["command"], "profile-ls");
+    let ids: Vec<&str> = profiles["profiles"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_str().unwrap())
+        .collect();
+    assert!(ids.contains(&"balanced"), "{ids:?}");
+    assert_eq!(
+        profiles["lines"].as_array().unwrap().len(),
+        ids.len(),
+        "text lines are kept too"
+    );
+
+    // `backend-ls --json`'s registry dump joins the envelope.
+    let backends = envelope(&zkd_json(&strings(&["backend-ls"])));
+    assert!(backends["backends"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|b| b["id"] == "native@0.0"));
+    assert_eq!(backends["aliases"]["native@latest"], "native@0.0");
+
+    let commit = envelope(&zkd_json(&strings(&[
+        "commit",
+        "--hash",
+        "blake3",
+        "--msg-hex",
+        "0102",
+        "--blind-hex",
+        "0304",
+    ])));
+    assert_eq!(commit["commitment"].as_str().unwrap().len(), 64);
+    assert_eq!(commit["command"], "commit");
+}
+
+#[test]
+fn errors_become_failed_envelopes() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+    let missing = zkd_json(&toy_args("verify", dir.path(), &[]));
+    assert_eq!(missing.status.code(), Some(1));
+    let missing = envelope(&missing);
+    assert_eq!(missing["ok"], false);
+    assert_eq!(missing["exit_code"], 1);
+    assert!(missing["msg"].as_str().unwrap().contains("toy.proof"));
+
+    let pkg = envelope(&zkd_json(&strings(&["pkg", "list"])));
+    assert_eq!(pkg["command"], "pkg list");
+}
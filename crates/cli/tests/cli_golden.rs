@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::tempdir;
+
+const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+
+fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn verify_golden(root: &Path, bless: bool) -> Output {
+    let mut cmd = Command::new(BIN);
+    cmd.args(["examples", "verify-golden", "--root"]).arg(root);
+    if bless {
+        cmd.arg("--bless");
+    }
+    cmd.output().unwrap()
+}
+
+#[test]
+fn committed_golden_lock_matches_examples() {
+    let out = verify_golden(&repo_root(), false);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(out.status.success(), "{stdout}");
+    assert!(stdout.contains("PASS air/toy.air"), "{stdout}");
+    assert!(!stdout.contains("FAIL"), "{stdout}");
+}
+
+#[test]
+fn bless_pins_new_airs_and_verify_detects_drift() {
+    let tmp = tempdir().unwrap();
+    let air_dir = tmp.path().join("examples/air");
+    fs::create_dir_all(&air_dir).unwrap();
+    fs::copy(
+        repo_root().join("examples/air/toy.air"),
+        air_dir.join("toy.air"),
+    )
+    .unwrap();
+    let lock = tmp.path().join("examples/golden.lock");
+
+    let out = verify_golden(tmp.path(), false);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stdout).contains("FAIL air/toy.air: not in golden.lock"));
+
+    let out = verify_golden(tmp.path(), true);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let text = fs::read_to_string(&lock).unwrap();
+    assert!(text.contains(r#"backend = "native@0.0""#), "{text}");
+    assert!(text.contains(r#"profile = "balanced""#), "{text}");
+    assert!(verify_golden(tmp.path(), false).status.success());
+
+    // Changing a pin changes the proof, so the recorded digest no longer matches.
+    fs::write(
+        &lock,
+        text.replace(r#"inputs = "{}""#, r#"inputs = '{"a":1}'"#),
+    )
+    .unwrap();
+    let out = verify_golden(tmp.path(), false);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&out.stdout).contains("FAIL air/toy.air: digest 0x"),
+        "{}",
+        String::from_utf8_lossy(&out.stdout)
+    );
+
+    fs::remove_file(air_dir.join("toy.air")).unwrap();
+    let out = verify_golden(tmp.path(), false);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("stale golden.lock entry"));
+    assert!(verify_golden(tmp.path(), true).status.success());
+    assert!(!fs::read_to_string(&lock).unwrap().contains("toy.air"));
+}
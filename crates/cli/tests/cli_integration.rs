@@ -1,18 +1,13 @@
+mod common;
+
 use std::fs;
 use std::process::Command;
 use tempfile::tempdir;
 
 use zkprov_corelib::evm::digest::digest_D;
-use zkprov_corelib::proof::ProofHeader;
+use zkprov_corelib::proof::{ProofHeader, HEADER_LEN};
 
-const BIN: &str = env!("CARGO_BIN_EXE_zkd");
-fn air_path() -> String {
-    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
-    base.join("../../examples/air/toy.air")
-        .to_str()
-        .expect("utf8 path")
-        .to_owned()
-}
+use common::{air_path, BIN};
 
 fn write(path: &std::path::Path, s: &str) {
     fs::write(path, s).expect("write");
@@ -100,6 +95,115 @@ fn io_schema_outputs_json() {
     // Must be valid JSON and include program field name
     let v: serde_json::Value = serde_json::from_str(&s).expect("json");
     assert_eq!(v["program"], serde_json::json!("toy_merkle"));
+    assert_eq!(v["degree_hint"], 4);
+    assert_eq!(v["rows_hint"], 65536);
+    assert_eq!(v["commitments"]["pedersen"], true);
+    assert_eq!(
+        v["commitments"]["curves"],
+        serde_json::json!(["placeholder"])
+    );
+}
+
+#[test]
+fn io_schema_commitments_follow_air_bindings() {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let demo = base.join("../../examples/air/commit_demo.air");
+    let out = Command::new(BIN)
+        .args(["io-schema", "-p", demo.to_str().unwrap()])
+        .output()
+        .expect("run io-schema");
+    assert!(out.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    let commitments = &v["commitments"];
+    assert_eq!(commitments["no_r_reuse"], false);
+    let bindings = commitments["bindings"].as_array().unwrap();
+    assert_eq!(bindings.len(), 3);
+    let pedersen = bindings.iter().find(|b| b["kind"] == "pedersen").unwrap();
+    assert_eq!(pedersen["curve"], "placeholder");
+    assert_eq!(pedersen["public_inputs"], serde_json::json!(["x", "y"]));
+    let keccak = bindings
+        .iter()
+        .find(|b| b["kind"] == "keccak_commit")
+        .unwrap();
+    assert_eq!(keccak["public_inputs"], serde_json::json!(["digest"]));
+    assert_eq!(v["public_inputs"]["declared"][3]["type"], "u64");
+    assert!(v["degree_hint"].is_null());
+
+    // AIRs without commitments report none instead of a hard-coded Pedersen block.
+    let tmp = tempdir().unwrap();
+    let plain = tmp.path().join("plain.air");
+    write(
+        &plain,
+        r#"[meta]
+name = "plain"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+"#,
+    );
+    let out = Command::new(BIN)
+        .args(["io-schema", "-p", plain.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(v["commitments"]["pedersen"], false);
+    assert_eq!(v["commitments"]["curves"], serde_json::json!([]));
+    assert_eq!(v["commitments"]["bindings"], serde_json::json!([]));
+}
+
+#[test]
+fn io_check_canonicalizes_declared_inputs() {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let air = base.join("../../examples/air/range_check.air");
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    let io_check = |json: &str, extra: &[&str]| {
+        write(&inputs, json);
+        Command::new(BIN)
+            .args(["io-check", "-p", air.to_str().unwrap()])
+            .args(["-i", inputs.to_str().unwrap()])
+            .args(extra)
+            .output()
+            .expect("run io-check")
+    };
+
+    let out = io_check(r#"{"amount": "0x2a", "note": "x"}"#, &[]);
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some(r#"{"amount":42}"#));
+    let summary = lines.next().unwrap();
+    assert!(
+        summary.starts_with("✅ InputsValid declared=1 supplied=1 pubio_hash=0x"),
+        "{summary}"
+    );
+    // Same value, other spelling: same pubio_hash.
+    let out = io_check(r#"{"amount": 42}"#, &[]);
+    assert!(String::from_utf8_lossy(&out.stdout).contains(summary));
+
+    let out = io_check(
+        r#"{"amount": 42, "note": "x"}"#,
+        &["--undeclared-inputs", "reject"],
+    );
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("undeclared public inputs: note"));
+
+    let out = io_check(r#"{"amount": -1}"#, &[]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("public input 'amount' (u64)"));
+
+    let out = io_check("{}", &[]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("missing declared public inputs: amount"));
+    let out = io_check("{}", &["--allow-missing"]);
+    assert!(out.status.success(), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("declared=1 supplied=0"));
 }
 
 #[test]
@@ -135,9 +239,9 @@ fn evm_digest_matches_testdata_fixture() {
     assert!(status.success());
 
     let proof = fs::read(&proof_path).expect("read proof");
-    assert!(proof.len() > 40, "proof missing body");
-    let header = ProofHeader::decode(&proof[0..40]).expect("decode header");
-    let body = &proof[40..];
+    assert!(proof.len() > HEADER_LEN, "proof missing body");
+    let header = ProofHeader::decode(&proof[..HEADER_LEN]).expect("decode header");
+    let body = &proof[HEADER_LEN..];
     assert_eq!(body.len() as u64, header.body_len);
 
     let digest = digest_D(&header, body);
@@ -160,4 +264,1638 @@ fn evm_digest_matches_testdata_fixture() {
         .trim()
         .to_owned();
     assert_eq!(expected_hex, fixture_hex);
+
+    // evm-fixtures regenerates the committed testdata byte for byte.
+    let out_dir = tmp.path().join("fixtures");
+    let fixtures = Command::new(BIN)
+        .args(["evm-fixtures", "-P", proof_path.to_str().unwrap()])
+        .args(["-i", inputs_path.to_str().unwrap()])
+        .args(["-o", out_dir.to_str().unwrap()])
+        .output()
+        .expect("run evm-fixtures");
+    assert!(fixtures.status.success(), "{:?}", fixtures);
+    for name in [
+        "meta.json",
+        "body.bin",
+        "digest.hex",
+        "meta.abi",
+        "body.abi",
+    ] {
+        assert_eq!(
+            fs::read(out_dir.join(name)).unwrap(),
+            fs::read(testdata_dir().join(name)).unwrap(),
+            "{name}"
+        );
+    }
+
+    // Inputs the proof does not bind are refused.
+    write(&inputs_path, r#"{"a":2}"#);
+    let mismatch = Command::new(BIN)
+        .args(["evm-fixtures", "-P", proof_path.to_str().unwrap()])
+        .args(["-i", inputs_path.to_str().unwrap()])
+        .args(["-o", out_dir.to_str().unwrap()])
+        .output()
+        .expect("run evm-fixtures");
+    assert!(!mismatch.status.success());
+    assert!(String::from_utf8_lossy(&mismatch.stderr).contains("pubio_hash"));
+}
+
+#[test]
+fn profile_override_recorded_in_manifest() {
+    let tmp = tempdir().expect("tempdir");
+    let inputs_path = tmp.path().join("inputs.json");
+    let proof_path = tmp.path().join("toy.proof");
+    write(&inputs_path, r#"{"a":1}"#);
+
+    let air = air_path();
+    let prove = |overrides: &[&str]| {
+        let mut args = vec![
+            "prove",
+            "-p",
+            &air,
+            "-i",
+            inputs_path.to_str().unwrap(),
+            "-o",
+            proof_path.to_str().unwrap(),
+            "--backend",
+            "native@0.0",
+            "--field",
+            "Prime254",
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+            "--profile",
+            "balanced",
+        ];
+        for o in overrides {
+            args.push("--profile-override");
+            args.push(o);
+        }
+        Command::new(BIN).args(&args).status().expect("run prove")
+    };
+
+    assert!(prove(&["fri_queries=40", "grind_bits=20"]).success());
+    let manifest_path = tmp.path().join("toy.proof.manifest.json");
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).expect("read manifest"))
+            .expect("manifest json");
+    let vector = &manifest["determinism_vector"];
+    assert_eq!(vector["profile"], serde_json::json!("balanced"));
+    assert_eq!(vector["profile_overrides"]["fri_queries"], 40);
+    assert_eq!(vector["profile_overrides"]["grind_bits"], 20);
+
+    // Overrides are re-validated against Profile::validate
+    assert!(!prove(&["fri_queries=8"]).success());
+    assert!(!prove(&["lambda_bits=128"]).success());
+}
+
+#[test]
+fn backend_options_validated_and_hashed_into_manifest() {
+    let tmp = tempdir().expect("tempdir");
+    let inputs_path = tmp.path().join("inputs.json");
+    let proof_path = tmp.path().join("toy.proof");
+    write(&inputs_path, r#"{"a":1}"#);
+
+    let air = air_path();
+    let prove = |options: &str| {
+        Command::new(BIN)
+            .args([
+                "prove",
+                "-p",
+                &air,
+                "-i",
+                inputs_path.to_str().unwrap(),
+                "-o",
+                proof_path.to_str().unwrap(),
+                "--backend",
+                "native@0.0",
+                "--field",
+                "Prime254",
+                "--hash",
+                "blake3",
+                "--fri-arity",
+                "2",
+                "--profile",
+                "balanced",
+                "--backend-options",
+                options,
+            ])
+            .output()
+            .expect("run prove")
+    };
+
+    assert!(prove(r#"{"partition_size":4,"batching":"linear"}"#)
+        .status
+        .success());
+    let manifest_path = tmp.path().join("toy.proof.manifest.json");
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest_path).expect("read manifest"))
+            .expect("manifest json");
+    let recorded = manifest["determinism_vector"]["backend_options"]
+        .as_str()
+        .expect("options hash");
+    assert_eq!(recorded.len(), 64);
+
+    let out = prove(r#"{"batching":"fast"}"#);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("invalid options for backend 'native@0.0': /batching"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn backend_ls_shows_alias_chain_and_alias_proves() {
+    let out = Command::new(BIN)
+        .args(["backend-ls"])
+        .output()
+        .expect("run backend-ls");
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("native@latest -> native@0.0"), "{stdout}");
+
+    let tmp = tempdir().expect("tempdir");
+    let inputs_path = tmp.path().join("inputs.json");
+    let proof_path = tmp.path().join("alias.proof");
+    write(&inputs_path, r#"{"a":1}"#);
+    let air = air_path();
+    let run = |cmd: &str, flag: &str, backend: &str| {
+        Command::new(BIN)
+            .args([
+                cmd,
+                "-p",
+                &air,
+                "-i",
+                inputs_path.to_str().unwrap(),
+                flag,
+                proof_path.to_str().unwrap(),
+                "--backend",
+                backend,
+                "--field",
+                "Prime254",
+                "--hash",
+                "blake3",
+                "--fri-arity",
+                "2",
+                "--profile",
+                "balanced",
+            ])
+            .status()
+            .expect("run zkd")
+    };
+    // Proofs made via the alias bind the canonical backend id.
+    assert!(run("prove", "-o", "native@latest").success());
+    assert!(run("verify", "-P", "native@0.0").success());
+}
+
+#[test]
+fn evm_typed_hash_is_deterministic_and_domain_bound() {
+    let tmp = tempdir().expect("tempdir");
+    let inputs_path = tmp.path().join("inputs.json");
+    let proof_path = tmp.path().join("typed.proof");
+    write(&inputs_path, r#"{"a":1}"#);
+    let air = air_path();
+    let status = Command::new(BIN)
+        .args([
+            "prove",
+            "-p",
+            &air,
+            "-i",
+            inputs_path.to_str().unwrap(),
+            "-o",
+            proof_path.to_str().unwrap(),
+            "--backend",
+            "native@0.0",
+            "--field",
+            "Prime254",
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+            "--profile",
+            "balanced",
+        ])
+        .status()
+        .expect("run prove");
+    assert!(status.success());
+
+    let typed_hash = |chain_id: &str| {
+        let out = Command::new(BIN)
+            .args([
+                "evm-typed-hash",
+                "-P",
+                proof_path.to_str().unwrap(),
+                "-p",
+                &air,
+                "-i",
+                inputs_path.to_str().unwrap(),
+                "--chain-id",
+                chain_id,
+                "--verifying-contract",
+                "0x1111111111111111111111111111111111111111",
+            ])
+            .output()
+            .expect("run evm-typed-hash");
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8(out.stdout).unwrap().trim().to_owned()
+    };
+    let h1 = typed_hash("1");
+    assert_eq!(h1.len(), 66);
+    assert_eq!(h1, typed_hash("1"));
+    assert_ne!(h1, typed_hash("10"));
+}
+
+#[test]
+fn trace_import_export_and_prove_from_trace() {
+    let tmp = tempdir().expect("tempdir");
+    let trace_json = tmp.path().join("trace.json");
+    let trace_bin = tmp.path().join("trace.zkt");
+    let exported = tmp.path().join("exported.json");
+    let inputs_path = tmp.path().join("inputs.json");
+    let proof_path = tmp.path().join("toy.proof");
+    write(
+        &trace_json,
+        r#"{"field":"Prime254","columns":[[1,1,1,1],[2,3,"4","0x5"],[0,0,0,0],[9,9,9,9]]}"#,
+    );
+    write(&inputs_path, r#"{"a":1}"#);
+
+    let import = Command::new(BIN)
+        .args([
+            "trace-import",
+            "-i",
+            trace_json.to_str().unwrap(),
+            "-o",
+            trace_bin.to_str().unwrap(),
+            "--compress",
+            "rle",
+        ])
+        .output()
+        .expect("run trace-import");
+    assert!(import.status.success(), "{:?}", import);
+    assert_eq!(&fs::read(&trace_bin).unwrap()[0..4], b"ZKTR");
+
+    let export = Command::new(BIN)
+        .args([
+            "trace-export",
+            "-i",
+            trace_bin.to_str().unwrap(),
+            "-o",
+            exported.to_str().unwrap(),
+        ])
+        .status()
+        .expect("run trace-export");
+    assert!(export.success());
+    let value: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&exported).unwrap()).unwrap();
+    assert_eq!(value["field"], "Prime254");
+    assert_eq!(
+        value["columns"][1],
+        serde_json::json!(["0x2", "0x3", "0x4", "0x5"])
+    );
+
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    let prove = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-o", proof_path.to_str().unwrap()])
+        .args(["--trace", trace_bin.to_str().unwrap()])
+        .args(common)
+        .output()
+        .expect("run prove");
+    assert!(prove.status.success(), "{:?}", prove);
+    assert!(String::from_utf8_lossy(&prove.stdout).contains("Trace: "));
+
+    // The JSON form proves the same.
+    let json_proof = tmp.path().join("json.proof");
+    let prove = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-o", json_proof.to_str().unwrap()])
+        .args(["--trace", trace_json.to_str().unwrap()])
+        .args(common)
+        .output()
+        .expect("run prove");
+    assert!(prove.status.success(), "{:?}", prove);
+    assert_eq!(
+        fs::read(&json_proof).unwrap(),
+        fs::read(&proof_path).unwrap()
+    );
+
+    // trace-import -p checks the shape against the AIR.
+    let import = |program: &str| {
+        Command::new(BIN)
+            .args(["trace-import", "-i", trace_json.to_str().unwrap()])
+            .args(["-o", trace_bin.to_str().unwrap(), "-p", program])
+            .output()
+            .expect("run trace-import")
+    };
+    assert!(import(&air).status.success());
+    let selector_air = air.replace("toy.air", "selector_counter.air");
+    let out = import(&selector_air);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("AIR declares 3"));
+
+    // A second run over the same trace reuses every cached column and yields
+    // the same proof.
+    let cache = tmp.path().join("toy.zktc");
+    let incremental_proof = tmp.path().join("incremental.proof");
+    for expected in ["reused_cols=0/4 reuse=0.0%", "reused_cols=4/4 reuse=100.0%"] {
+        let out = Command::new(BIN)
+            .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+            .args(["-o", incremental_proof.to_str().unwrap()])
+            .args(["--trace", trace_bin.to_str().unwrap()])
+            .args(["--incremental-cache", cache.to_str().unwrap(), "--stats"])
+            .args(common)
+            .output()
+            .expect("run incremental prove");
+        assert!(out.status.success(), "{:?}", out);
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(stdout.contains(expected), "{stdout}");
+    }
+    assert_eq!(
+        fs::read(&incremental_proof).unwrap(),
+        fs::read(&proof_path).unwrap()
+    );
+
+    // An expired deadline aborts at the first phase boundary with exit 6,
+    // naming the cache as the checkpoint the next run resumes from.
+    let checkpoint = tmp.path().join("deadline.zktc");
+    let timed_out = tmp.path().join("timed_out.proof");
+    let incremental = |extra: &[&str]| {
+        Command::new(BIN)
+            .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+            .args(["-o", timed_out.to_str().unwrap()])
+            .args(["--trace", trace_bin.to_str().unwrap()])
+            .args([
+                "--incremental-cache",
+                checkpoint.to_str().unwrap(),
+                "--stats",
+            ])
+            .args(extra)
+            .args(common)
+            .output()
+            .expect("run incremental prove")
+    };
+    let out = incremental(&["--deadline", "0ms"]);
+    assert_eq!(out.status.code(), Some(6), "{:?}", out);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let report: serde_json::Value = serde_json::from_str(
+        stderr
+            .lines()
+            .find_map(|l| l.strip_prefix("❌ TimedOut "))
+            .expect("TimedOut report"),
+    )
+    .unwrap();
+    assert_eq!(report["phase"], "trace");
+    assert_eq!(report["budget_ms"], 0);
+    assert_eq!(report["phases"].as_array().unwrap().len(), 1);
+    assert_eq!(report["checkpoint"], checkpoint.to_str().unwrap());
+    assert!(!timed_out.exists());
+    let out = incremental(&["--deadline", "1h"]);
+    assert!(out.status.success(), "{:?}", out);
+    assert_eq!(
+        fs::read(&timed_out).unwrap(),
+        fs::read(&proof_path).unwrap()
+    );
+
+    let verify = Command::new(BIN)
+        .args(["verify", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-P", proof_path.to_str().unwrap()])
+        .args(common)
+        .status()
+        .expect("run verify");
+    assert!(verify.success());
+}
+
+#[test]
+fn evm_digest_aggregate_over_multiple_proofs() {
+    let tmp = tempdir().expect("tempdir");
+    let air = air_path();
+    let mut proofs = Vec::new();
+    for n in 1..=3 {
+        let inputs = tmp.path().join(format!("in{n}.json"));
+        let proof = tmp.path().join(format!("p{n}.proof"));
+        write(&inputs, &format!(r#"{{"a":{n}}}"#));
+        let status = Command::new(BIN)
+            .args(["prove", "-p", &air, "-i", inputs.to_str().unwrap()])
+            .args(["-o", proof.to_str().unwrap()])
+            .args([
+                "--backend",
+                "native@0.0",
+                "--field",
+                "Prime254",
+                "--hash",
+                "blake3",
+                "--fri-arity",
+                "2",
+                "--profile",
+                "balanced",
+            ])
+            .status()
+            .expect("run prove");
+        assert!(status.success());
+        proofs.push(proof.to_str().unwrap().to_owned());
+    }
+
+    let abi_out = tmp.path().join("agg.abi");
+    let mut args = vec!["evm-digest".to_owned()];
+    for p in &proofs {
+        args.push("-P".into());
+        args.push(p.clone());
+    }
+    let out = Command::new(BIN)
+        .args(&args)
+        .args(["--aggregate", "--with-leaves", "--abi-out"])
+        .arg(&abi_out)
+        .output()
+        .expect("run evm-digest --aggregate");
+    assert!(out.status.success(), "{:?}", out);
+    let value: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+
+    let fixture: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(testdata_dir().join("aggregate.json")).expect("fixture"),
+    )
+    .unwrap();
+    assert_eq!(value["root"], fixture["root"]);
+    assert_eq!(value["leaves"], fixture["leaves"]);
+    assert_eq!(
+        fs::read(&abi_out).unwrap(),
+        fs::read(testdata_dir().join("aggregate.abi")).unwrap()
+    );
+
+    // Without --aggregate only a single proof is accepted.
+    let multi = Command::new(BIN).args(&args).output().unwrap();
+    assert!(!multi.status.success());
+}
+
+#[test]
+fn trace_dump_flags_violations_and_exports_csv() {
+    let tmp = tempdir().expect("tempdir");
+    let trace_json = tmp.path().join("trace.json");
+    let trace_bin = tmp.path().join("trace.zkt");
+    let csv_path = tmp.path().join("rows.csv");
+    // Row 1 doubles 2 into 5 instead of 4.
+    write(
+        &trace_json,
+        r#"{"field":"Prime254","columns":[[1,2,5,6],[1,0,1,0],[0,1,0,1]]}"#,
+    );
+    let import = Command::new(BIN)
+        .args([
+            "trace-import",
+            "-i",
+            trace_json.to_str().unwrap(),
+            "-o",
+            trace_bin.to_str().unwrap(),
+        ])
+        .status()
+        .expect("run trace-import");
+    assert!(import.success());
+
+    let air = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/air/selector_counter.air");
+    let out = Command::new(BIN)
+        .args([
+            "trace-dump",
+            "-p",
+            air.to_str().unwrap(),
+            "--trace",
+            trace_bin.to_str().unwrap(),
+            "--rows",
+            "1..3",
+            "--csv",
+            csv_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("run trace-dump");
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(
+        lines[0].contains("row") && lines[0].contains("s_dbl"),
+        "{stdout}"
+    );
+    assert!(
+        lines[1].starts_with('!') && lines[1].contains("✗ #1"),
+        "{stdout}"
+    );
+    assert!(lines[2].starts_with(' '), "{stdout}");
+    assert!(stdout.contains("rows 1..3 of 4  violations=1"), "{stdout}");
+    assert!(stdout.contains("✗ #1 at row 1 -> 2"), "{stdout}");
+
+    let csv = fs::read_to_string(&csv_path).unwrap();
+    assert_eq!(csv, "row,a,s_inc,s_dbl,violations\n1,2,0,1,1\n2,5,1,0,\n");
+
+    let bad_range = Command::new(BIN)
+        .args([
+            "trace-dump",
+            "-p",
+            air.to_str().unwrap(),
+            "--trace",
+            trace_bin.to_str().unwrap(),
+            "--rows",
+            "4..",
+        ])
+        .output()
+        .unwrap();
+    assert!(!bad_range.status.success());
+}
+
+#[test]
+fn check_reports_violations_with_cell_values() {
+    let tmp = tempdir().expect("tempdir");
+    let inputs = tmp.path().join("inputs.json");
+    write(&inputs, "{}");
+    let air = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/air/selector_counter.air");
+    let check = |name: &str, columns: &str| {
+        let trace_json = tmp.path().join(format!("{name}.json"));
+        let trace_bin = tmp.path().join(format!("{name}.zkt"));
+        write(
+            &trace_json,
+            &format!(r#"{{"field":"Prime254","columns":{columns}}}"#),
+        );
+        let import = Command::new(BIN)
+            .args([
+                "trace-import",
+                "-i",
+                trace_json.to_str().unwrap(),
+                "-o",
+                trace_bin.to_str().unwrap(),
+            ])
+            .status()
+            .expect("run trace-import");
+        assert!(import.success());
+        Command::new(BIN)
+            .args([
+                "check",
+                "-p",
+                air.to_str().unwrap(),
+                "-i",
+                inputs.to_str().unwrap(),
+                "--trace",
+                trace_bin.to_str().unwrap(),
+            ])
+            .output()
+            .expect("run check")
+    };
+
+    let ok = check("ok", "[[1,2,4,5],[1,0,1,0],[0,1,0,1]]");
+    assert!(ok.status.success(), "{:?}", ok);
+    let stdout = String::from_utf8_lossy(&ok.stdout);
+    assert!(
+        stdout.contains("check ok: rows=4 transitions=4"),
+        "{stdout}"
+    );
+
+    // Row 1 doubles 2 into 5 instead of 4.
+    let bad = check("bad", "[[1,2,5,6],[1,0,1,0],[0,1,0,1]]");
+    assert_eq!(bad.status.code(), Some(1), "{:?}", bad);
+    let stdout = String::from_utf8_lossy(&bad.stdout);
+    assert!(
+        stdout.contains("✗ #1 at row 1 -> 2: (cur.s_dbl * (next.a - (cur.a * 2))) = 0"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("cur.s_dbl=1 next.a=5 cur.a=2"), "{stdout}");
+    assert!(stdout.contains("check failed: violations=1 "), "{stdout}");
+}
+
+#[test]
+fn verify_json_diagnoses_failures() {
+    let dir = tempdir().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    let other_inputs = dir.path().join("other.json");
+    let proof = dir.path().join("ok.proof");
+    write(&inputs, r#"{"n":7}"#);
+    write(&other_inputs, r#"{"n":8}"#);
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    let status = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs.to_str().unwrap()])
+        .args(["-o", proof.to_str().unwrap()])
+        .args(common)
+        .status()
+        .expect("run prove");
+    assert!(status.success());
+
+    let verify = |inputs: &std::path::Path, proof: &std::path::Path| {
+        let out = Command::new(BIN)
+            .args(["verify", "-p", &air, "-i", inputs.to_str().unwrap()])
+            .args(["-P", proof.to_str().unwrap(), "--json"])
+            .args(common)
+            .output()
+            .expect("run verify");
+        let v: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+        (out.status.code(), v)
+    };
+
+    let (code, v) = verify(&inputs, &proof);
+    assert_eq!(code, Some(0));
+    assert_eq!(v["verified"], true);
+    assert_eq!(v["backend"], "native@0.0");
+
+    let (code, v) = verify(&other_inputs, &proof);
+    assert_eq!(code, Some(4));
+    assert_eq!(v["verified"], false);
+    assert_eq!(v["exit_code"], 4);
+    assert_eq!(v["diagnosis"]["category"], "header_binding");
+    assert_eq!(v["diagnosis"]["field"], "pubio_hash");
+
+    let bytes = fs::read(&proof).unwrap();
+    let mut bad_root = bytes.clone();
+    bad_root[HEADER_LEN] ^= 0x01;
+    let bad_root_path = dir.path().join("root.proof");
+    fs::write(&bad_root_path, bad_root).unwrap();
+    let (code, v) = verify(&inputs, &bad_root_path);
+    assert_eq!(code, Some(4));
+    assert_eq!(v["diagnosis"]["category"], "root_mismatch");
+    assert_ne!(v["diagnosis"]["expected"], v["diagnosis"]["actual"]);
+
+    let truncated = dir.path().join("short.proof");
+    fs::write(&truncated, &bytes[..bytes.len() - 1]).unwrap();
+    let (code, v) = verify(&inputs, &truncated);
+    assert_eq!(code, Some(4));
+    assert_eq!(v["diagnosis"]["category"], "body_length");
+    assert_eq!(v["diagnosis"]["expected"], 8);
+    assert_eq!(v["diagnosis"]["actual"], 7);
+
+    let garbage = dir.path().join("garbage.proof");
+    fs::write(&garbage, b"nope").unwrap();
+    let (_, v) = verify(&inputs, &garbage);
+    assert_eq!(v["diagnosis"]["category"], "header_decode");
+}
+
+#[test]
+fn blind_new_emits_random_or_derived_blinds() {
+    let blind_new = |args: &[&str]| {
+        let out = Command::new(BIN)
+            .arg("blind-new")
+            .args(args)
+            .output()
+            .expect("run blind-new");
+        (out.status.success(), String::from_utf8(out.stdout).unwrap())
+    };
+    let (ok_a, a) = blind_new(&[]);
+    let (ok_b, b) = blind_new(&[]);
+    assert!(ok_a && ok_b);
+    assert_eq!(a.trim().len(), 64);
+    assert_ne!(a, b);
+
+    let dir = tempdir().unwrap();
+    let master = dir.path().join("master.hex");
+    write(&master, &format!("{}\n", "ab".repeat(32)));
+    let master = master.to_str().unwrap();
+    let (_, d1) = blind_new(&["--master-file", master, "--label", "orders/1"]);
+    let (_, d2) = blind_new(&["--master-file", master, "--label", "orders/1"]);
+    let (_, d3) = blind_new(&["--master-file", master, "--label", "orders/2"]);
+    assert_eq!(d1, d2);
+    assert_ne!(d1, d3);
+
+    let short = dir.path().join("short.hex");
+    write(&short, "abcd");
+    let (ok, _) = blind_new(&["--master-file", short.to_str().unwrap(), "--label", "x"]);
+    assert!(!ok);
+
+    let out = Command::new(BIN)
+        .args([
+            "commit",
+            "--hash",
+            "blake3",
+            "--msg-hex",
+            "2a",
+            "--blind-hex",
+        ])
+        .arg(d1.trim())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+}
+
+#[test]
+fn profile_check_reports_incompatibilities() {
+    let dir = tempdir().unwrap();
+    let profiles = dir.path().join("profiles");
+    fs::create_dir(&profiles).unwrap();
+    write(
+        &profiles.join("balanced.toml"),
+        "id = \"balanced\"\nlambda_bits = 100\nmerkle_arity = 2\n",
+    );
+    write(
+        &profiles.join("huge.toml"),
+        "id = \"huge\"\nlambda_bits = 100\nmerkle_arity = 8\nrows_max = 16777216\n",
+    );
+
+    let out = Command::new(BIN)
+        .current_dir(dir.path())
+        .arg("profile-check")
+        .output()
+        .expect("run profile-check");
+    assert!(out.status.success());
+    let text = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with("profile") && lines[0].contains("native@0.0"));
+    assert!(lines[1].starts_with("balanced") && lines[1].contains("ok"));
+    assert!(lines[2].starts_with("huge") && lines[2].contains('✗'));
+    assert!(text.contains("✗ huge on native@0.0 [arity]: merkle_arity 8"));
+    assert!(text.contains("[rows_max]: rows_max 16777216 exceeds backend limit 4194304"));
+
+    let out = Command::new(BIN)
+        .current_dir(dir.path())
+        .args(["profile-check", "--json"])
+        .output()
+        .expect("run profile-check --json");
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let huge = v
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["profile_id"] == "huge")
+        .unwrap();
+    assert_eq!(huge["compatible"], false);
+    assert_eq!(huge["reasons"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn snark_wrapped_profiles_need_a_wrapping_backend() {
+    let dir = tempdir().unwrap();
+    let profiles = dir.path().join("profiles");
+    fs::create_dir(&profiles).unwrap();
+    write(
+        &profiles.join("balanced.toml"),
+        "id = \"balanced\"\nlambda_bits = 100\n",
+    );
+    write(
+        &profiles.join("onchain.toml"),
+        "id = \"onchain\"\nlambda_bits = 100\ntarget = \"snark-wrapped\"\n",
+    );
+
+    let out = Command::new(BIN)
+        .current_dir(dir.path())
+        .arg("profile-ls")
+        .output()
+        .expect("run profile-ls");
+    assert!(out.status.success());
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(
+        text.contains("balanced  λ=100 bits  target=stark"),
+        "{text}"
+    );
+    assert!(
+        text.contains("onchain  λ=100 bits  target=snark-wrapped"),
+        "{text}"
+    );
+
+    let out = Command::new(BIN)
+        .current_dir(dir.path())
+        .arg("profile-check")
+        .output()
+        .expect("run profile-check");
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(text.contains("✗ onchain on native@0.0 [target]"), "{text}");
+
+    let inputs = dir.path().join("inputs.json");
+    write(&inputs, r#"{"demo":true,"n":7}"#);
+    let out = Command::new(BIN)
+        .current_dir(dir.path())
+        .args(["prove", "-p", &air_path(), "-i", inputs.to_str().unwrap()])
+        .args(["-o", dir.path().join("p.proof").to_str().unwrap()])
+        .args(["--backend", "native@0.0", "--field", "Prime254"])
+        .args([
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+            "--profile",
+            "onchain",
+        ])
+        .output()
+        .expect("run prove");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("profile 'onchain' targets snark-wrapped proofs"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn typed_pubio_encoding_binds_proof_and_emits_solidity() {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let demo = base.join("../../examples/air/commit_demo.air");
+    let demo = demo.to_str().unwrap();
+    let dir = tempdir().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    write(&inputs, r#"{"x":1,"y":2,"acc":"0xbeef","digest":7}"#);
+    let inputs = inputs.to_str().unwrap();
+    let abi_out = dir.path().join("io.abi");
+
+    let out = Command::new(BIN)
+        .args(["evm-pubio", "-p", demo, "-i", inputs, "--abi-out"])
+        .arg(&abi_out)
+        .output()
+        .expect("run evm-pubio");
+    assert!(out.status.success());
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["encoding"], "typed");
+    // x, y, digest words + bytes offset/len/data word.
+    assert_eq!(v["abi_len"], 6 * 32);
+    assert_eq!(fs::read(&abi_out).unwrap().len(), 6 * 32);
+
+    let out = Command::new(BIN)
+        .args(["io-schema", "-p", demo, "--solidity"])
+        .output()
+        .unwrap();
+    let sol = String::from_utf8(out.stdout).unwrap();
+    assert!(sol.contains("struct CommitDemoPublicIO {"));
+    assert!(sol.contains("    bytes acc;"));
+
+    let proof = dir.path().join("demo.proof");
+    let proof = proof.to_str().unwrap();
+    let run = |cmd: &str, encoding: &str| {
+        let io = if cmd == "prove" { "-o" } else { "-P" };
+        Command::new(BIN)
+            .args([cmd, "-p", demo, "-i", inputs, io, proof])
+            .args(["--backend", "native@0.0", "--field", "Prime254", "--hash"])
+            .args(["blake3", "--fri-arity", "2", "--profile", "balanced"])
+            .args(["--pubio-encoding", encoding])
+            .output()
+            .unwrap()
+    };
+    assert!(run("prove", "typed").status.success());
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(format!("{proof}.manifest.json")).unwrap())
+            .unwrap();
+    assert_eq!(manifest["determinism_vector"]["pubio_encoding"], "typed");
+    assert!(run("verify", "typed").status.success());
+    assert!(!run("verify", "json").status.success());
+}
+
+#[test]
+fn version_verbose_reports_build_info_and_prove_can_record_it() {
+    let out = Command::new(BIN).args(["version"]).output().unwrap();
+    assert!(out.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout).trim(),
+        format!("zkd {}", env!("CARGO_PKG_VERSION"))
+    );
+
+    let out = Command::new(BIN)
+        .args(["version", "--verbose"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    for key in ["commit:", "target:", "profile:", "rustc:", "features:"] {
+        assert!(stdout.contains(key), "missing {key} in {stdout}");
+    }
+
+    let out = Command::new(BIN)
+        .args(["version", "--json"])
+        .output()
+        .unwrap();
+    let info: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    write(&inputs, r#"{"a":1}"#);
+    let air = air_path();
+    let prove = |name: &str, extra: &[&str]| {
+        let proof = tmp.path().join(name);
+        let mut args = vec![
+            "prove",
+            "-p",
+            &air,
+            "-i",
+            inputs.to_str().unwrap(),
+            "-o",
+            proof.to_str().unwrap(),
+            "--backend",
+            "native@0.0",
+            "--field",
+            "Prime254",
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+            "--profile",
+            "balanced",
+        ];
+        args.extend_from_slice(extra);
+        assert!(Command::new(BIN).args(&args).status().unwrap().success());
+        let manifest = fs::read_to_string(format!("{}.manifest.json", proof.display())).unwrap();
+        serde_json::from_str::<serde_json::Value>(&manifest).unwrap()
+    };
+    let plain = prove("plain.proof", &[]);
+    let recorded = prove("recorded.proof", &["--build-info"]);
+    assert!(plain.get("build").is_none());
+    assert_eq!(recorded["build"], info["build"]);
+    assert_eq!(
+        plain["determinism_vector"]["manifest_hash"],
+        recorded["determinism_vector"]["manifest_hash"]
+    );
+}
+
+#[test]
+fn examples_run_evm_checks_fixtures() {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let out = Command::new(BIN)
+        .args(["examples", "run", "evm", "--root", root.to_str().unwrap()])
+        .output()
+        .expect("run examples");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(out.status.success(), "{stdout}");
+    assert!(
+        stdout.starts_with("PASS EVM fixtures") || stdout.starts_with("SKIP EVM fixtures"),
+        "{stdout}"
+    );
+
+    // A stale digest fixture is reported and fails the run.
+    let tmp = tempdir().unwrap();
+    let testdata = tmp.path().join("examples/evm_verifier/testdata");
+    fs::create_dir_all(&testdata).unwrap();
+    for entry in fs::read_dir(testdata_dir()).unwrap() {
+        let entry = entry.unwrap();
+        fs::copy(entry.path(), testdata.join(entry.file_name())).unwrap();
+    }
+    write(&tmp.path().join("Cargo.toml"), "[workspace]\n");
+    write(
+        &testdata.join("digest.hex"),
+        &format!("{}\n", "00".repeat(32)),
+    );
+    let out = Command::new(BIN)
+        .args([
+            "examples",
+            "run",
+            "evm",
+            "--root",
+            tmp.path().to_str().unwrap(),
+        ])
+        .output()
+        .expect("run examples");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert_eq!(out.status.code(), Some(1), "{stdout}");
+    assert!(stdout.contains("FAIL EVM fixtures: digest.hex"), "{stdout}");
+
+    let out = Command::new(BIN)
+        .args(["examples", "run", "--root", testdata.to_str().unwrap()])
+        .output()
+        .expect("run examples");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("not the zkprov workspace root"));
+}
+
+#[test]
+fn backend_ls_reports_plugins_that_fail_to_load() {
+    let tmp = tempdir().unwrap();
+    let junk = tmp
+        .path()
+        .join(format!("junk.{}", std::env::consts::DLL_EXTENSION));
+    write(&junk, "not a library");
+    let out = Command::new(BIN)
+        .args(["backend-ls"])
+        .env("ZKD_BACKEND_PATH", tmp.path())
+        .output()
+        .expect("run backend-ls");
+    assert!(out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains(&format!("⚠️ plugin {}: cannot load", junk.display())),
+        "{stderr}"
+    );
+    assert!(String::from_utf8_lossy(&out.stdout).contains("native@0.0"));
+
+    let out = Command::new(BIN)
+        .args(["backend-ls", "--json"])
+        .env("ZKD_BACKEND_PATH", tmp.path())
+        .output()
+        .expect("run backend-ls");
+    let registry: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        registry["plugin_errors"][0]["path"],
+        junk.display().to_string()
+    );
+}
+
+#[test]
+fn backend_auto_selects_registry_match_and_backend_ls_json_dumps_registry() {
+    let out = Command::new(BIN)
+        .args(["backend-ls", "--json"])
+        .output()
+        .expect("run backend-ls");
+    assert!(out.status.success());
+    let registry: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let backends = registry["backends"].as_array().unwrap();
+    let by_id = |id: &str| backends.iter().find(|b| b["id"] == id).unwrap();
+    let native = by_id("native@0.0");
+    assert_eq!(native["placeholder"], false);
+    assert_eq!(
+        native["capabilities"]["fields"],
+        serde_json::json!(["Prime254"])
+    );
+    assert_eq!(registry["aliases"]["native@latest"], "native@0.0");
+    assert_eq!(
+        by_id("ministark@0.1")["capabilities"]["fields"],
+        serde_json::json!(["Goldilocks"])
+    );
+
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    let proof = tmp.path().join("auto.proof");
+    write(&inputs, r#"{"a":1}"#);
+    let air = air_path();
+    let prove = |hash: &str| {
+        Command::new(BIN)
+            .args([
+                "prove",
+                "-p",
+                &air,
+                "-i",
+                inputs.to_str().unwrap(),
+                "-o",
+                proof.to_str().unwrap(),
+                "--backend",
+                "auto",
+                "--field",
+                "Prime254",
+                "--hash",
+                hash,
+                "--fri-arity",
+                "2",
+                "--profile",
+                "balanced",
+            ])
+            .output()
+            .expect("run prove")
+    };
+    let out = prove("blake3");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8_lossy(&out.stdout).contains("backend=native@0.0"));
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(format!("{}.manifest.json", proof.display())).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(manifest["determinism_vector"]["backend"], "native@0.0");
+
+    let out = prove("sha3");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no registered backend matches"));
+}
+
+#[test]
+fn prove_aborts_before_proving_when_memory_estimate_exceeds_max_mem() {
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    write(&inputs, r#"{"a":1}"#);
+    let proof = tmp.path().join("toy.proof");
+    let air = air_path();
+    let prove = |max_mem: &str| {
+        Command::new(BIN)
+            .args([
+                "prove",
+                "-p",
+                &air,
+                "-i",
+                inputs.to_str().unwrap(),
+                "-o",
+                proof.to_str().unwrap(),
+                "--backend",
+                "native@0.0",
+                "--field",
+                "Prime254",
+                "--hash",
+                "blake3",
+                "--fri-arity",
+                "2",
+                "--profile",
+                "balanced",
+                "--max-mem",
+                max_mem,
+            ])
+            .output()
+            .unwrap()
+    };
+
+    // toy.air: 65536 rows x 6 columns x 32 bytes, blowup 16 => ~268 MiB.
+    let out = prove("200");
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("MemoryBudgetExceeded"), "{stderr}");
+    assert!(stderr.contains("exceeds the 200.0 MiB limit"), "{stderr}");
+    assert!(stderr.contains("--profile dev-fast"), "{stderr}");
+    assert!(
+        stderr.contains("--profile-override fri_blowup=8"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("rows_hint <= 32768"), "{stderr}");
+    assert!(!proof.exists());
+
+    let out = prove("1");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(!stderr.contains("--profile dev-fast"), "{stderr}");
+    assert!(stderr.contains("rows_hint <= 128"), "{stderr}");
+
+    assert!(prove("1024").status.success());
+    assert!(proof.exists());
+}
+
+#[test]
+fn verify_stats_report_phase_timings() {
+    let dir = tempdir().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    let proof = dir.path().join("p.proof");
+    write(&inputs, r#"{"n":7}"#);
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    let status = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs.to_str().unwrap()])
+        .args(["-o", proof.to_str().unwrap()])
+        .args(common)
+        .status()
+        .expect("run prove");
+    assert!(status.success());
+    let verify = |extra: &[&str]| {
+        Command::new(BIN)
+            .args(["verify", "-p", &air, "-i", inputs.to_str().unwrap()])
+            .args(["-P", proof.to_str().unwrap()])
+            .args(common)
+            .args(extra)
+            .output()
+            .expect("run verify")
+    };
+
+    let out = verify(&["--stats", "--stats-abi", "--json"]);
+    assert!(out.status.success(), "{:?}", out);
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    let timings = v["stats"]["timing_us"].as_object().expect("timings");
+    for phase in [
+        "config_validation",
+        "read",
+        "header_decode",
+        "program_parse",
+        "backend_verify",
+        "digest",
+        "abi_encode",
+        "total",
+    ] {
+        assert!(timings[phase].is_u64(), "{phase}: {timings:?}");
+    }
+    let sum: u64 = timings
+        .iter()
+        .filter(|(k, _)| *k != "total")
+        .map(|(_, v)| v.as_u64().unwrap())
+        .sum();
+    assert!(sum <= timings["total"].as_u64().unwrap());
+
+    let out = verify(&["--stats"]);
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let line = stdout
+        .lines()
+        .find(|l| l.starts_with("stats timing_us "))
+        .expect("timing line");
+    assert!(line.contains(" backend_verify=") && line.contains(" digest="));
+    assert!(!line.contains("abi_encode"), "{line}");
+
+    // --stats-abi only makes sense alongside --stats
+    assert_eq!(verify(&["--stats-abi"]).status.code(), Some(2));
+}
+
+#[test]
+fn triage_localizes_tampered_regions() {
+    let dir = tempdir().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    let proof = dir.path().join("p.proof");
+    write(&inputs, r#"{"n":7}"#);
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    let status = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs.to_str().unwrap()])
+        .args(["-o", proof.to_str().unwrap()])
+        .args(common)
+        .status()
+        .expect("run prove");
+    assert!(status.success());
+    let bytes = fs::read(&proof).unwrap();
+    let triage = |bytes: &[u8]| {
+        let path = dir.path().join("t.proof");
+        fs::write(&path, bytes).unwrap();
+        let out = Command::new(BIN)
+            .args(["triage", "-p", &air, "-i", inputs.to_str().unwrap()])
+            .args(["-P", path.to_str().unwrap(), "--json"])
+            .args(common)
+            .output()
+            .expect("run triage");
+        let v: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+        let bad: Vec<String> = v["regions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|r| r["status"] != "ok")
+            .map(|r| r["region"].as_str().unwrap().to_string())
+            .collect();
+        (out.status.code(), bad, v)
+    };
+
+    let (code, bad, v) = triage(&bytes);
+    assert_eq!(code, Some(0));
+    assert!(bad.is_empty(), "{bad:?}");
+    assert_eq!(v["consistent"], true);
+
+    // Several regions at once: all are reported, not just the first.
+    let mut tampered = bytes.clone();
+    tampered[0] ^= 0xff; // magic
+    tampered[33] ^= 0x01; // pubio_hash
+    tampered[HEADER_LEN] ^= 0x01; // body
+    let (code, bad, v) = triage(&tampered);
+    assert_eq!(code, Some(4));
+    assert_eq!(bad, ["magic", "pubio_hash", "body"]);
+    assert_eq!(v["regions"][0]["expected"], "0x50524f46");
+
+    // A header field alone leaves the body consistent.
+    let mut tampered = bytes.clone();
+    tampered[50] ^= 0x80;
+    assert_eq!(triage(&tampered).1, ["config_hash"]);
+
+    // An unknown critical flag is reported on its own.
+    let mut tampered = bytes.clone();
+    tampered[12] ^= 0x01;
+    assert_eq!(triage(&tampered).1, ["flags"]);
+
+    // Truncation shows up as body_len plus an unverifiable body.
+    let (code, bad, v) = triage(&bytes[..bytes.len() - 1]);
+    assert_eq!(code, Some(4));
+    assert_eq!(bad, ["body_len", "body"]);
+    assert_eq!(v["regions"][8]["region"], "body_len");
+    assert_eq!(v["regions"][8]["actual"], "8");
+
+    let (_, bad, _) = triage(&bytes[..20]);
+    assert!(bad.contains(&"body".to_string()) && !bad.contains(&"magic".to_string()));
+}
+
+#[test]
+fn ministark_proves_goldilocks_trace_end_to_end() {
+    let dir = tempdir().unwrap();
+    let fixtures =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../backends/ministark/air");
+    let path = |name: &str| fixtures.join(name).to_str().unwrap().to_owned();
+    let trace = dir.path().join("merkle.zkt");
+    let proof = dir.path().join("merkle.proof");
+    let import = Command::new(BIN)
+        .args(["trace-import", "-i", &path("merkle_path.trace.json")])
+        .args(["-o", trace.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(import.success());
+
+    let common = [
+        "-p",
+        &path("merkle_path.air"),
+        "-i",
+        &path("merkle_path.inputs.json"),
+        "--backend",
+        "ministark@0.1",
+        "--field",
+        "Goldilocks",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "dev-fast",
+    ];
+    let native = dir.path().join("merkle.stark");
+    let out = Command::new(BIN)
+        .arg("prove")
+        .args(common)
+        .args(["-o", proof.to_str().unwrap()])
+        .args(["--trace", trace.to_str().unwrap()])
+        .args(["--emit-native-proof", native.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{:?}", out);
+
+    // The native proof is the body without the zkd header, in the
+    // backend's own encoding.
+    let native = fs::read(&native).unwrap();
+    assert_eq!(native, fs::read(&proof).unwrap()[HEADER_LEN..]);
+    let stark = zkprov_backend_ministark::StarkProof::from_bytes(&native).unwrap();
+    assert_eq!(stark.to_bytes(), native);
+
+    let verify = |proof: &std::path::Path| {
+        Command::new(BIN)
+            .arg("verify")
+            .args(common)
+            .args(["-P", proof.to_str().unwrap()])
+            .output()
+            .unwrap()
+    };
+    let out = verify(&proof);
+    assert!(out.status.success(), "{:?}", out);
+
+    // Without a trace there is nothing to prove.
+    let out = Command::new(BIN)
+        .arg("prove")
+        .args(common)
+        .args(["-o", dir.path().join("x.proof").to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("pass --trace"));
+
+    // A flipped bit in the last FRI opening fails verification.
+    let mut bytes = fs::read(&proof).unwrap();
+    *bytes.last_mut().unwrap() ^= 1;
+    let bad = dir.path().join("bad.proof");
+    fs::write(&bad, bytes).unwrap();
+    let out = verify(&bad);
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("FRI layer"),
+        "{:?}",
+        out
+    );
+}
+
+#[test]
+fn json_pubio_binds_declared_inputs_only() {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let demo = base.join("../../examples/air/commit_demo.air");
+    let demo = demo.to_str().unwrap();
+    let dir = tempdir().unwrap();
+    let proof = dir.path().join("demo.proof");
+    let proof = proof.to_str().unwrap();
+    let run = |cmd: &str, json: &str, policy: &str| {
+        let inputs = dir.path().join(format!("{cmd}-{policy}.json"));
+        write(&inputs, json);
+        let io = if cmd == "prove" { "-o" } else { "-P" };
+        Command::new(BIN)
+            .args([cmd, "-p", demo, "-i", inputs.to_str().unwrap(), io, proof])
+            .args(["--backend", "native@0.0", "--field", "Prime254", "--hash"])
+            .args(["blake3", "--fri-arity", "2", "--profile", "balanced"])
+            .args(["--undeclared-inputs", policy])
+            .output()
+            .unwrap()
+    };
+    let out = run(
+        "prove",
+        r#"{"x":1,"y":2,"acc":"0xbeef","digest":7}"#,
+        "reject",
+    );
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    // Reordered, respelled and with an extra key: same declared inputs.
+    let noisy = r#"{"digest":"7","note":"unbound","acc":"beef","y":"0x2","x":1}"#;
+    assert!(run("verify", noisy, "ignore").status.success());
+    let out = run("verify", noisy, "reject");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("undeclared public inputs: note"),
+        "{stderr}"
+    );
+
+    let other = r#"{"x":1,"y":2,"acc":"0xbeef","digest":8}"#;
+    assert!(!run("verify", other, "ignore").status.success());
+}
+
+#[test]
+fn air_diff_reports_severity_and_fails_on_breaking() {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let demo = base.join("../../examples/air/commit_demo.air");
+    let demo = demo.to_str().unwrap();
+    let tmp = tempdir().unwrap();
+    let changed = tmp.path().join("changed.air");
+    let src = fs::read_to_string(demo).unwrap();
+    fs::write(
+        &changed,
+        src.replace("boundary_count = 1", "boundary_count = 2"),
+    )
+    .unwrap();
+    let changed = changed.to_str().unwrap();
+
+    let out = Command::new(BIN)
+        .args(["air-diff", demo, changed, "--fail-on-breaking"])
+        .output()
+        .expect("run air-diff");
+    assert!(out.status.success());
+    let text = String::from_utf8(out.stdout).unwrap();
+    assert!(text.contains("cosmetic  constraints.boundary_count: 1 -> 2"));
+
+    fs::write(
+        tmp.path().join("changed.air"),
+        src.replace("keccak_commit = { public = [\"digest\"] }", ""),
+    )
+    .unwrap();
+    let out = Command::new(BIN)
+        .args(["air-diff", demo, changed, "--json", "--fail-on-breaking"])
+        .output()
+        .expect("run air-diff");
+    assert_eq!(out.status.code(), Some(1));
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).expect("json");
+    assert_eq!(v["breaking"], true);
+    assert_eq!(v["changes"][0]["path"], "commitments");
+    assert_eq!(v["changes"][0]["before"], "keccak_commit[digest]");
+}
+
+#[test]
+fn vectors_print_the_checked_in_test_vectors() {
+    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let checked_in = fs::read_to_string(base.join("../../examples/vectors/crypto.json")).unwrap();
+    let out = Command::new(BIN)
+        .arg("vectors")
+        .output()
+        .expect("run vectors");
+    assert!(out.status.success(), "{:?}", out);
+    let printed: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        printed,
+        serde_json::from_str::<serde_json::Value>(&checked_in).unwrap()
+    );
+
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("vectors.json");
+    let out = Command::new(BIN)
+        .args(["vectors", "--pretty", "-o", path.to_str().unwrap()])
+        .output()
+        .expect("run vectors -o");
+    assert!(out.status.success(), "{:?}", out);
+    assert_eq!(fs::read_to_string(&path).unwrap(), checked_in);
+}
+
+#[test]
+fn rows_hint_expressions_are_evaluated_into_stats_and_manifest() {
+    let dir = tempdir().unwrap();
+    let air = dir.path().join("leaves.air");
+    write(
+        &air,
+        r#"rows_hint = "next_pow2(len(leaves) * 64)"
+
+[meta]
+name = "leaves"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+
+[[public_inputs]]
+name = "leaves"
+type = "bytes"
+"#,
+    );
+    let air = air.to_str().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    write(&inputs, r#"{"leaves":"0x010203"}"#);
+    let inputs = inputs.to_str().unwrap();
+    let proof = dir.path().join("leaves.proof");
+    let proof = proof.to_str().unwrap();
+    let run = |cmd: &str| {
+        let io = if cmd == "prove" { "-o" } else { "-P" };
+        Command::new(BIN)
+            .args([cmd, "-p", air, "-i", inputs, io, proof, "--stats"])
+            .args(["--backend", "native@0.0", "--field", "Prime254", "--hash"])
+            .args(["blake3", "--fri-arity", "2", "--profile", "balanced"])
+            .output()
+            .unwrap()
+    };
+
+    let out = run("prove");
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("stats rows=256 cols=2"), "{stdout}");
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(format!("{proof}.manifest.json")).unwrap())
+            .unwrap();
+    assert_eq!(manifest["determinism_vector"]["rows"], 256);
+
+    let out = run("verify");
+    assert!(out.status.success(), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("stats rows=256 cols=2"));
+}
+
+#[test]
+fn verify_policy_accepts_or_lists_violated_rules() {
+    let air = air_path();
+    let air = air.as_str();
+    let program_hash = zkprov_corelib::evm::typed::program_hash(&fs::read(air).unwrap());
+    let dir = tempdir().unwrap();
+    let inputs = dir.path().join("inputs.json");
+    write(&inputs, r#"{"demo":true,"n":7}"#);
+    let inputs = inputs.to_str().unwrap();
+    let proof = dir.path().join("toy.proof");
+    let proof = proof.to_str().unwrap();
+    let run = |args: &[&str]| {
+        Command::new(BIN)
+            .args(args)
+            .args(["-p", air, "-i", inputs])
+            .args(["--backend", "native@0.0", "--field", "Prime254", "--hash"])
+            .args(["blake3", "--fri-arity", "2", "--profile", "balanced"])
+            .output()
+            .unwrap()
+    };
+    assert!(run(&["prove", "-o", proof]).status.success());
+
+    let policy = dir.path().join("rp.toml");
+    write(
+        &policy,
+        &format!(
+            "programs = [\"0x{}\"]\nbackends = [\"native\"]\nmin_lambda_bits = 100\n",
+            program_hash
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        ),
+    );
+    let policy = policy.to_str().unwrap();
+    let out = run(&["verify", "-P", proof, "--policy", policy]);
+    assert!(out.status.success(), "{:?}", out);
+
+    let strict_path = dir.path().join("strict.toml");
+    write(
+        &strict_path,
+        "min_lambda_bits = 128\nmax_proof_age = \"1d\"\n",
+    );
+    let strict = strict_path.to_str().unwrap();
+    let out = run(&["verify", "-P", proof, "--policy", strict, "--json"]);
+    assert_eq!(out.status.code(), Some(4));
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["diagnosis"]["category"], "policy");
+    let rules: Vec<&str> = v["diagnosis"]["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["rule"].as_str().unwrap())
+        .collect();
+    assert_eq!(rules, ["min_lambda_bits", "max_proof_age"]);
+    assert!(v["diagnosis"]["violations"][1]["reason"]
+        .as_str()
+        .unwrap()
+        .contains("no timestamp token"));
+
+    write(&strict_path, "max_age = \"1d\"\n");
+    let out = run(&["verify", "-P", proof, "--policy", strict]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("unknown field"));
 }
@@ -0,0 +1,90 @@
+use std::fs;
+use std::process::{Command, Output};
+use tempfile::tempdir;
+use zkprov_corelib::trace::{Trace, TraceCompression};
+
+const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+const AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
+
+fn prove(dir: &std::path::Path, out: &str, extra: &[&str], fault: Option<&str>) -> Output {
+    let inputs = dir.join("inputs.json");
+    fs::write(&inputs, r#"{"a":1}"#).unwrap();
+    let mut cmd = Command::new(BIN);
+    cmd.args(["prove", "-p", AIR, "-i", inputs.to_str().unwrap()])
+        .args(["-o", dir.join(out).to_str().unwrap()])
+        .args([
+            "--backend",
+            "native@0.0",
+            "--field",
+            "Prime254",
+            "--hash",
+            "blake3",
+            "--fri-arity",
+            "2",
+            "--profile",
+            "balanced",
+        ])
+        .args(extra);
+    if let Some(f) = fault {
+        cmd.env("ZKD_ISOLATE_FAULT", f);
+    }
+    cmd.output().expect("run prove")
+}
+
+fn failure_kind(out: &Output) -> String {
+    assert_eq!(out.status.code(), Some(5), "{:?}", out);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let line = stderr
+        .lines()
+        .find_map(|l| l.strip_prefix("❌ IsolatedProveFailed "))
+        .expect("structured failure line");
+    let v: serde_json::Value = serde_json::from_str(line).expect("failure json");
+    v["kind"].as_str().unwrap().to_owned()
+}
+
+#[test]
+fn isolated_prove_matches_in_process_prove() {
+    let tmp = tempdir().unwrap();
+    assert!(prove(tmp.path(), "plain.proof", &[], None).status.success());
+    let out = prove(tmp.path(), "isolated.proof", &["--isolate"], None);
+    assert!(out.status.success(), "{:?}", out);
+    assert_eq!(
+        fs::read(tmp.path().join("plain.proof")).unwrap(),
+        fs::read(tmp.path().join("isolated.proof")).unwrap()
+    );
+}
+
+#[test]
+fn isolated_worker_crashes_become_structured_errors() {
+    let tmp = tempdir().unwrap();
+    let panic = prove(tmp.path(), "p.proof", &["--isolate"], Some("panic"));
+    assert_eq!(failure_kind(&panic), "panic");
+
+    let abort = prove(tmp.path(), "a.proof", &["--isolate"], Some("abort"));
+    assert_eq!(failure_kind(&abort), "signal");
+
+    let hang = prove(
+        tmp.path(),
+        "h.proof",
+        &["--isolate", "--isolate-timeout-secs", "1"],
+        Some("hang"),
+    );
+    assert_eq!(failure_kind(&hang), "timeout");
+    assert!(!tmp.path().join("h.proof").exists());
+}
+
+#[test]
+fn isolated_prover_error_is_reported() {
+    let tmp = tempdir().unwrap();
+    // Two columns against a four-column AIR: the worker runs but the prover rejects it.
+    let trace = Trace::from_u64_columns("Prime254", &[vec![1; 8], vec![2; 8]]).unwrap();
+    let trace_path = tmp.path().join("narrow.zkt");
+    fs::write(&trace_path, trace.encode(TraceCompression::None).unwrap()).unwrap();
+    let out = prove(
+        tmp.path(),
+        "t.proof",
+        &["--isolate", "--trace", trace_path.to_str().unwrap()],
+        None,
+    );
+    assert_eq!(failure_kind(&out), "prover");
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::tempdir;
+
+use common::{BIN, CFG};
+
+fn example_dir() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/pkg/toy_merkle")
+}
+
+fn zkd(pkg_dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(BIN)
+        .args(args)
+        .env("ZKD_PKG_DIR", pkg_dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn built_package_installs_and_proves_by_name() {
+    let tmp = tempdir().unwrap();
+    let pkg_dir = tmp.path().join("packages");
+    let package = tmp.path().join("toy_merkle.zkpkg");
+    let manifest = example_dir().join("zkpkg.toml");
+
+    let out = zkd(
+        &pkg_dir,
+        &[
+            "pkg",
+            "build",
+            manifest.to_str().unwrap(),
+            "-o",
+            package.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("PASS golden default@native@0.0/balanced"),
+        "{stdout}"
+    );
+
+    let out = zkd(&pkg_dir, &["pkg", "install", package.to_str().unwrap()]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let out = zkd(&pkg_dir, &["pkg", "list"]);
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("toy_merkle 0.1.0 "));
+
+    let inputs = pkg_dir.join("toy_merkle/inputs/default.json");
+    let proof = tmp.path().join("toy.proof");
+    let (inputs, proof) = (inputs.to_str().unwrap(), proof.to_str().unwrap());
+    let mut args = vec!["prove", "-p", "pkg:toy_merkle", "-i", inputs, "-o", proof];
+    args.extend(CFG);
+    let out = zkd(&pkg_dir, &args);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let mut args = vec!["verify", "-p", "pkg:toy_merkle", "-i", inputs, "-P", proof];
+    args.extend(CFG);
+    assert!(zkd(&pkg_dir, &args).status.success());
+
+    let mut args = vec!["verify", "-p", "pkg:missing", "-i", inputs, "-P", proof];
+    args.extend(CFG);
+    let out = zkd(&pkg_dir, &args);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("'missing' is not installed"));
+}
+
+#[test]
+fn build_fails_on_golden_digest_mismatch() {
+    let tmp = tempdir().unwrap();
+    let src = fs::read_to_string(example_dir().join("zkpkg.toml")).unwrap();
+    let air = example_dir().join("../../air/toy.air");
+    let inputs = example_dir().join("inputs.json");
+    let manifest = tmp.path().join("zkpkg.toml");
+    fs::write(
+        &manifest,
+        src.replace("../../air/toy.air", air.to_str().unwrap())
+            .replace(
+                "\"inputs.json\"",
+                &format!("{:?}", inputs.to_str().unwrap()),
+            )
+            .replace("0xdb1b", "0xdc1b"),
+    )
+    .unwrap();
+    let package = tmp.path().join("toy_merkle.zkpkg");
+    let out = zkd(
+        tmp.path(),
+        &[
+            "pkg",
+            "build",
+            manifest.to_str().unwrap(),
+            "-o",
+            package.to_str().unwrap(),
+        ],
+    );
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("golden digest default@native@0.0/balanced mismatch"),
+        "{stderr}"
+    );
+    assert!(!package.exists());
+}
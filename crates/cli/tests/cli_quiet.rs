@@ -0,0 +1,131 @@
+mod common;
+
+use std::fs;
+use std::process::{Command, Output};
+
+use tempfile::tempdir;
+
+use common::{toy_args, zkd_toy, BIN};
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8(out.stdout.clone()).expect("utf8 stdout")
+}
+
+#[test]
+fn quiet_prove_and_verify_print_one_line() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+
+    let proved = zkd_toy("prove", dir.path(), &["--quiet"]);
+    assert!(proved.status.success(), "{:?}", proved);
+    let digest = stdout(&proved);
+    let manifest: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(dir.path().join("toy.proof.manifest.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        digest,
+        format!("{}\n", manifest["digest"].as_str().unwrap())
+    );
+
+    // The flag is global: it may come before the subcommand.
+    let verified = Command::new(BIN)
+        .arg("-q")
+        .args(toy_args("verify", dir.path(), &[]))
+        .output()
+        .unwrap();
+    assert!(verified.status.success(), "{:?}", verified);
+    assert_eq!(stdout(&verified), "ok\n");
+
+    // Without --quiet the status lines are back and there is no result line.
+    let loud = zkd_toy("verify", dir.path(), &[]);
+    assert!(loud.status.success());
+    assert!(stdout(&loud).starts_with("✅ ProofVerified"));
+    assert!(!stdout(&loud).lines().any(|l| l == "ok"));
+}
+
+#[test]
+fn quiet_verify_failures_print_fail_and_keep_diagnostics_on_stderr() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+    assert!(zkd_toy("prove", dir.path(), &["-q"]).status.success());
+
+    let proof_path = dir.path().join("toy.proof");
+    let mut proof = fs::read(&proof_path).unwrap();
+    let last = proof.len() - 1;
+    proof[last] ^= 1;
+    fs::write(&proof_path, &proof).unwrap();
+    let tampered = zkd_toy("verify", dir.path(), &["-q"]);
+    assert_eq!(tampered.status.code(), Some(4));
+    assert_eq!(stdout(&tampered), "fail\n");
+    assert!(!tampered.stderr.is_empty());
+
+    // Errors returned before verification starts also print `fail`.
+    fs::remove_file(&proof_path).unwrap();
+    let missing = zkd_toy("verify", dir.path(), &["-q"]);
+    assert!(!missing.status.success());
+    assert_eq!(stdout(&missing), "fail\n");
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("Error"));
+}
+
+#[test]
+fn quiet_json_verify_prints_only_the_envelope() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("inputs.json"), r#"{"a":1}"#).unwrap();
+    assert!(zkd_toy("prove", dir.path(), &["-q"]).status.success());
+    let out = zkd_toy("verify", dir.path(), &["-q", "--json"]);
+    assert!(out.status.success(), "{:?}", out);
+    let text = stdout(&out);
+    assert_eq!(text.lines().count(), 1, "{text}");
+    let envelope: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(envelope["verified"], true);
+}
+
+#[test]
+fn quiet_commit_prints_only_the_hex() {
+    let commit = |quiet: bool| {
+        let mut cmd = Command::new(BIN);
+        cmd.args([
+            "commit",
+            "--hash",
+            "blake3",
+            "--msg-hex",
+            "0102",
+            "--blind-hex",
+            "0304",
+        ]);
+        if quiet {
+            cmd.arg("--quiet");
+        }
+        cmd.output().unwrap()
+    };
+    let quiet = commit(true);
+    assert!(quiet.status.success(), "{:?}", quiet);
+    let hex = stdout(&quiet);
+    assert_eq!(hex, stdout(&commit(false)));
+    assert_eq!(hex.trim_end().len(), 64, "{hex}");
+
+    let open = |commit_hex: &str| {
+        Command::new(BIN)
+            .args([
+                "open-commit",
+                "--hash",
+                "blake3",
+                "--msg-hex",
+                "0102",
+                "--blind-hex",
+                "0304",
+                "--commit-hex",
+                commit_hex,
+                "-q",
+            ])
+            .output()
+            .unwrap()
+    };
+    let opened = open(hex.trim_end());
+    assert!(opened.status.success());
+    assert_eq!(stdout(&opened), "ok\n");
+    let wrong = open(&"00".repeat(32));
+    assert_eq!(wrong.status.code(), Some(1));
+    assert_eq!(stdout(&wrong), "fail\n");
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use std::fs;
+use tempfile::tempdir;
+
+use common::{air_path, zkd};
+
+fn cfg(profile: &str) -> Vec<&str> {
+    vec![
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        profile,
+    ]
+}
+
+#[test]
+fn verify_quorum_reports_each_proof() {
+    let dir = tempdir().unwrap();
+    let path = |name: &str| dir.path().join(name).to_str().unwrap().to_owned();
+    let (inputs, a, b, c) = (path("inputs.json"), path("a"), path("b"), path("c"));
+    fs::write(&inputs, r#"{"demo":true,"n":7}"#).unwrap();
+    let air = air_path();
+    for (out, profile) in [(&a, "balanced"), (&b, "dev-fast"), (&c, "balanced")] {
+        let prove = [
+            &["prove", "-p", &air, "-i", &inputs, "-o", out][..],
+            &cfg(profile),
+        ]
+        .concat();
+        assert!(zkd(&prove).status.success());
+    }
+    let mut corrupt = fs::read(&c).unwrap();
+    let last = corrupt.len() - 1;
+    corrupt[last] ^= 0xff;
+    fs::write(&c, corrupt).unwrap();
+
+    let verify = |quorum: &str, extra: &[&str]| {
+        let args = [
+            &["verify", "-p", &air, "-i", &inputs][..],
+            &["-P", &a, "-P", &b, "-P", &c, "--quorum", quorum],
+            extra,
+            &cfg("balanced"),
+        ]
+        .concat();
+        zkd(&args)
+    };
+    let out = verify("2", &[]);
+    assert!(out.status.success(), "{:?}", out);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("native@0.0/dev-fast"), "{stdout}");
+    assert!(stdout.contains("rejected "), "{stdout}");
+    assert!(
+        stdout.contains("✅ QuorumReached accepted=2/3 threshold=2"),
+        "{stdout}"
+    );
+
+    let out = verify("3", &["--json"]);
+    assert_eq!(out.status.code(), Some(4));
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["reached"], false);
+    assert_eq!(report["accepted"], 2);
+    assert_eq!(report["entries"][2]["status"], "rejected");
+    assert!(report["entries"][0]["digest"]
+        .as_str()
+        .unwrap()
+        .starts_with("0x"));
+
+    // Several proofs need --quorum, and --quorum excludes single-proof checks.
+    let args = [
+        &["verify", "-p", &air, "-i", &inputs, "-P", &a, "-P", &b][..],
+        &cfg("balanced"),
+    ]
+    .concat();
+    let out = zkd(&args);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("need --quorum"));
+    assert_eq!(verify("2", &["--stats"]).status.code(), Some(2));
+}
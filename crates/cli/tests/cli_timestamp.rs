@@ -0,0 +1,238 @@
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Arc;
+use std::thread;
+use tempfile::{tempdir, TempDir};
+
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::proof::{ProofHeader, HEADER_LEN};
+use zkprov_corelib::timestamp::imprint;
+
+use common::{air_path, BIN, CFG};
+
+/// A throwaway RFC 3161 authority run with `openssl ts -reply`: a CA and a
+/// TSA certificate (`timeStamping` usage) it signs. The first token it issues
+/// has serial 07.
+struct TestTsa {
+    dir: TempDir,
+}
+
+impl TestTsa {
+    fn new() -> Self {
+        let tsa = Self {
+            dir: tempdir().unwrap(),
+        };
+        let ec = "-newkey ec -pkeyopt ec_paramgen_curve:P-256 -nodes";
+        tsa.openssl(
+            &format!(
+                "req -x509 {ec} -keyout ca.key -out ca.pem -days 2 \
+                 -addext basicConstraints=critical,CA:TRUE -addext keyUsage=critical,keyCertSign"
+            ),
+            &["-subj", "/CN=zkd test TSA CA"],
+        );
+        tsa.openssl(
+            &format!("req -new {ec} -keyout tsa.key -out tsa.csr"),
+            &["-subj", "/CN=zkd test TSA"],
+        );
+        fs::write(
+            tsa.path("ext.cnf"),
+            "extendedKeyUsage=critical,timeStamping\n",
+        )
+        .unwrap();
+        tsa.openssl(
+            "x509 -req -in tsa.csr -CA ca.pem -CAkey ca.key -set_serial 1 -days 2 \
+             -extfile ext.cnf -out tsa.pem",
+            &[],
+        );
+        fs::write(tsa.path("serial"), "06\n").unwrap();
+        fs::write(
+            tsa.path("ts.cnf"),
+            "[tsa]\ndefault_tsa = tsa_config\n[tsa_config]\nserial = serial\n\
+             signer_cert = tsa.pem\nsigner_key = tsa.key\nsigner_digest = sha256\n\
+             default_policy = 1.2.3.4.1\ndigests = sha256\ness_cert_id_alg = sha256\n\
+             tsa_name = no\ness_cert_id_chain = no\n",
+        )
+        .unwrap();
+        tsa
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.path().join(name)
+    }
+
+    fn ca(&self) -> String {
+        self.path("ca.pem").to_str().unwrap().to_owned()
+    }
+
+    /// Run `openssl` in the TSA directory with whitespace-separated `args`
+    /// followed by `extra`.
+    fn openssl(&self, args: &str, extra: &[&str]) {
+        let out = Command::new("openssl")
+            .args(args.split_whitespace())
+            .args(extra)
+            .current_dir(self.dir.path())
+            .output()
+            .unwrap();
+        assert!(
+            out.status.success(),
+            "openssl {args}: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+
+    /// Signed `TimeStampResp` for a DER `TimeStampReq`.
+    fn reply(&self, req: &[u8]) -> Vec<u8> {
+        fs::write(self.path("req.tsq"), req).unwrap();
+        self.openssl(
+            "ts -reply -config ts.cnf -queryfile req.tsq -out resp.tsr",
+            &[],
+        );
+        fs::read(self.path("resp.tsr")).unwrap()
+    }
+}
+
+/// One-shot HTTP TSA on localhost; returns its URL and the received body.
+fn serve_tsa(tsa: Arc<TestTsa>) -> (String, thread::JoinHandle<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/tsa", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut len = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                len = v.trim().parse().unwrap();
+            }
+            if line == "\r\n" {
+                break;
+            }
+        }
+        let mut body = vec![0u8; len];
+        reader.read_exact(&mut body).unwrap();
+        let reply = tsa.reply(&body);
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/timestamp-reply\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            reply.len()
+        )
+        .unwrap();
+        stream.write_all(&reply).unwrap();
+        body
+    });
+    (url, handle)
+}
+
+fn zkd(args: &[&str]) -> std::process::Output {
+    Command::new(BIN)
+        .args(args)
+        .args(CFG)
+        .env("NO_PROXY", "*")
+        .env("no_proxy", "*")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn prove_timestamps_digest_and_verify_checks_token() {
+    for tool in ["curl", "openssl"] {
+        if Command::new(tool).arg("version").output().is_err() {
+            eprintln!("{tool} not available; skipping");
+            return;
+        }
+    }
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    fs::write(&inputs, r#"{"a":1}"#).unwrap();
+    let proof = tmp.path().join("toy.proof");
+    let token = tmp.path().join("toy.proof.tsr");
+    let air = air_path();
+    let (inputs, proof_s) = (inputs.to_str().unwrap(), proof.to_str().unwrap());
+
+    let issuer = Arc::new(TestTsa::new());
+    let ca = issuer.ca();
+    let (url, tsa) = serve_tsa(issuer.clone());
+    let out = zkd(&[
+        "prove",
+        "-p",
+        &air,
+        "-i",
+        inputs,
+        "-o",
+        proof_s,
+        "--timestamp-url",
+        &url,
+    ]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("serial=07"), "{stdout}");
+
+    // Only SHA-256(D) reached the TSA, never the proof.
+    let bytes = fs::read(&proof).unwrap();
+    let header = ProofHeader::decode(&bytes[..HEADER_LEN]).unwrap();
+    let sent = tsa.join().unwrap();
+    assert!(sent.len() < 80, "request is {} bytes", sent.len());
+    assert_eq!(
+        &sent[24..56],
+        &imprint(&digest_D(&header, &bytes[HEADER_LEN..]))
+    );
+    assert!(token.exists());
+
+    let verify = |extra: &[&str]| {
+        let mut args = vec!["verify", "-p", &air, "-i", inputs, "-P", proof_s];
+        args.extend_from_slice(extra);
+        zkd(&args)
+    };
+    let out = verify(&["--check-timestamp", "--tsa-ca", &ca, "--json"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stdout)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["timestamp"]["serial"], "07");
+
+    // Without trusted certificates there is nothing to check the token against.
+    let out = verify(&["--check-timestamp"]);
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--tsa-ca"));
+
+    // A well-formed token from a TSA outside --tsa-ca is not trusted.
+    let stranger = TestTsa::new();
+    let out = verify(&["--check-timestamp", "--tsa-ca", &stranger.ca()]);
+    assert_eq!(out.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("TimestampInvalid"), "{stderr}");
+    assert!(stderr.contains("not trusted"), "{stderr}");
+
+    // A token for a different digest is rejected.
+    let mut tampered = fs::read(&token).unwrap();
+    let at = tampered.len() - 40;
+    tampered[at] ^= 1;
+    fs::write(&token, &tampered).unwrap();
+    let out = verify(&["--check-timestamp", "--tsa-ca", &ca]);
+    assert_eq!(out.status.code(), Some(4));
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("TimestampInvalid"),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    fs::remove_file(&token).unwrap();
+    let out = verify(&["--check-timestamp", "--tsa-ca", &ca, "--json"]);
+    assert_eq!(out.status.code(), Some(4));
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(report["diagnosis"]["category"], "timestamp");
+    assert!(verify(&[]).status.success());
+}
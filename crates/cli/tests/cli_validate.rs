@@ -1,18 +1,12 @@
+mod common;
+
 use std::fs;
 use std::process::Command;
 
 use tempfile::tempdir;
-use zkprov_corelib::validation::ValidationReport;
-
-const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+use zkprov_corelib::validation::{ValidationErrorCode, ValidationReport};
 
-fn air_path() -> String {
-    let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
-    base.join("../../examples/air/toy.air")
-        .to_str()
-        .expect("utf8 path")
-        .to_owned()
-}
+use common::{air_path, BIN};
 
 #[test]
 fn validate_emits_report_with_commit_status() {
@@ -85,8 +79,196 @@ fn validate_emits_report_with_commit_status() {
 
     assert!(report.ok, "report must indicate success");
     assert!(report.commit_passed, "commitment checks must pass");
+    assert_eq!(report.checks[0].name, "verify");
+    assert!(
+        report.checks.iter().all(|c| c.passed),
+        "{:?}",
+        report.checks
+    );
     assert_eq!(report.meta.backend_id, "native@0.0");
     assert_eq!(report.meta.profile_id, "balanced");
     assert_eq!(report.meta.hash_id, "blake3");
     assert_eq!(report.meta.curve.as_deref(), Some("placeholder"));
 }
+
+#[test]
+fn validate_reports_header_binding_mismatches() {
+    let dir = tempdir().unwrap();
+    let inputs_path = dir.path().join("inputs.json");
+    let other_inputs = dir.path().join("other.json");
+    let proof_path = dir.path().join("toy.proof");
+    let reports_dir = dir.path().join("reports");
+    fs::write(&inputs_path, r#"{"n":5}"#).unwrap();
+    fs::write(&other_inputs, r#"{"n":6}"#).unwrap();
+
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+    ];
+    let prove = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-o", proof_path.to_str().unwrap()])
+        .args(common)
+        .args(["--profile", "balanced"])
+        .status()
+        .unwrap();
+    assert!(prove.success());
+
+    let out = Command::new(BIN)
+        .args(["validate", "-p", &air, "-i", other_inputs.to_str().unwrap()])
+        .args(["-P", proof_path.to_str().unwrap()])
+        .args(["-o", reports_dir.to_str().unwrap()])
+        .args(common)
+        .args(["--profile", "dev-fast"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(1), "{:?}", out);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    for code in ["ProfileIdMismatch", "PubIoMismatch", "ConfigHashMismatch"] {
+        assert!(stderr.contains(code), "{stderr}");
+    }
+
+    let report = fs::read_dir(&reports_dir).unwrap().next().unwrap().unwrap();
+    let report: ValidationReport =
+        serde_json::from_str(&fs::read_to_string(report.path()).unwrap()).unwrap();
+    assert!(!report.ok);
+    assert!(
+        report.commit_passed,
+        "binding errors are not commitment errors"
+    );
+    // Verification runs first and rejects the proof on its first binding.
+    assert_eq!(report.errors[0].code, ValidationErrorCode::ProofRejected);
+    assert_eq!(report.errors[0].context["category"], "header_binding");
+    assert_eq!(report.errors[1].context["field"], "profile_id_hash");
+}
+
+#[test]
+fn validate_rejects_a_proof_that_does_not_verify() {
+    let dir = tempdir().unwrap();
+    let inputs_path = dir.path().join("inputs.json");
+    let proof_path = dir.path().join("toy.proof");
+    let reports_dir = dir.path().join("reports");
+    fs::write(&inputs_path, r#"{"n":5}"#).unwrap();
+
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    let prove = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-o", proof_path.to_str().unwrap()])
+        .args(common)
+        .status()
+        .unwrap();
+    assert!(prove.success());
+
+    // Header intact, body damaged: only backend verification notices.
+    let mut proof = fs::read(&proof_path).unwrap();
+    let last = proof.len() - 1;
+    proof[last] ^= 0x01;
+    fs::write(&proof_path, proof).unwrap();
+
+    let out = Command::new(BIN)
+        .args(["validate", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-P", proof_path.to_str().unwrap()])
+        .args(["-o", reports_dir.to_str().unwrap()])
+        .args(common)
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(1), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("ok=false verified=false"));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("ProofRejected"));
+
+    let report = fs::read_dir(&reports_dir).unwrap().next().unwrap().unwrap();
+    let report: ValidationReport =
+        serde_json::from_str(&fs::read_to_string(report.path()).unwrap()).unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+    assert_eq!(report.errors[0].context["code"], "proof_rejected");
+    let verify = &report.checks[0];
+    assert_eq!((verify.name.as_str(), verify.passed), ("verify", false));
+    assert!(report.checks[1..].iter().all(|c| c.passed));
+}
+
+#[test]
+fn validate_refuses_a_known_blind() {
+    let dir = tempdir().unwrap();
+    let inputs_path = dir.path().join("inputs.json");
+    let proof_path = dir.path().join("toy.proof");
+    let known = dir.path().join("known.txt");
+    fs::write(&inputs_path, r#"{"n":5}"#).unwrap();
+
+    let air = air_path();
+    let common = [
+        "--backend",
+        "native@0.0",
+        "--field",
+        "Prime254",
+        "--hash",
+        "blake3",
+        "--fri-arity",
+        "2",
+        "--profile",
+        "balanced",
+    ];
+    let prove = Command::new(BIN)
+        .args(["prove", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+        .args(["-o", proof_path.to_str().unwrap()])
+        .args(common)
+        .status()
+        .unwrap();
+    assert!(prove.success());
+    let validate = |name: &str| {
+        Command::new(BIN)
+            .args(["validate", "-p", &air, "-i", inputs_path.to_str().unwrap()])
+            .args(["-P", proof_path.to_str().unwrap()])
+            .args(["-o", dir.path().join(name).to_str().unwrap()])
+            .args(["--known-blinds", known.to_str().unwrap()])
+            .args(common)
+            .output()
+            .unwrap()
+    };
+
+    fs::write(&known, "# ledger export\n0x00ff\n").unwrap();
+    let out = validate("fresh");
+    assert!(out.status.success(), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Known blinds: 1 from"));
+
+    // validate commits with blind pubio_hash || backend_id_hash || profile_id_hash.
+    let proof = fs::read(&proof_path).unwrap();
+    let (header, _) = zkprov_corelib::proof::split_proof(&proof).unwrap();
+    let blind: String = [
+        header.pubio_hash,
+        header.backend_id_hash,
+        header.profile_id_hash,
+    ]
+    .iter()
+    .flat_map(|h| h.to_le_bytes())
+    .map(|b| format!("{b:02x}"))
+    .collect();
+    fs::write(&known, format!("0x00ff\n{blind}\n")).unwrap();
+    let out = validate("reused");
+    assert_eq!(out.status.code(), Some(1), "{:?}", out);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("BlindingReuse"));
+
+    fs::write(&known, "not hex\n").unwrap();
+    let out = validate("bad");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains(":1: not a hex blind"));
+}
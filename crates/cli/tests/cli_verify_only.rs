@@ -0,0 +1,82 @@
+//! `zkd-verify`, the verification-only build. Run with
+//! `cargo test -p zkd --no-default-features --features verifier --test cli_verify_only`.
+#![cfg(feature = "verifier")]
+
+mod common;
+
+use std::fs;
+use std::process::Command;
+use tempfile::tempdir;
+
+use zkprov_backend_native::native_prove;
+use zkprov_corelib::config::Config;
+
+use common::{air_path, CFG};
+
+const BIN: &str = env!("CARGO_BIN_EXE_zkd-verify");
+
+#[test]
+fn verifies_and_digests_proofs_from_the_full_build() {
+    let tmp = tempdir().unwrap();
+    let inputs = tmp.path().join("inputs.json");
+    fs::write(&inputs, r#"{"a":1}"#).unwrap();
+    let proof = tmp.path().join("toy.proof");
+    let air = air_path();
+    let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    fs::write(&proof, native_prove(&config, r#"{"a":1}"#, &air).unwrap()).unwrap();
+    let (inputs, proof) = (inputs.to_str().unwrap(), proof.to_str().unwrap());
+
+    let out = Command::new(BIN)
+        .args(["verify", "-p", &air, "-i", inputs, "-P", proof])
+        .args(CFG)
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let out = Command::new(BIN)
+        .args(["evm-digest", "-P", proof])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("0x"));
+}
+
+#[cfg(not(feature = "prover"))]
+#[test]
+fn prover_commands_are_not_built_in() {
+    let out = Command::new(BIN).arg("--help").output().unwrap();
+    let help = String::from_utf8_lossy(&out.stdout);
+    assert!(help.contains("Usage: zkd-verify"), "{help}");
+    assert!(help.contains("verify"), "{help}");
+    for cmd in ["prove", "commit", "blind-new", "trace-import", "examples"] {
+        let out = Command::new(BIN).arg(cmd).arg("--help").output().unwrap();
+        assert!(!out.status.success(), "`{cmd}` should not exist");
+        assert!(
+            String::from_utf8_lossy(&out.stderr).contains("unrecognized subcommand"),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+}
+
+#[test]
+fn backend_plugins_are_never_loaded() {
+    let tmp = tempdir().unwrap();
+    let junk = tmp
+        .path()
+        .join(format!("junk.{}", std::env::consts::DLL_EXTENSION));
+    fs::write(&junk, "not a library").unwrap();
+    let out = Command::new(BIN)
+        .args(["backend-ls", "--json"])
+        .env("ZKD_BACKEND_PATH", tmp.path())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let registry: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert!(registry.get("plugin_errors").is_none(), "{registry}");
+    assert!(!String::from_utf8_lossy(&out.stderr).contains("plugin"));
+}
@@ -0,0 +1,66 @@
+//! Helpers shared by the CLI integration tests. Each test binary uses a
+//! subset, hence the `dead_code` allowance.
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+pub const BIN: &str = env!("CARGO_BIN_EXE_zkd");
+
+/// Config flags the toy proofs are made with.
+pub const CFG: [&str; 10] = [
+    "--backend",
+    "native@0.0",
+    "--field",
+    "Prime254",
+    "--hash",
+    "blake3",
+    "--fri-arity",
+    "2",
+    "--profile",
+    "balanced",
+];
+
+/// Path of `examples/air/toy.air`.
+pub fn air_path() -> String {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/air/toy.air")
+        .to_str()
+        .expect("utf8 path")
+        .to_owned()
+}
+
+/// Run zkd with `args`.
+pub fn zkd(args: &[&str]) -> Output {
+    Command::new(BIN).args(args).output().expect("run zkd")
+}
+
+/// `cmd -p toy.air -i dir/inputs.json -o|-P dir/toy.proof` with [`CFG`],
+/// then `extra`.
+pub fn toy_args(cmd: &str, dir: &Path, extra: &[&str]) -> Vec<String> {
+    let inputs = dir.join("inputs.json");
+    let proof = dir.join("toy.proof");
+    let mut args = vec![
+        cmd,
+        "-p",
+        &air_path(),
+        "-i",
+        inputs.to_str().unwrap(),
+        if cmd == "prove" { "-o" } else { "-P" },
+        proof.to_str().unwrap(),
+    ]
+    .into_iter()
+    .chain(CFG)
+    .map(str::to_owned)
+    .collect::<Vec<_>>();
+    args.extend(extra.iter().map(|s| s.to_string()));
+    args
+}
+
+/// Run [`toy_args`].
+pub fn zkd_toy(cmd: &str, dir: &Path, extra: &[&str]) -> Output {
+    Command::new(BIN)
+        .args(toy_args(cmd, dir, extra))
+        .output()
+        .expect("run zkd")
+}
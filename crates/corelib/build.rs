@@ -0,0 +1,50 @@
+//! Captures build provenance for `zkprov_corelib::buildinfo`.
+//!
+//! Only inputs that are fixed for a given source tree and toolchain are
+//! recorded (no timestamps or hostnames), so two builds of the same commit
+//! with the same target and features embed identical build info.
+
+use std::env;
+use std::process::Command;
+
+const COMMIT_VARS: [&str; 3] = ["ZKD_GIT_SHA", "VERGEN_GIT_SHA", "GIT_COMMIT_HASH"];
+
+fn main() {
+    for var in COMMIT_VARS {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let commit = COMMIT_VARS
+        .iter()
+        .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ZKPROV_BUILD_GIT_COMMIT={commit}");
+
+    let target = env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=ZKPROV_BUILD_TARGET={target}");
+
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=ZKPROV_BUILD_PROFILE={profile}");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ZKPROV_BUILD_RUSTC={rustc_version}");
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_owned))
+        .map(|name| name.to_ascii_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+    println!(
+        "cargo:rustc-env=ZKPROV_BUILD_FEATURES={}",
+        features.join(",")
+    );
+}
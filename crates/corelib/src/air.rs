@@ -1,15 +1,21 @@
 //! AIR-IR: minimal, backend-neutral representation + TOML/YAML parser.
 
 pub mod bindings;
+pub mod boundary;
+pub mod diff;
+pub mod expr;
 pub mod parser;
 mod parser_yaml;
+pub mod public;
+pub mod rows;
+pub mod stdlib;
 pub mod types;
 pub mod validate;
 
 pub use parser::{parse_air_file, parse_air_str};
 pub use types::{AirIr, CommitmentBinding};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context, Result};
 use regex::Regex;
 use serde::de::{self, Deserializer};
 use serde::{Deserialize, Serialize};
@@ -26,6 +32,32 @@ pub enum AirHash {
     Poseidon2,
     Blake3,
     Rescue,
+    /// Keccak-256, for EVM-centric programs (`hash = "keccak"`).
+    #[serde(alias = "keccak256")]
+    Keccak,
+}
+
+impl AirHash {
+    /// Capability id of this hash, as listed in `Capabilities::hashes`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AirHash::Poseidon2 => "poseidon2",
+            AirHash::Blake3 => "blake3",
+            AirHash::Rescue => "rescue",
+            AirHash::Keccak => "keccak256",
+        }
+    }
+
+    /// Inverse of [`AirHash::as_str`], accepting registry aliases.
+    pub fn from_capability(id: &str) -> Option<Self> {
+        match crate::crypto::registry::canonical_hash_id(id)? {
+            "poseidon2" => Some(AirHash::Poseidon2),
+            "blake3" => Some(AirHash::Blake3),
+            "rescue" => Some(AirHash::Rescue),
+            "keccak256" => Some(AirHash::Keccak),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -40,6 +72,11 @@ pub struct AirMeta {
     pub profile: Option<String>, // optional suggested profile id
     #[serde(default)]
     pub degree_hint: Option<u32>, // optional upper bound on transition degree
+    /// Backend capabilities the program depends on (`lookups`, `recursion`,
+    /// `pedersen` or a hash id such as `keccak`); backends lacking any are
+    /// refused, and `--backend auto` skips them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -50,6 +87,93 @@ pub struct AirColumns {
     pub const_cols: u32, // constant columns
     #[serde(default)]
     pub periodic_cols: u32, // periodic columns
+    /// Optional names for the leading trace columns, used by `constraints.transitions`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<String>,
+    /// Boolean selector columns, laid out after the named columns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selectors: Vec<String>,
+    /// Value of each constant column (one per `const_cols`), when declared.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constants: Vec<u64>,
+    /// Repeating cycle of each periodic column (one per `periodic_cols`), when
+    /// declared. Cycle lengths are powers of two, at least 2.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub periodic: Vec<Vec<u64>>,
+}
+
+impl AirColumns {
+    /// BLAKE3 commitment to the declared constant and periodic column contents,
+    /// or `None` when the AIR leaves them implicit. Recorded in the determinism
+    /// manifest and bound into proofs so a prover cannot substitute other data.
+    pub fn fixed_commitment(&self) -> Option<[u8; 32]> {
+        if self.constants.is_empty() && self.periodic.is_empty() {
+            return None;
+        }
+        let mut hasher = blake3::Hasher::new_derive_key("zkprov air fixed columns v1");
+        hasher.update(&self.const_cols.to_le_bytes());
+        hasher.update(&self.periodic_cols.to_le_bytes());
+        for value in &self.constants {
+            hasher.update(&value.to_le_bytes());
+        }
+        for cycle in &self.periodic {
+            hasher.update(&(cycle.len() as u32).to_le_bytes());
+            for value in cycle {
+                hasher.update(&value.to_le_bytes());
+            }
+        }
+        Some(*hasher.finalize().as_bytes())
+    }
+
+    fn validate_fixed(&self, field: &str, rows_hint: Option<u32>) -> Result<()> {
+        if !self.constants.is_empty() && self.constants.len() != self.const_cols as usize {
+            return Err(anyhow!(
+                "columns.constants has {} values but const_cols = {}",
+                self.constants.len(),
+                self.const_cols
+            ));
+        }
+        if !self.periodic.is_empty() && self.periodic.len() != self.periodic_cols as usize {
+            return Err(anyhow!(
+                "columns.periodic has {} cycles but periodic_cols = {}",
+                self.periodic.len(),
+                self.periodic_cols
+            ));
+        }
+        for (i, cycle) in self.periodic.iter().enumerate() {
+            if cycle.len() < 2 || !cycle.len().is_power_of_two() {
+                return Err(anyhow!(
+                    "columns.periodic[{}] length {} is not a power of two >= 2",
+                    i,
+                    cycle.len()
+                ));
+            }
+            if let Some(rows) = rows_hint {
+                if cycle.len() > rows as usize {
+                    return Err(anyhow!(
+                        "columns.periodic[{}] length {} exceeds rows_hint {}",
+                        i,
+                        cycle.len(),
+                        rows
+                    ));
+                }
+            }
+        }
+        if let Ok(p) = crate::trace::eval::field_modulus(field) {
+            let values = self.constants.iter().chain(self.periodic.iter().flatten());
+            if let Some(v) = values
+                .into_iter()
+                .find(|v| num_bigint::BigUint::from(**v) >= p)
+            {
+                return Err(anyhow!(
+                    "fixed column value {} is not below the {} modulus",
+                    v,
+                    field
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -59,6 +183,10 @@ pub struct AirConstraints {
     pub transition_count: u32,
     /// Placeholder: number of boundary constraints.
     pub boundary_count: u32,
+    /// Transition constraints over named columns, optionally gated on a
+    /// selector (`when s1: next.a = cur.a + 1`). See [`expr`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<String>,
 }
 
 /// Optional commitments requirements (Phase-0 validation surface)
@@ -302,15 +430,24 @@ pub struct AirProgram {
     pub meta: AirMeta,
     pub columns: AirColumns,
     pub constraints: AirConstraints,
-    /// Optional hint for expected row count (power of two). Used to derive TraceShape.
+    /// Optional hint for expected row count (power of two), or an expression
+    /// over public inputs evaluated at prove time; see [`rows`]. Used to
+    /// derive TraceShape.
     #[serde(default)]
-    pub rows_hint: Option<u32>,
+    pub rows_hint: Option<rows::RowsHint>,
     /// Declared public inputs available for bindings.
     #[serde(default)]
     pub public_inputs: Vec<AirPublicInput>,
     /// Optional commitments requirements (pedersen/curve hints)
     #[serde(default)]
     pub commitments: Option<AirCommitments>,
+    /// Boundary constraints (`[[boundary]]`); see [`boundary`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boundary: Vec<boundary::BoundaryConstraint>,
+    /// Standard fragments to merge in (`include = ["std:range_u64@1"]`);
+    /// emptied by [`AirProgram::resolve_includes`]. See [`stdlib`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
 }
 
 impl AirProgram {
@@ -327,8 +464,9 @@ impl AirProgram {
             _ => {
                 let s = fs::read_to_string(path_ref)
                     .with_context(|| format!("reading AIR file {}", path_ref.display()))?;
-                let prog: AirProgram = toml::from_str(&s)
+                let mut prog: AirProgram = toml::from_str(&s)
                     .with_context(|| format!("parsing AIR file {}", path_ref.display()))?;
+                prog.resolve_includes()?;
                 prog.validate()?;
                 prog
             }
@@ -337,12 +475,38 @@ impl AirProgram {
         Ok(program)
     }
 
+    /// Merge the `include`d standard fragments into this program.
+    pub fn resolve_includes(&mut self) -> Result<()> {
+        stdlib::resolve_includes(self)
+    }
+
+    /// Column layout for named and selector columns.
+    pub fn column_layout(&self) -> Result<expr::ColumnLayout> {
+        expr::ColumnLayout::new(
+            &self.columns.names,
+            &self.columns.selectors,
+            self.columns.trace_cols,
+        )
+    }
+
+    /// Lower `constraints.transitions` (plus selector booleanity) into
+    /// degree-adjusted transition constraints.
+    pub fn transition_constraints(&self) -> Result<Vec<expr::TransitionConstraint>> {
+        let layout = self.column_layout()?;
+        expr::lower_transitions(&layout, &self.constraints.transitions)
+    }
+
     pub fn validate(&self) -> Result<()> {
         // name: alnum, underscore, dash only; 2..64 chars
         let re = Regex::new(r"^[A-Za-z0-9_\-]{2,64}$").unwrap();
         if !re.is_match(&self.meta.name) {
             return Err(anyhow!("invalid meta.name '{}'", self.meta.name));
         }
+        ensure!(
+            self.include.is_empty(),
+            "include {:?} is not resolved (call AirProgram::resolve_includes)",
+            self.include
+        );
         // field basic sanity (we’ll cross-check with backend caps elsewhere)
         if self.meta.field.trim().is_empty() {
             return Err(anyhow!("meta.field cannot be empty"));
@@ -360,23 +524,66 @@ impl AirProgram {
         if self.constraints.transition_count == 0 {
             return Err(anyhow!("constraints.transition_count must be > 0"));
         }
+        for req in &self.meta.requires {
+            ensure!(
+                crate::backend::is_known_requirement(req),
+                "unknown meta.requires entry '{}' (expected one of {} or a hash id)",
+                req,
+                crate::backend::REQUIREMENTS.join(", ")
+            );
+        }
         // degree hint sanity
         if let Some(d) = self.meta.degree_hint {
             if d == 0 || d > 64 {
                 return Err(anyhow!("degree_hint out of range (1..=64)"));
             }
         }
-        // rows_hint sanity (power of two)
-        if let Some(r) = self.rows_hint {
-            if !(8u32..=(1u32 << 22)).contains(&r) {
-                return Err(anyhow!("rows_hint out of range [2^3 .. 2^22]"));
-            }
-            if r.count_ones() != 1 {
-                return Err(anyhow!("rows_hint must be a power of two"));
+        // selector-gated transitions must parse and respect the degree hint
+        let lowered = self.transition_constraints()?;
+        if let (Some(hint), Some(max)) = (
+            self.meta.degree_hint,
+            lowered.iter().map(|c| c.degree).max(),
+        ) {
+            if max > hint {
+                return Err(anyhow!(
+                    "lowered transition degree {} exceeds degree_hint {}",
+                    max,
+                    hint
+                ));
             }
         }
+        self.columns
+            .validate_fixed(&self.meta.field, self.fixed_rows())?;
+        if !self.boundary.is_empty() {
+            ensure!(
+                self.boundary.len() == self.constraints.boundary_count as usize,
+                "constraints.boundary_count is {} but {} [[boundary]] entries are declared",
+                self.constraints.boundary_count,
+                self.boundary.len()
+            );
+            boundary::validate_boundaries(
+                &self.boundary,
+                &self.column_layout()?,
+                self.columns.trace_cols,
+                &self.public_inputs,
+                self.fixed_rows(),
+            )?;
+        }
+        // rows_hint sanity (power of two, or an expression over public inputs)
+        if let Some(hint) = &self.rows_hint {
+            hint.validate(
+                self.public_inputs
+                    .iter()
+                    .map(|pi| (pi.name.as_str(), pi.ty)),
+            )?;
+        }
         Ok(())
     }
+
+    /// `rows_hint` when it does not depend on public inputs.
+    pub fn fixed_rows(&self) -> Option<u32> {
+        self.rows_hint.as_ref().and_then(rows::RowsHint::fixed)
+    }
 }
 
 #[cfg(test)]
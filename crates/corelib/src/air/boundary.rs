@@ -0,0 +1,260 @@
+//! Boundary constraints: `[[boundary]]` entries pinning one trace cell to a
+//! constant or to a declared public input.
+//!
+//! ```toml
+//! [[boundary]]
+//! column = "acc"
+//! row = "first"        # "first", "last" or a row index
+//! value = 0
+//!
+//! [[boundary]]
+//! column = "acc"
+//! row = "last"
+//! public = "total"     # a declared public input of any non-bytes type
+//! ```
+//!
+//! Lowering resolves column names and `last` against the trace length and
+//! yields one [`Assertion`] per entry: the `(column, step, value)` triple a
+//! STARK backend asserts (e.g. Winterfell's `Assertion::single`).
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use num_bigint::BigUint;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::expr::ColumnLayout;
+use super::public::PublicValue;
+use super::types::{PublicInput, PublicTy};
+use super::AirPublicInput;
+
+/// Row targeted by a boundary constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BoundaryRow {
+    First,
+    Last,
+    Index(u32),
+}
+
+impl BoundaryRow {
+    /// Concrete row index in a trace of `rows` rows.
+    pub fn resolve(self, rows: u32) -> u32 {
+        match self {
+            BoundaryRow::First => 0,
+            BoundaryRow::Last => rows.saturating_sub(1),
+            BoundaryRow::Index(i) => i,
+        }
+    }
+}
+
+impl fmt::Display for BoundaryRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoundaryRow::First => f.write_str("first"),
+            BoundaryRow::Last => f.write_str("last"),
+            BoundaryRow::Index(i) => write!(f, "{}", i),
+        }
+    }
+}
+
+impl Serialize for BoundaryRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BoundaryRow::Index(i) => serializer.serialize_u32(*i),
+            other => serializer.serialize_str(&other.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BoundaryRow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Index(u32),
+            Name(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Index(i) => Ok(BoundaryRow::Index(i)),
+            Raw::Name(s) => match s.as_str() {
+                "first" => Ok(BoundaryRow::First),
+                "last" => Ok(BoundaryRow::Last),
+                other => Err(de::Error::custom(format!(
+                    "boundary row must be \"first\", \"last\" or an index, got '{other}'"
+                ))),
+            },
+        }
+    }
+}
+
+/// One `[[boundary]]` entry as written in the AIR source. Exactly one of
+/// `value` and `public` must be set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BoundaryConstraint {
+    /// Column name (`columns.names` / `columns.selectors`) or `c<index>`.
+    pub column: String,
+    pub row: BoundaryRow,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+    /// Name of a declared non-`bytes` public input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public: Option<String>,
+}
+
+/// Value a boundary cell must hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssertionValue {
+    Constant(u64),
+    /// Read from the public inputs at prove/verify time and mapped into the
+    /// field per its declared type (see [`super::public`]).
+    Public {
+        name: String,
+        ty: PublicTy,
+    },
+}
+
+/// A lowered boundary constraint: `trace[row][column] = value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assertion {
+    pub column: usize,
+    pub row: u32,
+    pub value: AssertionValue,
+    /// Canonical rendering, e.g. `acc[first] = 0` or `acc[last] = public.total`.
+    pub display: String,
+}
+
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl Assertion {
+    /// The asserted field element modulo `p`, reading public references from
+    /// `inputs` (the public inputs JSON object).
+    pub fn resolve(&self, inputs: &serde_json::Value, p: &BigUint) -> Result<BigUint> {
+        match &self.value {
+            AssertionValue::Constant(v) => Ok(BigUint::from(*v) % p),
+            AssertionValue::Public { name, ty } => {
+                let raw = inputs.get(name).ok_or_else(|| {
+                    anyhow!("boundary {}: public input '{}' is missing", self, name)
+                })?;
+                PublicValue::parse(*ty, raw)
+                    .and_then(|v| v.to_field(p))
+                    .map_err(|e| anyhow!("boundary {}: public input '{}': {}", self, name, e))
+            }
+        }
+    }
+}
+
+fn column_index(layout: &ColumnLayout, trace_cols: u32, name: &str) -> Option<usize> {
+    layout.column(name).or_else(|| {
+        name.strip_prefix('c')
+            .and_then(|i| i.parse::<usize>().ok())
+            .filter(|&i| i < trace_cols as usize)
+    })
+}
+
+/// Check entries against the column layout, the declared public inputs, and
+/// `rows_hint` (explicit row indices must fall inside the trace).
+pub fn validate_boundaries(
+    entries: &[BoundaryConstraint],
+    layout: &ColumnLayout,
+    trace_cols: u32,
+    public_inputs: &[AirPublicInput],
+    rows_hint: Option<u32>,
+) -> Result<()> {
+    let mut seen = BTreeSet::new();
+    for (i, b) in entries.iter().enumerate() {
+        let col = column_index(layout, trace_cols, &b.column)
+            .ok_or_else(|| anyhow!("boundary[{}]: unknown column '{}'", i, b.column))?;
+        ensure!(
+            seen.insert((col, b.row)),
+            "boundary[{}]: duplicate constraint on {}[{}]",
+            i,
+            b.column,
+            b.row
+        );
+        if let (BoundaryRow::Index(row), Some(rows)) = (b.row, rows_hint) {
+            ensure!(
+                row < rows,
+                "boundary[{}]: row {} is outside rows_hint {}",
+                i,
+                row,
+                rows
+            );
+        }
+        match (&b.value, &b.public) {
+            (Some(_), None) => {}
+            (None, Some(name)) => {
+                let pi = public_inputs
+                    .iter()
+                    .find(|pi| &pi.name == name)
+                    .ok_or_else(|| anyhow!("boundary[{}]: unknown public input '{}'", i, name))?;
+                ensure!(
+                    pi.ty.is_numeric(),
+                    "boundary[{}]: public input '{}' is bytes; only numeric inputs can be asserted",
+                    i,
+                    name
+                );
+            }
+            _ => bail!("boundary[{}]: set exactly one of 'value' and 'public'", i),
+        }
+    }
+    Ok(())
+}
+
+/// Lower validated entries into assertions over a trace of `rows` rows.
+pub fn lower_boundaries(
+    layout: &ColumnLayout,
+    trace_cols: u32,
+    entries: &[BoundaryConstraint],
+    public_inputs: &[PublicInput],
+    rows: u32,
+) -> Result<Vec<Assertion>> {
+    let mut cells = BTreeSet::new();
+    entries
+        .iter()
+        .map(|b| {
+            let column = column_index(layout, trace_cols, &b.column)
+                .ok_or_else(|| anyhow!("unknown boundary column '{}'", b.column))?;
+            let row = b.row.resolve(rows);
+            ensure!(
+                row < rows,
+                "boundary row {} is outside the {}-row trace",
+                row,
+                rows
+            );
+            ensure!(
+                cells.insert((column, row)),
+                "boundary constraints on {}[{}] resolve to the same cell",
+                b.column,
+                b.row
+            );
+            let (value, rhs) = match (&b.value, &b.public) {
+                (Some(v), _) => (AssertionValue::Constant(*v), v.to_string()),
+                (None, Some(name)) => (
+                    AssertionValue::Public {
+                        name: name.clone(),
+                        ty: public_inputs
+                            .iter()
+                            .find(|pi| &pi.name == name)
+                            .map(|pi| pi.ty)
+                            .ok_or_else(|| anyhow!("unknown public input '{}'", name))?,
+                    },
+                    format!("public.{}", name),
+                ),
+                (None, None) => bail!("boundary on {}[{}] has no value", b.column, b.row),
+            };
+            Ok(Assertion {
+                column,
+                row,
+                value,
+                display: format!("{}[{}] = {}", b.column, b.row, rhs),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,333 @@
+//! Semantic diff of two AIRs at the IR level.
+//!
+//! Each change is annotated with a [`Severity`]: `breaking` when proofs made
+//! for one AIR no longer verify (or get a different digest) under the other,
+//! `cosmetic` when only hints, placeholder counts or the spelling of a
+//! constraint changed. Constraints are compared in their canonical lowered
+//! rendering (the form backends bind), so reformatting
+//! `next.a=cur.a+1` as `next.a = cur.a + 1` is cosmetic.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::types::{AirIr, CommitmentBinding, CommitmentKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Changes what proofs bind or which proofs verify.
+    Breaking,
+    /// Hints, placeholders and formatting; proofs are unaffected.
+    Cosmetic,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Severity::Breaking => "breaking",
+            Severity::Cosmetic => "cosmetic",
+        })
+    }
+}
+
+/// One difference, located by a dotted path such as `meta.field` or
+/// `public_inputs.root`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub severity: Severity,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Extra explanation, e.g. `reordered` or `reformatted`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<9} {}: ", self.severity, self.path)?;
+        match (&self.before, &self.after) {
+            (None, Some(after)) => write!(f, "added {after}")?,
+            (Some(before), None) => write!(f, "removed {before}")?,
+            (Some(before), Some(after)) => write!(f, "{before} -> {after}")?,
+            (None, None) => {}
+        }
+        if let Some(note) = &self.note {
+            if self.before.is_some() || self.after.is_some() {
+                f.write_str(" ")?;
+            }
+            write!(f, "({note})")?;
+        }
+        Ok(())
+    }
+}
+
+/// All differences between two AIRs, breaking ones first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AirDiff {
+    pub changes: Vec<Change>,
+}
+
+impl AirDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.severity == Severity::Breaking)
+    }
+
+    fn push<T: fmt::Display>(
+        &mut self,
+        severity: Severity,
+        path: impl Into<String>,
+        before: Option<T>,
+        after: Option<T>,
+    ) {
+        self.changes.push(Change {
+            severity,
+            path: path.into(),
+            before: before.map(|v| v.to_string()),
+            after: after.map(|v| v.to_string()),
+            note: None,
+        });
+    }
+
+    fn note(&mut self, severity: Severity, path: impl Into<String>, note: &str) {
+        self.changes.push(Change {
+            severity,
+            path: path.into(),
+            before: None,
+            after: None,
+            note: Some(note.to_string()),
+        });
+    }
+
+    /// Record `before -> after` when they differ.
+    fn field<T: PartialEq + fmt::Display>(
+        &mut self,
+        severity: Severity,
+        path: &str,
+        before: &T,
+        after: &T,
+    ) {
+        if before != after {
+            self.push(severity, path, Some(before), Some(after));
+        }
+    }
+
+    fn optional<T: PartialEq + fmt::Display>(
+        &mut self,
+        severity: Severity,
+        path: &str,
+        before: &Option<T>,
+        after: &Option<T>,
+    ) {
+        if before != after {
+            self.push(severity, path, before.as_ref(), after.as_ref());
+        }
+    }
+
+    /// Diff two ordered lists item by item: changed positions, then the
+    /// tail of the longer one as added or removed.
+    fn list(&mut self, severity: Severity, path: &str, before: &[String], after: &[String]) {
+        for i in 0..before.len().max(after.len()) {
+            let (b, a) = (before.get(i), after.get(i));
+            if b != a {
+                self.push(severity, format!("{path}[{i}]"), b, a);
+            }
+        }
+    }
+
+    /// Diff two sets of names: membership changes only.
+    fn set(&mut self, severity: Severity, path: &str, before: &[String], after: &[String]) {
+        for b in before.iter().filter(|b| !after.contains(b)) {
+            self.push(severity, path, Some(b), None);
+        }
+        for a in after.iter().filter(|a| !before.contains(a)) {
+            self.push(severity, path, None, Some(a));
+        }
+    }
+}
+
+impl fmt::Display for AirDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Semantic differences from `old` to `new`.
+pub fn diff_air(old: &AirIr, new: &AirIr) -> AirDiff {
+    use Severity::{Breaking, Cosmetic};
+    let mut d = AirDiff::default();
+
+    let (om, nm) = (&old.meta, &new.meta);
+    d.field(Breaking, "meta.name", &om.name, &nm.name);
+    d.field(Breaking, "meta.field", &om.field, &nm.field);
+    d.field(Breaking, "meta.hash", &om.hash.as_str(), &nm.hash.as_str());
+    d.set(Breaking, "meta.requires", &om.requires, &nm.requires);
+    d.optional(Cosmetic, "meta.backend", &om.backend, &nm.backend);
+    d.optional(Cosmetic, "meta.profile", &om.profile, &nm.profile);
+    d.optional(Cosmetic, "degree_hint", &old.degree_hint, &new.degree_hint);
+
+    let (oc, nc) = (&old.columns, &new.columns);
+    d.field(
+        Breaking,
+        "columns.trace_cols",
+        &oc.trace_cols,
+        &nc.trace_cols,
+    );
+    d.field(
+        Breaking,
+        "columns.const_cols",
+        &oc.const_cols,
+        &nc.const_cols,
+    );
+    d.field(
+        Breaking,
+        "columns.periodic_cols",
+        &oc.periodic_cols,
+        &nc.periodic_cols,
+    );
+    d.list(Breaking, "columns.names", &oc.names, &nc.names);
+    d.list(Breaking, "columns.selectors", &oc.selectors, &nc.selectors);
+    if oc.constants != nc.constants {
+        d.note(Breaking, "columns.constants", "values changed");
+    }
+    if oc.periodic != nc.periodic {
+        d.note(Breaking, "columns.periodic", "cycles changed");
+    }
+    d.optional(Breaking, "rows_hint", &old.rows_hint, &new.rows_hint);
+
+    let (ok, nk) = (&old.constraints, &new.constraints);
+    d.field(
+        Cosmetic,
+        "constraints.transition_count",
+        &ok.transition_count,
+        &nk.transition_count,
+    );
+    d.field(
+        Cosmetic,
+        "constraints.boundary_count",
+        &ok.boundary_count,
+        &nk.boundary_count,
+    );
+    diff_transitions(&mut d, old, new);
+    let render_boundary = |ir: &AirIr| -> Vec<String> {
+        ir.boundary
+            .iter()
+            .map(|b| {
+                let rhs = match (&b.value, &b.public) {
+                    (Some(v), _) => v.to_string(),
+                    (None, Some(name)) => format!("public.{name}"),
+                    (None, None) => "?".to_string(),
+                };
+                format!("{}[{}] = {}", b.column, b.row, rhs)
+            })
+            .collect()
+    };
+    d.list(
+        Breaking,
+        "boundary",
+        &render_boundary(old),
+        &render_boundary(new),
+    );
+
+    diff_public_inputs(&mut d, old, new);
+    let render_commitments =
+        |ir: &AirIr| -> Vec<String> { ir.commitments.iter().map(render_commitment).collect() };
+    d.set(
+        Breaking,
+        "commitments",
+        &render_commitments(old),
+        &render_commitments(new),
+    );
+
+    d.changes.sort_by_key(|c| c.severity);
+    d
+}
+
+/// Compare lowered constraints; fall back to the source text when either
+/// side does not lower (it would fail validation anyway).
+fn diff_transitions(d: &mut AirDiff, old: &AirIr, new: &AirIr) {
+    let lower = |ir: &AirIr| -> Option<Vec<String>> {
+        ir.transition_constraints()
+            .ok()
+            .map(|cs| cs.iter().map(|c| c.to_string()).collect())
+    };
+    let (os, ns) = (&old.constraints.transitions, &new.constraints.transitions);
+    match (lower(old), lower(new)) {
+        (Some(ol), Some(nl)) if ol == nl => {
+            for (i, (o, n)) in os.iter().zip(ns).enumerate() {
+                if o != n {
+                    d.changes.push(Change {
+                        severity: Severity::Cosmetic,
+                        path: format!("constraints.transitions[{i}]"),
+                        before: Some(o.clone()),
+                        after: Some(n.clone()),
+                        note: Some("reformatted".to_string()),
+                    });
+                }
+            }
+        }
+        (Some(ol), Some(nl)) => d.list(Severity::Breaking, "constraints.transitions", &ol, &nl),
+        _ => d.list(Severity::Breaking, "constraints.transitions", os, ns),
+    }
+}
+
+fn diff_public_inputs(d: &mut AirDiff, old: &AirIr, new: &AirIr) {
+    let (op, np) = (&old.public_inputs, &new.public_inputs);
+    for o in op {
+        match np.iter().find(|n| n.name == o.name) {
+            None => d.push(
+                Severity::Breaking,
+                format!("public_inputs.{}", o.name),
+                Some(&o.ty),
+                None,
+            ),
+            Some(n) if n.ty != o.ty => d.push(
+                Severity::Breaking,
+                format!("public_inputs.{}", o.name),
+                Some(&o.ty),
+                Some(&n.ty),
+            ),
+            Some(_) => {}
+        }
+    }
+    for n in np.iter().filter(|n| !op.iter().any(|o| o.name == n.name)) {
+        d.push(
+            Severity::Breaking,
+            format!("public_inputs.{}", n.name),
+            None,
+            Some(&n.ty),
+        );
+    }
+    // Declaration order fixes the typed ABI layout and the structured
+    // `pubio_hash` binding.
+    let common = |a: &[super::types::PublicInput], b: &[super::types::PublicInput]| {
+        a.iter()
+            .filter(|x| b.iter().any(|y| y.name == x.name))
+            .map(|x| x.name.clone())
+            .collect::<Vec<_>>()
+    };
+    if common(op, np) != common(np, op) {
+        d.note(Severity::Breaking, "public_inputs", "reordered");
+    }
+}
+
+fn render_commitment(c: &CommitmentBinding) -> String {
+    let kind = match &c.kind {
+        CommitmentKind::Pedersen { curve } => format!("pedersen({curve})"),
+        CommitmentKind::PoseidonCommit => "poseidon_commit".to_string(),
+        CommitmentKind::KeccakCommit => "keccak_commit".to_string(),
+    };
+    format!("{kind}[{}]", c.public_inputs.join(","))
+}
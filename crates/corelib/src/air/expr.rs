@@ -0,0 +1,467 @@
+//! Transition-constraint expressions and selector lowering.
+//!
+//! Constraints are written over named columns of the current (`cur.x`) and
+//! next (`next.x`) row, optionally gated on a selector column:
+//!
+//! ```text
+//! next.acc = cur.acc + cur.x
+//! when s_add: next.a = cur.a + 1
+//! next.b - cur.b - 1
+//! ```
+//!
+//! A bare polynomial `P` stands for `P = 0` and is kept as written.
+//! Lowering turns `L = R` into `L - R = 0` and a gated constraint
+//! `when s: L = R` into `s * (L - R) = 0`, raising its degree by one. Every
+//! selector additionally receives a booleanity constraint `s * (s - 1) = 0`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use num_bigint::BigUint;
+
+/// Polynomial expression over trace cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Const(u64),
+    /// Column index in the current row.
+    Cur(usize),
+    /// Column index in the next row.
+    Next(usize),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+impl Expr {
+    pub fn degree(&self) -> u32 {
+        match self {
+            Expr::Const(_) => 0,
+            Expr::Cur(_) | Expr::Next(_) => 1,
+            Expr::Add(a, b) | Expr::Sub(a, b) => a.degree().max(b.degree()),
+            Expr::Mul(a, b) => a.degree() + b.degree(),
+            Expr::Neg(a) => a.degree(),
+        }
+    }
+
+    /// Evaluate modulo `p` given the (reduced) values of the current and
+    /// next row, indexed by trace column.
+    pub fn eval(&self, cur: &[BigUint], next: &[BigUint], p: &BigUint) -> BigUint {
+        match self {
+            Expr::Const(c) => BigUint::from(*c) % p,
+            Expr::Cur(i) => cur[*i].clone(),
+            Expr::Next(i) => next[*i].clone(),
+            Expr::Add(a, b) => (a.eval(cur, next, p) + b.eval(cur, next, p)) % p,
+            Expr::Sub(a, b) => (a.eval(cur, next, p) + p - b.eval(cur, next, p)) % p,
+            Expr::Mul(a, b) => (a.eval(cur, next, p) * b.eval(cur, next, p)) % p,
+            Expr::Neg(a) => (p - a.eval(cur, next, p)) % p,
+        }
+    }
+
+    /// The `Cur`/`Next` cells read, in first-use order without duplicates.
+    pub fn cells(&self) -> Vec<Expr> {
+        fn walk(e: &Expr, out: &mut Vec<Expr>) {
+            match e {
+                Expr::Const(_) => {}
+                Expr::Cur(_) | Expr::Next(_) => {
+                    if !out.contains(e) {
+                        out.push(e.clone());
+                    }
+                }
+                Expr::Add(a, b) | Expr::Sub(a, b) | Expr::Mul(a, b) => {
+                    walk(a, out);
+                    walk(b, out);
+                }
+                Expr::Neg(a) => walk(a, out),
+            }
+        }
+        let mut out = Vec::new();
+        walk(self, &mut out);
+        out
+    }
+
+    /// Render with column names, e.g. `(cur.a + 1)`.
+    pub fn to_named_string(&self, names: &[String]) -> String {
+        let mut out = String::new();
+        self.render(names, &mut out);
+        out
+    }
+
+    fn render(&self, names: &[String], out: &mut String) {
+        let name = |i: &usize| names.get(*i).cloned().unwrap_or_else(|| format!("c{}", i));
+        match self {
+            Expr::Const(c) => out.push_str(&c.to_string()),
+            Expr::Cur(i) => out.push_str(&format!("cur.{}", name(i))),
+            Expr::Next(i) => out.push_str(&format!("next.{}", name(i))),
+            Expr::Add(a, b) | Expr::Sub(a, b) | Expr::Mul(a, b) => {
+                let op = match self {
+                    Expr::Add(..) => " + ",
+                    Expr::Sub(..) => " - ",
+                    _ => " * ",
+                };
+                out.push('(');
+                a.render(names, out);
+                out.push_str(op);
+                b.render(names, out);
+                out.push(')');
+            }
+            Expr::Neg(a) => {
+                out.push('-');
+                a.render(names, out);
+            }
+        }
+    }
+}
+
+/// A constraint `expr = 0` that must hold on every row transition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionConstraint {
+    pub expr: Expr,
+    pub degree: u32,
+    /// Selector column gating the constraint, if any.
+    pub selector: Option<usize>,
+    /// Canonical rendering with column names, e.g. `(cur.s * (next.a - (cur.a + 1)))`.
+    pub display: String,
+}
+
+impl fmt::Display for TransitionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = 0", self.display)
+    }
+}
+
+/// Column layout: named data columns first, then selector columns.
+#[derive(Debug, Clone)]
+pub struct ColumnLayout {
+    names: Vec<String>,
+    index: BTreeMap<String, usize>,
+    selector_start: usize,
+}
+
+impl ColumnLayout {
+    pub fn new(names: &[String], selectors: &[String], trace_cols: u32) -> Result<Self> {
+        let mut all = Vec::with_capacity(names.len() + selectors.len());
+        let mut index = BTreeMap::new();
+        for name in names.iter().chain(selectors) {
+            ensure!(is_ident(name), "invalid column name '{}'", name);
+            ensure!(
+                index.insert(name.clone(), all.len()).is_none(),
+                "duplicate column name '{}'",
+                name
+            );
+            all.push(name.clone());
+        }
+        ensure!(
+            all.len() <= trace_cols as usize,
+            "{} named/selector columns exceed trace_cols = {}",
+            all.len(),
+            trace_cols
+        );
+        Ok(Self {
+            names: all,
+            index,
+            selector_start: names.len(),
+        })
+    }
+
+    /// Column names in layout order (data columns, then selectors).
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    pub fn is_selector(&self, col: usize) -> bool {
+        col >= self.selector_start && col < self.names.len()
+    }
+
+    /// Indices of all selector columns.
+    pub fn selectors(&self) -> std::ops::Range<usize> {
+        self.selector_start..self.names.len()
+    }
+
+    fn finish(&self, expr: Expr, selector: Option<usize>) -> TransitionConstraint {
+        let mut display = String::new();
+        expr.render(&self.names, &mut display);
+        TransitionConstraint {
+            degree: expr.degree(),
+            expr,
+            selector,
+            display,
+        }
+    }
+}
+
+/// Parse and lower `sources` into transition constraints, followed by one
+/// booleanity constraint per selector column.
+pub fn lower_transitions(
+    layout: &ColumnLayout,
+    sources: &[String],
+) -> Result<Vec<TransitionConstraint>> {
+    let mut out = Vec::with_capacity(sources.len() + layout.selectors().len());
+    for src in sources {
+        out.push(lower_one(layout, src).map_err(|e| anyhow!("constraint '{}': {}", src, e))?);
+    }
+    for s in layout.selectors() {
+        let expr = Expr::Mul(
+            Box::new(Expr::Cur(s)),
+            Box::new(Expr::Sub(Box::new(Expr::Cur(s)), Box::new(Expr::Const(1)))),
+        );
+        out.push(layout.finish(expr, None));
+    }
+    Ok(out)
+}
+
+fn lower_one(layout: &ColumnLayout, src: &str) -> Result<TransitionConstraint> {
+    let src = src.trim();
+    let (selector, body) = match src.strip_prefix("when ") {
+        Some(rest) => {
+            let (sel, body) = rest
+                .split_once(':')
+                .ok_or_else(|| anyhow!("expected ':' after selector"))?;
+            let sel = sel.trim();
+            let col = layout
+                .column(sel)
+                .ok_or_else(|| anyhow!("unknown selector '{}'", sel))?;
+            ensure!(
+                layout.is_selector(col),
+                "'{}' is not declared in columns.selectors",
+                sel
+            );
+            (Some(col), body)
+        }
+        None => (None, src),
+    };
+    // A bare polynomial `P` means `P = 0`.
+    let diff = match body.split_once('=') {
+        Some((lhs, rhs)) => {
+            let lhs = Parser::new(lhs, layout)?.parse_all()?;
+            let rhs = Parser::new(rhs, layout)?.parse_all()?;
+            Expr::Sub(Box::new(lhs), Box::new(rhs))
+        }
+        None => Parser::new(body, layout)?.parse_all()?,
+    };
+    let expr = match selector {
+        Some(s) => Expr::Mul(Box::new(Expr::Cur(s)), Box::new(diff)),
+        None => diff,
+    };
+    Ok(layout.finish(expr, selector))
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Num(u64),
+    Ident(String),
+    Dot,
+    Plus,
+    Minus,
+    Star,
+    LParen,
+    RParen,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let mut out = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '0'..='9' => {
+                let mut n = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                    n.push(d);
+                    chars.next();
+                }
+                out.push(Token::Num(
+                    n.parse()
+                        .map_err(|_| anyhow!("constant '{}' too large", n))?,
+                ));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut id = String::new();
+                while let Some(&d) = chars
+                    .peek()
+                    .filter(|d| d.is_ascii_alphanumeric() || **d == '_')
+                {
+                    id.push(d);
+                    chars.next();
+                }
+                out.push(Token::Ident(id));
+            }
+            _ => {
+                chars.next();
+                out.push(match c {
+                    '.' => Token::Dot,
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    other => bail!("unexpected character '{}'", other),
+                });
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Recursive-descent parser: expr := term (('+'|'-') term)*,
+/// term := unary ('*' unary)*, unary := '-' unary | atom.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    layout: &'a ColumnLayout,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &str, layout: &'a ColumnLayout) -> Result<Self> {
+        Ok(Self {
+            tokens: tokenize(src)?,
+            pos: 0,
+            layout,
+        })
+    }
+
+    fn parse_all(mut self) -> Result<Expr> {
+        let expr = self.expr()?;
+        ensure!(self.pos == self.tokens.len(), "unexpected trailing input");
+        Ok(expr)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.pos += 1;
+                    lhs = Expr::Add(Box::new(lhs), Box::new(self.term()?));
+                }
+                Some(Token::Minus) => {
+                    self.pos += 1;
+                    lhs = Expr::Sub(Box::new(lhs), Box::new(self.term()?));
+                }
+                _ => return Ok(lhs),
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::Star) {
+            self.pos += 1;
+            lhs = Expr::Mul(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek() == Some(&Token::Minus) {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(Expr::Const(n)),
+            Some(Token::LParen) => {
+                let e = self.expr()?;
+                ensure!(self.next() == Some(Token::RParen), "expected ')'");
+                Ok(e)
+            }
+            Some(Token::Ident(row)) if row == "cur" || row == "next" => {
+                ensure!(
+                    self.next() == Some(Token::Dot),
+                    "expected '.' after '{}'",
+                    row
+                );
+                let name = match self.next() {
+                    Some(Token::Ident(name)) => name,
+                    _ => bail!("expected column name after '{}.'", row),
+                };
+                let col = self
+                    .layout
+                    .column(&name)
+                    .ok_or_else(|| anyhow!("unknown column '{}'", name))?;
+                Ok(if row == "cur" {
+                    Expr::Cur(col)
+                } else {
+                    Expr::Next(col)
+                })
+            }
+            Some(Token::Ident(id)) => bail!("expected 'cur.<col>' or 'next.<col>', found '{}'", id),
+            Some(t) => bail!("unexpected token {:?}", t),
+            None => bail!("unexpected end of expression"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> ColumnLayout {
+        ColumnLayout::new(&["a".to_string(), "b".to_string()], &["s1".to_string()], 4).unwrap()
+    }
+
+    #[test]
+    fn gated_constraint_gains_one_degree() {
+        let l = layout();
+        let out = lower_transitions(&l, &["when s1: next.a = cur.a * cur.b + 1".into()]).unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].degree, 3);
+        assert_eq!(out[0].selector, Some(2));
+        assert_eq!(
+            out[0].display,
+            "(cur.s1 * (next.a - ((cur.a * cur.b) + 1)))"
+        );
+        // booleanity
+        assert_eq!(out[1].display, "(cur.s1 * (cur.s1 - 1))");
+        assert_eq!(out[1].degree, 2);
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_non_selector_gates() {
+        let l = layout();
+        assert!(lower_transitions(&l, &["when a: next.a = cur.a".into()]).is_err());
+        assert!(lower_transitions(&l, &["next.z = cur.a".into()]).is_err());
+        assert!(lower_transitions(&l, &["next.a = cur.a +".into()]).is_err());
+        assert!(lower_transitions(&l, &["next.a cur.a".into()]).is_err());
+    }
+
+    #[test]
+    fn eval_is_modular() {
+        let p = BigUint::from(7u32);
+        let l = layout();
+        let c = &lower_transitions(&l, &["when s1: next.a = cur.a - 3".into()]).unwrap()[0];
+        let row = |a: u32, s: u32| vec![a.into(), 0u32.into(), s.into()];
+        // 5 - 3 = 2, and 1 - 3 wraps to 5 mod 7
+        assert_eq!(c.expr.eval(&row(5, 1), &row(2, 0), &p), BigUint::from(0u32));
+        assert_eq!(c.expr.eval(&row(1, 1), &row(5, 0), &p), BigUint::from(0u32));
+        assert_eq!(c.expr.eval(&row(1, 1), &row(4, 0), &p), BigUint::from(6u32));
+        // gate off: anything goes
+        assert_eq!(c.expr.eval(&row(1, 0), &row(6, 0), &p), BigUint::from(0u32));
+    }
+}
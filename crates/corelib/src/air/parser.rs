@@ -105,7 +105,8 @@ pub fn parse_air_file(path: &Path) -> Result<AirIr> {
 /// Returns an error if the input cannot be decoded, violates structural
 /// constraints, or declares invalid commitment bindings.
 pub fn parse_air_str(src: &str) -> Result<AirIr> {
-    let program: AirProgram = toml::from_str(src).context("parsing AIR source")?;
+    let mut program: AirProgram = toml::from_str(src).context("parsing AIR source")?;
+    program.resolve_includes()?;
     program.validate()?;
     let ir = AirIr::from(program);
     validate_bindings(&ir)?;
@@ -6,7 +6,8 @@ use anyhow::{Context, Result};
 use super::AirProgram;
 
 pub fn load_from_str(input: &str) -> Result<AirProgram> {
-    let program: AirProgram = serde_yaml::from_str(input).context("parsing AIR YAML")?;
+    let mut program: AirProgram = serde_yaml::from_str(input).context("parsing AIR YAML")?;
+    program.resolve_includes()?;
     program.validate()?;
     Ok(program)
 }
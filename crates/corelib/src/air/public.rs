@@ -0,0 +1,455 @@
+//! Typed public input values: parsing from the inputs JSON, canonical byte
+//! encodings, and the mapping into the proof field.
+//!
+//! | type         | JSON                                   | canonical bytes (LE)      | field element                 |
+//! |--------------|----------------------------------------|---------------------------|-------------------------------|
+//! | `field`      | integer, decimal or `0x` hex string    | 32 bytes                  | the value; must be `< p`      |
+//! | `u64`        | integer, decimal or `0x` hex string    | 8 bytes                   | the value                     |
+//! | `u128`       | integer, decimal or `0x` hex string    | 16 bytes                  | the value                     |
+//! | `i64`        | integer or decimal string              | 8 bytes, two's complement | `v` or `p - abs(v)`           |
+//! | `fixed(s)`   | integer or decimal string, `<= s` fractional digits | 16 bytes, two's complement `i128` units of `10^-s` | units as for `i64` |
+//! | `bytes`      | `0x` hex string                        | the bytes                 | hash-to-field (below)         |
+//! | `bytes_ref`  | `{"digest": "0x…", "len": n}` once resolved | 32-byte digest, then `len` as `u64` | hash-to-field of those 40 bytes |
+//!
+//! Negative values map to `p - |v|`, so `-1` is `p - 1`. `bytes` map to
+//! `LE(BLAKE3-XOF-64("ZKD.PUBLIC.BYTES" || bytes)) mod p`; the 512-bit
+//! reduction keeps the bias below 2^-250. Fixed-point values written as JSON
+//! floats are rejected: `12.5` is not exact in binary, write `"12.5"`.
+//!
+//! A `bytes_ref` is written as a file path or `file://` URI, which
+//! [`resolve_bytes_refs`] streams through the config's hash (unlabeled, so
+//! the digest matches e.g. `b3sum`) and replaces with the resolved object.
+//! Supplying the resolved object directly binds the same value, so a
+//! verifier needs the digest and length, not the document.
+
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+
+use crate::crypto::registry::hash32_reader_by_id;
+use crate::io::TypedInputs;
+use crate::proof::pubio::UndeclaredInputs;
+
+use super::types::{PublicInput, PublicTy};
+
+const BYTES_TO_FIELD_LABEL: &[u8] = b"ZKD.PUBLIC.BYTES";
+
+/// A public input parsed under its declared [`PublicTy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicValue {
+    Field(BigUint),
+    U64(u64),
+    U128(u128),
+    I64(i64),
+    /// `units * 10^-scale`.
+    Fixed {
+        units: i128,
+        scale: u8,
+    },
+    Bytes(Vec<u8>),
+    /// A resolved `bytes_ref`: digest and byte length of the document.
+    BytesRef {
+        digest: [u8; 32],
+        len: u64,
+    },
+}
+
+impl PublicValue {
+    /// Parse `raw` as a value of type `ty`.
+    pub fn parse(ty: PublicTy, raw: &Value) -> Result<Self> {
+        Ok(match ty {
+            PublicTy::Field => {
+                let v = parse_unsigned(raw)?;
+                ensure!(v.bits() <= 256, "{} does not fit in 256 bits", v);
+                PublicValue::Field(v)
+            }
+            PublicTy::U64 => {
+                let v = parse_unsigned(raw)?;
+                PublicValue::U64(
+                    u64::try_from(&v).map_err(|_| anyhow!("{} does not fit in u64", v))?,
+                )
+            }
+            PublicTy::U128 => {
+                let v = parse_unsigned(raw)?;
+                PublicValue::U128(
+                    u128::try_from(&v).map_err(|_| anyhow!("{} does not fit in u128", v))?,
+                )
+            }
+            PublicTy::I64 => {
+                let v = parse_signed(raw, 0)?;
+                PublicValue::I64(
+                    i64::try_from(v).map_err(|_| anyhow!("{} does not fit in i64", v))?,
+                )
+            }
+            PublicTy::Fixed { scale } => PublicValue::Fixed {
+                units: parse_signed(raw, scale)?,
+                scale,
+            },
+            PublicTy::Bytes => {
+                let s = raw
+                    .as_str()
+                    .ok_or_else(|| anyhow!("expected a 0x-hex string, got {}", raw))?;
+                let s = s.strip_prefix("0x").unwrap_or(s);
+                PublicValue::Bytes(
+                    alloy_primitives::hex::decode(s).map_err(|e| anyhow!("not hex: {}", e))?,
+                )
+            }
+            PublicTy::BytesRef => {
+                if let Value::String(s) = raw {
+                    bail!(
+                        "unresolved reference '{}'; bytes_ref values are resolved from files \
+                         when proving or verifying",
+                        s
+                    );
+                }
+                let digest = raw
+                    .get("digest")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow!("expected {{\"digest\", \"len\"}}, got {}", raw))?;
+                let digest = alloy_primitives::hex::decode(digest)
+                    .ok()
+                    .and_then(|d| <[u8; 32]>::try_from(d).ok())
+                    .ok_or_else(|| anyhow!("digest must be 32 bytes of 0x-hex"))?;
+                let len = raw
+                    .get("len")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| anyhow!("len must be a non-negative integer"))?;
+                ensure!(
+                    raw.as_object().is_some_and(|o| o.len() == 2),
+                    "unexpected keys in {}",
+                    raw
+                );
+                PublicValue::BytesRef { digest, len }
+            }
+        })
+    }
+
+    pub fn ty(&self) -> PublicTy {
+        match self {
+            PublicValue::Field(_) => PublicTy::Field,
+            PublicValue::U64(_) => PublicTy::U64,
+            PublicValue::U128(_) => PublicTy::U128,
+            PublicValue::I64(_) => PublicTy::I64,
+            PublicValue::Fixed { scale, .. } => PublicTy::Fixed { scale: *scale },
+            PublicValue::Bytes(_) => PublicTy::Bytes,
+            PublicValue::BytesRef { .. } => PublicTy::BytesRef,
+        }
+    }
+
+    /// Canonical little-endian encoding (see the module table).
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        match self {
+            PublicValue::Field(v) => {
+                let mut out = v.to_bytes_le();
+                out.resize(32, 0);
+                out
+            }
+            PublicValue::U64(v) => v.to_le_bytes().to_vec(),
+            PublicValue::U128(v) => v.to_le_bytes().to_vec(),
+            PublicValue::I64(v) => v.to_le_bytes().to_vec(),
+            PublicValue::Fixed { units, .. } => units.to_le_bytes().to_vec(),
+            PublicValue::Bytes(b) => b.clone(),
+            PublicValue::BytesRef { digest, len } => {
+                let mut out = digest.to_vec();
+                out.extend_from_slice(&len.to_le_bytes());
+                out
+            }
+        }
+    }
+
+    /// The field element bound for this value modulo `p`.
+    pub fn to_field(&self, p: &BigUint) -> Result<BigUint> {
+        let signed = |v: i128| {
+            let abs = BigUint::from(v.unsigned_abs());
+            ensure!(&abs < p, "{} is out of range for the field", v);
+            Ok(if v < 0 && abs != BigUint::default() {
+                p - abs
+            } else {
+                abs
+            })
+        };
+        let unsigned = |v: BigUint| {
+            ensure!(&v < p, "{} is not below the field modulus", v);
+            Ok(v)
+        };
+        match self {
+            PublicValue::Field(v) => unsigned(v.clone()),
+            PublicValue::U64(v) => unsigned(BigUint::from(*v)),
+            PublicValue::U128(v) => unsigned(BigUint::from(*v)),
+            PublicValue::I64(v) => signed(i128::from(*v)),
+            PublicValue::Fixed { units, .. } => signed(*units),
+            PublicValue::Bytes(_) | PublicValue::BytesRef { .. } => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(BYTES_TO_FIELD_LABEL);
+                hasher.update(&self.to_le_bytes());
+                let mut wide = [0u8; 64];
+                hasher.finalize_xof().fill(&mut wide);
+                Ok(BigUint::from_bytes_le(&wide) % p)
+            }
+        }
+    }
+
+    /// Canonical JSON: `u64`/`i64` as numbers, `field`/`u128` as decimal
+    /// strings, `fixed` as a decimal string with exactly `scale` fractional
+    /// digits, `bytes` as 0x-hex, `bytes_ref` as `{"digest", "len"}`.
+    pub fn to_json(&self) -> Value {
+        match self {
+            PublicValue::Field(v) => Value::String(v.to_string()),
+            PublicValue::U64(v) => Value::from(*v),
+            PublicValue::U128(v) => Value::String(v.to_string()),
+            PublicValue::I64(v) => Value::from(*v),
+            PublicValue::Fixed { units, scale } => Value::String(format_fixed(*units, *scale)),
+            PublicValue::Bytes(b) => {
+                Value::String(format!("0x{}", alloy_primitives::hex::encode(b)))
+            }
+            PublicValue::BytesRef { digest, len } => json!({
+                "digest": format!("0x{}", alloy_primitives::hex::encode(digest)),
+                "len": len,
+            }),
+        }
+    }
+}
+
+/// Resolve every declared `bytes_ref` input written as a path or `file://`
+/// URI: stream the file through `hash_id` and put `{"digest", "len"}` in its
+/// place. Relative paths are taken from the working directory. Returns
+/// `None` when nothing needed resolving.
+pub fn resolve_bytes_refs(
+    decls: &[PublicInput],
+    inputs: &Value,
+    hash_id: &str,
+) -> Result<Option<Value>> {
+    let mut resolved = inputs.clone();
+    let mut changed = false;
+    for pi in decls.iter().filter(|pi| pi.ty == PublicTy::BytesRef) {
+        let Some(Value::String(reference)) = inputs.get(&pi.name) else {
+            continue;
+        };
+        let path = match reference.split_once("://") {
+            Some(("file", path)) => path,
+            Some((scheme, _)) => bail!(
+                "public input '{}': unsupported bytes_ref scheme '{}' (only file paths)",
+                pi.name,
+                scheme
+            ),
+            None => reference.as_str(),
+        };
+        let file = File::open(Path::new(path))
+            .with_context(|| format!("public input '{}': failed to open '{}'", pi.name, path))?;
+        let (digest, len) = hash32_reader_by_id(hash_id, file)
+            .with_context(|| format!("public input '{}': failed to read '{}'", pi.name, path))?
+            .ok_or_else(|| anyhow!("unknown hash '{}'", hash_id))?;
+        resolved[&pi.name] = PublicValue::BytesRef { digest, len }.to_json();
+        changed = true;
+    }
+    Ok(changed.then_some(resolved))
+}
+
+/// Check every declared input present in `inputs` against its type. Missing
+/// and undeclared keys are left to the consumer (typed public IO rejects
+/// both; boundary constraints reject missing references). See
+/// [`crate::io::TypedInputs`] for the parsed values.
+pub fn validate_public_inputs(decls: &[PublicInput], inputs: &Value) -> Result<()> {
+    if decls.is_empty() {
+        return Ok(());
+    }
+    TypedInputs::parse(decls, inputs, UndeclaredInputs::Ignore).map(|_| ())
+}
+
+fn parse_unsigned(raw: &Value) -> Result<BigUint> {
+    match raw {
+        Value::Number(n) => n
+            .as_u64()
+            .map(BigUint::from)
+            .ok_or_else(|| anyhow!("expected a non-negative integer, got {}", n)),
+        Value::String(s) => {
+            let parsed = match s.strip_prefix("0x") {
+                Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+                None if s.bytes().all(|b| b.is_ascii_digit()) => {
+                    BigUint::parse_bytes(s.as_bytes(), 10)
+                }
+                None => None,
+            };
+            parsed.ok_or_else(|| anyhow!("expected a non-negative integer, got '{}'", s))
+        }
+        other => Err(anyhow!("expected an integer, got {}", other)),
+    }
+}
+
+/// A signed decimal scaled by `10^scale`: `"-1.25"` at scale 2 is `-125`.
+fn parse_signed(raw: &Value, scale: u8) -> Result<i128> {
+    let text = match raw {
+        Value::Number(n) if n.is_f64() => bail!(
+            "{} is a JSON float; write decimals as strings, e.g. \"{}\"",
+            n,
+            n
+        ),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        other => bail!("expected a decimal, got {}", other),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    ensure!(
+        !int.is_empty()
+            && int.bytes().all(|b| b.is_ascii_digit())
+            && frac.bytes().all(|b| b.is_ascii_digit())
+            && !digits.ends_with('.'),
+        "expected a decimal, got '{}'",
+        text
+    );
+    ensure!(
+        frac.len() <= scale as usize,
+        "'{}' has {} fractional digits; at most {} allowed",
+        text,
+        frac.len(),
+        scale
+    );
+    let out_of_range = || anyhow!("'{}' is out of range", text);
+    let mut units: i128 = 0;
+    for b in int.bytes().chain(frac.bytes()) {
+        units = units
+            .checked_mul(10)
+            .and_then(|u| u.checked_add(i128::from(b - b'0')))
+            .ok_or_else(out_of_range)?;
+    }
+    units = units
+        .checked_mul(10i128.pow(u32::from(scale) - frac.len() as u32))
+        .ok_or_else(out_of_range)?;
+    Ok(if negative { -units } else { units })
+}
+
+fn format_fixed(units: i128, scale: u8) -> String {
+    let sign = if units < 0 { "-" } else { "" };
+    let digits = units.unsigned_abs().to_string();
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let digits = format!("{:0>width$}", digits, width = scale as usize + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale as usize);
+    format!("{}{}.{}", sign, int, frac)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::field::prime254_modulus;
+    use serde_json::json;
+
+    #[test]
+    fn parses_and_encodes_each_type() {
+        let cases = [
+            (PublicTy::U64, json!("0x10"), vec![16, 0, 0, 0, 0, 0, 0, 0]),
+            (PublicTy::I64, json!(-2), (-2i64).to_le_bytes().to_vec()),
+            (
+                PublicTy::U128,
+                json!("340282366920938463463374607431768211455"),
+                vec![0xff; 16],
+            ),
+            (
+                PublicTy::Fixed { scale: 2 },
+                json!("-12.5"),
+                (-1250i128).to_le_bytes().to_vec(),
+            ),
+            (PublicTy::Bytes, json!("0xabcd"), vec![0xab, 0xcd]),
+        ];
+        for (ty, raw, bytes) in cases {
+            let v = PublicValue::parse(ty, &raw).unwrap();
+            assert_eq!(v.ty(), ty);
+            assert_eq!(v.to_le_bytes(), bytes, "{ty}");
+        }
+        let field = PublicValue::parse(PublicTy::Field, &json!("258")).unwrap();
+        assert_eq!(field.to_le_bytes()[..3], [2, 1, 0]);
+        assert_eq!(field.to_le_bytes().len(), 32);
+    }
+
+    #[test]
+    fn rejects_out_of_range_and_inexact_values() {
+        let cases = [
+            (
+                PublicTy::U64,
+                json!("18446744073709551616"),
+                "does not fit in u64",
+            ),
+            (PublicTy::U128, json!(-1), "non-negative"),
+            (
+                PublicTy::I64,
+                json!("9223372036854775808"),
+                "does not fit in i64",
+            ),
+            (PublicTy::I64, json!("1.5"), "at most 0 allowed"),
+            (
+                PublicTy::Fixed { scale: 2 },
+                json!("1.234"),
+                "3 fractional digits",
+            ),
+            (PublicTy::Fixed { scale: 2 }, json!(1.25), "JSON float"),
+            (
+                PublicTy::Fixed { scale: 2 },
+                json!("1."),
+                "expected a decimal",
+            ),
+            (
+                PublicTy::Fixed { scale: 2 },
+                json!("+1"),
+                "expected a decimal",
+            ),
+        ];
+        for (ty, raw, needle) in cases {
+            let err = PublicValue::parse(ty, &raw).unwrap_err().to_string();
+            assert!(err.contains(needle), "{ty} {raw}: {err}");
+        }
+    }
+
+    #[test]
+    fn fixed_round_trips_through_canonical_json() {
+        let ty = PublicTy::Fixed { scale: 3 };
+        for (raw, canonical) in [("7", "7.000"), ("-0.05", "-0.050"), ("12.345", "12.345")] {
+            let v = PublicValue::parse(ty, &json!(raw)).unwrap();
+            assert_eq!(v.to_json(), json!(canonical));
+            assert_eq!(PublicValue::parse(ty, &v.to_json()).unwrap(), v);
+        }
+        let v = PublicValue::parse(PublicTy::Fixed { scale: 0 }, &json!(-3)).unwrap();
+        assert_eq!(v.to_json(), json!("-3"));
+    }
+
+    #[test]
+    fn maps_into_the_field() {
+        let p = prime254_modulus();
+        let field = |ty, raw| PublicValue::parse(ty, &raw).unwrap().to_field(&p).unwrap();
+        assert_eq!(field(PublicTy::I64, json!(-1)), &p - 1u32);
+        assert_eq!(field(PublicTy::I64, json!(0)), BigUint::default());
+        assert_eq!(
+            field(PublicTy::Fixed { scale: 2 }, json!("-0.01")),
+            &p - 1u32
+        );
+        assert_eq!(field(PublicTy::U128, json!(5)), BigUint::from(5u32));
+        let a = field(PublicTy::Bytes, json!("0x01"));
+        assert!(a < p);
+        assert_ne!(a, field(PublicTy::Bytes, json!("0x0100")));
+        let too_big = PublicValue::Field(p.clone());
+        assert!(too_big.to_field(&p).is_err());
+    }
+
+    #[test]
+    fn validates_declared_inputs_only() {
+        let decls = [PublicInput {
+            name: "price".into(),
+            ty: PublicTy::Fixed { scale: 2 },
+        }];
+        validate_public_inputs(&decls, &json!({"price": "1.25", "other": 1.5})).unwrap();
+        validate_public_inputs(&decls, &json!({})).unwrap();
+        let err = validate_public_inputs(&decls, &json!({"price": "1.255"})).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("public input 'price' (fixed(2))"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! `rows_hint`: a fixed row count, or an expression over public inputs for
+//! variable-size workloads.
+//!
+//! ```toml
+//! rows_hint = 1024
+//! rows_hint = "next_pow2(len(leaves) * 64)"
+//! ```
+//!
+//! Expressions use unsigned integers, `+ - * /`, parentheses, numeric public
+//! inputs by name, `len(name)` (the byte length of a `bytes` or `bytes_ref`
+//! input), `next_pow2(x)`, `min(a, b, ...)` and `max(a, b, ...)`. Only
+//! declared public inputs may be referenced, so the row count is bound by the
+//! proof's public IO. The value is evaluated at prove and verify time and
+//! must be a power of two in `[2^3, 2^22]`.
+
+use std::fmt;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::types::PublicTy;
+
+/// Smallest and largest row counts an AIR may declare or evaluate to.
+pub const MIN_ROWS: u32 = 1 << 3;
+pub const MAX_ROWS: u32 = 1 << 22;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RowsHint {
+    Fixed(u32),
+    Expr(String),
+}
+
+impl RowsHint {
+    /// The row count when it does not depend on inputs.
+    pub fn fixed(&self) -> Option<u32> {
+        match self {
+            RowsHint::Fixed(rows) => Some(*rows),
+            RowsHint::Expr(_) => None,
+        }
+    }
+
+    pub fn is_expr(&self) -> bool {
+        matches!(self, RowsHint::Expr(_))
+    }
+
+    /// Static checks: a fixed count must be in range, an expression must
+    /// parse and reference only `declared` public inputs of a fitting type
+    /// (`len()` of bytes, arithmetic on numbers).
+    pub fn validate<'a>(
+        &self,
+        declared: impl IntoIterator<Item = (&'a str, PublicTy)>,
+    ) -> Result<()> {
+        match self {
+            RowsHint::Fixed(rows) => check_rows(*rows),
+            RowsHint::Expr(src) => {
+                let expr = parse(src)?;
+                let declared: Vec<(&str, PublicTy)> = declared.into_iter().collect();
+                let mut names = Vec::new();
+                expr.names(&mut names);
+                for (name, len) in names {
+                    let (_, ty) = declared.iter().find(|(n, _)| *n == name).ok_or_else(|| {
+                        anyhow!(
+                            "rows_hint references '{name}', which is not a declared public input"
+                        )
+                    })?;
+                    ensure!(
+                        len != ty.is_numeric(),
+                        "rows_hint: {} public input '{}' of type {}",
+                        if len { "len() of" } else { "arithmetic on" },
+                        name,
+                        ty
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The row count for `inputs` (the public inputs JSON object).
+    pub fn evaluate(&self, inputs: &Value) -> Result<u32> {
+        match self {
+            RowsHint::Fixed(rows) => Ok(*rows),
+            RowsHint::Expr(src) => {
+                let value = parse(src)?.eval(inputs)?;
+                let rows = u32::try_from(value)
+                    .map_err(|_| anyhow!("rows_hint \"{src}\" evaluates to {value}"))?;
+                check_rows(rows)
+                    .map_err(|e| anyhow!("rows_hint \"{src}\" evaluates to {rows}: {e}"))?;
+                Ok(rows)
+            }
+        }
+    }
+}
+
+impl fmt::Display for RowsHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowsHint::Fixed(rows) => write!(f, "{rows}"),
+            RowsHint::Expr(src) => write!(f, "\"{src}\""),
+        }
+    }
+}
+
+fn check_rows(rows: u32) -> Result<()> {
+    ensure!(
+        (MIN_ROWS..=MAX_ROWS).contains(&rows),
+        "rows_hint out of range [2^3 .. 2^22]"
+    );
+    ensure!(rows.is_power_of_two(), "rows_hint must be a power of two");
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Num(u64),
+    Input(String),
+    Len(String),
+    Call(Func, Vec<Expr>),
+    Bin(Op, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    NextPow2,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Expr {
+    /// Referenced inputs, flagged `true` when used through `len()`.
+    fn names<'a>(&'a self, out: &mut Vec<(&'a str, bool)>) {
+        match self {
+            Expr::Num(_) => {}
+            Expr::Input(name) => out.push((name, false)),
+            Expr::Len(name) => out.push((name, true)),
+            Expr::Call(_, args) => args.iter().for_each(|a| a.names(out)),
+            Expr::Bin(_, l, r) => {
+                l.names(out);
+                r.names(out);
+            }
+        }
+    }
+
+    fn eval(&self, inputs: &Value) -> Result<u64> {
+        let lookup = |name: &str| {
+            inputs
+                .get(name)
+                .ok_or_else(|| anyhow!("rows_hint: public input '{name}' is missing"))
+        };
+        Ok(match self {
+            Expr::Num(n) => *n,
+            Expr::Input(name) => match lookup(name)? {
+                Value::Number(n) => n.as_u64(),
+                Value::String(s) => match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                    None => s.parse().ok(),
+                },
+                _ => None,
+            }
+            .ok_or_else(|| {
+                anyhow!("rows_hint: public input '{name}' is not an unsigned integer")
+            })?,
+            Expr::Len(name) => match lookup(name)? {
+                Value::String(s) if s.starts_with("0x") => (s.len() as u64 - 1) / 2,
+                Value::Object(obj) => obj
+                    .get("len")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| anyhow!("rows_hint: len({name}) needs a resolved bytes_ref"))?,
+                _ => bail!("rows_hint: len({name}) needs 0x-prefixed bytes"),
+            },
+            Expr::Call(func, args) => {
+                let values = args
+                    .iter()
+                    .map(|a| a.eval(inputs))
+                    .collect::<Result<Vec<_>>>()?;
+                match func {
+                    Func::NextPow2 => values[0]
+                        .checked_next_power_of_two()
+                        .ok_or_else(|| anyhow!("rows_hint: next_pow2 overflows"))?,
+                    Func::Min => values.into_iter().min().unwrap_or_default(),
+                    Func::Max => values.into_iter().max().unwrap_or_default(),
+                }
+            }
+            Expr::Bin(op, l, r) => {
+                let (l, r) = (l.eval(inputs)?, r.eval(inputs)?);
+                match op {
+                    Op::Add => l.checked_add(r),
+                    Op::Sub => l.checked_sub(r),
+                    Op::Mul => l.checked_mul(r),
+                    Op::Div => l.checked_div(r),
+                }
+                .ok_or_else(|| anyhow!("rows_hint: {l} {} {r} is out of range", op.symbol()))?
+            }
+        })
+    }
+}
+
+impl Op {
+    fn symbol(self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+        }
+    }
+}
+
+fn parse(src: &str) -> Result<Expr> {
+    let mut p = Parser {
+        src,
+        tokens: tokenize(src)?,
+        pos: 0,
+    };
+    let expr = p.expr()?;
+    match p.peek() {
+        None => Ok(expr),
+        Some(t) => Err(p.error(&format!("unexpected {t}"))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Num(u64),
+    Ident(String),
+    Sym(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Num(n) => write!(f, "{n}"),
+            Token::Ident(s) => write!(f, "'{s}'"),
+            Token::Sym(c) => write!(f, "'{c}'"),
+        }
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek() {
+                if !d.is_ascii_digit() {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            let n = src[start..end].parse().map_err(|_| {
+                anyhow!(
+                    "rows_hint \"{src}\": number {} is too large",
+                    &src[start..end]
+                )
+            })?;
+            tokens.push(Token::Num(n));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek() {
+                if !(d.is_ascii_alphanumeric() || d == '_') {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            tokens.push(Token::Ident(src[start..end].to_string()));
+        } else if "+-*/(),".contains(c) {
+            tokens.push(Token::Sym(c));
+            chars.next();
+        } else {
+            bail!("rows_hint \"{src}\": unexpected character '{c}'");
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn error(&self, msg: &str) -> anyhow::Error {
+        anyhow!("rows_hint \"{}\": {msg}", self.src)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Sym(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{c}'")))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = if self.eat('+') {
+                Op::Add
+            } else if self.eat('-') {
+                Op::Sub
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let mut lhs = self.factor()?;
+        loop {
+            let op = if self.eat('*') {
+                Op::Mul
+            } else if self.eat('/') {
+                Op::Div
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(Expr::Num(n)),
+            Some(Token::Sym('(')) => {
+                let e = self.expr()?;
+                self.expect(')')?;
+                Ok(e)
+            }
+            Some(Token::Ident(name)) if self.eat('(') => self.call(&name),
+            Some(Token::Ident(name)) => Ok(Expr::Input(name)),
+            Some(t) => Err(self.error(&format!("unexpected {t}"))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    fn call(&mut self, name: &str) -> Result<Expr> {
+        if name == "len" {
+            let arg = match self.next() {
+                Some(Token::Ident(arg)) => arg,
+                _ => return Err(self.error("len() takes a public input name")),
+            };
+            self.expect(')')?;
+            return Ok(Expr::Len(arg));
+        }
+        let func = match name {
+            "next_pow2" => Func::NextPow2,
+            "min" => Func::Min,
+            "max" => Func::Max,
+            _ => return Err(self.error(&format!("unknown function '{name}'"))),
+        };
+        let mut args = vec![self.expr()?];
+        while self.eat(',') {
+            args.push(self.expr()?);
+        }
+        self.expect(')')?;
+        if func == Func::NextPow2 && args.len() != 1 {
+            return Err(self.error("next_pow2() takes one argument"));
+        }
+        Ok(Expr::Call(func, args))
+    }
+}
@@ -0,0 +1,182 @@
+//! Standard AIR fragments (`include = ["std:range_u64@1"]`).
+//!
+//! Fragments are small, reviewed AIR pieces embedded in the crate: named
+//! columns, transitions and boundary entries. Including one appends its
+//! columns after the program's own named columns (growing `trace_cols`), its
+//! transitions and boundary entries, and raises `transition_count` /
+//! `boundary_count` to match. Fragment columns share the program's namespace,
+//! so the program constrains them by name like its own columns.
+//!
+//! Ids are `std:<name>@<version>`; `std:<name>` resolves to the latest
+//! version. A published version never changes, so pin versions in programs
+//! whose proofs must stay verifiable across releases.
+//!
+//! | Id | Columns | Provides |
+//! | --- | --- | --- |
+//! | `std:counter@1` | `ctr` | `ctr[r] = r` |
+//! | `std:range_u64@1` | `rc_acc`, `rc_bit` | `rc_acc[64]` is below 2^64 |
+//! | `std:merkle_path@1` | `mp_node`, `mp_sib`, `mp_dir`, `mp_left`, `mp_right` | per-level child ordering |
+
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use serde::Deserialize;
+
+use super::boundary::BoundaryConstraint;
+use super::AirProgram;
+
+/// Namespace prefix of standard fragment ids.
+pub const NAMESPACE: &str = "std";
+
+const SOURCES: &[&str] = &[
+    include_str!("std/counter_v1.toml"),
+    include_str!("std/range_u64_v1.toml"),
+    include_str!("std/merkle_path_v1.toml"),
+];
+
+/// Fragments that are planned but cannot be expressed yet, with the reason.
+const PLANNED: &[(&str, &str)] = &[(
+    "poseidon2_perm",
+    "transitions cannot reference periodic round constants yet, and \
+     crypto::poseidon2 is still a placeholder",
+)];
+
+/// One embedded fragment.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Fragment {
+    #[serde(rename = "fragment")]
+    pub meta: FragmentMeta,
+    #[serde(default)]
+    pub columns: FragmentColumns,
+    pub constraints: FragmentConstraints,
+    #[serde(default)]
+    pub boundary: Vec<BoundaryConstraint>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FragmentMeta {
+    pub name: String,
+    pub version: u32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FragmentColumns {
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub selectors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FragmentConstraints {
+    pub transitions: Vec<String>,
+}
+
+impl Fragment {
+    /// Versioned id, e.g. `std:range_u64@1`.
+    pub fn id(&self) -> String {
+        format!("{}:{}@{}", NAMESPACE, self.meta.name, self.meta.version)
+    }
+
+    fn apply(&self, program: &mut AirProgram) -> Result<()> {
+        let columns = &mut program.columns;
+        for name in self.columns.names.iter().chain(&self.columns.selectors) {
+            ensure!(
+                !columns.names.contains(name) && !columns.selectors.contains(name),
+                "column '{}' is already declared",
+                name
+            );
+        }
+        let added = self.columns.names.len() + self.columns.selectors.len();
+        columns.names.extend(self.columns.names.iter().cloned());
+        columns
+            .selectors
+            .extend(self.columns.selectors.iter().cloned());
+        columns.trace_cols += added as u32;
+
+        let constraints = &mut program.constraints;
+        constraints
+            .transitions
+            .extend(self.constraints.transitions.iter().cloned());
+        constraints.transition_count +=
+            (self.constraints.transitions.len() + self.columns.selectors.len()) as u32;
+        constraints.boundary_count += self.boundary.len() as u32;
+        program.boundary.extend(self.boundary.iter().cloned());
+        Ok(())
+    }
+}
+
+/// Every embedded fragment, all versions, in id order.
+pub fn fragments() -> Vec<Fragment> {
+    let mut all: Vec<Fragment> = SOURCES
+        .iter()
+        .map(|src| toml::from_str(src).expect("embedded AIR fragment parses"))
+        .collect();
+    all.sort_by(|a, b| (&a.meta.name, a.meta.version).cmp(&(&b.meta.name, b.meta.version)));
+    all
+}
+
+/// Resolve `std:<name>` (latest version) or `std:<name>@<version>`.
+pub fn lookup(id: &str) -> Result<Fragment> {
+    let rest = id
+        .strip_prefix(NAMESPACE)
+        .and_then(|r| r.strip_prefix(':'))
+        .ok_or_else(|| anyhow!("unknown include '{}' (expected std:<name>[@<version>])", id))?;
+    let (name, version) = match rest.split_once('@') {
+        Some((name, v)) => {
+            let v = v
+                .parse::<u32>()
+                .map_err(|_| anyhow!("invalid version in include '{}'", id))?;
+            (name, Some(v))
+        }
+        None => (rest, None),
+    };
+    if let Some((_, reason)) = PLANNED.iter().find(|(planned, _)| *planned == name) {
+        bail!("{}:{} is not available yet: {}", NAMESPACE, name, reason);
+    }
+    let candidates: Vec<Fragment> = fragments()
+        .into_iter()
+        .filter(|f| f.meta.name == name)
+        .collect();
+    ensure!(
+        !candidates.is_empty(),
+        "unknown standard fragment '{}' (available: {})",
+        id,
+        available().join(", ")
+    );
+    match version {
+        Some(v) => candidates
+            .into_iter()
+            .find(|f| f.meta.version == v)
+            .ok_or_else(|| anyhow!("unknown version in include '{}'", id)),
+        None => Ok(candidates.into_iter().last().expect("non-empty")),
+    }
+}
+
+fn available() -> Vec<String> {
+    fragments().iter().map(Fragment::id).collect()
+}
+
+/// Merge `program.include` into the program, leaving the list empty.
+pub fn resolve_includes(program: &mut AirProgram) -> Result<()> {
+    let includes = std::mem::take(&mut program.include);
+    let mut seen = BTreeSet::new();
+    for id in &includes {
+        let fragment = lookup(id)?;
+        ensure!(
+            seen.insert(fragment.meta.name.clone()),
+            "{}:{} is included more than once",
+            NAMESPACE,
+            fragment.meta.name
+        );
+        fragment
+            .apply(program)
+            .with_context(|| format!("including {}", fragment.id()))?;
+    }
+    Ok(())
+}
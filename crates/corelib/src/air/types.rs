@@ -1,39 +1,107 @@
 use serde::{Deserialize, Serialize};
 
+use super::boundary::{lower_boundaries, Assertion, BoundaryConstraint};
+use super::expr::{lower_transitions, ColumnLayout};
+use super::rows::RowsHint;
 use super::{AirColumns, AirConstraints, AirMeta, AirProgram};
 
-#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+/// Constraint expression AST, as lowered from `constraints.transitions`.
+pub use super::expr::{Expr, TransitionConstraint};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 /// Public input surface area supported by the AIR DSL.
 ///
 /// Values default to [`PublicTy::Field`] when the `type` key is omitted in the
-/// mini-DSL.
+/// mini-DSL. Value parsing, canonical encodings and the mapping into the field
+/// live in [`super::public`].
 pub enum PublicTy {
+    #[default]
     Field,
     Bytes,
+    /// A document too large to inline: the JSON value names a file whose
+    /// digest and length are bound instead (see
+    /// [`super::public::resolve_bytes_refs`]).
+    BytesRef,
     U64,
+    U128,
+    I64,
+    /// Signed decimal with `scale` fractional digits (`fixed(2)` for cents),
+    /// carried as an `i128` count of `10^-scale` units.
+    Fixed {
+        scale: u8,
+    },
 }
 
-impl<'de> serde::Deserialize<'de> for PublicTy {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let value = String::deserialize(deserializer)?;
-        match value.as_str() {
+impl PublicTy {
+    /// Largest `fixed(scale)`: 10^38 still fits an `i128`.
+    pub const MAX_FIXED_SCALE: u8 = 38;
+
+    /// Whether values of this type can be asserted by boundary constraints.
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, PublicTy::Bytes | PublicTy::BytesRef)
+    }
+}
+
+impl std::fmt::Display for PublicTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PublicTy::Field => f.write_str("field"),
+            PublicTy::Bytes => f.write_str("bytes"),
+            PublicTy::BytesRef => f.write_str("bytes_ref"),
+            PublicTy::U64 => f.write_str("u64"),
+            PublicTy::U128 => f.write_str("u128"),
+            PublicTy::I64 => f.write_str("i64"),
+            PublicTy::Fixed { scale } => write!(f, "fixed({})", scale),
+        }
+    }
+}
+
+impl std::str::FromStr for PublicTy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
             "field" => Ok(Self::Field),
             "bytes" => Ok(Self::Bytes),
+            "bytes_ref" => Ok(Self::BytesRef),
             "u64" => Ok(Self::U64),
-            other => Err(serde::de::Error::custom(format!(
-                "unknown public input type '{other}'"
-            ))),
+            "u128" => Ok(Self::U128),
+            "i64" => Ok(Self::I64),
+            other => {
+                let scale = other
+                    .strip_prefix("fixed(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .ok_or_else(|| format!("unknown public input type '{other}'"))?;
+                let scale: u8 = scale
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid scale in '{other}'"))?;
+                if scale > Self::MAX_FIXED_SCALE {
+                    return Err(format!(
+                        "fixed scale {} exceeds {}",
+                        scale,
+                        Self::MAX_FIXED_SCALE
+                    ));
+                }
+                Ok(Self::Fixed { scale })
+            }
         }
     }
 }
 
-impl Default for PublicTy {
-    fn default() -> Self {
-        Self::Field
+impl Serialize for PublicTy {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PublicTy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -100,6 +168,55 @@ pub struct AirIr {
     pub commitments: Vec<CommitmentBinding>,
     #[serde(default)]
     pub public_inputs: Vec<PublicInput>,
+    /// Expected row count (power of two) or an expression over public
+    /// inputs, carried over from the AIR source.
+    #[serde(default)]
+    pub rows_hint: Option<RowsHint>,
+    /// Boundary constraints, validated against columns and public inputs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boundary: Vec<BoundaryConstraint>,
+}
+
+impl AirIr {
+    /// Column layout for named and selector columns.
+    pub fn column_layout(&self) -> anyhow::Result<ColumnLayout> {
+        ColumnLayout::new(
+            &self.columns.names,
+            &self.columns.selectors,
+            self.columns.trace_cols,
+        )
+    }
+
+    /// Lowered transition constraints; see [`AirProgram::transition_constraints`].
+    pub fn transition_constraints(&self) -> anyhow::Result<Vec<TransitionConstraint>> {
+        lower_transitions(&self.column_layout()?, &self.constraints.transitions)
+    }
+
+    /// Boundary constraints lowered into assertions over a `rows`-row trace.
+    pub fn boundary_assertions(&self, rows: u32) -> anyhow::Result<Vec<Assertion>> {
+        lower_boundaries(
+            &self.column_layout()?,
+            self.columns.trace_cols,
+            &self.boundary,
+            &self.public_inputs,
+            rows,
+        )
+    }
+
+    /// `rows_hint` when it does not depend on public inputs.
+    pub fn fixed_rows(&self) -> Option<u32> {
+        self.rows_hint.as_ref().and_then(RowsHint::fixed)
+    }
+
+    /// Evaluate `rows_hint` against the public inputs JSON object and pin it
+    /// as a fixed row count, so backends see a concrete trace shape.
+    pub fn with_rows_for(&self, inputs: &serde_json::Value) -> anyhow::Result<AirIr> {
+        let mut ir = self.clone();
+        if let Some(hint) = &self.rows_hint {
+            ir.rows_hint = Some(RowsHint::Fixed(hint.evaluate(inputs)?));
+        }
+        Ok(ir)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -139,9 +256,11 @@ impl From<AirProgram> for AirIr {
             meta,
             columns,
             constraints,
+            rows_hint,
             public_inputs,
             commitments,
-            ..
+            boundary,
+            include: _, // merged by `AirProgram::resolve_includes`
         } = program;
 
         let commitments = commitments.map(|c| c.bindings).unwrap_or_default();
@@ -163,6 +282,8 @@ impl From<AirProgram> for AirIr {
             degree_hint,
             commitments,
             public_inputs,
+            rows_hint,
+            boundary,
         }
     }
 }
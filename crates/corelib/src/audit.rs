@@ -0,0 +1,222 @@
+//! Append-only, hash-chained audit log of prove and verify operations.
+//!
+//! Each line of the log is one [`AuditEntry`] as JSON. An entry records who
+//! ran which operation on what (program hash, inputs hash, backend,
+//! profile), the proof digest, the outcome and how long it took. It also
+//! carries `prev`, the `hash` of the entry before it (64 zeros for the
+//! first), and its own `hash`: hex BLAKE3 over the entry's JSON with `hash`
+//! left out. Editing, dropping or reordering a line therefore breaks the
+//! chain at that line, which [`verify_log`] reports. Truncating the tail is
+//! not detectable from the log alone; keep the last `hash` elsewhere (e.g.
+//! in a periodic report) to pin it.
+//!
+//! [`AuditLog::append`] holds an exclusive lock on the file while it reads
+//! the last entry and appends the next, so concurrent writers on one host
+//! keep a single chain.
+
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// `prev` of the first entry.
+pub const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Audited operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOp {
+    Prove,
+    Verify,
+}
+
+/// Outcome of an audited operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditResult {
+    Ok,
+    Failed,
+}
+
+/// What is recorded about one operation; [`AuditLog::append`] chains it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    pub actor: String,
+    pub op: AuditOp,
+    /// `0x` Keccak-256 of the AIR source, as in `zkd evm-typed-hash`.
+    pub program_hash: String,
+    /// `0x` BLAKE3 of the public inputs JSON text.
+    pub inputs_hash: String,
+    pub backend: String,
+    pub profile: String,
+    /// `0x` EVM digest `D` of the proof, when one was produced or read.
+    pub digest: Option<String>,
+    pub result: AuditResult,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// One line of the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditEntry {
+    /// Position in the log, from 0.
+    pub seq: u64,
+    pub time_ms: u64,
+    pub actor: String,
+    pub op: AuditOp,
+    pub program_hash: String,
+    pub inputs_hash: String,
+    pub backend: String,
+    pub profile: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    pub result: AuditResult,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    /// `hash` of the previous entry, or [`GENESIS`].
+    pub prev: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Hex BLAKE3 of this entry's JSON without `hash`.
+    pub fn compute_hash(&self) -> String {
+        let unhashed = AuditEntry {
+            hash: String::new(),
+            ..self.clone()
+        };
+        let json = serde_json::to_vec(&unhashed).expect("audit entry serializes");
+        blake3::hash(&json).to_hex().to_string()
+    }
+}
+
+/// Result of [`verify_log`] on an intact chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainReport {
+    pub entries: u64,
+    /// `hash` of the last entry, or [`GENESIS`] for an empty log.
+    pub head: String,
+}
+
+/// A JSON-lines audit log file.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chain `record` onto the log (creating it if needed) and return the
+    /// entry written.
+    pub fn append(&self, record: AuditRecord) -> Result<AuditEntry> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .with_context(|| format!("failed to open audit log '{}'", self.path.display()))?;
+        file.lock()
+            .with_context(|| format!("failed to lock audit log '{}'", self.path.display()))?;
+        let mut text = String::new();
+        file.seek(SeekFrom::Start(0))?;
+        file.read_to_string(&mut text)
+            .with_context(|| format!("failed to read audit log '{}'", self.path.display()))?;
+        ensure!(
+            text.is_empty() || text.ends_with('\n'),
+            "audit log '{}' ends in a partial line",
+            self.path.display()
+        );
+        let (seq, prev) = match text.lines().last() {
+            Some(line) => {
+                let last: AuditEntry = serde_json::from_str(line).with_context(|| {
+                    format!("corrupt last entry in audit log '{}'", self.path.display())
+                })?;
+                (last.seq + 1, last.hash)
+            }
+            None => (0, GENESIS.to_string()),
+        };
+        let mut entry = AuditEntry {
+            seq,
+            time_ms: now_ms(),
+            actor: record.actor,
+            op: record.op,
+            program_hash: record.program_hash,
+            inputs_hash: record.inputs_hash,
+            backend: record.backend,
+            profile: record.profile,
+            digest: record.digest,
+            result: record.result,
+            error: record.error,
+            duration_ms: record.duration_ms,
+            prev,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        file.write_all(&line)
+            .and_then(|()| file.sync_data())
+            .with_context(|| format!("failed to write audit log '{}'", self.path.display()))?;
+        Ok(entry)
+    }
+}
+
+/// Check every entry's hash, `prev` link and sequence number. Fails at the
+/// first broken line, naming it (1-based).
+pub fn verify_log(path: &Path) -> Result<ChainReport> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("failed to read audit log '{}'", path.display()))?;
+    let mut head = GENESIS.to_string();
+    let mut entries = 0u64;
+    for (i, line) in text.lines().enumerate() {
+        let n = i + 1;
+        let entry: AuditEntry = serde_json::from_str(line)
+            .with_context(|| format!("line {}: not an audit entry", n))?;
+        if entry.seq != entries {
+            bail!(
+                "line {}: seq {} where {} was expected",
+                n,
+                entry.seq,
+                entries
+            );
+        }
+        if entry.prev != head {
+            match i {
+                0 => bail!("line 1: prev is not the genesis hash"),
+                _ => bail!("line {}: prev does not match the hash of line {}", n, i),
+            }
+        }
+        let expected = entry.compute_hash();
+        if entry.hash != expected {
+            bail!(
+                "line {}: hash {} does not match its contents ({})",
+                n,
+                entry.hash,
+                expected
+            );
+        }
+        head = entry.hash;
+        entries += 1;
+    }
+    Ok(ChainReport { entries, head })
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
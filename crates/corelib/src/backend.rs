@@ -2,6 +2,21 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::air::types::AirIr;
+use crate::crypto::registry::canonical_hash_id;
+use crate::deadline::Deadline;
+use crate::errors::{ProveError, VerifyError};
+use crate::evm::pubio::{self, PubIoEncoding};
+use crate::gadgets::commitment::CommitKind;
+use crate::io::TypedInputs;
+use crate::profile::{Profile, ProofTarget};
+use crate::proof::canonical::canonical_json;
+use crate::proof::pubio::{structured_binding, undeclared_keys, UndeclaredInputs};
+use crate::proof::{Proof, ProofView};
+use crate::trace::{Reuse, Trace, TraceCache};
+
+pub mod options;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Capabilities {
     pub fields: Vec<&'static str>, // e.g., ["Goldilocks","BabyBear"]
@@ -13,22 +28,318 @@ pub struct Capabilities {
     pub curves: Vec<&'static str>, // e.g., ["placeholder"]
     /// Whether Pedersen-style commitments (and related gadgets) are supported
     pub pedersen: bool,
+    /// Largest trace (rows) the backend can prove; `None` means no stated limit.
+    #[serde(default)]
+    pub max_rows: Option<u32>,
+    /// Can re-prove from a [`TraceCache`], rehashing only changed columns.
+    #[serde(default)]
+    pub incremental: bool,
+    /// Code paths whose output could depend on scheduling or the platform,
+    /// from [`NONDETERMINISM_SOURCES`]; empty when proofs are bit-identical
+    /// everywhere. Reported by [`crate::determinism::inspect`].
+    #[serde(default)]
+    pub nondeterminism: Vec<&'static str>,
+}
+
+/// Capability names an AIR may list in `meta.requires`, besides hash ids.
+pub const REQUIREMENTS: &[&str] = &["lookups", "recursion", "pedersen"];
+
+/// Values of [`Capabilities::nondeterminism`]: reductions whose order
+/// follows thread scheduling, iteration over randomly seeded hash maps, and
+/// CPU-specific intrinsics whose results differ between platforms.
+pub const NONDETERMINISM_SOURCES: &[&str] = &[
+    "parallel-reduction",
+    "hashmap-iteration",
+    "platform-intrinsics",
+];
+
+/// Whether `requirement` is a valid `meta.requires` entry.
+pub fn is_known_requirement(requirement: &str) -> bool {
+    REQUIREMENTS.contains(&requirement) || canonical_hash_id(requirement).is_some()
+}
+
+impl Capabilities {
+    /// Whether these capabilities meet an AIR `meta.requires` entry; unknown
+    /// requirements are never met.
+    pub fn satisfies(&self, requirement: &str) -> bool {
+        match requirement {
+            "lookups" => self.lookups,
+            "recursion" => self.recursion != "none",
+            "pedersen" => self.pedersen,
+            other => canonical_hash_id(other).is_some_and(|h| self.hashes.contains(&h)),
+        }
+    }
+
+    /// Whether the backend can produce proofs for a profile's `target`:
+    /// SNARK-wrapped proofs need `recursion = "snark-wrapper"`.
+    pub fn supports_target(&self, target: ProofTarget) -> bool {
+        match target {
+            ProofTarget::Stark => true,
+            ProofTarget::SnarkWrapped => self.recursion == "snark-wrapper",
+        }
+    }
+
+    /// Commitment kinds an AIR can bind on this backend, in
+    /// [`CommitKind::ALL`] order. Pedersen needs `pedersen` and takes any of
+    /// the backend's curves and hashes; `poseidon_commit` and `keccak_commit`
+    /// are computed host-side and bound as public inputs, so every backend
+    /// accepts them over their fixed hash.
+    pub fn commitments(&self) -> Vec<CommitmentSupport> {
+        CommitKind::ALL
+            .into_iter()
+            .filter_map(|kind| match kind.fixed_hash() {
+                None if !self.pedersen => None,
+                None => Some(CommitmentSupport {
+                    kind: kind.as_str(),
+                    curves: self.curves.clone(),
+                    hashes: self.hashes.clone(),
+                }),
+                Some(hash) => Some(CommitmentSupport {
+                    kind: kind.as_str(),
+                    curves: Vec::new(),
+                    hashes: vec![hash],
+                }),
+            })
+            .collect()
+    }
+}
+
+/// One entry of [`Capabilities::commitments`].
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CommitmentSupport {
+    /// `pedersen`, `poseidon_commit` or `keccak_commit`.
+    pub kind: &'static str,
+    /// Curves accepted for the kind; empty for hash-based kinds.
+    pub curves: Vec<&'static str>,
+    pub hashes: Vec<&'static str>,
 }
 
+/// Public inputs for a prove/verify call.
+///
+/// The JSON text is kept verbatim. Unbound, proof headers hash its canonical
+/// form (`pubio_hash`, see [`crate::proof::canonical`]), so key order,
+/// whitespace and number spelling do not matter; once bound to an AIR that
+/// declares `[[public_inputs]]` they hash the declared inputs only (see
+/// [`crate::proof::pubio`]). With the typed encoding the header binds the ABI
+/// v2 bytes instead (see [`crate::evm::pubio`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputs {
+    json: String,
+    canonical: String,
+    typed: Option<Vec<u8>>,
+    structured: Option<Vec<u8>>,
+}
+
+impl PublicInputs {
+    /// Wrap a JSON document, rejecting text that is not valid JSON.
+    pub fn from_json(json: impl Into<String>) -> Result<Self, ProveError> {
+        let json = json.into();
+        let value = serde_json::from_str::<serde_json::Value>(&json)
+            .map_err(|e| ProveError::InvalidInput(format!("public inputs are not JSON: {e}")))?;
+        Ok(Self {
+            canonical: canonical_json(&value),
+            json,
+            typed: None,
+            structured: None,
+        })
+    }
+
+    /// Select the encoding that binds the proof; `Typed` requires the inputs
+    /// to match the declared `[[public_inputs]]` of `ir`. Undeclared keys
+    /// are ignored under `Json`.
+    pub fn with_encoding(self, encoding: PubIoEncoding, ir: &AirIr) -> Result<Self, ProveError> {
+        self.bind(encoding, ir, UndeclaredInputs::Ignore)
+    }
+
+    /// Bind the inputs to `ir` under `encoding`. With `Json`, an AIR that
+    /// declares `[[public_inputs]]` gets the structured per-input binding;
+    /// `undeclared` decides whether other keys are ignored or refused.
+    pub fn bind(
+        mut self,
+        encoding: PubIoEncoding,
+        ir: &AirIr,
+        undeclared: UndeclaredInputs,
+    ) -> Result<Self, ProveError> {
+        let value = self.value();
+        self.typed = None;
+        self.structured = None;
+        match encoding {
+            PubIoEncoding::Typed => {
+                self.typed =
+                    Some(pubio::encode_typed(ir, &value).map_err(|e| {
+                        ProveError::InvalidInput(format!("typed public IO: {e:#}"))
+                    })?);
+            }
+            PubIoEncoding::Json if !ir.public_inputs.is_empty() => {
+                self.structured = Some(
+                    structured_binding(&ir.public_inputs, &value, undeclared)
+                        .map_err(|e| ProveError::InvalidInput(format!("{e:#}")))?,
+                );
+            }
+            PubIoEncoding::Json => {
+                if undeclared == UndeclaredInputs::Reject {
+                    let extra = undeclared_keys(&ir.public_inputs, &value);
+                    if !extra.is_empty() {
+                        return Err(ProveError::InvalidInput(format!(
+                            "undeclared public inputs: {}",
+                            extra.join(", ")
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn encoding(&self) -> PubIoEncoding {
+        if self.typed.is_some() {
+            PubIoEncoding::Typed
+        } else {
+            PubIoEncoding::Json
+        }
+    }
+
+    /// Bytes bound by the proof header's `pubio_hash`.
+    pub fn binding(&self) -> &[u8] {
+        self.typed
+            .as_deref()
+            .or(self.structured.as_deref())
+            .unwrap_or(self.canonical.as_bytes())
+    }
+
+    pub fn as_json(&self) -> &str {
+        &self.json
+    }
+
+    /// The inputs' canonical JSON text ([`crate::proof::canonical`]).
+    pub fn canonical_json(&self) -> &str {
+        &self.canonical
+    }
+
+    /// The declared inputs of `ir` as typed values (see [`crate::io`]).
+    /// Undeclared keys are ignored; binding applies the configured policy.
+    pub fn typed(&self, ir: &AirIr) -> Result<TypedInputs, ProveError> {
+        TypedInputs::parse(&ir.public_inputs, &self.value(), UndeclaredInputs::Ignore)
+            .map_err(|e| ProveError::InvalidInput(format!("{e:#}")))
+    }
+
+    pub fn value(&self) -> serde_json::Value {
+        serde_json::from_str(&self.json).expect("validated in from_json")
+    }
+}
+
+/// Per-call state of one prove or verify, built by dispatch from the run's
+/// [`Config`](crate::config::Config). The [`Profile`] stays what the profile
+/// files and overrides say; everything that belongs to a single call is here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallContext {
+    /// [`Config::config_hash`](crate::config::Config::config_hash) of this
+    /// run, written to and checked against the proof header's `config_hash`.
+    pub config_hash: u64,
+    /// `Config::options`, validated against
+    /// [`ProverBackend::options_schema`]; `None` when no options were given.
+    pub options: Option<serde_json::Value>,
+    /// `Config::deadline`; provers that can stop early check it at phase
+    /// boundaries through [`crate::deadline::Phases`].
+    pub deadline: Option<Deadline>,
+    /// Canonical id of `Config::hash`, the hash for transcripts and
+    /// commitments; `None` defers to the AIR's `meta.hash`.
+    pub hash: Option<String>,
+}
+
+impl CallContext {
+    pub fn new(config_hash: u64) -> Self {
+        Self {
+            config_hash,
+            options: None,
+            deadline: None,
+            hash: None,
+        }
+    }
+}
+
+/// Proving half of a backend adapter.
+///
+/// `profile` is the resolved profile (overrides applied), and `ctx` carries
+/// the call's state, including the hash selected for this run. Callers normally go through
+/// [`crate::prove`], which validates the config and picks the backend from the
+/// registry.
+///
+/// The returned [`Proof`]'s body must be the backend's native proof
+/// serialization, unwrapped, so that
+/// [`ProofView::native_proof`](crate::proof::ProofView::native_proof) can be
+/// handed to the backend's own verifier.
 pub trait ProverBackend: Send + Sync {
     fn id(&self) -> &'static str; // "native@0.0" etc.
     fn capabilities(&self) -> Capabilities;
-    fn prove_stub(&self) -> Vec<u8> {
-        // Placeholder to ensure end-to-end linkage for now; real prove() lands later.
-        // Returns a deterministic short "proof" for smoke tests.
-        b"PROOF\0".to_vec()
+    fn prove(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+    ) -> Result<Proof, ProveError>;
+    /// Prove from an externally generated trace. Backends that build their own
+    /// trace keep the default.
+    fn prove_with_trace(
+        &self,
+        _ir: &AirIr,
+        _profile: &Profile,
+        _ctx: &CallContext,
+        _inputs: &PublicInputs,
+        _trace: &Trace,
+    ) -> Result<Proof, ProveError> {
+        Err(ProveError::Unsupported {
+            backend_id: self.id().to_string(),
+            feature: "proving from a trace file".to_string(),
+        })
+    }
+    /// Like [`Self::prove_with_trace`], reusing work cached from the previous
+    /// prove in `cache` and updating it. Only called when
+    /// `capabilities().incremental` is set; the proof must be byte-identical
+    /// to a full prove.
+    fn prove_incremental(
+        &self,
+        _ir: &AirIr,
+        _profile: &Profile,
+        _ctx: &CallContext,
+        _inputs: &PublicInputs,
+        _trace: &Trace,
+        _cache: &mut TraceCache,
+    ) -> Result<(Proof, Reuse), ProveError> {
+        Err(ProveError::Unsupported {
+            backend_id: self.id().to_string(),
+            feature: "incremental proving".to_string(),
+        })
+    }
+    /// JSON Schema (see [`options`]) for the opaque `Config::options` this
+    /// backend understands, handed over as [`CallContext::options`]. `None` means it takes no options, and any non-null
+    /// options are rejected.
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        None
     }
 }
 
+/// Verifying half of a backend adapter; see [`ProverBackend`].
 pub trait VerifierBackend: Send + Sync {
-    fn verify_stub(&self, proof: &[u8]) -> bool {
-        proof == b"PROOF\0"
-    }
+    fn verify(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        proof: &ProofView<'_>,
+    ) -> Result<(), VerifyError>;
+}
+
+/// Deprecation notice attached to a registered backend.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Date (YYYY-MM-DD) after which the backend may be removed.
+    pub sunset: String,
+    /// Suggested replacement backend id, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
 }
 
 /// Public info returned by listing APIs (subset of Capabilities)
@@ -36,4 +347,9 @@ pub trait VerifierBackend: Send + Sync {
 pub struct BackendInfo {
     pub id: &'static str,
     pub recursion: bool,
+    /// Aliases that resolve (possibly through other aliases) to this backend.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecation>,
 }
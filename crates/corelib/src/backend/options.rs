@@ -0,0 +1,233 @@
+//! Opaque per-call backend options (`Config::options`) and the JSON Schema
+//! subset backends use to describe them.
+//!
+//! corelib does not interpret options; it only checks them against
+//! [`crate::backend::ProverBackend::options_schema`] before dispatch and
+//! hands them to the backend through
+//! [`crate::backend::CallContext::options`].
+//!
+//! Supported keywords: `type` (a name or a list of names), `enum`,
+//! `properties`, `required`, `additionalProperties` (boolean or schema),
+//! `items`, `minimum`, `maximum`, `minLength`, `maxLength`. Unknown keywords
+//! are ignored, as JSON Schema requires.
+
+use serde_json::{Map, Value};
+
+/// Check `options` against `schema`. The error names the offending location as
+/// a JSON pointer (`/batching`, `/partitions/0`).
+pub fn validate_options(schema: &Value, options: &Value) -> Result<(), String> {
+    check(schema, options, "")
+}
+
+/// Canonical (key-sorted) JSON hashed into determinism manifests.
+pub fn options_hash(options: &Value) -> String {
+    let json = serde_json::to_string(options).expect("JSON values serialize");
+    blake3::hash(json.as_bytes()).to_hex().to_string()
+}
+
+fn check(schema: &Value, value: &Value, at: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return match schema {
+            Value::Bool(false) => Err(format!("{}: not allowed", location(at))),
+            _ => Ok(()),
+        };
+    };
+    if let Some(ty) = schema.get("type") {
+        let names: Vec<&str> = match ty {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(list) => list.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !names.iter().any(|name| has_type(value, name)) {
+            return Err(format!(
+                "{}: expected {}, got {}",
+                location(at),
+                names.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            return Err(format!(
+                "{}: {} is not one of {}",
+                location(at),
+                value,
+                Value::Array(allowed.clone())
+            ));
+        }
+    }
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+            if n < min {
+                return Err(format!(
+                    "{}: {} is below minimum {}",
+                    location(at),
+                    value,
+                    min
+                ));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+            if n > max {
+                return Err(format!(
+                    "{}: {} is above maximum {}",
+                    location(at),
+                    value,
+                    max
+                ));
+            }
+        }
+    }
+    if let Some(s) = value.as_str() {
+        let len = s.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+            if len < min {
+                return Err(format!("{}: shorter than {} characters", location(at), min));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+            if len > max {
+                return Err(format!("{}: longer than {} characters", location(at), max));
+            }
+        }
+    }
+    if let Value::Array(items) = value {
+        if let Some(item_schema) = schema.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                check(item_schema, item, &format!("{}/{}", at, i))?;
+            }
+        }
+    }
+    if let Value::Object(fields) = value {
+        check_object(schema, fields, at)?;
+    }
+    Ok(())
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    fields: &Map<String, Value>,
+    at: &str,
+) -> Result<(), String> {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !fields.contains_key(key) {
+                return Err(format!("{}: missing required '{}'", location(at), key));
+            }
+        }
+    }
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, field) in fields {
+        let path = format!("{}/{}", at, key);
+        match properties.and_then(|p| p.get(key)) {
+            Some(field_schema) => check(field_schema, field, &path)?,
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    return Err(format!("{}: unknown option", path));
+                }
+                Some(extra) => check(extra, field, &path)?,
+                None => {}
+            },
+        }
+    }
+    Ok(())
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn location(at: &str) -> &str {
+    if at.is_empty() {
+        "options"
+    } else {
+        at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "batching": { "enum": ["linear", "algebraic"] },
+                "partition_size": { "type": "integer", "minimum": 1 },
+                "tags": { "type": "array", "items": { "type": "string", "maxLength": 4 } }
+            },
+            "required": ["batching"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn accepts_conforming_options() {
+        let opts = json!({ "batching": "linear", "partition_size": 8, "tags": ["a"] });
+        validate_options(&schema(), &opts).unwrap();
+    }
+
+    #[test]
+    fn reports_the_offending_pointer() {
+        let cases = [
+            (json!([]), "options: expected object, got array"),
+            (json!({}), "options: missing required 'batching'"),
+            (
+                json!({ "batching": "fast" }),
+                "/batching: \"fast\" is not one of",
+            ),
+            (
+                json!({ "batching": "linear", "partition_size": 0 }),
+                "/partition_size: 0 is below minimum 1",
+            ),
+            (
+                json!({ "batching": "linear", "partition_size": 1.5 }),
+                "/partition_size: expected integer, got number",
+            ),
+            (
+                json!({ "batching": "linear", "tags": ["ok", "too-long"] }),
+                "/tags/1: longer than 4 characters",
+            ),
+            (
+                json!({ "batching": "linear", "threads": 4 }),
+                "/threads: unknown option",
+            ),
+        ];
+        for (opts, expected) in cases {
+            let err = validate_options(&schema(), &opts).unwrap_err();
+            assert!(err.starts_with(expected), "{err} / {expected}");
+        }
+    }
+
+    #[test]
+    fn hash_ignores_key_order() {
+        let a: Value = serde_json::from_str(r#"{"x":1,"y":2}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"y":2,"x":1}"#).unwrap();
+        assert_eq!(options_hash(&a), options_hash(&b));
+        assert_ne!(options_hash(&a), options_hash(&json!({ "x": 2, "y": 1 })));
+    }
+}
@@ -0,0 +1,70 @@
+//! Provenance of the running binary: crate version, git commit, target
+//! triple, cargo profile, rustc version and enabled features.
+//!
+//! Values are captured by `build.rs` at compile time. The git commit comes from
+//! `ZKD_GIT_SHA`, `VERGEN_GIT_SHA` or `GIT_COMMIT_HASH` (first set wins) and is
+//! `unknown` otherwise. Nothing time- or host-dependent is recorded, so the
+//! same source and toolchain always yield the same [`BuildInfo`].
+
+use serde::{Deserialize, Serialize};
+
+/// Build provenance, as printed by `zkd version --verbose` and `zkp_version`
+/// and optionally recorded in determinism manifests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub target: String,
+    pub profile: String,
+    pub rustc: String,
+    /// Enabled cargo features of `zkprov-corelib`, sorted.
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_COMMIT: &str = env!("ZKPROV_BUILD_GIT_COMMIT");
+pub const TARGET: &str = env!("ZKPROV_BUILD_TARGET");
+pub const PROFILE: &str = env!("ZKPROV_BUILD_PROFILE");
+pub const RUSTC: &str = env!("ZKPROV_BUILD_RUSTC");
+const FEATURES: &str = env!("ZKPROV_BUILD_FEATURES");
+
+/// Git commit the binary was built from, when one was supplied at build time.
+pub fn git_commit() -> Option<&'static str> {
+    (GIT_COMMIT != "unknown").then_some(GIT_COMMIT)
+}
+
+/// Build info of this binary.
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: VERSION.to_string(),
+        git_commit: GIT_COMMIT.to_string(),
+        target: TARGET.to_string(),
+        profile: PROFILE.to_string(),
+        rustc: RUSTC.to_string(),
+        features: FEATURES
+            .split(',')
+            .filter(|f| !f.is_empty())
+            .map(str::to_owned)
+            .collect(),
+    }
+}
+
+impl BuildInfo {
+    /// One `key: value` line per field, for human-readable output.
+    pub fn to_lines(&self) -> Vec<String> {
+        let features = if self.features.is_empty() {
+            "none".to_string()
+        } else {
+            self.features.join(",")
+        };
+        vec![
+            format!("version: {}", self.version),
+            format!("commit: {}", self.git_commit),
+            format!("target: {}", self.target),
+            format!("profile: {}", self.profile),
+            format!("rustc: {}", self.rustc),
+            format!("features: {}", features),
+        ]
+    }
+}
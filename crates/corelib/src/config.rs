@@ -1,5 +1,18 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 
+use crate::air::AirMeta;
+use crate::crypto::registry::canonical_hash_id;
+use crate::deadline::Deadline;
+use crate::errors::CapabilityError;
+use crate::evm::pubio::PubIoEncoding;
+use crate::profile::ProfileOverrides;
+use crate::proof::hash64;
+use crate::proof::pubio::UndeclaredInputs;
+use crate::registry;
+
 /// User/CLI-selected configuration to be validated against a backend's capabilities.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Config {
@@ -9,6 +22,31 @@ pub struct Config {
     pub fri_arity: u32,         // e.g., 2 or 4
     pub recursion_needed: bool, // true if caller intends to use recursion features
     pub profile_id: String,     // e.g., "balanced"
+    /// Ad-hoc profile parameter overrides (e.g., fri_queries=40).
+    #[serde(default, skip_serializing_if = "ProfileOverrides::is_empty")]
+    pub profile_overrides: ProfileOverrides,
+    /// Public IO encoding bound by the proof header (`json` unless selected).
+    #[serde(default, skip_serializing_if = "PubIoEncoding::is_json")]
+    pub pubio_encoding: PubIoEncoding,
+    /// Whether JSON keys the AIR does not declare as public inputs are
+    /// ignored (the default) or refused. Not part of `config_hash`: they are
+    /// never bound, so the policy only decides which inputs are accepted.
+    #[serde(default, skip_serializing_if = "UndeclaredInputs::is_ignore")]
+    pub undeclared_inputs: UndeclaredInputs,
+    /// Backend-specific knobs corelib does not model (e.g. partitioning,
+    /// batching), passed through opaquely after validation against the
+    /// backend's options schema. `null` when unused.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub options: serde_json::Value,
+    /// How the program's `meta.backend` / `meta.profile` hints are used
+    /// (see [`Config::apply_air_hints`]). Not part of `config_hash`: it only
+    /// decides how the backend and profile are chosen, and those are bound.
+    #[serde(default, skip_serializing_if = "AirHints::is_ignore")]
+    pub air_hints: AirHints,
+    /// Wall-clock proving budget, checked by backends at phase boundaries.
+    /// Local to this run: never serialized and not part of `config_hash`.
+    #[serde(skip)]
+    pub deadline: Option<Deadline>,
 }
 
 impl Config {
@@ -27,6 +65,170 @@ impl Config {
             fri_arity,
             recursion_needed,
             profile_id: profile_id.into(),
+            profile_overrides: ProfileOverrides::new(),
+            pubio_encoding: PubIoEncoding::Json,
+            undeclared_inputs: UndeclaredInputs::Ignore,
+            options: serde_json::Value::Null,
+            air_hints: AirHints::Ignore,
+            deadline: None,
+        }
+    }
+
+    /// Attach profile parameter overrides; they are re-validated by `validate_config`.
+    pub fn with_profile_overrides(mut self, overrides: ProfileOverrides) -> Self {
+        self.profile_overrides = overrides;
+        self
+    }
+
+    pub fn with_pubio_encoding(mut self, encoding: PubIoEncoding) -> Self {
+        self.pubio_encoding = encoding;
+        self
+    }
+
+    pub fn with_undeclared_inputs(mut self, policy: UndeclaredInputs) -> Self {
+        self.undeclared_inputs = policy;
+        self
+    }
+
+    /// Attach backend options; they are validated by `validate_config`.
+    pub fn with_options(mut self, options: serde_json::Value) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_air_hints(mut self, mode: AirHints) -> Self {
+        self.air_hints = mode;
+        self
+    }
+
+    /// Apply `meta`'s backend and profile hints under [`Config::air_hints`],
+    /// returning which of `backend` and `profile` were taken from them.
+    ///
+    /// An empty `backend_id` (or `auto`) and an empty `profile_id` are unset.
+    /// Precedence is: an explicit value, then the AIR's hint; a value that is
+    /// still unset is [`CapabilityError::AirHintMissing`]. Under
+    /// [`AirHints::Strict`] an explicit value must also name what the hint
+    /// names (backend aliases resolved), or it is
+    /// [`CapabilityError::AirHintConflict`]. [`AirHints::Ignore`] changes
+    /// nothing.
+    pub fn apply_air_hints(
+        &mut self,
+        meta: &AirMeta,
+    ) -> Result<Vec<&'static str>, CapabilityError> {
+        if self.air_hints.is_ignore() {
+            return Ok(Vec::new());
+        }
+        let strict = self.air_hints == AirHints::Strict;
+        let mut applied = Vec::new();
+        let backend_unset = self.backend_id.is_empty() || self.backend_id == registry::AUTO_BACKEND;
+        let profile_unset = self.profile_id.is_empty();
+        let same_backend = |a: &str, b: &str| {
+            let resolve = |id: &str| registry::resolve_backend_id(id).unwrap_or(id).to_string();
+            resolve(a) == resolve(b)
+        };
+        for (what, value, hint, unset) in [
+            (
+                "backend",
+                &mut self.backend_id,
+                meta.backend.as_deref(),
+                backend_unset,
+            ),
+            (
+                "profile",
+                &mut self.profile_id,
+                meta.profile.as_deref(),
+                profile_unset,
+            ),
+        ] {
+            match hint {
+                Some(hint) if unset => {
+                    *value = hint.to_string();
+                    applied.push(what);
+                }
+                Some(hint) if strict => {
+                    let same = if what == "backend" {
+                        same_backend(value, hint)
+                    } else {
+                        value == hint
+                    };
+                    if !same {
+                        return Err(CapabilityError::AirHintConflict {
+                            what,
+                            config: value.clone(),
+                            air: hint.to_string(),
+                        });
+                    }
+                }
+                None if value.is_empty() => return Err(CapabilityError::AirHintMissing { what }),
+                _ => {}
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Abort proving with `ProveError::TimedOut` once `deadline` has passed.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// 64-bit commitment to every field of this config, bound by the proof
+    /// header's `config_hash` so a proof verifies only under the config it
+    /// was produced with. Backend and hash aliases are resolved first, so
+    /// `blake3` and its aliases bind the same proof.
+    pub fn config_hash(&self) -> u64 {
+        let backend = registry::resolve_backend_id(&self.backend_id)
+            .map(str::to_string)
+            .unwrap_or_else(|_| self.backend_id.clone());
+        let hash = canonical_hash_id(&self.hash).unwrap_or(&self.hash);
+        // Keys serialize sorted, so the encoding is canonical.
+        let canonical = serde_json::json!({
+            "backend": backend,
+            "field": self.field,
+            "hash": hash,
+            "fri_arity": self.fri_arity,
+            "recursion_needed": self.recursion_needed,
+            "profile": self.profile_id,
+            "profile_overrides": self.profile_overrides,
+            "pubio_encoding": self.pubio_encoding,
+            "options": self.options,
+        });
+        hash64("CONFIG", canonical.to_string().as_bytes())
+    }
+}
+
+/// Whether the program's `meta.backend` / `meta.profile` hints choose the
+/// backend and profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AirHints {
+    /// Hints are informational.
+    #[default]
+    Ignore,
+    /// Hints fill an unset backend or profile; explicit values win.
+    Respect,
+    /// Like `Respect`, and an explicit value contradicting a hint is an error.
+    Strict,
+}
+
+impl AirHints {
+    pub fn is_ignore(&self) -> bool {
+        *self == Self::Ignore
+    }
+}
+
+impl FromStr for AirHints {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "ignore" => Ok(Self::Ignore),
+            "respect" => Ok(Self::Respect),
+            "strict" => Ok(Self::Strict),
+            other => Err(anyhow!(
+                "unknown AIR hint mode '{}' (expected ignore, respect or strict)",
+                other
+            )),
         }
     }
 }
@@ -1,9 +1,165 @@
-//! Hash-to-field for a 254-bit prime (BN254-like placeholder).
-//! We implement a simple wide-reduce from 256-bit (or 512-bit) digests.
+//! Prime fields and hash-to-field.
+//!
+//! [`PrimeField`] is the arithmetic every field-generic caller goes through:
+//! modular add/sub/mul/neg/inverse on `BigUint` elements, fixed-width
+//! canonical bytes, wide-reduce hash-to-field and Montgomery form. The
+//! fields are unit types ([`Prime254`], [`Goldilocks`], [`BabyBear`]) looked
+//! up by their AIR `field` id with [`field_by_id`], so adding a field is one
+//! impl plus one entry in [`FIELDS`].
 
+use anyhow::{bail, Result};
 use num_bigint::BigUint;
 use num_traits::One;
 
+/// A prime field `Z/pZ`. Elements are `BigUint`s in `[0, p)`; arguments
+/// outside that range are reduced first.
+pub trait PrimeField: Send + Sync {
+    /// The AIR `field` id, e.g. `"Goldilocks"`.
+    fn id(&self) -> &'static str;
+
+    fn modulus(&self) -> BigUint;
+
+    /// Width of [`canonical_bytes`](Self::canonical_bytes).
+    fn byte_len(&self) -> usize {
+        (self.modulus().bits() as usize).div_ceil(8)
+    }
+
+    fn reduce(&self, x: &BigUint) -> BigUint {
+        x % self.modulus()
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + b) % self.modulus()
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        let p = self.modulus();
+        (a % &p + &p - b % &p) % p
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a * b) % self.modulus()
+    }
+
+    fn neg(&self, a: &BigUint) -> BigUint {
+        let p = self.modulus();
+        (&p - a % &p) % p
+    }
+
+    /// `a^-1`, or `None` when `a` has no inverse (zero, or a factor of a
+    /// placeholder modulus that is not actually prime).
+    fn inverse(&self, a: &BigUint) -> Option<BigUint> {
+        let p = self.modulus();
+        (a % &p).modinv(&p)
+    }
+
+    /// Big-endian, left-padded to [`byte_len`](Self::byte_len).
+    fn canonical_bytes(&self, a: &BigUint) -> Vec<u8> {
+        let len = self.byte_len();
+        let bytes = self.reduce(a).to_bytes_be();
+        let mut out = vec![0u8; len];
+        out[len - bytes.len()..].copy_from_slice(&bytes);
+        out
+    }
+
+    /// Inverse of [`canonical_bytes`](Self::canonical_bytes): exactly
+    /// [`byte_len`](Self::byte_len) bytes encoding a value below `p`.
+    fn read_canonical(&self, bytes: &[u8]) -> Result<BigUint> {
+        if bytes.len() != self.byte_len() {
+            bail!(
+                "{} element must be {} bytes, got {}",
+                self.id(),
+                self.byte_len(),
+                bytes.len()
+            );
+        }
+        let x = BigUint::from_bytes_be(bytes);
+        if x >= self.modulus() {
+            bail!("{} element is not below the modulus", self.id());
+        }
+        Ok(x)
+    }
+
+    /// Wide reduce of big-endian `bytes` (e.g. a digest) into the field.
+    fn hash_to_field(&self, bytes: &[u8]) -> BigUint {
+        BigUint::from_bytes_be(bytes) % self.modulus()
+    }
+
+    /// Montgomery radix `R = 2^(64·limbs) mod p`, for 64-bit limbs.
+    fn montgomery_r(&self) -> BigUint {
+        let limbs = (self.modulus().bits() as usize).div_ceil(64);
+        (BigUint::one() << (64 * limbs)) % self.modulus()
+    }
+
+    /// `a·R mod p`.
+    fn montgomery_encode(&self, a: &BigUint) -> BigUint {
+        self.mul(a, &self.montgomery_r())
+    }
+
+    /// `m·R^-1 mod p`.
+    fn montgomery_decode(&self, m: &BigUint) -> BigUint {
+        let r_inv = self
+            .inverse(&self.montgomery_r())
+            .expect("R is a power of two and the modulus is odd");
+        self.mul(m, &r_inv)
+    }
+}
+
+/// Placeholder Prime254: `2^254 - 127·2^120 + 1` (see [`prime254_modulus`]).
+/// That modulus is divisible by 11, so multiples of 11 have no
+/// [`inverse`](PrimeField::inverse); everything else behaves as a field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Prime254;
+
+impl PrimeField for Prime254 {
+    fn id(&self) -> &'static str {
+        "Prime254"
+    }
+
+    fn modulus(&self) -> BigUint {
+        prime254_modulus()
+    }
+}
+
+/// Goldilocks, `2^64 - 2^32 + 1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Goldilocks;
+
+impl PrimeField for Goldilocks {
+    fn id(&self) -> &'static str {
+        "Goldilocks"
+    }
+
+    fn modulus(&self) -> BigUint {
+        goldilocks_modulus()
+    }
+}
+
+/// BabyBear prime `2^31 - 2^27 + 1`.
+pub const BABYBEAR_MODULUS: u32 = 0x7800_0001;
+
+/// BabyBear, [`BABYBEAR_MODULUS`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BabyBear;
+
+impl PrimeField for BabyBear {
+    fn id(&self) -> &'static str {
+        "BabyBear"
+    }
+
+    fn modulus(&self) -> BigUint {
+        BigUint::from(BABYBEAR_MODULUS)
+    }
+}
+
+/// Every field known to corelib.
+pub static FIELDS: &[&dyn PrimeField] = &[&Prime254, &Goldilocks, &BabyBear];
+
+/// The field with AIR `field` id `id`.
+pub fn field_by_id(id: &str) -> Option<&'static dyn PrimeField> {
+    FIELDS.iter().copied().find(|f| f.id() == id)
+}
+
 /// Prime modulus (placeholder Prime254: 2^254 - 127 * 2^120 + 1).
 /// This is NOT BN254; it's a "Prime254" placeholder used across the scaffold.
 /// Replace with the exact field modulus when wiring real backends.
@@ -13,11 +169,17 @@ pub fn prime254_modulus() -> BigUint {
     (two.pow(254) - (BigUint::from(127u32) * two.pow(120))) + BigUint::one()
 }
 
+/// Goldilocks prime `2^64 - 2^32 + 1`.
+pub const GOLDILOCKS_MODULUS: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// [`GOLDILOCKS_MODULUS`] as a `BigUint`, for the generic interpreters.
+pub fn goldilocks_modulus() -> BigUint {
+    BigUint::from(GOLDILOCKS_MODULUS)
+}
+
 /// Reduce arbitrary bytes to field element in [0, p).
 pub fn reduce_to_prime254(bytes: &[u8]) -> BigUint {
-    let p = prime254_modulus();
-    let x = BigUint::from_bytes_be(bytes);
-    x % p
+    Prime254.hash_to_field(bytes)
 }
 
 /// Convenience: hash-to-field from a 32-byte digest (big-endian)
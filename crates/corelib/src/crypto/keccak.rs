@@ -1,6 +1,15 @@
-//! Keccak-256 (SHA3-256 without padding change) as Hash32.
+//! Keccak-256 (SHA3-256 without padding change) as Hash32, plus
+//! [`KeccakHasher`], the transcript/Merkle hasher shape STARK backends bind to.
+//!
+//! Digest parity with native: `KeccakHasher::hash(b)` equals the EVM's
+//! `keccak256(b)` and `hash_one_shot::<Keccak256>(b)`; `merge` equals
+//! `keccak256(abi.encodePacked(left, right))`, so a Solidity verifier can
+//! recompute Merkle paths. Native roots (`crypto::merkle`) add `"LEAF"` /
+//! `"NODE2"` domain labels and the native backend labels every input, so
+//! native and Winterfell roots over the same data intentionally differ; only
+//! the primitive is shared.
 
-use crate::crypto::hash::Hash32;
+use crate::crypto::hash::{hash_one_shot, Hash32};
 use tiny_keccak::{Hasher as TKHasher, Keccak};
 
 pub struct Keccak256 {
@@ -26,10 +35,54 @@ impl Hash32 for Keccak256 {
     }
 }
 
+/// Keccak-256 with Winterfell's `Hasher` semantics (`winter_crypto::Hasher`
+/// over a 32-byte digest): a Winterfell backend implements that trait by
+/// forwarding to these functions.
+pub struct KeccakHasher;
+
+impl KeccakHasher {
+    /// Collision resistance in bits (half the digest size).
+    pub const COLLISION_RESISTANCE: u32 = 128;
+
+    pub fn hash(bytes: &[u8]) -> [u8; 32] {
+        hash_one_shot::<Keccak256>(bytes)
+    }
+
+    /// `keccak256(left || right)`: one Merkle node.
+    pub fn merge(values: &[[u8; 32]; 2]) -> [u8; 32] {
+        Self::merge_many(values)
+    }
+
+    pub fn merge_many(values: &[[u8; 32]]) -> [u8; 32] {
+        let mut h = Keccak256::new();
+        for v in values {
+            h.update(v);
+        }
+        h.finalize()
+    }
+
+    /// `keccak256(seed || value as u64 little-endian)`: transcript reseeding
+    /// and proof-of-work grinding.
+    pub fn merge_with_int(seed: [u8; 32], value: u64) -> [u8; 32] {
+        let mut h = Keccak256::new();
+        h.update(&seed);
+        h.update(&value.to_le_bytes());
+        h.finalize()
+    }
+
+    /// Hash field elements given in their canonical byte encoding.
+    pub fn hash_elements<E: AsRef<[u8]>>(elements: &[E]) -> [u8; 32] {
+        let mut h = Keccak256::new();
+        for e in elements {
+            h.update(e.as_ref());
+        }
+        h.finalize()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::hash::hash_one_shot;
 
     // Keccak-256("") =
     // c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
@@ -40,6 +93,25 @@ mod tests {
             .unwrap();
         assert_eq!(got, exp.as_slice());
     }
+
+    #[test]
+    fn keccak_hasher_matches_evm_packed_encoding() {
+        let (a, b) = (KeccakHasher::hash(b"left"), KeccakHasher::hash(b"right"));
+        assert_eq!(KeccakHasher::hash(b""), hash_one_shot::<Keccak256>(b""));
+        assert_eq!(
+            KeccakHasher::merge(&[a, b]),
+            hash_one_shot::<Keccak256>(&[a, b].concat())
+        );
+        assert_ne!(KeccakHasher::merge(&[a, b]), KeccakHasher::merge(&[b, a]));
+        assert_eq!(
+            KeccakHasher::merge_with_int(a, 7),
+            hash_one_shot::<Keccak256>(&[&a[..], &7u64.to_le_bytes()].concat())
+        );
+        assert_eq!(
+            KeccakHasher::hash_elements(&[1u64.to_le_bytes(), 2u64.to_le_bytes()]),
+            KeccakHasher::hash(&[1u64.to_le_bytes(), 2u64.to_le_bytes()].concat())
+        );
+    }
 }
 
 // lightweight hex for test only
@@ -1,7 +1,7 @@
 //! Crypto primitives surface area.
 //! Phase-0 provides generic hash traits, BLAKE3 and Keccak-256 implementations,
-//! placeholder sponge-style hashes, and hash-to-field for the 254-bit prime we
-//! use in stubs.
+//! placeholder sponge-style hashes, and the `PrimeField` arithmetic (with
+//! hash-to-field) for Prime254, Goldilocks and BabyBear.
 
 pub mod blake3;
 pub mod field;
@@ -11,3 +11,4 @@ pub mod merkle;
 pub mod poseidon2;
 pub mod registry;
 pub mod rescue;
+pub mod vectors;
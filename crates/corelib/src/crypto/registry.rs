@@ -1,20 +1,71 @@
 //! String-id -> Hash32 mapping and convenience helpers.
 
 use crate::crypto::blake3::Blake3;
-use crate::crypto::hash::hash_labeled;
+use crate::crypto::hash::{hash_labeled, Hash32};
 use crate::crypto::keccak::Keccak256;
 use crate::crypto::poseidon2::Poseidon2;
 use crate::crypto::rescue::Rescue;
+use serde::Serialize;
+use std::io::{self, Read};
 
-fn normalize(id: &str) -> String {
-    id.trim().to_ascii_lowercase()
+/// A supported hash id with its accepted aliases and how it behaves when
+/// re-proven inside a circuit (recursion, on-chain verification).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HashInfo {
+    pub id: &'static str,
+    pub aliases: &'static [&'static str],
+    /// Arithmetization-friendly (algebraic) hashes are cheap in-circuit.
+    pub algebraic: bool,
+    pub in_circuit: &'static str,
+}
+
+/// Every hash id the registry knows, in canonical spelling.
+pub const HASHES: &[HashInfo] = &[
+    HashInfo {
+        id: "blake3",
+        aliases: &[],
+        algebraic: false,
+        in_circuit: "bitwise (32-bit ARX); expensive in-circuit, fast natively",
+    },
+    HashInfo {
+        id: "keccak256",
+        aliases: &["keccak", "keccak-256"],
+        algebraic: false,
+        in_circuit: "bitwise (64-bit lanes); very expensive in-circuit, but matches the EVM                      KECCAK256 opcode, so Solidity verifiers recompute commitments cheaply",
+    },
+    HashInfo {
+        id: "poseidon2",
+        aliases: &[],
+        algebraic: true,
+        in_circuit: "algebraic sponge; cheap in-circuit, preferred for recursion",
+    },
+    HashInfo {
+        id: "rescue",
+        aliases: &[],
+        algebraic: true,
+        in_circuit: "algebraic sponge; cheap in-circuit, slower natively than poseidon2",
+    },
+];
+
+/// Registry entry for `id` or one of its aliases (case-insensitive).
+pub fn hash_info(id: &str) -> Option<&'static HashInfo> {
+    let id = id.trim().to_ascii_lowercase();
+    HASHES
+        .iter()
+        .find(|h| h.id == id || h.aliases.contains(&id.as_str()))
+}
+
+/// Canonical id for `id` (`"keccak"` -> `"keccak256"`), as listed in
+/// backend capabilities.
+pub fn canonical_hash_id(id: &str) -> Option<&'static str> {
+    hash_info(id).map(|h| h.id)
 }
 
 /// Return H(label || data) for the given hash id.
 ///
-/// Supported ids: "blake3", "keccak256", "poseidon2", "rescue".
+/// Supported ids: "blake3", "keccak256" (alias "keccak"), "poseidon2", "rescue".
 pub fn hash32_by_id(id: &str, label: &str, data: &[u8]) -> Option<[u8; 32]> {
-    match normalize(id).as_str() {
+    match canonical_hash_id(id)? {
         "blake3" => Some(hash_labeled::<Blake3>(label, data)),
         "keccak256" => Some(hash_labeled::<Keccak256>(label, data)),
         "poseidon2" => Some(hash_labeled::<Poseidon2>(label, data)),
@@ -23,6 +74,36 @@ pub fn hash32_by_id(id: &str, label: &str, data: &[u8]) -> Option<[u8; 32]> {
     }
 }
 
+/// Stream `reader` through the hash `id` without a label, returning the
+/// digest and the number of bytes read; `None` for an unknown id. Memory use
+/// is constant, so inputs of any size can be hashed.
+pub fn hash32_reader_by_id(id: &str, reader: impl Read) -> io::Result<Option<([u8; 32], u64)>> {
+    Ok(Some(match canonical_hash_id(id) {
+        Some("blake3") => stream::<Blake3>(reader)?,
+        Some("keccak256") => stream::<Keccak256>(reader)?,
+        Some("poseidon2") => stream::<Poseidon2>(reader)?,
+        Some("rescue") => stream::<Rescue>(reader)?,
+        _ => return Ok(None),
+    }))
+}
+
+fn stream<H: Hash32>(mut reader: impl Read) -> io::Result<([u8; 32], u64)> {
+    let mut hasher = H::new();
+    let mut buf = vec![0u8; 1 << 16];
+    let mut len = 0u64;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buf[..n]);
+        len += n as u64;
+    }
+    Ok((hasher.finalize(), len))
+}
+
 /// Convenience helper deriving a u64 from the first 8 bytes (little-endian).
 pub fn hash64_by_id(id: &str, label: &str, data: &[u8]) -> Option<u64> {
     hash32_by_id(id, label, data).map(|digest| {
@@ -44,6 +125,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keccak_aliases_resolve_to_keccak256() {
+        for alias in ["keccak", "KECCAK-256", " keccak256 "] {
+            assert_eq!(canonical_hash_id(alias), Some("keccak256"));
+            assert_eq!(
+                hash32_by_id(alias, "LBL", b"data"),
+                hash32_by_id("keccak256", "LBL", b"data")
+            );
+        }
+        assert!(!hash_info("keccak").unwrap().algebraic);
+        assert!(hash_info("poseidon2").unwrap().algebraic);
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let data = vec![7u8; 200_000];
+        for id in ["blake3", "keccak256", "poseidon2", "rescue"] {
+            let (digest, len) = hash32_reader_by_id(id, &data[..]).unwrap().unwrap();
+            assert_eq!(len, data.len() as u64);
+            assert_eq!(Some(digest), hash32_by_id(id, "", &data));
+        }
+        assert!(hash32_reader_by_id("unknown", &data[..]).unwrap().is_none());
+    }
+
     #[test]
     fn registry_unknown_hash_returns_none() {
         assert!(hash32_by_id("unknown", "LBL", b"data").is_none());
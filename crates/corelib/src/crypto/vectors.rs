@@ -0,0 +1,194 @@
+//! Deterministic test vectors for the hashes and commitments zkd binds, for
+//! implementations in other languages to check compatibility against.
+//!
+//! Every registered hash is run over fixed messages under the labels zkd
+//! uses (`hash32` is `H(label || msg)`, `hash64` its first 8 bytes read
+//! little-endian), through the Pedersen placeholder, and through arity-2
+//! and arity-4 Merkle trees over fixed leaf sets. Byte strings and digests
+//! are `0x`-prefixed lowercase hex; `hash64` is 16 hex digits so readers
+//! without 64-bit integers lose nothing.
+
+use serde::Serialize;
+
+use crate::crypto::blake3::Blake3;
+use crate::crypto::hash::Hash32;
+use crate::crypto::keccak::Keccak256;
+use crate::crypto::merkle::{root_arity2, root_arity4};
+use crate::crypto::poseidon2::Poseidon2;
+use crate::crypto::registry::{canonical_hash_id, hash32_by_id, HASHES};
+use crate::crypto::rescue::Rescue;
+use crate::gadgets::commitment::{
+    CommitmentScheme32, PedersenParams, PedersenPlaceholder, Witness,
+};
+
+/// Schema id of [`TestVectors`]; bumped only on incompatible changes.
+pub const SCHEMA: &str = "zkd-vectors/1";
+
+/// Labels zkd hashes under: proof header fields, trace commitments, Merkle
+/// leaves, plus the empty label.
+pub const LABELS: &[&str] = &[
+    "",
+    "BACKEND",
+    "PROFILE",
+    "PUBIO",
+    "TRACE.COL",
+    "TRACE.ROOT",
+    "LEAF",
+];
+
+/// Leaf counts of the Merkle vectors, covering the odd-width padding rules.
+pub const MERKLE_LEAF_COUNTS: &[usize] = &[1, 2, 3, 4, 5, 8, 17];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestVectors {
+    pub schema: &'static str,
+    pub hashes: Vec<HashVector>,
+    pub pedersen: Vec<CommitmentVector>,
+    pub merkle: Vec<MerkleVector>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HashVector {
+    pub hash: &'static str,
+    pub label: &'static str,
+    pub msg: String,
+    pub hash32: String,
+    pub hash64: String,
+}
+
+/// `H("PEDERSEN" || len(msg) || msg || len(blind) || blind)`, lengths as
+/// u64 little-endian.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitmentVector {
+    pub hash: &'static str,
+    pub msg: String,
+    pub blind: String,
+    pub commitment: String,
+}
+
+/// Root over `leaves` (see [`crate::crypto::merkle`] for the domain
+/// separation and padding).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MerkleVector {
+    pub hash: &'static str,
+    pub arity: u32,
+    pub leaves: Vec<String>,
+    pub root: String,
+}
+
+/// Messages every hash is run over.
+pub fn messages() -> Vec<Vec<u8>> {
+    vec![
+        Vec::new(),
+        b"abc".to_vec(),
+        b"The quick brown fox jumps over the lazy dog".to_vec(),
+        (0u8..=255).collect(),
+    ]
+}
+
+/// `(msg, blind)` pairs for the Pedersen placeholder.
+pub fn commitment_witnesses() -> Vec<(Vec<u8>, Vec<u8>)> {
+    vec![
+        (Vec::new(), Vec::new()),
+        (b"hello".to_vec(), b"blind".to_vec()),
+        (b"hello".to_vec(), vec![0u8; 32]),
+        ((0u8..64).collect(), (0u8..32).rev().collect()),
+    ]
+}
+
+/// Leaf `i` of every Merkle vector: `leaf-<i>` as ASCII.
+pub fn merkle_leaves(count: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|i| format!("leaf-{i}").into_bytes())
+        .collect()
+}
+
+/// Generate the full vector set. Output is identical on every run and
+/// platform.
+pub fn generate() -> TestVectors {
+    let ids = || HASHES.iter().map(|h| h.id);
+    let mut hashes = Vec::new();
+    for hash in ids() {
+        for &label in LABELS {
+            for msg in messages() {
+                let digest = hash32_by_id(hash, label, &msg).expect("registered hash");
+                let mut low = [0u8; 8];
+                low.copy_from_slice(&digest[..8]);
+                hashes.push(HashVector {
+                    hash,
+                    label,
+                    msg: hex(&msg),
+                    hash32: hex(&digest),
+                    hash64: format!("0x{:016x}", u64::from_le_bytes(low)),
+                });
+            }
+        }
+    }
+
+    let mut pedersen = Vec::new();
+    for hash in ids() {
+        let scheme = PedersenPlaceholder::new(PedersenParams {
+            hash_id: hash.to_string(),
+        });
+        for (msg, blind) in commitment_witnesses() {
+            let commitment = scheme
+                .commit(&Witness {
+                    msg: &msg,
+                    blind: &blind,
+                })
+                .expect("registered hash");
+            pedersen.push(CommitmentVector {
+                hash,
+                msg: hex(&msg),
+                blind: hex(&blind),
+                commitment: hex(commitment.as_bytes()),
+            });
+        }
+    }
+
+    let mut merkle = Vec::new();
+    for hash in ids() {
+        for arity in [2, 4] {
+            for &count in MERKLE_LEAF_COUNTS {
+                let leaves = merkle_leaves(count);
+                let root = merkle_root_by_id(hash, arity, &leaves).expect("registered hash");
+                merkle.push(MerkleVector {
+                    hash,
+                    arity,
+                    leaves: leaves.iter().map(|l| hex(l)).collect(),
+                    root: hex(&root),
+                });
+            }
+        }
+    }
+
+    TestVectors {
+        schema: SCHEMA,
+        hashes,
+        pedersen,
+        merkle,
+    }
+}
+
+/// Merkle root of `leaves` under hash `id` and arity 2 or 4; `None` for an
+/// unknown hash or arity.
+pub fn merkle_root_by_id(id: &str, arity: u32, leaves: &[Vec<u8>]) -> Option<[u8; 32]> {
+    fn root<H: Hash32>(arity: u32, leaves: &[Vec<u8>]) -> Option<[u8; 32]> {
+        match arity {
+            2 => Some(root_arity2::<H>(leaves)),
+            4 => Some(root_arity4::<H>(leaves)),
+            _ => None,
+        }
+    }
+    match canonical_hash_id(id)? {
+        "blake3" => root::<Blake3>(arity, leaves),
+        "keccak256" => root::<Keccak256>(arity, leaves),
+        "poseidon2" => root::<Poseidon2>(arity, leaves),
+        "rescue" => root::<Rescue>(arity, leaves),
+        _ => None,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    format!("0x{}", alloy_primitives::hex::encode(bytes))
+}
@@ -0,0 +1,121 @@
+//! Wall-clock proving budgets.
+//!
+//! A [`Deadline`] travels on `Config::deadline` to the backend (as
+//! `Profile::deadline`). Backends cannot stop mid-phase, so they check it at
+//! phase boundaries through [`Phases`]: once the budget is spent the next
+//! [`Phases::finish`] fails with [`ProveError::TimedOut`], carrying the
+//! phases completed so far and their timings.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::errors::ProveError;
+
+/// A wall-clock budget measured from when it was created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    started: Instant,
+    budget: Duration,
+}
+
+impl Deadline {
+    /// Start a budget of `budget` from now.
+    pub fn after(budget: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            budget,
+        }
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn expired(&self) -> bool {
+        self.elapsed() >= self.budget
+    }
+}
+
+/// Wall-clock time spent in one completed prover phase.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PhaseStat {
+    pub phase: String,
+    pub elapsed_ms: u64,
+}
+
+/// Phase timings of one proving run, checked against an optional deadline.
+#[derive(Debug)]
+pub struct Phases {
+    deadline: Option<Deadline>,
+    mark: Instant,
+    stats: Vec<PhaseStat>,
+}
+
+impl Phases {
+    pub fn new(deadline: Option<Deadline>) -> Self {
+        Self {
+            deadline,
+            mark: Instant::now(),
+            stats: Vec::new(),
+        }
+    }
+
+    /// Record the end of `phase`; fails with [`ProveError::TimedOut`] when
+    /// the deadline has passed, so the caller stops before the next phase.
+    pub fn finish(&mut self, phase: &str) -> Result<(), ProveError> {
+        let now = Instant::now();
+        self.stats.push(PhaseStat {
+            phase: phase.to_string(),
+            elapsed_ms: millis(now - self.mark),
+        });
+        self.mark = now;
+        match self.deadline {
+            Some(deadline) if deadline.expired() => Err(ProveError::TimedOut {
+                phase: phase.to_string(),
+                elapsed_ms: millis(deadline.elapsed()),
+                budget_ms: millis(deadline.budget()),
+                phases: self.stats.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn stats(&self) -> &[PhaseStat] {
+        &self.stats
+    }
+}
+
+fn millis(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Parse a duration such as `90` (seconds), `90s`, `1500ms`, `5m`, `2h` or `30d`.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, unit) = s.split_at(split);
+    let n: u64 = digits
+        .parse()
+        .map_err(|_| anyhow!("invalid duration '{s}' (expected e.g. 90s, 1500ms, 5m, 2h, 30d)"))?;
+    let secs = |mult: u64| {
+        n.checked_mul(mult)
+            .map(Duration::from_secs)
+            .ok_or_else(|| anyhow!("duration '{s}' is too large"))
+    };
+    match unit {
+        "ms" => Ok(Duration::from_millis(n)),
+        "" | "s" => secs(1),
+        "m" => secs(60),
+        "h" => secs(3600),
+        "d" => secs(86_400),
+        other => Err(anyhow!(
+            "unknown duration unit '{other}' in '{s}' (use ms, s, m, h or d)"
+        )),
+    }
+}
@@ -0,0 +1,208 @@
+//! Determinism audit: where a proof could stop being a function of the
+//! config, program and inputs alone, and a check that it is one on this host.
+//!
+//! zkd promises byte-identical proofs for the same config, AIR and inputs,
+//! on every platform and whatever the scheduling. The audit has two halves:
+//!
+//! * [`inspect`] reports what could break that promise without running
+//!   anything: the code paths a backend declares in
+//!   [`Capabilities::nondeterminism`] (reductions ordered by thread
+//!   scheduling, hash map iteration, platform intrinsics), and the features
+//!   and CPU target features this binary was built with;
+//! * [`audit`] proves repeatedly under different thread counts and compares
+//!   the EVM digests `D`. A run with `n` threads proves `n` times at once,
+//!   each on its own thread; every proof also gets fresh hash map seeds. All
+//!   digests of all runs must be equal.
+//!
+//! A clean audit is evidence, not proof: it only exercises this host, this
+//! build and these inputs.
+
+use std::thread;
+
+use serde::Serialize;
+
+use crate::air::AirIr;
+use crate::backend::{Capabilities, PublicInputs};
+use crate::buildinfo;
+use crate::config::Config;
+use crate::errors::ProveError;
+use crate::evm::digest::digest_D;
+use crate::registry;
+
+/// CPU target features worth reporting, with whether this build enables them.
+const TARGET_FEATURES: &[(&str, bool)] = &[
+    ("sse2", cfg!(target_feature = "sse2")),
+    ("avx2", cfg!(target_feature = "avx2")),
+    ("avx512f", cfg!(target_feature = "avx512f")),
+    ("fma", cfg!(target_feature = "fma")),
+    ("aes", cfg!(target_feature = "aes")),
+    ("neon", cfg!(target_feature = "neon")),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Context for the audit; not a nondeterminism source by itself.
+    Info,
+    /// A code path that can make proofs differ between runs or hosts.
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// One result of [`inspect`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// A [`crate::backend::NONDETERMINISM_SOURCES`] entry, or `"features"`.
+    pub source: &'static str,
+    /// `build`, or `backend <id>`.
+    pub origin: String,
+    pub detail: String,
+}
+
+/// Proofs made concurrently by `threads` threads, in thread order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditRun {
+    pub threads: usize,
+    /// `0x`-hex EVM digests `D`.
+    pub digests: Vec<String>,
+}
+
+/// Result of [`audit`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditReport {
+    pub backend: String,
+    pub profile: String,
+    pub findings: Vec<Finding>,
+    pub runs: Vec<AuditRun>,
+    /// Every digest of every run is the same.
+    pub deterministic: bool,
+}
+
+impl AuditReport {
+    /// Number of [`Severity::Warning`] findings.
+    pub fn warnings(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Warning)
+            .count()
+    }
+}
+
+/// Static findings for `backend_id` (an id, alias or name) and this build.
+pub fn inspect(backend_id: &str) -> Result<Vec<Finding>, ProveError> {
+    registry::ensure_builtins_registered();
+    let id = registry::resolve_backend_id(backend_id)
+        .map_err(|e| ProveError::InvalidInput(e.to_string()))?;
+    let caps = registry::get_backend_capabilities(id)
+        .map_err(|e| ProveError::InvalidInput(e.to_string()))?;
+    let mut out = backend_findings(id, &caps);
+
+    let features = buildinfo::build_info().features;
+    out.push(Finding {
+        severity: Severity::Info,
+        source: "features",
+        origin: "build".into(),
+        detail: if features.is_empty() {
+            "zkprov-corelib built without optional features".into()
+        } else {
+            format!("zkprov-corelib features: {}", features.join(","))
+        },
+    });
+    let enabled: Vec<&str> = TARGET_FEATURES
+        .iter()
+        .filter(|(_, on)| *on)
+        .map(|(name, _)| *name)
+        .collect();
+    out.push(Finding {
+        severity: Severity::Info,
+        source: "platform-intrinsics",
+        origin: "build".into(),
+        detail: format!(
+            "{} target features: {}; corelib field and hash arithmetic is integer-only",
+            buildinfo::TARGET,
+            if enabled.is_empty() {
+                "none".to_string()
+            } else {
+                enabled.join(",")
+            }
+        ),
+    });
+    Ok(out)
+}
+
+fn backend_findings(id: &str, caps: &Capabilities) -> Vec<Finding> {
+    caps.nondeterminism
+        .iter()
+        .map(|&source| Finding {
+            severity: Severity::Warning,
+            source,
+            origin: format!("backend {}", id),
+            detail: match source {
+                "parallel-reduction" => "reduction order follows thread scheduling".into(),
+                "hashmap-iteration" => "output depends on hash map iteration order".into(),
+                "platform-intrinsics" => "CPU-specific intrinsics can change results".into(),
+                other => format!("declares '{}'", other),
+            },
+        })
+        .collect()
+}
+
+/// [`inspect`] `config.backend_id`, then prove once per thread for each
+/// entry of `threads` and compare the digests. At least two proofs in total
+/// are needed to compare anything.
+pub fn audit(
+    config: &Config,
+    ir: &AirIr,
+    inputs: &PublicInputs,
+    threads: &[usize],
+) -> Result<AuditReport, ProveError> {
+    if threads.contains(&0) || threads.iter().sum::<usize>() < 2 {
+        return Err(ProveError::InvalidInput(
+            "determinism audit needs thread counts of at least 1 and two proofs in total".into(),
+        ));
+    }
+    let findings = inspect(&config.backend_id)?;
+    let mut runs = Vec::with_capacity(threads.len());
+    for &n in threads {
+        let digests = thread::scope(|s| {
+            let handles: Vec<_> = (0..n)
+                .map(|_| s.spawn(|| prove_digest(config, ir, inputs)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("prover thread panicked"))
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+        runs.push(AuditRun {
+            threads: n,
+            digests,
+        });
+    }
+    let first = &runs[0].digests[0];
+    let deterministic = runs.iter().flat_map(|r| &r.digests).all(|d| d == first);
+    Ok(AuditReport {
+        backend: config.backend_id.clone(),
+        profile: config.profile_id.clone(),
+        findings,
+        runs,
+        deterministic,
+    })
+}
+
+fn prove_digest(config: &Config, ir: &AirIr, inputs: &PublicInputs) -> Result<String, ProveError> {
+    let proof = crate::prove(config, ir, inputs)?;
+    let digest: String = digest_D(&proof.header, &proof.body)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("0x{}", digest))
+}
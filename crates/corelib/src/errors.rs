@@ -1,11 +1,39 @@
 use thiserror::Error;
 
+use crate::profile::ProofTarget;
+
 #[derive(Debug, Error)]
 pub enum RegistryError {
     #[error("backend with id '{0}' is already registered")]
     DuplicateBackend(String),
     #[error("backend '{0}' not found")]
     BackendNotFound(String),
+    #[error("alias '{alias}' conflicts with existing backend or alias '{existing}'")]
+    AliasConflict { alias: String, existing: String },
+    #[error("invalid sunset date '{0}' (expected YYYY-MM-DD)")]
+    InvalidSunset(String),
+    #[error("no registered backend matches {0}")]
+    NoMatchingBackend(String),
+}
+
+/// Why a backend plugin ([`crate::plugin`]) could not be registered.
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("cannot load {path}: {reason}")]
+    Load { path: String, reason: String },
+    #[error("{0} does not export zkd_register_backend")]
+    MissingEntryPoint(String),
+    #[error("no common plugin ABI version (host supports {host_min}..={host_max}{})",
+        .plugin.map(|v| format!(", plugin answered {v}")).unwrap_or_default())]
+    AbiMismatch {
+        host_min: u32,
+        host_max: u32,
+        plugin: Option<u32>,
+    },
+    #[error("invalid backend: {0}")]
+    InvalidBackend(String),
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
 }
 
 #[derive(Debug, Error)]
@@ -20,7 +48,207 @@ pub enum CapabilityError {
     FriArityUnsupported { backend_id: String, fri_arity: u32 },
     #[error("recursion required but backend '{backend_id}' reports none")]
     RecursionUnavailable { backend_id: String },
+    #[error("profile '{profile_id}' targets {target} proofs, which backend '{backend_id}' does not produce")]
+    TargetUnsupported {
+        backend_id: String,
+        profile_id: String,
+        target: ProofTarget,
+    },
+    #[error("program requires '{requirement}' but backend '{backend_id}' does not provide it")]
+    RequirementUnmet {
+        backend_id: String,
+        requirement: String,
+    },
 
     #[error("profile '{0}' not found")]
     ProfileNotFound(String),
+    #[error(
+        "profile '{0}' is not signed by a trusted owner key, and only signed profiles are accepted"
+    )]
+    ProfileUnsigned(String),
+    #[error("invalid profile override for '{profile_id}': {reason}")]
+    ProfileOverrideInvalid { profile_id: String, reason: String },
+    #[error("invalid options for backend '{backend_id}': {reason}")]
+    OptionsInvalid { backend_id: String, reason: String },
+    #[error(
+        "config {what} '{config}' contradicts the program's meta.{what} hint '{air}' (strict AIR \
+         hints: an explicit {what} must match the hint; leave it unset to take the hint, or use \
+         respect mode, where explicit values take precedence over hints)"
+    )]
+    AirHintConflict {
+        what: &'static str,
+        config: String,
+        air: String,
+    },
+    #[error("no {what} configured and the program declares no meta.{what} hint")]
+    AirHintMissing { what: &'static str },
+}
+
+/// Errors surfaced by [`crate::backend::ProverBackend::prove`] and the
+/// registry-backed [`crate::prove`] dispatch.
+#[derive(Debug, Error)]
+pub enum ProveError {
+    #[error(transparent)]
+    Capability(#[from] CapabilityError),
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    #[error("backend '{backend_id}' does not support {feature}")]
+    Unsupported { backend_id: String, feature: String },
+    #[error("invalid prover input: {0}")]
+    InvalidInput(String),
+    /// A transition constraint evaluates non-zero on the supplied trace.
+    #[error(
+        "transition constraint #{constraint} ({display} = 0) does not hold at row {row}{}",
+        fmt_columns(columns)
+    )]
+    Unsatisfied {
+        /// Index into the lowered constraints (`AirIr::transition_constraints`).
+        constraint: usize,
+        /// Row `r` of the failing `r -> r + 1` transition.
+        row: u32,
+        display: String,
+        /// Columns the constraint reads, by name (`c<index>` when unnamed).
+        columns: Vec<String>,
+    },
+    /// A boundary constraint (`[[boundary]]`) does not hold on the supplied trace.
+    #[error("boundary constraint {display} does not hold: row {row} holds {actual}, expected {expected}")]
+    BoundaryUnsatisfied {
+        display: String,
+        row: u32,
+        expected: String,
+        actual: String,
+    },
+    /// The pre-flight memory estimate exceeds the caller's budget
+    /// (see [`crate::trace::memory::check_memory_budget`]).
+    #[error(
+        "estimated prover memory {} exceeds the {} limit; {hint}",
+        crate::trace::memory::fmt_mib(*.estimated),
+        crate::trace::memory::fmt_mib(*.limit)
+    )]
+    MemoryBudget {
+        estimated: u64,
+        limit: u64,
+        hint: String,
+    },
+    /// The `Config::deadline` budget ran out; the backend stopped at the
+    /// boundary after `phase` (see [`crate::deadline::Phases`]).
+    #[error(
+        "proving deadline of {budget_ms}ms exceeded after phase '{phase}' ({elapsed_ms}ms elapsed)"
+    )]
+    TimedOut {
+        phase: String,
+        elapsed_ms: u64,
+        budget_ms: u64,
+        /// Phases completed before aborting, with their timings.
+        phases: Vec<crate::deadline::PhaseStat>,
+    },
+    #[error(transparent)]
+    Backend(#[from] anyhow::Error),
+}
+
+/// Errors surfaced by [`crate::backend::VerifierBackend::verify`] and the
+/// registry-backed [`crate::verify`] dispatch.
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error(transparent)]
+    Capability(#[from] CapabilityError),
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+    #[error("backend '{backend_id}' does not support {feature}")]
+    Unsupported { backend_id: String, feature: String },
+    /// The proof header cannot be decoded (truncated, bad magic, version).
+    #[error("malformed proof: {0}")]
+    Malformed(String),
+    #[error("body length mismatch: header declares {expected} bytes, proof carries {actual}")]
+    BodyLength { expected: u64, actual: u64 },
+    /// A header hash does not match the config/inputs it should bind.
+    #[error("{field} mismatch: proof has 0x{actual:016x}, expected 0x{expected:016x}")]
+    HeaderBinding {
+        field: HeaderField,
+        expected: u64,
+        actual: u64,
+    },
+    /// The backend's recomputed commitment/root differs from the proof body.
+    #[error("{what} mismatch: proof has {actual}, expected {expected}")]
+    RootMismatch {
+        what: String,
+        expected: String,
+        actual: String,
+    },
+    /// A typed body segment is malformed or fails its checksum.
+    #[error("proof body segment {index}: {reason}")]
+    Segment { index: usize, reason: String },
+    /// Any other backend check that rejects the proof.
+    #[error("proof rejected: {0}")]
+    Rejected(String),
+    /// The proof is outside the relying party's verification policy.
+    #[error(
+        "verification policy violated: {}",
+        .0.iter().map(|v| v.to_string()).collect::<Vec<_>>().join("; ")
+    )]
+    Policy(Vec<crate::policy::PolicyViolation>),
+    #[error(transparent)]
+    Backend(#[from] anyhow::Error),
+}
+
+impl VerifyError {
+    /// Stable failure category for machine-readable diagnostics.
+    pub fn category(&self) -> &'static str {
+        match self {
+            VerifyError::Capability(_) => "capability",
+            VerifyError::Registry(_) => "registry",
+            VerifyError::Unsupported { .. } => "unsupported",
+            VerifyError::Malformed(_) => "header_decode",
+            VerifyError::BodyLength { .. } => "body_length",
+            VerifyError::HeaderBinding { .. } => "header_binding",
+            VerifyError::RootMismatch { .. } => "root_mismatch",
+            VerifyError::Segment { .. } => "segment",
+            VerifyError::Policy(_) => "policy",
+            VerifyError::Rejected(_) | VerifyError::Backend(_) => "backend",
+        }
+    }
+}
+
+/// Proof header fields bound to the verifier's config and inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderField {
+    BackendId,
+    ProfileId,
+    PubIo,
+    Config,
+}
+
+impl HeaderField {
+    /// Every bound field, in header order.
+    pub const ALL: [HeaderField; 4] = [
+        HeaderField::BackendId,
+        HeaderField::ProfileId,
+        HeaderField::PubIo,
+        HeaderField::Config,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HeaderField::BackendId => "backend_id_hash",
+            HeaderField::ProfileId => "profile_id_hash",
+            HeaderField::PubIo => "pubio_hash",
+            HeaderField::Config => "config_hash",
+        }
+    }
+}
+
+impl std::fmt::Display for HeaderField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// ` on column `a`` / ` on columns `a`, `b``, or nothing for no columns.
+fn fmt_columns(columns: &[String]) -> String {
+    let quoted: Vec<String> = columns.iter().map(|c| format!("`{c}`")).collect();
+    match quoted.len() {
+        0 => String::new(),
+        1 => format!(" on column {}", quoted[0]),
+        _ => format!(" on columns {}", quoted.join(", ")),
+    }
 }
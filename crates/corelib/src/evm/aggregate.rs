@@ -0,0 +1,114 @@
+//! Batched EVM submission: a Keccak Merkle root over per-proof digests (D).
+//!
+//! Tree rules (mirrored by `examples/evm_verifier/contracts/AggregatorVerifier.sol`):
+//! - leaf  = keccak256(0x00 || D)
+//! - node  = keccak256(0x01 || left || right)
+//! - an odd node at the end of a level is carried up unchanged
+//! - the root of a single leaf is that leaf hash; an empty batch is rejected
+
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolType, SolValue};
+use anyhow::{anyhow, ensure, Result};
+
+use crate::evm::digest::keccak256_bytes;
+
+sol! {
+    /// ABI payload for an aggregate submission. `leaves` may be empty when the
+    /// caller only posts the root.
+    struct EvmAggregate {
+        bytes32 root;
+        uint64 count;
+        bytes32[] leaves;
+    }
+}
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn leaf_hash(digest: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 33];
+    buf[0] = LEAF_PREFIX;
+    buf[1..].copy_from_slice(digest);
+    keccak256_bytes(&buf)
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut buf = [0u8; 65];
+    buf[0] = NODE_PREFIX;
+    buf[1..33].copy_from_slice(left);
+    buf[33..].copy_from_slice(right);
+    keccak256_bytes(&buf)
+}
+
+/// Merkle root over the given digests, in order.
+pub fn aggregate_root(digests: &[[u8; 32]]) -> Result<[u8; 32]> {
+    ensure!(
+        !digests.is_empty(),
+        "aggregate requires at least one digest"
+    );
+    let mut level: Vec<[u8; 32]> = digests.iter().map(leaf_hash).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [l, r] => node_hash(l, r),
+                [single] => *single,
+                _ => unreachable!("chunks(2) yields one or two items"),
+            })
+            .collect();
+    }
+    Ok(level[0])
+}
+
+/// Aggregate of `count` proof digests, optionally carrying the leaves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    pub root: [u8; 32],
+    pub count: u64,
+    /// Per-proof digests (D); `None` when only the root is published.
+    pub leaves: Option<Vec<[u8; 32]>>,
+}
+
+impl Aggregate {
+    pub fn from_digests(digests: &[[u8; 32]], include_leaves: bool) -> Result<Self> {
+        Ok(Self {
+            root: aggregate_root(digests)?,
+            count: digests.len() as u64,
+            leaves: include_leaves.then(|| digests.to_vec()),
+        })
+    }
+
+    /// Recompute the root from the carried leaves, if any.
+    pub fn verify(&self) -> Result<bool> {
+        match &self.leaves {
+            Some(leaves) => {
+                Ok(leaves.len() as u64 == self.count && aggregate_root(leaves)? == self.root)
+            }
+            None => Err(anyhow!("aggregate carries no leaves to verify")),
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        EvmAggregate {
+            root: B256::from(self.root),
+            count: self.count,
+            leaves: self
+                .leaves
+                .iter()
+                .flatten()
+                .map(|l| B256::from(*l))
+                .collect(),
+        }
+        .abi_encode()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let agg = <EvmAggregate as SolType>::abi_decode(data)?;
+        let leaves: Vec<[u8; 32]> = agg.leaves.iter().map(|l| l.0).collect();
+        Ok(Self {
+            root: agg.root.0,
+            count: agg.count,
+            leaves: (!leaves.is_empty()).then_some(leaves),
+        })
+    }
+}
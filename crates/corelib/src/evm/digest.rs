@@ -1,32 +1,55 @@
-use alloy_sol_types::{sol, SolValue};
-
-use crate::crypto::hash::hash_one_shot;
-use crate::crypto::keccak::Keccak256;
 use crate::proof::ProofHeader;
 
-sol! {
-    struct EvmDigestInput {
-        uint64 backendIdHash;
-        uint64 profileIdHash;
-        uint64 pubioHash;
-        uint64 bodyLen;
-        bytes body;
-    }
-}
-
-pub fn keccak256_bytes(data: &[u8]) -> [u8; 32] {
-    hash_one_shot::<Keccak256>(data)
-}
+pub use zkd_evm_utils::keccak256 as keccak256_bytes;
 
+/// `keccak256(abi.encode(EvmDigestInput{backendIdHash, profileIdHash,
+/// pubioHash, bodyLen, body}))`, as recomputed by the Solidity verifier
+/// ([`zkd_evm_utils::digest_d`]).
 #[allow(non_snake_case)]
 pub fn digest_D(header: &ProofHeader, body: &[u8]) -> [u8; 32] {
-    let payload = EvmDigestInput {
-        backendIdHash: header.backend_id_hash,
-        profileIdHash: header.profile_id_hash,
-        pubioHash: header.pubio_hash,
-        bodyLen: header.body_len,
-        body: body.to_vec().into(),
-    };
-    let encoded = payload.abi_encode();
-    keccak256_bytes(&encoded)
+    zkd_evm_utils::digest_d(&header.into(), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::{sol, SolValue};
+
+    sol! {
+        struct EvmDigestInput {
+            uint64 backendIdHash;
+            uint64 profileIdHash;
+            uint64 pubioHash;
+            uint64 bodyLen;
+            bytes body;
+        }
+    }
+
+    #[test]
+    fn streamed_digest_matches_abi_encode() {
+        for len in [0usize, 1, 8, 31, 32, 33, 40, 1000] {
+            let body: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let header = ProofHeader {
+                backend_id_hash: 0x0102_0304_0506_0708,
+                profile_id_hash: u64::MAX,
+                pubio_hash: 7,
+                body_len: len as u64,
+                config_hash: 0xdead,
+                flags: 0,
+            };
+            let encoded = EvmDigestInput {
+                backendIdHash: header.backend_id_hash,
+                profileIdHash: header.profile_id_hash,
+                pubioHash: header.pubio_hash,
+                bodyLen: header.body_len,
+                body: body.clone().into(),
+            }
+            .abi_encode();
+            assert_eq!(
+                digest_D(&header, &body),
+                keccak256_bytes(&encoded),
+                "len {len}"
+            );
+        }
+    }
 }
@@ -0,0 +1,135 @@
+//! Fixtures read by the Solidity verifier tests (see
+//! `examples/evm_verifier/testdata`): `meta.json` (header fields as numbers),
+//! `body.bin` (raw body), `digest.hex` (`D`, lowercase hex and a newline),
+//! and the ABI encodings `meta.abi` / `body.abi`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use serde_json::{json, Value};
+
+use super::abi::{encode_body, encode_meta};
+use super::digest::digest_D;
+use crate::backend::PublicInputs;
+use crate::proof::pubio::pubio_hash;
+use crate::proof::ProofHeader;
+
+/// Files written by [`write_all`], in order.
+pub const FILES: [&str; 5] = [
+    "meta.json",
+    "body.bin",
+    "digest.hex",
+    "meta.abi",
+    "body.abi",
+];
+
+/// `meta.json` contents for `header`.
+pub fn meta_json(header: &ProofHeader) -> Value {
+    json!({
+        "backendId": header.backend_id_hash,
+        "profileId": header.profile_id_hash,
+        "pubioHash": header.pubio_hash,
+        "bodyLen": header.body_len,
+        "configHash": header.config_hash,
+    })
+}
+
+/// Inverse of [`meta_json`].
+pub fn parse_meta_json(meta: &Value) -> Result<ProofHeader> {
+    let field = |key: &str| -> Result<u64> {
+        meta[key]
+            .as_u64()
+            .ok_or_else(|| anyhow!("meta.json: '{}' is not a u64", key))
+    };
+    Ok(ProofHeader {
+        backend_id_hash: field("backendId")?,
+        profile_id_hash: field("profileId")?,
+        pubio_hash: field("pubioHash")?,
+        body_len: field("bodyLen")?,
+        config_hash: field("configHash")?,
+        flags: 0,
+    })
+}
+
+/// Write every fixture for the proof `header` + `body` into `dir` (created if
+/// missing) and return their paths. Refuses a body whose length disagrees
+/// with the header, or `inputs` the header's `pubio_hash` does not bind, so
+/// the fixtures always describe a proof that verifies.
+pub fn write_all(
+    dir: &Path,
+    header: &ProofHeader,
+    body: &[u8],
+    inputs: &PublicInputs,
+) -> Result<Vec<PathBuf>> {
+    ensure!(
+        body.len() as u64 == header.body_len,
+        "body is {} bytes, header says {}",
+        body.len(),
+        header.body_len
+    );
+    let bound = pubio_hash(inputs);
+    ensure!(
+        bound == header.pubio_hash,
+        "inputs ({} encoding) hash to pubio_hash 0x{:016x}, proof binds 0x{:016x}",
+        inputs.encoding().as_str(),
+        bound,
+        header.pubio_hash
+    );
+    fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+
+    let digest: String = digest_D(header, body)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let contents: [Vec<u8>; 5] = [
+        format!("{}\n", serde_json::to_string_pretty(&meta_json(header))?).into_bytes(),
+        body.to_vec(),
+        format!("{}\n", digest).into_bytes(),
+        encode_meta(header),
+        encode_body(body),
+    ];
+    FILES
+        .iter()
+        .zip(contents)
+        .map(|(name, bytes)| {
+            let path = dir.join(name);
+            fs::write(&path, bytes).with_context(|| format!("write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm::abi::decode_meta;
+
+    #[test]
+    fn writes_consistent_fixtures_and_rejects_mismatches() {
+        let inputs = PublicInputs::from_json(r#"{"a":1}"#).unwrap();
+        let body = [7u8; 8];
+        let header = ProofHeader {
+            backend_id_hash: 1,
+            profile_id_hash: 2,
+            pubio_hash: pubio_hash(&inputs),
+            body_len: body.len() as u64,
+            config_hash: 3,
+            flags: 0,
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let paths = write_all(dir.path(), &header, &body, &inputs).unwrap();
+        assert_eq!(paths.len(), FILES.len());
+
+        let meta: Value = serde_json::from_slice(&fs::read(&paths[0]).unwrap()).unwrap();
+        assert_eq!(parse_meta_json(&meta).unwrap(), header);
+        assert_eq!(decode_meta(&fs::read(&paths[3]).unwrap()).unwrap(), header);
+        let digest = fs::read_to_string(&paths[2]).unwrap();
+        assert_eq!(digest.trim().len(), 64);
+
+        let other = PublicInputs::from_json(r#"{"a":2}"#).unwrap();
+        let err = write_all(dir.path(), &header, &body, &other).unwrap_err();
+        assert!(err.to_string().contains("pubio_hash"), "{err}");
+        assert!(write_all(dir.path(), &header, &body[..4], &inputs).is_err());
+    }
+}
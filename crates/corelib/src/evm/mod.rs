@@ -1,2 +1,40 @@
+//! EVM bridge. Digest and ABI encoding live in the standalone
+//! `zkd-evm-utils` crate; [`digest`] and [`abi`] adapt them to
+//! [`ProofHeader`].
+
+use crate::proof::ProofHeader;
+
+pub use zkd_evm_utils::{calldata, ProofMeta};
+
 pub mod abi;
+pub mod aggregate;
 pub mod digest;
+pub mod fixtures;
+pub mod pubio;
+pub mod typed;
+
+impl From<&ProofHeader> for ProofMeta {
+    fn from(header: &ProofHeader) -> Self {
+        Self {
+            backend_id_hash: header.backend_id_hash,
+            profile_id_hash: header.profile_id_hash,
+            pubio_hash: header.pubio_hash,
+            body_len: header.body_len,
+            config_hash: header.config_hash,
+        }
+    }
+}
+
+/// A v3 header with no flags, which the EVM side does not see.
+impl From<ProofMeta> for ProofHeader {
+    fn from(meta: ProofMeta) -> Self {
+        Self {
+            backend_id_hash: meta.backend_id_hash,
+            profile_id_hash: meta.profile_id_hash,
+            pubio_hash: meta.pubio_hash,
+            body_len: meta.body_len,
+            config_hash: meta.config_hash,
+            flags: 0,
+        }
+    }
+}
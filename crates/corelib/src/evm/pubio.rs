@@ -0,0 +1,246 @@
+//! Public IO ABI v2: typed encoding of the declared public inputs.
+//!
+//! v1 (`json`) ABI-encodes the inputs JSON verbatim as `bytes`. v2 (`typed`)
+//! maps each `[[public_inputs]]` entry, in declaration order, to an ABI value
+//! and encodes them as `abi.encode(a, b, ...)`:
+//! - `field` -> `uint256` (JSON number, decimal string, or 0x-hex; must be
+//!   below the field modulus)
+//! - `u64`   -> `uint64` (JSON number or decimal string)
+//! - `u128`  -> `uint128`
+//! - `i64`   -> `int64`
+//! - `fixed(s)` -> `int128` holding the value in units of `10^-s`
+//! - `bytes` -> `bytes` (hex string, 0x prefix optional)
+//! - `bytes_ref` -> `bytes` holding the 32-byte digest then `len` as a
+//!   little-endian `u64`
+//!
+//! Accepted JSON forms and range checks are those of
+//! [`crate::air::public::PublicValue::parse`].
+//!
+//! Contracts recompute the typed digest with `keccak256(abi.encode(...))`
+//! over the struct emitted by [`solidity_struct`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{I256, U256};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::air::public::PublicValue;
+use crate::air::types::{AirIr, PublicTy};
+use crate::backend::PublicInputs;
+use crate::evm::abi::encode_public_io;
+use crate::evm::digest::keccak256_bytes;
+use crate::trace::eval::field_modulus;
+
+/// Which public IO encoding binds a proof (`pubio_hash`) and goes on-chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PubIoEncoding {
+    /// v1: the inputs JSON text, verbatim.
+    #[default]
+    Json,
+    /// v2: ABI tuple of the declared public inputs.
+    Typed,
+}
+
+impl PubIoEncoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PubIoEncoding::Json => "json",
+            PubIoEncoding::Typed => "typed",
+        }
+    }
+
+    pub fn is_json(&self) -> bool {
+        *self == PubIoEncoding::Json
+    }
+}
+
+impl fmt::Display for PubIoEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PubIoEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(PubIoEncoding::Json),
+            "typed" => Ok(PubIoEncoding::Typed),
+            other => Err(anyhow!(
+                "unknown public IO encoding '{}' (expected json or typed)",
+                other
+            )),
+        }
+    }
+}
+
+/// ABI type of a declared public input.
+pub fn sol_type(ty: &PublicTy) -> DynSolType {
+    match ty {
+        PublicTy::Field => DynSolType::Uint(256),
+        PublicTy::U64 => DynSolType::Uint(64),
+        PublicTy::U128 => DynSolType::Uint(128),
+        PublicTy::I64 => DynSolType::Int(64),
+        PublicTy::Fixed { .. } => DynSolType::Int(128),
+        PublicTy::Bytes | PublicTy::BytesRef => DynSolType::Bytes,
+    }
+}
+
+fn sol_type_name(ty: &PublicTy) -> &'static str {
+    match ty {
+        PublicTy::Field => "uint256",
+        PublicTy::U64 => "uint64",
+        PublicTy::U128 => "uint128",
+        PublicTy::I64 => "int64",
+        PublicTy::Fixed { .. } => "int128",
+        PublicTy::Bytes | PublicTy::BytesRef => "bytes",
+    }
+}
+
+/// `abi.encode(...)` of the declared public inputs, read from `inputs` by name.
+/// Missing or undeclared keys are errors so nothing is left unbound.
+pub fn encode_typed(ir: &AirIr, inputs: &Value) -> Result<Vec<u8>> {
+    ensure!(
+        !ir.public_inputs.is_empty(),
+        "typed public IO requires [[public_inputs]] declarations in '{}'",
+        ir.meta.name
+    );
+    let obj = inputs
+        .as_object()
+        .ok_or_else(|| anyhow!("typed public IO requires a JSON object"))?;
+    if let Some(extra) = obj
+        .keys()
+        .find(|k| !ir.public_inputs.iter().any(|pi| &pi.name == *k))
+    {
+        bail!("input '{}' is not a declared public input", extra);
+    }
+
+    let modulus = field_modulus(&ir.meta.field).ok();
+    let mut values = Vec::with_capacity(ir.public_inputs.len());
+    for pi in &ir.public_inputs {
+        let raw = obj
+            .get(&pi.name)
+            .ok_or_else(|| anyhow!("public input '{}' is missing", pi.name))?;
+        let value = match PublicValue::parse(pi.ty, raw)
+            .with_context(|| format!("public input '{}'", pi.name))?
+        {
+            PublicValue::Field(v) => {
+                let v = U256::from_le_slice(&v.to_bytes_le());
+                if let Some(p) = &modulus {
+                    let p = U256::from_be_slice(&p.to_bytes_be());
+                    ensure!(
+                        v < p,
+                        "public input '{}' is not below the {} modulus",
+                        pi.name,
+                        ir.meta.field
+                    );
+                }
+                DynSolValue::Uint(v, 256)
+            }
+            PublicValue::U64(v) => DynSolValue::Uint(U256::from(v), 64),
+            PublicValue::U128(v) => DynSolValue::Uint(U256::from(v), 128),
+            PublicValue::I64(v) => DynSolValue::Int(I256::try_from(v)?, 64),
+            PublicValue::Fixed { units, .. } => DynSolValue::Int(I256::try_from(units)?, 128),
+            PublicValue::Bytes(bytes) => DynSolValue::Bytes(bytes),
+            v @ PublicValue::BytesRef { .. } => DynSolValue::Bytes(v.to_le_bytes()),
+        };
+        values.push(value);
+    }
+    Ok(DynSolValue::Tuple(values).abi_encode_params())
+}
+
+/// Inverse of [`encode_typed`], in the canonical JSON of
+/// [`PublicValue::to_json`]: `field`, `u128` and `fixed` values come back as
+/// decimal strings, `u64` and `i64` as numbers, `bytes` as 0x-hex and
+/// `bytes_ref` as `{"digest", "len"}`.
+pub fn decode_typed(ir: &AirIr, data: &[u8]) -> Result<Value> {
+    let ty = DynSolType::Tuple(ir.public_inputs.iter().map(|pi| sol_type(&pi.ty)).collect());
+    let DynSolValue::Tuple(values) = ty.abi_decode_params(data)? else {
+        unreachable!("tuple type decodes to a tuple");
+    };
+    let mut out = serde_json::Map::new();
+    for (pi, value) in ir.public_inputs.iter().zip(values) {
+        let json = match (&pi.ty, value) {
+            (PublicTy::Field, DynSolValue::Uint(v, _)) => Value::String(v.to_string()),
+            (PublicTy::U64, DynSolValue::Uint(v, _)) => Value::from(v.to::<u64>()),
+            (PublicTy::U128, DynSolValue::Uint(v, _)) => Value::String(v.to_string()),
+            (PublicTy::I64, DynSolValue::Int(v, _)) => Value::from(i64::try_from(v)?),
+            (PublicTy::Fixed { scale }, DynSolValue::Int(v, _)) => PublicValue::Fixed {
+                units: i128::try_from(v)?,
+                scale: *scale,
+            }
+            .to_json(),
+            (PublicTy::Bytes, DynSolValue::Bytes(b)) => {
+                Value::String(format!("0x{}", alloy_primitives::hex::encode(b)))
+            }
+            (PublicTy::BytesRef, DynSolValue::Bytes(b)) if b.len() == 40 => PublicValue::BytesRef {
+                digest: b[..32].try_into().expect("32 bytes"),
+                len: u64::from_le_bytes(b[32..].try_into().expect("8 bytes")),
+            }
+            .to_json(),
+            (_, other) => bail!("unexpected ABI value {:?} for '{}'", other, pi.name),
+        };
+        out.insert(pi.name.clone(), json);
+    }
+    Ok(Value::Object(out))
+}
+
+/// Keccak-256 over [`encode_typed`]; what a contract recomputes on-chain.
+pub fn typed_digest(ir: &AirIr, inputs: &Value) -> Result<[u8; 32]> {
+    Ok(keccak256_bytes(&encode_typed(ir, inputs)?))
+}
+
+/// ABI bytes for `inputs` under `encoding` (v1 wraps the JSON text in `bytes`).
+pub fn encode(encoding: PubIoEncoding, ir: &AirIr, inputs: &PublicInputs) -> Result<Vec<u8>> {
+    match encoding {
+        PubIoEncoding::Json => Ok(encode_public_io(inputs.as_json())),
+        PubIoEncoding::Typed => encode_typed(ir, &inputs.value()),
+    }
+}
+
+/// Solidity struct (and hash helper) mirroring the typed encoding of `ir`.
+pub fn solidity_struct(ir: &AirIr) -> String {
+    let name = format!("{}PublicIO", pascal_case(&ir.meta.name));
+    let mut out = String::new();
+    out.push_str("// SPDX-License-Identifier: MIT\n");
+    out.push_str("pragma solidity ^0.8.24;\n\n");
+    out.push_str(&format!(
+        "/// Public inputs of `{}` in declaration order (typed public IO, ABI v2).\n",
+        ir.meta.name
+    ));
+    out.push_str(&format!("struct {} {{\n", name));
+    for pi in &ir.public_inputs {
+        out.push_str(&format!("    {} {};\n", sol_type_name(&pi.ty), pi.name));
+    }
+    out.push_str("}\n\n");
+    let args = ir
+        .public_inputs
+        .iter()
+        .map(|pi| format!("io.{}", pi.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    out.push_str("/// Matches `zkd evm-pubio --encoding typed` digest.\n");
+    out.push_str(&format!(
+        "function hash{name}({name} memory io) pure returns (bytes32) {{\n"
+    ));
+    out.push_str(&format!("    return keccak256(abi.encode({}));\n", args));
+    out.push_str("}\n");
+    out
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().expect("non-empty part").to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+//! EIP-712 typed-data hashing for on-chain proof submissions.
+//!
+//! Wallets sign `ProofSubmission(digest, programHash, publicInputsHash, context)`
+//! under a `ZKProv` domain bound to a chain id and verifying contract, so a
+//! signed submission cannot be replayed on another chain or contract.
+
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::{sol, Eip712Domain, SolStruct};
+use serde_json::json;
+
+use crate::evm::digest::keccak256_bytes;
+
+sol! {
+    /// Typed payload signed by wallets when submitting a proof.
+    struct ProofSubmission {
+        bytes32 digest;
+        bytes32 programHash;
+        bytes32 publicInputsHash;
+        bytes32 context;
+    }
+}
+
+pub const DOMAIN_NAME: &str = "ZKProv";
+pub const DOMAIN_VERSION: &str = "1";
+
+/// Chain/contract binding for the EIP-712 domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedDomain {
+    pub chain_id: u64,
+    pub verifying_contract: [u8; 20],
+}
+
+impl TypedDomain {
+    fn to_eip712(self) -> Eip712Domain {
+        Eip712Domain::new(
+            Some(DOMAIN_NAME.into()),
+            Some(DOMAIN_VERSION.into()),
+            Some(U256::from(self.chain_id)),
+            Some(Address::from(self.verifying_contract)),
+            None,
+        )
+    }
+
+    pub fn separator(self) -> [u8; 32] {
+        self.to_eip712().separator().0
+    }
+}
+
+/// Fields of a proof submission, all as 32-byte words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedSubmission {
+    /// Digest D from [`crate::evm::digest::digest_D`].
+    pub digest: [u8; 32],
+    /// Keccak-256 of the AIR program source.
+    pub program_hash: [u8; 32],
+    /// Keccak-256 of the public inputs JSON.
+    pub public_inputs_hash: [u8; 32],
+    /// Caller-chosen context (session id, nonce, ...); zero if unused.
+    pub context: [u8; 32],
+}
+
+impl TypedSubmission {
+    fn to_sol(self) -> ProofSubmission {
+        ProofSubmission {
+            digest: B256::from(self.digest),
+            programHash: B256::from(self.program_hash),
+            publicInputsHash: B256::from(self.public_inputs_hash),
+            context: B256::from(self.context),
+        }
+    }
+
+    /// hashStruct(ProofSubmission)
+    pub fn struct_hash(&self) -> [u8; 32] {
+        self.to_sol().eip712_hash_struct().0
+    }
+
+    /// keccak256(0x1901 || domainSeparator || hashStruct(message))
+    pub fn signing_hash(&self, domain: TypedDomain) -> [u8; 32] {
+        self.to_sol().eip712_signing_hash(&domain.to_eip712()).0
+    }
+
+    /// `eth_signTypedData_v4` payload for wallets.
+    pub fn typed_data_json(&self, domain: TypedDomain) -> serde_json::Value {
+        json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "ProofSubmission": [
+                    {"name": "digest", "type": "bytes32"},
+                    {"name": "programHash", "type": "bytes32"},
+                    {"name": "publicInputsHash", "type": "bytes32"},
+                    {"name": "context", "type": "bytes32"}
+                ]
+            },
+            "primaryType": "ProofSubmission",
+            "domain": {
+                "name": DOMAIN_NAME,
+                "version": DOMAIN_VERSION,
+                "chainId": domain.chain_id,
+                "verifyingContract": Address::from(domain.verifying_contract).to_checksum(None),
+            },
+            "message": {
+                "digest": B256::from(self.digest).to_string(),
+                "programHash": B256::from(self.program_hash).to_string(),
+                "publicInputsHash": B256::from(self.public_inputs_hash).to_string(),
+                "context": B256::from(self.context).to_string(),
+            }
+        })
+    }
+}
+
+/// Keccak-256 of the AIR program source bytes.
+pub fn program_hash(air_source: &[u8]) -> [u8; 32] {
+    keccak256_bytes(air_source)
+}
+
+/// Keccak-256 of the public inputs JSON bytes (as supplied to the prover).
+pub fn public_inputs_hash(inputs_json: &str) -> [u8; 32] {
+    keccak256_bytes(inputs_json.as_bytes())
+}
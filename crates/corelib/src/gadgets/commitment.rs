@@ -5,8 +5,13 @@
 //! where H_id is resolved from crypto::registry by its string id.
 //!
 //! API is stable so we can replace internals later with real curve math.
+//!
+//! The AIR's hash-based kinds, `poseidon_commit` and `keccak_commit`, have
+//! their own schemes ([`PoseidonCommitment`], [`KeccakCommitment`]);
+//! [`CommitKind`] selects among all three by name.
 
-use crate::crypto::registry::hash32_by_id;
+use crate::crypto::field::{Prime254, PrimeField};
+use crate::crypto::registry::{canonical_hash_id, hash32_by_id};
 use anyhow::{anyhow, Result};
 
 /// 32-byte commitment type
@@ -93,3 +98,161 @@ impl CommitmentScheme32 for PedersenPlaceholder {
         "pedersen"
     }
 }
+
+/// Commitment kinds an AIR can bind in `[commitments]` (see
+/// [`crate::air::types::CommitmentKind`]), selectable by name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitKind {
+    Pedersen,
+    PoseidonCommit,
+    KeccakCommit,
+}
+
+impl CommitKind {
+    pub const ALL: [CommitKind; 3] = [
+        CommitKind::Pedersen,
+        CommitKind::PoseidonCommit,
+        CommitKind::KeccakCommit,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitKind::Pedersen => "pedersen",
+            CommitKind::PoseidonCommit => "poseidon_commit",
+            CommitKind::KeccakCommit => "keccak_commit",
+        }
+    }
+
+    /// Hash the kind is defined over; `None` when the caller picks it
+    /// (Pedersen uses the AIR's commitment hash).
+    pub fn fixed_hash(&self) -> Option<&'static str> {
+        match self {
+            CommitKind::Pedersen => None,
+            CommitKind::PoseidonCommit => Some("poseidon2"),
+            CommitKind::KeccakCommit => Some("keccak256"),
+        }
+    }
+
+    /// The scheme for this kind; `hash_id` is required for Pedersen and, when
+    /// given for the other kinds, must name their fixed hash.
+    pub fn scheme(&self, hash_id: Option<&str>) -> Result<Box<dyn CommitmentScheme32>> {
+        if let (Some(fixed), Some(requested)) = (self.fixed_hash(), hash_id) {
+            if canonical_hash_id(requested) != Some(fixed) {
+                return Err(anyhow!(
+                    "{} commitments always use {}, not '{}'",
+                    self.as_str(),
+                    fixed,
+                    requested
+                ));
+            }
+        }
+        Ok(match self {
+            CommitKind::Pedersen => {
+                let hash_id =
+                    hash_id.ok_or_else(|| anyhow!("pedersen commitments need a hash id"))?;
+                Box::new(PedersenPlaceholder::new(PedersenParams {
+                    hash_id: hash_id.to_string(),
+                }))
+            }
+            CommitKind::PoseidonCommit => Box::new(PoseidonCommitment),
+            CommitKind::KeccakCommit => Box::new(KeccakCommitment),
+        })
+    }
+
+    /// Display form of a commitment of this kind: bare hex for Pedersen,
+    /// `0x`-hex for the others (a Prime254 element, big-endian, for
+    /// `poseidon_commit`; an EVM `bytes32` for `keccak_commit`).
+    pub fn format(&self, commitment: &Comm32) -> String {
+        let hex = alloy_primitives::hex::encode(commitment.as_bytes());
+        match self {
+            CommitKind::Pedersen => hex,
+            CommitKind::PoseidonCommit | CommitKind::KeccakCommit => format!("0x{hex}"),
+        }
+    }
+}
+
+impl std::fmt::Display for CommitKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CommitKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.as_str() == s)
+            .ok_or_else(|| {
+                anyhow!("unknown commitment kind '{s}' (pedersen, poseidon_commit, keccak_commit)")
+            })
+    }
+}
+
+/// `poseidon_commit`: the Poseidon2 digest of the length-prefixed message and
+/// blind, reduced into Prime254 so it can be bound as a `field` input.
+///   C = H_poseidon2("POSEIDON_COMMIT" || len(m) || m || len(r) || r) mod p
+pub struct PoseidonCommitment;
+
+impl PoseidonCommitment {
+    fn commit_raw(msg: &[u8], blind: &[u8]) -> [u8; 32] {
+        let mut buf = Vec::with_capacity(16 + msg.len() + blind.len());
+        buf.extend_from_slice(&(msg.len() as u64).to_le_bytes());
+        buf.extend_from_slice(msg);
+        buf.extend_from_slice(&(blind.len() as u64).to_le_bytes());
+        buf.extend_from_slice(blind);
+        let digest =
+            hash32_by_id("poseidon2", "POSEIDON_COMMIT", &buf).expect("poseidon2 is registered");
+        let element = Prime254.canonical_bytes(&Prime254.hash_to_field(&digest));
+        element.try_into().expect("Prime254 elements are 32 bytes")
+    }
+}
+
+impl CommitmentScheme32 for PoseidonCommitment {
+    fn commit(&self, w: &Witness<'_>) -> Result<Comm32> {
+        Ok(Comm32(Self::commit_raw(w.msg, w.blind)))
+    }
+
+    fn open(&self, w: &Witness<'_>, commitment: &Comm32) -> Result<bool> {
+        Ok(Self::commit_raw(w.msg, w.blind) == commitment.0)
+    }
+
+    fn id(&self) -> &'static str {
+        "poseidon_commit"
+    }
+}
+
+/// `keccak_commit`: `keccak256(m || r)` with a 32-byte blind, i.e. Solidity's
+/// `keccak256(abi.encodePacked(m, r))`, so contracts can check openings. The
+/// fixed blind length keeps the split between `m` and `r` unambiguous.
+pub struct KeccakCommitment;
+
+impl KeccakCommitment {
+    pub const BLIND_LEN: usize = 32;
+
+    fn commit_raw(msg: &[u8], blind: &[u8]) -> Result<[u8; 32]> {
+        if blind.len() != Self::BLIND_LEN {
+            return Err(anyhow!(
+                "keccak_commit blinds must be {} bytes, got {}",
+                Self::BLIND_LEN,
+                blind.len()
+            ));
+        }
+        Ok(alloy_primitives::keccak256([msg, blind].concat()).0)
+    }
+}
+
+impl CommitmentScheme32 for KeccakCommitment {
+    fn commit(&self, w: &Witness<'_>) -> Result<Comm32> {
+        Ok(Comm32(Self::commit_raw(w.msg, w.blind)?))
+    }
+
+    fn open(&self, w: &Witness<'_>, commitment: &Comm32) -> Result<bool> {
+        Ok(Self::commit_raw(w.msg, w.blind)? == commitment.0)
+    }
+
+    fn id(&self) -> &'static str {
+        "keccak_commit"
+    }
+}
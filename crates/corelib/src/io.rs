@@ -0,0 +1,144 @@
+//! Public inputs parsed against an AIR's `[[public_inputs]]` declarations.
+//!
+//! [`TypedInputs::parse`] is the one place an inputs JSON document is read
+//! against `AirIr::public_inputs`. The document must be an object, every
+//! declared input it supplies must parse as its declared type
+//! ([`PublicValue::parse`]), and keys the AIR does not declare follow the
+//! [`UndeclaredInputs`] policy. The result holds one entry per declaration,
+//! in declaration order, so backends read typed values instead of JSON, and
+//! the structured `pubio_hash` binding ([`crate::proof::pubio`]) is computed
+//! from those values, independent of key order and number formatting.
+//!
+//! A declared input may be absent. The binding records it as absent, and
+//! boundary constraints that read it fail. [`TypedInputs::missing`] lists
+//! absent inputs and [`TypedInputs::require_all`] refuses them.
+
+use anyhow::{anyhow, bail, Result};
+use num_bigint::BigUint;
+use serde_json::{Map, Value};
+
+use crate::air::public::PublicValue;
+use crate::air::types::PublicInput;
+use crate::proof::pubio::{typed_binding, undeclared_keys, UndeclaredInputs};
+
+/// One declared input and its value, if the document supplies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedInput {
+    pub decl: PublicInput,
+    pub value: Option<PublicValue>,
+}
+
+/// Declared public inputs with their parsed values, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedInputs {
+    inputs: Vec<TypedInput>,
+}
+
+impl TypedInputs {
+    /// Parse `inputs` against `decls`.
+    pub fn parse(
+        decls: &[PublicInput],
+        inputs: &Value,
+        undeclared: UndeclaredInputs,
+    ) -> Result<Self> {
+        let obj = inputs
+            .as_object()
+            .ok_or_else(|| anyhow!("public inputs must be a JSON object"))?;
+        if undeclared == UndeclaredInputs::Reject {
+            let extra = undeclared_keys(decls, inputs);
+            if !extra.is_empty() {
+                bail!("undeclared public inputs: {}", extra.join(", "));
+            }
+        }
+        let inputs = decls
+            .iter()
+            .map(|decl| {
+                let value = obj
+                    .get(&decl.name)
+                    .map(|raw| {
+                        PublicValue::parse(decl.ty, raw).map_err(|e| {
+                            anyhow!("public input '{}' ({}): {}", decl.name, decl.ty, e)
+                        })
+                    })
+                    .transpose()?;
+                Ok(TypedInput {
+                    decl: decl.clone(),
+                    value,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { inputs })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TypedInput> {
+        self.inputs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Value of the declared input `name`, when supplied.
+    pub fn get(&self, name: &str) -> Option<&PublicValue> {
+        self.inputs
+            .iter()
+            .find(|input| input.decl.name == name)
+            .and_then(|input| input.value.as_ref())
+    }
+
+    /// Declared inputs the document does not supply, in declaration order.
+    pub fn missing(&self) -> Vec<&str> {
+        self.inputs
+            .iter()
+            .filter(|input| input.value.is_none())
+            .map(|input| input.decl.name.as_str())
+            .collect()
+    }
+
+    /// Fail unless every declared input is supplied.
+    pub fn require_all(&self) -> Result<()> {
+        let missing = self.missing();
+        if !missing.is_empty() {
+            bail!("missing declared public inputs: {}", missing.join(", "));
+        }
+        Ok(())
+    }
+
+    /// The supplied inputs in canonical JSON ([`PublicValue::to_json`]),
+    /// keys sorted, undeclared keys dropped.
+    pub fn canonical_json(&self) -> Value {
+        let map: Map<String, Value> = self
+            .inputs
+            .iter()
+            .filter_map(|input| {
+                let value = input.value.as_ref()?;
+                Some((input.decl.name.clone(), value.to_json()))
+            })
+            .collect();
+        Value::Object(map)
+    }
+
+    /// Field elements of all declared inputs modulo `p`, in declaration
+    /// order. Every input must be supplied.
+    pub fn field_elements(&self, p: &BigUint) -> Result<Vec<BigUint>> {
+        self.require_all()?;
+        self.inputs
+            .iter()
+            .map(|input| {
+                let value = input.value.as_ref().expect("checked by require_all");
+                value
+                    .to_field(p)
+                    .map_err(|e| anyhow!("public input '{}': {}", input.decl.name, e))
+            })
+            .collect()
+    }
+
+    /// Structured binding bytes, hashed into `pubio_hash`.
+    pub fn binding(&self) -> Vec<u8> {
+        typed_binding(self)
+    }
+}
@@ -4,15 +4,27 @@ pub mod air;
 pub mod air_bindings {
     pub use crate::air::bindings::*;
 }
+pub mod audit;
 pub mod backend;
+pub mod buildinfo;
 pub mod config;
 pub mod crypto;
+pub mod deadline;
+pub mod determinism;
 pub mod errors;
 pub mod evm;
 pub mod gadgets;
+pub mod io;
+pub mod manifest;
+pub mod pkg;
+pub mod plugin;
+pub mod policy;
 pub mod profile;
 pub mod proof;
+pub mod quorum;
 pub mod registry;
+pub mod storage;
+pub mod timestamp;
 pub mod trace;
 pub mod validate;
 pub mod validation;
@@ -21,7 +33,7 @@ pub mod zkprov_bundles;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
-use profile::{load_all_profiles_or_default, Profile};
+use profile::{load_all_profiles_or_default, Profile, ProofTarget};
 
 static PROFILES: Lazy<Vec<Profile>> = Lazy::new(load_all_profiles_or_default);
 
@@ -29,6 +41,7 @@ static PROFILES: Lazy<Vec<Profile>> = Lazy::new(load_all_profiles_or_default);
 pub struct ProfileInfo {
     pub id: String,
     pub lambda_bits: u32,
+    pub target: ProofTarget,
 }
 
 pub fn list_profiles() -> Vec<ProfileInfo> {
@@ -37,6 +50,7 @@ pub fn list_profiles() -> Vec<ProfileInfo> {
         .map(|p| ProfileInfo {
             id: p.id.clone(),
             lambda_bits: p.lambda_bits,
+            target: p.target(),
         })
         .collect()
 }
@@ -49,6 +63,272 @@ pub fn list_backends() -> Vec<backend::BackendInfo> {
 
 pub use validate::validate_config;
 
+/// Prove `ir` with the backend named by `config`, looked up in the registry.
+///
+/// Applies the program's backend/profile hints under `config.air_hints`,
+/// validates the config and the program's commitment requirements, then
+/// resolves the profile (with overrides) and records `config.hash` on it.
+pub fn prove(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+) -> Result<proof::Proof, errors::ProveError> {
+    let (backend, profile, ctx) = dispatch_target(config, ir)?;
+    observe_prove(
+        &backend,
+        &profile,
+        ir,
+        |p| p,
+        || {
+            let inputs = bind_inputs(config, ir, inputs)?;
+            let ir = &pin_rows(ir, &profile, &backend, &inputs)?;
+            backend.prover.prove(ir, &profile, &ctx, &inputs)
+        },
+    )
+}
+
+/// Like [`prove`], but from an externally generated trace.
+pub fn prove_with_trace(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+    trace: &trace::Trace,
+) -> Result<proof::Proof, errors::ProveError> {
+    let (backend, profile, ctx) = dispatch_target(config, ir)?;
+    observe_prove(
+        &backend,
+        &profile,
+        ir,
+        |p| p,
+        || {
+            let inputs = bind_inputs(config, ir, inputs)?;
+            let ir = &pin_rows(ir, &profile, &backend, &inputs)?;
+            backend
+                .prover
+                .prove_with_trace(ir, &profile, &ctx, &inputs, trace)
+        },
+    )
+}
+
+/// Experimental: like [`prove_with_trace`], but reusing the trace cached from
+/// the previous call in `cache`, so only columns that changed are recommitted.
+/// Backends without `Capabilities::incremental` prove from scratch (reporting
+/// no reuse); `cache` is updated either way.
+pub fn prove_incremental(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+    trace: &trace::Trace,
+    cache: &mut trace::TraceCache,
+) -> Result<(proof::Proof, trace::Reuse), errors::ProveError> {
+    let (backend, profile, ctx) = dispatch_target(config, ir)?;
+    observe_prove(
+        &backend,
+        &profile,
+        ir,
+        |(p, _)| p,
+        || {
+            let inputs = bind_inputs(config, ir, inputs)?;
+            let ir = &pin_rows(ir, &profile, &backend, &inputs)?;
+            if backend.prover.capabilities().incremental {
+                return backend
+                    .prover
+                    .prove_incremental(ir, &profile, &ctx, &inputs, trace, cache);
+            }
+            let proof = backend
+                .prover
+                .prove_with_trace(ir, &profile, &ctx, &inputs, trace)?;
+            let hash = ctx.hash.as_deref().unwrap_or(ir.meta.hash.as_str());
+            cache.commit(trace, hash)?;
+            Ok((proof, trace::Reuse::none(trace.cols())))
+        },
+    )
+}
+
+/// Verify `proof` with the backend named by `config`; see [`prove`].
+pub fn verify(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+    proof: &proof::ProofView<'_>,
+) -> Result<(), errors::VerifyError> {
+    let to_verify_error = |e| match e {
+        errors::ProveError::Capability(e) => errors::VerifyError::Capability(e),
+        errors::ProveError::Registry(e) => errors::VerifyError::Registry(e),
+        other => errors::VerifyError::Backend(other.into()),
+    };
+    let (backend, profile, ctx) = dispatch_target(config, ir).map_err(to_verify_error)?;
+    let started = std::time::Instant::now();
+    let result = bind_inputs(config, ir, inputs)
+        .and_then(|inputs| Ok((pin_rows(ir, &profile, &backend, &inputs)?, inputs)))
+        .map_err(to_verify_error)
+        .and_then(|(ir, inputs)| backend.verifier.verify(&ir, &profile, &ctx, &inputs, proof));
+    registry::emit(registry::RegistryEvent::Verified {
+        backend_id: backend.prover.id(),
+        profile_id: profile.id.clone(),
+        program: ir.meta.name.clone(),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        verified: result.is_ok(),
+        error: result.as_ref().err().map(|e| e.to_string()),
+    });
+    result
+}
+
+/// Like [`verify`], but first checks `proof` against a relying party's
+/// [`policy::VerifyPolicy`]; every violated rule is reported in
+/// [`errors::VerifyError::Policy`] before the backend verifier runs.
+pub fn verify_with_policy(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+    proof: &proof::ProofView<'_>,
+    policy: &policy::VerifyPolicy,
+    evidence: &policy::PolicyEvidence<'_>,
+) -> Result<(), errors::VerifyError> {
+    let violations = policy.check(config, proof, evidence);
+    if !violations.is_empty() {
+        return Err(errors::VerifyError::Policy(violations));
+    }
+    verify(config, ir, inputs, proof)
+}
+
+/// Run a prove between `ProveStarted` and `ProveFinished` events.
+fn observe_prove<T>(
+    backend: &registry::DynBackend,
+    profile: &Profile,
+    ir: &air::AirIr,
+    proof_of: impl Fn(&T) -> &proof::Proof,
+    run: impl FnOnce() -> Result<T, errors::ProveError>,
+) -> Result<T, errors::ProveError> {
+    let backend_id = backend.prover.id();
+    registry::emit(registry::RegistryEvent::ProveStarted {
+        backend_id,
+        profile_id: profile.id.clone(),
+        program: ir.meta.name.clone(),
+    });
+    let started = std::time::Instant::now();
+    let result = run();
+    registry::emit(registry::RegistryEvent::ProveFinished {
+        backend_id,
+        profile_id: profile.id.clone(),
+        program: ir.meta.name.clone(),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        proof_len: result
+            .as_ref()
+            .ok()
+            .map(proof_of)
+            .map(|p| (p.header.encoded_len() + p.body.len()) as u64),
+        error: result.as_ref().err().map(|e| e.to_string()),
+    });
+    result
+}
+
+/// Resolve `bytes_ref` files, check `inputs` against the declared public
+/// input types, then bind them to `ir` under the config's public IO encoding
+/// and undeclared-key policy.
+pub(crate) fn bind_inputs(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+) -> Result<backend::PublicInputs, errors::ProveError> {
+    let invalid = |e: anyhow::Error| errors::ProveError::InvalidInput(format!("{e:#}"));
+    let inputs =
+        match air::public::resolve_bytes_refs(&ir.public_inputs, &inputs.value(), &config.hash)
+            .map_err(invalid)?
+        {
+            Some(resolved) => backend::PublicInputs::from_json(resolved.to_string())?,
+            None => inputs.clone(),
+        };
+    air::public::validate_public_inputs(&ir.public_inputs, &inputs.value()).map_err(invalid)?;
+    inputs.bind(config.pubio_encoding, ir, config.undeclared_inputs)
+}
+
+/// Row count `ir` proves with for `inputs`: a `rows_hint` expression
+/// evaluated against the (resolved) public inputs and checked against the
+/// profile's `rows_max` and the backend's `max_rows`, or the fixed hint.
+/// `None` when the AIR declares no hint.
+pub fn resolve_rows(
+    config: &config::Config,
+    ir: &air::AirIr,
+    inputs: &backend::PublicInputs,
+) -> Result<Option<u32>, errors::ProveError> {
+    registry::ensure_builtins_registered();
+    let backend = registry::get_backend(&config.backend_id)?;
+    let profile = profile::resolve_profile(&config.profile_id, &config.profile_overrides)?;
+    let inputs = bind_inputs(config, ir, inputs)?;
+    Ok(pin_rows(ir, &profile, &backend, &inputs)?.fixed_rows())
+}
+
+/// `ir` with a `rows_hint` expression replaced by its value for `inputs`, so
+/// backends see a fixed trace shape. Fixed hints pass through unchanged.
+fn pin_rows<'a>(
+    ir: &'a air::AirIr,
+    profile: &Profile,
+    backend: &registry::DynBackend,
+    inputs: &backend::PublicInputs,
+) -> Result<std::borrow::Cow<'a, air::AirIr>, errors::ProveError> {
+    if !ir
+        .rows_hint
+        .as_ref()
+        .is_some_and(air::rows::RowsHint::is_expr)
+    {
+        return Ok(std::borrow::Cow::Borrowed(ir));
+    }
+    let invalid = |e: anyhow::Error| errors::ProveError::InvalidInput(format!("{e:#}"));
+    let pinned = ir.with_rows_for(&inputs.value()).map_err(invalid)?;
+    let rows = pinned.fixed_rows().unwrap_or_default();
+    let limits = [
+        ("profile rows_max", profile.rows_max),
+        ("backend max_rows", backend.prover.capabilities().max_rows),
+    ];
+    for (what, max) in limits {
+        if let Some(max) = max.filter(|max| rows > *max) {
+            return Err(errors::ProveError::InvalidInput(format!(
+                "rows_hint evaluates to {rows}, above the {what} {max}"
+            )));
+        }
+    }
+    Ok(std::borrow::Cow::Owned(pinned))
+}
+
+fn dispatch_target(
+    config: &config::Config,
+    ir: &air::AirIr,
+) -> Result<
+    (
+        std::sync::Arc<registry::DynBackend>,
+        Profile,
+        backend::CallContext,
+    ),
+    errors::ProveError,
+> {
+    registry::ensure_builtins_registered();
+    let config = &if config.air_hints.is_ignore() {
+        std::borrow::Cow::Borrowed(config)
+    } else {
+        let mut hinted = config.clone();
+        hinted.apply_air_hints(&ir.meta)?;
+        std::borrow::Cow::Owned(hinted)
+    };
+    validate_config(config)?;
+    validate::validate_ir_against_backend(ir, &config.backend_id)?;
+    let backend = registry::get_backend(&config.backend_id)?;
+    let profile = profile::resolve_profile(&config.profile_id, &config.profile_overrides)?;
+    registry::emit(registry::RegistryEvent::BackendSelected {
+        requested: config.backend_id.clone(),
+        backend_id: backend.prover.id(),
+        profile_id: profile.id.clone(),
+    });
+    let hash = crypto::registry::canonical_hash_id(&config.hash).unwrap_or(&config.hash);
+    let ctx = backend::CallContext {
+        options: (!config.options.is_null()).then(|| config.options.clone()),
+        deadline: config.deadline,
+        hash: Some(hash.to_string()),
+        ..backend::CallContext::new(config.config_hash())
+    };
+    Ok((backend, profile, ctx))
+}
+
 /// Version helper for CLI
 pub fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
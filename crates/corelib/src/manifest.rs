@@ -0,0 +1,226 @@
+//! Determinism manifest persisted alongside proofs (see docs/manifest-format.md).
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::options::options_hash;
+use crate::buildinfo::{self, BuildInfo};
+use crate::config::Config;
+use crate::evm::pubio::PubIoEncoding;
+use crate::profile::adaptive::AdaptiveChoice;
+use crate::profile::ProfileOverrides;
+
+/// Inputs that must be identical for two runs to produce the same proof.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeterminismVector {
+    pub compiler_commit: String,
+    pub backend: String,
+    pub system: String,
+    pub seed: String,
+    pub profile: String,
+    /// Profile parameters overridden for this run; empty when the preset was used as-is.
+    #[serde(default, skip_serializing_if = "ProfileOverrides::is_empty")]
+    pub profile_overrides: ProfileOverrides,
+    /// Public IO encoding bound by the proof; omitted for the default `json`.
+    #[serde(default, skip_serializing_if = "PubIoEncoding::is_json")]
+    pub pubio_encoding: PubIoEncoding,
+    /// Hex commitment to the AIR's declared constant/periodic column contents
+    /// (see `AirColumns::fixed_commitment`); omitted when they are implicit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_columns: Option<String>,
+    /// Trace rows a `rows_hint` expression evaluated to for this run's public
+    /// inputs; omitted for fixed hints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<u32>,
+    /// BLAKE3 of the canonical JSON of `Config::options` (see
+    /// `backend::options::options_hash`); omitted when no options were passed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_options: Option<String>,
+    /// `fri_queries` / `grind_bits` chosen at prove time (`zkd prove
+    /// --adaptive-queries`); they are also in `profile_overrides`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<AdaptiveChoice>,
+    pub manifest_hash: String,
+}
+
+impl DeterminismVector {
+    /// Build a sealed vector for `cfg` with the given transcript seed.
+    pub fn from_config(cfg: &Config, seed: impl Into<String>) -> Self {
+        let mut v = Self {
+            compiler_commit: compiler_commit().to_string(),
+            backend: cfg.backend_id.clone(),
+            system: host_system(),
+            seed: seed.into(),
+            profile: cfg.profile_id.clone(),
+            profile_overrides: cfg.profile_overrides.clone(),
+            pubio_encoding: cfg.pubio_encoding,
+            fixed_columns: None,
+            rows: None,
+            backend_options: (!cfg.options.is_null()).then(|| options_hash(&cfg.options)),
+            adaptive: None,
+            manifest_hash: String::new(),
+        };
+        v.manifest_hash = v.compute_hash();
+        v
+    }
+
+    /// Record the fixed column commitment of the proven AIR and reseal.
+    pub fn with_fixed_columns(mut self, commitment: Option<[u8; 32]>) -> Self {
+        self.fixed_columns = commitment.map(|c| blake3::Hash::from(c).to_hex().to_string());
+        self.manifest_hash = self.compute_hash();
+        self
+    }
+
+    /// Record the evaluated `rows_hint` expression and reseal.
+    pub fn with_rows(mut self, rows: Option<u32>) -> Self {
+        self.rows = rows;
+        self.manifest_hash = self.compute_hash();
+        self
+    }
+
+    /// Record the adaptive query/grinding choice and reseal.
+    pub fn with_adaptive(mut self, choice: Option<AdaptiveChoice>) -> Self {
+        self.adaptive = choice;
+        self.manifest_hash = self.compute_hash();
+        self
+    }
+
+    /// BLAKE3 over the canonical (key-sorted) JSON of the vector with an empty
+    /// `manifest_hash`, hex encoded.
+    pub fn compute_hash(&self) -> String {
+        let mut unsealed = self.clone();
+        unsealed.manifest_hash.clear();
+        let value = serde_json::to_value(&unsealed).expect("manifest vector serializes");
+        let json = serde_json::to_string(&value).expect("manifest vector serializes");
+        blake3::hash(json.as_bytes()).to_hex().to_string()
+    }
+
+    /// Check the recorded `manifest_hash` against a fresh derivation.
+    pub fn verify(&self) -> Result<()> {
+        let expected = self.compute_hash();
+        if self.manifest_hash != expected {
+            return Err(anyhow!(
+                "determinism manifest mismatch: expected {}, saw {}",
+                expected,
+                self.manifest_hash
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeterminismManifest {
+    pub program: String,
+    pub digest: String,
+    pub determinism_vector: DeterminismVector,
+    /// Provenance of the binary that wrote the manifest (`zkd prove --build-info`).
+    /// Kept outside the vector so `manifest_hash` does not depend on it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildInfo>,
+}
+
+impl DeterminismManifest {
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(data: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(data)
+    }
+}
+
+fn compiler_commit() -> &'static str {
+    buildinfo::GIT_COMMIT
+}
+
+fn host_system() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_change_manifest_hash() {
+        let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+        let plain = DeterminismVector::from_config(&cfg, "00");
+        let mut overrides = ProfileOverrides::new();
+        overrides.insert("fri_queries".to_string(), 40);
+        let tweaked =
+            DeterminismVector::from_config(&cfg.with_profile_overrides(overrides.clone()), "00");
+        assert_ne!(plain.manifest_hash, tweaked.manifest_hash);
+        assert_eq!(tweaked.profile_overrides, overrides);
+        plain.verify().unwrap();
+        tweaked.verify().unwrap();
+    }
+
+    #[test]
+    fn backend_options_are_hashed_into_the_vector() {
+        let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+        let plain = DeterminismVector::from_config(&cfg, "00");
+        assert_eq!(plain.backend_options, None);
+        let opts = serde_json::json!({ "batching": "linear", "partition_size": 4 });
+        let with = DeterminismVector::from_config(&cfg.clone().with_options(opts.clone()), "00");
+        assert_eq!(with.backend_options, Some(options_hash(&opts)));
+        assert_ne!(plain.manifest_hash, with.manifest_hash);
+        with.verify().unwrap();
+    }
+
+    #[test]
+    fn tampered_overrides_fail_verification() {
+        let mut overrides = ProfileOverrides::new();
+        overrides.insert("grind_bits".to_string(), 20);
+        let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced")
+            .with_profile_overrides(overrides);
+        let mut v = DeterminismVector::from_config(&cfg, "00");
+        v.profile_overrides.insert("grind_bits".to_string(), 0);
+        assert!(v.verify().is_err());
+    }
+
+    #[test]
+    fn fixed_column_commitment_is_sealed_into_the_vector() {
+        let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+        let plain = DeterminismVector::from_config(&cfg, "00");
+        let fixed = plain.clone().with_fixed_columns(Some([0xab; 32]));
+        assert_eq!(
+            fixed.fixed_columns.as_deref(),
+            Some("ab".repeat(32).as_str())
+        );
+        assert_ne!(plain.manifest_hash, fixed.manifest_hash);
+        fixed.verify().unwrap();
+        assert_eq!(plain.clone().with_fixed_columns(None), plain);
+        assert!(!serde_json::to_string(&plain)
+            .unwrap()
+            .contains("fixed_columns"));
+
+        let mut swapped = fixed.clone();
+        swapped.fixed_columns = Some("cd".repeat(32));
+        assert!(swapped.verify().is_err());
+    }
+
+    #[test]
+    fn build_info_is_optional_and_outside_the_vector_hash() {
+        let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+        let mut manifest = DeterminismManifest {
+            program: "toy".to_string(),
+            digest: "0x00".to_string(),
+            determinism_vector: DeterminismVector::from_config(&cfg, "00"),
+            build: None,
+        };
+        let plain = manifest.to_json_pretty().unwrap();
+        assert!(!plain.contains("\"build\""));
+        assert_eq!(DeterminismManifest::from_json(&plain).unwrap(), manifest);
+
+        manifest.build = Some(buildinfo::build_info());
+        let with_build =
+            DeterminismManifest::from_json(&manifest.to_json_pretty().unwrap()).unwrap();
+        assert_eq!(with_build.build, Some(buildinfo::build_info()));
+        assert_eq!(
+            with_build.determinism_vector.compiler_commit,
+            buildinfo::GIT_COMMIT
+        );
+        with_build.determinism_vector.verify().unwrap();
+    }
+}
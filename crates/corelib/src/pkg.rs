@@ -0,0 +1,325 @@
+//! Program packages: one `.zkpkg` file carrying a vetted AIR together with
+//! its recommended profiles, registry metadata, example inputs and the
+//! golden digests proofs of those inputs must reproduce.
+//!
+//! A package is built from a `zkpkg.toml` manifest whose paths are relative
+//! to the manifest:
+//!
+//! ```toml
+//! name = "toy_merkle"
+//! version = "0.1.0"
+//! description = "Toy Merkle membership"
+//! air = "toy.air"
+//! profiles = ["balanced", "secure"]   # recommended, first is the default
+//!
+//! [inputs]
+//! default = "inputs.json"
+//!
+//! [[golden]]
+//! inputs = "default"
+//! backend = "native@0.0"
+//! field = "Prime254"
+//! hash = "blake3"
+//! fri_arity = 2
+//! profile = "balanced"
+//! digest = "0x…"
+//! ```
+//!
+//! The `.zkpkg` itself is JSON ([`Package`], schema [`PKG_SCHEMA`]) with
+//! the sources inlined, so it can be copied or archived as one file.
+//! A [`PackageStore`] installs packages under one directory, and
+//! `pkg:<name>` program paths resolve to the installed AIR:
+//!
+//! ```text
+//! <name>/package.zkpkg       the package as installed
+//! <name>/program.air         its AIR source
+//! <name>/inputs/<key>.json   its example inputs
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::air::parse_air_str;
+use crate::evm::typed::program_hash;
+
+/// `schema` of every `.zkpkg` this version writes and reads.
+pub const PKG_SCHEMA: &str = "zkd-pkg/1";
+
+/// Program path prefix naming an installed package, as in `pkg:toy_merkle`.
+pub const PKG_PREFIX: &str = "pkg:";
+
+/// A digest a proof of one of the package's example inputs must have.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenDigest {
+    /// Key into the package's `inputs`.
+    pub inputs: String,
+    pub backend: String,
+    pub field: String,
+    pub hash: String,
+    pub fri_arity: u32,
+    pub profile: String,
+    /// `0x`-prefixed EVM digest `D` of the proof.
+    pub digest: String,
+}
+
+/// A `zkpkg.toml` source manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageManifest {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// AIR path, relative to the manifest.
+    pub air: String,
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// Example inputs by key; paths relative to the manifest.
+    #[serde(default)]
+    pub inputs: BTreeMap<String, String>,
+    #[serde(default)]
+    pub golden: Vec<GoldenDigest>,
+}
+
+/// A `.zkpkg` file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Package {
+    pub schema: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `0x` Keccak-256 of `air`, as in `zkd evm-typed-hash`.
+    pub program_hash: String,
+    /// AIR source text.
+    pub air: String,
+    /// Recommended profile ids, the first being the default.
+    pub profiles: Vec<String>,
+    /// Example public inputs JSON text by key, kept verbatim.
+    pub inputs: BTreeMap<String, String>,
+    pub golden: Vec<GoldenDigest>,
+}
+
+impl Package {
+    /// Read the manifest at `path` and the files it names.
+    pub fn from_manifest(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let manifest: PackageManifest =
+            toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let read = |rel: &str| {
+            let p = dir.join(rel);
+            fs::read_to_string(&p).with_context(|| format!("reading {}", p.display()))
+        };
+        let air = read(&manifest.air)?;
+        let mut inputs = BTreeMap::new();
+        for (key, rel) in &manifest.inputs {
+            inputs.insert(key.clone(), read(rel)?);
+        }
+        let package = Package {
+            schema: PKG_SCHEMA.to_string(),
+            name: manifest.name,
+            version: manifest.version,
+            description: manifest.description,
+            program_hash: hex_hash(&air),
+            air,
+            profiles: manifest.profiles,
+            inputs,
+            golden: manifest.golden,
+        };
+        package
+            .check()
+            .with_context(|| format!("in {}", path.display()))?;
+        Ok(package)
+    }
+
+    /// Parse and [`check`](Self::check) a `.zkpkg`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let package: Package = serde_json::from_slice(bytes).context("parsing package")?;
+        package.check()?;
+        Ok(package)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_bytes(&bytes).with_context(|| format!("in {}", path.display()))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = serde_json::to_vec_pretty(self).expect("package serializes");
+        out.push(b'\n');
+        out
+    }
+
+    /// Structural checks: schema, name, `program_hash`, that the AIR and
+    /// example inputs parse, and that golden digests name known inputs.
+    /// Golden digests are only reproduced by proving (`zkd pkg build`).
+    pub fn check(&self) -> Result<()> {
+        ensure!(
+            self.schema == PKG_SCHEMA,
+            "unsupported package schema '{}' (expected {})",
+            self.schema,
+            PKG_SCHEMA
+        );
+        check_name(&self.name)?;
+        ensure!(!self.version.is_empty(), "package version is empty");
+        ensure!(
+            self.program_hash == hex_hash(&self.air),
+            "program_hash {} does not match the package's AIR",
+            self.program_hash
+        );
+        parse_air_str(&self.air).context("package AIR")?;
+        for (key, json) in &self.inputs {
+            check_name(key)?;
+            serde_json::from_str::<serde_json::Value>(json)
+                .with_context(|| format!("example inputs '{}' are not JSON", key))?;
+        }
+        for golden in &self.golden {
+            ensure!(
+                self.inputs.contains_key(&golden.inputs),
+                "golden digest names unknown inputs '{}'",
+                golden.inputs
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Directory of installed packages; see the module docs for the layout.
+#[derive(Debug, Clone)]
+pub struct PackageStore {
+    root: PathBuf,
+}
+
+impl PackageStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `$ZKD_PKG_DIR`, else `~/.zkd/packages`.
+    pub fn default_root() -> Result<PathBuf> {
+        if let Some(dir) = std::env::var_os("ZKD_PKG_DIR") {
+            return Ok(PathBuf::from(dir));
+        }
+        let home = std::env::var_os("HOME")
+            .context("neither $ZKD_PKG_DIR nor $HOME is set; cannot locate installed packages")?;
+        Ok(PathBuf::from(home).join(".zkd").join("packages"))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Install `package`, replacing any installed package of that name.
+    /// Returns the installed AIR path.
+    pub fn install(&self, package: &Package) -> Result<PathBuf> {
+        package.check()?;
+        let dir = self.root.join(&package.name);
+        let staging = self.root.join(format!(".{}.partial", package.name));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)
+                .with_context(|| format!("failed to remove '{}'", staging.display()))?;
+        }
+        fs::create_dir_all(staging.join("inputs"))
+            .with_context(|| format!("failed to create dir '{}'", staging.display()))?;
+        let write = |rel: &str, bytes: &[u8]| {
+            let p = staging.join(rel);
+            fs::write(&p, bytes).with_context(|| format!("failed to write '{}'", p.display()))
+        };
+        write("package.zkpkg", &package.to_bytes())?;
+        write("program.air", package.air.as_bytes())?;
+        for (key, json) in &package.inputs {
+            write(&format!("inputs/{}.json", key), json.as_bytes())?;
+        }
+        // Swap the staged copy in, so `pkg:<name>` never sees a half install.
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("failed to remove '{}'", dir.display()))?;
+        }
+        fs::rename(&staging, &dir)
+            .with_context(|| format!("failed to install '{}'", dir.display()))?;
+        Ok(dir.join("program.air"))
+    }
+
+    /// The installed package `name`, if any.
+    pub fn get(&self, name: &str) -> Result<Option<Package>> {
+        check_name(name)?;
+        let path = self.root.join(name).join("package.zkpkg");
+        if !path.is_file() {
+            return Ok(None);
+        }
+        Package::load(&path).map(Some)
+    }
+
+    /// Every installed package, by name.
+    pub fn list(&self) -> Result<Vec<Package>> {
+        let mut out = Vec::new();
+        if !self.root.is_dir() {
+            return Ok(out);
+        }
+        for entry in
+            fs::read_dir(&self.root).with_context(|| format!("reading {}", self.root.display()))?
+        {
+            let name = entry?.file_name();
+            let Some(name) = name.to_str() else { continue };
+            if check_name(name).is_err() {
+                continue;
+            }
+            if let Some(package) = self.get(name)? {
+                out.push(package);
+            }
+        }
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(out)
+    }
+
+    /// AIR path of the installed package `name`.
+    pub fn program_path(&self, name: &str) -> Result<PathBuf> {
+        check_name(name)?;
+        let path = self.root.join(name).join("program.air");
+        if !path.is_file() {
+            bail!(
+                "package '{}' is not installed in {} (zkd pkg install)",
+                name,
+                self.root.display()
+            );
+        }
+        Ok(path)
+    }
+
+    /// `program` itself, or the installed AIR when it is `pkg:<name>`.
+    pub fn resolve(&self, program: &str) -> Result<PathBuf> {
+        match program.strip_prefix(PKG_PREFIX) {
+            Some(name) => self.program_path(name),
+            None => Ok(PathBuf::from(program)),
+        }
+    }
+}
+
+/// Names and input keys become directory and file names, so they are
+/// limited to `[A-Za-z0-9_-]` and may not start with `-`.
+fn check_name(name: &str) -> Result<()> {
+    ensure!(
+        !name.is_empty()
+            && !name.starts_with('-')
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-'),
+        "invalid package name '{}' (use letters, digits, '_' and '-')",
+        name
+    );
+    Ok(())
+}
+
+fn hex_hash(air: &str) -> String {
+    let hash = program_hash(air.as_bytes());
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("0x{}", hex)
+}
@@ -0,0 +1,503 @@
+//! Backends loaded at runtime from shared libraries.
+//!
+//! With the `plugins` feature, [`load_from_env`] loads every shared library
+//! (`.so`, `.dylib` or `.dll`) in the directories listed in
+//! `ZKD_BACKEND_PATH`, separated like `PATH`, in file-name order. Nothing
+//! loads plugins implicitly: loading runs the library's code in-process, so
+//! only the `zkd` CLI calls it, and embedders such as the C FFI and
+//! `zkd-verify` build without the feature. Each library exports the C entry
+//! point declared in `include/zkd_backend.h`:
+//!
+//! ```c
+//! int32_t zkd_register_backend(uint32_t host_min, uint32_t host_max,
+//!                              const zkd_backend_v1 **out);
+//! ```
+//!
+//! The host passes the range of ABI versions it speaks ([`MIN_ABI_VERSION`]
+//! ..= [`ABI_VERSION`]). The plugin picks one and returns a static function
+//! table whose `abi_version` names it, or [`ERR_ABI`] when it speaks none of
+//! them. The table reports the backend id (`name@version`) and its
+//! [`Capabilities`] as JSON, and proves and verifies. A statically linked
+//! plugin registers through [`register_entry`], which needs no feature.
+//!
+//! Plugins see proofs through a JSON request
+//! `{"abi_version", "backend_id", "air", "profile", "inputs"}` (the resolved
+//! [`Profile`] and the canonical inputs) and handle the proof body only. The
+//! host writes and checks the header, as the builtin backends do, so a plugin
+//! cannot get the header bindings wrong. Plugin functions may be called from
+//! several threads at once and must not unwind.
+//!
+//! A library that fails to load, negotiate or validate is skipped. The failure
+//! is kept for [`failures`], which `zkd backend-ls` reports. Loaded libraries
+//! stay mapped while their backend is registered, which is for the life of
+//! the process.
+
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CStr};
+#[cfg(feature = "plugins")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "plugins")]
+use std::sync::Once;
+use std::sync::{Arc, RwLock};
+
+use serde::Serialize;
+
+use crate::air::AirIr;
+use crate::backend::{
+    CallContext, Capabilities, ProverBackend, PublicInputs, VerifierBackend, NONDETERMINISM_SOURCES,
+};
+use crate::crypto::field::field_by_id;
+use crate::crypto::registry::canonical_hash_id;
+use crate::errors::{PluginError, ProveError, VerifyError};
+use crate::profile::Profile;
+use crate::proof::pubio::pubio_hash;
+use crate::proof::{hash64, Proof, ProofHeader, ProofView};
+use crate::registry;
+
+/// Environment variable listing plugin directories.
+pub const PATH_VAR: &str = "ZKD_BACKEND_PATH";
+/// Newest plugin ABI this host speaks.
+pub const ABI_VERSION: u32 = 1;
+/// Oldest plugin ABI this host speaks.
+pub const MIN_ABI_VERSION: u32 = 1;
+#[cfg(feature = "plugins")]
+const ENTRY_POINT: &[u8] = b"zkd_register_backend\0";
+
+/// Status codes of plugin functions (`ZKD_BACKEND_*` in the C header).
+pub const OK: i32 = 0;
+pub const ERR_INVALID_INPUT: i32 = 1;
+pub const ERR_UNSUPPORTED: i32 = 2;
+pub const ERR_REJECTED: i32 = 3;
+pub const ERR_INTERNAL: i32 = 4;
+pub const ERR_ABI: i32 = 5;
+
+/// Bytes allocated by the plugin: a proof body on success, a UTF-8 error
+/// message otherwise. Released with the table's `free_buf`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Buf {
+    pub ptr: *mut u8,
+    pub len: u64,
+}
+
+/// `zkd_backend_v1`: the function table of ABI version 1.
+#[repr(C)]
+pub struct BackendV1 {
+    pub abi_version: u32,
+    /// `name@version`, NUL-terminated.
+    pub id: *const c_char,
+    /// [`Capabilities`] as JSON, NUL-terminated.
+    pub capabilities_json: *const c_char,
+    pub prove: unsafe extern "C" fn(request: *const u8, request_len: u64, out: *mut Buf) -> i32,
+    pub verify: unsafe extern "C" fn(
+        request: *const u8,
+        request_len: u64,
+        body: *const u8,
+        body_len: u64,
+        out: *mut Buf,
+    ) -> i32,
+    pub free_buf: unsafe extern "C" fn(buf: Buf),
+}
+
+/// Signature of `zkd_register_backend`.
+pub type RegisterFn =
+    unsafe extern "C" fn(host_min: u32, host_max: u32, out: *mut *const BackendV1) -> i32;
+
+/// Where a registered plugin backend came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginInfo {
+    /// The shared library; `None` for [`register_entry`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub abi_version: u32,
+}
+
+/// A library under `ZKD_BACKEND_PATH` that was skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginFailure {
+    pub path: String,
+    pub error: String,
+}
+
+static LOADED: RwLock<BTreeMap<&'static str, PluginInfo>> = RwLock::new(BTreeMap::new());
+static FAILURES: RwLock<Vec<PluginFailure>> = RwLock::new(Vec::new());
+
+/// Plugin origin of backend `id`, if it was loaded as a plugin.
+pub fn info(id: &str) -> Option<PluginInfo> {
+    LOADED
+        .read()
+        .expect("poisoned plugin table")
+        .get(id)
+        .cloned()
+}
+
+/// Libraries skipped while loading `ZKD_BACKEND_PATH`.
+pub fn failures() -> Vec<PluginFailure> {
+    FAILURES.read().expect("poisoned plugin table").clone()
+}
+
+/// A loaded shared library; nothing can be loaded without `plugins`.
+#[cfg(feature = "plugins")]
+type Library = libloading::Library;
+#[cfg(not(feature = "plugins"))]
+type Library = std::convert::Infallible;
+
+#[cfg(feature = "plugins")]
+static LOAD: Once = Once::new();
+
+/// Load every library in the `ZKD_BACKEND_PATH` directories, recording
+/// failures for [`failures`]; returns the ids registered. Only the first
+/// call loads anything. Registers the builtins first.
+///
+/// Loading runs the libraries' code in this process, so only call it where
+/// whoever sets the environment is trusted with that.
+#[cfg(feature = "plugins")]
+pub fn load_from_env() -> Vec<&'static str> {
+    let mut ids = Vec::new();
+    LOAD.call_once(|| {
+        registry::ensure_builtins_registered();
+        if let Some(dirs) = std::env::var_os(PATH_VAR) {
+            ids = load_paths(&dirs);
+        }
+    });
+    ids
+}
+
+#[cfg(feature = "plugins")]
+fn load_paths(dirs: &std::ffi::OsStr) -> Vec<&'static str> {
+    std::env::split_paths(dirs)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .flat_map(|dir| load_dir(&dir))
+        .filter_map(|(path, result)| match result {
+            Ok(id) => Some(id),
+            Err(e) => {
+                FAILURES
+                    .write()
+                    .expect("poisoned plugin table")
+                    .push(PluginFailure {
+                        path: path.display().to_string(),
+                        error: e.to_string(),
+                    });
+                None
+            }
+        })
+        .collect()
+}
+
+/// [`load_library`] every shared library in `dir`, in file-name order.
+#[cfg(feature = "plugins")]
+pub fn load_dir(dir: &Path) -> Vec<(PathBuf, Result<&'static str, PluginError>)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            let error = PluginError::Load {
+                path: dir.display().to_string(),
+                reason: e.to_string(),
+            };
+            return vec![(dir.to_path_buf(), Err(error))];
+        }
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && p.extension()
+                    .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let result = load_library(&path);
+            (path, result)
+        })
+        .collect()
+}
+
+/// Load the plugin at `path` and register its backend; returns its id.
+///
+/// Loading runs the library's initializers, so only load trusted code.
+#[cfg(feature = "plugins")]
+pub fn load_library(path: &Path) -> Result<&'static str, PluginError> {
+    let shown = path.display().to_string();
+    // SAFETY: the caller trusts the library (see above); its initializers
+    // are the only code run before the ABI checks.
+    let library = unsafe { Library::new(path) }.map_err(|e| PluginError::Load {
+        path: shown.clone(),
+        reason: e.to_string(),
+    })?;
+    // SAFETY: `RegisterFn` is the entry point's documented signature.
+    let entry = unsafe { library.get::<RegisterFn>(ENTRY_POINT) }
+        .map(|symbol| *symbol)
+        .map_err(|_| PluginError::MissingEntryPoint(shown.clone()))?;
+    // SAFETY: `entry` belongs to `library`, which the backend keeps loaded.
+    unsafe { register(entry, Some(library), Some(shown)) }
+}
+
+/// Register the backend behind a `zkd_register_backend` function that is
+/// already part of this process, e.g. a statically linked plugin.
+///
+/// # Safety
+///
+/// `entry` must implement the ABI in `include/zkd_backend.h`, and the
+/// function table it returns must stay valid for the life of the process.
+pub unsafe fn register_entry(entry: RegisterFn) -> Result<&'static str, PluginError> {
+    register(entry, None, None)
+}
+
+unsafe fn register(
+    entry: RegisterFn,
+    library: Option<Library>,
+    path: Option<String>,
+) -> Result<&'static str, PluginError> {
+    let mut table: *const BackendV1 = std::ptr::null();
+    let status = entry(MIN_ABI_VERSION, ABI_VERSION, &mut table);
+    let mismatch = |plugin| PluginError::AbiMismatch {
+        host_min: MIN_ABI_VERSION,
+        host_max: ABI_VERSION,
+        plugin,
+    };
+    if status == ERR_ABI {
+        return Err(mismatch(None));
+    }
+    if status != OK || table.is_null() {
+        return Err(PluginError::InvalidBackend(format!(
+            "zkd_register_backend returned {status}"
+        )));
+    }
+    let table = &*table;
+    if !(MIN_ABI_VERSION..=ABI_VERSION).contains(&table.abi_version) {
+        return Err(mismatch(Some(table.abi_version)));
+    }
+    // Copies outlive the library even if registration fails and unloads it.
+    let id: &'static str = Box::leak(c_string(table.id, "id")?.into_boxed_str());
+    if id
+        .split_once('@')
+        .is_none_or(|(n, v)| n.is_empty() || v.is_empty())
+    {
+        return Err(PluginError::InvalidBackend(format!(
+            "id '{id}' is not name@version"
+        )));
+    }
+    let json: &'static str =
+        Box::leak(c_string(table.capabilities_json, "capabilities_json")?.into_boxed_str());
+    let caps: Capabilities = serde_json::from_str(json)
+        .map_err(|e| PluginError::InvalidBackend(format!("{id}: capabilities: {e}")))?;
+    check_capabilities(&caps).map_err(|e| PluginError::InvalidBackend(format!("{id}: {e}")))?;
+
+    let backend = PluginBackend(Arc::new(Loaded {
+        id,
+        caps,
+        table,
+        _library: library,
+    }));
+    registry::register_backend(Box::new(backend.clone()), Box::new(backend))?;
+    LOADED.write().expect("poisoned plugin table").insert(
+        id,
+        PluginInfo {
+            path,
+            abi_version: table.abi_version,
+        },
+    );
+    Ok(id)
+}
+
+unsafe fn c_string(ptr: *const c_char, what: &str) -> Result<String, PluginError> {
+    if ptr.is_null() {
+        return Err(PluginError::InvalidBackend(format!("{what} is NULL")));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(str::to_owned)
+        .map_err(|_| PluginError::InvalidBackend(format!("{what} is not UTF-8")))
+}
+
+/// The checks `zkd-backend-testkit` runs on capabilities, minus the registry.
+fn check_capabilities(caps: &Capabilities) -> Result<(), String> {
+    if caps.fields.is_empty() || caps.hashes.is_empty() || caps.fri_arities.is_empty() {
+        return Err("capabilities list no fields, hashes or FRI arities".into());
+    }
+    if let Some(field) = caps.fields.iter().find(|f| field_by_id(f).is_none()) {
+        return Err(format!("unknown field '{field}'"));
+    }
+    if let Some(hash) = caps
+        .hashes
+        .iter()
+        .find(|h| canonical_hash_id(h) != Some(**h))
+    {
+        return Err(format!("unknown or non-canonical hash '{hash}'"));
+    }
+    if let Some(arity) = caps
+        .fri_arities
+        .iter()
+        .find(|a| **a < 2 || !a.is_power_of_two())
+    {
+        return Err(format!("FRI arity {arity} is not a power of two"));
+    }
+    if !["none", "stark-in-stark", "snark-wrapper"].contains(&caps.recursion) {
+        return Err(format!("unknown recursion '{}'", caps.recursion));
+    }
+    if let Some(source) = caps
+        .nondeterminism
+        .iter()
+        .find(|s| !NONDETERMINISM_SOURCES.contains(s))
+    {
+        return Err(format!("unknown nondeterminism source '{source}'"));
+    }
+    Ok(())
+}
+
+struct Loaded {
+    id: &'static str,
+    caps: Capabilities,
+    table: *const BackendV1,
+    /// Keeps `table` mapped; `None` for [`register_entry`].
+    _library: Option<Library>,
+}
+
+// SAFETY: the ABI requires plugin functions to be thread-safe, and the table
+// is immutable.
+unsafe impl Send for Loaded {}
+unsafe impl Sync for Loaded {}
+
+#[derive(Clone)]
+struct PluginBackend(Arc<Loaded>);
+
+impl PluginBackend {
+    fn request(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+    ) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "abi_version": ABI_VERSION,
+            "backend_id": self.0.id,
+            "air": ir,
+            "profile": profile,
+            "hash": ctx.hash.as_deref().unwrap_or(ir.meta.hash.as_str()),
+            "inputs": serde_json::from_str::<serde_json::Value>(inputs.canonical_json())
+                .expect("canonical JSON parses"),
+        }))
+        .expect("request serializes")
+    }
+
+    /// Run a table function, taking ownership of its output buffer.
+    fn call(&self, f: impl FnOnce(&BackendV1, *mut Buf) -> i32) -> (i32, Vec<u8>) {
+        // SAFETY: `table` is valid while `self.0` holds the library.
+        let table = unsafe { &*self.0.table };
+        let mut out = Buf {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+        };
+        let status = f(table, &mut out);
+        let bytes = if out.ptr.is_null() {
+            Vec::new()
+        } else {
+            // SAFETY: the plugin handed over `len` initialized bytes at `ptr`.
+            let bytes = unsafe { std::slice::from_raw_parts(out.ptr, out.len as usize) }.to_vec();
+            unsafe { (table.free_buf)(out) };
+            bytes
+        };
+        (status, bytes)
+    }
+
+    fn header(&self, profile: &Profile, ctx: &CallContext, inputs: &PublicInputs) -> ProofHeader {
+        ProofHeader {
+            backend_id_hash: hash64("BACKEND", self.0.id.as_bytes()),
+            profile_id_hash: hash64("PROFILE", profile.id.as_bytes()),
+            pubio_hash: pubio_hash(inputs),
+            body_len: 0,
+            config_hash: ctx.config_hash,
+            flags: 0,
+        }
+    }
+}
+
+impl ProverBackend for PluginBackend {
+    fn id(&self) -> &'static str {
+        self.0.id
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.0.caps.clone()
+    }
+
+    fn prove(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        let request = self.request(ir, profile, ctx, inputs);
+        let (status, out) =
+            self.call(|t, buf| unsafe { (t.prove)(request.as_ptr(), request.len() as u64, buf) });
+        let message = || String::from_utf8_lossy(&out).into_owned();
+        match status {
+            OK => Ok(Proof::new(self.header(profile, ctx, inputs), out)),
+            ERR_INVALID_INPUT => Err(ProveError::InvalidInput(message())),
+            ERR_UNSUPPORTED => Err(ProveError::Unsupported {
+                backend_id: self.0.id.to_string(),
+                feature: message(),
+            }),
+            code => Err(ProveError::Backend(anyhow::anyhow!(
+                "plugin {} failed ({}): {}",
+                self.0.id,
+                code,
+                message()
+            ))),
+        }
+    }
+}
+
+impl VerifierBackend for PluginBackend {
+    fn verify(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+        proof: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        if proof.body.len() as u64 != proof.header.body_len {
+            return Err(VerifyError::BodyLength {
+                expected: proof.header.body_len,
+                actual: proof.body.len() as u64,
+            });
+        }
+        proof
+            .header
+            .check_bindings(&self.header(profile, ctx, inputs))?;
+
+        let request = self.request(ir, profile, ctx, inputs);
+        let body = proof.body;
+        let (status, out) = self.call(|t, buf| unsafe {
+            (t.verify)(
+                request.as_ptr(),
+                request.len() as u64,
+                body.as_ptr(),
+                body.len() as u64,
+                buf,
+            )
+        });
+        let message = || String::from_utf8_lossy(&out).into_owned();
+        match status {
+            OK => Ok(()),
+            ERR_INVALID_INPUT => Err(VerifyError::Malformed(message())),
+            ERR_REJECTED => Err(VerifyError::Rejected(message())),
+            ERR_UNSUPPORTED => Err(VerifyError::Unsupported {
+                backend_id: self.0.id.to_string(),
+                feature: message(),
+            }),
+            code => Err(VerifyError::Backend(anyhow::anyhow!(
+                "plugin {} failed ({}): {}",
+                self.0.id,
+                code,
+                message()
+            ))),
+        }
+    }
+}
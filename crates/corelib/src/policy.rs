@@ -0,0 +1,343 @@
+//! Relying-party verification policies.
+//!
+//! A proof that verifies is only as good as what it proves and how. A
+//! [`VerifyPolicy`] states, in one auditable document, which proofs a relying
+//! party accepts:
+//!
+//! ```toml
+//! # Keccak-256 of accepted AIR sources (`evm::typed::program_hash`).
+//! programs = ["0x3f1c…"]
+//! # Minimum `lambda_bits` and estimated security of the proof's profile.
+//! min_lambda_bits = 100
+//! # Backend ids (`native@0.0`) or names (`native`).
+//! backends = ["native"]
+//! # Maximum age of the proof's RFC 3161 timestamp (`<proof>.tsr`).
+//! max_proof_age = "30d"
+//! # Ed25519 keys, one of which must have signed the proof's profile.
+//! signers = ["3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"]
+//! # Whether the public inputs may come from the proof container itself.
+//! allow_embedded_inputs = false
+//! ```
+//!
+//! Every rule is optional; an empty policy accepts any proof that verifies.
+//! [`crate::verify_with_policy`] checks the policy before running the
+//! backend verifier and fails with [`crate::errors::VerifyError::Policy`],
+//! listing every violated rule.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::deadline::parse_duration;
+use crate::evm::digest::digest_D;
+use crate::evm::typed::program_hash;
+use crate::profile::{resolve_profile, Profile, ProfileOverrides};
+use crate::proof::ProofView;
+use crate::registry;
+use crate::timestamp::{self, TokenVerifier};
+
+/// Relying-party acceptance rules; see the module docs for the file format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerifyPolicy {
+    /// `0x`-hex Keccak-256 hashes of accepted AIR sources; empty accepts any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub programs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_lambda_bits: Option<u32>,
+    /// Accepted backend ids or names; empty accepts any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<String>,
+    /// Maximum age of the proof's timestamp, e.g. `"12h"` or `"30d"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_proof_age: Option<String>,
+    /// Hex Ed25519 public keys; the profile must be signed by one of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signers: Vec<String>,
+    /// `false` refuses inputs read from the proof container
+    /// ([`crate::proof::container`]); unset allows them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_embedded_inputs: Option<bool>,
+}
+
+/// What a policy is checked against besides the config and proof.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyEvidence<'a> {
+    /// The AIR source bytes, hashed for `programs`.
+    pub program_source: &'a [u8],
+    /// The proof's DER `TimeStampResp`, for `max_proof_age`.
+    pub timestamp: Option<&'a [u8]>,
+    /// Authenticates `timestamp` against trusted TSA certificates; without
+    /// one, `max_proof_age` is violated.
+    pub tsa: Option<&'a dyn TokenVerifier>,
+    /// Reference time for `max_proof_age`.
+    pub now: SystemTime,
+    /// The public inputs were taken from the proof container, not supplied
+    /// by the relying party; for `allow_embedded_inputs`.
+    pub embedded_inputs: bool,
+}
+
+/// One violated policy rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PolicyViolation {
+    /// The rule's key in the policy file, e.g. `"backends"`.
+    pub rule: &'static str,
+    pub reason: String,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.rule, self.reason)
+    }
+}
+
+impl VerifyPolicy {
+    /// Parse and validate a policy from TOML.
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        let policy: Self = toml::from_str(s).context("parsing verification policy")?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("reading verification policy {}", path.display()))?;
+        Self::from_toml_str(&s).with_context(|| format!("in {}", path.display()))
+    }
+
+    /// Check that every hash, duration and key is well formed.
+    pub fn validate(&self) -> Result<()> {
+        for program in &self.programs {
+            decode_hex::<32>(program).with_context(|| format!("programs: '{program}'"))?;
+        }
+        self.max_age()?;
+        self.signer_keys()?;
+        Ok(())
+    }
+
+    fn max_age(&self) -> Result<Option<Duration>> {
+        self.max_proof_age
+            .as_deref()
+            .map(|s| parse_duration(s).context("max_proof_age"))
+            .transpose()
+    }
+
+    fn signer_keys(&self) -> Result<Vec<VerifyingKey>> {
+        self.signers
+            .iter()
+            .map(|hex| {
+                let bytes = decode_hex::<32>(hex).with_context(|| format!("signers: '{hex}'"))?;
+                VerifyingKey::from_bytes(&bytes).map_err(|e| anyhow!("signers: '{hex}': {e}"))
+            })
+            .collect()
+    }
+
+    /// Every rule `config`, `proof` and `evidence` violate; empty when the
+    /// policy accepts them. The proof itself is not verified here.
+    pub fn check(
+        &self,
+        config: &Config,
+        proof: &ProofView<'_>,
+        evidence: &PolicyEvidence<'_>,
+    ) -> Vec<PolicyViolation> {
+        let mut out = Vec::new();
+
+        if !self.programs.is_empty() {
+            let hash = program_hash(evidence.program_source);
+            if !self
+                .programs
+                .iter()
+                .any(|p| decode_hex::<32>(p).is_ok_and(|p| p == hash))
+            {
+                out.push(violation(
+                    "programs",
+                    format!(
+                        "program hash 0x{} is not accepted",
+                        alloy_primitives::hex::encode(hash)
+                    ),
+                ));
+            }
+        }
+
+        if !self.backends.is_empty() {
+            registry::ensure_builtins_registered();
+            match registry::get_backend(&config.backend_id) {
+                Ok(backend) => {
+                    let id = backend.prover.id();
+                    let name = id.split('@').next().unwrap_or(id);
+                    if !self.backends.iter().any(|b| b == id || b == name) {
+                        out.push(violation(
+                            "backends",
+                            format!("backend '{id}' is not accepted"),
+                        ));
+                    }
+                }
+                Err(e) => out.push(violation("backends", e.to_string())),
+            }
+        }
+
+        if self.min_lambda_bits.is_some() {
+            // Strength is that of the parameters the proof was made with.
+            match resolve_profile(&config.profile_id, &config.profile_overrides) {
+                Ok(profile) => out.extend(self.check_strength(&profile)),
+                Err(e) => out.push(violation("min_lambda_bits", format!("{e:#}"))),
+            }
+        }
+        if !self.signers.is_empty() {
+            // Signatures cover the profile as loaded, so a run that overrides
+            // any of its parameters used a profile nobody signed.
+            if !config.profile_overrides.is_empty() {
+                let keys: Vec<&str> = config
+                    .profile_overrides
+                    .keys()
+                    .map(String::as_str)
+                    .collect();
+                out.push(violation(
+                    "signers",
+                    format!(
+                        "profile '{}' is signed as loaded, but the run overrides {}",
+                        config.profile_id,
+                        keys.join(", ")
+                    ),
+                ));
+            } else {
+                match resolve_profile(&config.profile_id, &ProfileOverrides::new()) {
+                    Ok(profile) => out.extend(self.check_signers(&profile)),
+                    Err(e) => out.push(violation("signers", format!("{e:#}"))),
+                }
+            }
+        }
+
+        if let Ok(Some(max)) = self.max_age() {
+            match proof_age(proof, evidence) {
+                Ok(age) if age > max => out.push(violation(
+                    "max_proof_age",
+                    format!(
+                        "proof is {}s old, above the {}s limit",
+                        age.as_secs(),
+                        max.as_secs()
+                    ),
+                )),
+                Ok(_) => {}
+                Err(e) => out.push(violation("max_proof_age", format!("{e:#}"))),
+            }
+        }
+
+        if evidence.embedded_inputs && self.allow_embedded_inputs == Some(false) {
+            out.push(violation(
+                "allow_embedded_inputs",
+                "public inputs were read from the proof, not supplied".into(),
+            ));
+        }
+        out
+    }
+
+    /// The `min_lambda_bits` and `signers` rules `profile` violates.
+    pub fn check_profile(&self, profile: &Profile) -> Vec<PolicyViolation> {
+        let mut out = self.check_strength(profile);
+        out.extend(self.check_signers(profile));
+        out
+    }
+
+    /// `min_lambda_bits`: both the profile's target and the security its FRI
+    /// parameters give ([`Profile::security_bits`]) must reach the minimum.
+    fn check_strength(&self, profile: &Profile) -> Vec<PolicyViolation> {
+        let Some(min) = self.min_lambda_bits else {
+            return Vec::new();
+        };
+        let reason = if profile.lambda_bits < min {
+            format!(
+                "profile '{}' targets {} bits, below {}",
+                profile.id, profile.lambda_bits, min
+            )
+        } else {
+            match profile.security_bits() {
+                Some(bits) if bits >= min => return Vec::new(),
+                Some(bits) => format!(
+                    "profile '{}' with fri_queries={} fri_blowup={} grind_bits={} gives {} \
+                     bits, below {}",
+                    profile.id,
+                    profile.fri_queries.unwrap_or_default(),
+                    profile.fri_blowup.unwrap_or_default(),
+                    profile.grind_bits.unwrap_or(0),
+                    bits,
+                    min
+                ),
+                None => format!(
+                    "profile '{}' sets no fri_blowup/fri_queries, so its security cannot \
+                     be estimated",
+                    profile.id
+                ),
+            }
+        };
+        vec![violation("min_lambda_bits", reason)]
+    }
+
+    /// `signers`: the profile must carry a signature by a listed key.
+    fn check_signers(&self, profile: &Profile) -> Vec<PolicyViolation> {
+        let mut out = Vec::new();
+        if !self.signers.is_empty() {
+            let keys = self.signer_keys().unwrap_or_default();
+            let msg = profile.signing_message();
+            let signed = profile
+                .signature
+                .as_deref()
+                .and_then(|hex| decode_hex::<64>(hex).ok())
+                .map(|sig| Signature::from_bytes(&sig))
+                .is_some_and(|sig| keys.iter().any(|k| k.verify(&msg, &sig).is_ok()));
+            if !signed {
+                out.push(violation(
+                    "signers",
+                    format!("profile '{}' is not signed by a listed key", profile.id),
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// How far a timestamp's `genTime` may be ahead of the verifier's clock.
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(300);
+
+/// Age of the proof's authenticated timestamp at `evidence.now`. A `genTime`
+/// up to [`MAX_CLOCK_SKEW`] ahead counts as fresh; further ahead is an error.
+fn proof_age(proof: &ProofView<'_>, evidence: &PolicyEvidence<'_>) -> Result<Duration> {
+    let token = evidence
+        .timestamp
+        .ok_or_else(|| anyhow!("no timestamp token for the proof"))?;
+    let tsa = evidence
+        .tsa
+        .ok_or_else(|| anyhow!("no trusted TSA certificates to authenticate the timestamp"))?;
+    let info = timestamp::check_response(token, &digest_D(&proof.header, proof.body), tsa)?;
+    let now = evidence
+        .now
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| anyhow!("reference time predates 1970"))?;
+    let stamped = Duration::from_secs(info.unix_time()?);
+    if stamped > now + MAX_CLOCK_SKEW {
+        return Err(anyhow!(
+            "timestamp genTime {} is {}s ahead of the verifier's clock (allowed skew {}s)",
+            info.time,
+            (stamped - now).as_secs(),
+            MAX_CLOCK_SKEW.as_secs()
+        ));
+    }
+    Ok(now.saturating_sub(stamped))
+}
+
+fn violation(rule: &'static str, reason: String) -> PolicyViolation {
+    PolicyViolation { rule, reason }
+}
+
+fn decode_hex<const N: usize>(s: &str) -> Result<[u8; N]> {
+    let s = s.trim();
+    alloy_primitives::hex::decode(s.strip_prefix("0x").unwrap_or(s))
+        .map_err(|e| anyhow!("not hex: {e}"))?
+        .try_into()
+        .map_err(|_| anyhow!("expected {N} bytes"))
+}
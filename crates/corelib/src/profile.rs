@@ -1,8 +1,42 @@
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod adaptive;
+pub mod trust;
+
+pub use trust::{set_trust_anchors, trust_anchors, TrustAnchors};
+
+/// Proof system a profile's proofs are meant for.
+///
+/// `SnarkWrapped` asks the backend to wrap the STARK in a succinct SNARK
+/// (e.g. Groth16) for cheap on-chain verification; only backends reporting
+/// `recursion = "snark-wrapper"` honor it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProofTarget {
+    #[default]
+    Stark,
+    SnarkWrapped,
+}
+
+impl ProofTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProofTarget::Stark => "stark",
+            ProofTarget::SnarkWrapped => "snark-wrapped",
+        }
+    }
+}
+
+impl std::fmt::Display for ProofTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Profile {
     pub id: String,
@@ -19,13 +53,65 @@ pub struct Profile {
     pub const_col_limit: Option<u32>,
     #[serde(default)]
     pub rows_max: Option<u32>,
+    /// Hash id this profile is meant for; `zkd profile-check` flags backends
+    /// without it. The hash a run uses is `CallContext::hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Proof system target; `None` means a plain STARK (see [`Profile::target`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<ProofTarget>,
+    /// Tenant namespace; when set, `id` must be `<owner>/<name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Hex Ed25519 signature over [`Profile::signing_message`] by a key
+    /// anchored for `owner` (see [`trust`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Set by the loader when `signature` verified against the installed
+    /// trust anchors; never read from profile files.
+    #[serde(skip)]
+    pub signature_verified: bool,
 }
 
 impl Profile {
+    /// The proof system this profile targets, defaulting to a plain STARK.
+    pub fn target(&self) -> ProofTarget {
+        self.target.unwrap_or_default()
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.id.trim().is_empty() {
             return Err(anyhow!("profile id cannot be empty"));
         }
+        if let Some(owner) = &self.owner {
+            let valid = !owner.is_empty()
+                && owner
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(anyhow!(
+                    "profile owner '{}' is not a valid namespace",
+                    owner
+                ));
+            }
+            let prefixed = self
+                .id
+                .strip_prefix(owner.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+                .is_some_and(|name| !name.is_empty());
+            if !prefixed {
+                return Err(anyhow!(
+                    "profile id '{}' must be namespaced as '{}/<name>'",
+                    self.id,
+                    owner
+                ));
+            }
+        } else if self.signature.is_some() {
+            return Err(anyhow!(
+                "signed profile '{}' must declare an owner",
+                self.id
+            ));
+        }
         if !(64..=256).contains(&self.lambda_bits) {
             return Err(anyhow!(
                 "lambda_bits {} out of allowed range [64..256]",
@@ -54,6 +140,106 @@ impl Profile {
         }
         Ok(())
     }
+
+    /// Return a copy of this profile with `overrides` applied and re-validated.
+    ///
+    /// Overrides may trade FRI parameters against each other but not lower
+    /// the profile's security: the result's [`Profile::security_bits`]
+    /// (`fri_queries · log2(fri_blowup) + grind_bits`) must still reach
+    /// `lambda_bits`. Keys must already be canonical (see [`parse_override`]).
+    pub fn with_overrides(&self, overrides: &ProfileOverrides) -> Result<Profile> {
+        let mut p = self.clone();
+        for (key, value) in overrides {
+            match key.as_str() {
+                "fri_blowup" => p.fri_blowup = Some(*value),
+                "fri_queries" => p.fri_queries = Some(*value),
+                "grind_bits" => p.grind_bits = Some(*value),
+                other => return Err(anyhow!("unknown profile override '{}'", other)),
+            }
+        }
+        p.validate()
+            .with_context(|| format!("profile '{}' invalid after overrides", self.id))?;
+        if !overrides.is_empty() {
+            match p.security_bits() {
+                Some(bits) if bits >= p.lambda_bits => {}
+                Some(bits) => {
+                    return Err(anyhow!(
+                        "profile '{}' invalid after overrides: fri_queries={} fri_blowup={} \
+                         grind_bits={} give {} bits, below lambda_bits {}",
+                        self.id,
+                        p.fri_queries.unwrap_or_default(),
+                        p.fri_blowup.unwrap_or_default(),
+                        p.grind_bits.unwrap_or(0),
+                        bits,
+                        p.lambda_bits
+                    ))
+                }
+                None => {
+                    return Err(anyhow!(
+                        "profile '{}' invalid after overrides: security cannot be estimated \
+                         without fri_blowup and fri_queries",
+                        self.id
+                    ))
+                }
+            }
+        }
+        Ok(p)
+    }
+}
+
+/// Ad-hoc profile parameter overrides, keyed by canonical parameter name.
+/// BTreeMap keeps iteration (and therefore manifest serialization) stable.
+pub type ProfileOverrides = BTreeMap<String, u32>;
+
+/// Profile parameters that may be overridden per invocation.
+pub const OVERRIDABLE_KEYS: &[&str] = &["fri_blowup", "fri_queries", "grind_bits"];
+
+fn canonical_override_key(key: &str) -> Option<&'static str> {
+    match key.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+        "fri_blowup" | "blowup" => Some("fri_blowup"),
+        "fri_queries" | "queries" => Some("fri_queries"),
+        "grind_bits" => Some("grind_bits"),
+        _ => None,
+    }
+}
+
+/// Parse a single `key=value` override into its canonical key and value.
+pub fn parse_override(s: &str) -> Result<(String, u32)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("profile override '{}' must be key=value", s))?;
+    let key = canonical_override_key(key).ok_or_else(|| {
+        anyhow!(
+            "unknown profile override '{}' (expected one of {})",
+            key.trim(),
+            OVERRIDABLE_KEYS.join(", ")
+        )
+    })?;
+    let value: u32 = value
+        .trim()
+        .parse()
+        .with_context(|| format!("profile override '{}' value must be an integer", key))?;
+    Ok((key.to_string(), value))
+}
+
+/// Parse a list of `key=value` overrides; later entries win on duplicate keys.
+pub fn parse_overrides<S: AsRef<str>>(items: &[S]) -> Result<ProfileOverrides> {
+    let mut out = ProfileOverrides::new();
+    for item in items {
+        let (key, value) = parse_override(item.as_ref())?;
+        out.insert(key, value);
+    }
+    Ok(out)
+}
+
+/// Resolve a profile by id and apply `overrides`, re-validating the result.
+pub fn resolve_profile(id: &str, overrides: &ProfileOverrides) -> Result<Profile> {
+    let profiles = load_all_profiles()?;
+    let base = profiles
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| anyhow!("profile '{}' not found", id))?;
+    base.with_overrides(overrides)
 }
 
 fn profiles_dir() -> PathBuf {
@@ -63,9 +249,12 @@ fn profiles_dir() -> PathBuf {
 fn read_one(path: &Path) -> Result<Profile> {
     let s =
         fs::read_to_string(path).with_context(|| format!("reading profile {}", path.display()))?;
-    let p: Profile =
+    let mut p: Profile =
         toml::from_str(&s).with_context(|| format!("parsing profile {}", path.display()))?;
     p.validate()?;
+    p.signature_verified = trust_anchors()
+        .verify(&p)
+        .with_context(|| format!("verifying profile {}", path.display()))?;
     Ok(p)
 }
 
@@ -80,6 +269,11 @@ fn builtin_profiles() -> Vec<Profile> {
             merkle_arity: Some(2),
             const_col_limit: None,
             rows_max: None,
+            hash: None,
+            target: None,
+            owner: None,
+            signature: None,
+            signature_verified: false,
         },
         Profile {
             id: "dev-fast".to_string(),
@@ -90,6 +284,11 @@ fn builtin_profiles() -> Vec<Profile> {
             merkle_arity: Some(2),
             const_col_limit: None,
             rows_max: None,
+            hash: None,
+            target: None,
+            owner: None,
+            signature: None,
+            signature_verified: false,
         },
         Profile {
             id: "secure".to_string(),
@@ -100,6 +299,11 @@ fn builtin_profiles() -> Vec<Profile> {
             merkle_arity: Some(2),
             const_col_limit: None,
             rows_max: None,
+            hash: None,
+            target: None,
+            owner: None,
+            signature: None,
+            signature_verified: false,
         },
     ];
     profiles.sort_by(|a, b| a.id.cmp(&b.id));
@@ -108,10 +312,16 @@ fn builtin_profiles() -> Vec<Profile> {
 
 /// Load all TOML profiles from /profiles, sorted by id (stable order).
 pub fn load_all_profiles() -> Result<Vec<Profile>> {
-    let dir = profiles_dir();
+    load_profiles_from(&profiles_dir())
+}
+
+/// Like [`load_all_profiles`], from `dir`; built-in profiles are returned
+/// when it holds none. Signed profiles are verified against the installed
+/// trust anchors.
+pub fn load_profiles_from(dir: &Path) -> Result<Vec<Profile>> {
     let mut out = Vec::new();
     if dir.is_dir() {
-        for entry in fs::read_dir(&dir).with_context(|| format!("listing {}", dir.display()))? {
+        for entry in fs::read_dir(dir).with_context(|| format!("listing {}", dir.display()))? {
             let entry = entry?;
             let path = entry.path();
             if path.extension().map(|e| e == "toml").unwrap_or(false) {
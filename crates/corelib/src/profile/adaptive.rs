@@ -0,0 +1,197 @@
+//! Adaptive FRI queries: trade queries for grinding at prove time.
+//!
+//! A profile fixes `lambda_bits`. Its conjectured security is
+//! `fri_queries · log2(fri_blowup) + grind_bits` ([`security_bits`]), so the
+//! same target can be met with fewer queries and more grinding. Queries are
+//! paid for by every verifier (proof size, hashing); grinding is paid once,
+//! by the prover, at `2^grind_bits` hashes. [`solve`] therefore grinds as
+//! many bits as fit a time budget under a [`CostModel`], and takes the fewest
+//! queries that still reach `lambda_bits`. The default model is the fixed
+//! [`CostModel::REFERENCE`], so the same profile and budget always give the
+//! same choice; timing this host ([`CostModel::measure`]) is opt-in.
+//!
+//! The choice is applied as `fri_queries` / `grind_bits` profile overrides,
+//! so it is bound by the proof's `config_hash` like any other override, and
+//! recorded in the determinism manifest as an [`AdaptiveChoice`]. Verifiers
+//! [`AdaptiveChoice::check`] it against their own [`Config`]: the profile it
+//! resolves to is the one `config_hash` is checked against.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{resolve_profile, Profile};
+use crate::config::Config;
+use crate::crypto::registry::hash32_by_id;
+
+/// Fewest queries a profile may use (see [`Profile::validate`]).
+pub const MIN_QUERIES: u32 = 16;
+/// Most grinding bits [`solve`] chooses.
+pub const MAX_GRIND_BITS: u32 = 32;
+/// Grinding time budget when none is given.
+pub const DEFAULT_GRIND_BUDGET: Duration = Duration::from_millis(250);
+/// Attempts timed by [`CostModel::measure`].
+const SAMPLES: u32 = 1 << 14;
+
+/// Conjectured bits of security of FRI with these parameters.
+pub fn security_bits(fri_blowup: u32, fri_queries: u32, grind_bits: u32) -> u32 {
+    fri_queries.saturating_mul(fri_blowup.max(1).ilog2()) + grind_bits
+}
+
+impl Profile {
+    /// [`security_bits`] of this profile, when it sets blowup and queries.
+    pub fn security_bits(&self) -> Option<u32> {
+        Some(security_bits(
+            self.fri_blowup?,
+            self.fri_queries?,
+            self.grind_bits.unwrap_or(0),
+        ))
+    }
+}
+
+/// Cost of grinding, fixed or measured on this host.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostModel {
+    /// Nanoseconds per grinding attempt (one hash of a nonce-extended seed).
+    pub grind_ns: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self::REFERENCE
+    }
+}
+
+impl CostModel {
+    /// Host-independent model used unless measuring is asked for.
+    pub const REFERENCE: Self = Self { grind_ns: 1000.0 };
+
+    /// Time grinding attempts with hash `hash_id`. The result depends on the
+    /// host and its load, so choices made with it are not reproducible.
+    pub fn measure(hash_id: &str) -> Result<Self> {
+        let mut seed = [0u8; 40];
+        hash32_by_id(hash_id, "GRIND", &seed)
+            .ok_or_else(|| anyhow!("unknown hash '{}'", hash_id))?;
+        let started = Instant::now();
+        let mut acc = 0u8;
+        for nonce in 0..SAMPLES as u64 {
+            seed[32..].copy_from_slice(&nonce.to_le_bytes());
+            acc ^= hash32_by_id(hash_id, "GRIND", &seed).expect("hash checked above")[0];
+        }
+        std::hint::black_box(acc);
+        let ns = started.elapsed().as_nanos() as f64 / SAMPLES as f64;
+        Ok(Self {
+            grind_ns: ns.max(1.0),
+        })
+    }
+
+    /// Expected time to grind `bits` bits.
+    pub fn grind_time(&self, bits: u32) -> Duration {
+        Duration::from_nanos((self.grind_ns * 2f64.powi(bits as i32)).min(u64::MAX as f64) as u64)
+    }
+
+    /// Most bits whose expected grinding time fits `budget`.
+    pub fn affordable_grind_bits(&self, budget: Duration) -> u32 {
+        (1..=MAX_GRIND_BITS)
+            .take_while(|&bits| self.grind_time(bits) <= budget)
+            .last()
+            .unwrap_or(0)
+    }
+}
+
+/// Parameters [`solve`] chose, as recorded in the determinism manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdaptiveChoice {
+    pub lambda_bits: u32,
+    pub fri_blowup: u32,
+    pub fri_queries: u32,
+    pub grind_bits: u32,
+    /// [`security_bits`] of the choice, at least `lambda_bits`.
+    pub security_bits: u32,
+    /// Nanoseconds per grinding attempt of the cost model, rounded.
+    pub grind_ns: u64,
+    pub budget_ms: u64,
+}
+
+impl AdaptiveChoice {
+    /// Check the choice against `config`, the verifier's own configuration:
+    /// the profile it resolves to with its overrides (those `config_hash`
+    /// binds) must run the recorded blowup, queries and grinding, and their
+    /// security must reach that profile's `lambda_bits`.
+    pub fn check(&self, config: &Config) -> Result<()> {
+        let profile = resolve_profile(&config.profile_id, &config.profile_overrides)?;
+        let applied = (
+            profile.fri_blowup,
+            profile.fri_queries,
+            profile.grind_bits.unwrap_or(0),
+        );
+        ensure!(
+            applied
+                == (
+                    Some(self.fri_blowup),
+                    Some(self.fri_queries),
+                    self.grind_bits
+                ),
+            "profile '{}' runs fri_blowup={:?} fri_queries={:?} grind_bits={}, \
+             not the recorded fri_blowup={} fri_queries={} grind_bits={}",
+            profile.id,
+            applied.0,
+            applied.1,
+            applied.2,
+            self.fri_blowup,
+            self.fri_queries,
+            self.grind_bits
+        );
+        let bits = security_bits(self.fri_blowup, self.fri_queries, self.grind_bits);
+        ensure!(
+            bits == self.security_bits,
+            "recorded security_bits {} do not match the {} bits of fri_queries={} grind_bits={}",
+            self.security_bits,
+            bits,
+            self.fri_queries,
+            self.grind_bits
+        );
+        ensure!(
+            bits >= profile.lambda_bits,
+            "fri_queries={} grind_bits={} give {} bits, below profile '{}' lambda_bits {}",
+            self.fri_queries,
+            self.grind_bits,
+            bits,
+            profile.id,
+            profile.lambda_bits
+        );
+        Ok(())
+    }
+}
+
+/// The fewest queries reaching `profile.lambda_bits` when grinding up to
+/// `budget` on a host with cost `model`, and the least grinding that then
+/// completes the target.
+pub fn solve(profile: &Profile, model: &CostModel, budget: Duration) -> Result<AdaptiveChoice> {
+    let Some(blowup) = profile.fri_blowup else {
+        bail!(
+            "profile '{}' sets no fri_blowup to trade queries against",
+            profile.id
+        );
+    };
+    ensure!(
+        blowup >= 2 && blowup.is_power_of_two(),
+        "fri_blowup {} is not a power of two >= 2",
+        blowup
+    );
+    let lambda = profile.lambda_bits;
+    let per_query = blowup.ilog2();
+    let max_grind = model.affordable_grind_bits(budget).min(lambda);
+    let fri_queries = (lambda - max_grind).div_ceil(per_query).max(MIN_QUERIES);
+    let grind_bits = lambda.saturating_sub(fri_queries * per_query);
+    Ok(AdaptiveChoice {
+        lambda_bits: lambda,
+        fri_blowup: blowup,
+        fri_queries,
+        grind_bits,
+        security_bits: security_bits(blowup, fri_queries, grind_bits),
+        grind_ns: model.grind_ns.round() as u64,
+        budget_ms: budget.as_millis() as u64,
+    })
+}
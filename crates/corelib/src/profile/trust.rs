@@ -0,0 +1,179 @@
+//! Profile namespaces and signatures for multi-tenant deployments.
+//!
+//! A profile may name an `owner`, in which case its id must be
+//! `<owner>/<name>`, and may carry an Ed25519 `signature` over
+//! [`Profile::signing_message`]. Trust anchors map each owner to the public
+//! keys allowed to sign for it:
+//!
+//! ```toml
+//! require_signed = true
+//!
+//! [[anchor]]
+//! owner = "acme"
+//! public_key = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"
+//! ```
+//!
+//! The profile loader verifies every signed profile against the installed
+//! anchors (see [`set_trust_anchors`]) and rejects forgeries; with
+//! `require_signed`, `validate_config` also refuses unsigned profiles.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use anyhow::{anyhow, bail, Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Deserialize;
+
+use super::Profile;
+
+const SIGNING_DOMAIN: &[u8] = b"zkprov/profile-sig/v1\0";
+
+static TRUST: RwLock<TrustAnchors> = RwLock::new(TrustAnchors::new());
+
+/// Owners and the keys allowed to sign their profiles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustAnchors {
+    anchors: BTreeMap<String, Vec<VerifyingKey>>,
+    /// Only accept profiles whose signature verified at load time.
+    pub require_signed: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnchorFile {
+    #[serde(default)]
+    require_signed: bool,
+    #[serde(default)]
+    anchor: Vec<AnchorEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnchorEntry {
+    owner: String,
+    public_key: String,
+}
+
+impl TrustAnchors {
+    pub const fn new() -> Self {
+        Self {
+            anchors: BTreeMap::new(),
+            require_signed: false,
+        }
+    }
+
+    /// Parse the TOML anchor file format shown in the module docs.
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        let file: AnchorFile = toml::from_str(s).context("parsing trust anchors")?;
+        let mut out = Self {
+            require_signed: file.require_signed,
+            ..Self::new()
+        };
+        for entry in file.anchor {
+            out.add(&entry.owner, &entry.public_key)?;
+        }
+        Ok(out)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("reading trust anchors {}", path.display()))?;
+        Self::from_toml_str(&s).with_context(|| format!("in {}", path.display()))
+    }
+
+    /// Trust `public_key_hex` (32-byte Ed25519 key) to sign for `owner`.
+    pub fn add(&mut self, owner: &str, public_key_hex: &str) -> Result<()> {
+        let bytes: [u8; 32] = alloy_primitives::hex::decode(public_key_hex.trim())
+            .map_err(|e| anyhow!("public key for '{}' is not hex: {}", owner, e))?
+            .try_into()
+            .map_err(|_| anyhow!("public key for '{}' must be 32 bytes", owner))?;
+        let key = VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| anyhow!("invalid public key for '{}': {}", owner, e))?;
+        self.anchors.entry(owner.to_string()).or_default().push(key);
+        Ok(())
+    }
+
+    /// Whether `profile`'s signature verifies: `Ok(false)` when unsigned, an
+    /// error when signed by a key not anchored for its owner.
+    pub fn verify(&self, profile: &Profile) -> Result<bool> {
+        let Some(sig_hex) = &profile.signature else {
+            return Ok(false);
+        };
+        let owner = profile
+            .owner
+            .as_deref()
+            .ok_or_else(|| anyhow!("profile '{}' is signed but has no owner", profile.id))?;
+        let bytes: [u8; 64] = alloy_primitives::hex::decode(sig_hex.trim())
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| anyhow!("profile '{}' signature must be 64 bytes of hex", profile.id))?;
+        let sig = Signature::from_bytes(&bytes);
+        let keys = self
+            .anchors
+            .get(owner)
+            .ok_or_else(|| anyhow!("no trust anchor for owner '{}'", owner))?;
+        let msg = profile.signing_message();
+        if keys.iter().any(|k| k.verify(&msg, &sig).is_ok()) {
+            Ok(true)
+        } else {
+            bail!(
+                "profile '{}' signature does not verify against owner '{}'",
+                profile.id,
+                owner
+            )
+        }
+    }
+}
+
+/// Install the anchors (and signing policy) used by the profile loader and
+/// `validate_config`.
+pub fn set_trust_anchors(anchors: TrustAnchors) {
+    *TRUST.write().unwrap() = anchors;
+}
+
+/// The currently installed trust anchors.
+pub fn trust_anchors() -> TrustAnchors {
+    TRUST.read().unwrap().clone()
+}
+
+/// Hex Ed25519 signature over `profile`'s signing message with the 32-byte
+/// secret key `secret`, for the `signature` field.
+pub fn sign_profile(profile: &Profile, secret: &[u8; 32]) -> String {
+    let key = SigningKey::from_bytes(secret);
+    alloy_primitives::hex::encode(key.sign(&profile.signing_message()).to_bytes())
+}
+
+/// Hex public key matching the secret key given to [`sign_profile`], for
+/// [`TrustAnchors::add`].
+pub fn public_key_hex(secret: &[u8; 32]) -> String {
+    alloy_primitives::hex::encode(SigningKey::from_bytes(secret).verifying_key().to_bytes())
+}
+
+impl Profile {
+    /// Bytes covered by `signature`: a domain tag followed by the canonical
+    /// JSON of every parameter read from profile files (keys sorted).
+    /// `target` is only covered when set, so signatures made before it
+    /// existed stay valid.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut canonical = serde_json::json!({
+            "id": self.id,
+            "owner": self.owner,
+            "lambda_bits": self.lambda_bits,
+            "fri_blowup": self.fri_blowup,
+            "fri_queries": self.fri_queries,
+            "grind_bits": self.grind_bits,
+            "merkle_arity": self.merkle_arity,
+            "const_col_limit": self.const_col_limit,
+            "rows_max": self.rows_max,
+            "hash": self.hash,
+        });
+        if let Some(target) = self.target {
+            canonical["target"] = target.as_str().into();
+        }
+        let mut out = SIGNING_DOMAIN.to_vec();
+        out.extend_from_slice(canonical.to_string().as_bytes());
+        out
+    }
+}
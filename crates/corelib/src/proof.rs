@@ -10,61 +10,206 @@ use serde::{Deserialize, Serialize};
 pub const HEADER_HASH_ID: &str = "blake3";
 
 use crate::crypto::registry;
+use crate::errors::{HeaderField, VerifyError};
+
+pub mod canonical;
+pub mod container;
+pub mod pubio;
+pub mod segment;
+pub mod triage;
+
+use segment::Segment;
 
 /// Magic/version
 pub const MAGIC: [u8; 4] = *b"PROF";
-pub const VERSION: u32 = 1;
+/// v3 made the header extensible (explicit header length, byte-order
+/// marker, flags). v2 added `config_hash` and still decodes; v1 headers bound
+/// only the backend and profile ids, so a v1 proof verified under any field,
+/// hash or FRI arity and is rejected.
+pub const VERSION: u32 = 3;
+/// Encoded size of a v3 header; the body of every proof zkd writes starts at
+/// this offset.
+pub const HEADER_LEN: usize = 64;
+/// Encoded size of a v2 header.
+pub const V2_HEADER_LEN: usize = 48;
+/// `byte_order` marker for little-endian fields, the only order defined.
+pub const BYTE_ORDER_LE: u8 = 1;
+/// Flag bits 0..16 are critical: a decoder rejects a header that sets a
+/// critical bit it does not know. Bits 16..32 are non-critical, like X.509
+/// extension criticality, but nothing binds the flags word yet, so an
+/// unassigned non-critical bit is rejected too until one is defined.
+pub const CRITICAL_FLAGS: u32 = 0x0000_ffff;
+/// Flag bits this version interprets; none are assigned yet.
+pub const KNOWN_FLAGS: u32 = 0;
 
-/// Fixed-size header (little endian).
+/// Extensible header (little endian).
 /// Layout (bytes):
 /// 0..4   MAGIC "PROF"
 /// 4..8   VERSION (u32)
-/// 8..16  backend_id_hash (u64)
-///16..24  profile_id_hash (u64)
-///24..32  pubio_hash (u64)     -- hash of canonical public inputs JSON
-///32..40  body_len (u64)
+/// 8..10  header_len (u16)      -- offset of the body, >= HEADER_LEN
+///10      byte_order (u8)       -- BYTE_ORDER_LE
+///11      reserved (u8)         -- zero on write, ignored on read
+///12..16  flags (u32)           -- see CRITICAL_FLAGS
+///16..24  backend_id_hash (u64)
+///24..32  profile_id_hash (u64)
+///32..40  pubio_hash (u64)     -- `pubio::pubio_hash` of the bound public inputs
+///40..48  body_len (u64)
+///48..56  config_hash (u64)    -- `Config::config_hash`, every proving parameter
+///56..64  reserved             -- zero on write, ignored on read
+///64..header_len extensions    -- reserved for fields appended by newer
+///                                 writers; none are defined and a decoder
+///                                 rejects a header that carries any
+///
+/// A v2 header is the 48-byte `MAGIC, VERSION, backend_id_hash,
+/// profile_id_hash, pubio_hash, body_len, config_hash` prefix; it decodes
+/// with no flags.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProofHeader {
     pub backend_id_hash: u64,
     pub profile_id_hash: u64,
     pub pubio_hash: u64,
     pub body_len: u64,
+    pub config_hash: u64,
+    /// Header flag bits; see [`CRITICAL_FLAGS`].
+    #[serde(default)]
+    pub flags: u32,
 }
 
 impl ProofHeader {
-    pub fn encode(&self) -> [u8; 40] {
-        let mut out = [0u8; 40];
+    /// Encoded size, i.e. the offset of the body.
+    pub fn encoded_len(&self) -> usize {
+        HEADER_LEN
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let header_len = self.encoded_len() as u16;
+        let mut out = vec![0u8; HEADER_LEN];
         out[0..4].copy_from_slice(&MAGIC);
         out[4..8].copy_from_slice(&VERSION.to_le_bytes());
-        out[8..16].copy_from_slice(&self.backend_id_hash.to_le_bytes());
-        out[16..24].copy_from_slice(&self.profile_id_hash.to_le_bytes());
-        out[24..32].copy_from_slice(&self.pubio_hash.to_le_bytes());
-        out[32..40].copy_from_slice(&self.body_len.to_le_bytes());
+        out[8..10].copy_from_slice(&header_len.to_le_bytes());
+        out[10] = BYTE_ORDER_LE;
+        out[12..16].copy_from_slice(&self.flags.to_le_bytes());
+        out[16..24].copy_from_slice(&self.backend_id_hash.to_le_bytes());
+        out[24..32].copy_from_slice(&self.profile_id_hash.to_le_bytes());
+        out[32..40].copy_from_slice(&self.pubio_hash.to_le_bytes());
+        out[40..48].copy_from_slice(&self.body_len.to_le_bytes());
+        out[48..56].copy_from_slice(&self.config_hash.to_le_bytes());
         out
     }
 
+    /// Value of the bound field `field`.
+    pub fn binding(&self, field: HeaderField) -> u64 {
+        match field {
+            HeaderField::BackendId => self.backend_id_hash,
+            HeaderField::ProfileId => self.profile_id_hash,
+            HeaderField::PubIo => self.pubio_hash,
+            HeaderField::Config => self.config_hash,
+        }
+    }
+
+    /// Bound fields of this header that differ from `expected`, the header
+    /// a verifier derives from its own config and inputs, as
+    /// `(field, expected, actual)` in header order.
+    pub fn binding_mismatches(&self, expected: &ProofHeader) -> Vec<(HeaderField, u64, u64)> {
+        HeaderField::ALL
+            .into_iter()
+            .map(|field| (field, expected.binding(field), self.binding(field)))
+            .filter(|(_, expected, actual)| expected != actual)
+            .collect()
+    }
+
+    /// Check every bound field against `expected`; the first mismatch is a
+    /// [`VerifyError::HeaderBinding`]. Backends call this before looking at
+    /// the body.
+    pub fn check_bindings(&self, expected: &ProofHeader) -> Result<(), VerifyError> {
+        match self.binding_mismatches(expected).into_iter().next() {
+            Some((field, expected, actual)) => Err(VerifyError::HeaderBinding {
+                field,
+                expected,
+                actual,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Decode the header at the start of `bytes` (which may continue with
+    /// the body).
     pub fn decode(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 40 {
+        Self::decode_prefix(bytes).map(|(header, _)| header)
+    }
+
+    /// Decode the header at the start of `bytes` and return it with its
+    /// encoded length, the offset of the body.
+    pub fn decode_prefix(bytes: &[u8]) -> Result<(Self, usize)> {
+        if bytes.len() < 8 {
             bail!("proof too short for header");
         }
         if bytes[0..4] != MAGIC {
             bail!("bad magic");
         }
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
         let ver = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if ver == 1 {
+            bail!("proof version 1 does not bind its config; re-prove to get a version {VERSION} proof");
+        }
+        if ver == 2 {
+            if bytes.len() < V2_HEADER_LEN {
+                bail!("proof too short for header");
+            }
+            let header = ProofHeader {
+                backend_id_hash: u64_at(8),
+                profile_id_hash: u64_at(16),
+                pubio_hash: u64_at(24),
+                body_len: u64_at(32),
+                config_hash: u64_at(40),
+                flags: 0,
+            };
+            return Ok((header, V2_HEADER_LEN));
+        }
         if ver != VERSION {
             bail!("unsupported proof version {ver}");
         }
-        let backend_id_hash = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
-        let profile_id_hash = u64::from_le_bytes(bytes[16..24].try_into().unwrap());
-        let pubio_hash = u64::from_le_bytes(bytes[24..32].try_into().unwrap());
-        let body_len = u64::from_le_bytes(bytes[32..40].try_into().unwrap());
-
-        Ok(ProofHeader {
-            backend_id_hash,
-            profile_id_hash,
-            pubio_hash,
-            body_len,
-        })
+        if bytes.len() < HEADER_LEN {
+            bail!("proof too short for header");
+        }
+        let header_len = u16::from_le_bytes(bytes[8..10].try_into().unwrap()) as usize;
+        if header_len < HEADER_LEN {
+            bail!("header length {header_len} is below the {HEADER_LEN} fixed bytes");
+        }
+        if bytes.len() < header_len {
+            bail!("proof too short for its {header_len}-byte header");
+        }
+        // Neither the backends nor `D` bind bytes past the fixed fields, so
+        // they are refused until an extension is defined and bound.
+        if header_len > HEADER_LEN {
+            bail!(
+                "header carries {} bytes of extensions; none are defined for version {VERSION}",
+                header_len - HEADER_LEN
+            );
+        }
+        if bytes[10] != BYTE_ORDER_LE {
+            bail!("unsupported header byte order {}", bytes[10]);
+        }
+        let flags = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+        let unknown = flags & !KNOWN_FLAGS;
+        if unknown & CRITICAL_FLAGS != 0 {
+            bail!(
+                "header sets critical flags {:#06x} this verifier does not understand",
+                unknown & CRITICAL_FLAGS
+            );
+        }
+        if unknown != 0 {
+            bail!("header sets unassigned non-critical flags {unknown:#010x}, which are not bound");
+        }
+        let header = ProofHeader {
+            backend_id_hash: u64_at(16),
+            profile_id_hash: u64_at(24),
+            pubio_hash: u64_at(32),
+            body_len: u64_at(40),
+            config_hash: u64_at(48),
+            flags,
+        };
+        Ok((header, header_len))
     }
 }
 
@@ -74,10 +219,110 @@ pub fn hash64(label: &str, data: &[u8]) -> u64 {
     registry::hash64_by_id(HEADER_HASH_ID, label, data).expect("HEADER_HASH_ID must be supported")
 }
 
-/// Encode full proof: header(40) + body
+/// Encode full proof: header + body
 pub fn assemble_proof(header: &ProofHeader, body: &[u8]) -> Vec<u8> {
-    let mut v = Vec::with_capacity(40 + body.len());
+    let mut v = Vec::with_capacity(header.encoded_len() + body.len());
     v.extend_from_slice(&header.encode());
     v.extend_from_slice(body);
     v
 }
+
+/// Split encoded proof bytes into the header and the body that follows it,
+/// without checking the body against `body_len` (see [`ProofView::from_bytes`]).
+pub fn split_proof(bytes: &[u8]) -> Result<(ProofHeader, &[u8])> {
+    let (header, header_len) = ProofHeader::decode_prefix(bytes)?;
+    Ok((header, &bytes[header_len..]))
+}
+
+/// Decoded proof: header plus backend-specific body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    pub header: ProofHeader,
+    pub body: Vec<u8>,
+}
+
+impl Proof {
+    /// Build a proof from a body, filling in `header.body_len`.
+    pub fn new(mut header: ProofHeader, body: Vec<u8>) -> Self {
+        header.body_len = body.len() as u64;
+        Self { header, body }
+    }
+
+    /// Decode `header + body`, checking the body length against the header.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        ProofView::from_bytes(bytes).map(ProofView::into_proof)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        assemble_proof(&self.header, &self.body)
+    }
+
+    /// The backend-native proof; see [`ProofView::native_proof`].
+    pub fn native_proof(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Borrow this proof as a [`ProofView`].
+    pub fn view(&self) -> ProofView<'_> {
+        ProofView {
+            header: self.header.clone(),
+            body: &self.body,
+        }
+    }
+}
+
+/// A decoded proof whose body borrows the caller's buffer.
+///
+/// Verification ([`crate::verify`], [`crate::backend::VerifierBackend`]) works
+/// on views, so a proof in a memory-mapped file or a foreign (FFI) buffer is
+/// never copied, whatever its size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofView<'a> {
+    pub header: ProofHeader,
+    pub body: &'a [u8],
+}
+
+impl<'a> ProofView<'a> {
+    /// Decode `header + body` without copying the body, checking its
+    /// length against the header.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, VerifyError> {
+        let (header, body) =
+            split_proof(bytes).map_err(|e| VerifyError::Malformed(e.to_string()))?;
+        if body.len() as u64 != header.body_len {
+            return Err(VerifyError::BodyLength {
+                expected: header.body_len,
+                actual: body.len() as u64,
+            });
+        }
+        Ok(Self { header, body })
+    }
+
+    /// The proof exactly as the backend's own verifier consumes it, without
+    /// the zkd framing (the header binding backend, profile, public inputs
+    /// and config). Backends store their native serialization as the body
+    /// unchanged, e.g. `StarkProof::to_bytes` for `ministark@0.1`; which
+    /// backend produced it is bound by `header.backend_id_hash`.
+    pub fn native_proof(&self) -> &'a [u8] {
+        self.body
+    }
+
+    /// Typed body segments, for backends whose body uses the
+    /// [`segment`] framing; each segment's checksum is verified.
+    pub fn segments(&self) -> Result<Vec<Segment<'a>>, VerifyError> {
+        segment::parse_segments(self.body)
+    }
+
+    /// Copy the body into an owned [`Proof`].
+    pub fn into_proof(self) -> Proof {
+        Proof {
+            header: self.header,
+            body: self.body.to_vec(),
+        }
+    }
+}
+
+impl<'a> From<&'a Proof> for ProofView<'a> {
+    fn from(proof: &'a Proof) -> Self {
+        proof.view()
+    }
+}
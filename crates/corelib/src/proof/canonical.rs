@@ -0,0 +1,74 @@
+//! Canonical JSON text of public inputs.
+//!
+//! An AIR without `[[public_inputs]]` has nothing to bind inputs against, so
+//! the header's `pubio_hash` covers the whole JSON document. It hashes the
+//! canonical form below rather than the text as written, so inputs that mean
+//! the same produce identical proofs whatever their layout:
+//!
+//! * no whitespace outside strings;
+//! * object keys sorted by their UTF-8 bytes (on duplicate keys the last one
+//!   wins, as when parsing);
+//! * strings as `serde_json` writes them: only `"`, `\` and control
+//!   characters escaped, everything else (including non-ASCII) literal;
+//! * integers that fit `i64`/`u64` in plain decimal. Any other number is an
+//!   IEEE double, as in RFC 8785: integral values below 2^53 in plain decimal
+//!   (`1.0` and `1e2` become `1` and `100`, `-0` becomes `0`), the rest in
+//!   the shortest form that reads back as the same double (`0.5`, `1e+22`).
+//!   Values that differ beyond double precision must be written as strings
+//!   to be told apart.
+
+use serde_json::{Number, Value};
+
+/// Integral doubles below this magnitude are exact and written as integers.
+const EXACT_INTEGER_LIMIT: f64 = 9_007_199_254_740_992.0; // 2^53
+
+/// Canonical text of `value`.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null | Value::Bool(_) | Value::String(_) => out.push_str(&value.to_string()),
+        Value::Number(n) => write_number(out, n),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, n: &Number) {
+    if n.is_u64() || n.is_i64() {
+        out.push_str(&n.to_string());
+        return;
+    }
+    let f = n.as_f64().expect("non-integer JSON numbers are f64");
+    if f.fract() == 0.0 && f.abs() < EXACT_INTEGER_LIMIT {
+        out.push_str(&(f as i64).to_string());
+    } else {
+        out.push_str(&n.to_string());
+    }
+}
@@ -0,0 +1,294 @@
+//! Proof containers: one blob carrying a proof together with what is
+//! otherwise written beside it (the determinism manifest) or recomputed by
+//! the verifier (the full public IO digest).
+//!
+//! Layout (little endian):
+//! ```text
+//! 0..4    magic "ZKPC"
+//! 4..8    version (u32, CONTAINER_VERSION)
+//! 8..10   section count (u16)
+//! then, per section:
+//!   0..2   tag (u16)
+//!   2..6   value length (u32)
+//!   6..    value
+//! ```
+//! Sections:
+//!
+//! | tag | value                                              | required |
+//! | --- | -------------------------------------------------- | -------- |
+//! | 1   | encoded [`ProofHeader`]                            | yes      |
+//! | 2   | backend payload, i.e. the proof body               | yes      |
+//! | 3   | [`DeterminismManifest`] JSON                       | no       |
+//! | 4   | 32-byte [`pubio_digest`](super::pubio::pubio_digest) | no     |
+//! | 5   | public inputs, [canonical JSON](super::canonical)    | no       |
+//!
+//! Each known tag appears at most once. Tags from [`OPTIONAL_TAGS`] up are
+//! optional: decoders that do not know them skip them (and keep them, so a
+//! container re-encodes unchanged). An unknown tag below that is rejected,
+//! like a critical header flag.
+//!
+//! Embedded inputs make the proof self-contained: a verifier without an
+//! inputs file reads them from the container and recomputes `pubio_hash`
+//! from them, so a copy that does not match the proof fails verification.
+//!
+//! Version 1 is the bare `header || body` proof zkd has always written;
+//! [`ProofContainer::decode`] reads it as a container with only the header
+//! and payload, so callers can accept both.
+
+use std::convert::TryInto;
+
+use crate::errors::VerifyError;
+use crate::evm::digest::digest_D;
+use crate::manifest::DeterminismManifest;
+
+use super::canonical::canonical_json;
+use super::{assemble_proof, split_proof, ProofHeader, MAGIC};
+
+pub const CONTAINER_MAGIC: [u8; 4] = *b"ZKPC";
+/// Version written by [`ProofContainer::encode`]; 1 is a bare proof.
+pub const CONTAINER_VERSION: u32 = 2;
+pub const TAG_HEADER: u16 = 1;
+pub const TAG_PAYLOAD: u16 = 2;
+pub const TAG_MANIFEST: u16 = 3;
+pub const TAG_PUBIO_DIGEST: u16 = 4;
+pub const TAG_INPUTS: u16 = 5;
+/// First tag a decoder may skip when it does not know it.
+pub const OPTIONAL_TAGS: u16 = 0x8000;
+const PREFIX_LEN: usize = 10;
+const SECTION_LEN: usize = 6;
+
+/// A decoded container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofContainer {
+    pub header: ProofHeader,
+    /// Backend payload (the proof body), `header.body_len` bytes.
+    pub payload: Vec<u8>,
+    pub manifest: Option<DeterminismManifest>,
+    /// Full digest of the bound public IO; its first 8 bytes are the
+    /// header's `pubio_hash`.
+    pub pubio_digest: Option<[u8; 32]>,
+    /// Embedded public inputs, as canonical JSON text.
+    pub inputs: Option<String>,
+    /// Optional sections this version does not interpret, in order.
+    pub extra: Vec<(u16, Vec<u8>)>,
+}
+
+impl ProofContainer {
+    /// A container for the bare proof `bytes`, with no optional sections.
+    pub fn from_proof(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let (header, body) =
+            split_proof(bytes).map_err(|e| VerifyError::Malformed(e.to_string()))?;
+        let container = Self {
+            header,
+            payload: body.to_vec(),
+            manifest: None,
+            pubio_digest: None,
+            inputs: None,
+            extra: Vec::new(),
+        };
+        container.check()?;
+        Ok(container)
+    }
+
+    pub fn with_manifest(mut self, manifest: DeterminismManifest) -> Self {
+        self.manifest = Some(manifest);
+        self
+    }
+
+    pub fn with_pubio_digest(mut self, digest: [u8; 32]) -> Self {
+        self.pubio_digest = Some(digest);
+        self
+    }
+
+    /// Embed `inputs` in canonical form.
+    pub fn with_inputs(mut self, inputs: &serde_json::Value) -> Self {
+        self.inputs = Some(canonical_json(inputs));
+        self
+    }
+
+    /// The bare (version 1) proof: header followed by the payload.
+    pub fn to_proof(&self) -> Vec<u8> {
+        assemble_proof(&self.header, &self.payload)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut sections: Vec<(u16, Vec<u8>)> = vec![
+            (TAG_HEADER, self.header.encode()),
+            (TAG_PAYLOAD, self.payload.clone()),
+        ];
+        if let Some(manifest) = &self.manifest {
+            let json = serde_json::to_vec(manifest).expect("manifest serializes");
+            sections.push((TAG_MANIFEST, json));
+        }
+        if let Some(digest) = &self.pubio_digest {
+            sections.push((TAG_PUBIO_DIGEST, digest.to_vec()));
+        }
+        if let Some(inputs) = &self.inputs {
+            sections.push((TAG_INPUTS, inputs.as_bytes().to_vec()));
+        }
+        sections.extend(self.extra.iter().cloned());
+
+        let count = u16::try_from(sections.len()).expect("container section count fits u16");
+        let mut out = Vec::with_capacity(
+            PREFIX_LEN
+                + sections
+                    .iter()
+                    .map(|(_, v)| SECTION_LEN + v.len())
+                    .sum::<usize>(),
+        );
+        out.extend_from_slice(&CONTAINER_MAGIC);
+        out.extend_from_slice(&CONTAINER_VERSION.to_le_bytes());
+        out.extend_from_slice(&count.to_le_bytes());
+        for (tag, value) in sections {
+            let len = u32::try_from(value.len()).expect("container section exceeds 4 GiB");
+            out.extend_from_slice(&tag.to_le_bytes());
+            out.extend_from_slice(&len.to_le_bytes());
+            out.extend_from_slice(&value);
+        }
+        out
+    }
+
+    /// Decode a container, or a bare proof as a version 1 container.
+    pub fn decode(bytes: &[u8]) -> Result<Self, VerifyError> {
+        if bytes.starts_with(&MAGIC) {
+            return Self::from_proof(bytes);
+        }
+        let malformed = |msg: String| VerifyError::Malformed(format!("proof container: {}", msg));
+        if bytes.len() < PREFIX_LEN || bytes[0..4] != CONTAINER_MAGIC {
+            return Err(malformed("bad magic".into()));
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != CONTAINER_VERSION {
+            return Err(malformed(format!(
+                "unsupported version {} (expected {})",
+                version, CONTAINER_VERSION
+            )));
+        }
+        let count = u16::from_le_bytes(bytes[8..10].try_into().unwrap());
+
+        let mut header = None;
+        let mut payload = None;
+        let mut manifest = None;
+        let mut pubio_digest = None;
+        let mut inputs = None;
+        let mut extra = Vec::new();
+        let mut at = PREFIX_LEN;
+        for index in 0..count {
+            let Some(prefix) = bytes.get(at..at + SECTION_LEN) else {
+                return Err(malformed(format!("section {} truncated", index)));
+            };
+            let tag = u16::from_le_bytes(prefix[0..2].try_into().unwrap());
+            let len = u32::from_le_bytes(prefix[2..6].try_into().unwrap()) as usize;
+            at += SECTION_LEN;
+            let Some(value) = bytes.get(at..at.saturating_add(len)) else {
+                return Err(malformed(format!(
+                    "section {} (tag {}) truncated",
+                    index, tag
+                )));
+            };
+            at += len;
+            let duplicate = match tag {
+                TAG_HEADER => header
+                    .replace(
+                        ProofHeader::decode(value)
+                            .map_err(|e| malformed(format!("header: {}", e)))?,
+                    )
+                    .is_some(),
+                TAG_PAYLOAD => payload.replace(value.to_vec()).is_some(),
+                TAG_MANIFEST => manifest
+                    .replace(
+                        serde_json::from_slice::<DeterminismManifest>(value)
+                            .map_err(|e| malformed(format!("manifest: {}", e)))?,
+                    )
+                    .is_some(),
+                TAG_PUBIO_DIGEST => {
+                    let digest: [u8; 32] = value.try_into().map_err(|_| {
+                        malformed(format!("pubio digest is {} bytes, expected 32", len))
+                    })?;
+                    pubio_digest.replace(digest).is_some()
+                }
+                TAG_INPUTS => {
+                    let text = std::str::from_utf8(value)
+                        .map_err(|e| malformed(format!("inputs: {}", e)))?;
+                    inputs.replace(text.to_owned()).is_some()
+                }
+                tag if tag >= OPTIONAL_TAGS => {
+                    extra.push((tag, value.to_vec()));
+                    false
+                }
+                tag => return Err(malformed(format!("unknown required section tag {}", tag))),
+            };
+            if duplicate {
+                return Err(malformed(format!("section tag {} repeated", tag)));
+            }
+        }
+        if at != bytes.len() {
+            return Err(malformed(format!(
+                "{} trailing bytes after the last section",
+                bytes.len() - at
+            )));
+        }
+        let container = Self {
+            header: header.ok_or_else(|| malformed("no header section".into()))?,
+            payload: payload.ok_or_else(|| malformed("no payload section".into()))?,
+            manifest,
+            pubio_digest,
+            inputs,
+            extra,
+        };
+        container.check()?;
+        Ok(container)
+    }
+
+    /// Consistency of the sections with the header: the payload length, the
+    /// manifest's digest and seal, and the pubio digest's truncation. Embedded
+    /// inputs must be canonical JSON; whether they are the proof's inputs is
+    /// for verification to show.
+    pub fn check(&self) -> Result<(), VerifyError> {
+        if self.header.body_len != self.payload.len() as u64 {
+            return Err(VerifyError::BodyLength {
+                expected: self.header.body_len,
+                actual: self.payload.len() as u64,
+            });
+        }
+        if let Some(manifest) = &self.manifest {
+            let digest = digest_D(&self.header, &self.payload);
+            let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+            if manifest.digest.trim_start_matches("0x") != hex {
+                return Err(VerifyError::Malformed(format!(
+                    "proof container: manifest digest {} is not the proof's 0x{}",
+                    manifest.digest, hex
+                )));
+            }
+            manifest
+                .determinism_vector
+                .verify()
+                .map_err(|e| VerifyError::Malformed(format!("proof container: {}", e)))?;
+        }
+        if let Some(digest) = &self.pubio_digest {
+            let truncated = u64::from_le_bytes(digest[..8].try_into().unwrap());
+            if truncated != self.header.pubio_hash {
+                return Err(VerifyError::Malformed(format!(
+                    "proof container: pubio digest does not match header pubio_hash 0x{:016x}",
+                    self.header.pubio_hash
+                )));
+            }
+        }
+        if let Some(inputs) = &self.inputs {
+            let value: serde_json::Value = serde_json::from_str(inputs).map_err(|e| {
+                VerifyError::Malformed(format!("proof container: inputs are not JSON: {}", e))
+            })?;
+            if canonical_json(&value) != *inputs {
+                return Err(VerifyError::Malformed(
+                    "proof container: inputs are not in canonical form".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `bytes` start like a container (as opposed to a bare proof).
+pub fn is_container(bytes: &[u8]) -> bool {
+    bytes.starts_with(&CONTAINER_MAGIC)
+}
@@ -0,0 +1,135 @@
+//! Structured `pubio_hash` binding for JSON public inputs.
+//!
+//! When an AIR declares `[[public_inputs]]`, the header binds each declared
+//! input rather than the raw JSON text, so key order, formatting and keys the
+//! AIR does not declare leave the digest unchanged. Each input is hashed in
+//! its own domain, then the digests are combined in declaration order:
+//!
+//! ```text
+//! input_i = BLAKE3("ZKD.PUBIO.INPUT" || len(name) || name || len(ty) || ty
+//!                  || present (u8) || len(value) || value)
+//! binding = "ZKD.PUBIO.STRUCT" || count (u32) || input_0 || input_1 || ...
+//! pubio_hash = hash64("PUBIO", binding)
+//! ```
+//!
+//! Lengths are u32 little endian, `ty` is the declared type as written in the
+//! AIR (`u64`, `fixed(2)`, ...) and `value` is the canonical
+//! [`PublicValue::to_le_bytes`] encoding. An absent input is bound as
+//! `present = 0` with an empty value.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::air::public::PublicValue;
+use crate::air::types::PublicInput;
+use crate::backend::PublicInputs;
+use crate::io::TypedInputs;
+
+use super::{hash64, HEADER_HASH_ID};
+
+const STRUCT_TAG: &[u8] = b"ZKD.PUBIO.STRUCT";
+const INPUT_TAG: &[u8] = b"ZKD.PUBIO.INPUT";
+
+/// What to do with JSON keys that are not declared public inputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UndeclaredInputs {
+    /// Leave them out of the binding.
+    #[default]
+    Ignore,
+    /// Refuse the inputs.
+    Reject,
+}
+
+impl UndeclaredInputs {
+    pub fn is_ignore(&self) -> bool {
+        *self == Self::Ignore
+    }
+}
+
+impl std::str::FromStr for UndeclaredInputs {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ignore" => Ok(Self::Ignore),
+            "reject" => Ok(Self::Reject),
+            other => Err(anyhow!(
+                "unknown undeclared-inputs policy '{}' (expected ignore or reject)",
+                other
+            )),
+        }
+    }
+}
+
+/// Header `pubio_hash` of `inputs`, over whatever they are bound to.
+pub fn pubio_hash(inputs: &PublicInputs) -> u64 {
+    hash64("PUBIO", inputs.binding())
+}
+
+/// Full 32-byte digest [`pubio_hash`] truncates to its first 8 bytes (LE).
+pub fn pubio_digest(inputs: &PublicInputs) -> [u8; 32] {
+    crate::crypto::registry::hash32_by_id(HEADER_HASH_ID, "PUBIO", inputs.binding())
+        .expect("HEADER_HASH_ID must be supported")
+}
+
+/// Keys of `inputs` that `decls` does not declare, in document order.
+pub fn undeclared_keys<'a>(decls: &[PublicInput], inputs: &'a Value) -> Vec<&'a str> {
+    inputs
+        .as_object()
+        .map(|obj| {
+            obj.keys()
+                .filter(|k| !decls.iter().any(|pi| &pi.name == *k))
+                .map(String::as_str)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Digest of one declared input; `raw` is its JSON value, if present.
+pub fn input_digest(decl: &PublicInput, raw: Option<&Value>) -> Result<[u8; 32]> {
+    let value = raw
+        .map(|raw| {
+            PublicValue::parse(decl.ty, raw)
+                .map_err(|e| anyhow!("public input '{}' ({}): {}", decl.name, decl.ty, e))
+        })
+        .transpose()?;
+    Ok(typed_input_digest(decl, value.as_ref()))
+}
+
+/// Digest of one declared input from its parsed value, if present.
+pub fn typed_input_digest(decl: &PublicInput, value: Option<&PublicValue>) -> [u8; 32] {
+    let ty = decl.ty.to_string();
+    let mut h = blake3::Hasher::new();
+    h.update(INPUT_TAG);
+    h.update(&(decl.name.len() as u32).to_le_bytes());
+    h.update(decl.name.as_bytes());
+    h.update(&(ty.len() as u32).to_le_bytes());
+    h.update(ty.as_bytes());
+    h.update(&[value.is_some() as u8]);
+    let value = value.map(PublicValue::to_le_bytes).unwrap_or_default();
+    h.update(&(value.len() as u32).to_le_bytes());
+    h.update(&value);
+    *h.finalize().as_bytes()
+}
+
+/// Binding bytes for `inputs` under the declarations `decls`.
+pub fn structured_binding(
+    decls: &[PublicInput],
+    inputs: &Value,
+    undeclared: UndeclaredInputs,
+) -> Result<Vec<u8>> {
+    Ok(TypedInputs::parse(decls, inputs, undeclared)?.binding())
+}
+
+/// Binding bytes for already parsed inputs (see [`TypedInputs::binding`]).
+pub fn typed_binding(inputs: &TypedInputs) -> Vec<u8> {
+    let mut out = Vec::with_capacity(STRUCT_TAG.len() + 4 + 32 * inputs.len());
+    out.extend_from_slice(STRUCT_TAG);
+    out.extend_from_slice(&(inputs.len() as u32).to_le_bytes());
+    for input in inputs.iter() {
+        out.extend_from_slice(&typed_input_digest(&input.decl, input.value.as_ref()));
+    }
+    out
+}
@@ -0,0 +1,252 @@
+//! Typed body segments: a framing that splits a proof body into sections a
+//! feature can target individually (compress, encrypt, or verify only some
+//! of them) without understanding the backend's encoding.
+//!
+//! Layout (little endian):
+//! ```text
+//! 0..4    magic "SEGB"
+//! 4..6    segment count (u16)
+//! then, per segment, a 16-byte descriptor followed by its payload:
+//!   0      kind (u8)      -- SegmentKind
+//!   1      flags (u8)     -- SegmentFlags
+//!   2..4   id (u16)       -- backend-defined section id, unique per body
+//!   4..8   payload length (u32)
+//!   8..16  checksum (u64) -- hash64("PROOF.SEGMENT", kind || flags || id || payload)
+//! ```
+//! The checksum covers the payload as stored, so a compressed or encrypted
+//! segment can be integrity-checked without being decoded.
+
+use std::convert::TryInto;
+
+use crate::crypto::blake3::Blake3;
+use crate::crypto::hash::Hash32;
+use crate::errors::VerifyError;
+
+pub const SEGMENT_MAGIC: [u8; 4] = *b"SEGB";
+const PREFIX_LEN: usize = 6;
+const DESCRIPTOR_LEN: usize = 16;
+const CHECKSUM_LABEL: &str = "PROOF.SEGMENT";
+
+/// What a segment may reveal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SegmentKind {
+    /// Commitments, roots and other data that is public by construction.
+    Public = 0,
+    /// Witness-derived data (openings, evaluations) whose secrecy the
+    /// zero-knowledge argument depends on; the target for encryption.
+    ZkCritical = 1,
+    /// Backend metadata (parameters, hints) that is neither.
+    Metadata = 2,
+}
+
+impl SegmentKind {
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0 => Some(SegmentKind::Public),
+            1 => Some(SegmentKind::ZkCritical),
+            2 => Some(SegmentKind::Metadata),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentKind::Public => "public",
+            SegmentKind::ZkCritical => "zk_critical",
+            SegmentKind::Metadata => "metadata",
+        }
+    }
+}
+
+/// Transformations applied to a segment's stored payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SegmentFlags(u8);
+
+impl SegmentFlags {
+    pub const NONE: Self = Self(0);
+    pub const COMPRESSED: Self = Self(1);
+    pub const ENCRYPTED: Self = Self(1 << 1);
+    const KNOWN: u8 = Self::COMPRESSED.0 | Self::ENCRYPTED.0;
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn with(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// One decoded segment, borrowing its payload from the body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment<'a> {
+    pub kind: SegmentKind,
+    pub flags: SegmentFlags,
+    pub id: u16,
+    pub payload: &'a [u8],
+}
+
+impl Segment<'_> {
+    pub fn checksum(&self) -> u64 {
+        checksum(self.kind, self.flags, self.id, self.payload)
+    }
+}
+
+/// Same value as `hash64(CHECKSUM_LABEL, kind || flags || id || payload)`,
+/// streamed so the payload is not copied.
+fn checksum(kind: SegmentKind, flags: SegmentFlags, id: u16, payload: &[u8]) -> u64 {
+    let mut h = Blake3::new();
+    h.update(CHECKSUM_LABEL.as_bytes());
+    h.update(&[kind as u8, flags.bits()]);
+    h.update(&id.to_le_bytes());
+    h.update(payload);
+    let digest = h.finalize();
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+/// Builder for a segmented body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentedBody {
+    segments: Vec<(SegmentKind, SegmentFlags, u16, Vec<u8>)>,
+}
+
+impl SegmentedBody {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a segment. Ids must be unique within the body.
+    pub fn push(
+        &mut self,
+        kind: SegmentKind,
+        flags: SegmentFlags,
+        id: u16,
+        payload: Vec<u8>,
+    ) -> &mut Self {
+        assert!(
+            self.segments.iter().all(|s| s.2 != id),
+            "duplicate segment id {id}"
+        );
+        assert!(u32::try_from(payload.len()).is_ok(), "segment too large");
+        self.segments.push((kind, flags, id, payload));
+        self
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let count = u16::try_from(self.segments.len()).expect("at most u16::MAX segments");
+        let payload_len: usize = self.segments.iter().map(|s| s.3.len()).sum();
+        let mut out =
+            Vec::with_capacity(PREFIX_LEN + self.segments.len() * DESCRIPTOR_LEN + payload_len);
+        out.extend_from_slice(&SEGMENT_MAGIC);
+        out.extend_from_slice(&count.to_le_bytes());
+        for (kind, flags, id, payload) in &self.segments {
+            out.push(*kind as u8);
+            out.push(flags.bits());
+            out.extend_from_slice(&id.to_le_bytes());
+            out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            out.extend_from_slice(&checksum(*kind, *flags, *id, payload).to_le_bytes());
+            out.extend_from_slice(payload);
+        }
+        out
+    }
+}
+
+/// Whether `body` uses the segment framing (backends with opaque bodies do
+/// not).
+pub fn is_segmented(body: &[u8]) -> bool {
+    body.starts_with(&SEGMENT_MAGIC)
+}
+
+/// Decode and checksum every segment of `body`, without copying payloads.
+pub fn parse_segments(body: &[u8]) -> Result<Vec<Segment<'_>>, VerifyError> {
+    let malformed = |index: usize, reason: String| VerifyError::Segment { index, reason };
+    if !is_segmented(body) || body.len() < PREFIX_LEN {
+        return Err(malformed(
+            0,
+            "body is not segmented (missing SEGB magic)".into(),
+        ));
+    }
+    let count = u16::from_le_bytes(body[4..6].try_into().unwrap()) as usize;
+    let mut segments = Vec::with_capacity(count);
+    let mut rest = &body[PREFIX_LEN..];
+    for index in 0..count {
+        if rest.len() < DESCRIPTOR_LEN {
+            return Err(malformed(index, "truncated segment descriptor".into()));
+        }
+        let (desc, tail) = rest.split_at(DESCRIPTOR_LEN);
+        let kind = SegmentKind::from_u8(desc[0])
+            .ok_or_else(|| malformed(index, format!("unknown segment kind {}", desc[0])))?;
+        if desc[1] & !SegmentFlags::KNOWN != 0 {
+            return Err(malformed(
+                index,
+                format!("unknown segment flags 0x{:02x}", desc[1]),
+            ));
+        }
+        let flags = SegmentFlags(desc[1]);
+        let id = u16::from_le_bytes(desc[2..4].try_into().unwrap());
+        let len = u32::from_le_bytes(desc[4..8].try_into().unwrap()) as usize;
+        let expected = u64::from_le_bytes(desc[8..16].try_into().unwrap());
+        if tail.len() < len {
+            return Err(malformed(
+                index,
+                format!(
+                    "payload of {} bytes exceeds the remaining {}",
+                    len,
+                    tail.len()
+                ),
+            ));
+        }
+        let (payload, next) = tail.split_at(len);
+        let segment = Segment {
+            kind,
+            flags,
+            id,
+            payload,
+        };
+        if segments.iter().any(|s: &Segment<'_>| s.id == id) {
+            return Err(malformed(index, format!("duplicate segment id {}", id)));
+        }
+        if segment.checksum() != expected {
+            return Err(malformed(
+                index,
+                format!("checksum mismatch in {} segment {}", kind.as_str(), id),
+            ));
+        }
+        segments.push(segment);
+        rest = next;
+    }
+    if !rest.is_empty() {
+        return Err(malformed(
+            count,
+            format!("{} trailing bytes after the last segment", rest.len()),
+        ));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::{hash64, HEADER_HASH_ID};
+
+    #[test]
+    fn streamed_checksum_matches_header_hash_policy() {
+        assert_eq!(HEADER_HASH_ID, "blake3");
+        let payload = b"segment payload";
+        let mut data = vec![
+            SegmentKind::ZkCritical as u8,
+            SegmentFlags::ENCRYPTED.bits(),
+        ];
+        data.extend_from_slice(&7u16.to_le_bytes());
+        data.extend_from_slice(payload);
+        assert_eq!(
+            checksum(SegmentKind::ZkCritical, SegmentFlags::ENCRYPTED, 7, payload),
+            hash64(CHECKSUM_LABEL, &data)
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Tamper localization: which region of a rejected proof is inconsistent.
+//!
+//! [`triage`] checks every header field and the body independently instead
+//! of stopping at the first failure, as verification does. The magic,
+//! version and body length are checked structurally. Header bindings and
+//! the body are checked by verifying repeatedly: each
+//! [`VerifyError::HeaderBinding`] is recorded and patched to the value the
+//! backend expects, until verification either passes (the body is
+//! consistent) or fails on the body itself (root, segment or backend
+//! rejection).
+//!
+//! Bindings are recomputed from the caller's config and inputs, so a proof
+//! made under another backend, profile or config shows up as a mismatch of
+//! the corresponding hash, not as tampering.
+//!
+//! Regions follow the current (v3) layout; a v2 proof reports a version
+//! mismatch and its later regions are read at v3 offsets.
+
+use std::convert::TryInto;
+
+use serde::Serialize;
+
+use super::{
+    ProofHeader, ProofView, BYTE_ORDER_LE, CRITICAL_FLAGS, HEADER_LEN, KNOWN_FLAGS, MAGIC, VERSION,
+};
+use crate::air::AirIr;
+use crate::backend::PublicInputs;
+use crate::config::Config;
+use crate::errors::{HeaderField, VerifyError};
+
+/// Outcome of one region's check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionStatus {
+    Ok,
+    Mismatch,
+    /// Not checked because the proof is too short to contain the region.
+    Missing,
+}
+
+/// One byte range of the proof and what was found there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegionCheck {
+    pub region: &'static str,
+    /// Byte range `start..end` within the proof.
+    pub start: usize,
+    pub end: usize,
+    pub status: RegionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl RegionCheck {
+    fn new(region: &'static str, start: usize, end: usize, status: RegionStatus) -> Self {
+        Self {
+            region,
+            start,
+            end,
+            status,
+            expected: None,
+            actual: None,
+            detail: None,
+        }
+    }
+
+    fn values(mut self, expected: String, actual: String) -> Self {
+        self.expected = Some(expected);
+        self.actual = Some(actual);
+        self
+    }
+}
+
+/// Per-region findings, in proof byte order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TriageReport {
+    pub proof_len: usize,
+    pub regions: Vec<RegionCheck>,
+}
+
+impl TriageReport {
+    /// Whether every region checked out; the proof then verifies.
+    pub fn is_consistent(&self) -> bool {
+        self.regions.iter().all(|r| r.status == RegionStatus::Ok)
+    }
+
+    /// Regions that did not check out.
+    pub fn findings(&self) -> impl Iterator<Item = &RegionCheck> {
+        self.regions.iter().filter(|r| r.status != RegionStatus::Ok)
+    }
+}
+
+const BINDINGS: [(HeaderField, usize); 4] = [
+    (HeaderField::BackendId, 16),
+    (HeaderField::ProfileId, 24),
+    (HeaderField::PubIo, 32),
+    (HeaderField::Config, 48),
+];
+
+fn field_mut(header: &mut ProofHeader, field: HeaderField) -> &mut u64 {
+    match field {
+        HeaderField::BackendId => &mut header.backend_id_hash,
+        HeaderField::ProfileId => &mut header.profile_id_hash,
+        HeaderField::PubIo => &mut header.pubio_hash,
+        HeaderField::Config => &mut header.config_hash,
+    }
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Localize what is inconsistent in `bytes` when verified under `config`
+/// against `ir` and `inputs`. Errors only when the config itself cannot be
+/// dispatched (unknown backend, capability mismatch).
+pub fn triage(
+    config: &Config,
+    ir: &AirIr,
+    inputs: &PublicInputs,
+    bytes: &[u8],
+) -> Result<TriageReport, VerifyError> {
+    let mut regions = Vec::new();
+    let hex = |b: &[u8]| format!("0x{}", alloy_primitives::hex::encode(b));
+    let fixed = |name, start: usize, want: &[u8]| {
+        let end = start + want.len();
+        match bytes.get(start..end) {
+            None => RegionCheck::new(name, start, end, RegionStatus::Missing),
+            Some(got) if got == want => RegionCheck::new(name, start, end, RegionStatus::Ok),
+            Some(got) => RegionCheck::new(name, start, end, RegionStatus::Mismatch)
+                .values(hex(want), hex(got)),
+        }
+    };
+    regions.push(fixed("magic", 0, &MAGIC));
+    regions.push(fixed("version", 4, &VERSION.to_le_bytes()));
+    regions.push(fixed("byte_order", 10, &[BYTE_ORDER_LE]));
+
+    if bytes.len() < HEADER_LEN {
+        for (field, offset) in BINDINGS {
+            regions.push(RegionCheck::new(
+                field.as_str(),
+                offset,
+                offset + 8,
+                RegionStatus::Missing,
+            ));
+        }
+        regions.push(RegionCheck::new("header_len", 8, 10, RegionStatus::Missing));
+        regions.push(RegionCheck::new("flags", 12, 16, RegionStatus::Missing));
+        regions.push(RegionCheck::new("body_len", 40, 48, RegionStatus::Missing));
+        regions.push(RegionCheck::new(
+            "body",
+            HEADER_LEN,
+            HEADER_LEN,
+            RegionStatus::Missing,
+        ));
+        regions.sort_by_key(|r| r.start);
+        return Ok(TriageReport {
+            proof_len: bytes.len(),
+            regions,
+        });
+    }
+
+    // An implausible header length is reported, and the body is then taken
+    // to start after the fixed fields.
+    let declared_header = u16::from_le_bytes(bytes[8..10].try_into().unwrap()) as usize;
+    let header_len = if declared_header == HEADER_LEN {
+        regions.push(RegionCheck::new("header_len", 8, 10, RegionStatus::Ok));
+        declared_header
+    } else if (HEADER_LEN..=bytes.len()).contains(&declared_header) {
+        // Extensions are not defined, but the body still starts after them.
+        let mut check = RegionCheck::new("header_len", 8, 10, RegionStatus::Mismatch)
+            .values(HEADER_LEN.to_string(), declared_header.to_string());
+        check.detail = Some(format!(
+            "header carries {} bytes of extensions; none are defined",
+            declared_header - HEADER_LEN
+        ));
+        regions.push(check);
+        declared_header
+    } else {
+        let mut check = RegionCheck::new("header_len", 8, 10, RegionStatus::Mismatch);
+        check.actual = Some(declared_header.to_string());
+        check.detail = Some(format!(
+            "header length must be between {} and the proof length {}",
+            HEADER_LEN,
+            bytes.len()
+        ));
+        regions.push(check);
+        HEADER_LEN
+    };
+    let flags = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+    let unknown = flags & !KNOWN_FLAGS;
+    regions.push(if unknown == 0 {
+        RegionCheck::new("flags", 12, 16, RegionStatus::Ok)
+    } else {
+        let mut check = RegionCheck::new("flags", 12, 16, RegionStatus::Mismatch);
+        check.actual = Some(format!("{flags:#010x}"));
+        check.detail = Some(if unknown & CRITICAL_FLAGS != 0 {
+            format!("unknown critical flags {:#06x}", unknown & CRITICAL_FLAGS)
+        } else {
+            format!("unassigned non-critical flags {unknown:#010x}")
+        });
+        check
+    });
+
+    let body = &bytes[header_len..];
+    let declared = u64_at(bytes, 40);
+    regions.push(if declared == body.len() as u64 {
+        RegionCheck::new("body_len", 40, 48, RegionStatus::Ok)
+    } else {
+        let mut check = RegionCheck::new("body_len", 40, 48, RegionStatus::Mismatch)
+            .values(body.len().to_string(), declared.to_string());
+        check.detail = Some(format!(
+            "header declares {} body bytes, proof carries {}",
+            declared,
+            body.len()
+        ));
+        check
+    });
+
+    // Read the fields regardless of magic/version, and take the body as it
+    // is, so each later check sees only its own region's damage.
+    let mut header = ProofHeader {
+        backend_id_hash: u64_at(bytes, 16),
+        profile_id_hash: u64_at(bytes, 24),
+        pubio_hash: u64_at(bytes, 32),
+        body_len: body.len() as u64,
+        config_hash: u64_at(bytes, 48),
+        flags,
+    };
+    let mut bindings: Vec<RegionCheck> = BINDINGS
+        .iter()
+        .map(|&(field, offset)| {
+            RegionCheck::new(field.as_str(), offset, offset + 8, RegionStatus::Ok)
+        })
+        .collect();
+    let mut body_check = RegionCheck::new("body", header_len, bytes.len(), RegionStatus::Ok);
+    for _ in 0..=BINDINGS.len() {
+        let view = ProofView {
+            header: header.clone(),
+            body,
+        };
+        match crate::verify(config, ir, inputs, &view) {
+            Ok(()) => break,
+            Err(VerifyError::HeaderBinding {
+                field,
+                expected,
+                actual,
+            }) => {
+                let i = BINDINGS.iter().position(|&(f, _)| f == field).unwrap();
+                if bindings[i].status == RegionStatus::Mismatch {
+                    // Patching did not take; stop rather than loop.
+                    body_check.status = RegionStatus::Mismatch;
+                    body_check.detail = Some(format!("{field} still mismatched after repair"));
+                    break;
+                }
+                bindings[i].status = RegionStatus::Mismatch;
+                bindings[i].expected = Some(format!("0x{expected:016x}"));
+                bindings[i].actual = Some(format!("0x{actual:016x}"));
+                *field_mut(&mut header, field) = expected;
+            }
+            Err(VerifyError::RootMismatch {
+                what,
+                expected,
+                actual,
+            }) => {
+                body_check.status = RegionStatus::Mismatch;
+                body_check = body_check.values(expected, actual);
+                body_check.detail = Some(what);
+                break;
+            }
+            Err(
+                e @ (VerifyError::Segment { .. }
+                | VerifyError::Rejected(_)
+                | VerifyError::Backend(_)),
+            ) => {
+                body_check.status = RegionStatus::Mismatch;
+                body_check.detail = Some(e.to_string());
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    regions.extend(bindings);
+    regions.push(body_check);
+    regions.sort_by_key(|r| r.start);
+    Ok(TriageReport {
+        proof_len: bytes.len(),
+        regions,
+    })
+}
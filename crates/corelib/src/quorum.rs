@@ -0,0 +1,209 @@
+//! K-of-N verification of redundant proofs.
+//!
+//! Running several provers for the same program and inputs (possibly on
+//! different backends) tolerates some of them failing or misbehaving. A
+//! quorum holds when at least `threshold` of the proofs verify and agree:
+//!
+//! * each proof is verified with the backend and profile its header names,
+//!   falling back to the config's when the header names no registered one;
+//! * proving is deterministic, so proofs from the same backend and profile
+//!   must share one EVM digest `D`. Within such a group the most common
+//!   digest wins and proofs with another digest are [`QuorumStatus::Diverged`]
+//!   (a nondeterministic or misconfigured prover) and do not count.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::air::AirIr;
+use crate::backend::PublicInputs;
+use crate::config::Config;
+use crate::evm::digest::digest_D;
+use crate::proof::{hash64, ProofHeader, ProofView};
+use crate::registry;
+
+/// Outcome for one proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumStatus {
+    /// Verified and agrees with its backend/profile group.
+    Accepted,
+    /// Failed to decode or verify.
+    Rejected,
+    /// Verified, but its digest differs from its group's majority.
+    Diverged,
+}
+
+impl QuorumStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QuorumStatus::Accepted => "accepted",
+            QuorumStatus::Rejected => "rejected",
+            QuorumStatus::Diverged => "diverged",
+        }
+    }
+}
+
+/// One proof's line in a [`QuorumReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuorumEntry {
+    /// Caller-supplied label, e.g. the proof path.
+    pub label: String,
+    pub status: QuorumStatus,
+    /// Backend and profile the proof was verified with.
+    pub backend: String,
+    pub profile: String,
+    /// `0x`-hex EVM digest `D`, when the header decodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// Failure category ([`crate::errors::VerifyError::category`]) and
+    /// message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of [`verify_quorum`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuorumReport {
+    pub threshold: usize,
+    pub total: usize,
+    /// Number of [`QuorumStatus::Accepted`] proofs.
+    pub accepted: usize,
+    /// `accepted >= threshold`.
+    pub reached: bool,
+    pub entries: Vec<QuorumEntry>,
+}
+
+/// Registered backend whose id hashes to the header's `backend_id_hash`.
+pub fn backend_for_header(header: &ProofHeader) -> Option<&'static str> {
+    registry::list_backend_infos()
+        .into_iter()
+        .map(|info| info.id)
+        .find(|id| hash64("BACKEND", id.as_bytes()) == header.backend_id_hash)
+}
+
+/// Built-in profile whose id hashes to the header's `profile_id_hash`.
+pub fn profile_for_header(header: &ProofHeader) -> Option<String> {
+    crate::list_profiles()
+        .into_iter()
+        .map(|info| info.id)
+        .find(|id| hash64("PROFILE", id.as_bytes()) == header.profile_id_hash)
+}
+
+/// Verify every `(label, proof bytes)` pair and decide whether at least
+/// `threshold` of them are accepted; see the module docs.
+///
+/// Fails only when `threshold` is not in `1..=proofs.len()`.
+pub fn verify_quorum(
+    config: &Config,
+    ir: &AirIr,
+    inputs: &PublicInputs,
+    proofs: &[(String, Vec<u8>)],
+    threshold: usize,
+) -> anyhow::Result<QuorumReport> {
+    anyhow::ensure!(
+        (1..=proofs.len()).contains(&threshold),
+        "quorum threshold {} must be between 1 and the number of proofs ({})",
+        threshold,
+        proofs.len()
+    );
+    let mut entries: Vec<QuorumEntry> = proofs
+        .iter()
+        .map(|(label, bytes)| verify_one(config, ir, inputs, label, bytes))
+        .collect();
+
+    // Majority digest per (backend, profile) among verified proofs.
+    let mut votes: BTreeMap<(&str, &str), Vec<&str>> = BTreeMap::new();
+    for entry in entries
+        .iter()
+        .filter(|e| e.status == QuorumStatus::Accepted)
+    {
+        votes
+            .entry((&entry.backend, &entry.profile))
+            .or_default()
+            .push(entry.digest.as_deref().unwrap_or_default());
+    }
+    let winners: BTreeMap<(String, String), String> = votes
+        .into_iter()
+        .map(|((backend, profile), digests)| {
+            // Most votes wins; ties go to the digest listed first.
+            let mut winner = ("", 0);
+            for d in &digests {
+                let count = digests.iter().filter(|other| *other == d).count();
+                if count > winner.1 {
+                    winner = (d, count);
+                }
+            }
+            (
+                (backend.to_string(), profile.to_string()),
+                winner.0.to_string(),
+            )
+        })
+        .collect();
+    for entry in &mut entries {
+        let key = (entry.backend.clone(), entry.profile.clone());
+        if entry.status == QuorumStatus::Accepted
+            && winners.get(&key).map(String::as_str) != entry.digest.as_deref()
+        {
+            entry.status = QuorumStatus::Diverged;
+            entry.error = Some(format!(
+                "digest differs from the other {} / {} proofs",
+                entry.backend, entry.profile
+            ));
+        }
+    }
+
+    let accepted = entries
+        .iter()
+        .filter(|e| e.status == QuorumStatus::Accepted)
+        .count();
+    Ok(QuorumReport {
+        threshold,
+        total: entries.len(),
+        accepted,
+        reached: accepted >= threshold,
+        entries,
+    })
+}
+
+fn verify_one(
+    config: &Config,
+    ir: &AirIr,
+    inputs: &PublicInputs,
+    label: &str,
+    bytes: &[u8],
+) -> QuorumEntry {
+    let mut entry = QuorumEntry {
+        label: label.to_string(),
+        status: QuorumStatus::Rejected,
+        backend: config.backend_id.clone(),
+        profile: config.profile_id.clone(),
+        digest: None,
+        category: None,
+        error: None,
+    };
+    let result = ProofView::from_bytes(bytes).and_then(|proof| {
+        let mut config = config.clone();
+        if let Some(backend) = backend_for_header(&proof.header) {
+            config.backend_id = backend.to_string();
+        }
+        if let Some(profile) = profile_for_header(&proof.header) {
+            config.profile_id = profile;
+        }
+        entry.backend = config.backend_id.clone();
+        entry.profile = config.profile_id.clone();
+        let digest = digest_D(&proof.header, proof.body);
+        entry.digest = Some(format!("0x{}", alloy_primitives::hex::encode(digest)));
+        crate::verify(&config, ir, inputs, &proof)
+    });
+    match result {
+        Ok(()) => entry.status = QuorumStatus::Accepted,
+        Err(e) => {
+            entry.category = Some(e.category().to_string());
+            entry.error = Some(e.to_string());
+        }
+    }
+    entry
+}
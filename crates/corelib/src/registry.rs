@@ -1,9 +1,26 @@
 //! Global backend registry (thread-safe).
-use std::collections::BTreeMap;
+//!
+//! Every function here may be called from any thread at any time. Updates
+//! that touch several tables hold all of their locks at once, always taken in
+//! the order `REGISTRY`, `ALIASES`, `PLACEHOLDERS`, `DEPRECATIONS`, so a
+//! concurrent registration can neither be observed half-done nor deadlock a
+//! reader.
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Once, RwLock};
 
-use crate::backend::{BackendInfo, Capabilities, ProverBackend, VerifierBackend};
-use crate::errors::RegistryError;
+use serde::{Deserialize, Serialize};
+
+use crate::air::types::AirIr;
+use crate::backend::{
+    BackendInfo, CallContext, Capabilities, Deprecation, ProverBackend, PublicInputs,
+    VerifierBackend,
+};
+use crate::crypto::registry::{canonical_hash_id, HashInfo, HASHES};
+use crate::errors::{ProveError, RegistryError, VerifyError};
+use crate::plugin::{PluginFailure, PluginInfo};
+use crate::profile::Profile;
+use crate::proof::{Proof, ProofView};
 
 pub struct DynBackend {
     pub prover: Box<dyn ProverBackend>,
@@ -11,33 +28,407 @@ pub struct DynBackend {
 }
 
 static REGISTRY: RwLock<BTreeMap<&'static str, Arc<DynBackend>>> = RwLock::new(BTreeMap::new());
+/// alias -> target (a backend id or another alias)
+static ALIASES: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+/// canonical backend id -> deprecation notice
+static DEPRECATIONS: RwLock<BTreeMap<&'static str, Deprecation>> = RwLock::new(BTreeMap::new());
+/// Builtin entries that only advertise capabilities; a real adapter registered
+/// under the same id replaces them.
+static PLACEHOLDERS: RwLock<BTreeSet<&'static str>> = RwLock::new(BTreeSet::new());
 static INIT: Once = Once::new();
+static HOOKS: RwLock<Vec<(EventHookId, Arc<EventHook>)>> = RwLock::new(Vec::new());
+static NEXT_HOOK: AtomicU64 = AtomicU64::new(0);
+
+/// Something observable that happened in the registry or in a
+/// [`crate::prove`]/[`crate::verify`] dispatch, delivered to [`on_event`]
+/// hooks. Serializes as `{"event": "prove_finished", ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RegistryEvent {
+    BackendRegistered {
+        backend_id: &'static str,
+    },
+    /// A dispatch resolved `requested` (an id or alias) to `backend_id`.
+    BackendSelected {
+        requested: String,
+        backend_id: &'static str,
+        profile_id: String,
+    },
+    ProveStarted {
+        backend_id: &'static str,
+        profile_id: String,
+        program: String,
+    },
+    /// `proof_len` on success, `error` on failure.
+    ProveFinished {
+        backend_id: &'static str,
+        profile_id: String,
+        program: String,
+        elapsed_ms: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        proof_len: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Verified {
+        backend_id: &'static str,
+        profile_id: String,
+        program: String,
+        elapsed_ms: u64,
+        verified: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+pub type EventHook = dyn Fn(RegistryEvent) + Send + Sync;
+
+/// Handle returned by [`on_event`], for [`remove_event_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventHookId(u64);
+
+/// Subscribe `hook` to every [`RegistryEvent`], process-wide. Hooks run
+/// synchronously on the thread that caused the event, in subscription order,
+/// so they should be quick (hand off to a channel for slow sinks).
+pub fn on_event(hook: Box<EventHook>) -> EventHookId {
+    let id = EventHookId(NEXT_HOOK.fetch_add(1, Ordering::Relaxed));
+    HOOKS
+        .write()
+        .expect("poisoned event hooks")
+        .push((id, Arc::from(hook)));
+    id
+}
+
+/// Unsubscribe a hook; `false` if it was already removed.
+pub fn remove_event_hook(id: EventHookId) -> bool {
+    let mut hooks = HOOKS.write().expect("poisoned event hooks");
+    let before = hooks.len();
+    hooks.retain(|(h, _)| *h != id);
+    hooks.len() != before
+}
+
+/// Deliver `event` to every hook. The hook list is copied first, so hooks
+/// may subscribe or unsubscribe without deadlocking.
+pub(crate) fn emit(event: RegistryEvent) {
+    let hooks: Vec<Arc<EventHook>> = HOOKS
+        .read()
+        .expect("poisoned event hooks")
+        .iter()
+        .map(|(_, h)| h.clone())
+        .collect();
+    for hook in hooks {
+        hook(event.clone());
+    }
+}
 
 pub fn register_backend(
     prover: Box<dyn ProverBackend>,
     verifier: Box<dyn VerifierBackend>,
+) -> Result<(), RegistryError> {
+    insert_backend(prover, verifier, false)
+}
+
+/// Insert a backend, replacing a placeholder under the same id. The entry and
+/// its placeholder flag change under one set of locks, so a real adapter
+/// registered concurrently with the builtin stand-in always wins.
+fn insert_backend(
+    prover: Box<dyn ProverBackend>,
+    verifier: Box<dyn VerifierBackend>,
+    placeholder: bool,
 ) -> Result<(), RegistryError> {
     let id = prover.id();
     let mut guard = REGISTRY.write().expect("poisoned backend registry");
-    if guard.contains_key(id) {
+    if ALIASES
+        .read()
+        .expect("poisoned alias registry")
+        .contains_key(id)
+    {
         return Err(RegistryError::DuplicateBackend(id.to_string()));
     }
+    let mut placeholders = PLACEHOLDERS.write().expect("poisoned placeholder registry");
+    if guard.contains_key(id) && (placeholder || !placeholders.contains(id)) {
+        return Err(RegistryError::DuplicateBackend(id.to_string()));
+    }
+    if placeholder {
+        placeholders.insert(id);
+    } else {
+        placeholders.remove(id);
+    }
     guard.insert(id, Arc::new(DynBackend { prover, verifier }));
+    drop(placeholders);
+    drop(guard);
+    emit(RegistryEvent::BackendRegistered { backend_id: id });
+    Ok(())
+}
+
+/// Register `alias` as another name for `target` (a backend id or an existing alias).
+/// Re-registering the same alias for the same target is a no-op.
+pub fn register_alias(alias: &str, target: &str) -> Result<(), RegistryError> {
+    let backends = REGISTRY.read().expect("poisoned backend registry");
+    if backends.contains_key(alias) {
+        return Err(RegistryError::AliasConflict {
+            alias: alias.to_string(),
+            existing: alias.to_string(),
+        });
+    }
+    let mut aliases = ALIASES.write().expect("poisoned alias registry");
+    // Target must already resolve, which also rules out cycles.
+    resolve_in(&backends, &aliases, target)?;
+    if let Some(existing) = aliases.get(alias) {
+        if existing == target {
+            return Ok(());
+        }
+        return Err(RegistryError::AliasConflict {
+            alias: alias.to_string(),
+            existing: existing.clone(),
+        });
+    }
+    aliases.insert(alias.to_string(), target.to_string());
+    Ok(())
+}
+
+/// Resolve a backend id or alias to the canonical registered backend id.
+pub fn resolve_backend_id(id: &str) -> Result<&'static str, RegistryError> {
+    let backends = REGISTRY.read().expect("poisoned backend registry");
+    let aliases = ALIASES.read().expect("poisoned alias registry");
+    resolve_in(&backends, &aliases, id)
+}
+
+fn resolve_in(
+    backends: &BTreeMap<&'static str, Arc<DynBackend>>,
+    aliases: &BTreeMap<String, String>,
+    id: &str,
+) -> Result<&'static str, RegistryError> {
+    let mut current = id;
+    // Each hop consumes one alias; more hops than aliases means a cycle.
+    for _ in 0..=aliases.len() {
+        if let Some((key, _)) = backends.get_key_value(current) {
+            return Ok(key);
+        }
+        match aliases.get(current) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    Err(RegistryError::BackendNotFound(id.to_string()))
+}
+
+/// Alias chain starting at `id`, e.g. `["winterfell@latest", "winterfell@0.6"]`.
+/// Returns just `[id]` when `id` is not an alias.
+pub fn alias_chain(id: &str) -> Vec<String> {
+    let aliases = ALIASES.read().expect("poisoned alias registry");
+    let mut chain = vec![id.to_string()];
+    let mut current = id;
+    while let Some(next) = aliases.get(current) {
+        if chain.iter().any(|c| c == next) {
+            break;
+        }
+        chain.push(next.clone());
+        current = next;
+    }
+    chain
+}
+
+/// All registered aliases with their immediate targets, sorted by alias.
+pub fn list_aliases() -> Vec<(String, String)> {
+    let aliases = ALIASES.read().expect("poisoned alias registry");
+    aliases
+        .iter()
+        .map(|(a, t)| (a.clone(), t.clone()))
+        .collect()
+}
+
+/// Mark a backend (or the backend an alias resolves to) as deprecated.
+pub fn deprecate_backend(id: &str, deprecation: Deprecation) -> Result<(), RegistryError> {
+    if !is_iso_date(&deprecation.sunset) {
+        return Err(RegistryError::InvalidSunset(deprecation.sunset));
+    }
+    let canonical = resolve_backend_id(id)?;
+    DEPRECATIONS
+        .write()
+        .expect("poisoned deprecation registry")
+        .insert(canonical, deprecation);
     Ok(())
 }
 
+/// Deprecation notice for a backend id or alias, if any.
+pub fn backend_deprecation(id: &str) -> Option<Deprecation> {
+    let canonical = resolve_backend_id(id).ok()?;
+    DEPRECATIONS
+        .read()
+        .expect("poisoned deprecation registry")
+        .get(canonical)
+        .cloned()
+}
+
+fn is_iso_date(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() == 10
+        && b[4] == b'-'
+        && b[7] == b'-'
+        && b.iter()
+            .enumerate()
+            .all(|(i, c)| i == 4 || i == 7 || c.is_ascii_digit())
+}
+
 pub fn list_backend_infos() -> Vec<BackendInfo> {
+    // Resolve alias chains before taking the backend lock (see lock order).
+    let aliases: Vec<(String, Option<String>)> = list_aliases()
+        .into_iter()
+        .map(|(alias, _)| {
+            let target = alias_chain(&alias).pop();
+            (alias, target)
+        })
+        .collect();
     let guard = REGISTRY.read().expect("poisoned backend registry");
+    let deprecations = DEPRECATIONS.read().expect("poisoned deprecation registry");
     guard
         .iter()
         .map(|(id, dynb)| BackendInfo {
             id,
             recursion: dynb.prover.capabilities().recursion != "none",
+            aliases: aliases
+                .iter()
+                .filter(|(_, target)| target.as_deref() == Some(*id))
+                .map(|(alias, _)| alias.clone())
+                .collect(),
+            deprecated: deprecations.get(id).cloned(),
         })
         .collect()
 }
 
+/// Ids of registered backends whose capabilities satisfy `pred`, sorted by id.
+///
+/// ```
+/// use zkprov_corelib::registry::{ensure_builtins_registered, find_backends};
+/// ensure_builtins_registered();
+/// let ids = find_backends(|caps| caps.hashes.contains(&"poseidon2") && caps.recursion == "none");
+/// assert!(ids.contains(&"native@0.0"));
+/// ```
+pub fn find_backends(pred: impl Fn(&Capabilities) -> bool) -> Vec<&'static str> {
+    let guard = REGISTRY.read().expect("poisoned backend registry");
+    guard
+        .iter()
+        .filter(|(_, dynb)| pred(&dynb.prover.capabilities()))
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+/// Serializable capability predicate for embedders (bindings, `--backend auto`).
+/// Unset fields match anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackendQuery {
+    pub field: Option<String>,
+    pub hash: Option<String>,
+    pub fri_arity: Option<u32>,
+    /// `true` requires recursion, `false` requires none.
+    pub recursion: Option<bool>,
+    pub lookups: Option<bool>,
+    pub pedersen: Option<bool>,
+    pub curve: Option<String>,
+    /// Backends must accept traces of at least this many rows.
+    pub min_rows: Option<u32>,
+    /// Backends must satisfy every entry (an AIR's `meta.requires`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+}
+
+impl BackendQuery {
+    pub fn matches(&self, caps: &Capabilities) -> bool {
+        let has = |list: &[&str], want: &Option<String>| {
+            want.as_deref().is_none_or(|w| list.contains(&w))
+        };
+        let hash = self
+            .hash
+            .as_deref()
+            .map(|h| canonical_hash_id(h).unwrap_or(h).to_string());
+        has(&caps.fields, &self.field)
+            && has(&caps.hashes, &hash)
+            && has(&caps.curves, &self.curve)
+            && self.fri_arity.is_none_or(|a| caps.fri_arities.contains(&a))
+            && self
+                .recursion
+                .is_none_or(|r| r == (caps.recursion != "none"))
+            && self.lookups.is_none_or(|l| l == caps.lookups)
+            && self.pedersen.is_none_or(|p| p == caps.pedersen)
+            && self
+                .min_rows
+                .is_none_or(|rows| caps.max_rows.is_none_or(|max| rows <= max))
+            && self.requires.iter().all(|r| caps.satisfies(r))
+    }
+}
+
+/// Backend id accepted by the CLI in place of a concrete id; resolved with
+/// [`select_backend`].
+pub const AUTO_BACKEND: &str = "auto";
+
+/// First backend (by id) matching `query`, skipping deprecated backends.
+pub fn select_backend(query: &BackendQuery) -> Result<&'static str, RegistryError> {
+    let matches = find_backends(|caps| query.matches(caps));
+    let deprecations = DEPRECATIONS.read().expect("poisoned deprecation registry");
+    matches
+        .into_iter()
+        .find(|id| !deprecations.contains_key(id))
+        .ok_or_else(|| {
+            RegistryError::NoMatchingBackend(
+                serde_json::to_string(query).expect("query serializes"),
+            )
+        })
+}
+
+/// Registered backend with its full capability matrix.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendEntry {
+    #[serde(flatten)]
+    pub info: BackendInfo,
+    pub capabilities: Capabilities,
+    /// Capabilities-only builtin that cannot prove until the real adapter is registered.
+    pub placeholder: bool,
+    /// Set for backends loaded from a shared library.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<PluginInfo>,
+}
+
+/// Whole registry state: backends (sorted by id), alias -> target edges, and
+/// the hash ids capabilities refer to (with in-circuit cost notes).
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistrySnapshot {
+    pub backends: Vec<BackendEntry>,
+    pub aliases: BTreeMap<String, String>,
+    pub hashes: &'static [HashInfo],
+    /// `ZKD_BACKEND_PATH` libraries that were skipped, and why.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub plugin_errors: Vec<PluginFailure>,
+}
+
+pub fn snapshot() -> RegistrySnapshot {
+    let infos = list_backend_infos();
+    let aliases = list_aliases().into_iter().collect();
+    let guard = REGISTRY.read().expect("poisoned backend registry");
+    let placeholders = PLACEHOLDERS.read().expect("poisoned placeholder registry");
+    let backends = infos
+        .into_iter()
+        .filter_map(|info| {
+            let dynb = guard.get(info.id)?;
+            Some(BackendEntry {
+                capabilities: dynb.prover.capabilities(),
+                placeholder: placeholders.contains(info.id),
+                plugin: crate::plugin::info(info.id),
+                info,
+            })
+        })
+        .collect();
+    RegistrySnapshot {
+        backends,
+        aliases,
+        hashes: HASHES,
+        plugin_errors: crate::plugin::failures(),
+    }
+}
+
 pub fn get_backend_capabilities(id: &str) -> Result<Capabilities, RegistryError> {
+    let id = resolve_backend_id(id)?;
     let guard = REGISTRY.read().expect("poisoned backend registry");
     let Some(dynb) = guard.get(id) else {
         return Err(RegistryError::BackendNotFound(id.to_string()));
@@ -45,7 +436,13 @@ pub fn get_backend_capabilities(id: &str) -> Result<Capabilities, RegistryError>
     Ok(dynb.prover.capabilities().clone())
 }
 
+/// Options schema of a registered backend; `None` if it takes no options.
+pub fn get_backend_options_schema(id: &str) -> Result<Option<serde_json::Value>, RegistryError> {
+    Ok(get_backend(id)?.prover.options_schema())
+}
+
 pub fn get_backend(id: &str) -> Result<Arc<DynBackend>, RegistryError> {
+    let id = resolve_backend_id(id)?;
     let guard = REGISTRY.read().expect("poisoned backend registry");
     guard
         .get(id)
@@ -54,19 +451,24 @@ pub fn get_backend(id: &str) -> Result<Arc<DynBackend>, RegistryError> {
 }
 
 /// Helper used by CLI/tests to ensure at least builtins are available.
+/// Backend plugins are never loaded here; see `plugin::load_from_env`.
 pub fn ensure_builtins_registered() {
     INIT.call_once(|| {
         let _ = register_native_backend(); // ignore duplicate errors if any
+        let _ = register_alias("native@latest", "native@0.0");
     });
 }
 
 fn register_native_backend() -> Result<(), RegistryError> {
-    register_backend(
+    insert_backend(
         Box::new(NativeBuiltinBackend),
         Box::new(NativeBuiltinBackend),
+        true,
     )
 }
 
+/// Capabilities-only stand-in for `native@0.0`, so validation works without
+/// linking the native backend crate. Registering the real adapter replaces it.
 #[derive(Debug, Default)]
 struct NativeBuiltinBackend;
 
@@ -84,8 +486,38 @@ impl ProverBackend for NativeBuiltinBackend {
             lookups: false,
             curves: vec!["placeholder"],
             pedersen: true,
+            max_rows: Some(1 << 22),
+            incremental: false,
+            nondeterminism: Vec::new(),
         }
     }
+
+    fn prove(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        Err(ProveError::Unsupported {
+            backend_id: self.id().to_string(),
+            feature: "proving (register zkprov-backend-native)".to_string(),
+        })
+    }
 }
 
-impl VerifierBackend for NativeBuiltinBackend {}
+impl VerifierBackend for NativeBuiltinBackend {
+    fn verify(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        _: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        Err(VerifyError::Unsupported {
+            backend_id: self.id().to_string(),
+            feature: "verification (register zkprov-backend-native)".to_string(),
+        })
+    }
+}
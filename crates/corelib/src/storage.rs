@@ -0,0 +1,187 @@
+//! Artifact storage for proofs and their sidecars.
+//!
+//! Proving services archive each proof under its EVM digest `D`:
+//!
+//! ```text
+//! <hex D>/proof            proof blob
+//! <hex D>/manifest.json    determinism manifest
+//! <hex D>/proof.tsr        RFC 3161 timestamp token, when present
+//! ```
+//!
+//! Stores implement [`Storage`]; this crate ships [`FsStorage`] (a local
+//! directory), and `zkd` adds an S3-compatible store behind its `s3`
+//! feature. Which one to use is read from the `[storage]` table of
+//! `zkd.toml` (or a service config) into a [`StorageConfig`]:
+//!
+//! ```toml
+//! [storage]
+//! kind = "s3"
+//! bucket = "proofs"
+//! endpoint = "https://storage.googleapis.com"   # GCS via HMAC keys
+//! region = "auto"
+//! prefix = "prod/"
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// Artifact names stored under each digest, with the local sidecar suffix
+/// each corresponds to (appended to the proof path).
+pub const ARTIFACTS: [(&str, &str); 3] = [
+    ("proof", ""),
+    ("manifest.json", ".manifest.json"),
+    ("proof.tsr", ".tsr"),
+];
+
+/// A key/value store for artifact files.
+pub trait Storage {
+    /// Store the file at `src` under `key`, replacing any previous object.
+    fn put(&self, key: &str, src: &Path) -> Result<()>;
+    /// Fetch `key` into the file `dest`; fails if `key` does not exist.
+    fn get(&self, key: &str, dest: &Path) -> Result<()>;
+    fn exists(&self, key: &str) -> Result<bool>;
+    /// Human-readable location of `key`, e.g. a path or URL.
+    fn locate(&self, key: &str) -> String;
+}
+
+/// Key of artifact `name` for the proof with digest `digest_d`.
+pub fn artifact_key(digest_d: &[u8; 32], name: &str) -> String {
+    format!("{}/{}", alloy_primitives::hex::encode(digest_d), name)
+}
+
+/// Reject keys that are empty, absolute, or contain empty, `.` or `..`
+/// segments, so no store can be pointed outside its root.
+pub fn check_key(key: &str) -> Result<()> {
+    if key.is_empty() || key.starts_with('/') || key.contains('\\') {
+        bail!("invalid storage key '{}'", key);
+    }
+    if key
+        .split('/')
+        .any(|seg| seg.is_empty() || seg == "." || seg == "..")
+    {
+        bail!("invalid storage key '{}'", key);
+    }
+    Ok(())
+}
+
+/// Store rooted at a local directory; keys map to relative paths.
+#[derive(Debug, Clone)]
+pub struct FsStorage {
+    root: PathBuf,
+}
+
+impl FsStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        check_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+impl Storage for FsStorage {
+    fn put(&self, key: &str, src: &Path) -> Result<()> {
+        let dest = self.path(key)?;
+        if let Some(dir) = dest.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create dir '{}'", dir.display()))?;
+        }
+        // Copy beside the destination and rename, so readers never see a
+        // partial object.
+        let tmp = dest.with_extension("partial");
+        fs::copy(src, &tmp).with_context(|| {
+            format!("failed to copy '{}' to '{}'", src.display(), tmp.display())
+        })?;
+        fs::rename(&tmp, &dest).with_context(|| format!("failed to write '{}'", dest.display()))?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str, dest: &Path) -> Result<()> {
+        let src = self.path(key)?;
+        if !src.is_file() {
+            bail!("no object '{}' in {}", key, self.root.display());
+        }
+        fs::copy(&src, dest).with_context(|| {
+            format!("failed to copy '{}' to '{}'", src.display(), dest.display())
+        })?;
+        Ok(())
+    }
+
+    fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.path(key)?.is_file())
+    }
+
+    fn locate(&self, key: &str) -> String {
+        self.root.join(key).display().to_string()
+    }
+}
+
+/// Connection settings for an S3-compatible object store (AWS S3, GCS
+/// interoperability, MinIO, ...). Objects are addressed path-style as
+/// `<endpoint>/<bucket>/<prefix><key>`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3Config {
+    pub bucket: String,
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// Prepended to every key, e.g. `prod/`.
+    #[serde(default)]
+    pub prefix: String,
+    /// Environment variables holding the access key pair; credentials never
+    /// live in the config file.
+    #[serde(default = "default_access_key_env")]
+    pub access_key_env: String,
+    #[serde(default = "default_secret_key_env")]
+    pub secret_key_env: String,
+}
+
+fn default_endpoint() -> String {
+    "https://s3.amazonaws.com".to_string()
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_access_key_env() -> String {
+    "AWS_ACCESS_KEY_ID".to_string()
+}
+
+fn default_secret_key_env() -> String {
+    "AWS_SECRET_ACCESS_KEY".to_string()
+}
+
+/// The `[storage]` table of `zkd.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum StorageConfig {
+    Fs { root: PathBuf },
+    S3(S3Config),
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    storage: Option<StorageConfig>,
+}
+
+impl StorageConfig {
+    /// The `[storage]` table of a `zkd.toml` document, if any; other tables
+    /// are ignored.
+    pub fn from_toml_str(s: &str) -> Result<Option<Self>> {
+        let file: ConfigFile = toml::from_str(s).context("parsing [storage]")?;
+        Ok(file.storage)
+    }
+
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let s = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_toml_str(&s).with_context(|| format!("in {}", path.display()))
+    }
+}
@@ -0,0 +1,462 @@
+//! Trusted timestamps (RFC 3161) over the EVM digest `D` of a proof.
+//!
+//! Only a hash leaves the machine: the request's message imprint is
+//! `SHA-256(D)`, so the TSA learns nothing about the proof or its inputs. The
+//! TSA's reply (a DER `TimeStampResp`) is stored next to the proof as
+//! `<proof>.tsr`, the format `openssl ts -reply` / `openssl ts -verify` use.
+//!
+//! Transport is pluggable through [`TimestampClient`]; `zkd` ships an HTTP
+//! client, and tests or air-gapped setups can provide their own.
+//!
+//! [`read_response`] checks that a reply is granted and that its `TSTInfo`
+//! imprint is `SHA-256(D)` for the given proof, without authenticating it.
+//! [`check_response`] additionally has a [`TokenVerifier`] check the CMS
+//! signature and the TSA's certificate chain against trusted certificates;
+//! `zkd` ships one that runs `openssl ts -verify`. There is no default
+//! verifier: a token is only trusted against certificates the caller names.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// File extension of the stored `TimeStampResp`.
+pub const TOKEN_EXTENSION: &str = "tsr";
+
+const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+const OID_SIGNED_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+const OID_TST_INFO: &[u8] = &[
+    0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x01, 0x04,
+];
+
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_CONTEXT_0: u8 = 0xa0;
+
+/// Message imprint sent to the TSA: `SHA-256(D)`.
+pub fn imprint(digest_d: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(digest_d).into()
+}
+
+/// An RFC 3161 `TimeStampReq` for one proof digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampRequest {
+    pub imprint: [u8; 32],
+    pub nonce: u64,
+}
+
+impl TimestampRequest {
+    pub fn new(digest_d: &[u8; 32], nonce: u64) -> Self {
+        Self {
+            imprint: imprint(digest_d),
+            nonce,
+        }
+    }
+
+    /// DER encoding (version 1, SHA-256 imprint, nonce, `certReq = TRUE`).
+    pub fn to_der(&self) -> Vec<u8> {
+        let algorithm = der(
+            TAG_SEQUENCE,
+            &[der(TAG_OID, OID_SHA256), der(TAG_NULL, &[])].concat(),
+        );
+        let message_imprint = der(
+            TAG_SEQUENCE,
+            &[algorithm, der(TAG_OCTET_STRING, &self.imprint)].concat(),
+        );
+        der(
+            TAG_SEQUENCE,
+            &[
+                der(TAG_INTEGER, &[1]),
+                message_imprint,
+                der(TAG_INTEGER, &uint_content(self.nonce)),
+                der(TAG_BOOLEAN, &[0xff]),
+            ]
+            .concat(),
+        )
+    }
+}
+
+/// Transport to a time-stamping authority.
+pub trait TimestampClient {
+    /// Submit `request` and return the DER `TimeStampResp` bytes.
+    fn submit(&self, request: &TimestampRequest) -> Result<Vec<u8>>;
+}
+
+/// Authenticates a `TimeStampResp`: the CMS `SignedData` signature over its
+/// `TSTInfo` and the signer's certificate chain up to a trusted TSA
+/// certificate or CA.
+pub trait TokenVerifier: std::fmt::Debug {
+    /// Fail unless `response` is signed by a trusted TSA and its `TSTInfo`
+    /// imprints `imprint`.
+    fn verify_token(&self, response: &[u8], imprint: &[u8; 32]) -> Result<()>;
+}
+
+/// Fields of the `TSTInfo` signed by the TSA.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampInfo {
+    /// `genTime` as RFC 3339 (UTC), e.g. `2026-10-17T09:30:00Z`.
+    pub time: String,
+    /// Serial number, hex.
+    pub serial: String,
+    /// Message imprint, hex (`SHA-256(D)`).
+    pub imprint: String,
+    /// Nonce echoed by the TSA, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+}
+
+impl TimestampInfo {
+    /// `time` as seconds since the Unix epoch, fractional seconds dropped.
+    pub fn unix_time(&self) -> Result<u64> {
+        let t = self.time.as_bytes();
+        let num = |range: std::ops::Range<usize>| -> Result<i64> {
+            std::str::from_utf8(t.get(range).unwrap_or_default())
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| anyhow!("malformed timestamp time '{}'", self.time))
+        };
+        let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+        let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+        // Days from 1970-01-01 in the proleptic Gregorian calendar.
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+        u64::try_from(days * 86_400 + hour * 3600 + minute * 60 + second)
+            .map_err(|_| anyhow!("timestamp time '{}' predates 1970", self.time))
+    }
+}
+
+/// Request a timestamp for `digest_d` through `client` and check the reply
+/// with [`read_response`] and the nonce; the signature is left to
+/// [`check_response`] at verification time. Returns the raw response (to
+/// store as `<proof>.tsr`) and its `TSTInfo`.
+pub fn stamp(
+    client: &dyn TimestampClient,
+    digest_d: &[u8; 32],
+    nonce: u64,
+) -> Result<(Vec<u8>, TimestampInfo)> {
+    let request = TimestampRequest::new(digest_d, nonce);
+    let response = client
+        .submit(&request)
+        .context("timestamp request failed")?;
+    let info = read_response(&response, digest_d)?;
+    ensure!(
+        info.nonce == Some(nonce),
+        "TSA reply does not echo the request nonce"
+    );
+    Ok((response, info))
+}
+
+/// Validate a stored `TimeStampResp` against the proof digest `D` and
+/// authenticate it with `verifier`.
+pub fn check_response(
+    response: &[u8],
+    digest_d: &[u8; 32],
+    verifier: &dyn TokenVerifier,
+) -> Result<TimestampInfo> {
+    let info = read_response(response, digest_d)?;
+    verifier
+        .verify_token(response, &imprint(digest_d))
+        .context("timestamp signature not trusted")?;
+    Ok(info)
+}
+
+/// Parse a `TimeStampResp` and check that it is granted over the proof
+/// digest `D`. The signature is not checked, so the returned fields are
+/// only as trustworthy as the file; see [`check_response`].
+pub fn read_response(response: &[u8], digest_d: &[u8; 32]) -> Result<TimestampInfo> {
+    let (resp, _) = expect(response, TAG_SEQUENCE, "TimeStampResp")?;
+    let (status_info, token) = expect(resp, TAG_SEQUENCE, "PKIStatusInfo")?;
+    let (status, _) = expect(status_info, TAG_INTEGER, "PKIStatus")?;
+    match status {
+        [0] | [1] => {}
+        other => bail!("TSA refused the request (PKIStatus {:?})", other),
+    }
+    ensure!(!token.is_empty(), "TSA reply has no timeStampToken");
+    let info = tst_info(token)?;
+    ensure!(
+        info.imprint == hex(&imprint(digest_d)),
+        "timestamp imprint {} does not match SHA-256 of the proof digest",
+        info.imprint
+    );
+    Ok(info)
+}
+
+/// Walk `ContentInfo -> SignedData -> encapContentInfo -> TSTInfo`.
+fn tst_info(token: &[u8]) -> Result<TimestampInfo> {
+    let (content_info, _) = expect(token, TAG_SEQUENCE, "ContentInfo")?;
+    let (content_type, rest) = expect(content_info, TAG_OID, "contentType")?;
+    ensure!(
+        content_type == OID_SIGNED_DATA,
+        "timeStampToken is not SignedData"
+    );
+    let (explicit, _) = expect(rest, TAG_CONTEXT_0, "content")?;
+    let (signed_data, _) = expect(explicit, TAG_SEQUENCE, "SignedData")?;
+    let (_, rest) = expect(signed_data, TAG_INTEGER, "version")?;
+    let (_, _, rest) = tlv(rest).context("digestAlgorithms")?;
+    let (encap, _) = expect(rest, TAG_SEQUENCE, "encapContentInfo")?;
+    let (e_type, rest) = expect(encap, TAG_OID, "eContentType")?;
+    ensure!(e_type == OID_TST_INFO, "signed content is not a TSTInfo");
+    let (explicit, _) = expect(rest, TAG_CONTEXT_0, "eContent")?;
+    let (tst, _) = expect(explicit, TAG_OCTET_STRING, "eContent")?;
+    let (tst, _) = expect(tst, TAG_SEQUENCE, "TSTInfo")?;
+
+    let (_, rest) = expect(tst, TAG_INTEGER, "version")?;
+    let (_, rest) = expect(rest, TAG_OID, "policy")?;
+    let (message_imprint, rest) = expect(rest, TAG_SEQUENCE, "messageImprint")?;
+    let (serial, rest) = expect(rest, TAG_INTEGER, "serialNumber")?;
+    let (gen_time, mut rest) = expect(rest, TAG_GENERALIZED_TIME, "genTime")?;
+
+    let (algorithm, hashed) = expect(message_imprint, TAG_SEQUENCE, "hashAlgorithm")?;
+    let (alg_oid, _) = expect(algorithm, TAG_OID, "hashAlgorithm")?;
+    ensure!(alg_oid == OID_SHA256, "timestamp imprint is not SHA-256");
+    let (hashed, _) = expect(hashed, TAG_OCTET_STRING, "hashedMessage")?;
+
+    // accuracy (SEQUENCE) and ordering (BOOLEAN) may precede the nonce.
+    let mut nonce = None;
+    while !rest.is_empty() {
+        let (tag, content, next) = tlv(rest)?;
+        if tag == TAG_INTEGER {
+            nonce = Some(uint_value(content).context("nonce")?);
+            break;
+        }
+        rest = next;
+    }
+
+    Ok(TimestampInfo {
+        time: rfc3339(gen_time)?,
+        serial: hex(serial),
+        imprint: hex(hashed),
+        nonce,
+    })
+}
+
+/// `YYYYMMDDHHMMSS[.fff]Z` -> `YYYY-MM-DDTHH:MM:SS[.fff]Z`.
+fn rfc3339(gen_time: &[u8]) -> Result<String> {
+    let s = std::str::from_utf8(gen_time).map_err(|_| anyhow!("genTime is not ASCII"))?;
+    let body = s
+        .strip_suffix('Z')
+        .ok_or_else(|| anyhow!("genTime '{}' is not UTC", s))?;
+    ensure!(
+        body.len() >= 14 && body[..14].bytes().all(|b| b.is_ascii_digit()),
+        "malformed genTime '{}'",
+        s
+    );
+    Ok(format!(
+        "{}-{}-{}T{}:{}:{}{}Z",
+        &body[0..4],
+        &body[4..6],
+        &body[6..8],
+        &body[8..10],
+        &body[10..12],
+        &body[12..14],
+        &body[14..]
+    ))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Encode one DER TLV.
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Minimal two's-complement content of a non-negative integer.
+fn uint_content(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    let mut out = bytes[skip..].to_vec();
+    if out[0] & 0x80 != 0 {
+        out.insert(0, 0);
+    }
+    out
+}
+
+fn uint_value(content: &[u8]) -> Result<u64> {
+    let trimmed = match content {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        other => other,
+    };
+    ensure!(
+        !trimmed.is_empty() && trimmed.len() <= 8 && content[0] & 0x80 == 0,
+        "integer out of range"
+    );
+    Ok(trimmed
+        .iter()
+        .fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+}
+
+/// Split one TLV off `input`: `(tag, content, rest)`.
+fn tlv(input: &[u8]) -> Result<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input
+        .split_first()
+        .ok_or_else(|| anyhow!("truncated DER"))?;
+    let (&first, mut rest) = rest.split_first().ok_or_else(|| anyhow!("truncated DER"))?;
+    let len = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let n = usize::from(first & 0x7f);
+        ensure!((1..=4).contains(&n) && rest.len() >= n, "bad DER length");
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+        rest = &rest[n..];
+        len
+    };
+    ensure!(rest.len() >= len, "truncated DER");
+    Ok((tag, &rest[..len], &rest[len..]))
+}
+
+fn expect<'a>(input: &'a [u8], tag: u8, what: &str) -> Result<(&'a [u8], &'a [u8])> {
+    let (found, content, rest) = tlv(input).with_context(|| format!("reading {}", what))?;
+    ensure!(
+        found == tag,
+        "expected {} (tag 0x{:02x}), found tag 0x{:02x}",
+        what,
+        tag,
+        found
+    );
+    Ok((content, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fake TSA: answers with a granted reply carrying an unsigned TSTInfo.
+    struct FakeTsa {
+        gen_time: &'static str,
+        echo_nonce: bool,
+    }
+
+    impl FakeTsa {
+        fn reply(&self, req: &TimestampRequest) -> Vec<u8> {
+            let algorithm = der(
+                TAG_SEQUENCE,
+                &[der(TAG_OID, OID_SHA256), der(TAG_NULL, &[])].concat(),
+            );
+            let mut tst = vec![
+                der(TAG_INTEGER, &[1]),
+                der(TAG_OID, &[0x2a, 0x03]),
+                der(
+                    TAG_SEQUENCE,
+                    &[algorithm, der(TAG_OCTET_STRING, &req.imprint)].concat(),
+                ),
+                der(TAG_INTEGER, &[0x01, 0x2c]),
+                der(TAG_GENERALIZED_TIME, self.gen_time.as_bytes()),
+                der(TAG_BOOLEAN, &[0]),
+            ];
+            if self.echo_nonce {
+                tst.push(der(TAG_INTEGER, &uint_content(req.nonce)));
+            }
+            let tst = der(TAG_SEQUENCE, &tst.concat());
+            let encap = der(
+                TAG_SEQUENCE,
+                &[
+                    der(TAG_OID, OID_TST_INFO),
+                    der(TAG_CONTEXT_0, &der(TAG_OCTET_STRING, &tst)),
+                ]
+                .concat(),
+            );
+            let signed_data = der(
+                TAG_SEQUENCE,
+                &[
+                    der(TAG_INTEGER, &[3]),
+                    der(0x31, &[]),
+                    encap,
+                    der(0x31, &[]),
+                ]
+                .concat(),
+            );
+            let token = der(
+                TAG_SEQUENCE,
+                &[
+                    der(TAG_OID, OID_SIGNED_DATA),
+                    der(TAG_CONTEXT_0, &signed_data),
+                ]
+                .concat(),
+            );
+            let status = der(TAG_SEQUENCE, &der(TAG_INTEGER, &[0]));
+            der(TAG_SEQUENCE, &[status, token].concat())
+        }
+    }
+
+    /// Stands in for a real signature check.
+    #[derive(Debug)]
+    struct Trust(bool);
+
+    impl TokenVerifier for Trust {
+        fn verify_token(&self, _: &[u8], _: &[u8; 32]) -> Result<()> {
+            ensure!(self.0, "signer is not a trusted TSA");
+            Ok(())
+        }
+    }
+
+    impl TimestampClient for FakeTsa {
+        fn submit(&self, request: &TimestampRequest) -> Result<Vec<u8>> {
+            Ok(self.reply(request))
+        }
+    }
+
+    #[test]
+    fn request_encodes_sha256_imprint_and_nonce() {
+        let req = TimestampRequest::new(&[7u8; 32], 0x80);
+        let der_bytes = req.to_der();
+        let (body, rest) = expect(&der_bytes, TAG_SEQUENCE, "req").unwrap();
+        assert!(rest.is_empty());
+        let (_, body) = expect(body, TAG_INTEGER, "version").unwrap();
+        let (imprint_seq, body) = expect(body, TAG_SEQUENCE, "imprint").unwrap();
+        let (nonce, body) = expect(body, TAG_INTEGER, "nonce").unwrap();
+        assert_eq!(nonce, [0x00, 0x80]);
+        assert_eq!(body, der(TAG_BOOLEAN, &[0xff]));
+        let (_, hashed) = expect(imprint_seq, TAG_SEQUENCE, "alg").unwrap();
+        let (hashed, _) = expect(hashed, TAG_OCTET_STRING, "hash").unwrap();
+        assert_eq!(hashed, imprint(&[7u8; 32]));
+    }
+
+    #[test]
+    fn stamp_and_check_bind_the_proof_digest() {
+        let d = [0x42u8; 32];
+        let tsa = FakeTsa {
+            gen_time: "20261017093000.5Z",
+            echo_nonce: true,
+        };
+        let (response, info) = stamp(&tsa, &d, 0xdead_beef).unwrap();
+        assert_eq!(info.time, "2026-10-17T09:30:00.5Z");
+        assert_eq!(info.serial, "012c");
+        assert_eq!(info.nonce, Some(0xdead_beef));
+        assert_eq!(check_response(&response, &d, &Trust(true)).unwrap(), info);
+        let err = check_response(&response, &d, &Trust(false)).unwrap_err();
+        assert!(format!("{err:#}").contains("not a trusted TSA"), "{err:#}");
+
+        let err = read_response(&response, &[0x43u8; 32]).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+        assert!(read_response(&response[..response.len() - 1], &d).is_err());
+
+        let no_nonce = FakeTsa {
+            echo_nonce: false,
+            ..tsa
+        };
+        assert!(stamp(&no_nonce, &d, 1).is_err());
+    }
+}
@@ -1,6 +1,19 @@
-//! Trace shape derived from AIR and/or profile hints.
+//! Trace shape derived from AIR and/or profile hints, the binary columnar
+//! trace file format, and a row-by-row [`TraceBuilder`].
 
-use crate::air::AirProgram;
+pub mod builder;
+pub mod columnar;
+pub mod eval;
+pub mod incremental;
+pub mod memory;
+
+pub use builder::TraceBuilder;
+pub use columnar::{Trace, TraceCompression};
+pub use incremental::{Reuse, TraceCache};
+
+use anyhow::{bail, Result};
+
+use crate::air::{AirColumns, AirIr, AirProgram};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TraceShape {
@@ -12,14 +25,60 @@ pub struct TraceShape {
 
 impl TraceShape {
     /// Derive a conservative TraceShape from an AIR program.
-    /// If rows_hint is missing, default to 2^16 for Phase-0 demos.
+    /// If rows_hint is missing (or an expression not yet evaluated against
+    /// public inputs), default to 2^16 for Phase-0 demos.
     pub fn from_air(air: &AirProgram) -> Self {
-        let rows = air.rows_hint.unwrap_or(1 << 16);
+        Self::from_parts(air.fixed_rows(), &air.columns)
+    }
+
+    /// Same as [`TraceShape::from_air`], for the parsed IR.
+    pub fn from_ir(ir: &AirIr) -> Self {
+        Self::from_parts(ir.fixed_rows(), &ir.columns)
+    }
+
+    fn from_parts(rows_hint: Option<u32>, columns: &AirColumns) -> Self {
         Self {
-            rows,
-            cols: air.columns.trace_cols,
-            const_cols: air.columns.const_cols,
-            periodic_cols: air.columns.periodic_cols,
+            rows: rows_hint.unwrap_or(1 << 16),
+            cols: columns.trace_cols,
+            const_cols: columns.const_cols,
+            periodic_cols: columns.periodic_cols,
+        }
+    }
+}
+
+impl Trace {
+    /// Check that the trace fits `ir` before proving from it: internally
+    /// consistent, in the AIR's field, `trace_cols` columns, a non-zero power
+    /// of two rows, and boolean selector columns. Constraints are not
+    /// evaluated (see [`eval`]).
+    pub fn check_shape(&self, ir: &AirIr) -> Result<()> {
+        self.validate()?;
+        if self.field != ir.meta.field {
+            bail!(
+                "trace field '{}' does not match AIR field '{}'",
+                self.field,
+                ir.meta.field
+            );
+        }
+        if self.cols() != ir.columns.trace_cols {
+            bail!(
+                "trace has {} columns, AIR declares {}",
+                self.cols(),
+                ir.columns.trace_cols
+            );
+        }
+        if self.rows == 0 || !self.rows.is_power_of_two() {
+            bail!(
+                "trace rows must be a non-zero power of two, got {}",
+                self.rows
+            );
+        }
+        let layout = ir.column_layout()?;
+        for (col, name) in layout.selectors().zip(&ir.columns.selectors) {
+            if let Some(row) = self.first_non_boolean(col as u32) {
+                bail!("selector '{}' is not boolean at row {}", name, row);
+            }
         }
+        Ok(())
     }
 }
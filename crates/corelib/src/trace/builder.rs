@@ -0,0 +1,138 @@
+//! Row-by-row construction of a [`Trace`].
+//!
+//! Witness generators fill one row per execution step, either positionally
+//! ([`TraceBuilder::push_row`]) or by the AIR's column names
+//! ([`TraceBuilder::push_named_row`]). [`TraceBuilder::build_for`] checks the
+//! result against the AIR with [`Trace::check_shape`] before it is handed to
+//! [`crate::prove_with_trace`] or written as a `.zkt` file.
+
+use anyhow::{anyhow, ensure, Result};
+use num_bigint::BigUint;
+
+use super::Trace;
+use crate::air::expr::ColumnLayout;
+use crate::air::AirIr;
+use crate::crypto::field::field_by_id;
+
+/// Accumulates trace rows, column-major, for one field.
+#[derive(Debug, Clone)]
+pub struct TraceBuilder {
+    field: String,
+    /// `None` for fields corelib has no arithmetic for; values are then
+    /// only bounded by the 256-bit element width.
+    modulus: Option<BigUint>,
+    layout: Option<ColumnLayout>,
+    columns: Vec<Vec<BigUint>>,
+}
+
+impl TraceBuilder {
+    /// An empty trace of `cols` columns over `field`.
+    pub fn new(field: impl Into<String>, cols: u32) -> Self {
+        let field = field.into();
+        Self {
+            modulus: field_by_id(&field).map(|f| f.modulus()),
+            field,
+            layout: None,
+            columns: vec![Vec::new(); cols as usize],
+        }
+    }
+
+    /// An empty trace in the AIR's field with its `trace_cols` columns,
+    /// addressable by the AIR's column names.
+    pub fn for_air(ir: &AirIr) -> Result<Self> {
+        let mut builder = Self::new(ir.meta.field.clone(), ir.columns.trace_cols);
+        builder.layout = Some(ir.column_layout()?);
+        Ok(builder)
+    }
+
+    pub fn cols(&self) -> u32 {
+        self.columns.len() as u32
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.columns.first().map_or(0, |c| c.len() as u32)
+    }
+
+    /// Append a row holding one value per column, in column order.
+    pub fn push_row<I, V>(&mut self, values: I) -> Result<()>
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<BigUint>,
+    {
+        let row: Vec<BigUint> = values.into_iter().map(Into::into).collect();
+        ensure!(
+            row.len() == self.columns.len(),
+            "trace row {} has {} values, expected {}",
+            self.rows(),
+            row.len(),
+            self.columns.len()
+        );
+        for (col, value) in row.iter().enumerate() {
+            self.check_element(col, value)?;
+        }
+        for (column, value) in self.columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+        Ok(())
+    }
+
+    /// Append a row from `(column name, value)` pairs; columns not named
+    /// are zero. Needs a builder from [`Self::for_air`].
+    pub fn push_named_row<'a, I, V>(&mut self, values: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (&'a str, V)>,
+        V: Into<BigUint>,
+    {
+        let layout = self
+            .layout
+            .as_ref()
+            .ok_or_else(|| anyhow!("trace builder has no AIR column names"))?;
+        let mut row = vec![BigUint::default(); self.columns.len()];
+        let mut set = vec![false; self.columns.len()];
+        for (name, value) in values {
+            let col = layout
+                .column(name)
+                .ok_or_else(|| anyhow!("AIR declares no column '{}'", name))?;
+            ensure!(
+                !std::mem::replace(&mut set[col], true),
+                "column '{}' set twice in trace row {}",
+                name,
+                self.rows()
+            );
+            row[col] = value.into();
+        }
+        self.push_row(row)
+    }
+
+    /// The trace built so far.
+    pub fn build(self) -> Result<Trace> {
+        Trace::from_biguint_columns(self.field, &self.columns)
+    }
+
+    /// [`Self::build`], then [`Trace::check_shape`] against `ir`.
+    pub fn build_for(self, ir: &AirIr) -> Result<Trace> {
+        let trace = self.build()?;
+        trace.check_shape(ir)?;
+        Ok(trace)
+    }
+
+    fn check_element(&self, col: usize, value: &BigUint) -> Result<()> {
+        let row = self.rows();
+        match &self.modulus {
+            Some(p) => ensure!(
+                value < p,
+                "trace value at row {} column {} is not a canonical {} element",
+                row,
+                col,
+                self.field
+            ),
+            None => ensure!(
+                value.bits() <= 256,
+                "trace value at row {} column {} exceeds 256 bits",
+                row,
+                col
+            ),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,430 @@
+//! Compact binary columnar trace format (`.zkt`).
+//!
+//! Layout (little endian):
+//! 0..4    MAGIC "ZKTR"
+//! 4..6    VERSION (u16)
+//! 6       compression (u8: 0 = none, 1 = rle)
+//! 7       elem_width (u8: bytes per element, 1..=32)
+//! 8..12   rows (u32)
+//! 12..16  cols (u32)
+//! 16..18  field_id_len (u16), followed by the UTF-8 field id
+//! then, per column: data_len (u64) followed by `data_len` bytes
+//! trailer: BLAKE3 checksum (32 bytes) over everything before it
+//!
+//! Column data holds `rows` elements of `elem_width` bytes each. With RLE
+//! compression the column is a sequence of `(run_len u32, element)` pairs.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use num_bigint::BigUint;
+use num_traits::Num;
+use serde_json::{json, Value};
+
+use crate::crypto::registry::hash32_by_id;
+
+pub const TRACE_MAGIC: [u8; 4] = *b"ZKTR";
+pub const TRACE_VERSION: u16 = 1;
+const HEADER_LEN: usize = 18;
+const CHECKSUM_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceCompression {
+    None,
+    Rle,
+}
+
+impl TraceCompression {
+    fn to_u8(self) -> u8 {
+        match self {
+            TraceCompression::None => 0,
+            TraceCompression::Rle => 1,
+        }
+    }
+
+    fn from_u8(b: u8) -> Result<Self> {
+        match b {
+            0 => Ok(TraceCompression::None),
+            1 => Ok(TraceCompression::Rle),
+            other => Err(anyhow!("unknown trace compression {}", other)),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(TraceCompression::None),
+            "rle" => Ok(TraceCompression::Rle),
+            other => Err(anyhow!("unknown trace compression '{}'", other)),
+        }
+    }
+}
+
+/// Execution trace stored column-major as fixed-width little-endian elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub field: String,
+    pub elem_width: u8,
+    pub rows: u32,
+    /// One entry per column, each `rows * elem_width` bytes.
+    pub columns: Vec<Vec<u8>>,
+}
+
+impl Trace {
+    /// Build a trace from u64 columns (8-byte elements). All columns must have equal length.
+    pub fn from_u64_columns(field: impl Into<String>, columns: &[Vec<u64>]) -> Result<Self> {
+        let rows = columns.first().map(|c| c.len()).unwrap_or(0);
+        ensure!(
+            columns.iter().all(|c| c.len() == rows),
+            "trace columns must have equal length"
+        );
+        let trace = Self {
+            field: field.into(),
+            elem_width: 8,
+            rows: u32::try_from(rows).map_err(|_| anyhow!("too many trace rows"))?,
+            columns: columns
+                .iter()
+                .map(|c| c.iter().flat_map(|v| v.to_le_bytes()).collect())
+                .collect(),
+        };
+        Ok(trace)
+    }
+
+    /// Build a trace from arbitrary-precision columns, using the smallest
+    /// element width that fits every value.
+    pub fn from_biguint_columns(
+        field: impl Into<String>,
+        columns: &[Vec<BigUint>],
+    ) -> Result<Self> {
+        let rows = columns.first().map(|c| c.len()).unwrap_or(0);
+        ensure!(
+            columns.iter().all(|c| c.len() == rows),
+            "trace columns must have equal length"
+        );
+        let max_bits = columns
+            .iter()
+            .flatten()
+            .map(|v| v.bits())
+            .max()
+            .unwrap_or(0);
+        let width = max_bits.div_ceil(8).max(1);
+        ensure!(width <= 32, "trace element exceeds 256 bits");
+        let width = width as usize;
+        let columns = columns
+            .iter()
+            .map(|c| {
+                c.iter()
+                    .flat_map(|v| {
+                        let mut bytes = v.to_bytes_le();
+                        bytes.resize(width, 0);
+                        bytes
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            field: field.into(),
+            elem_width: width as u8,
+            rows: u32::try_from(rows).map_err(|_| anyhow!("too many trace rows"))?,
+            columns,
+        })
+    }
+
+    pub fn cols(&self) -> u32 {
+        self.columns.len() as u32
+    }
+
+    /// Element at (`row`, `col`) as a big integer.
+    pub fn get(&self, row: u32, col: u32) -> Option<BigUint> {
+        let w = self.elem_width as usize;
+        let column = self.columns.get(col as usize)?;
+        let start = (row as usize).checked_mul(w)?;
+        column.get(start..start + w).map(BigUint::from_bytes_le)
+    }
+
+    /// First row whose element in `col` is neither 0 nor 1, if any.
+    pub fn first_non_boolean(&self, col: u32) -> Option<u32> {
+        let column = self.columns.get(col as usize)?;
+        column
+            .chunks_exact(self.elem_width as usize)
+            .position(|e| e[0] > 1 || e[1..].iter().any(|&b| b != 0))
+            .map(|row| row as u32)
+    }
+
+    /// Check internal consistency (widths, lengths, field id).
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            !self.field.trim().is_empty(),
+            "trace field id cannot be empty"
+        );
+        ensure!(
+            (1..=32).contains(&self.elem_width),
+            "trace elem_width {} out of range [1..=32]",
+            self.elem_width
+        );
+        let expected = self.rows as usize * self.elem_width as usize;
+        for (i, c) in self.columns.iter().enumerate() {
+            ensure!(
+                c.len() == expected,
+                "trace column {} has {} bytes, expected {}",
+                i,
+                c.len(),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    /// Serialize into the `.zkt` binary format.
+    pub fn encode(&self, compression: TraceCompression) -> Result<Vec<u8>> {
+        self.validate()?;
+        let field = self.field.as_bytes();
+        let field_len = u16::try_from(field.len()).map_err(|_| anyhow!("field id too long"))?;
+        let mut out = Vec::with_capacity(HEADER_LEN + field.len() + CHECKSUM_LEN);
+        out.extend_from_slice(&TRACE_MAGIC);
+        out.extend_from_slice(&TRACE_VERSION.to_le_bytes());
+        out.push(compression.to_u8());
+        out.push(self.elem_width);
+        out.extend_from_slice(&self.rows.to_le_bytes());
+        out.extend_from_slice(&self.cols().to_le_bytes());
+        out.extend_from_slice(&field_len.to_le_bytes());
+        out.extend_from_slice(field);
+        for column in &self.columns {
+            let data = match compression {
+                TraceCompression::None => column.clone(),
+                TraceCompression::Rle => rle_encode(column, self.elem_width as usize),
+            };
+            out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            out.extend_from_slice(&data);
+        }
+        let checksum = blake3::hash(&out);
+        out.extend_from_slice(checksum.as_bytes());
+        Ok(out)
+    }
+
+    /// Parse a `.zkt` file, verifying the checksum and shape.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+            bail!("trace file too short");
+        }
+        let (payload, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if blake3::hash(payload).as_bytes() != checksum {
+            bail!("trace checksum mismatch");
+        }
+        if payload[0..4] != TRACE_MAGIC {
+            bail!("bad trace magic");
+        }
+        let version = u16::from_le_bytes([payload[4], payload[5]]);
+        if version != TRACE_VERSION {
+            bail!("unsupported trace version {version}");
+        }
+        let compression = TraceCompression::from_u8(payload[6])?;
+        let elem_width = payload[7];
+        let rows = u32::from_le_bytes(payload[8..12].try_into().unwrap());
+        let cols = u32::from_le_bytes(payload[12..16].try_into().unwrap());
+        let field_len = u16::from_le_bytes([payload[16], payload[17]]) as usize;
+
+        let mut cursor = HEADER_LEN;
+        let field = payload
+            .get(cursor..cursor + field_len)
+            .ok_or_else(|| anyhow!("trace file truncated in field id"))?;
+        let field = String::from_utf8(field.to_vec()).map_err(|_| anyhow!("field id not UTF-8"))?;
+        cursor += field_len;
+
+        let mut columns = Vec::with_capacity(cols as usize);
+        for i in 0..cols {
+            let len_bytes = payload
+                .get(cursor..cursor + 8)
+                .ok_or_else(|| anyhow!("trace file truncated at column {}", i))?;
+            let len = usize::try_from(u64::from_le_bytes(len_bytes.try_into().unwrap()))
+                .map_err(|_| anyhow!("column {} too large", i))?;
+            cursor += 8;
+            let data = payload
+                .get(cursor..cursor.saturating_add(len))
+                .ok_or_else(|| anyhow!("trace file truncated in column {}", i))?;
+            cursor += len;
+            let column = match compression {
+                TraceCompression::None => data.to_vec(),
+                TraceCompression::Rle => rle_decode(data, elem_width as usize, rows)?,
+            };
+            columns.push(column);
+        }
+        ensure!(
+            cursor == payload.len(),
+            "trailing bytes after trace columns"
+        );
+
+        let trace = Self {
+            field,
+            elem_width,
+            rows,
+            columns,
+        };
+        trace.validate()?;
+        Ok(trace)
+    }
+
+    /// Read either form: `.zkt` bytes (by their magic) or the JSON form.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.starts_with(&TRACE_MAGIC) {
+            return Self::decode(bytes);
+        }
+        let value: Value = serde_json::from_slice(bytes)
+            .map_err(|e| anyhow!("trace is neither .zkt nor JSON: {}", e))?;
+        Self::from_json(&value)
+    }
+
+    /// JSON form: `{"field": "...", "columns": [["0x..", ...], ...]}`.
+    /// Elements are emitted as minimal hex strings.
+    pub fn to_json(&self) -> Value {
+        let columns: Vec<Vec<String>> = (0..self.cols())
+            .map(|c| {
+                (0..self.rows)
+                    .map(|r| format!("0x{}", self.get(r, c).unwrap_or_default().to_str_radix(16)))
+                    .collect()
+            })
+            .collect();
+        json!({ "field": self.field, "columns": columns })
+    }
+
+    /// Parse the JSON form. Elements may be JSON numbers, decimal strings or
+    /// `0x`-prefixed hex strings.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let field = value
+            .get("field")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("trace JSON missing string 'field'"))?;
+        let columns = value
+            .get("columns")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("trace JSON missing array 'columns'"))?;
+        let mut parsed = Vec::with_capacity(columns.len());
+        for (c, column) in columns.iter().enumerate() {
+            let column = column
+                .as_array()
+                .ok_or_else(|| anyhow!("trace column {} is not an array", c))?;
+            let mut values = Vec::with_capacity(column.len());
+            for (r, v) in column.iter().enumerate() {
+                values.push(
+                    parse_element(v)
+                        .ok_or_else(|| anyhow!("invalid element at row {} column {}", r, c))?,
+                );
+            }
+            parsed.push(values);
+        }
+        Self::from_biguint_columns(field, &parsed)
+    }
+
+    /// 32-byte commitment to the trace contents under the given hash id:
+    /// H("TRACE.ROOT", field || rows || cols || H("TRACE.COL", col_0) || ...).
+    pub fn commitment(&self, hash_id: &str) -> Result<[u8; 32]> {
+        let digests = self
+            .columns
+            .iter()
+            .map(|column| column_digest(hash_id, column))
+            .collect::<Result<Vec<_>>>()?;
+        self.commitment_from_digests(hash_id, &digests)
+    }
+
+    /// [`Self::commitment`] from precomputed per-column digests
+    /// (see [`column_digest`]), one per column in order.
+    pub fn commitment_from_digests(&self, hash_id: &str, digests: &[[u8; 32]]) -> Result<[u8; 32]> {
+        ensure!(
+            digests.len() == self.columns.len(),
+            "{} column digests for {} columns",
+            digests.len(),
+            self.columns.len()
+        );
+        let mut buf = Vec::with_capacity(16 + self.field.len() + 32 * digests.len());
+        buf.extend_from_slice(self.field.as_bytes());
+        buf.extend_from_slice(&self.rows.to_le_bytes());
+        buf.extend_from_slice(&self.cols().to_le_bytes());
+        for digest in digests {
+            buf.extend_from_slice(digest);
+        }
+        hash32_by_id(hash_id, "TRACE.ROOT", &buf)
+            .ok_or_else(|| anyhow!("unsupported hash id '{}'", hash_id))
+    }
+}
+
+/// H("TRACE.COL", column bytes), the per-column leaf of [`Trace::commitment`].
+pub fn column_digest(hash_id: &str, column: &[u8]) -> Result<[u8; 32]> {
+    hash32_by_id(hash_id, "TRACE.COL", column)
+        .ok_or_else(|| anyhow!("unsupported hash id '{}'", hash_id))
+}
+
+fn parse_element(v: &Value) -> Option<BigUint> {
+    match v {
+        Value::Number(n) => n.as_u64().map(BigUint::from),
+        Value::String(s) => {
+            let s = s.trim();
+            match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(hex) => BigUint::from_str_radix(hex, 16).ok(),
+                None => BigUint::from_str_radix(s, 10).ok(),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn rle_encode(column: &[u8], width: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut elems = column.chunks_exact(width).peekable();
+    while let Some(elem) = elems.next() {
+        let mut run: u32 = 1;
+        while run < u32::MAX && elems.peek() == Some(&elem) {
+            elems.next();
+            run += 1;
+        }
+        out.extend_from_slice(&run.to_le_bytes());
+        out.extend_from_slice(elem);
+    }
+    out
+}
+
+fn rle_decode(data: &[u8], width: usize, rows: u32) -> Result<Vec<u8>> {
+    ensure!(width > 0, "trace elem_width must be > 0");
+    let expected = rows as usize * width;
+    let mut out = Vec::with_capacity(expected);
+    for pair in data.chunks(4 + width) {
+        ensure!(pair.len() == 4 + width, "truncated RLE run");
+        let run = u32::from_le_bytes(pair[0..4].try_into().unwrap()) as usize;
+        ensure!(
+            out.len() + run * width <= expected,
+            "RLE column longer than declared rows"
+        );
+        for _ in 0..run {
+            out.extend_from_slice(&pair[4..]);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Trace {
+        Trace::from_u64_columns(
+            "Prime254",
+            &[vec![0, 0, 0, 0, 1, 1, 2, 3], vec![7, 7, 7, 7, 7, 7, 7, 7]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn roundtrip_plain_and_rle() {
+        let t = sample();
+        for c in [TraceCompression::None, TraceCompression::Rle] {
+            let bytes = t.encode(c).unwrap();
+            assert_eq!(Trace::decode(&bytes).unwrap(), t);
+        }
+        let plain = t.encode(TraceCompression::None).unwrap();
+        let rle = t.encode(TraceCompression::Rle).unwrap();
+        assert!(rle.len() < plain.len());
+    }
+
+    #[test]
+    fn checksum_detects_corruption() {
+        let mut bytes = sample().encode(TraceCompression::None).unwrap();
+        bytes[20] ^= 1;
+        assert!(Trace::decode(&bytes).is_err());
+    }
+}
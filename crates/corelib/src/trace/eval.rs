@@ -0,0 +1,136 @@
+//! Reference evaluation of lowered transition constraints over a [`Trace`].
+//!
+//! Every constraint is evaluated on each `r -> r + 1` row pair modulo the
+//! field prime; a non-zero result is a violation. Used by the native backend
+//! at prove time and by `zkd trace-dump` and `zkd check`.
+
+use anyhow::{bail, Result};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use super::Trace;
+use crate::air::AirIr;
+use crate::crypto::field::field_by_id;
+
+/// A transition constraint that does not hold on the row pair `row -> row + 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Index into the lowered constraints (`AirIr::transition_constraints`).
+    pub constraint: usize,
+    pub row: u32,
+    /// Rendering of the lowered constraint, without the trailing `= 0`.
+    pub display: String,
+    /// Cells the constraint reads on this row pair with their reduced values,
+    /// in first-use order, e.g. `("next.a", 5)`.
+    pub cells: Vec<(String, BigUint)>,
+}
+
+impl Violation {
+    /// Columns the constraint reads, in first-use order without the
+    /// `cur.` / `next.` prefix, e.g. `["balance", "s_dbl"]`.
+    pub fn columns(&self) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for (cell, _) in &self.cells {
+            let name = cell.split_once('.').map_or(cell.as_str(), |(_, n)| n);
+            if !out.iter().any(|seen| seen == name) {
+                out.push(name.to_string());
+            }
+        }
+        out
+    }
+}
+
+/// Prime modulus for a field id.
+pub fn field_modulus(field: &str) -> Result<BigUint> {
+    match field_by_id(field) {
+        Some(f) => Ok(f.modulus()),
+        None => bail!("no constraint interpreter for field '{}'", field),
+    }
+}
+
+/// All values of `row`, reduced modulo `p`.
+pub fn row_values(trace: &Trace, row: u32, p: &BigUint) -> Vec<BigUint> {
+    (0..trace.cols())
+        .map(|col| trace.get(row, col).unwrap_or_default() % p)
+        .collect()
+}
+
+/// Violations in row order (then constraint order), stopping after `limit`
+/// when one is given.
+pub fn violations(trace: &Trace, ir: &AirIr, limit: Option<usize>) -> Result<Vec<Violation>> {
+    let constraints = ir.transition_constraints()?;
+    let mut out = Vec::new();
+    if constraints.is_empty() || trace.rows < 2 {
+        return Ok(out);
+    }
+    let layout = ir.column_layout()?;
+    let p = field_modulus(&ir.meta.field)?;
+    let mut next = row_values(trace, 0, &p);
+    for row in 0..trace.rows - 1 {
+        let cur = std::mem::replace(&mut next, row_values(trace, row + 1, &p));
+        for (index, c) in constraints.iter().enumerate() {
+            if c.expr.eval(&cur, &next, &p).is_zero() {
+                continue;
+            }
+            let cells = c
+                .expr
+                .cells()
+                .into_iter()
+                .map(|cell| {
+                    let value = cell.eval(&cur, &next, &p);
+                    (cell.to_named_string(layout.names()), value)
+                })
+                .collect();
+            out.push(Violation {
+                constraint: index,
+                row,
+                display: c.display.clone(),
+                cells,
+            });
+            if limit.is_some_and(|l| out.len() >= l) {
+                return Ok(out);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// A boundary assertion whose cell holds a different value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryViolation {
+    /// Index into `AirIr::boundary`.
+    pub assertion: usize,
+    pub row: u32,
+    /// Rendering of the assertion, e.g. `acc[last] = public.total`.
+    pub display: String,
+    pub expected: BigUint,
+    pub actual: BigUint,
+}
+
+/// Boundary assertions (`[[boundary]]`) that do not hold on `trace`, with
+/// public references read from `inputs`.
+pub fn boundary_violations(
+    trace: &Trace,
+    ir: &AirIr,
+    inputs: &serde_json::Value,
+) -> Result<Vec<BoundaryViolation>> {
+    if ir.boundary.is_empty() {
+        return Ok(Vec::new());
+    }
+    let p = field_modulus(&ir.meta.field)?;
+    let mut out = Vec::new();
+    for (index, a) in ir.boundary_assertions(trace.rows)?.into_iter().enumerate() {
+        let expected = a.resolve(inputs, &p)?;
+        let actual = trace.get(a.row, a.column as u32).unwrap_or_default() % &p;
+        if expected != actual {
+            out.push(BoundaryViolation {
+                assertion: index,
+                row: a.row,
+                display: a.display,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,219 @@
+//! Experimental incremental re-proving: keep the last proven trace and its
+//! per-column digests, so the next prove over a trace that differs in a few
+//! columns (typically after a small public input change) rehashes only those.
+//!
+//! The commitment is identical to [`Trace::commitment`]; only the work to get
+//! there shrinks. A cache built for another field, shape or hash id is simply
+//! not reused. Backends opt in through `Capabilities::incremental`; others
+//! prove from scratch (see [`crate::prove_incremental`]).
+//!
+//! Cache file layout (little endian):
+//! 0..4    MAGIC "ZKTC"
+//! 4..6    VERSION (u16)
+//! 6..8    hash_id_len (u16), followed by the UTF-8 hash id
+//! then    digest count (u32) and that many 32-byte column digests
+//! rest    the cached trace as an uncompressed `.zkt` file
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::Serialize;
+
+use super::columnar::{column_digest, Trace, TraceCompression};
+
+const MAGIC: &[u8; 4] = b"ZKTC";
+const VERSION: u16 = 1;
+
+/// How much of a commitment came from the cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Reuse {
+    pub columns: u32,
+    pub reused: u32,
+}
+
+impl Reuse {
+    /// Nothing reused: a full prove over `cols` columns.
+    pub fn none(cols: u32) -> Self {
+        Self {
+            columns: cols,
+            reused: 0,
+        }
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.columns == 0 {
+            return 0.0;
+        }
+        100.0 * f64::from(self.reused) / f64::from(self.columns)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TraceCache {
+    hash_id: String,
+    trace: Option<Trace>,
+    digests: Vec<[u8; 32]>,
+}
+
+impl TraceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the cache at `path`; a missing file is an empty cache.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Self::decode(&bytes).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(e).with_context(|| format!("read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.encode()?).with_context(|| format!("write {}", path.display()))
+    }
+
+    /// Commitment to `trace` under `hash_id`, rehashing only columns that
+    /// differ from the cached trace. The cache then holds `trace`.
+    pub fn commit(&mut self, trace: &Trace, hash_id: &str) -> Result<([u8; 32], Reuse)> {
+        let cached = self
+            .trace
+            .as_ref()
+            .filter(|t| self.hash_id == hash_id && compatible(t, trace));
+        let mut digests = Vec::with_capacity(trace.columns.len());
+        let mut reused = 0;
+        for (i, column) in trace.columns.iter().enumerate() {
+            match cached {
+                Some(t) if t.columns[i] == *column => {
+                    digests.push(self.digests[i]);
+                    reused += 1;
+                }
+                _ => digests.push(column_digest(hash_id, column)?),
+            }
+        }
+        let commitment = trace.commitment_from_digests(hash_id, &digests)?;
+        self.hash_id = hash_id.to_string();
+        self.trace = Some(trace.clone());
+        self.digests = digests;
+        Ok((
+            commitment,
+            Reuse {
+                columns: trace.cols(),
+                reused,
+            },
+        ))
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        let hash = self.hash_id.as_bytes();
+        out.extend_from_slice(&u16::try_from(hash.len())?.to_le_bytes());
+        out.extend_from_slice(hash);
+        out.extend_from_slice(&u32::try_from(self.digests.len())?.to_le_bytes());
+        for digest in &self.digests {
+            out.extend_from_slice(digest);
+        }
+        if let Some(trace) = &self.trace {
+            out.extend_from_slice(&trace.encode(TraceCompression::None)?);
+        }
+        Ok(out)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut at = 0usize;
+        let mut take = |n: usize| -> Result<&[u8]> {
+            let end = at.checked_add(n).filter(|&end| end <= bytes.len());
+            let Some(end) = end else {
+                bail!("trace cache truncated");
+            };
+            let slice = &bytes[at..end];
+            at = end;
+            Ok(slice)
+        };
+        ensure!(take(4)? == MAGIC, "not a trace cache (bad magic)");
+        let version = u16::from_le_bytes(take(2)?.try_into()?);
+        ensure!(
+            version == VERSION,
+            "unsupported trace cache version {}",
+            version
+        );
+        let hash_len = u16::from_le_bytes(take(2)?.try_into()?) as usize;
+        let hash_id = String::from_utf8(take(hash_len)?.to_vec())?;
+        let count = u32::from_le_bytes(take(4)?.try_into()?) as usize;
+        let digests = (0..count)
+            .map(|_| Ok(take(32)?.try_into()?))
+            .collect::<Result<Vec<[u8; 32]>>>()?;
+        let rest = &bytes[at..];
+        let trace = if rest.is_empty() {
+            None
+        } else {
+            Some(Trace::decode(rest)?)
+        };
+        ensure!(
+            trace.as_ref().map_or(0, |t| t.columns.len()) == digests.len(),
+            "trace cache holds {} digests for {} columns",
+            digests.len(),
+            trace.as_ref().map_or(0, |t| t.columns.len())
+        );
+        Ok(Self {
+            hash_id,
+            trace,
+            digests,
+        })
+    }
+}
+
+fn compatible(cached: &Trace, trace: &Trace) -> bool {
+    cached.field == trace.field
+        && cached.elem_width == trace.elem_width
+        && cached.rows == trace.rows
+        && cached.columns.len() == trace.columns.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(last: u64) -> Trace {
+        Trace::from_u64_columns(
+            "Prime254",
+            &[vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![0, 0, 0, last]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reuses_unchanged_columns_and_matches_full_commitment() {
+        let mut cache = TraceCache::new();
+        let (first, reuse) = cache.commit(&trace(1), "blake3").unwrap();
+        assert_eq!(first, trace(1).commitment("blake3").unwrap());
+        assert_eq!(reuse, Reuse::none(3));
+
+        let (second, reuse) = cache.commit(&trace(2), "blake3").unwrap();
+        assert_eq!(second, trace(2).commitment("blake3").unwrap());
+        assert_eq!(reuse.reused, 2);
+        assert!((reuse.percent() - 66.666).abs() < 0.01);
+
+        // Another hash id invalidates every cached digest.
+        let (_, reuse) = cache.commit(&trace(2), "poseidon2").unwrap();
+        assert_eq!(reuse.reused, 0);
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let mut cache = TraceCache::new();
+        cache.commit(&trace(1), "blake3").unwrap();
+        let mut restored = TraceCache::decode(&cache.encode().unwrap()).unwrap();
+        let (_, reuse) = restored.commit(&trace(1), "blake3").unwrap();
+        assert_eq!(reuse.reused, 3);
+
+        assert!(TraceCache::decode(&TraceCache::new().encode().unwrap())
+            .unwrap()
+            .trace
+            .is_none());
+        assert!(TraceCache::decode(b"ZKTC\x01").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Pre-flight estimate of prover memory, checked before any trace is built.
+//!
+//! The estimate is deliberately coarse: the main trace (`rows × columns ×
+//! field size`), its low-degree extension (`× fri_blowup`), and the Merkle tree
+//! committed over the extended rows (leaf and node digests, 32 bytes each).
+//! It exists to fail fast with actionable advice instead of being OOM-killed
+//! halfway through a prove.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::errors::ProveError;
+use crate::profile::{self, Profile};
+use crate::trace::TraceShape;
+
+/// Blowup assumed when a profile leaves `fri_blowup` unset.
+pub const DEFAULT_BLOWUP: u32 = 8;
+/// Merkle arity assumed when a profile leaves `merkle_arity` unset.
+pub const DEFAULT_MERKLE_ARITY: u32 = 2;
+const DIGEST_BYTES: u64 = 32;
+const MIB: u64 = 1 << 20;
+
+/// Bytes needed to store one element of `field`; unknown fields are assumed
+/// to be 256-bit.
+pub fn field_bytes(field: &str) -> u64 {
+    match field {
+        "Goldilocks" => 8,
+        "BabyBear" => 4,
+        _ => 32,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEstimate {
+    /// Main, constant and periodic columns over the base domain.
+    pub trace_bytes: u64,
+    /// The same columns over the `fri_blowup`-times larger evaluation domain.
+    pub lde_bytes: u64,
+    /// Leaf and internal node digests of the LDE commitment.
+    pub merkle_bytes: u64,
+}
+
+impl MemoryEstimate {
+    pub fn new(shape: &TraceShape, field: &str, profile: &Profile) -> Self {
+        let cols =
+            u64::from(shape.cols) + u64::from(shape.const_cols) + u64::from(shape.periodic_cols);
+        let trace_bytes = u64::from(shape.rows)
+            .saturating_mul(cols)
+            .saturating_mul(field_bytes(field));
+        let blowup = u64::from(profile.fri_blowup.unwrap_or(DEFAULT_BLOWUP));
+        let arity = u64::from(profile.merkle_arity.unwrap_or(DEFAULT_MERKLE_ARITY)).max(2);
+        let leaves = u64::from(shape.rows).saturating_mul(blowup);
+        // A full `arity`-ary tree over `leaves` has about leaves / (arity - 1)
+        // internal nodes.
+        let nodes = leaves.saturating_add(leaves / (arity - 1));
+        Self {
+            trace_bytes,
+            lde_bytes: trace_bytes.saturating_mul(blowup),
+            merkle_bytes: nodes.saturating_mul(DIGEST_BYTES),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.trace_bytes
+            .saturating_add(self.lde_bytes)
+            .saturating_add(self.merkle_bytes)
+    }
+}
+
+/// Estimate the memory proving `shape` under `config` needs and compare it
+/// against `limit_bytes`.
+///
+/// On overflow, returns [`ProveError::MemoryBudget`] whose hint lists the
+/// changes that would fit: another profile, a smaller `fri_blowup` override,
+/// or fewer rows.
+pub fn check_memory_budget(
+    config: &Config,
+    shape: &TraceShape,
+    limit_bytes: u64,
+) -> Result<MemoryEstimate, ProveError> {
+    let profile = profile::resolve_profile(&config.profile_id, &config.profile_overrides)?;
+    let estimate = MemoryEstimate::new(shape, &config.field, &profile);
+    if estimate.total() <= limit_bytes {
+        return Ok(estimate);
+    }
+    Err(ProveError::MemoryBudget {
+        estimated: estimate.total(),
+        limit: limit_bytes,
+        hint: suggestions(config, shape, &profile, limit_bytes),
+    })
+}
+
+fn suggestions(config: &Config, shape: &TraceShape, current: &Profile, limit: u64) -> String {
+    let fits = |shape: &TraceShape, profile: &Profile| {
+        MemoryEstimate::new(shape, &config.field, profile).total() <= limit
+    };
+    let mut hints = Vec::new();
+
+    let others: Vec<String> = profile::load_all_profiles_or_default()
+        .into_iter()
+        .filter(|p| p.id != current.id && fits(shape, p))
+        .map(|p| format!("--profile {}", p.id))
+        .collect();
+    if !others.is_empty() {
+        hints.push(others.join(" or "));
+    }
+
+    let blowup = current.fri_blowup.unwrap_or(DEFAULT_BLOWUP);
+    let smaller_blowup = (1..blowup.trailing_zeros().max(1))
+        .rev()
+        .map(|log| 1u32 << log)
+        .find(|&b| {
+            fits(
+                shape,
+                &Profile {
+                    fri_blowup: Some(b),
+                    ..current.clone()
+                },
+            )
+        });
+    if let Some(b) = smaller_blowup {
+        hints.push(format!("--profile-override fri_blowup={}", b));
+    }
+
+    let max_rows = (1..shape.rows.max(2).ilog2())
+        .rev()
+        .map(|log| 1u32 << log)
+        .find(|&rows| fits(&TraceShape { rows, ..*shape }, current));
+    if let Some(rows) = max_rows {
+        hints.push(format!("rows_hint <= {}", rows));
+    }
+
+    if hints.is_empty() {
+        "no profile fits; raise the limit with --max-mem".to_string()
+    } else {
+        format!("try {}", hints.join(", "))
+    }
+}
+
+/// Format a byte count as MiB with one decimal, for messages.
+pub fn fmt_mib(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / MIB as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(blowup: u32) -> Profile {
+        Profile {
+            id: "t".to_string(),
+            lambda_bits: 100,
+            fri_blowup: Some(blowup),
+            fri_queries: None,
+            grind_bits: None,
+            merkle_arity: Some(2),
+            const_col_limit: None,
+            rows_max: None,
+            hash: None,
+            target: None,
+            owner: None,
+            signature: None,
+            signature_verified: false,
+        }
+    }
+
+    #[test]
+    fn estimate_scales_with_rows_cols_field_and_blowup() {
+        let shape = TraceShape {
+            rows: 1024,
+            cols: 3,
+            const_cols: 1,
+            periodic_cols: 0,
+        };
+        let est = MemoryEstimate::new(&shape, "Prime254", &profile(8));
+        assert_eq!(est.trace_bytes, 1024 * 4 * 32);
+        assert_eq!(est.lde_bytes, 1024 * 4 * 32 * 8);
+        assert_eq!(est.merkle_bytes, 2 * 1024 * 8 * 32);
+        assert_eq!(
+            est.total(),
+            est.trace_bytes + est.lde_bytes + est.merkle_bytes
+        );
+
+        let small = MemoryEstimate::new(&shape, "Goldilocks", &profile(8));
+        assert_eq!(small.trace_bytes * 4, est.trace_bytes);
+    }
+}
@@ -1,9 +1,14 @@
+use crate::air::types::{AirIr, CommitmentKind};
 use crate::air::AirProgram;
+use crate::backend::options::validate_options;
 use crate::backend::Capabilities;
 use crate::config::Config;
+use crate::crypto::registry::canonical_hash_id;
 use crate::errors::{CapabilityError, RegistryError};
-use crate::profile::load_all_profiles;
+use crate::profile::{load_all_profiles, trust_anchors, Profile};
 use crate::registry;
+use crate::validation::ValidationWarning;
+use serde::Serialize;
 
 fn get_caps(backend_id: &str) -> Result<Capabilities, RegistryError> {
     registry::get_backend_capabilities(backend_id)
@@ -23,8 +28,11 @@ pub fn validate_config(cfg: &Config) -> Result<(), CapabilityError> {
         });
     }
 
-    // Hash
-    if !caps.hashes.contains(&cfg.hash.as_str()) {
+    // Hash (aliases such as "keccak" name the capability id "keccak256")
+    if !caps
+        .hashes
+        .contains(&canonical_hash_id(&cfg.hash).unwrap_or(&cfg.hash))
+    {
         return Err(CapabilityError::HashUnsupported {
             backend_id: cfg.backend_id.clone(),
             hash: cfg.hash.clone(),
@@ -48,14 +56,201 @@ pub fn validate_config(cfg: &Config) -> Result<(), CapabilityError> {
 
     // Profile existence
     let profiles = load_all_profiles().map_err(|e| CapabilityError::Mismatch(e.to_string()))?;
-    if !profiles.iter().any(|p| p.id == cfg.profile_id) {
+    let Some(profile) = profiles.iter().find(|p| p.id == cfg.profile_id) else {
         return Err(CapabilityError::ProfileNotFound(cfg.profile_id.clone()));
+    };
+
+    // Signing policy for tenant-uploaded profiles
+    if trust_anchors().require_signed && !profile.signature_verified {
+        return Err(CapabilityError::ProfileUnsigned(cfg.profile_id.clone()));
+    }
+
+    // Proof system target (plain STARK or SNARK-wrapped)
+    if !caps.supports_target(profile.target()) {
+        return Err(CapabilityError::TargetUnsupported {
+            backend_id: cfg.backend_id.clone(),
+            profile_id: cfg.profile_id.clone(),
+            target: profile.target(),
+        });
+    }
+
+    // Profile overrides must still satisfy Profile::validate
+    if !cfg.profile_overrides.is_empty() {
+        profile
+            .with_overrides(&cfg.profile_overrides)
+            .map_err(|e| CapabilityError::ProfileOverrideInvalid {
+                profile_id: cfg.profile_id.clone(),
+                reason: format!("{e:#}"),
+            })?;
+    }
+
+    // Backend options must match the backend's schema
+    if !cfg.options.is_null() {
+        let invalid = |reason: String| CapabilityError::OptionsInvalid {
+            backend_id: cfg.backend_id.clone(),
+            reason,
+        };
+        let schema = registry::get_backend_options_schema(&cfg.backend_id)
+            .map_err(|e| invalid(e.to_string()))?
+            .ok_or_else(|| invalid("backend accepts no options".to_string()))?;
+        validate_options(&schema, &cfg.options).map_err(invalid)?;
     }
 
     Ok(())
 }
 
-/// Validate program (AIR) commitments against backend capabilities.
+/// Like [`validate_config`], but also returns non-fatal warnings (e.g., the
+/// selected backend is deprecated) for surfacing in the CLI and reports.
+pub fn validate_config_with_warnings(
+    cfg: &Config,
+) -> Result<Vec<ValidationWarning>, CapabilityError> {
+    validate_config(cfg)?;
+    Ok(backend_warnings(&cfg.backend_id))
+}
+
+/// Non-fatal warnings about a backend id or alias.
+pub fn backend_warnings(backend_id: &str) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    if let Some(dep) = registry::backend_deprecation(backend_id) {
+        let canonical = registry::resolve_backend_id(backend_id).unwrap_or_default();
+        let mut msg = format!(
+            "backend '{}' is deprecated and will be removed after {}",
+            canonical, dep.sunset
+        );
+        if let Some(replacement) = &dep.replacement {
+            msg.push_str(&format!("; use '{}' instead", replacement));
+        }
+        warnings.push(ValidationWarning::with_context(
+            "BackendDeprecated",
+            msg,
+            serde_json::json!({
+                "backend": canonical,
+                "requested": backend_id,
+                "sunset": dep.sunset,
+                "replacement": dep.replacement,
+            }),
+        ));
+    }
+    warnings
+}
+
+/// One reason a profile cannot run on a backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Incompatibility {
+    /// Which check failed: `"arity"`, `"rows_max"`, `"hash"`, or `"target"`.
+    pub check: &'static str,
+    pub reason: String,
+}
+
+/// Result of checking one profile against one backend.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileCompat {
+    pub profile_id: String,
+    pub backend_id: String,
+    pub compatible: bool,
+    pub reasons: Vec<Incompatibility>,
+}
+
+/// Check a profile's parameters against a backend's capabilities.
+/// - `merkle_arity` must be one of the backend's `fri_arities`
+/// - `rows_max` must not exceed the backend's `max_rows`
+/// - a pinned `hash` must be one of the backend's `hashes`
+/// - a `snark-wrapped` target needs a `snark-wrapper` backend
+///
+/// Returns every failed check (empty when compatible).
+pub fn check_profile_against_backend(
+    profile: &Profile,
+    backend_id: &str,
+) -> Result<Vec<Incompatibility>, CapabilityError> {
+    let caps = get_caps(backend_id)
+        .map_err(|_| CapabilityError::Mismatch(format!("unknown backend '{}'", backend_id)))?;
+    let mut reasons = Vec::new();
+
+    if let Some(arity) = profile.merkle_arity {
+        if !caps.fri_arities.contains(&arity) {
+            reasons.push(Incompatibility {
+                check: "arity",
+                reason: format!(
+                    "merkle_arity {} not in backend arities {:?}",
+                    arity, caps.fri_arities
+                ),
+            });
+        }
+    }
+    if let (Some(rows), Some(max)) = (profile.rows_max, caps.max_rows) {
+        if rows > max {
+            reasons.push(Incompatibility {
+                check: "rows_max",
+                reason: format!("rows_max {} exceeds backend limit {}", rows, max),
+            });
+        }
+    }
+    if let Some(hash) = &profile.hash {
+        if !caps
+            .hashes
+            .contains(&canonical_hash_id(hash).unwrap_or(hash))
+        {
+            reasons.push(Incompatibility {
+                check: "hash",
+                reason: format!("hash '{}' not in backend hashes {:?}", hash, caps.hashes),
+            });
+        }
+    }
+    if !caps.supports_target(profile.target()) {
+        reasons.push(Incompatibility {
+            check: "target",
+            reason: format!(
+                "target '{}' needs recursion 'snark-wrapper', backend reports '{}'",
+                profile.target(),
+                caps.recursion
+            ),
+        });
+    }
+    Ok(reasons)
+}
+
+/// Cross-check every loaded profile against every registered backend.
+/// Ordered by profile id, then backend id.
+pub fn profile_compat_matrix() -> Result<Vec<ProfileCompat>, CapabilityError> {
+    let profiles = load_all_profiles().map_err(|e| CapabilityError::Mismatch(e.to_string()))?;
+    let mut backends: Vec<&'static str> = registry::list_backend_infos()
+        .iter()
+        .map(|b| b.id)
+        .collect();
+    backends.sort_unstable();
+
+    let mut out = Vec::with_capacity(profiles.len() * backends.len());
+    for profile in &profiles {
+        for backend_id in &backends {
+            let reasons = check_profile_against_backend(profile, backend_id)?;
+            out.push(ProfileCompat {
+                profile_id: profile.id.clone(),
+                backend_id: backend_id.to_string(),
+                compatible: reasons.is_empty(),
+                reasons,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// Every `meta.requires` entry must be satisfied by the backend.
+fn check_requirements(
+    requires: &[String],
+    caps: &Capabilities,
+    backend_id: &str,
+) -> Result<(), CapabilityError> {
+    match requires.iter().find(|r| !caps.satisfies(r)) {
+        Some(req) => Err(CapabilityError::RequirementUnmet {
+            backend_id: backend_id.to_string(),
+            requirement: req.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Validate program (AIR) requirements and commitments against backend capabilities.
+/// - Every `meta.requires` entry must be satisfied.
 /// - If AIR requires pedersen, backend must advertise pedersen=true.
 /// - If AIR provides a curve hint, backend.curves must contain it.
 pub fn validate_air_against_backend(
@@ -64,6 +259,7 @@ pub fn validate_air_against_backend(
 ) -> Result<(), CapabilityError> {
     let caps = get_caps(backend_id)
         .map_err(|_| CapabilityError::Mismatch(format!("unknown backend '{}'", backend_id)))?;
+    check_requirements(&air.meta.requires, &caps, backend_id)?;
 
     if let Some(req) = &air.commitments {
         if req.pedersen && !caps.pedersen {
@@ -83,3 +279,30 @@ pub fn validate_air_against_backend(
     }
     Ok(())
 }
+
+/// [`validate_air_against_backend`] for the parsed IR, where commitment
+/// requirements come from the normalized bindings.
+pub fn validate_ir_against_backend(ir: &AirIr, backend_id: &str) -> Result<(), CapabilityError> {
+    let caps = get_caps(backend_id)
+        .map_err(|_| CapabilityError::Mismatch(format!("unknown backend '{}'", backend_id)))?;
+    check_requirements(&ir.meta.requires, &caps, backend_id)?;
+
+    for binding in &ir.commitments {
+        let CommitmentKind::Pedersen { curve } = &binding.kind else {
+            continue;
+        };
+        if !caps.pedersen {
+            return Err(CapabilityError::Mismatch(format!(
+                "program requires pedersen commitments but backend '{}' does not support them",
+                backend_id
+            )));
+        }
+        if !curve.is_empty() && !caps.curves.contains(&curve.as_str()) {
+            return Err(CapabilityError::Mismatch(format!(
+                "program requests curve '{}' but backend '{}' supports {:?}",
+                curve, backend_id, caps.curves
+            )));
+        }
+    }
+    Ok(())
+}
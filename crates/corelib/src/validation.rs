@@ -1,16 +1,33 @@
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::path::Path;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::zkprov_bundles::{BlindingTracker, PedersenCtx, PrivacyError, RangeCheck};
+use crate::air::AirIr;
+use crate::backend::PublicInputs;
+use crate::config::Config;
+use crate::errors::HeaderField;
+use crate::proof::pubio::pubio_hash;
+use crate::proof::{hash64, ProofHeader, ProofView};
+use crate::registry;
+use crate::zkprov_bundles::{
+    BlindingTracker, CommitEquality, PedersenCommit, PedersenCtx, PrivacyError, RangeCheck,
+};
 use anyhow::{anyhow, ensure, Result};
 use serde::{Deserialize, Serialize};
 
+pub mod catalog;
+
 /// Structured validation report propagated through bindings and CLI.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ValidationReport {
     pub ok: bool,
     pub commit_passed: bool,
+    /// Checks run, in order, with their outcome and duration. `verify`
+    /// (backend verification, [`Validator::check_proof`]) comes first when
+    /// the report covers a proof.
+    #[serde(default)]
+    pub checks: Vec<ValidationCheck>,
     pub errors: Vec<ValidationError>,
     pub warnings: Vec<ValidationWarning>,
     pub meta: ReportMeta,
@@ -27,6 +44,7 @@ impl ValidationReport {
         Self {
             ok: true,
             commit_passed: true,
+            checks: Vec::new(),
             errors: Vec::new(),
             warnings: Vec::new(),
             meta,
@@ -123,6 +141,16 @@ impl ValidationReport {
     }
 }
 
+/// One [`Validator`] check in a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationCheck {
+    /// `verify`, `header_bindings`, `commit_point`, `r_reuse` or `range_u64`.
+    pub name: String,
+    /// Whether the check added no errors.
+    pub passed: bool,
+    pub time_us: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportMeta {
     pub backend_id: String,
@@ -132,7 +160,9 @@ pub struct ReportMeta {
     pub time_ms: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Validation failure kinds. Each has a stable id, number and message
+/// template in [`catalog::CATALOG`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ValidationErrorCode {
     InvalidCurvePoint,
     BlindingReuse,
@@ -141,12 +171,45 @@ pub enum ValidationErrorCode {
     KeccakNotEnabled,
     PedersenNotEnabled,
     CurveNotAllowed,
+    /// Two commitments do not open to the same message.
+    CommitmentMismatch,
+    /// The proof header's `backend_id_hash` is not the configured backend's.
+    BackendIdMismatch,
+    /// The proof header's `profile_id_hash` is not the configured profile's.
+    ProfileIdMismatch,
+    /// The proof header's `pubio_hash` does not bind the supplied inputs.
+    PubIoMismatch,
+    /// The proof header's `config_hash` is not `Config::config_hash`.
+    ConfigHashMismatch,
+    /// The supplied public inputs cannot be bound to the AIR at all.
+    InvalidPublicInputs,
+    /// The backend verifier rejects the proof.
+    ProofRejected,
+}
+
+impl ValidationErrorCode {
+    /// Stable snake_case id, e.g. `"blinding_reuse"`.
+    pub fn id(&self) -> &'static str {
+        catalog::lookup(self).id
+    }
+
+    /// Stable number, e.g. `1002`.
+    pub fn num(&self) -> u16 {
+        catalog::lookup(self).num
+    }
+
+    /// English message template with `{param}` placeholders.
+    pub fn template(&self) -> &'static str {
+        catalog::lookup(self).template
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationError {
     pub code: ValidationErrorCode,
     pub msg: String,
+    /// Parameters of the message, plus the stable `code` id and `code_num`
+    /// (an object unless the caller passed another non-null value).
     pub context: serde_json::Value,
 }
 
@@ -156,12 +219,28 @@ impl ValidationError {
         msg: impl Into<String>,
         context: impl Into<serde_json::Value>,
     ) -> Self {
+        let mut context = context.into();
+        if context.is_null() {
+            context = serde_json::Value::Object(Default::default());
+        }
+        if let Some(map) = context.as_object_mut() {
+            map.insert("code".into(), code.id().into());
+            map.insert("code_num".into(), code.num().into());
+        }
         Self {
             code,
             msg: msg.into(),
-            context: context.into(),
+            context,
         }
     }
+
+    /// Error whose message is `code`'s catalog template rendered with
+    /// `context`.
+    pub fn from_catalog(code: ValidationErrorCode, context: impl Into<serde_json::Value>) -> Self {
+        let context = context.into();
+        let msg = catalog::render(code.template(), &context);
+        Self::new(code, msg, context)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -300,45 +379,69 @@ impl<'a> Validator<'a> {
         &mut self.cfg
     }
 
-    pub fn check_commit_point(&mut self, msg: &[u8], r: &[u8]) {
-        if !self.cfg.pedersen_enabled {
-            self.report.push_error(ValidationError::new(
-                ValidationErrorCode::PedersenNotEnabled,
-                "pedersen commitments disabled by configuration",
-                serde_json::json!({"operation": "check_commit_point"}),
-            ));
-            return;
-        }
+    /// Check blinds against `tracker` instead of a fresh in-memory one, e.g.
+    /// [`BlindingTracker::persistent`] to catch reuse across sessions.
+    pub fn with_blinding_tracker(mut self, tracker: BlindingTracker) -> Self {
+        self.blinds = tracker;
+        self
+    }
 
-        if let Some(curve) = self.cfg.requested_curve() {
-            if !self.cfg.allowed_curves.is_empty()
-                && !self
-                    .cfg
-                    .allowed_curves
-                    .iter()
-                    .any(|allowed| matches_ignore_ascii_case(allowed, curve))
-            {
-                self.report.push_error(ValidationError::new(
-                    ValidationErrorCode::CurveNotAllowed,
-                    "curve not allowed by configuration",
+    /// Count the blinds listed in `path` as used, so `no_r_reuse` also
+    /// covers blinds from before this session ([`BlindingTracker::load_known`]).
+    /// Returns how many were new.
+    pub fn load_known_blinds(&mut self, path: impl AsRef<Path>) -> Result<usize, PrivacyError> {
+        self.blinds.load_known(path)
+    }
+
+    /// Verify `proof` with the configured backend, as `zkd verify` does.
+    /// Run it first: the other checks only inspect the header and gadget
+    /// inputs, so without it a report can pass for a proof that does not
+    /// verify.
+    pub fn check_proof(
+        &mut self,
+        config: &Config,
+        ir: &AirIr,
+        inputs_json: &str,
+        proof: &ProofView<'_>,
+    ) {
+        self.timed("verify", |v| {
+            let result = PublicInputs::from_json(inputs_json)
+                .map_err(|e| ("inputs", format!("{e:#}")))
+                .and_then(|inputs| {
+                    crate::verify(config, ir, &inputs, proof)
+                        .map_err(|e| (e.category(), e.to_string()))
+                });
+            if let Err((category, reason)) = result {
+                v.report.push_error(ValidationError::from_catalog(
+                    ValidationErrorCode::ProofRejected,
                     serde_json::json!({
-                        "operation": "check_commit_point",
-                        "curve": curve,
+                        "operation": "check_proof",
+                        "category": category,
+                        "reason": reason,
                     }),
                 ));
-                return;
             }
-        }
+        });
+    }
 
-        if self.cfg.keccak_requested() && !self.cfg.keccak_enabled {
-            self.report.push_error(ValidationError::new(
-                ValidationErrorCode::KeccakNotEnabled,
-                "keccak commitments disabled by configuration",
-                serde_json::json!({
-                    "operation": "check_commit_point",
-                    "hash": self.cfg.requested_hash(),
-                }),
-            ));
+    /// Run `check` and record it in the report's `checks`.
+    fn timed(&mut self, name: &str, check: impl FnOnce(&mut Self)) {
+        let started = Instant::now();
+        let errors = self.report.errors.len();
+        check(self);
+        self.report.checks.push(ValidationCheck {
+            name: name.to_string(),
+            passed: self.report.errors.len() == errors,
+            time_us: started.elapsed().as_micros() as u64,
+        });
+    }
+
+    pub fn check_commit_point(&mut self, msg: &[u8], r: &[u8]) {
+        self.timed("commit_point", |v| v.commit_point(msg, r));
+    }
+
+    fn commit_point(&mut self, msg: &[u8], r: &[u8]) {
+        if !self.commitments_enabled("check_commit_point") {
             return;
         }
 
@@ -372,13 +475,71 @@ impl<'a> Validator<'a> {
         cx: &[u8; 32],
         cy: &[u8; 32],
     ) {
+        self.timed("commit_point", |v| v.commit_point_with_pair(msg, r, cx, cy));
+    }
+
+    fn commit_point_with_pair(&mut self, msg: &[u8], r: &[u8], cx: &[u8; 32], cy: &[u8; 32]) {
+        if !self.commitments_enabled("check_commit_point") {
+            return;
+        }
+
+        let Some(ctx) = self.ped.as_ref() else {
+            return;
+        };
+
+        if let Err(err) = ctx.open(msg, r, cx, cy) {
+            self.push_privacy_error(err, serde_json::json!({"operation": "check_commit_point"}));
+        }
+    }
+
+    /// Check that commitments `a` and `b` both open to `msg`, with blinds
+    /// `r_a` and `r_b` ([`CommitEquality::verify`]).
+    pub fn check_commit_equality(
+        &mut self,
+        msg: &[u8],
+        a: &PedersenCommit,
+        r_a: &[u8],
+        b: &PedersenCommit,
+        r_b: &[u8],
+    ) {
+        self.timed("commit_equality", |v| {
+            v.commit_equality(msg, a, r_a, b, r_b)
+        });
+    }
+
+    fn commit_equality(
+        &mut self,
+        msg: &[u8],
+        a: &PedersenCommit,
+        r_a: &[u8],
+        b: &PedersenCommit,
+        r_b: &[u8],
+    ) {
+        if !self.commitments_enabled("check_commit_equality") {
+            return;
+        }
+
+        let Some(ctx) = self.ped.as_ref() else {
+            return;
+        };
+
+        if let Err(err) = CommitEquality::verify(ctx, a, r_a, ctx, b, r_b, msg) {
+            self.push_privacy_error(
+                err,
+                serde_json::json!({"operation": "check_commit_equality"}),
+            );
+        }
+    }
+
+    /// Record why Pedersen commitment checks cannot run for `operation`, if
+    /// they are disabled or the requested curve or hash is not allowed.
+    fn commitments_enabled(&mut self, operation: &str) -> bool {
         if !self.cfg.pedersen_enabled {
-            self.report.push_error(ValidationError::new(
+            self.report.push_error(ValidationError::from_catalog(
                 ValidationErrorCode::PedersenNotEnabled,
-                "pedersen commitments disabled by configuration",
-                serde_json::json!({"operation": "check_commit_point"}),
+                serde_json::json!({"operation": operation}),
             ));
-            return;
+            return false;
         }
 
         if let Some(curve) = self.cfg.requested_curve() {
@@ -389,44 +550,39 @@ impl<'a> Validator<'a> {
                     .iter()
                     .any(|allowed| matches_ignore_ascii_case(allowed, curve))
             {
-                self.report.push_error(ValidationError::new(
+                self.report.push_error(ValidationError::from_catalog(
                     ValidationErrorCode::CurveNotAllowed,
-                    "curve not allowed by configuration",
                     serde_json::json!({
-                        "operation": "check_commit_point",
+                        "operation": operation,
                         "curve": curve,
                     }),
                 ));
-                return;
+                return false;
             }
         }
 
         if self.cfg.keccak_requested() && !self.cfg.keccak_enabled {
-            self.report.push_error(ValidationError::new(
+            self.report.push_error(ValidationError::from_catalog(
                 ValidationErrorCode::KeccakNotEnabled,
-                "keccak commitments disabled by configuration",
                 serde_json::json!({
-                    "operation": "check_commit_point",
+                    "operation": operation,
                     "hash": self.cfg.requested_hash(),
                 }),
             ));
-            return;
+            return false;
         }
 
-        let Some(ctx) = self.ped.as_ref() else {
-            return;
-        };
-
-        if let Err(err) = ctx.open(msg, r, cx, cy) {
-            self.push_privacy_error(err, serde_json::json!({"operation": "check_commit_point"}));
-        }
+        true
     }
 
     pub fn check_r_reuse(&mut self, r: &[u8]) {
+        self.timed("r_reuse", |v| v.r_reuse(r));
+    }
+
+    fn r_reuse(&mut self, r: &[u8]) {
         if !self.cfg.pedersen_enabled {
-            self.report.push_error(ValidationError::new(
+            self.report.push_error(ValidationError::from_catalog(
                 ValidationErrorCode::PedersenNotEnabled,
-                "pedersen commitments disabled by configuration",
                 serde_json::json!({"operation": "check_r_reuse"}),
             ));
             return;
@@ -442,6 +598,10 @@ impl<'a> Validator<'a> {
     }
 
     pub fn check_range_u64(&mut self, v: u64, k: u32) {
+        self.timed("range_u64", |this| this.range_u64(v, k));
+    }
+
+    fn range_u64(&mut self, v: u64, k: u32) {
         if let Err(err) = RangeCheck::check_u64(v, k) {
             self.push_privacy_error(
                 err,
@@ -454,6 +614,92 @@ impl<'a> Validator<'a> {
         }
     }
 
+    /// Check that `header` binds `config` and `inputs_json` (bound to `ir`'s
+    /// public input declarations as prove does), recording one error per
+    /// mismatching hash with the expected and actual values.
+    pub fn check_header_bindings(
+        &mut self,
+        header: &ProofHeader,
+        config: &Config,
+        ir: &AirIr,
+        inputs_json: &str,
+    ) {
+        self.timed("header_bindings", |v| {
+            v.header_bindings(header, config, ir, inputs_json)
+        });
+    }
+
+    fn header_bindings(
+        &mut self,
+        header: &ProofHeader,
+        config: &Config,
+        ir: &AirIr,
+        inputs_json: &str,
+    ) {
+        let backend_id = registry::resolve_backend_id(&config.backend_id)
+            .unwrap_or(&config.backend_id)
+            .to_string();
+        let mut expected = ProofHeader {
+            backend_id_hash: hash64("BACKEND", backend_id.as_bytes()),
+            profile_id_hash: hash64("PROFILE", config.profile_id.as_bytes()),
+            config_hash: config.config_hash(),
+            ..header.clone()
+        };
+        match PublicInputs::from_json(inputs_json).and_then(|i| crate::bind_inputs(config, ir, &i))
+        {
+            Ok(inputs) => expected.pubio_hash = pubio_hash(&inputs),
+            Err(err) => self.report.push_error(ValidationError::from_catalog(
+                ValidationErrorCode::InvalidPublicInputs,
+                serde_json::json!({
+                    "operation": "check_header_bindings",
+                    "reason": format!("{err:#}"),
+                }),
+            )),
+        }
+        for (field, expected, actual) in header.binding_mismatches(&expected) {
+            let (code, extra) = match field {
+                HeaderField::BackendId => (
+                    ValidationErrorCode::BackendIdMismatch,
+                    serde_json::json!({ "backend_id": backend_id }),
+                ),
+                HeaderField::ProfileId => (
+                    ValidationErrorCode::ProfileIdMismatch,
+                    serde_json::json!({ "profile_id": config.profile_id }),
+                ),
+                HeaderField::PubIo => (
+                    ValidationErrorCode::PubIoMismatch,
+                    serde_json::json!({ "pubio_encoding": config.pubio_encoding }),
+                ),
+                HeaderField::Config => (
+                    ValidationErrorCode::ConfigHashMismatch,
+                    serde_json::Value::Null,
+                ),
+            };
+            self.push_header_mismatch(code, field, expected, actual, extra);
+        }
+    }
+
+    fn push_header_mismatch(
+        &mut self,
+        code: ValidationErrorCode,
+        field: HeaderField,
+        expected: u64,
+        actual: u64,
+        extra: serde_json::Value,
+    ) {
+        let mut context = serde_json::json!({
+            "operation": "check_header_bindings",
+            "field": field.as_str(),
+            "expected": format!("0x{expected:016x}"),
+            "actual": format!("0x{actual:016x}"),
+        });
+        if let (Some(ctx), Some(extra)) = (context.as_object_mut(), extra.as_object()) {
+            ctx.extend(extra.clone());
+        }
+        self.report
+            .push_error(ValidationError::from_catalog(code, context));
+    }
+
     pub fn finalize(mut self) -> ValidationReport {
         let elapsed = self.clock.elapsed().as_millis() as u64;
         self.report.meta.time_ms = elapsed;
@@ -462,6 +708,7 @@ impl<'a> Validator<'a> {
             ValidationErrorCode::InvalidCurvePoint,
             ValidationErrorCode::BlindingReuse,
             ValidationErrorCode::RangeCheckOverflow,
+            ValidationErrorCode::CommitmentMismatch,
             ValidationErrorCode::CurveNotAllowed,
             ValidationErrorCode::PedersenNotEnabled,
             ValidationErrorCode::KeccakNotEnabled,
@@ -476,10 +723,13 @@ impl<'a> Validator<'a> {
         self.report
     }
 
-    fn push_privacy_error(&mut self, err: PrivacyError, context: serde_json::Value) {
+    fn push_privacy_error(&mut self, err: PrivacyError, mut context: serde_json::Value) {
         let code = Self::map_privacy_error(&err);
+        if let (PrivacyError::Internal(detail), Some(map)) = (&err, context.as_object_mut()) {
+            map.insert("detail".into(), detail.clone().into());
+        }
         self.report
-            .push_error(ValidationError::new(code, err.to_string(), context));
+            .push_error(ValidationError::from_catalog(code, context));
     }
 
     fn map_privacy_error(err: &PrivacyError) -> ValidationErrorCode {
@@ -487,6 +737,7 @@ impl<'a> Validator<'a> {
             PrivacyError::InvalidCurvePoint => ValidationErrorCode::InvalidCurvePoint,
             PrivacyError::BlindingReuse => ValidationErrorCode::BlindingReuse,
             PrivacyError::RangeCheckOverflow => ValidationErrorCode::RangeCheckOverflow,
+            PrivacyError::CommitmentMismatch => ValidationErrorCode::CommitmentMismatch,
             PrivacyError::UnsupportedCurve => ValidationErrorCode::CurveNotAllowed,
             PrivacyError::Internal(_) => ValidationErrorCode::UnsupportedCurve,
         }
@@ -546,6 +797,10 @@ mod tests {
             Validator::map_privacy_error(&PrivacyError::RangeCheckOverflow),
             ValidationErrorCode::RangeCheckOverflow
         );
+        assert_eq!(
+            Validator::map_privacy_error(&PrivacyError::CommitmentMismatch),
+            ValidationErrorCode::CommitmentMismatch
+        );
         assert_eq!(
             Validator::map_privacy_error(&PrivacyError::UnsupportedCurve),
             ValidationErrorCode::CurveNotAllowed
@@ -583,6 +838,86 @@ mod tests {
             .any(|e| e.code == ValidationErrorCode::BlindingReuse));
     }
 
+    #[test]
+    fn blinding_reuse_detected_across_sessions() {
+        let mut bindings = bindings_with_pedersen();
+        bindings.commitments.no_r_reuse = Some(true);
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("blinds.log");
+        for expect_reuse in [false, true] {
+            let tracker = BlindingTracker::persistent(&log).unwrap();
+            let mut validator = Validator::new(&bindings).with_blinding_tracker(tracker);
+            validator.check_r_reuse(b"r1");
+            let reused = validator
+                .report
+                .errors
+                .iter()
+                .any(|e| e.code == ValidationErrorCode::BlindingReuse);
+            assert_eq!(reused, expect_reuse);
+        }
+    }
+
+    #[test]
+    fn known_blinds_count_as_used() {
+        let mut bindings = bindings_with_pedersen();
+        bindings.commitments.no_r_reuse = Some(true);
+        let dir = tempdir().unwrap();
+        let known = dir.path().join("known.txt");
+        fs::write(&known, "# ledger\n7231\n").unwrap();
+        let mut validator = Validator::new(&bindings);
+        assert_eq!(validator.load_known_blinds(&known).unwrap(), 1);
+        validator.check_r_reuse(b"r2");
+        assert!(validator.report.errors.is_empty());
+        validator.check_r_reuse(b"r1");
+        assert_eq!(
+            validator.report.errors[0].code,
+            ValidationErrorCode::BlindingReuse
+        );
+    }
+
+    #[test]
+    fn scoped_blinds_are_forgotten_on_close_but_known_ones_are_not() {
+        let mut tracker = BlindingTracker::new();
+        tracker.note_and_check(b"outer", true).unwrap();
+        tracker.open_scope("tx-1");
+        tracker.open_scope("tx-1/retry");
+        assert_eq!(tracker.scope(), Some("tx-1/retry"));
+        assert!(tracker.note_and_check(b"outer", true).is_err());
+        tracker.note_and_check(b"a", true).unwrap();
+        assert!(matches!(
+            tracker.close_scope("tx-1"),
+            Err(PrivacyError::Internal(_))
+        ));
+        assert_eq!(tracker.close_scope("tx-1/retry").unwrap(), 1);
+        assert_eq!(tracker.close_scope("tx-1").unwrap(), 0);
+        assert_eq!(tracker.scope(), None);
+        tracker.note_and_check(b"a", true).unwrap();
+
+        assert_eq!(tracker.import_known([b"old".as_slice(), b"old"]), 1);
+        tracker.open_scope("tx-2");
+        assert_eq!(
+            tracker.note_and_check(b"old", true),
+            Err(PrivacyError::BlindingReuse)
+        );
+        tracker.note_and_check(b"old", false).unwrap();
+    }
+
+    #[test]
+    fn known_blinds_apply_to_persistent_trackers() {
+        let dir = tempdir().unwrap();
+        let mut tracker = BlindingTracker::persistent(dir.path().join("blinds.log")).unwrap();
+        tracker.import_known([b"ledger"]);
+        assert_eq!(
+            tracker.note_and_check(b"ledger", true),
+            Err(PrivacyError::BlindingReuse)
+        );
+        tracker.open_scope("s");
+        tracker.note_and_check(b"fresh", true).unwrap();
+        assert_eq!(tracker.close_scope("s").unwrap(), 1);
+        // The log remembers what the closed scope noted.
+        assert!(tracker.note_and_check(b"fresh", true).is_err());
+    }
+
     #[test]
     fn range_check_overflow_detected() {
         let bindings = bindings_with_pedersen();
@@ -672,6 +1007,7 @@ mod tests {
         let report = ValidationReport {
             ok: true,
             commit_passed: true,
+            checks: Vec::new(),
             errors: Vec::new(),
             warnings: Vec::new(),
             meta: ReportMeta {
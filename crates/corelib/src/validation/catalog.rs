@@ -0,0 +1,159 @@
+//! Message catalog for [`ValidationErrorCode`]s.
+//!
+//! Every code has a stable string id and number (never reused or renumbered)
+//! and an English message template. Templates name their parameters as
+//! `{param}`; the values come from the error's JSON `context`, which also
+//! carries `code` and `code_num`, so a UI can pick its own translation of the
+//! template and fill it from the same context.
+
+use serde_json::Value;
+
+use super::ValidationErrorCode;
+
+/// One catalog entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatalogEntry {
+    pub code: ValidationErrorCode,
+    /// Stable snake_case id, e.g. `"blinding_reuse"`.
+    pub id: &'static str,
+    /// Stable number: `10xx` commitment checks, `11xx` header bindings,
+    /// `12xx` proof verification.
+    pub num: u16,
+    /// English template; `{param}` is replaced from the context.
+    pub template: &'static str,
+}
+
+const fn entry(
+    code: ValidationErrorCode,
+    id: &'static str,
+    num: u16,
+    template: &'static str,
+) -> CatalogEntry {
+    CatalogEntry {
+        code,
+        id,
+        num,
+        template,
+    }
+}
+
+/// Every code, in numeric order.
+pub const CATALOG: &[CatalogEntry] = &[
+    entry(
+        ValidationErrorCode::InvalidCurvePoint,
+        "invalid_curve_point",
+        1001,
+        "commitment does not open to a valid curve point",
+    ),
+    entry(
+        ValidationErrorCode::BlindingReuse,
+        "blinding_reuse",
+        1002,
+        "blinding factor was already used",
+    ),
+    entry(
+        ValidationErrorCode::RangeCheckOverflow,
+        "range_check_overflow",
+        1003,
+        "value is out of range for the range check",
+    ),
+    entry(
+        ValidationErrorCode::UnsupportedCurve,
+        "unsupported_curve",
+        1004,
+        "commitment gadget failed: {detail}",
+    ),
+    entry(
+        ValidationErrorCode::KeccakNotEnabled,
+        "keccak_not_enabled",
+        1005,
+        "keccak commitments disabled by configuration",
+    ),
+    entry(
+        ValidationErrorCode::PedersenNotEnabled,
+        "pedersen_not_enabled",
+        1006,
+        "pedersen commitments disabled by configuration",
+    ),
+    entry(
+        ValidationErrorCode::CurveNotAllowed,
+        "curve_not_allowed",
+        1007,
+        "curve not allowed by configuration",
+    ),
+    entry(
+        ValidationErrorCode::CommitmentMismatch,
+        "commitment_mismatch",
+        1008,
+        "commitments do not open to the same message",
+    ),
+    entry(
+        ValidationErrorCode::BackendIdMismatch,
+        "backend_id_mismatch",
+        1101,
+        "proof header {field} does not match",
+    ),
+    entry(
+        ValidationErrorCode::ProfileIdMismatch,
+        "profile_id_mismatch",
+        1102,
+        "proof header {field} does not match",
+    ),
+    entry(
+        ValidationErrorCode::PubIoMismatch,
+        "pubio_mismatch",
+        1103,
+        "proof header {field} does not match",
+    ),
+    entry(
+        ValidationErrorCode::ConfigHashMismatch,
+        "config_hash_mismatch",
+        1104,
+        "proof header {field} does not match",
+    ),
+    entry(
+        ValidationErrorCode::InvalidPublicInputs,
+        "invalid_public_inputs",
+        1105,
+        "public inputs cannot be bound to the AIR: {reason}",
+    ),
+    entry(
+        ValidationErrorCode::ProofRejected,
+        "proof_rejected",
+        1201,
+        "proof does not verify: {reason}",
+    ),
+];
+
+/// The catalog entry for `code`.
+pub fn lookup(code: &ValidationErrorCode) -> &'static CatalogEntry {
+    CATALOG
+        .iter()
+        .find(|e| &e.code == code)
+        .expect("every ValidationErrorCode has a catalog entry")
+}
+
+/// Fill `{param}` placeholders in `template` from `context`. Strings are
+/// inserted verbatim, other values as JSON; unknown parameters are left as
+/// written so a missing value is visible rather than silently dropped.
+pub fn render(template: &str, context: &Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+        let name = &after[..close];
+        match context.get(name) {
+            Some(Value::String(s)) => out.push_str(s),
+            Some(v) => out.push_str(&v.to_string()),
+            None => out.push_str(&rest[open..open + close + 2]),
+        }
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,273 @@
+//! Persistent backing store for [`super::BlindingTracker`]: an append-only log
+//! of blind fingerprints, so `no_r_reuse` holds across process restarts.
+//!
+//! Layout: the 8-byte magic `ZKBLIND1`, then one 32-byte record per blind,
+//! `BLAKE3("ZKPROV.BLIND.FP" || blind)`; blinds themselves never touch disk.
+//! A torn trailing record (crash mid-append) is dropped when the log is
+//! opened, and duplicate records are compacted away at the same time.
+//!
+//! Records appended by other processes are read back before every check, so
+//! processes sharing a log see each other's blinds. The check and the append
+//! are not one atomic step, though: two processes inserting the same blind at
+//! the same instant can both succeed.
+//!
+//! Blinds used before any log existed (e.g. exported from a ledger) are read
+//! with [`read_known_blinds`]: one hex blind per line, `0x` optional, blank
+//! lines and `#` comments ignored.
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+pub const LOG_MAGIC: [u8; 8] = *b"ZKBLIND1";
+const RECORD_LEN: usize = 32;
+const FINGERPRINT_LABEL: &[u8] = b"ZKPROV.BLIND.FP";
+
+/// Fingerprint recorded for `blind`.
+pub fn fingerprint(blind: &[u8]) -> [u8; RECORD_LEN] {
+    let mut h = blake3::Hasher::new();
+    h.update(FINGERPRINT_LABEL);
+    h.update(blind);
+    *h.finalize().as_bytes()
+}
+
+/// Parse a known-blinds file; see the module docs for the format.
+pub fn read_known_blinds(path: impl AsRef<Path>) -> io::Result<Vec<Vec<u8>>> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+    let mut blinds = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let hex = line.strip_prefix("0x").unwrap_or(line);
+        let blind = alloy_primitives::hex::decode(hex)
+            .ok()
+            .filter(|b| !b.is_empty())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: not a hex blind", path.display(), i + 1),
+                )
+            })?;
+        blinds.push(blind);
+    }
+    Ok(blinds)
+}
+
+#[derive(Debug)]
+pub struct BlindStore {
+    path: PathBuf,
+    file: File,
+    seen: HashSet<[u8; RECORD_LEN]>,
+    /// Bytes of the log already folded into `seen`.
+    offset: u64,
+    /// Records read so far, duplicates included.
+    records: u64,
+}
+
+impl BlindStore {
+    /// Open (or create) the log at `path`, compacting it if it holds a torn
+    /// record or duplicates.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = open_log(&path)?;
+        let mut store = Self {
+            path,
+            file,
+            seen: HashSet::new(),
+            offset: LOG_MAGIC.len() as u64,
+            records: 0,
+        };
+        store.refresh()?;
+        let torn = store.file.metadata()?.len() != store.offset;
+        if torn || store.records != store.seen.len() as u64 {
+            store.compact()?;
+        }
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Distinct blinds recorded.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Whether `blind` was recorded, by this or another process.
+    pub fn contains(&mut self, blind: &[u8]) -> io::Result<bool> {
+        self.refresh()?;
+        Ok(self.seen.contains(&fingerprint(blind)))
+    }
+
+    /// Record `blind` durably. Returns `false`, writing nothing, when it was
+    /// already recorded.
+    pub fn insert(&mut self, blind: &[u8]) -> io::Result<bool> {
+        self.refresh()?;
+        let fp = fingerprint(blind);
+        if self.seen.contains(&fp) {
+            return Ok(false);
+        }
+        self.file.write_all(&fp)?;
+        self.file.sync_data()?;
+        self.seen.insert(fp);
+        // Our own record is read back (and skipped as known) on the next
+        // refresh, which keeps `offset` in step with other writers.
+        Ok(true)
+    }
+
+    /// Rewrite the log with one record per distinct blind, replacing it
+    /// atomically (write to a sibling file, then rename).
+    pub fn compact(&mut self) -> io::Result<()> {
+        self.refresh()?;
+        let mut records: Vec<_> = self.seen.iter().copied().collect();
+        records.sort_unstable();
+        let tmp = self.path.with_extension("compact");
+        {
+            let mut out = File::create(&tmp)?;
+            out.write_all(&LOG_MAGIC)?;
+            for record in &records {
+                out.write_all(record)?;
+            }
+            out.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path)?;
+        self.file = open_log(&self.path)?;
+        self.offset = (LOG_MAGIC.len() + records.len() * RECORD_LEN) as u64;
+        self.records = records.len() as u64;
+        Ok(())
+    }
+
+    /// Fold complete records appended since the last refresh into `seen`.
+    fn refresh(&mut self) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(self.offset))?;
+        let mut tail = Vec::new();
+        self.file.read_to_end(&mut tail)?;
+        let complete = tail.len() / RECORD_LEN * RECORD_LEN;
+        for record in tail[..complete].chunks_exact(RECORD_LEN) {
+            self.seen
+                .insert(record.try_into().expect("chunk is a record"));
+            self.records += 1;
+        }
+        self.offset += complete as u64;
+        Ok(())
+    }
+}
+
+fn open_log(path: &Path) -> io::Result<File> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?;
+    if file.metadata()?.len() == 0 {
+        file.write_all(&LOG_MAGIC)?;
+        file.sync_data()?;
+        return Ok(file);
+    }
+    let mut magic = [0u8; LOG_MAGIC.len()];
+    file.read_exact(&mut magic).and_then(|_| {
+        if magic == LOG_MAGIC {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not a blinding log", path.display()),
+            ))
+        }
+    })?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn survives_reopen_and_drops_torn_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blinds.log");
+        {
+            let mut store = BlindStore::open(&path).unwrap();
+            assert!(store.insert(&[1u8; 32]).unwrap());
+            assert!(!store.insert(&[1u8; 32]).unwrap());
+            assert!(store.insert(&[2u8; 32]).unwrap());
+        }
+        // Simulate a crash halfway through a third append.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[0xaa; 5])
+            .unwrap();
+
+        let mut store = BlindStore::open(&path).unwrap();
+        assert_eq!(store.len(), 2);
+        assert!(store.contains(&[2u8; 32]).unwrap());
+        assert!(!store.contains(&[3u8; 32]).unwrap());
+        assert_eq!(
+            fs::metadata(&path).unwrap().len(),
+            (LOG_MAGIC.len() + 2 * RECORD_LEN) as u64
+        );
+        // The log holds fingerprints, not blinds.
+        let raw = fs::read(&path).unwrap();
+        assert!(!raw.windows(32).any(|w| w == [1u8; 32]));
+    }
+
+    #[test]
+    fn sees_records_from_other_handles_and_compacts_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blinds.log");
+        let mut a = BlindStore::open(&path).unwrap();
+        let mut b = BlindStore::open(&path).unwrap();
+        assert!(a.insert(b"first").unwrap());
+        assert!(!b.insert(b"first").unwrap());
+
+        // A duplicate written behind both handles' backs is folded away.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&fingerprint(b"first"))
+            .unwrap();
+        drop((a, b));
+        let store = BlindStore::open(&path).unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            fs::metadata(&path).unwrap().len(),
+            (LOG_MAGIC.len() + RECORD_LEN) as u64
+        );
+    }
+
+    #[test]
+    fn reads_known_blinds_with_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known.txt");
+        fs::write(&path, "# ledger export\n0x0102\n\n  ff  # trailing\n").unwrap();
+        assert_eq!(
+            read_known_blinds(&path).unwrap(),
+            vec![vec![1u8, 2], vec![0xff]]
+        );
+        fs::write(&path, "0102\nzz\n").unwrap();
+        let err = read_known_blinds(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().ends_with(":2: not a hex blind"), "{err}");
+    }
+
+    #[test]
+    fn rejects_foreign_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        fs::write(&path, b"not a blinding log").unwrap();
+        let err = BlindStore::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
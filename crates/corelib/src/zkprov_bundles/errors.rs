@@ -6,6 +6,8 @@ pub enum PrivacyError {
     InvalidCurvePoint,
     BlindingReuse,
     RangeCheckOverflow,
+    /// Two commitments do not open to the same message.
+    CommitmentMismatch,
     UnsupportedCurve, // helpful internal; not required by DoD but used in messages
     Internal(String),
 }
@@ -17,6 +19,7 @@ impl std::fmt::Display for PrivacyError {
             InvalidCurvePoint => write!(f, "InvalidCurvePoint"),
             BlindingReuse => write!(f, "BlindingReuse"),
             RangeCheckOverflow => write!(f, "RangeCheckOverflow"),
+            CommitmentMismatch => write!(f, "CommitmentMismatch"),
             UnsupportedCurve => write!(f, "UnsupportedCurve"),
             Internal(s) => write!(f, "Internal({})", s),
         }
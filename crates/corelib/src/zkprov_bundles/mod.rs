@@ -1,9 +1,13 @@
 pub mod arith;
+pub mod blind_store;
+pub mod equality;
 pub mod errors;
 pub mod pedersen;
 pub mod range;
 
 pub use arith::AddUnderCommit;
+pub use blind_store::BlindStore;
+pub use equality::CommitEquality;
 pub use errors::PrivacyError;
 pub use pedersen::{BlindingTracker, PedersenCommit, PedersenCtx};
 pub use range::RangeCheck;
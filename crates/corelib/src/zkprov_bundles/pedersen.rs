@@ -2,6 +2,7 @@
 //! Backed by corelib's PedersenPlaceholder. For placeholder, we synthesize (Cx,Cy)
 //! as two domain-separated 32-byte digests, then expose them as a pair.
 
+use super::blind_store::{read_known_blinds, BlindStore};
 use super::errors::PrivacyError;
 use crate::air::bindings::Bindings;
 use crate::crypto::registry::hash32_by_id;
@@ -9,31 +10,129 @@ use crate::gadgets::commitment::{
     Comm32, CommitmentScheme32, PedersenParams, PedersenPlaceholder, Witness,
 };
 use std::collections::HashSet;
+use std::path::Path;
 
-/// Tracks used blindings in a session to enforce no-reuse when policy says so.
+/// Tracks used blindings to enforce no-reuse when policy says so: in memory
+/// for one session by default, or across sessions with [`Self::persistent`].
+///
+/// Named scopes ([`Self::open_scope`]) split one in-memory tracker into
+/// sessions: a scope's blinds are forgotten when it closes, while blinds noted
+/// outside any scope last as long as the tracker. Blinds used before the
+/// tracker existed, e.g. exported from a ledger, are added with
+/// [`Self::import_known`] and count as used in every scope and mode.
 #[derive(Debug, Default)]
 pub struct BlindingTracker {
     used: HashSet<Vec<u8>>,
+    store: Option<BlindStore>,
+    known: HashSet<Vec<u8>>,
+    /// Open scopes, innermost last.
+    scopes: Vec<Scope>,
 }
+
+#[derive(Debug)]
+struct Scope {
+    name: String,
+    used: HashSet<Vec<u8>>,
+}
+
 impl BlindingTracker {
     pub fn new() -> Self {
-        Self {
+        Self::default()
+    }
+    /// Tracker backed by the append-only blinding log at `path` (created if
+    /// missing), so blinds used by earlier sessions count as used.
+    pub fn persistent(path: impl AsRef<Path>) -> Result<Self, PrivacyError> {
+        let store = BlindStore::open(path.as_ref()).map_err(|e| log_error(path.as_ref(), e))?;
+        Ok(Self {
+            store: Some(store),
+            ..Self::default()
+        })
+    }
+    /// Rewrite the backing log without duplicates; a no-op in memory.
+    pub fn compact(&mut self) -> Result<(), PrivacyError> {
+        match &mut self.store {
+            Some(store) => store.compact().map_err(|e| log_error(store.path(), e)),
+            None => Ok(()),
+        }
+    }
+    /// Start the scope `name` inside any open one; it sees the blinds of the
+    /// scopes around it.
+    pub fn open_scope(&mut self, name: impl Into<String>) {
+        self.scopes.push(Scope {
+            name: name.into(),
             used: HashSet::new(),
+        });
+    }
+    /// Close the innermost scope, which must be `name`, and return how many
+    /// blinds it noted. In memory they may be used again afterwards; a
+    /// persistent log keeps them.
+    pub fn close_scope(&mut self, name: &str) -> Result<usize, PrivacyError> {
+        match self.scopes.last() {
+            Some(scope) if scope.name == name => {
+                Ok(self.scopes.pop().expect("scope is open").used.len())
+            }
+            Some(scope) => Err(PrivacyError::Internal(format!(
+                "cannot close scope '{}' while '{}' is open",
+                name, scope.name
+            ))),
+            None => Err(PrivacyError::Internal(format!(
+                "scope '{}' is not open",
+                name
+            ))),
         }
     }
+    /// Name of the innermost open scope.
+    pub fn scope(&self) -> Option<&str> {
+        self.scopes.last().map(|s| s.name.as_str())
+    }
+    /// Count `blinds` as already used; returns how many were new.
+    pub fn import_known<B: AsRef<[u8]>>(&mut self, blinds: impl IntoIterator<Item = B>) -> usize {
+        blinds
+            .into_iter()
+            .filter(|b| self.known.insert(b.as_ref().to_vec()))
+            .count()
+    }
+    /// [`Self::import_known`] the blinds listed in `path`
+    /// ([`read_known_blinds`]).
+    pub fn load_known(&mut self, path: impl AsRef<Path>) -> Result<usize, PrivacyError> {
+        let blinds = read_known_blinds(path.as_ref()).map_err(|e| {
+            PrivacyError::Internal(format!("known blinds {}: {}", path.as_ref().display(), e))
+        })?;
+        Ok(self.import_known(blinds))
+    }
     pub fn note_and_check(&mut self, r: &[u8], no_reuse: bool) -> Result<(), PrivacyError> {
         if !no_reuse {
             return Ok(());
         }
-        let key = r.to_vec();
-        if self.used.contains(&key) {
+        if self.known.contains(r) {
             return Err(PrivacyError::BlindingReuse);
         }
-        self.used.insert(key);
+        if let Some(store) = &mut self.store {
+            match store.insert(r) {
+                Ok(true) => {}
+                Ok(false) => return Err(PrivacyError::BlindingReuse),
+                Err(e) => return Err(log_error(store.path(), e)),
+            }
+        } else if self.used.contains(r) || self.scopes.iter().any(|s| s.used.contains(r)) {
+            return Err(PrivacyError::BlindingReuse);
+        }
+        match self.scopes.last_mut() {
+            Some(scope) => {
+                scope.used.insert(r.to_vec());
+            }
+            None if self.store.is_none() => {
+                self.used.insert(r.to_vec());
+            }
+            None => {}
+        }
         Ok(())
     }
 }
 
+fn log_error(path: &Path, err: std::io::Error) -> PrivacyError {
+    PrivacyError::Internal(format!("blinding log {}: {}", path.display(), err))
+}
+
 /// Context: curve + hash selection resolved from AIR bindings.
 pub struct PedersenCtx {
     ped: PedersenPlaceholder,
@@ -1,4 +1,5 @@
 use zkprov_corelib::air::AirProgram;
+use zkprov_corelib::errors::CapabilityError;
 use zkprov_corelib::registry::ensure_builtins_registered;
 use zkprov_corelib::validate::validate_air_against_backend;
 
@@ -45,3 +46,42 @@ fn pedersen_required_fails_on_unknown_curve() {
     air.validate().unwrap();
     assert!(validate_air_against_backend(&air, "native@0.0").is_err());
 }
+
+#[test]
+fn meta_requires_is_checked_against_backend() {
+    ensure_builtins_registered();
+    let air_with = |requires: &str| -> AirProgram {
+        toml::from_str(&format!(
+            r#"
+        [meta]
+        name = "needs_caps"
+        field = "Prime254"
+        hash = "blake3"
+        requires = {requires}
+        [columns]
+        trace_cols = 2
+        [constraints]
+        transition_count = 1
+        boundary_count = 1
+    "#
+        ))
+        .unwrap()
+    };
+
+    let portable = air_with(r#"["keccak", "pedersen"]"#);
+    portable.validate().unwrap();
+    validate_air_against_backend(&portable, "native@0.0").unwrap();
+
+    let lookups = air_with(r#"["pedersen", "lookups"]"#);
+    lookups.validate().unwrap();
+    assert!(matches!(
+        validate_air_against_backend(&lookups, "native@0.0"),
+        Err(CapabilityError::RequirementUnmet { requirement, .. }) if requirement == "lookups"
+    ));
+
+    let unknown = air_with(r#"["teleport"]"#);
+    let err = unknown.validate().unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown meta.requires entry 'teleport'"));
+}
@@ -0,0 +1,88 @@
+use zkprov_corelib::air::diff::{diff_air, Severity};
+use zkprov_corelib::air::parser::parse_air_str;
+
+const AIR: &str = r#"
+[meta]
+name = "counter"
+field = "Prime254"
+hash = "blake3"
+degree_hint = 2
+
+[columns]
+trace_cols = 2
+names = ["a"]
+selectors = ["s_inc"]
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+transitions = ["when s_inc: next.a = cur.a + 1"]
+
+[[public_inputs]]
+name = "x"
+type = "field"
+
+[[public_inputs]]
+name = "y"
+type = "u64"
+"#;
+
+fn diff(new: &str) -> Vec<(Severity, String)> {
+    let old = parse_air_str(AIR).unwrap();
+    let new = parse_air_str(new).unwrap();
+    diff_air(&old, &new)
+        .changes
+        .into_iter()
+        .map(|c| (c.severity, c.path))
+        .collect()
+}
+
+#[test]
+fn identical_airs_have_no_changes() {
+    assert!(diff(AIR).is_empty());
+}
+
+#[test]
+fn reformatting_and_hints_are_cosmetic() {
+    let changes = diff(
+        &AIR.replace("next.a = cur.a + 1", "next.a=cur.a+1")
+            .replace("degree_hint = 2", "degree_hint = 3")
+            .replace("transition_count = 1", "transition_count = 4"),
+    );
+    assert_eq!(changes.len(), 3);
+    assert!(changes.iter().all(|(s, _)| *s == Severity::Cosmetic));
+    assert!(changes
+        .iter()
+        .any(|(_, p)| p == "constraints.transitions[0]"));
+}
+
+#[test]
+fn binding_changes_are_breaking_and_listed_first() {
+    let changed = AIR
+        .replace("cur.a + 1", "cur.a + 2")
+        .replace("field = \"Prime254\"", "field = \"Goldilocks\"")
+        .replace("type = \"u64\"", "type = \"bytes\"")
+        .replace("degree_hint = 2", "degree_hint = 3");
+    let changes = diff(&changed);
+    let paths: Vec<&str> = changes.iter().map(|(_, p)| p.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "meta.field",
+            "constraints.transitions[0]",
+            "public_inputs.y",
+            "degree_hint"
+        ]
+    );
+    assert_eq!(changes[3].0, Severity::Cosmetic);
+
+    // Swapping declarations changes the typed ABI layout.
+    let swapped = AIR.replace(
+        "name = \"x\"\ntype = \"field\"\n\n[[public_inputs]]\nname = \"y\"\ntype = \"u64\"",
+        "name = \"y\"\ntype = \"u64\"\n\n[[public_inputs]]\nname = \"x\"\ntype = \"field\"",
+    );
+    assert_eq!(
+        diff(&swapped),
+        [(Severity::Breaking, "public_inputs".to_string())]
+    );
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::air::{AirIr, AirMeta};
+use zkprov_corelib::config::{AirHints, Config};
+use zkprov_corelib::errors::CapabilityError;
+use zkprov_corelib::registry::ensure_builtins_registered;
+
+const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
+
+fn hinted() -> AirMeta {
+    let mut ir: AirIr = parse_air_file(Path::new(TOY_AIR)).expect("parse toy.air");
+    ir.meta.backend = Some("native@0.0".into());
+    ir.meta.profile = Some("dev-fast".into());
+    ir.meta
+}
+
+fn config(backend: &str, profile: &str, mode: AirHints) -> Config {
+    ensure_builtins_registered();
+    Config::new(backend, "Prime254", "blake3", 2, false, profile).with_air_hints(mode)
+}
+
+#[test]
+fn ignore_leaves_config_untouched() {
+    let mut cfg = config("", "", AirHints::Ignore);
+    assert!(cfg.apply_air_hints(&hinted()).unwrap().is_empty());
+    assert_eq!(cfg.backend_id, "");
+    assert_eq!(cfg.profile_id, "");
+}
+
+#[test]
+fn respect_fills_unset_values() {
+    let mut cfg = config("auto", "", AirHints::Respect);
+    let applied = cfg.apply_air_hints(&hinted()).unwrap();
+    assert_eq!(applied, vec!["backend", "profile"]);
+    assert_eq!(cfg.backend_id, "native@0.0");
+    assert_eq!(cfg.profile_id, "dev-fast");
+}
+
+#[test]
+fn respect_keeps_explicit_values() {
+    let mut cfg = config("ministark@0.1", "balanced", AirHints::Respect);
+    assert!(cfg.apply_air_hints(&hinted()).unwrap().is_empty());
+    assert_eq!(cfg.backend_id, "ministark@0.1");
+    assert_eq!(cfg.profile_id, "balanced");
+}
+
+#[test]
+fn strict_rejects_contradicting_values() {
+    let mut cfg = config("ministark@0.1", "", AirHints::Strict);
+    match cfg.apply_air_hints(&hinted()) {
+        Err(CapabilityError::AirHintConflict { what, config, air }) => {
+            assert_eq!(what, "backend");
+            assert_eq!(config, "ministark@0.1");
+            assert_eq!(air, "native@0.0");
+        }
+        other => panic!("expected AirHintConflict, got {other:?}"),
+    }
+
+    let mut cfg = config("", "balanced", AirHints::Strict);
+    assert!(matches!(
+        cfg.apply_air_hints(&hinted()),
+        Err(CapabilityError::AirHintConflict {
+            what: "profile",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn strict_resolves_backend_aliases() {
+    let mut cfg = config("native@latest", "dev-fast", AirHints::Strict);
+    assert!(cfg.apply_air_hints(&hinted()).unwrap().is_empty());
+    assert_eq!(cfg.backend_id, "native@latest");
+}
+
+#[test]
+fn unset_value_without_hint_is_missing() {
+    let mut meta = hinted();
+    meta.profile = None;
+    let mut cfg = config("native@0.0", "", AirHints::Respect);
+    assert!(matches!(
+        cfg.apply_air_hints(&meta),
+        Err(CapabilityError::AirHintMissing { what: "profile" })
+    ));
+}
+
+#[test]
+fn mode_parses_and_round_trips() {
+    assert_eq!("strict".parse::<AirHints>().unwrap(), AirHints::Strict);
+    assert!("sometimes".parse::<AirHints>().is_err());
+    let cfg = config("native@0.0", "balanced", AirHints::Respect);
+    let json = serde_json::to_value(&cfg).unwrap();
+    assert_eq!(json["air_hints"], "respect");
+    let plain = serde_json::to_value(config("native@0.0", "balanced", AirHints::Ignore)).unwrap();
+    assert!(plain.get("air_hints").is_none());
+    assert_eq!(
+        cfg.config_hash(),
+        config("native@0.0", "balanced", AirHints::Ignore).config_hash()
+    );
+}
@@ -1,4 +1,4 @@
-use zkprov_corelib::air::AirProgram;
+use zkprov_corelib::air::{AirHash, AirProgram};
 
 const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
 const TOY_AIR_SRC: &str = include_str!("../../../examples/air/toy.air");
@@ -9,8 +9,162 @@ fn parse_and_validate_air() {
     assert_eq!(air.meta.name, "toy_merkle");
     assert_eq!(air.columns.trace_cols, 4);
     assert_eq!(air.constraints.transition_count, 3);
-    assert_eq!(air.rows_hint, Some(65536));
+    assert_eq!(air.fixed_rows(), Some(65536));
 
     let inline: AirProgram = toml::from_str(TOY_AIR_SRC).unwrap();
-    assert_eq!(inline.rows_hint, Some(65536));
+    assert_eq!(inline.fixed_rows(), Some(65536));
+}
+
+#[test]
+fn keccak_meta_hash_maps_to_keccak256_capability() {
+    for spelling in ["keccak", "keccak256"] {
+        let src = TOY_AIR_SRC.replace("hash = \"blake3\"", &format!("hash = \"{spelling}\""));
+        let air: AirProgram = toml::from_str(&src).unwrap();
+        assert_eq!(air.meta.hash, AirHash::Keccak);
+        assert_eq!(air.meta.hash.as_str(), "keccak256");
+    }
+    assert_eq!(AirHash::from_capability("keccak256"), Some(AirHash::Keccak));
+    assert_eq!(AirHash::from_capability("Keccak"), Some(AirHash::Keccak));
+    assert_eq!(AirHash::from_capability("sha256"), None);
+}
+
+fn with_fixed(columns: &str) -> String {
+    TOY_AIR_SRC.replace(
+        "periodic_cols = 1\n",
+        &format!("periodic_cols = 1\n{columns}\n"),
+    )
+}
+
+#[test]
+fn fixed_column_contents_are_validated_and_committed() {
+    let implicit: AirProgram = toml::from_str(TOY_AIR_SRC).unwrap();
+    assert_eq!(implicit.columns.fixed_commitment(), None);
+
+    let load = |columns: &str| {
+        let air: AirProgram = toml::from_str(&with_fixed(columns)).unwrap();
+        air.validate().map(|_| air)
+    };
+    let a = load("constants = [7]\nperiodic = [[1, 0, 0, 0]]").unwrap();
+    let b = load("constants = [7]\nperiodic = [[0, 1, 0, 0]]").unwrap();
+    let c = load("constants = [8]\nperiodic = [[1, 0, 0, 0]]").unwrap();
+    let commit = a.columns.fixed_commitment().unwrap();
+    assert_ne!(Some(commit), b.columns.fixed_commitment());
+    assert_ne!(Some(commit), c.columns.fixed_commitment());
+    assert_eq!(
+        Some(commit),
+        load("constants = [7]\nperiodic = [[1, 0, 0, 0]]")
+            .unwrap()
+            .columns
+            .fixed_commitment()
+    );
+
+    for (columns, needle) in [
+        ("constants = [1, 2]", "const_cols = 1"),
+        ("periodic = [[1, 0], [0, 1]]", "periodic_cols = 1"),
+        ("periodic = [[1, 0, 0]]", "not a power of two"),
+        ("periodic = [[1]]", "not a power of two"),
+    ] {
+        let err = load(columns).unwrap_err().to_string();
+        assert!(err.contains(needle), "{columns}: {err}");
+    }
+}
+
+const BOUNDARY_AIR: &str = r#"rows_hint = 8
+
+[meta]
+name = "bounded"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+names = ["acc"]
+
+[constraints]
+transition_count = 1
+boundary_count = 3
+
+[[public_inputs]]
+name = "total"
+type = "u64"
+
+[[public_inputs]]
+name = "blob"
+type = "bytes"
+
+[[boundary]]
+column = "acc"
+row = "first"
+value = 0
+
+[[boundary]]
+column = "acc"
+row = "last"
+public = "total"
+
+[[boundary]]
+column = "c1"
+row = 3
+value = 5
+"#;
+
+#[test]
+fn boundary_entries_are_validated_and_lowered() {
+    let ir = zkprov_corelib::air::parse_air_str(BOUNDARY_AIR).expect("parse");
+    let rendered: Vec<String> = ir
+        .boundary_assertions(8)
+        .unwrap()
+        .iter()
+        .map(|a| format!("{}@{}:{}", a.display, a.row, a.column))
+        .collect();
+    assert_eq!(
+        rendered,
+        [
+            "acc[first] = 0@0:0",
+            "acc[last] = public.total@7:0",
+            "c1[3] = 5@3:1"
+        ]
+    );
+    let last = &ir.boundary_assertions(8).unwrap()[1];
+    let p = zkprov_corelib::crypto::field::prime254_modulus();
+    assert_eq!(
+        last.resolve(&serde_json::json!({"total": "12"}), &p)
+            .unwrap(),
+        12u32.into()
+    );
+    assert!(last.resolve(&serde_json::json!({}), &p).is_err());
+
+    for (from, to, needle) in [
+        (
+            "boundary_count = 3",
+            "boundary_count = 2",
+            "boundary_count is 2",
+        ),
+        ("column = \"c1\"", "column = \"c2\"", "unknown column 'c2'"),
+        ("row = 3", "row = 8", "outside rows_hint"),
+        (
+            "row = 3",
+            "row = \"middle\"",
+            "\"first\", \"last\" or an index",
+        ),
+        (
+            "public = \"total\"",
+            "public = \"nope\"",
+            "unknown public input 'nope'",
+        ),
+        ("public = \"total\"", "public = \"blob\"", "is bytes"),
+        ("value = 5", "value = 5\npublic = \"total\"", "exactly one"),
+        (
+            "row = \"last\"",
+            "row = \"first\"",
+            "duplicate constraint on acc[first]",
+        ),
+    ] {
+        let src = BOUNDARY_AIR.replacen(from, to, 1);
+        let err = format!(
+            "{:#}",
+            zkprov_corelib::air::parse_air_str(&src).unwrap_err()
+        );
+        assert!(err.contains(needle), "{to}: {err}");
+    }
 }
@@ -0,0 +1,122 @@
+use serde_json::json;
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::air::rows::RowsHint;
+use zkprov_corelib::air::AirProgram;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::ProveError;
+
+const AIR: &str = r#"rows_hint = "next_pow2(len(leaves) * 64)"
+
+[meta]
+name = "leaves"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+
+[[public_inputs]]
+name = "leaves"
+type = "bytes"
+
+[[public_inputs]]
+name = "depth"
+type = "u64"
+"#;
+
+fn with_hint(hint: &str) -> String {
+    AIR.replace("next_pow2(len(leaves) * 64)", hint)
+}
+
+fn air_error(hint: &str) -> String {
+    let air: AirProgram = toml::from_str(&with_hint(hint)).unwrap();
+    format!("{:#}", air.validate().unwrap_err())
+}
+
+#[test]
+fn expressions_parse_and_are_checked_statically() {
+    let air: AirProgram = toml::from_str(AIR).unwrap();
+    air.validate().unwrap();
+    assert_eq!(
+        air.rows_hint,
+        Some(RowsHint::Expr("next_pow2(len(leaves) * 64)".into()))
+    );
+    assert_eq!(air.fixed_rows(), None);
+
+    let cases = [
+        (
+            "len(leaf) * 8",
+            "'leaf', which is not a declared public input",
+        ),
+        (
+            "len(depth) * 8",
+            "len() of public input 'depth' of type u64",
+        ),
+        (
+            "leaves * 8",
+            "arithmetic on public input 'leaves' of type bytes",
+        ),
+        ("next_pow2(depth", "expected ')'"),
+        ("sqrt(depth)", "unknown function 'sqrt'"),
+        ("depth ^ 2", "unexpected character '^'"),
+    ];
+    for (hint, needle) in cases {
+        let err = air_error(hint);
+        assert!(err.contains(needle), "{hint}: {err}");
+    }
+}
+
+#[test]
+fn expressions_evaluate_against_public_inputs() {
+    let hint = |src: &str| RowsHint::Expr(src.to_string());
+    let inputs =
+        json!({ "leaves": "0x010203", "depth": "0x10", "doc": { "digest": "0x00", "len": 40 } });
+
+    let rows = |src: &str| hint(src).evaluate(&inputs);
+    assert_eq!(rows("next_pow2(len(leaves) * 64)").unwrap(), 256);
+    assert_eq!(rows("max(depth, 8) * (1 + 1)").unwrap(), 32);
+    assert_eq!(rows("min(next_pow2(len(doc)), 1024)").unwrap(), 64);
+    assert_eq!(RowsHint::Fixed(64).evaluate(&inputs).unwrap(), 64);
+
+    let err = |src: &str| format!("{:#}", rows(src).unwrap_err());
+    assert!(err("len(leaves)").contains("evaluates to 3: rows_hint out of range"));
+    assert!(err("depth * 3").contains("must be a power of two"));
+    assert!(err("depth - 17").contains("16 - 17 is out of range"));
+    assert!(err("missing + 8").contains("public input 'missing' is missing"));
+}
+
+#[test]
+fn native_proofs_pin_the_evaluated_rows() {
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(AIR).unwrap();
+    let config = Config::new("native@latest", "Prime254", "blake3", 2, false, "balanced");
+    let small = PublicInputs::from_json(r#"{"leaves":"0x0102","depth":1}"#).unwrap();
+    let large = PublicInputs::from_json(r#"{"leaves":"0x010203","depth":1}"#).unwrap();
+
+    assert_eq!(
+        zkprov_corelib::resolve_rows(&config, &ir, &small).unwrap(),
+        Some(128)
+    );
+    let proof = zkprov_corelib::prove(&config, &ir, &small).unwrap();
+    zkprov_corelib::verify(&config, &ir, &small, &proof.view()).unwrap();
+
+    // The evaluated rows match a fixed hint of the same size.
+    let mut fixed = ir.clone();
+    fixed.rows_hint = Some(RowsHint::Fixed(128));
+    let pinned = zkprov_corelib::prove(&config, &fixed, &small).unwrap();
+    assert_eq!(pinned.to_bytes(), proof.to_bytes());
+    let other = zkprov_corelib::prove(&config, &ir, &large).unwrap();
+    assert_ne!(other.body, proof.body);
+
+    let empty = PublicInputs::from_json(r#"{"leaves":"0x","depth":1}"#).unwrap();
+    let err = zkprov_corelib::prove(&config, &ir, &empty).unwrap_err();
+    assert!(matches!(err, ProveError::InvalidInput(_)), "{err}");
+    assert!(err
+        .to_string()
+        .contains("evaluates to 1: rows_hint out of range"));
+}
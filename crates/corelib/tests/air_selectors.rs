@@ -0,0 +1,118 @@
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::air::AirProgram;
+
+const SELECTOR_AIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../examples/air/selector_counter.air"
+);
+
+fn air_with(columns: &str, transitions: &str, degree_hint: u32) -> String {
+    format!(
+        r#"
+[meta]
+name = "sel"
+field = "Prime254"
+hash = "blake3"
+degree_hint = {degree_hint}
+
+[columns]
+trace_cols = 4
+{columns}
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+transitions = [{transitions}]
+"#
+    )
+}
+
+#[test]
+fn selector_example_lowers_with_booleanity() {
+    let air = AirProgram::load_from_file(SELECTOR_AIR).expect("load");
+    let lowered = air.transition_constraints().unwrap();
+    let rendered: Vec<String> = lowered.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        rendered,
+        [
+            "(cur.s_inc * (next.a - (cur.a + 1))) = 0",
+            "(cur.s_dbl * (next.a - (cur.a * 2))) = 0",
+            "(cur.s_inc * (cur.s_inc - 1)) = 0",
+            "(cur.s_dbl * (cur.s_dbl - 1)) = 0",
+        ]
+    );
+    assert_eq!(lowered[0].selector, Some(1));
+    assert!(lowered.iter().all(|c| c.degree == 2));
+}
+
+#[test]
+fn gating_raises_degree_and_respects_hint() {
+    let cols = r#"names = ["a", "b"]
+selectors = ["s"]"#;
+    let src = air_with(cols, r#""when s: next.a = cur.a * cur.b""#, 3);
+    let ir = parse_air_str(&src).expect("degree 3 fits hint");
+    assert_eq!(ir.constraints.transitions.len(), 1);
+
+    let too_low = air_with(cols, r#""when s: next.a = cur.a * cur.b""#, 2);
+    let err = parse_air_str(&too_low).unwrap_err();
+    assert!(
+        format!("{err:#}").contains("exceeds degree_hint"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn invalid_selector_usage_is_rejected() {
+    let cols = r#"names = ["a"]
+selectors = ["s"]"#;
+    // gating on a data column
+    assert!(parse_air_str(&air_with(cols, r#""when a: next.a = cur.a""#, 4)).is_err());
+    // undeclared selector
+    assert!(parse_air_str(&air_with(cols, r#""when t: next.a = cur.a""#, 4)).is_err());
+    // more named columns than trace_cols
+    let crowded = r#"names = ["a", "b", "c", "d"]
+selectors = ["s"]"#;
+    assert!(parse_air_str(&air_with(crowded, "", 4)).is_err());
+    // duplicate names
+    let dup = r#"names = ["a"]
+selectors = ["a"]"#;
+    assert!(parse_air_str(&air_with(dup, "", 4)).is_err());
+}
+
+#[test]
+fn programs_without_selectors_are_unchanged() {
+    let src = air_with("", "", 4);
+    let ir = parse_air_str(&src).unwrap();
+    assert!(ir.columns.selectors.is_empty());
+    let toml = toml::to_string(&ir.columns).unwrap();
+    assert!(!toml.contains("selectors"));
+}
+
+#[test]
+fn bare_polynomials_are_constraints_equal_to_zero() {
+    use zkprov_corelib::air::types::Expr;
+
+    let cols = r#"names = ["a", "b"]
+selectors = ["s"]"#;
+    let src = air_with(
+        cols,
+        r#""next.a - cur.a - 1", "when s: next.b - cur.b * cur.b""#,
+        3,
+    );
+    let ir = parse_air_str(&src).unwrap();
+    let lowered = ir.transition_constraints().unwrap();
+    assert_eq!(lowered[0].to_string(), "((next.a - cur.a) - 1) = 0");
+    assert_eq!(
+        lowered[0].expr,
+        Expr::Sub(
+            Box::new(Expr::Sub(Box::new(Expr::Next(0)), Box::new(Expr::Cur(0)))),
+            Box::new(Expr::Const(1)),
+        )
+    );
+    assert_eq!(lowered[0].degree, 1);
+    assert_eq!(
+        lowered[1].to_string(),
+        "(cur.s * (next.b - (cur.b * cur.b))) = 0"
+    );
+    assert_eq!(lowered[1].degree, 3);
+}
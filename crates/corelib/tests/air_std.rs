@@ -0,0 +1,167 @@
+use num_bigint::BigUint;
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::air::{stdlib, AirIr, AirProgram};
+use zkprov_corelib::trace::eval::{boundary_violations, field_modulus, violations};
+use zkprov_corelib::trace::Trace;
+
+const RANGE_AIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../examples/air/range_check.air"
+);
+
+fn program(include: &str, transitions: &str) -> String {
+    format!(
+        r#"
+include = [{include}]
+
+[meta]
+name = "with_std"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 1
+names = ["a"]
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+transitions = [{transitions}]
+"#
+    )
+}
+
+/// 128-row trace for `range_check.air` whose bits on rows 1..=64 are `bits`.
+fn range_trace(bits: &[u64]) -> Trace {
+    let p = field_modulus("Prime254").unwrap();
+    let mut acc = vec![BigUint::from(0u32)];
+    let mut bit = vec![BigUint::from(0u32)];
+    for row in 1..128 {
+        let b = BigUint::from(bits.get(row - 1).copied().unwrap_or(0));
+        acc.push((&acc[row - 1] * 2u32 + &b) % &p);
+        bit.push(b);
+    }
+    Trace::from_biguint_columns("Prime254", &[acc, bit]).unwrap()
+}
+
+fn bits_of(value: u64) -> Vec<u64> {
+    (0..64).rev().map(|i| (value >> i) & 1).collect()
+}
+
+#[test]
+fn range_check_example_merges_the_fragment() {
+    let air = AirProgram::load_from_file(RANGE_AIR).expect("load");
+    assert!(air.include.is_empty());
+    assert_eq!(air.columns.names, ["rc_acc", "rc_bit"]);
+    assert_eq!(air.columns.trace_cols, 2);
+    assert_eq!(air.constraints.transition_count, 2);
+    assert_eq!(air.constraints.boundary_count, 2);
+
+    let ir = AirIr::from(air);
+    let amount = 0xdead_beef_0123_4567u64;
+    let inputs = serde_json::json!({ "amount": amount });
+    let trace = range_trace(&bits_of(amount));
+    assert!(violations(&trace, &ir, None).unwrap().is_empty());
+    assert!(boundary_violations(&trace, &ir, &inputs)
+        .unwrap()
+        .is_empty());
+
+    // A non-boolean "bit" is caught, so rc_acc[64] cannot exceed 64 bits.
+    let mut bits = bits_of(amount);
+    bits[10] = 2;
+    let bad = violations(&range_trace(&bits), &ir, None).unwrap();
+    assert_eq!((bad[0].constraint, bad[0].row), (0, 10));
+}
+
+#[test]
+fn fragments_resolve_by_versioned_id() {
+    let ids: Vec<String> = stdlib::fragments().iter().map(|f| f.id()).collect();
+    assert_eq!(
+        ids,
+        ["std:counter@1", "std:merkle_path@1", "std:range_u64@1"]
+    );
+    for id in &ids {
+        let ir = parse_air_str(&program(&format!("{id:?}"), r#""next.a = cur.a""#))
+            .unwrap_or_else(|e| panic!("{id}: {e:#}"));
+        assert_eq!(ir.columns.names[0], "a");
+    }
+    assert_eq!(stdlib::lookup("std:counter").unwrap().id(), "std:counter@1");
+
+    let ir = parse_air_str(&program(
+        r#""std:counter@1""#,
+        r#""next.a = cur.a + cur.ctr""#,
+    ))
+    .unwrap();
+    assert_eq!(ir.columns.names, ["a", "ctr"]);
+    assert_eq!(ir.constraints.transitions.len(), 2);
+    assert_eq!(ir.boundary.len(), 1);
+    let trace = Trace::from_u64_columns("Prime254", &[vec![5, 5, 6, 8], vec![0, 1, 2, 3]]).unwrap();
+    assert!(violations(&trace, &ir, None).unwrap().is_empty());
+}
+
+#[test]
+fn merkle_path_orders_children_by_direction() {
+    let ir = parse_air_str(&program(r#""std:merkle_path@1""#, r#""next.a = cur.a""#)).unwrap();
+    // Columns: a, node, sib, dir, left, right.
+    let ok = Trace::from_u64_columns(
+        "Prime254",
+        &[
+            vec![0, 0, 0],
+            vec![7, 9, 4],
+            vec![3, 1, 1],
+            vec![0, 1, 0],
+            vec![7, 1, 4],
+            vec![3, 9, 1],
+        ],
+    )
+    .unwrap();
+    assert!(violations(&ok, &ir, None).unwrap().is_empty());
+
+    // Row 1 claims node 9 is the left child with dir = 1.
+    let swapped = Trace::from_u64_columns(
+        "Prime254",
+        &[
+            vec![0, 0, 0],
+            vec![7, 9, 4],
+            vec![3, 1, 1],
+            vec![0, 1, 0],
+            vec![7, 9, 4],
+            vec![3, 1, 1],
+        ],
+    )
+    .unwrap();
+    let bad = violations(&swapped, &ir, None).unwrap();
+    assert_eq!(bad.iter().map(|v| v.row).collect::<Vec<_>>(), [1, 1]);
+}
+
+#[test]
+fn bad_includes_are_rejected() {
+    let err = |include: &str| {
+        format!(
+            "{:#}",
+            parse_air_str(&program(include, r#""next.a = cur.a""#)).unwrap_err()
+        )
+    };
+    assert!(err(r#""std:nope""#).contains("unknown standard fragment 'std:nope'"));
+    assert!(err(r#""std:nope""#).contains("std:range_u64@1"));
+    assert!(err(r#""std:counter@9""#).contains("unknown version"));
+    assert!(err(r#""local:counter""#).contains("expected std:<name>[@<version>]"));
+    assert!(err(r#""std:poseidon2_perm""#).contains("not available yet"));
+    assert!(err(r#""std:counter", "std:counter@1""#).contains("included more than once"));
+
+    // Fragment columns share the program's namespace.
+    let clash = program(r#""std:counter""#, r#""next.a = cur.a""#)
+        .replace(r#"names = ["a"]"#, r#"names = ["ctr"]"#)
+        .replace("cur.a", "cur.ctr")
+        .replace("next.a", "next.ctr");
+    let e = format!("{:#}", parse_air_str(&clash).unwrap_err());
+    assert!(e.contains("including std:counter@1"), "{e}");
+    assert!(e.contains("column 'ctr' is already declared"), "{e}");
+
+    // Programs built by hand must resolve includes before validating.
+    let mut raw: AirProgram =
+        toml::from_str(&program(r#""std:counter""#, r#""next.a = cur.a""#)).unwrap();
+    assert!(raw.validate().is_err());
+    raw.resolve_includes().unwrap();
+    raw.validate().unwrap();
+}
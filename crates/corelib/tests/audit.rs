@@ -0,0 +1,70 @@
+use std::fs;
+
+use tempfile::tempdir;
+use zkprov_corelib::audit::{verify_log, AuditLog, AuditOp, AuditRecord, AuditResult, GENESIS};
+
+fn record(op: AuditOp, result: AuditResult) -> AuditRecord {
+    AuditRecord {
+        actor: "alice".to_string(),
+        op,
+        program_hash: "0x01".to_string(),
+        inputs_hash: "0x02".to_string(),
+        backend: "native@0.0".to_string(),
+        profile: "balanced".to_string(),
+        digest: Some("0x03".to_string()),
+        result,
+        error: (result == AuditResult::Failed).then(|| "VerifyFail".to_string()),
+        duration_ms: 5,
+    }
+}
+
+#[test]
+fn entries_chain_and_verify() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let log = AuditLog::new(&path);
+    let first = log.append(record(AuditOp::Prove, AuditResult::Ok)).unwrap();
+    let second = log
+        .append(record(AuditOp::Verify, AuditResult::Failed))
+        .unwrap();
+    assert_eq!((first.seq, first.prev.as_str()), (0, GENESIS));
+    assert_eq!((second.seq, &second.prev), (1, &first.hash));
+    assert_eq!(second.hash, second.compute_hash());
+
+    // A fresh handle continues the same chain.
+    let third = AuditLog::new(&path)
+        .append(record(AuditOp::Verify, AuditResult::Ok))
+        .unwrap();
+    assert_eq!(third.prev, second.hash);
+
+    let report = verify_log(&path).unwrap();
+    assert_eq!(report.entries, 3);
+    assert_eq!(report.head, third.hash);
+}
+
+#[test]
+fn edits_drops_and_reorders_break_the_chain() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let log = AuditLog::new(&path);
+    for _ in 0..3 {
+        log.append(record(AuditOp::Prove, AuditResult::Ok)).unwrap();
+    }
+    let text = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    let check = |lines: &[&str]| {
+        let bad = dir.path().join("bad.jsonl");
+        fs::write(&bad, lines.join("\n") + "\n").unwrap();
+        format!("{:#}", verify_log(&bad).unwrap_err())
+    };
+
+    let edited = lines[1].replace("\"result\":\"ok\"", "\"result\":\"failed\"");
+    assert!(check(&[lines[0], &edited, lines[2]]).starts_with("line 2: hash"));
+    assert!(check(&[lines[0], lines[2]]).starts_with("line 2: seq 2"));
+    assert!(check(&[lines[1], lines[0], lines[2]]).starts_with("line 1: seq 1"));
+    assert!(check(&[lines[0], "{}"]).starts_with("line 2: not an audit entry"));
+
+    // Appending after a torn write is refused rather than forking the chain.
+    fs::write(&path, &text[..text.len() - 5]).unwrap();
+    assert!(log.append(record(AuditOp::Prove, AuditResult::Ok)).is_err());
+}
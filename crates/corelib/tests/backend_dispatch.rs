@@ -0,0 +1,245 @@
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::{CapabilityError, ProveError, VerifyError};
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{hash64, Proof, ProofHeader, ProofView, HEADER_LEN};
+use zkprov_corelib::registry::{ensure_builtins_registered, register_backend};
+
+const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
+
+/// Body = program name, so dispatch can be observed from the proof.
+struct Echo;
+
+impl ProverBackend for Echo {
+    fn id(&self) -> &'static str {
+        "echo@0.1"
+    }
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            fields: vec!["Prime254"],
+            hashes: vec!["blake3"],
+            fri_arities: vec![2],
+            recursion: "none",
+            lookups: false,
+            curves: vec!["placeholder"],
+            pedersen: true,
+            max_rows: None,
+            incremental: false,
+            nondeterminism: Vec::new(),
+        }
+    }
+    fn prove(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        inputs: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        let header = ProofHeader {
+            backend_id_hash: hash64("BACKEND", self.id().as_bytes()),
+            profile_id_hash: hash64("PROFILE", profile.id.as_bytes()),
+            pubio_hash: hash64("PUBIO", inputs.as_json().as_bytes()),
+            body_len: 0,
+            config_hash: ctx.config_hash,
+            flags: 0,
+        };
+        Ok(Proof::new(header, ir.meta.name.as_bytes().to_vec()))
+    }
+}
+
+impl VerifierBackend for Echo {
+    fn verify(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        _: &PublicInputs,
+        proof: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        // The run's hash travels in the context; the profile stays as loaded.
+        if ctx.hash.as_deref() != Some("blake3") || profile.hash.is_some() {
+            return Err(VerifyError::Rejected("hash not in the context".to_string()));
+        }
+        if proof.body == ir.meta.name.as_bytes() {
+            Ok(())
+        } else {
+            Err(VerifyError::Rejected("name mismatch".to_string()))
+        }
+    }
+}
+
+/// Takes a `batching` option and puts the options it received in the body.
+struct Opts;
+
+impl ProverBackend for Opts {
+    fn id(&self) -> &'static str {
+        "opts@0.1"
+    }
+    fn capabilities(&self) -> Capabilities {
+        Echo.capabilities()
+    }
+    fn prove(
+        &self,
+        _: &AirIr,
+        profile: &Profile,
+        ctx: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        let body = serde_json::to_vec(&ctx.options).unwrap();
+        let header = ProofHeader {
+            backend_id_hash: hash64("BACKEND", self.id().as_bytes()),
+            profile_id_hash: hash64("PROFILE", profile.id.as_bytes()),
+            pubio_hash: 0,
+            body_len: 0,
+            config_hash: 0,
+            flags: 0,
+        };
+        Ok(Proof::new(header, body))
+    }
+    fn options_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "object",
+            "properties": { "batching": { "enum": ["linear", "algebraic"] } },
+            "additionalProperties": false
+        }))
+    }
+}
+
+impl VerifierBackend for Opts {
+    fn verify(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        _: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        Ok(())
+    }
+}
+
+fn echo_config() -> Config {
+    Config::new("echo@0.1", "Prime254", "blake3", 2, false, "balanced")
+}
+
+#[test]
+fn prove_and_verify_dispatch_through_registry() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Echo), Box::new(Echo));
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json(r#"{"a":1}"#).unwrap();
+
+    let proof = zkprov_corelib::prove(&echo_config(), &ir, &inputs).unwrap();
+    assert_eq!(proof.body, b"toy_merkle");
+    assert_eq!(proof.header.body_len, 10);
+    assert_eq!(proof.header.config_hash, echo_config().config_hash());
+
+    let bytes = proof.to_bytes();
+    let decoded = ProofView::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.body.as_ptr(), bytes[HEADER_LEN..].as_ptr());
+    zkprov_corelib::verify(&echo_config(), &ir, &inputs, &decoded).unwrap();
+
+    let mut tampered = decoded.into_proof();
+    tampered.body = b"other_prog".to_vec();
+    assert!(matches!(
+        zkprov_corelib::verify(&echo_config(), &ir, &inputs, &tampered.view()),
+        Err(VerifyError::Rejected(_))
+    ));
+}
+
+#[test]
+fn dispatch_validates_config_before_proving() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Echo), Box::new(Echo));
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json("{}").unwrap();
+    let cfg = Config::new("echo@0.1", "Prime254", "keccak256", 2, false, "balanced");
+    assert!(matches!(
+        zkprov_corelib::prove(&cfg, &ir, &inputs),
+        Err(ProveError::Capability(_))
+    ));
+}
+
+#[test]
+fn backends_without_trace_support_report_unsupported() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Echo), Box::new(Echo));
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json("{}").unwrap();
+    let trace =
+        zkprov_corelib::trace::Trace::from_u64_columns("Prime254", &vec![vec![0; 8]; 4]).unwrap();
+    let err = zkprov_corelib::prove_with_trace(&echo_config(), &ir, &inputs, &trace).unwrap_err();
+    assert!(matches!(err, ProveError::Unsupported { .. }), "{err}");
+
+    // Without the incremental capability, incremental proving falls back to
+    // a full prove from the trace.
+    let mut cache = zkprov_corelib::trace::TraceCache::new();
+    let err = zkprov_corelib::prove_incremental(&echo_config(), &ir, &inputs, &trace, &mut cache)
+        .unwrap_err();
+    match err {
+        ProveError::Unsupported { feature, .. } => assert_eq!(feature, "proving from a trace file"),
+        other => panic!("expected Unsupported, got {other}"),
+    }
+}
+
+#[test]
+fn builtin_native_placeholder_cannot_prove() {
+    ensure_builtins_registered();
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json("{}").unwrap();
+    let cfg = Config::new("native@latest", "Prime254", "blake3", 2, false, "balanced");
+    assert!(matches!(
+        zkprov_corelib::prove(&cfg, &ir, &inputs),
+        Err(ProveError::Unsupported { .. })
+    ));
+}
+
+#[test]
+fn public_inputs_must_be_json() {
+    assert!(matches!(
+        PublicInputs::from_json("not json"),
+        Err(ProveError::InvalidInput(_))
+    ));
+    let inputs = PublicInputs::from_json(r#"{"a": 1}"#).unwrap();
+    assert_eq!(inputs.as_json(), r#"{"a": 1}"#);
+    assert_eq!(inputs.value()["a"], 1);
+}
+
+#[test]
+fn backend_options_are_validated_and_passed_through() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Echo), Box::new(Echo));
+    let _ = register_backend(Box::new(Opts), Box::new(Opts));
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    let inputs = PublicInputs::from_json("{}").unwrap();
+    let opts_config = Config::new("opts@0.1", "Prime254", "blake3", 2, false, "balanced");
+
+    let proof = zkprov_corelib::prove(&opts_config, &ir, &inputs).unwrap();
+    assert_eq!(proof.body, b"null");
+    let options = serde_json::json!({ "batching": "algebraic" });
+    let cfg = opts_config.clone().with_options(options.clone());
+    let proof = zkprov_corelib::prove(&cfg, &ir, &inputs).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&proof.body).unwrap(),
+        options
+    );
+
+    let rejected = [
+        opts_config.with_options(serde_json::json!({ "batching": "fast" })),
+        echo_config().with_options(options),
+    ];
+    for cfg in rejected {
+        let err = zkprov_corelib::prove(&cfg, &ir, &inputs).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ProveError::Capability(CapabilityError::OptionsInvalid { .. })
+            ),
+            "{err}"
+        );
+    }
+}
@@ -15,3 +15,82 @@ fn h2f_basic_bounds() {
     assert!(y < p);
     assert_ne!(x, y);
 }
+
+use num_bigint::BigUint;
+use zkprov_corelib::crypto::field::{
+    field_by_id, BabyBear, Goldilocks, Prime254, PrimeField, BABYBEAR_MODULUS, FIELDS,
+    GOLDILOCKS_MODULUS,
+};
+
+#[test]
+fn fields_are_found_by_id() {
+    assert_eq!(
+        field_by_id("Prime254").unwrap().modulus(),
+        prime254_modulus()
+    );
+    assert_eq!(
+        field_by_id("Goldilocks").unwrap().modulus(),
+        BigUint::from(GOLDILOCKS_MODULUS)
+    );
+    assert_eq!(
+        field_by_id("BabyBear").unwrap().modulus(),
+        BigUint::from(BABYBEAR_MODULUS)
+    );
+    assert!(field_by_id("Mersenne31").is_none());
+    assert_eq!(Prime254.byte_len(), 32);
+    assert_eq!(Goldilocks.byte_len(), 8);
+    assert_eq!(BabyBear.byte_len(), 4);
+}
+
+#[test]
+fn arithmetic_is_modular_in_every_field() {
+    for f in FIELDS {
+        let p = f.modulus();
+        let a = &p - 3u32;
+        let b = BigUint::from(5u32);
+        assert_eq!(f.add(&a, &b), BigUint::from(2u32), "{}", f.id());
+        assert_eq!(f.sub(&b, &a), BigUint::from(8u32), "{}", f.id());
+        assert_eq!(f.mul(&a, &b), &p - 15u32, "{}", f.id());
+        assert_eq!(f.add(&a, &f.neg(&a)), BigUint::from(0u32), "{}", f.id());
+        let inv = f.inverse(&a).unwrap();
+        assert_eq!(f.mul(&a, &inv), BigUint::from(1u32), "{}", f.id());
+        assert!(f.inverse(&p).is_none(), "{}", f.id());
+    }
+    // The placeholder Prime254 modulus is a multiple of 11.
+    assert!(Prime254.inverse(&BigUint::from(11u32)).is_none());
+    assert!(BabyBear.inverse(&BigUint::from(11u32)).is_some());
+}
+
+#[test]
+fn canonical_bytes_round_trip_and_reject_non_canonical() {
+    for f in FIELDS {
+        let x = f.hash_to_field(&hash_one_shot::<Blake3>(f.id().as_bytes()));
+        let bytes = f.canonical_bytes(&x);
+        assert_eq!(bytes.len(), f.byte_len());
+        assert_eq!(f.read_canonical(&bytes).unwrap(), x);
+
+        let p = f.modulus().to_bytes_be();
+        let mut padded = vec![0u8; f.byte_len() - p.len()];
+        padded.extend_from_slice(&p);
+        assert!(f.read_canonical(&padded).is_err(), "{}", f.id());
+        assert!(f.read_canonical(&bytes[1..]).is_err(), "{}", f.id());
+    }
+}
+
+#[test]
+fn montgomery_form_round_trips() {
+    // Goldilocks R = 2^64 mod p = 2^32 - 1.
+    assert_eq!(Goldilocks.montgomery_r(), BigUint::from(0xFFFF_FFFFu64));
+    for f in FIELDS {
+        let x = f.hash_to_field(b"montgomery");
+        let m = f.montgomery_encode(&x);
+        assert_eq!(m, f.mul(&x, &f.montgomery_r()));
+        assert_eq!(f.montgomery_decode(&m), x, "{}", f.id());
+    }
+}
+
+#[test]
+fn h2f_matches_prime254_hash_to_field() {
+    let d = hash_one_shot::<Blake3>(b"hello");
+    assert_eq!(h2f_32_be(d), Prime254.hash_to_field(&d));
+}
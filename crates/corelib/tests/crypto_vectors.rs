@@ -0,0 +1,44 @@
+use zkprov_corelib::crypto::registry::HASHES;
+use zkprov_corelib::crypto::vectors::{generate, merkle_root_by_id, SCHEMA};
+
+const CHECKED_IN: &str = include_str!("../../../examples/vectors/crypto.json");
+
+#[test]
+fn vectors_match_the_checked_in_file() {
+    // Regenerate with `zkd vectors --pretty -o examples/vectors/crypto.json`
+    // only for a deliberate, versioned change of a hash or commitment.
+    let expected: serde_json::Value = serde_json::from_str(CHECKED_IN).unwrap();
+    assert_eq!(serde_json::to_value(generate()).unwrap(), expected);
+}
+
+#[test]
+fn vectors_cover_every_hash_and_known_answers() {
+    let v = generate();
+    assert_eq!(v.schema, SCHEMA);
+    for h in HASHES {
+        assert!(v.hashes.iter().any(|x| x.hash == h.id), "{}", h.id);
+        assert!(v.pedersen.iter().any(|x| x.hash == h.id), "{}", h.id);
+        assert!(v.merkle.iter().any(|x| x.hash == h.id), "{}", h.id);
+    }
+    let unlabeled = |hash: &str, msg: &str| {
+        v.hashes
+            .iter()
+            .find(|x| x.hash == hash && x.label.is_empty() && x.msg == msg)
+            .unwrap()
+    };
+    // Standard BLAKE3("") and Keccak-256("abc").
+    let blake3 = unlabeled("blake3", "0x");
+    assert_eq!(
+        blake3.hash32,
+        "0xaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+    assert_eq!(blake3.hash64, "0xa6a1f9f5b94913af");
+    assert_eq!(
+        unlabeled("keccak256", "0x616263").hash32,
+        "0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+
+    assert!(merkle_root_by_id("keccak", 4, &[b"x".to_vec()]).is_some());
+    assert!(merkle_root_by_id("blake3", 3, &[b"x".to_vec()]).is_none());
+    assert!(merkle_root_by_id("sha1", 2, &[b"x".to_vec()]).is_none());
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use zkprov_corelib::deadline::{parse_duration, Deadline, Phases};
+use zkprov_corelib::errors::ProveError;
+
+#[test]
+fn durations_parse_with_units() {
+    assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+    assert_eq!(
+        parse_duration("1500ms").unwrap(),
+        Duration::from_millis(1500)
+    );
+    assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+    assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+    assert_eq!(
+        parse_duration("30d").unwrap(),
+        Duration::from_secs(2_592_000)
+    );
+    for bad in ["", "s", "1.5s", "10w", "-1s"] {
+        assert!(parse_duration(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn phases_time_out_after_the_budget() {
+    let mut phases = Phases::new(None);
+    phases.finish("trace").unwrap();
+    assert_eq!(phases.stats()[0].phase, "trace");
+
+    let mut phases = Phases::new(Some(Deadline::after(Duration::from_secs(3600))));
+    phases.finish("trace").unwrap();
+
+    let mut phases = Phases::new(Some(Deadline::after(Duration::ZERO)));
+    let err = phases.finish("commit").unwrap_err();
+    assert!(
+        matches!(&err, ProveError::TimedOut { phase, phases, .. }
+            if phase == "commit" && phases.len() == 1),
+        "{err}"
+    );
+    assert!(err
+        .to_string()
+        .starts_with("proving deadline of 0ms exceeded after phase 'commit'"));
+}
@@ -0,0 +1,177 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::determinism::{audit, inspect, Severity};
+use zkprov_corelib::errors::{ProveError, VerifyError};
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{hash64, Proof, ProofHeader, ProofView};
+use zkprov_corelib::registry::{ensure_builtins_registered, register_backend};
+
+const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
+
+/// Declares a thread-ordered reduction and puts a global counter in the
+/// body, so no two proofs are alike.
+struct Racy;
+
+static PROOFS: AtomicU64 = AtomicU64::new(0);
+
+impl ProverBackend for Racy {
+    fn id(&self) -> &'static str {
+        "racy@0.1"
+    }
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            fields: vec!["Prime254"],
+            hashes: vec!["blake3"],
+            fri_arities: vec![2],
+            recursion: "none",
+            lookups: false,
+            curves: vec!["placeholder"],
+            pedersen: true,
+            max_rows: None,
+            incremental: false,
+            nondeterminism: vec!["parallel-reduction"],
+        }
+    }
+    fn prove(
+        &self,
+        _: &AirIr,
+        profile: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        let header = ProofHeader {
+            backend_id_hash: hash64("BACKEND", self.id().as_bytes()),
+            profile_id_hash: hash64("PROFILE", profile.id.as_bytes()),
+            pubio_hash: 0,
+            body_len: 0,
+            config_hash: 0,
+            flags: 0,
+        };
+        let n = PROOFS.fetch_add(1, Ordering::SeqCst);
+        Ok(Proof::new(header, n.to_le_bytes().to_vec()))
+    }
+}
+
+impl VerifierBackend for Racy {
+    fn verify(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        _: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        Ok(())
+    }
+}
+
+/// Same capabilities without the declared source; the body is the program
+/// name, so every proof is identical.
+struct Steady;
+
+impl ProverBackend for Steady {
+    fn id(&self) -> &'static str {
+        "steady@0.1"
+    }
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            nondeterminism: Vec::new(),
+            ..Racy.capabilities()
+        }
+    }
+    fn prove(
+        &self,
+        ir: &AirIr,
+        profile: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        let header = ProofHeader {
+            backend_id_hash: hash64("BACKEND", self.id().as_bytes()),
+            profile_id_hash: hash64("PROFILE", profile.id.as_bytes()),
+            pubio_hash: 0,
+            body_len: 0,
+            config_hash: 0,
+            flags: 0,
+        };
+        Ok(Proof::new(header, ir.meta.name.as_bytes().to_vec()))
+    }
+}
+
+impl VerifierBackend for Steady {
+    fn verify(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        _: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        Ok(())
+    }
+}
+
+fn config(backend: &str) -> Config {
+    Config::new(backend, "Prime254", "blake3", 2, false, "balanced")
+}
+
+fn toy() -> (AirIr, PublicInputs) {
+    let ir = parse_air_file(TOY_AIR.as_ref()).unwrap();
+    (ir, PublicInputs::from_json(r#"{"a":1}"#).unwrap())
+}
+
+#[test]
+fn identical_proofs_pass_across_thread_counts() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Steady), Box::new(Steady));
+    let (ir, inputs) = toy();
+    let report = audit(&config("steady@0.1"), &ir, &inputs, &[1, 3]).unwrap();
+    assert!(report.deterministic, "{report:?}");
+    assert_eq!(report.warnings(), 0);
+    assert_eq!(report.runs.len(), 2);
+    assert_eq!(report.runs[1].threads, 3);
+    assert_eq!(report.runs[1].digests.len(), 3);
+    assert!(report.runs[0].digests[0].starts_with("0x"));
+    assert!(report
+        .findings
+        .iter()
+        .any(|f| f.source == "platform-intrinsics" && f.origin == "build"));
+}
+
+#[test]
+fn declared_sources_warn_and_differing_digests_fail() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Racy), Box::new(Racy));
+    let findings = inspect("racy@0.1").unwrap();
+    let warning = findings
+        .iter()
+        .find(|f| f.severity == Severity::Warning)
+        .expect("declared source");
+    assert_eq!(warning.source, "parallel-reduction");
+    assert_eq!(warning.origin, "backend racy@0.1");
+
+    let (ir, inputs) = toy();
+    let report = audit(&config("racy@0.1"), &ir, &inputs, &[1, 2]).unwrap();
+    assert!(!report.deterministic);
+    assert_eq!(report.warnings(), 1);
+}
+
+#[test]
+fn audit_needs_two_proofs_and_a_known_backend() {
+    ensure_builtins_registered();
+    let (ir, inputs) = toy();
+    let config = config("native@0.0");
+    for threads in [&[1][..], &[0, 4][..], &[]] {
+        assert!(matches!(
+            audit(&config, &ir, &inputs, threads),
+            Err(ProveError::InvalidInput(_))
+        ));
+    }
+    assert!(inspect("nope@9.9").is_err());
+}
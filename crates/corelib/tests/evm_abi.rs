@@ -1,7 +1,9 @@
+use zkprov_corelib::air::parser::parse_air_str;
 use zkprov_corelib::evm::abi::{
     decode_body, decode_meta, decode_public_io, encode_body, encode_meta, encode_public_io,
 };
 use zkprov_corelib::evm::digest::keccak256_bytes;
+use zkprov_corelib::evm::pubio::{decode_typed, encode_typed, solidity_struct, typed_digest};
 use zkprov_corelib::proof::ProofHeader;
 
 #[test]
@@ -27,6 +29,8 @@ fn abi_round_trip_meta_and_body() {
         profile_id_hash: 7,
         pubio_hash: 1337,
         body_len: 5,
+        config_hash: 99,
+        flags: 0,
     };
     let body = b"hello";
     let json = "{\"foo\":42}";
@@ -44,6 +48,140 @@ fn abi_round_trip_meta_and_body() {
     assert_eq!(decoded_io, json);
 }
 
+const TYPED_AIR: &str = r#"
+[meta]
+name = "io_demo"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 1
+
+[[public_inputs]]
+name = "root"
+type = "field"
+
+[[public_inputs]]
+name = "nonce"
+type = "u64"
+
+[[public_inputs]]
+name = "memo"
+type = "bytes"
+"#;
+
+#[test]
+fn typed_public_io_is_abi_encode_of_declared_inputs() {
+    let ir = parse_air_str(TYPED_AIR).unwrap();
+    let inputs = serde_json::json!({"root": "5", "nonce": 9, "memo": "0xabcd"});
+    let encoded = encode_typed(&ir, &inputs).unwrap();
+
+    // abi.encode(uint256 5, uint64 9, bytes 0xabcd): two words, offset, len, data.
+    let mut expected = Vec::new();
+    for word in [5u8, 9, 0x60, 2] {
+        expected.extend_from_slice(&[0u8; 31]);
+        expected.push(word);
+    }
+    expected.extend_from_slice(&[0xab, 0xcd]);
+    expected.extend_from_slice(&[0u8; 30]);
+    assert_eq!(encoded, expected);
+    assert_eq!(
+        typed_digest(&ir, &inputs).unwrap(),
+        keccak256_bytes(&expected)
+    );
+
+    let decoded = decode_typed(&ir, &encoded).unwrap();
+    assert_eq!(
+        decoded,
+        serde_json::json!({"root": "5", "nonce": 9, "memo": "0xabcd"})
+    );
+}
+
+#[test]
+fn typed_public_io_rejects_unbound_or_out_of_range_values() {
+    let ir = parse_air_str(TYPED_AIR).unwrap();
+    let missing = serde_json::json!({"root": 1, "nonce": 1});
+    assert!(encode_typed(&ir, &missing).is_err());
+    let extra = serde_json::json!({"root": 1, "nonce": 1, "memo": "", "other": 1});
+    assert!(encode_typed(&ir, &extra).is_err());
+    let modulus = "0x3fffffffffffffffffffffffffffffff81000000000000000000000000000001";
+    let too_big = serde_json::json!({"root": modulus, "nonce": 1, "memo": ""});
+    assert!(encode_typed(&ir, &too_big).is_err());
+}
+
+#[test]
+fn solidity_struct_mirrors_declared_inputs() {
+    let ir = parse_air_str(TYPED_AIR).unwrap();
+    let sol = solidity_struct(&ir);
+    assert!(sol.contains(
+        "struct IoDemoPublicIO {\n    uint256 root;\n    uint64 nonce;\n    bytes memo;\n}"
+    ));
+    assert!(sol.contains("keccak256(abi.encode(io.root, io.nonce, io.memo))"));
+}
+
+const NUMERIC_AIR: &str = r#"
+[meta]
+name = "ledger"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 1
+
+[[public_inputs]]
+name = "supply"
+type = "u128"
+
+[[public_inputs]]
+name = "delta"
+type = "i64"
+
+[[public_inputs]]
+name = "price"
+type = "fixed(2)"
+"#;
+
+#[test]
+fn typed_public_io_encodes_wide_signed_and_fixed_inputs() {
+    let ir = parse_air_str(NUMERIC_AIR).unwrap();
+    let inputs = serde_json::json!({"supply": "340282366920938463463374607431768211455", "delta": -1, "price": "-12.5"});
+    let encoded = encode_typed(&ir, &inputs).unwrap();
+
+    // uint128 max, then int64 -1 and int128 -1250 sign-extended to a word.
+    let signed_word = |v: i128| [[0xff; 16], v.to_be_bytes()].concat();
+    let expected = [
+        [[0u8; 16], [0xff; 16]].concat(),
+        signed_word(-1),
+        signed_word(-1250),
+    ]
+    .concat();
+    assert_eq!(encoded, expected);
+
+    let decoded = decode_typed(&ir, &encoded).unwrap();
+    assert_eq!(
+        decoded,
+        serde_json::json!({"supply": "340282366920938463463374607431768211455", "delta": -1, "price": "-12.50"})
+    );
+    assert!(encode_typed(
+        &ir,
+        &serde_json::json!({"supply": 1, "delta": 0, "price": "0.001"})
+    )
+    .is_err());
+
+    let sol = solidity_struct(&ir);
+    assert!(sol.contains(
+        "struct LedgerPublicIO {\n    uint128 supply;\n    int64 delta;\n    int128 price;\n}"
+    ));
+}
+
 mod hex {
     pub fn decode(s: &str) -> Result<Vec<u8>, String> {
         if !s.len().is_multiple_of(2) {
@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::PathBuf;
+
+use zkprov_backend_native::native_prove;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::evm::aggregate::{aggregate_root, leaf_hash, Aggregate};
+use zkprov_corelib::evm::digest::{digest_D, keccak256_bytes};
+use zkprov_corelib::proof::{ProofHeader, HEADER_LEN};
+
+const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
+
+fn testdata_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/evm_verifier/testdata")
+}
+
+fn hex32(s: &str) -> [u8; 32] {
+    let s = s.trim_start_matches("0x");
+    let mut out = [0u8; 32];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+    }
+    out
+}
+
+fn toy_digest(inputs: &str) -> [u8; 32] {
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let proof = native_prove(&cfg, inputs, TOY_AIR).expect("prove");
+    let header = ProofHeader::decode(&proof[..HEADER_LEN]).unwrap();
+    digest_D(&header, &proof[HEADER_LEN..])
+}
+
+#[test]
+fn aggregate_matches_fixture() {
+    let digests: Vec<[u8; 32]> = (1..=3)
+        .map(|n| toy_digest(&format!(r#"{{"a":{n}}}"#)))
+        .collect();
+    let agg = Aggregate::from_digests(&digests, true).unwrap();
+
+    let fixture: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(testdata_dir().join("aggregate.json")).unwrap())
+            .unwrap();
+    assert_eq!(agg.root, hex32(fixture["root"].as_str().unwrap()));
+    assert_eq!(agg.count, fixture["count"].as_u64().unwrap());
+    let leaves: Vec<[u8; 32]> = fixture["leaves"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| hex32(l.as_str().unwrap()))
+        .collect();
+    assert_eq!(agg.leaves.as_deref(), Some(leaves.as_slice()));
+
+    let abi = fs::read(testdata_dir().join("aggregate.abi")).unwrap();
+    assert_eq!(agg.encode(), abi);
+    assert_eq!(Aggregate::decode(&abi).unwrap(), agg);
+    assert!(agg.verify().unwrap());
+}
+
+#[test]
+fn tree_rules_are_domain_separated_and_carry_odd_nodes() {
+    let d = [[1u8; 32], [2u8; 32], [3u8; 32]];
+    let node = |l: &[u8; 32], r: &[u8; 32]| {
+        let mut buf = vec![0x01];
+        buf.extend_from_slice(l);
+        buf.extend_from_slice(r);
+        keccak256_bytes(&buf)
+    };
+    let l: Vec<[u8; 32]> = d.iter().map(leaf_hash).collect();
+    assert_eq!(l[0], keccak256_bytes(&[&[0u8][..], &d[0][..]].concat()));
+    assert_eq!(aggregate_root(&d[..1]).unwrap(), l[0]);
+    assert_eq!(
+        aggregate_root(&d).unwrap(),
+        node(&node(&l[0], &l[1]), &l[2])
+    );
+    assert!(aggregate_root(&[]).is_err());
+
+    // Root-only aggregates omit leaves and cannot be self-verified.
+    let root_only = Aggregate::from_digests(&d, false).unwrap();
+    let decoded = Aggregate::decode(&root_only.encode()).unwrap();
+    assert_eq!(decoded, root_only);
+    assert!(decoded.verify().is_err());
+
+    // Reordering leaves changes the root.
+    assert_ne!(
+        aggregate_root(&[d[1], d[0], d[2]]).unwrap(),
+        aggregate_root(&d).unwrap()
+    );
+}
@@ -2,9 +2,11 @@ use std::fs;
 use std::path::PathBuf;
 
 use zkprov_backend_native::native_prove;
+use zkprov_corelib::backend::PublicInputs;
 use zkprov_corelib::config::Config;
 use zkprov_corelib::evm::digest::digest_D;
-use zkprov_corelib::proof::ProofHeader;
+use zkprov_corelib::evm::fixtures;
+use zkprov_corelib::proof::{ProofHeader, HEADER_LEN};
 
 const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
 
@@ -14,10 +16,10 @@ fn evm_digest_matches_native_proof() {
     let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
     let inputs = r#"{"a":1,"b":[2,3]}"#;
     let proof = native_prove(&cfg, inputs, TOY_AIR).expect("native prove");
-    assert!(proof.len() > 40, "proof must contain header + body");
+    assert!(proof.len() > HEADER_LEN, "proof must contain header + body");
 
-    let header = ProofHeader::decode(&proof[0..40]).expect("decode header");
-    let body = &proof[40..];
+    let header = ProofHeader::decode(&proof[..HEADER_LEN]).expect("decode header");
+    let body = &proof[HEADER_LEN..];
     assert_eq!(
         body.len() as u64,
         header.body_len,
@@ -36,26 +38,13 @@ fn evm_digest_matches_native_proof() {
         .join("examples")
         .join("evm_verifier")
         .join("testdata");
-    fs::create_dir_all(&testdata_dir).expect("create testdata dir");
+    let inputs = PublicInputs::from_json(inputs).unwrap();
+    let paths = fixtures::write_all(&testdata_dir, &header, body, &inputs).expect("write fixtures");
 
-    let digest_path = testdata_dir.join("digest.hex");
-    fs::write(&digest_path, format!("{}\n", digest_hex)).expect("write digest");
-
-    let meta_path = testdata_dir.join("meta.json");
-    let meta = serde_json::json!({
-        "backendId": header.backend_id_hash,
-        "profileId": header.profile_id_hash,
-        "pubioHash": header.pubio_hash,
-        "bodyLen": header.body_len,
-    });
-    let meta_json = serde_json::to_string_pretty(&meta).expect("serialize meta");
-    fs::write(&meta_path, format!("{}\n", meta_json)).expect("write meta");
-
-    let body_path = testdata_dir.join("body.bin");
-    fs::write(&body_path, body).expect("write body");
-
-    for path in [&digest_path, &meta_path, &body_path] {
+    for path in &paths {
         let metadata = fs::metadata(path).expect("metadata");
         assert!(metadata.len() > 0, "file {:?} should not be empty", path);
     }
+    let written = fs::read_to_string(testdata_dir.join("digest.hex")).expect("read digest");
+    assert_eq!(written.trim(), digest_hex);
 }
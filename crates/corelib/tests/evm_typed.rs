@@ -0,0 +1,75 @@
+use zkprov_corelib::evm::digest::keccak256_bytes;
+use zkprov_corelib::evm::typed::{
+    program_hash, public_inputs_hash, TypedDomain, TypedSubmission, DOMAIN_NAME, DOMAIN_VERSION,
+};
+
+fn word_u64(x: u64) -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[24..].copy_from_slice(&x.to_be_bytes());
+    w
+}
+
+fn word_addr(a: [u8; 20]) -> [u8; 32] {
+    let mut w = [0u8; 32];
+    w[12..].copy_from_slice(&a);
+    w
+}
+
+fn sample() -> (TypedDomain, TypedSubmission) {
+    let domain = TypedDomain {
+        chain_id: 31337,
+        verifying_contract: [0x11; 20],
+    };
+    let submission = TypedSubmission {
+        digest: [0xaa; 32],
+        program_hash: program_hash(b"[meta]\nname = \"toy\"\n"),
+        public_inputs_hash: public_inputs_hash(r#"{"a":1}"#),
+        context: [0x01; 32],
+    };
+    (domain, submission)
+}
+
+#[test]
+fn signing_hash_matches_manual_eip712() {
+    let (domain, s) = sample();
+
+    let domain_type = keccak256_bytes(
+        b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+    );
+    let mut enc = domain_type.to_vec();
+    enc.extend_from_slice(&keccak256_bytes(DOMAIN_NAME.as_bytes()));
+    enc.extend_from_slice(&keccak256_bytes(DOMAIN_VERSION.as_bytes()));
+    enc.extend_from_slice(&word_u64(domain.chain_id));
+    enc.extend_from_slice(&word_addr(domain.verifying_contract));
+    let separator = keccak256_bytes(&enc);
+    assert_eq!(domain.separator(), separator);
+
+    let type_hash = keccak256_bytes(
+        b"ProofSubmission(bytes32 digest,bytes32 programHash,bytes32 publicInputsHash,bytes32 context)",
+    );
+    let mut enc = type_hash.to_vec();
+    for w in [s.digest, s.program_hash, s.public_inputs_hash, s.context] {
+        enc.extend_from_slice(&w);
+    }
+    let struct_hash = keccak256_bytes(&enc);
+    assert_eq!(s.struct_hash(), struct_hash);
+
+    let mut enc = vec![0x19, 0x01];
+    enc.extend_from_slice(&separator);
+    enc.extend_from_slice(&struct_hash);
+    assert_eq!(s.signing_hash(domain), keccak256_bytes(&enc));
+}
+
+#[test]
+fn domain_binding_prevents_replay() {
+    let (domain, s) = sample();
+    let other_chain = TypedDomain {
+        chain_id: 1,
+        ..domain
+    };
+    assert_ne!(s.signing_hash(domain), s.signing_hash(other_chain));
+
+    let json = s.typed_data_json(domain);
+    assert_eq!(json["primaryType"], "ProofSubmission");
+    assert_eq!(json["domain"]["chainId"], 31337);
+}
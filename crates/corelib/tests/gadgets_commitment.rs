@@ -72,3 +72,54 @@ fn different_hash_ids_change_commitment() {
     };
     assert_ne!(p1.commit(&w).unwrap().0, p2.commit(&w).unwrap().0);
 }
+
+#[test]
+fn commit_kinds_match_the_air_taxonomy() {
+    use zkprov_corelib::crypto::field::prime254_modulus;
+    use zkprov_corelib::gadgets::commitment::CommitKind;
+
+    for kind in CommitKind::ALL {
+        assert_eq!(kind.as_str().parse::<CommitKind>().unwrap(), kind);
+    }
+    assert!("poseidon".parse::<CommitKind>().is_err());
+
+    let blind = [7u8; 32];
+    let w = Witness {
+        msg: b"hello",
+        blind: &blind,
+    };
+    let pedersen = CommitKind::Pedersen.scheme(Some("blake3")).unwrap();
+    let placeholder = c(PedersenParams {
+        hash_id: "blake3".into(),
+    });
+    assert_eq!(
+        pedersen.commit(&w).unwrap(),
+        placeholder.commit(&w).unwrap()
+    );
+    assert!(CommitKind::Pedersen.scheme(None).is_err());
+
+    // Poseidon commitments are canonical Prime254 elements.
+    let poseidon = CommitKind::PoseidonCommit
+        .scheme(Some("poseidon2"))
+        .unwrap();
+    let commitment = poseidon.commit(&w).unwrap();
+    assert!(num_bigint::BigUint::from_bytes_be(commitment.as_bytes()) < prime254_modulus());
+    assert!(CommitKind::PoseidonCommit
+        .format(&commitment)
+        .starts_with("0x"));
+    assert_ne!(commitment, placeholder.commit(&w).unwrap());
+
+    let keccak = CommitKind::KeccakCommit.scheme(None).unwrap();
+    let commitment = keccak.commit(&w).unwrap();
+    assert_eq!(
+        commitment.0,
+        alloy_primitives::keccak256([&b"hello"[..], &blind].concat()).0
+    );
+    assert!(keccak.open(&w, &commitment).unwrap());
+    let short = Witness {
+        msg: b"hello",
+        blind: b"r",
+    };
+    assert!(keccak.commit(&short).is_err());
+    assert!(CommitKind::KeccakCommit.scheme(Some("blake3")).is_err());
+}
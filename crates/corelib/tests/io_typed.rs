@@ -0,0 +1,121 @@
+use num_bigint::BigUint;
+use serde_json::json;
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::air::public::PublicValue;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::crypto::field::prime254_modulus;
+use zkprov_corelib::evm::pubio::PubIoEncoding;
+use zkprov_corelib::io::TypedInputs;
+use zkprov_corelib::proof::pubio::UndeclaredInputs;
+
+const AIR: &str = r#"
+[meta]
+name = "typed"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+
+[[public_inputs]]
+name = "root"
+type = "field"
+
+[[public_inputs]]
+name = "amount"
+type = "fixed(2)"
+
+[[public_inputs]]
+name = "delta"
+type = "i64"
+"#;
+
+fn ir() -> AirIr {
+    parse_air_str(AIR).unwrap()
+}
+
+fn parse(inputs: serde_json::Value) -> anyhow::Result<TypedInputs> {
+    TypedInputs::parse(&ir().public_inputs, &inputs, UndeclaredInputs::Ignore)
+}
+
+#[test]
+fn parses_declared_inputs_in_declaration_order() {
+    let typed = parse(json!({"delta": -3, "amount": "1.5", "root": "0x10", "note": 1})).unwrap();
+    let names: Vec<&str> = typed.iter().map(|i| i.decl.name.as_str()).collect();
+    assert_eq!(names, ["root", "amount", "delta"]);
+    assert_eq!(
+        typed.get("root"),
+        Some(&PublicValue::Field(BigUint::from(16u32)))
+    );
+    assert_eq!(
+        typed.get("amount"),
+        Some(&PublicValue::Fixed {
+            units: 150,
+            scale: 2
+        })
+    );
+    assert_eq!(typed.get("note"), None);
+    typed.require_all().unwrap();
+
+    let p = prime254_modulus();
+    assert_eq!(
+        typed.field_elements(&p).unwrap(),
+        [BigUint::from(16u32), BigUint::from(150u32), &p - 3u32]
+    );
+}
+
+#[test]
+fn canonical_json_ignores_layout() {
+    let a = parse(json!({"root": 7, "amount": "1.50", "delta": 0})).unwrap();
+    let b = parse(json!({"delta": "0", "amount": "1.5", "root": "0x7", "x": []})).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(
+        a.canonical_json(),
+        json!({"amount": "1.50", "delta": 0, "root": "7"})
+    );
+    assert_eq!(a.binding(), b.binding());
+
+    // The binding is the one proofs hash.
+    let bound = PublicInputs::from_json(r#"{"root":7,"amount":"1.50","delta":0}"#)
+        .unwrap()
+        .bind(PubIoEncoding::Json, &ir(), UndeclaredInputs::Ignore)
+        .unwrap();
+    assert_eq!(bound.binding(), a.binding());
+    assert_eq!(bound.typed(&ir()).unwrap(), a);
+}
+
+#[test]
+fn missing_inputs_are_reported() {
+    let typed = parse(json!({"root": 7})).unwrap();
+    assert_eq!(typed.missing(), ["amount", "delta"]);
+    let err = typed.require_all().unwrap_err().to_string();
+    assert_eq!(err, "missing declared public inputs: amount, delta");
+    assert!(typed.field_elements(&prime254_modulus()).is_err());
+    assert_eq!(typed.canonical_json(), json!({"root": "7"}));
+    assert_ne!(
+        typed.binding(),
+        parse(json!({"root": 7, "amount": "0", "delta": 0}))
+            .unwrap()
+            .binding()
+    );
+}
+
+#[test]
+fn rejects_mistyped_and_undeclared_inputs() {
+    let err = parse(json!({"amount": "1.255"})).unwrap_err().to_string();
+    assert!(err.starts_with("public input 'amount' (fixed(2))"), "{err}");
+    assert!(parse(json!([1, 2])).is_err());
+
+    let strict = TypedInputs::parse(
+        &ir().public_inputs,
+        &json!({"root": 7, "note": 1}),
+        UndeclaredInputs::Reject,
+    )
+    .unwrap_err();
+    assert_eq!(strict.to_string(), "undeclared public inputs: note");
+}
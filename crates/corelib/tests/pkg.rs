@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tempfile::tempdir;
+use zkprov_corelib::pkg::{Package, PackageStore, PKG_SCHEMA};
+
+fn example_manifest() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/pkg/toy_merkle/zkpkg.toml")
+}
+
+#[test]
+fn package_round_trips_and_installs_under_its_name() {
+    let package = Package::from_manifest(&example_manifest()).unwrap();
+    assert_eq!(package.schema, PKG_SCHEMA);
+    assert_eq!(package.name, "toy_merkle");
+    assert_eq!(package.profiles, ["balanced", "dev-fast"]);
+    assert_eq!(package.inputs["default"], r#"{"demo":true,"n":7}"#);
+    assert_eq!(Package::from_bytes(&package.to_bytes()).unwrap(), package);
+
+    let dir = tempdir().unwrap();
+    let store = PackageStore::new(dir.path());
+    assert!(store.list().unwrap().is_empty());
+    assert!(store.resolve("pkg:toy_merkle").is_err());
+
+    let air = store.install(&package).unwrap();
+    assert_eq!(fs::read_to_string(&air).unwrap(), package.air);
+    assert_eq!(store.resolve("pkg:toy_merkle").unwrap(), air);
+    assert_eq!(
+        store.resolve("examples/air/toy.air").unwrap(),
+        PathBuf::from("examples/air/toy.air")
+    );
+    let inputs = dir.path().join("toy_merkle/inputs/default.json");
+    assert_eq!(
+        fs::read_to_string(inputs).unwrap(),
+        package.inputs["default"]
+    );
+    assert_eq!(store.list().unwrap(), vec![package.clone()]);
+
+    // Reinstalling replaces the package.
+    let mut next = package.clone();
+    next.version = "0.2.0".to_string();
+    store.install(&next).unwrap();
+    assert_eq!(store.get("toy_merkle").unwrap().unwrap().version, "0.2.0");
+}
+
+#[test]
+fn tampered_or_malformed_packages_are_rejected() {
+    let package = Package::from_manifest(&example_manifest()).unwrap();
+
+    let mut tampered = package.clone();
+    tampered.air.push_str("\n# edited\n");
+    let err = Package::from_bytes(&tampered.to_bytes()).unwrap_err();
+    assert!(format!("{err:#}").contains("program_hash"), "{err:#}");
+
+    let mut other = package.clone();
+    other.schema = "zkd-pkg/9".to_string();
+    assert!(Package::from_bytes(&other.to_bytes()).is_err());
+
+    let mut escape = package.clone();
+    escape.name = "../evil".to_string();
+    assert!(escape.check().is_err());
+    let dir = tempdir().unwrap();
+    let store = PackageStore::new(dir.path());
+    assert!(store.install(&escape).is_err());
+    assert!(store.resolve("pkg:../evil").is_err());
+
+    let mut dangling = package;
+    dangling.golden[0].inputs = "missing".to_string();
+    assert!(dangling.check().is_err());
+}
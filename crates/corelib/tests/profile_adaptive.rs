@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use zkprov_corelib::config::Config;
+use zkprov_corelib::manifest::DeterminismVector;
+use zkprov_corelib::profile::adaptive::{
+    security_bits, solve, CostModel, MAX_GRIND_BITS, MIN_QUERIES,
+};
+use zkprov_corelib::profile::{resolve_profile, ProfileOverrides};
+
+fn balanced() -> zkprov_corelib::profile::Profile {
+    resolve_profile("balanced", &ProfileOverrides::new()).unwrap()
+}
+
+fn config(overrides: ProfileOverrides) -> Config {
+    Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced")
+        .with_profile_overrides(overrides)
+}
+
+fn overrides(queries: u32, grind: u32) -> ProfileOverrides {
+    [
+        ("fri_queries".to_string(), queries),
+        ("grind_bits".to_string(), grind),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn security_bits_counts_queries_and_grinding() {
+    assert_eq!(security_bits(16, 30, 18), 138);
+    assert_eq!(security_bits(8, 24, 0), 72);
+    assert_eq!(balanced().security_bits(), Some(138));
+}
+
+#[test]
+fn affordable_grind_bits_follow_the_budget() {
+    let model = CostModel { grind_ns: 1000.0 };
+    assert_eq!(model.grind_time(10), Duration::from_nanos(1_024_000));
+    assert_eq!(model.affordable_grind_bits(Duration::from_millis(1)), 9);
+    assert_eq!(model.affordable_grind_bits(Duration::from_micros(1)), 0);
+    assert!(CostModel::measure("blake3").unwrap().grind_ns >= 1.0);
+    assert!(CostModel::measure("nope").is_err());
+}
+
+#[test]
+fn solve_is_deterministic_with_the_default_model() {
+    assert_eq!(CostModel::default(), CostModel::REFERENCE);
+    let budget = Duration::from_millis(1);
+    let first = solve(&balanced(), &CostModel::default(), budget).unwrap();
+    let again = solve(&balanced(), &CostModel::default(), budget).unwrap();
+    assert_eq!(first, again);
+    assert_eq!((first.fri_queries, first.grind_bits), (23, 8));
+    assert_eq!(first.grind_ns, 1000);
+}
+
+#[test]
+fn solve_trades_queries_for_grinding() {
+    let profile = balanced();
+    let model = CostModel { grind_ns: 1000.0 };
+
+    // 9 affordable bits: ceil((100 - 9) / 4) = 23 queries, 8 bits finish it.
+    let choice = solve(&profile, &model, Duration::from_millis(1)).unwrap();
+    assert_eq!((choice.fri_queries, choice.grind_bits), (23, 8));
+    assert_eq!(choice.security_bits, 100);
+    assert_eq!((choice.grind_ns, choice.budget_ms), (1000, 1));
+
+    // No budget: queries alone carry lambda_bits.
+    let choice = solve(&profile, &model, Duration::ZERO).unwrap();
+    assert_eq!((choice.fri_queries, choice.grind_bits), (25, 0));
+
+    // A huge budget grinds at most MAX_GRIND_BITS.
+    let fast = CostModel { grind_ns: 1.0 };
+    let choice = solve(&profile, &fast, Duration::from_secs(3600)).unwrap();
+    assert_eq!(
+        (choice.fri_queries, choice.grind_bits),
+        (17, MAX_GRIND_BITS)
+    );
+
+    // ... and never goes below the query floor.
+    let mut low = profile.clone();
+    low.lambda_bits = 64;
+    let choice = solve(&low, &fast, Duration::from_secs(3600)).unwrap();
+    assert_eq!((choice.fri_queries, choice.grind_bits), (MIN_QUERIES, 0));
+    assert!(choice.security_bits >= low.lambda_bits);
+}
+
+#[test]
+fn check_uses_the_verifier_config_and_lambda_bits() {
+    let model = CostModel { grind_ns: 1000.0 };
+    let choice = solve(&balanced(), &model, Duration::from_millis(1)).unwrap();
+
+    choice.check(&config(overrides(23, 8))).unwrap();
+
+    // Verifier's config without the overrides.
+    let err = choice.check(&config(ProfileOverrides::new())).unwrap_err();
+    assert!(err.to_string().contains("not the recorded"), "{err}");
+
+    // A recorded combination below lambda_bits: the overrides themselves
+    // do not resolve, so no verifier config can carry them.
+    let mut weak = choice;
+    weak.grind_bits = 0;
+    weak.security_bits = 92;
+    let err = weak.check(&config(overrides(23, 0))).unwrap_err();
+    assert!(err.to_string().contains("below lambda_bits"), "{err}");
+
+    // The recorded security must be the one the parameters give.
+    let mut inflated = choice;
+    inflated.security_bits = 128;
+    let err = inflated.check(&config(overrides(23, 8))).unwrap_err();
+    assert!(err.to_string().contains("security_bits 128"), "{err}");
+}
+
+#[test]
+fn adaptive_choice_is_sealed_in_the_manifest() {
+    let model = CostModel { grind_ns: 1000.0 };
+    let choice = solve(&balanced(), &model, Duration::from_millis(1)).unwrap();
+    let cfg = config(overrides(23, 8));
+
+    let plain = DeterminismVector::from_config(&cfg, "0x00");
+    let json = serde_json::to_value(&plain).unwrap();
+    assert!(json.get("adaptive").is_none());
+
+    let vector = plain.clone().with_adaptive(Some(choice));
+    vector.verify().unwrap();
+    assert_ne!(vector.manifest_hash, plain.manifest_hash);
+    let back: DeterminismVector =
+        serde_json::from_value(serde_json::to_value(&vector).unwrap()).unwrap();
+    assert_eq!(back.adaptive, Some(choice));
+
+    let mut tampered = vector;
+    tampered.adaptive.as_mut().unwrap().grind_bits = 20;
+    assert!(tampered.verify().is_err());
+}
@@ -1,4 +1,8 @@
-use zkprov_corelib::profile::{load_all_profiles, Profile};
+use zkprov_corelib::profile::{
+    load_all_profiles, parse_overrides, resolve_profile, Profile, ProofTarget,
+};
+use zkprov_corelib::registry::ensure_builtins_registered;
+use zkprov_corelib::validate::{check_profile_against_backend, profile_compat_matrix};
 
 #[test]
 fn parse_three_default_profiles() {
@@ -19,6 +23,94 @@ fn validation_enforces_bounds() {
         merkle_arity: Some(7),
         const_col_limit: None,
         rows_max: None,
+        hash: None,
+        target: None,
+        owner: None,
+        signature: None,
+        signature_verified: false,
     };
     assert!(bad.validate().is_err());
 }
+
+#[test]
+fn overrides_are_parsed_and_revalidated() {
+    let overrides =
+        parse_overrides(&["fri_queries=40", "blowup=32", "grind_bits=20"]).expect("parse");
+    assert_eq!(overrides.get("fri_queries"), Some(&40));
+    assert_eq!(overrides.get("fri_blowup"), Some(&32));
+
+    let p = resolve_profile("balanced", &overrides).expect("resolve");
+    assert_eq!(p.fri_queries, Some(40));
+    assert_eq!(p.fri_blowup, Some(32));
+    assert_eq!(p.grind_bits, Some(20));
+
+    let too_few = parse_overrides(&["fri_queries=8"]).unwrap();
+    assert!(resolve_profile("balanced", &too_few).is_err());
+    assert!(parse_overrides(&["lambda_bits=128"]).is_err());
+    assert!(parse_overrides(&["fri_queries"]).is_err());
+}
+
+#[test]
+fn overrides_cannot_drop_below_lambda_bits() {
+    // balanced: lambda_bits 100, blowup 16 (4 bits per query).
+    let weak = parse_overrides(&["fri_queries=16", "grind_bits=0"]).unwrap();
+    let err = resolve_profile("balanced", &weak).unwrap_err();
+    assert!(
+        format!("{err:#}").contains("64 bits, below lambda_bits 100"),
+        "{err:#}"
+    );
+
+    // Fewer queries are fine when grinding makes up the difference.
+    let traded = parse_overrides(&["fri_queries=20", "grind_bits=20"]).unwrap();
+    let p = resolve_profile("balanced", &traded).expect("100 bits still reached");
+    assert_eq!(p.security_bits(), Some(100));
+    let short = parse_overrides(&["fri_queries=20", "grind_bits=19"]).unwrap();
+    assert!(resolve_profile("balanced", &short).is_err());
+}
+
+#[test]
+fn profiles_are_checked_against_backend_limits() {
+    ensure_builtins_registered();
+    let matrix = profile_compat_matrix().expect("matrix");
+    assert!(matrix
+        .iter()
+        .filter(|c| c.backend_id == "native@0.0")
+        .all(|c| c.compatible));
+
+    let wide = Profile {
+        id: "wide".to_string(),
+        lambda_bits: 100,
+        fri_blowup: None,
+        fri_queries: None,
+        grind_bits: None,
+        merkle_arity: Some(8),
+        const_col_limit: None,
+        rows_max: Some(1 << 24),
+        hash: Some("sha3".to_string()),
+        target: Some(ProofTarget::SnarkWrapped),
+        owner: None,
+        signature: None,
+        signature_verified: false,
+    };
+    let reasons = check_profile_against_backend(&wide, "native@0.0").unwrap();
+    let checks: Vec<_> = reasons.iter().map(|r| r.check).collect();
+    assert_eq!(checks, ["arity", "rows_max", "hash", "target"]);
+    assert!(check_profile_against_backend(&wide, "nope@0.0").is_err());
+}
+
+#[test]
+fn target_defaults_to_stark_and_is_signed_only_when_set() {
+    let mut p: Profile = toml::from_str("id = \"onchain\"\nlambda_bits = 100\n").unwrap();
+    assert_eq!(p.target, None);
+    assert_eq!(p.target(), ProofTarget::Stark);
+    let unset = p.signing_message();
+    assert!(!String::from_utf8_lossy(&unset).contains("target"));
+
+    p = toml::from_str("id = \"onchain\"\nlambda_bits = 100\ntarget = \"snark-wrapped\"\n")
+        .unwrap();
+    assert_eq!(p.target(), ProofTarget::SnarkWrapped);
+    assert_ne!(p.signing_message(), unset);
+    assert!(
+        toml::from_str::<Profile>("id = \"x\"\nlambda_bits = 100\ntarget = \"groth16\"\n").is_err()
+    );
+}
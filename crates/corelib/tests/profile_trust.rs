@@ -0,0 +1,86 @@
+use std::fs;
+
+use tempfile::tempdir;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::CapabilityError;
+use zkprov_corelib::profile::trust::{public_key_hex, sign_profile};
+use zkprov_corelib::profile::{load_profiles_from, set_trust_anchors, Profile, TrustAnchors};
+use zkprov_corelib::registry::ensure_builtins_registered;
+use zkprov_corelib::validate_config;
+
+const ACME_KEY: [u8; 32] = [7u8; 32];
+const MALLORY_KEY: [u8; 32] = [9u8; 32];
+
+fn acme_fast() -> Profile {
+    toml::from_str(
+        r#"
+id = "acme/fast"
+owner = "acme"
+lambda_bits = 80
+fri_blowup = 8
+fri_queries = 24
+"#,
+    )
+    .unwrap()
+}
+
+fn write_profile(dir: &std::path::Path, name: &str, p: &Profile) {
+    fs::write(dir.join(name), toml::to_string(p).unwrap()).unwrap();
+}
+
+#[test]
+fn namespaced_profiles_are_verified_against_trust_anchors() {
+    let mut anchors = TrustAnchors::from_toml_str(&format!(
+        "[[anchor]]\nowner = \"acme\"\npublic_key = \"{}\"\n",
+        public_key_hex(&ACME_KEY)
+    ))
+    .unwrap();
+    set_trust_anchors(anchors.clone());
+
+    let dir = tempdir().unwrap();
+    let mut signed = acme_fast();
+    signed.signature = Some(sign_profile(&signed, &ACME_KEY));
+    write_profile(dir.path(), "fast.toml", &signed);
+    let loaded = load_profiles_from(dir.path()).expect("signed profile loads");
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].owner.as_deref(), Some("acme"));
+    assert!(loaded[0].signature_verified);
+
+    // Tampering with a parameter, or signing with another key, is rejected.
+    let mut tampered = signed.clone();
+    tampered.fri_queries = Some(16);
+    write_profile(dir.path(), "fast.toml", &tampered);
+    let err = load_profiles_from(dir.path()).unwrap_err();
+    assert!(format!("{err:#}").contains("does not verify"), "{err:#}");
+    let mut forged = acme_fast();
+    forged.signature = Some(sign_profile(&forged, &MALLORY_KEY));
+    write_profile(dir.path(), "fast.toml", &forged);
+    assert!(load_profiles_from(dir.path()).is_err());
+
+    // Unsigned profiles load, but are not marked verified.
+    write_profile(dir.path(), "fast.toml", &acme_fast());
+    let loaded = load_profiles_from(dir.path()).unwrap();
+    assert!(!loaded[0].signature_verified);
+
+    // Owners cannot claim ids outside their namespace.
+    let mut squatter = acme_fast();
+    squatter.id = "fast".to_string();
+    assert!(squatter.validate().is_err());
+    let mut orphan = acme_fast();
+    orphan.owner = None;
+    orphan.id = "fast".to_string();
+    orphan.signature = Some(sign_profile(&orphan, &ACME_KEY));
+    assert!(orphan.validate().is_err());
+
+    // With require_signed, validate_config refuses the unsigned built-ins.
+    ensure_builtins_registered();
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    assert!(validate_config(&cfg).is_ok());
+    anchors.require_signed = true;
+    set_trust_anchors(anchors);
+    assert!(matches!(
+        validate_config(&cfg),
+        Err(CapabilityError::ProfileUnsigned(id)) if id == "balanced"
+    ));
+    set_trust_anchors(TrustAnchors::new());
+}
@@ -0,0 +1,158 @@
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::VerifyError;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::manifest::{DeterminismManifest, DeterminismVector};
+use zkprov_corelib::proof::container::{
+    is_container, ProofContainer, CONTAINER_VERSION, OPTIONAL_TAGS, TAG_PAYLOAD,
+};
+use zkprov_corelib::proof::pubio::{pubio_digest, pubio_hash};
+use zkprov_corelib::proof::{Proof, ProofHeader};
+
+fn proof() -> (Vec<u8>, PublicInputs) {
+    let inputs = PublicInputs::from_json(r#"{"a":1}"#).unwrap();
+    let header = ProofHeader {
+        backend_id_hash: 1,
+        profile_id_hash: 2,
+        pubio_hash: pubio_hash(&inputs),
+        body_len: 0,
+        config_hash: 5,
+        flags: 0,
+    };
+    (
+        Proof::new(header, b"backend payload".to_vec()).to_bytes(),
+        inputs,
+    )
+}
+
+fn manifest(bytes: &[u8]) -> DeterminismManifest {
+    let proof = Proof::from_bytes(bytes).unwrap();
+    let digest: String = digest_D(&proof.header, &proof.body)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    DeterminismManifest {
+        program: "toy".into(),
+        digest: format!("0x{digest}"),
+        determinism_vector: DeterminismVector::from_config(&config, "seed"),
+        build: None,
+    }
+}
+
+/// The payload section's length field, for tampering.
+fn payload_len_offset(bytes: &[u8]) -> usize {
+    let header_len = u32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+    let at = 10 + 6 + header_len;
+    assert_eq!(u16::from_le_bytes([bytes[at], bytes[at + 1]]), TAG_PAYLOAD);
+    at + 2
+}
+
+#[test]
+fn container_round_trips_all_sections() {
+    let (bytes, inputs) = proof();
+    let mut container = ProofContainer::from_proof(&bytes)
+        .unwrap()
+        .with_manifest(manifest(&bytes))
+        .with_pubio_digest(pubio_digest(&inputs));
+    container.extra.push((OPTIONAL_TAGS + 1, b"later".to_vec()));
+    let encoded = container.encode();
+    assert!(is_container(&encoded));
+    assert_eq!(
+        u32::from_le_bytes(encoded[4..8].try_into().unwrap()),
+        CONTAINER_VERSION
+    );
+
+    let back = ProofContainer::decode(&encoded).unwrap();
+    assert_eq!(back, container);
+    assert_eq!(back.encode(), encoded);
+    assert_eq!(back.to_proof(), bytes);
+}
+
+#[test]
+fn bare_proofs_decode_as_version_1() {
+    let (bytes, _) = proof();
+    assert!(!is_container(&bytes));
+    let container = ProofContainer::decode(&bytes).unwrap();
+    assert_eq!(container.payload, b"backend payload");
+    assert!(container.manifest.is_none() && container.pubio_digest.is_none());
+    assert_eq!(container.to_proof(), bytes);
+}
+
+#[test]
+fn inconsistent_or_damaged_containers_are_malformed() {
+    let (bytes, inputs) = proof();
+    let base = ProofContainer::from_proof(&bytes).unwrap();
+
+    // Manifest of another proof.
+    let mut other = bytes.clone();
+    *other.last_mut().unwrap() ^= 1;
+    let bad = base.clone().with_manifest(manifest(&other)).encode();
+    let err = ProofContainer::decode(&bad).unwrap_err();
+    assert!(err.to_string().contains("manifest digest"), "{err}");
+
+    // Digest of other inputs.
+    let wrong = PublicInputs::from_json(r#"{"a":2}"#).unwrap();
+    let bad = base
+        .clone()
+        .with_pubio_digest(pubio_digest(&wrong))
+        .encode();
+    let err = ProofContainer::decode(&bad).unwrap_err();
+    assert!(err.to_string().contains("pubio digest"), "{err}");
+    assert_eq!(
+        u64::from_le_bytes(pubio_digest(&inputs)[..8].try_into().unwrap()),
+        pubio_hash(&inputs)
+    );
+
+    // Unknown required section.
+    let mut bad = base.clone();
+    bad.extra.push((7, Vec::new()));
+    let err = ProofContainer::decode(&bad.encode()).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown required section tag 7"),
+        "{err}"
+    );
+
+    // Payload shorter than the header declares.
+    let mut bad = base.encode();
+    let at = payload_len_offset(&bad);
+    let len = u32::from_le_bytes(bad[at..at + 4].try_into().unwrap()) - 1;
+    bad[at..at + 4].copy_from_slice(&len.to_le_bytes());
+    bad.pop();
+    assert!(matches!(
+        ProofContainer::decode(&bad),
+        Err(VerifyError::BodyLength { .. })
+    ));
+
+    // Truncation and trailing bytes.
+    let good = base.encode();
+    for bad in [
+        &good[..good.len() - 1],
+        &[good.as_slice(), &[0]].concat()[..],
+    ] {
+        assert!(matches!(
+            ProofContainer::decode(bad),
+            Err(VerifyError::Malformed(_))
+        ));
+    }
+}
+
+#[test]
+fn embedded_inputs_are_stored_canonically() {
+    let (bytes, _) = proof();
+    let container = ProofContainer::from_proof(&bytes)
+        .unwrap()
+        .with_inputs(&serde_json::json!({"b": [1.0, 2], "a": 1}));
+    assert_eq!(container.inputs.as_deref(), Some(r#"{"a":1,"b":[1,2]}"#));
+    let back = ProofContainer::decode(&container.encode()).unwrap();
+    assert_eq!(back.inputs, container.inputs);
+    assert_eq!(back.to_proof(), bytes);
+
+    let mut bad = container.clone();
+    bad.inputs = Some(r#"{"b":[1,2],"a":1}"#.into());
+    let err = ProofContainer::decode(&bad.encode()).unwrap_err();
+    assert!(err.to_string().contains("not in canonical form"), "{err}");
+    bad.inputs = Some("{".into());
+    let err = ProofContainer::decode(&bad.encode()).unwrap_err();
+    assert!(err.to_string().contains("inputs are not JSON"), "{err}");
+}
@@ -1,4 +1,18 @@
-use zkprov_corelib::proof::{assemble_proof, hash64, ProofHeader};
+use zkprov_corelib::errors::{HeaderField, VerifyError};
+use zkprov_corelib::proof::{
+    assemble_proof, hash64, ProofHeader, ProofView, CRITICAL_FLAGS, HEADER_LEN, V2_HEADER_LEN,
+};
+
+fn header() -> ProofHeader {
+    ProofHeader {
+        backend_id_hash: 1,
+        profile_id_hash: 2,
+        pubio_hash: 3,
+        body_len: 4,
+        config_hash: 5,
+        flags: 0,
+    }
+}
 
 #[test]
 fn header_roundtrip() {
@@ -7,6 +21,8 @@ fn header_roundtrip() {
         profile_id_hash: hash64("PROFILE", b"default"),
         pubio_hash: hash64("PUBIO", br#"{"x":1}"#),
         body_len: 8,
+        config_hash: hash64("CONFIG", b"{}"),
+        flags: 0,
     };
     let enc = hdr.encode();
     let dec = ProofHeader::decode(&enc).unwrap();
@@ -14,7 +30,7 @@ fn header_roundtrip() {
 
     let body = 12345678u64.to_le_bytes();
     let proof = assemble_proof(&hdr, &body);
-    assert_eq!(proof.len(), 40 + 8);
+    assert_eq!(proof.len(), HEADER_LEN + 8);
 }
 
 #[test]
@@ -24,6 +40,8 @@ fn header_rejects_bad_magic_or_version() {
         profile_id_hash: 2,
         pubio_hash: 3,
         body_len: 0,
+        config_hash: 0,
+        flags: 0,
     }
     .encode();
     // Corrupt magic
@@ -32,6 +50,121 @@ fn header_rejects_bad_magic_or_version() {
 
     // Fix magic, corrupt version
     enc[0] = b'P';
-    enc[4] ^= 0x01;
+    enc[4] ^= 0x04;
     assert!(ProofHeader::decode(&enc).is_err());
+
+    // Version 1 headers do not bind the config and are refused outright.
+    enc[4..8].copy_from_slice(&1u32.to_le_bytes());
+    let err = ProofHeader::decode(&enc[..40]).unwrap_err().to_string();
+    assert!(err.contains("does not bind its config"), "{err}");
+}
+
+#[test]
+fn header_is_little_endian_with_explicit_length() {
+    let enc = header().encode();
+    assert_eq!(enc.len(), HEADER_LEN);
+    assert_eq!(&enc[4..8], &3u32.to_le_bytes());
+    assert_eq!(&enc[8..10], &(HEADER_LEN as u16).to_le_bytes());
+    assert_eq!(enc[10], 1, "byte order marker");
+    assert_eq!(&enc[16..24], &1u64.to_le_bytes());
+    assert_eq!(&enc[48..56], &5u64.to_le_bytes());
+    assert!(
+        enc[56..64].iter().all(|&b| b == 0),
+        "reserved bytes are zero"
+    );
+
+    let mut swapped = enc.clone();
+    swapped[10] = 2;
+    let err = ProofHeader::decode(&swapped).unwrap_err().to_string();
+    assert!(err.contains("byte order"), "{err}");
+}
+
+#[test]
+fn appended_fields_are_rejected_until_defined() {
+    // A newer writer appended 8 bytes after the fixed fields; nothing binds
+    // them, so the header does not decode.
+    let mut proof = header().encode();
+    proof.extend_from_slice(&[0xee; 8]);
+    proof[8..10].copy_from_slice(&((HEADER_LEN + 8) as u16).to_le_bytes());
+    proof.extend_from_slice(&[9, 9, 9, 9]);
+    let err = ProofView::from_bytes(&proof).unwrap_err().to_string();
+    assert!(err.contains("8 bytes of extensions"), "{err}");
+
+    // A header length below the fixed fields or past the end is malformed.
+    let mut bad = proof.clone();
+    bad[8..10].copy_from_slice(&40u16.to_le_bytes());
+    assert!(ProofView::from_bytes(&bad).is_err());
+    bad[8..10].copy_from_slice(&200u16.to_le_bytes());
+    assert!(ProofView::from_bytes(&bad).is_err());
+
+    let proof = assemble_proof(&header(), &[9, 9, 9, 9]);
+    assert_eq!(proof.len(), header().encoded_len() + 4);
+    assert_eq!(ProofView::from_bytes(&proof).unwrap().body, &[9, 9, 9, 9]);
+}
+
+#[test]
+fn unknown_flags_are_rejected() {
+    // Unassigned non-critical flags are not bound, so they are refused too.
+    let noncritical = ProofHeader {
+        flags: 1 << 20,
+        ..header()
+    };
+    assert_eq!(CRITICAL_FLAGS & (1 << 20), 0);
+    let err = ProofHeader::decode(&noncritical.encode())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("non-critical flags"), "{err}");
+
+    // Unknown critical flags make the header undecodable.
+    for bit in [0, 15] {
+        assert_ne!(CRITICAL_FLAGS & (1 << bit), 0);
+        let critical = ProofHeader {
+            flags: 1 << bit,
+            ..header()
+        };
+        let err = ProofHeader::decode(&critical.encode())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("critical flags"), "{err}");
+    }
+}
+
+#[test]
+fn v2_headers_still_decode() {
+    let hdr = header();
+    let mut v2 = Vec::new();
+    v2.extend_from_slice(b"PROF");
+    v2.extend_from_slice(&2u32.to_le_bytes());
+    for field in [1u64, 2, 3, 4, 5] {
+        v2.extend_from_slice(&field.to_le_bytes());
+    }
+    assert_eq!(v2.len(), V2_HEADER_LEN);
+    v2.extend_from_slice(&[7, 7, 7, 7]);
+    let view = ProofView::from_bytes(&v2).unwrap();
+    assert_eq!(view.header, hdr);
+    assert_eq!(view.body, &[7, 7, 7, 7]);
+}
+
+#[test]
+fn check_bindings_reports_the_first_mismatched_field() {
+    let expected = header();
+    // body_len is not a binding.
+    let mut other = header();
+    other.body_len = 40;
+    other.check_bindings(&expected).unwrap();
+
+    other.pubio_hash = 30;
+    other.config_hash = 50;
+    assert_eq!(
+        other.binding_mismatches(&expected),
+        vec![(HeaderField::PubIo, 3, 30), (HeaderField::Config, 5, 50)]
+    );
+    match other.check_bindings(&expected).unwrap_err() {
+        VerifyError::HeaderBinding {
+            field,
+            expected,
+            actual,
+        } => assert_eq!((field, expected, actual), (HeaderField::PubIo, 3, 30)),
+        err => panic!("{err}"),
+    }
 }
@@ -0,0 +1,97 @@
+use zkprov_corelib::errors::VerifyError;
+use zkprov_corelib::proof::segment::{
+    is_segmented, parse_segments, SegmentFlags, SegmentKind, SegmentedBody,
+};
+use zkprov_corelib::proof::{Proof, ProofHeader};
+
+fn body() -> Vec<u8> {
+    let mut body = SegmentedBody::new();
+    body.push(SegmentKind::Public, SegmentFlags::NONE, 1, vec![0xaa; 32])
+        .push(
+            SegmentKind::ZkCritical,
+            SegmentFlags::ENCRYPTED,
+            2,
+            b"openings".to_vec(),
+        )
+        .push(SegmentKind::Metadata, SegmentFlags::NONE, 3, Vec::new());
+    body.encode()
+}
+
+#[test]
+fn segments_roundtrip_through_a_proof_without_copying() {
+    let body = body();
+    assert!(is_segmented(&body));
+    assert!(!is_segmented(&42u64.to_le_bytes()));
+
+    let header = ProofHeader {
+        backend_id_hash: 1,
+        profile_id_hash: 2,
+        pubio_hash: 3,
+        body_len: 0,
+        config_hash: 0,
+        flags: 0,
+    };
+    let proof = Proof::new(header, body);
+    let bytes = proof.to_bytes();
+    let view = zkprov_corelib::proof::ProofView::from_bytes(&bytes).unwrap();
+    let segments = view.segments().unwrap();
+
+    let kinds: Vec<_> = segments.iter().map(|s| (s.kind, s.id)).collect();
+    assert_eq!(
+        kinds,
+        [
+            (SegmentKind::Public, 1),
+            (SegmentKind::ZkCritical, 2),
+            (SegmentKind::Metadata, 3)
+        ]
+    );
+    let secret = &segments[1];
+    assert!(secret.flags.contains(SegmentFlags::ENCRYPTED));
+    assert!(!secret.flags.contains(SegmentFlags::COMPRESSED));
+    assert_eq!(secret.payload, b"openings");
+    assert!(std::ptr::eq(
+        secret.payload.as_ptr(),
+        bytes[bytes.len() - 8 - 16..].as_ptr()
+    ));
+    assert!(segments[2].payload.is_empty());
+}
+
+#[test]
+fn segment_checksums_and_framing_are_enforced() {
+    let good = body();
+    let segment_err = |bytes: &[u8]| match parse_segments(bytes) {
+        Err(VerifyError::Segment { index, reason }) => (index, reason),
+        other => panic!("expected a segment error, got {other:?}"),
+    };
+
+    // Flip one payload byte of the ZK-critical segment.
+    let mut bad = good.clone();
+    let at = 6 + 16 + 32 + 16;
+    bad[at] ^= 1;
+    let (index, reason) = segment_err(&bad);
+    assert_eq!(index, 1);
+    assert!(
+        reason.contains("checksum mismatch in zk_critical segment 2"),
+        "{reason}"
+    );
+
+    // Changing the declared kind also breaks the checksum.
+    let mut bad = good.clone();
+    bad[6 + 16 + 32] = SegmentKind::Public as u8;
+    assert_eq!(segment_err(&bad).0, 1);
+
+    let (index, reason) = segment_err(&good[..good.len() - 1]);
+    assert_eq!(index, 2);
+    assert!(reason.contains("truncated"), "{reason}");
+
+    let mut bad = good.clone();
+    bad.push(0);
+    assert!(segment_err(&bad).1.contains("trailing"));
+
+    let mut bad = good;
+    bad[6 + 1] = 0x80;
+    assert!(segment_err(&bad).1.contains("unknown segment flags"));
+
+    let err = parse_segments(&42u64.to_le_bytes()).unwrap_err();
+    assert_eq!(err.category(), "segment");
+}
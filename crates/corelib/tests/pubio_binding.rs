@@ -0,0 +1,146 @@
+use serde_json::json;
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::evm::pubio::PubIoEncoding;
+use zkprov_corelib::proof::hash64;
+use zkprov_corelib::proof::pubio::{pubio_hash, UndeclaredInputs};
+
+const AIR: &str = r#"
+[meta]
+name = "bound"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+
+[[public_inputs]]
+name = "root"
+type = "field"
+
+[[public_inputs]]
+name = "amount"
+type = "fixed(2)"
+"#;
+
+fn ir() -> AirIr {
+    parse_air_str(AIR).unwrap()
+}
+
+fn bound(json: &str, policy: UndeclaredInputs) -> Result<u64, String> {
+    PublicInputs::from_json(json)
+        .unwrap()
+        .bind(PubIoEncoding::Json, &ir(), policy)
+        .map(|inputs| pubio_hash(&inputs))
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn declared_inputs_bind_independently_of_json_layout() {
+    let base = bound(r#"{"root":7,"amount":"1.50"}"#, UndeclaredInputs::Ignore).unwrap();
+    // Key order, number spelling and undeclared keys do not matter.
+    for json in [
+        r#"{ "amount": "1.5", "root": "0x7" }"#,
+        r#"{"root":"7","amount":"1.50","note":"ignored","nested":{"a":1}}"#,
+    ] {
+        assert_eq!(
+            bound(json, UndeclaredInputs::Ignore).unwrap(),
+            base,
+            "{json}"
+        );
+    }
+
+    // Declared values, and their presence, do.
+    for json in [
+        r#"{"root":8,"amount":"1.50"}"#,
+        r#"{"root":7,"amount":"1.51"}"#,
+        r#"{"root":7}"#,
+    ] {
+        assert_ne!(
+            bound(json, UndeclaredInputs::Ignore).unwrap(),
+            base,
+            "{json}"
+        );
+    }
+}
+
+#[test]
+fn inputs_are_domain_separated_by_name_and_type() {
+    let swapped = AIR
+        .replace("\"root\"", "\"tmp\"")
+        .replace("\"amount\"", "\"root\"")
+        .replace("\"tmp\"", "\"amount\"");
+    let retyped = AIR.replace("fixed(2)", "fixed(3)");
+    let json = r#"{"root":7,"amount":7}"#;
+    let hash = |src: &str| {
+        let inputs = PublicInputs::from_json(json)
+            .unwrap()
+            .with_encoding(PubIoEncoding::Json, &parse_air_str(src).unwrap())
+            .unwrap();
+        pubio_hash(&inputs)
+    };
+    assert_ne!(hash(AIR), hash(&swapped));
+    assert_ne!(hash(AIR), hash(&retyped));
+}
+
+#[test]
+fn reject_policy_refuses_undeclared_keys() {
+    let err = bound(r#"{"root":7,"extra":1}"#, UndeclaredInputs::Reject).unwrap_err();
+    assert!(err.contains("undeclared public inputs: extra"), "{err}");
+    assert!(bound(r#"{"root":7}"#, UndeclaredInputs::Reject).is_ok());
+
+    // Without declarations the JSON text is bound as before, and every key
+    // is undeclared.
+    let bare = parse_air_str(&AIR[..AIR.find("[[public_inputs]]").unwrap()]).unwrap();
+    let raw = r#"{"a":1}"#;
+    let inputs = PublicInputs::from_json(raw).unwrap();
+    let ignored = inputs
+        .clone()
+        .bind(PubIoEncoding::Json, &bare, UndeclaredInputs::Ignore)
+        .unwrap();
+    assert_eq!(pubio_hash(&ignored), hash64("PUBIO", raw.as_bytes()));
+    assert!(inputs
+        .bind(PubIoEncoding::Json, &bare, UndeclaredInputs::Reject)
+        .is_err());
+}
+
+#[test]
+fn bytes_ref_binds_streamed_digest_and_length() {
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(&AIR.replace("fixed(2)", "bytes_ref")).unwrap();
+    let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let dir = tempfile::tempdir().unwrap();
+    let doc = dir.path().join("doc.bin");
+    std::fs::write(&doc, vec![0x5a; 300_000]).unwrap();
+    let by_path = PublicInputs::from_json(json!({"root": 7, "amount": doc}).to_string()).unwrap();
+    let proof = zkprov_corelib::prove(&config, &ir, &by_path).unwrap();
+    assert!(zkprov_corelib::verify(&config, &ir, &by_path, &proof.view()).is_ok());
+
+    // The digest and length stand in for the document.
+    let digest = blake3::hash(&std::fs::read(&doc).unwrap());
+    let resolved = json!({
+        "root": 7,
+        "amount": {"digest": format!("0x{}", digest.to_hex()), "len": 300_000},
+    });
+    let resolved = PublicInputs::from_json(resolved.to_string()).unwrap();
+    assert!(zkprov_corelib::verify(&config, &ir, &resolved, &proof.view()).is_ok());
+    let uri = json!({"root": 7, "amount": format!("file://{}", doc.display())});
+    let uri = PublicInputs::from_json(uri.to_string()).unwrap();
+    assert!(zkprov_corelib::verify(&config, &ir, &uri, &proof.view()).is_ok());
+
+    std::fs::write(&doc, vec![0x5a; 300_001]).unwrap();
+    assert!(zkprov_corelib::verify(&config, &ir, &by_path, &proof.view()).is_err());
+
+    let remote = PublicInputs::from_json(r#"{"root":7,"amount":"https://x/doc"}"#).unwrap();
+    let err = zkprov_corelib::prove(&config, &ir, &remote).unwrap_err();
+    assert!(
+        err.to_string().contains("unsupported bytes_ref scheme"),
+        "{err}"
+    );
+}
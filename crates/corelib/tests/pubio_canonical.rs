@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use serde_json::json;
+use zkprov_corelib::air::parse_air_file;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::proof::canonical::canonical_json;
+use zkprov_corelib::proof::hash64;
+use zkprov_corelib::proof::pubio::pubio_hash;
+
+const TOY_AIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../examples/air/toy.air");
+
+fn canonical(text: &str) -> String {
+    canonical_json(&serde_json::from_str(text).unwrap())
+}
+
+#[test]
+fn canonical_form_sorts_keys_and_drops_whitespace() {
+    assert_eq!(
+        canonical(r#" { "b" : [ 1 , { "z": null, "a": true } ], "a": "x" } "#),
+        r#"{"a":"x","b":[1,{"a":true,"z":null}]}"#
+    );
+    // Keys sort by UTF-8 bytes; strings keep non-ASCII and lose needless escapes.
+    assert_eq!(
+        canonical(r#"{"é":"é","Z":"A\n","a":1}"#),
+        "{\"Z\":\"A\\n\",\"a\":1,\"é\":\"é\"}"
+    );
+    assert_eq!(canonical(r#"{"a":1,"a":2}"#), r#"{"a":2}"#);
+}
+
+#[test]
+fn canonical_form_normalizes_numbers() {
+    for (written, canonical_text) in [
+        ("1.0", "1"),
+        ("1e2", "100"),
+        ("-0.0", "0"),
+        ("-5.000", "-5"),
+        ("0.50", "0.5"),
+        ("1E22", "1e+22"),
+        ("18446744073709551615", "18446744073709551615"),
+        ("-9223372036854775808", "-9223372036854775808"),
+    ] {
+        assert_eq!(canonical(written), canonical_text, "{written}");
+    }
+}
+
+#[test]
+fn semantically_equal_inputs_bind_identically() {
+    let a = PublicInputs::from_json(r#"{"a":1,"b":[2,3]}"#).unwrap();
+    let b = PublicInputs::from_json("{\n  \"b\": [2, 3.0],\n  \"a\": 1e0\n}").unwrap();
+    assert_ne!(a.as_json(), b.as_json());
+    assert_eq!(a.canonical_json(), b.canonical_json());
+    assert_eq!(a.binding(), b.binding());
+    assert_eq!(pubio_hash(&a), hash64("PUBIO", br#"{"a":1,"b":[2,3]}"#));
+    assert_eq!(pubio_hash(&a), pubio_hash(&b));
+
+    let c = PublicInputs::from_json(json!({"a": 2, "b": [2, 3]}).to_string()).unwrap();
+    assert_ne!(pubio_hash(&a), pubio_hash(&c));
+}
+
+#[test]
+fn reformatted_inputs_yield_identical_proofs() {
+    zkprov_backend_native::ensure_native_registered();
+    let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let ir = parse_air_file(Path::new(TOY_AIR)).unwrap();
+    let compact = PublicInputs::from_json(r#"{"a":1,"b":[2,3]}"#).unwrap();
+    let pretty = PublicInputs::from_json("{ \"b\": [2, 3], \"a\": 1.0 }").unwrap();
+
+    let proof = zkprov_corelib::prove(&config, &ir, &compact).unwrap();
+    let again = zkprov_corelib::prove(&config, &ir, &pretty).unwrap();
+    assert_eq!(proof.to_bytes(), again.to_bytes());
+    assert!(zkprov_corelib::verify(&config, &ir, &pretty, &proof.view()).is_ok());
+}
@@ -0,0 +1,135 @@
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, Deprecation, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::{ProveError, RegistryError, VerifyError};
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{Proof, ProofView};
+use zkprov_corelib::registry::{
+    alias_chain, backend_deprecation, deprecate_backend, ensure_builtins_registered,
+    get_backend_capabilities, list_backend_infos, register_alias, register_backend,
+    resolve_backend_id,
+};
+use zkprov_corelib::validate::validate_config_with_warnings;
+
+struct Legacy;
+
+impl ProverBackend for Legacy {
+    fn id(&self) -> &'static str {
+        "legacy@0.6"
+    }
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            fields: vec!["Prime254"],
+            hashes: vec!["blake3"],
+            fri_arities: vec![2],
+            recursion: "none",
+            lookups: false,
+            curves: vec![],
+            pedersen: false,
+            max_rows: None,
+            incremental: false,
+            nondeterminism: Vec::new(),
+        }
+    }
+    fn prove(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        unimplemented!("capability-only test backend")
+    }
+}
+impl VerifierBackend for Legacy {
+    fn verify(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        _: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        unimplemented!("capability-only test backend")
+    }
+}
+
+fn setup() {
+    ensure_builtins_registered();
+    let _ = register_backend(Box::new(Legacy), Box::new(Legacy));
+    register_alias("legacy@stable", "legacy@0.6").unwrap();
+    register_alias("legacy@latest", "legacy@stable").unwrap();
+    deprecate_backend(
+        "legacy@latest",
+        Deprecation {
+            sunset: "2026-06-30".to_string(),
+            replacement: Some("native@0.0".to_string()),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn aliases_resolve_through_chains() {
+    setup();
+    assert_eq!(resolve_backend_id("legacy@latest").unwrap(), "legacy@0.6");
+    assert_eq!(
+        alias_chain("legacy@latest"),
+        ["legacy@latest", "legacy@stable", "legacy@0.6"]
+    );
+    assert!(get_backend_capabilities("legacy@latest").is_ok());
+    assert_eq!(resolve_backend_id("native@latest").unwrap(), "native@0.0");
+
+    let info = list_backend_infos()
+        .into_iter()
+        .find(|b| b.id == "legacy@0.6")
+        .unwrap();
+    assert_eq!(info.aliases, ["legacy@latest", "legacy@stable"]);
+    assert!(info.deprecated.is_some());
+}
+
+#[test]
+fn alias_conflicts_are_rejected() {
+    setup();
+    assert!(matches!(
+        register_alias("legacy@stable", "native@0.0"),
+        Err(RegistryError::AliasConflict { .. })
+    ));
+    assert!(matches!(
+        register_alias("native@0.0", "legacy@0.6"),
+        Err(RegistryError::AliasConflict { .. })
+    ));
+    assert!(matches!(
+        register_alias("ghost@latest", "ghost@1.0"),
+        Err(RegistryError::BackendNotFound(_))
+    ));
+    assert!(matches!(
+        deprecate_backend(
+            "legacy@0.6",
+            Deprecation {
+                sunset: "soon".to_string(),
+                replacement: None
+            }
+        ),
+        Err(RegistryError::InvalidSunset(_))
+    ));
+}
+
+#[test]
+fn deprecated_backend_emits_warning() {
+    setup();
+    assert_eq!(
+        backend_deprecation("legacy@stable").unwrap().sunset,
+        "2026-06-30"
+    );
+    let cfg = Config::new("legacy@latest", "Prime254", "blake3", 2, false, "balanced");
+    let warnings = validate_config_with_warnings(&cfg).expect("config valid");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, "BackendDeprecated");
+    assert!(warnings[0].msg.contains("2026-06-30"));
+
+    let cfg = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    assert!(validate_config_with_warnings(&cfg).unwrap().is_empty());
+}
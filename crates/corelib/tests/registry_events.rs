@@ -0,0 +1,82 @@
+use std::sync::{Arc, Mutex};
+
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::registry::{self, RegistryEvent};
+
+const AIR: &str = r#"
+[meta]
+name = "observed"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+"#;
+
+#[test]
+fn hooks_observe_registration_selection_prove_and_verify() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let hook = registry::on_event(Box::new(move |e| sink.lock().unwrap().push(e)));
+
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(AIR).unwrap();
+    let config = Config::new("native@latest", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = PublicInputs::from_json(r#"{"x":1}"#).unwrap();
+    let proof = zkprov_corelib::prove(&config, &ir, &inputs).unwrap();
+    let other = PublicInputs::from_json(r#"{"x":2}"#).unwrap();
+    assert!(zkprov_corelib::verify(&config, &ir, &other, &proof.view()).is_err());
+
+    assert!(registry::remove_event_hook(hook));
+    assert!(!registry::remove_event_hook(hook));
+    let _ = zkprov_corelib::prove(&config, &ir, &inputs);
+
+    let events = events.lock().unwrap();
+    assert!(events.contains(&RegistryEvent::BackendRegistered {
+        backend_id: "native@0.0"
+    }));
+    let mut dispatch = events
+        .iter()
+        .filter(|e| !matches!(e, RegistryEvent::BackendRegistered { .. }));
+    assert_eq!(
+        dispatch.next(),
+        Some(&RegistryEvent::BackendSelected {
+            requested: "native@latest".to_string(),
+            backend_id: "native@0.0",
+            profile_id: "balanced".to_string(),
+        })
+    );
+    assert!(matches!(
+        dispatch.next(),
+        Some(RegistryEvent::ProveStarted { program, .. }) if program == "observed"
+    ));
+    assert!(matches!(
+        dispatch.next(),
+        Some(RegistryEvent::ProveFinished { proof_len: Some(len), error: None, .. })
+            if *len == proof.to_bytes().len() as u64
+    ));
+    assert!(matches!(
+        dispatch.next(),
+        Some(RegistryEvent::BackendSelected { .. })
+    ));
+    let verified = dispatch.next().unwrap();
+    assert!(matches!(
+        verified,
+        RegistryEvent::Verified {
+            verified: false,
+            error: Some(_),
+            ..
+        }
+    ));
+    let json = serde_json::to_value(verified).unwrap();
+    assert_eq!(json["event"], "verified");
+    assert_eq!(json["backend_id"], "native@0.0");
+    // Nothing after unsubscribing.
+    assert!(dispatch.next().is_none());
+}
@@ -0,0 +1,145 @@
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, Deprecation, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::errors::{ProveError, RegistryError, VerifyError};
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{Proof, ProofView};
+use zkprov_corelib::registry::{
+    deprecate_backend, ensure_builtins_registered, find_backends, register_alias, register_backend,
+    select_backend, snapshot, BackendQuery,
+};
+
+/// Capability-only backend with a configurable id and recursion mode.
+struct Stub(&'static str, &'static str);
+
+impl ProverBackend for Stub {
+    fn id(&self) -> &'static str {
+        self.0
+    }
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            fields: vec!["Prime254"],
+            hashes: vec!["poseidon2"],
+            fri_arities: vec![4],
+            recursion: self.1,
+            lookups: true,
+            curves: vec![],
+            pedersen: false,
+            max_rows: Some(1 << 16),
+            incremental: false,
+            nondeterminism: Vec::new(),
+        }
+    }
+    fn prove(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        unimplemented!("capability-only test backend")
+    }
+}
+impl VerifierBackend for Stub {
+    fn verify(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        _: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        unimplemented!("capability-only test backend")
+    }
+}
+
+fn register_stubs() {
+    ensure_builtins_registered();
+    for (id, recursion) in [
+        ("recur@0.1", "stark-in-stark"),
+        ("recur@0.2", "stark-in-stark"),
+    ] {
+        let _ = register_backend(Box::new(Stub(id, recursion)), Box::new(Stub(id, recursion)));
+    }
+    register_alias("recur@latest", "recur@0.2").unwrap();
+    deprecate_backend(
+        "recur@0.1",
+        Deprecation {
+            sunset: "2030-01-01".to_string(),
+            replacement: Some("recur@0.2".to_string()),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn backends_are_found_by_capability_predicate() {
+    register_stubs();
+    let recursive_poseidon =
+        find_backends(|caps| caps.hashes.contains(&"poseidon2") && caps.recursion != "none");
+    assert_eq!(recursive_poseidon, vec!["recur@0.1", "recur@0.2"]);
+    assert!(find_backends(|caps| caps.recursion == "none").contains(&"native@0.0"));
+
+    let query: BackendQuery =
+        serde_json::from_str(r#"{"hash":"poseidon2","fri_arity":4,"recursion":true}"#).unwrap();
+    assert_eq!(
+        find_backends(|caps| query.matches(caps)),
+        vec!["recur@0.1", "recur@0.2"]
+    );
+    // Deprecated backends are skipped when picking one.
+    assert_eq!(select_backend(&query).unwrap(), "recur@0.2");
+
+    let too_tall = BackendQuery {
+        min_rows: Some(1 << 20),
+        ..query.clone()
+    };
+    assert!(matches!(
+        select_backend(&too_tall),
+        Err(RegistryError::NoMatchingBackend(q)) if q.contains("\"min_rows\":1048576")
+    ));
+    assert!(serde_json::from_str::<BackendQuery>(r#"{"hashes":["blake3"]}"#).is_err());
+
+    // An AIR's `meta.requires` narrows the match.
+    let needs_lookups: BackendQuery =
+        serde_json::from_str(r#"{"requires":["lookups","recursion"]}"#).unwrap();
+    assert_eq!(
+        find_backends(|caps| needs_lookups.matches(caps)),
+        vec!["recur@0.1", "recur@0.2"]
+    );
+    let needs_keccak = BackendQuery {
+        requires: vec!["keccak".to_string()],
+        ..Default::default()
+    };
+    let keccak = find_backends(|caps| needs_keccak.matches(caps));
+    assert!(keccak.contains(&"native@0.0") && !keccak.contains(&"recur@0.1"));
+}
+
+#[test]
+fn snapshot_serializes_capabilities_aliases_and_placeholders() {
+    register_stubs();
+    let snap = serde_json::to_value(snapshot()).unwrap();
+    let backends = snap["backends"].as_array().unwrap();
+    let ids: Vec<&str> = backends.iter().map(|b| b["id"].as_str().unwrap()).collect();
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(ids, sorted);
+
+    let native = backends.iter().find(|b| b["id"] == "native@0.0").unwrap();
+    assert_eq!(native["placeholder"], true);
+    assert_eq!(native["aliases"], serde_json::json!(["native@latest"]));
+    let recur = backends.iter().find(|b| b["id"] == "recur@0.1").unwrap();
+    assert_eq!(recur["placeholder"], false);
+    assert_eq!(recur["capabilities"]["recursion"], "stark-in-stark");
+    assert_eq!(recur["capabilities"]["max_rows"], 65536);
+    assert_eq!(recur["deprecated"]["replacement"], "recur@0.2");
+    assert_eq!(snap["aliases"]["recur@latest"], "recur@0.2");
+    let keccak = snap["hashes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|h| h["id"] == "keccak256")
+        .unwrap();
+    assert_eq!(keccak["aliases"][0], "keccak");
+    assert_eq!(keccak["algebraic"], false);
+}
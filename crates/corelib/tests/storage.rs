@@ -0,0 +1,66 @@
+use std::fs;
+
+use tempfile::tempdir;
+use zkprov_corelib::storage::{artifact_key, FsStorage, S3Config, Storage, StorageConfig};
+
+#[test]
+fn fs_storage_round_trips_and_confines_keys() {
+    let dir = tempdir().unwrap();
+    let store = FsStorage::new(dir.path().join("store"));
+    let src = dir.path().join("p.proof");
+    fs::write(&src, b"proof bytes").unwrap();
+
+    let key = artifact_key(&[0xab; 32], "proof");
+    assert_eq!(key, format!("{}/proof", "ab".repeat(32)));
+    assert!(!store.exists(&key).unwrap());
+    store.put(&key, &src).unwrap();
+    assert!(store.exists(&key).unwrap());
+    let back = dir.path().join("back.proof");
+    store.get(&key, &back).unwrap();
+    assert_eq!(fs::read(&back).unwrap(), b"proof bytes");
+    assert!(store.get("missing/proof", &back).is_err());
+
+    for bad in ["", "/etc/passwd", "../escape", "a//b", "a/./b", "a\\b"] {
+        assert!(store.put(bad, &src).is_err(), "{bad:?}");
+    }
+}
+
+#[test]
+fn storage_config_reads_the_storage_table() {
+    let cfg = StorageConfig::from_toml_str(
+        r#"
+[other]
+ignored = true
+
+[storage]
+kind = "s3"
+bucket = "proofs"
+endpoint = "https://storage.googleapis.com"
+region = "auto"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        cfg,
+        Some(StorageConfig::S3(S3Config {
+            bucket: "proofs".into(),
+            endpoint: "https://storage.googleapis.com".into(),
+            region: "auto".into(),
+            prefix: String::new(),
+            access_key_env: "AWS_ACCESS_KEY_ID".into(),
+            secret_key_env: "AWS_SECRET_ACCESS_KEY".into(),
+        }))
+    );
+    assert_eq!(
+        StorageConfig::from_toml_str("[storage]\nkind = \"fs\"\nroot = \"archive\"\n").unwrap(),
+        Some(StorageConfig::Fs {
+            root: "archive".into()
+        })
+    );
+    assert_eq!(StorageConfig::from_toml_str("").unwrap(), None);
+    assert!(StorageConfig::from_toml_str("[storage]\nkind = \"ftp\"\n").is_err());
+    assert!(StorageConfig::from_toml_str(
+        "[storage]\nkind = \"s3\"\nbucket = \"b\"\nsecret = \"x\"\n"
+    )
+    .is_err());
+}
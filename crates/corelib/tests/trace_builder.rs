@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use num_bigint::BigUint;
+use zkprov_corelib::air::{parse_air_file, AirIr};
+use zkprov_corelib::trace::eval::violations;
+use zkprov_corelib::trace::{Trace, TraceBuilder, TraceCompression};
+
+const SELECTOR_AIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../examples/air/selector_counter.air"
+);
+
+fn selector_air() -> AirIr {
+    parse_air_file(Path::new(SELECTOR_AIR)).unwrap()
+}
+
+/// `a` counts up by one for three rows, then doubles.
+fn counter(ir: &AirIr) -> TraceBuilder {
+    let mut builder = TraceBuilder::for_air(ir).unwrap();
+    let mut a = 1u64;
+    for row in 0..8 {
+        let inc = row < 3;
+        builder
+            .push_named_row([("a", a), ("s_inc", inc as u64), ("s_dbl", !inc as u64)])
+            .unwrap();
+        a = if inc { a + 1 } else { a * 2 };
+    }
+    builder
+}
+
+#[test]
+fn named_rows_build_a_satisfying_trace() {
+    let ir = selector_air();
+    let builder = counter(&ir);
+    assert_eq!((builder.rows(), builder.cols()), (8, 3));
+    let trace = builder.build_for(&ir).unwrap();
+    assert_eq!(trace.field, "Prime254");
+    assert_eq!(trace.get(4, 0), Some(BigUint::from(8u32)));
+    assert!(violations(&trace, &ir, None).unwrap().is_empty());
+}
+
+#[test]
+fn positional_rows_match_named_rows() {
+    let ir = selector_air();
+    let mut builder = TraceBuilder::new("Prime254", 3);
+    let mut a = 1u64;
+    for row in 0..8 {
+        let inc = row < 3;
+        builder.push_row([a, inc as u64, !inc as u64]).unwrap();
+        a = if inc { a + 1 } else { a * 2 };
+    }
+    assert_eq!(builder.build().unwrap(), counter(&ir).build().unwrap());
+}
+
+#[test]
+fn rows_are_checked_as_they_are_pushed() {
+    let ir = selector_air();
+    let mut builder = TraceBuilder::for_air(&ir).unwrap();
+    let err = builder.push_row([1u64, 0]).unwrap_err();
+    assert!(
+        err.to_string().contains("has 2 values, expected 3"),
+        "{err}"
+    );
+    assert!(builder.push_named_row([("b", 1u64)]).is_err());
+    assert!(builder.push_named_row([("a", 1u64), ("a", 2u64)]).is_err());
+
+    let p = zkprov_corelib::crypto::field::field_by_id("Goldilocks")
+        .unwrap()
+        .modulus();
+    let mut goldilocks = TraceBuilder::new("Goldilocks", 1);
+    let err = goldilocks.push_row([p]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("not a canonical Goldilocks element"));
+    assert_eq!(goldilocks.rows(), 0);
+
+    // No AIR, so no column names.
+    assert!(TraceBuilder::new("Prime254", 1)
+        .push_named_row([("a", 1u64)])
+        .is_err());
+}
+
+#[test]
+fn check_shape_rejects_traces_that_do_not_fit_the_air() {
+    let ir = selector_air();
+    let shape_err = |builder: TraceBuilder| builder.build_for(&ir).unwrap_err().to_string();
+
+    let mut wrong_field = TraceBuilder::new("Goldilocks", 3);
+    wrong_field.push_row([1u64, 1, 0]).unwrap();
+    assert!(shape_err(wrong_field).contains("does not match AIR field"));
+
+    let mut wrong_cols = TraceBuilder::new("Prime254", 2);
+    wrong_cols.push_row([1u64, 1]).unwrap();
+    assert!(shape_err(wrong_cols).contains("AIR declares 3"));
+
+    let mut three_rows = TraceBuilder::for_air(&ir).unwrap();
+    for _ in 0..3 {
+        three_rows.push_row([1u64, 1, 0]).unwrap();
+    }
+    assert!(shape_err(three_rows).contains("power of two, got 3"));
+
+    let mut selector = TraceBuilder::for_air(&ir).unwrap();
+    selector.push_named_row([("s_dbl", 2u64)]).unwrap();
+    assert!(shape_err(selector).contains("selector 's_dbl' is not boolean at row 0"));
+}
+
+#[test]
+fn parse_reads_zkt_and_json() {
+    let trace = counter(&selector_air()).build().unwrap();
+    let zkt = trace.encode(TraceCompression::Rle).unwrap();
+    assert_eq!(Trace::parse(&zkt).unwrap(), trace);
+    let json = serde_json::to_vec(&trace.to_json()).unwrap();
+    assert_eq!(Trace::parse(&json).unwrap(), trace);
+    assert!(Trace::parse(b"not a trace").is_err());
+}
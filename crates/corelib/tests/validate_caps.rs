@@ -20,9 +20,16 @@ fn invalid_field() {
 #[test]
 fn invalid_hash() {
     ensure_builtins_registered();
-    let cfg = Config::new("native@0.0", "Prime254", "keccak", 2, false, "balanced");
+    let cfg = Config::new("native@0.0", "Prime254", "sha256", 2, false, "balanced");
     let err = validate_config(&cfg).unwrap_err().to_string();
-    assert!(err.contains("hash 'keccak'"));
+    assert!(err.contains("hash 'sha256'"));
+}
+
+#[test]
+fn keccak_alias_maps_to_keccak256_capability() {
+    ensure_builtins_registered();
+    let cfg = Config::new("native@0.0", "Prime254", "keccak", 2, false, "balanced");
+    assert!(validate_config(&cfg).is_ok());
 }
 
 #[test]
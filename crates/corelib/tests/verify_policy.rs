@@ -0,0 +1,375 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::backend::PublicInputs;
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::VerifyError;
+use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::evm::typed::program_hash;
+use zkprov_corelib::policy::{PolicyEvidence, VerifyPolicy, MAX_CLOCK_SKEW};
+use zkprov_corelib::profile::trust::{public_key_hex, sign_profile};
+use zkprov_corelib::profile::{parse_overrides, Profile};
+use zkprov_corelib::timestamp::{TimestampRequest, TokenVerifier};
+
+const AIR: &str = r#"
+[meta]
+name = "relied"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+"#;
+
+const SIGNER: [u8; 32] = [7u8; 32];
+/// `genTime` of [`timestamp_for`]'s tokens: 2026-10-17T09:30:00Z.
+const STAMPED_AT: u64 = 1_792_229_400;
+
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    if content.len() < 0x80 {
+        out.push(content.len() as u8);
+    } else {
+        out.extend([0x81, content.len() as u8]);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Granted `TimeStampResp` over `digest_d`, issued at [`STAMPED_AT`].
+fn timestamp_for(digest_d: &[u8; 32]) -> Vec<u8> {
+    let req = TimestampRequest::new(digest_d, 7).to_der();
+    let tst = der(
+        0x30,
+        &[
+            der(0x02, &[1]),
+            der(0x06, &[0x2a, 0x03]),
+            req[5..56].to_vec(),
+            der(0x02, &[0x07]),
+            der(0x18, b"20261017093000Z"),
+        ]
+        .concat(),
+    );
+    let tst_info_oid = [
+        0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x01, 0x04,
+    ];
+    let encap = der(
+        0x30,
+        &[der(0x06, &tst_info_oid), der(0xa0, &der(0x04, &tst))].concat(),
+    );
+    let signed_data = der(
+        0x30,
+        &[der(0x02, &[3]), der(0x31, &[]), encap, der(0x31, &[])].concat(),
+    );
+    let signed_data_oid = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+    let token = der(
+        0x30,
+        &[der(0x06, &signed_data_oid), der(0xa0, &signed_data)].concat(),
+    );
+    der(0x30, &[der(0x30, &der(0x02, &[0])), token].concat())
+}
+
+/// Accepts every token: the test tokens carry no signature, and signature
+/// checking itself is the CLI's `openssl ts -verify`.
+#[derive(Debug)]
+struct TrustAll;
+
+impl TokenVerifier for TrustAll {
+    fn verify_token(&self, _: &[u8], _: &[u8; 32]) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+fn rules(err: VerifyError) -> Vec<&'static str> {
+    match err {
+        VerifyError::Policy(violations) => violations.iter().map(|v| v.rule).collect(),
+        other => panic!("expected a policy violation, got {other}"),
+    }
+}
+
+#[test]
+fn policies_parse_and_reject_malformed_rules() {
+    let policy = VerifyPolicy::from_toml_str(
+        r#"
+programs = ["0x0000000000000000000000000000000000000000000000000000000000000000"]
+min_lambda_bits = 100
+backends = ["native"]
+max_proof_age = "30d"
+"#,
+    )
+    .unwrap();
+    assert_eq!(policy.backends, ["native"]);
+    assert_eq!(
+        VerifyPolicy::from_toml_str("").unwrap(),
+        VerifyPolicy::default()
+    );
+
+    for (src, needle) in [
+        ("programs = [\"0x1234\"]", "expected 32 bytes"),
+        ("max_proof_age = \"1 week\"", "max_proof_age"),
+        ("signers = [\"zz\"]", "signers"),
+        ("max_age = \"1d\"", "unknown field"),
+    ] {
+        let err = format!("{:#}", VerifyPolicy::from_toml_str(src).unwrap_err());
+        assert!(err.contains(needle), "{src}: {err}");
+    }
+}
+
+#[test]
+fn verify_with_policy_reports_every_violated_rule() {
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(AIR).unwrap();
+    let config = Config::new("native@latest", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = PublicInputs::from_json(r#"{"x":1}"#).unwrap();
+    let proof = zkprov_corelib::prove(&config, &ir, &inputs).unwrap();
+    let view = proof.view();
+    let token = timestamp_for(&digest_D(&view.header, view.body));
+    let evidence = PolicyEvidence {
+        program_source: AIR.as_bytes(),
+        timestamp: Some(&token),
+        tsa: Some(&TrustAll),
+        now: UNIX_EPOCH + Duration::from_secs(STAMPED_AT + 3600),
+        embedded_inputs: false,
+    };
+    let verify = |policy: &VerifyPolicy, evidence: &PolicyEvidence| {
+        zkprov_corelib::verify_with_policy(&config, &ir, &inputs, &view, policy, evidence)
+    };
+
+    let accepting = VerifyPolicy {
+        programs: vec![format!(
+            "0x{}",
+            alloy_primitives::hex::encode(program_hash(AIR.as_bytes()))
+        )],
+        min_lambda_bits: Some(100),
+        backends: vec!["native@0.0".into()],
+        max_proof_age: Some("2h".into()),
+        signers: Vec::new(),
+        allow_embedded_inputs: None,
+    };
+    verify(&accepting, &evidence).unwrap();
+    verify(&VerifyPolicy::default(), &evidence).unwrap();
+
+    let rejecting = VerifyPolicy {
+        programs: vec![format!("0x{}", "00".repeat(32))],
+        min_lambda_bits: Some(120),
+        backends: vec!["ministark".into()],
+        max_proof_age: Some("30m".into()),
+        signers: vec![public_key_hex(&SIGNER)],
+        allow_embedded_inputs: Some(false),
+    };
+    let err = verify(&rejecting, &evidence).unwrap_err();
+    assert_eq!(err.category(), "policy");
+    assert!(err
+        .to_string()
+        .contains("min_lambda_bits: profile 'balanced' targets 100 bits, below 120"));
+    assert_eq!(
+        rules(err),
+        [
+            "programs",
+            "backends",
+            "min_lambda_bits",
+            "signers",
+            "max_proof_age"
+        ]
+    );
+
+    // Inputs taken from the proof are refused only when the policy says so.
+    let embedded = PolicyEvidence {
+        embedded_inputs: true,
+        ..evidence
+    };
+    verify(&accepting, &embedded).unwrap();
+    let no_embedding = VerifyPolicy {
+        allow_embedded_inputs: Some(false),
+        ..VerifyPolicy::default()
+    };
+    verify(&no_embedding, &evidence).unwrap();
+    assert_eq!(
+        rules(verify(&no_embedding, &embedded).unwrap_err()),
+        ["allow_embedded_inputs"]
+    );
+
+    // Age needs a timestamp over this very proof.
+    let no_token = PolicyEvidence {
+        timestamp: None,
+        ..evidence
+    };
+    assert_eq!(
+        rules(verify(&accepting, &no_token).unwrap_err()),
+        ["max_proof_age"]
+    );
+    let unauthenticated = PolicyEvidence {
+        tsa: None,
+        ..evidence
+    };
+    let err = verify(&accepting, &unauthenticated).unwrap_err();
+    assert!(err.to_string().contains("no trusted TSA"), "{err}");
+    assert_eq!(rules(err), ["max_proof_age"]);
+    let other = timestamp_for(&[0u8; 32]);
+    let wrong_token = PolicyEvidence {
+        timestamp: Some(&other),
+        ..evidence
+    };
+    assert_eq!(
+        rules(verify(&accepting, &wrong_token).unwrap_err()),
+        ["max_proof_age"]
+    );
+    // A genTime from the future is only tolerated within the clock skew.
+    let early = PolicyEvidence {
+        now: UNIX_EPOCH + Duration::from_secs(STAMPED_AT) - MAX_CLOCK_SKEW,
+        ..evidence
+    };
+    verify(&accepting, &early).unwrap();
+    let future = PolicyEvidence {
+        now: UNIX_EPOCH + Duration::from_secs(STAMPED_AT - 3600),
+        ..evidence
+    };
+    let err = verify(&accepting, &future).unwrap_err();
+    assert!(err.to_string().contains("3600s ahead"), "{err}");
+    assert_eq!(rules(err), ["max_proof_age"]);
+    let stale = PolicyEvidence {
+        now: UNIX_EPOCH + Duration::from_secs(STAMPED_AT + 3 * 3600),
+        ..evidence
+    };
+    assert_eq!(
+        rules(verify(&accepting, &stale).unwrap_err()),
+        ["max_proof_age"]
+    );
+
+    // A policy-compliant proof that does not verify still fails verification.
+    let other_inputs = PublicInputs::from_json(r#"{"x":2}"#).unwrap();
+    let err = zkprov_corelib::verify_with_policy(
+        &config,
+        &ir,
+        &other_inputs,
+        &view,
+        &accepting,
+        &evidence,
+    )
+    .unwrap_err();
+    assert_ne!(err.category(), "policy");
+}
+
+#[test]
+fn signer_rule_accepts_profiles_signed_by_a_listed_key() {
+    let mut profile: Profile = toml::from_str(
+        r#"
+id = "acme/strong"
+owner = "acme"
+lambda_bits = 128
+fri_blowup = 16
+fri_queries = 30
+grind_bits = 8
+"#,
+    )
+    .unwrap();
+    let policy = VerifyPolicy {
+        signers: vec![public_key_hex(&[1u8; 32]), public_key_hex(&SIGNER)],
+        min_lambda_bits: Some(128),
+        ..VerifyPolicy::default()
+    };
+    assert_eq!(policy.check_profile(&profile)[0].rule, "signers");
+
+    profile.signature = Some(sign_profile(&profile, &SIGNER));
+    assert!(policy.check_profile(&profile).is_empty());
+    profile.signature = Some(sign_profile(&profile, &[9u8; 32]));
+    assert_eq!(policy.check_profile(&profile)[0].rule, "signers");
+}
+
+#[test]
+fn strength_rule_uses_the_run_overrides_and_estimated_security() {
+    let policy = VerifyPolicy {
+        min_lambda_bits: Some(100),
+        ..VerifyPolicy::default()
+    };
+    let mut profile: Profile = toml::from_str(
+        r#"
+id = "thin"
+lambda_bits = 128
+fri_blowup = 16
+fri_queries = 24
+grind_bits = 0
+"#,
+    )
+    .unwrap();
+    // Targets 128 bits, but 24 queries at blowup 16 give only 96.
+    let found = policy.check_profile(&profile);
+    assert_eq!(found[0].rule, "min_lambda_bits");
+    assert!(
+        found[0].reason.contains("gives 96 bits, below 100"),
+        "{found:?}"
+    );
+    profile.grind_bits = Some(4);
+    assert!(policy.check_profile(&profile).is_empty());
+    profile.fri_queries = None;
+    assert!(policy.check_profile(&profile)[0]
+        .reason
+        .contains("cannot be estimated"));
+
+    // The config's overrides are part of the proof's parameters.
+    let ir = parse_air_str(AIR).unwrap();
+    let proof = {
+        zkprov_backend_native::ensure_native_registered();
+        let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+        let inputs = PublicInputs::from_json(r#"{"x":1}"#).unwrap();
+        zkprov_corelib::prove(&config, &ir, &inputs).unwrap()
+    };
+    let evidence = PolicyEvidence {
+        program_source: AIR.as_bytes(),
+        timestamp: None,
+        tsa: None,
+        now: UNIX_EPOCH,
+        embedded_inputs: false,
+    };
+    let check = |overrides: &[&str]| {
+        let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced")
+            .with_profile_overrides(parse_overrides(overrides).unwrap());
+        policy.check(&config, &proof.view(), &evidence)
+    };
+    assert!(check(&[]).is_empty());
+    assert!(check(&["fri_queries=20", "grind_bits=20"]).is_empty());
+    let weak = check(&["fri_queries=16", "grind_bits=0"]);
+    assert_eq!(weak.len(), 1);
+    assert!(weak[0].reason.contains("below lambda_bits 100"), "{weak:?}");
+}
+
+#[test]
+fn signer_rule_refuses_runs_that_override_the_signed_profile() {
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(AIR).unwrap();
+    let config = Config::new("native@0.0", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = PublicInputs::from_json(r#"{"x":1}"#).unwrap();
+    let proof = zkprov_corelib::prove(&config, &ir, &inputs).unwrap();
+    let evidence = PolicyEvidence {
+        program_source: AIR.as_bytes(),
+        timestamp: None,
+        tsa: None,
+        now: UNIX_EPOCH,
+        embedded_inputs: false,
+    };
+    let policy = VerifyPolicy {
+        signers: vec![public_key_hex(&SIGNER)],
+        ..VerifyPolicy::default()
+    };
+
+    // A signature over the profile file says nothing about weakened
+    // parameters, so any override breaks the rule on its own.
+    let weakened = config
+        .clone()
+        .with_profile_overrides(parse_overrides(&["fri_queries=8"]).unwrap());
+    let found = policy.check(&weakened, &proof.view(), &evidence);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].rule, "signers");
+    assert!(
+        found[0].reason.contains("overrides fri_queries"),
+        "{found:?}"
+    );
+
+    let found = policy.check(&config, &proof.view(), &evidence);
+    assert_eq!(found[0].rule, "signers");
+    assert!(!found[0].reason.contains("overrides"), "{found:?}");
+}
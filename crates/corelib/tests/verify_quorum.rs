@@ -0,0 +1,189 @@
+use zkprov_corelib::air::parser::parse_air_str;
+use zkprov_corelib::air::AirIr;
+use zkprov_corelib::backend::{
+    CallContext, Capabilities, ProverBackend, PublicInputs, VerifierBackend,
+};
+use zkprov_corelib::config::Config;
+use zkprov_corelib::errors::{ProveError, VerifyError};
+use zkprov_corelib::profile::Profile;
+use zkprov_corelib::proof::{hash64, Proof, ProofHeader, ProofView};
+use zkprov_corelib::quorum::{backend_for_header, verify_quorum, QuorumStatus};
+use zkprov_corelib::registry::register_backend;
+
+const AIR: &str = r#"
+[meta]
+name = "redundant"
+field = "Prime254"
+hash = "blake3"
+
+[columns]
+trace_cols = 2
+
+[constraints]
+transition_count = 1
+boundary_count = 0
+"#;
+
+/// Accepts any body that starts with the program name, so differently
+/// salted proofs of one statement all verify: a nondeterministic prover.
+struct Salted;
+
+impl ProverBackend for Salted {
+    fn id(&self) -> &'static str {
+        "salted@0.1"
+    }
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            fields: vec!["Prime254"],
+            hashes: vec!["blake3"],
+            fri_arities: vec![2],
+            recursion: "none",
+            lookups: false,
+            curves: vec!["placeholder"],
+            pedersen: true,
+            max_rows: None,
+            incremental: false,
+            nondeterminism: Vec::new(),
+        }
+    }
+    fn prove(
+        &self,
+        _: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+    ) -> Result<Proof, ProveError> {
+        unreachable!("proofs are built by salted_proof")
+    }
+}
+
+impl VerifierBackend for Salted {
+    fn verify(
+        &self,
+        ir: &AirIr,
+        _: &Profile,
+        _: &CallContext,
+        _: &PublicInputs,
+        proof: &ProofView<'_>,
+    ) -> Result<(), VerifyError> {
+        if proof.body.starts_with(ir.meta.name.as_bytes()) {
+            Ok(())
+        } else {
+            Err(VerifyError::Rejected("name mismatch".to_string()))
+        }
+    }
+}
+
+fn salted_proof(salt: u8) -> Vec<u8> {
+    let header = ProofHeader {
+        backend_id_hash: hash64("BACKEND", b"salted@0.1"),
+        profile_id_hash: hash64("PROFILE", b"balanced"),
+        pubio_hash: 0,
+        body_len: 0,
+        config_hash: 0,
+        flags: 0,
+    };
+    Proof::new(header, [b"redundant".as_slice(), &[salt]].concat()).to_bytes()
+}
+
+fn config(backend: &str, profile: &str) -> Config {
+    Config::new(backend, "Prime254", "blake3", 2, false, profile)
+}
+
+#[test]
+fn quorum_counts_verified_proofs_per_header_backend_and_profile() {
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(AIR).unwrap();
+    let inputs = PublicInputs::from_json(r#"{"x":1}"#).unwrap();
+    let balanced = zkprov_corelib::prove(&config("native@0.0", "balanced"), &ir, &inputs)
+        .unwrap()
+        .to_bytes();
+    let fast = zkprov_corelib::prove(&config("native@0.0", "dev-fast"), &ir, &inputs)
+        .unwrap()
+        .to_bytes();
+    let other = zkprov_corelib::prove(
+        &config("native@0.0", "balanced"),
+        &ir,
+        &PublicInputs::from_json(r#"{"x":2}"#).unwrap(),
+    )
+    .unwrap()
+    .to_bytes();
+    let header = ProofView::from_bytes(&balanced).unwrap().header;
+    assert_eq!(backend_for_header(&header), Some("native@0.0"));
+
+    let proofs = vec![
+        ("a".to_string(), balanced.clone()),
+        ("b".to_string(), balanced.clone()),
+        ("c".to_string(), fast),
+        ("d".to_string(), other),
+        ("e".to_string(), balanced[..20].to_vec()),
+    ];
+    // The config's profile is only a fallback: `c` verifies as dev-fast.
+    let report =
+        verify_quorum(&config("native@0.0", "balanced"), &ir, &inputs, &proofs, 3).unwrap();
+    assert!(report.reached);
+    assert_eq!((report.accepted, report.total), (3, 5));
+    let status: Vec<QuorumStatus> = report.entries.iter().map(|e| e.status).collect();
+    use QuorumStatus::*;
+    assert_eq!(status, [Accepted, Accepted, Accepted, Rejected, Rejected]);
+    assert_eq!(report.entries[2].profile, "dev-fast");
+    assert_eq!(report.entries[0].digest, report.entries[1].digest);
+    assert_eq!(
+        report.entries[3].category.as_deref(),
+        Some("header_binding")
+    );
+    assert_eq!(report.entries[4].category.as_deref(), Some("header_decode"));
+    assert!(report.entries[4].digest.is_none());
+
+    let report =
+        verify_quorum(&config("native@0.0", "balanced"), &ir, &inputs, &proofs, 4).unwrap();
+    assert!(!report.reached);
+
+    for threshold in [0, 6] {
+        let err = verify_quorum(
+            &config("native@0.0", "balanced"),
+            &ir,
+            &inputs,
+            &proofs,
+            threshold,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("between 1 and"), "{err}");
+    }
+}
+
+#[test]
+fn diverging_digests_from_one_backend_do_not_count() {
+    register_backend(Box::new(Salted), Box::new(Salted)).unwrap();
+    let ir = parse_air_str(AIR).unwrap();
+    let inputs = PublicInputs::from_json("{}").unwrap();
+    let proofs: Vec<(String, Vec<u8>)> = [1, 2, 1]
+        .into_iter()
+        .enumerate()
+        .map(|(i, salt)| (i.to_string(), salted_proof(salt)))
+        .collect();
+
+    let report =
+        verify_quorum(&config("salted@0.1", "balanced"), &ir, &inputs, &proofs, 2).unwrap();
+    assert!(report.reached);
+    assert_eq!(report.accepted, 2);
+    assert_eq!(report.entries[1].status, QuorumStatus::Diverged);
+    assert!(report.entries[1]
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("digest differs"));
+
+    // With no majority the first digest wins the tie.
+    let report = verify_quorum(
+        &config("salted@0.1", "balanced"),
+        &ir,
+        &inputs,
+        &proofs[..2],
+        2,
+    )
+    .unwrap();
+    assert!(!report.reached);
+    assert_eq!(report.entries[0].status, QuorumStatus::Accepted);
+    assert_eq!(report.entries[1].status, QuorumStatus::Diverged);
+}
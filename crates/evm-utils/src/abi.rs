@@ -0,0 +1,80 @@
+use alloy_sol_types::{sol, SolType, SolValue};
+use anyhow::{anyhow, Result};
+
+use crate::ProofMeta;
+
+sol! {
+    /// ABI surface for proof metadata used by the EVM bridge.
+    #[derive(Debug, PartialEq, Eq)]
+    struct EvmProofMeta {
+        uint64 backendId;
+        uint64 profileId;
+        uint64 pubioHash;
+        uint64 bodyLen;
+        uint64 configHash;
+    }
+
+    /// ABI container for serialized public IO JSON.
+    #[derive(Debug, PartialEq, Eq)]
+    struct EvmPublicIO {
+        bytes data;
+    }
+
+    /// ABI container for proof body bytes.
+    #[derive(Debug, PartialEq, Eq)]
+    struct EvmProofBody {
+        bytes data;
+    }
+}
+
+impl From<&ProofMeta> for EvmProofMeta {
+    fn from(meta: &ProofMeta) -> Self {
+        Self {
+            backendId: meta.backend_id_hash,
+            profileId: meta.profile_id_hash,
+            pubioHash: meta.pubio_hash,
+            bodyLen: meta.body_len,
+            configHash: meta.config_hash,
+        }
+    }
+}
+
+impl From<EvmProofMeta> for ProofMeta {
+    fn from(meta: EvmProofMeta) -> Self {
+        Self {
+            backend_id_hash: meta.backendId,
+            profile_id_hash: meta.profileId,
+            pubio_hash: meta.pubioHash,
+            body_len: meta.bodyLen,
+            config_hash: meta.configHash,
+        }
+    }
+}
+
+pub fn encode_meta(meta: &ProofMeta) -> Vec<u8> {
+    EvmProofMeta::from(meta).abi_encode()
+}
+
+pub fn decode_meta(data: &[u8]) -> Result<ProofMeta> {
+    Ok(<EvmProofMeta as SolType>::abi_decode(data)?.into())
+}
+
+pub fn encode_body(body: &[u8]) -> Vec<u8> {
+    body.abi_encode()
+}
+
+pub fn decode_body(data: &[u8]) -> Result<Vec<u8>> {
+    <Vec<u8> as SolValue>::abi_decode(data).map_err(|e| anyhow!(e))
+}
+
+pub fn encode_public_io(json: &str) -> Vec<u8> {
+    let public_io = EvmPublicIO {
+        data: json.as_bytes().to_vec().into(),
+    };
+    public_io.abi_encode()
+}
+
+pub fn decode_public_io(data: &[u8]) -> Result<String> {
+    let decoded = <EvmPublicIO as SolType>::abi_decode(data)?;
+    String::from_utf8(decoded.data.to_vec()).map_err(|e| anyhow!(e))
+}
@@ -0,0 +1,92 @@
+//! Calldata for the reference contracts in `examples/evm_verifier/contracts`:
+//! `VerifierStub` (digest recomputation) and `AggregatorVerifier` (batched
+//! digests, see `zkprov_corelib::evm::aggregate`).
+
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolCall, SolValue};
+use anyhow::{anyhow, Result};
+
+use crate::ProofMeta;
+
+sol! {
+    interface IVerifierStub {
+        function computeDigest(
+            uint64 backendIdHash,
+            uint64 profileIdHash,
+            uint64 pubioHash,
+            uint64 bodyLen,
+            bytes body
+        ) external pure returns (bytes32 D);
+
+        function verifyDigest(
+            uint64 backendIdHash,
+            uint64 profileIdHash,
+            uint64 pubioHash,
+            uint64 bodyLen,
+            bytes body,
+            bytes32 expectedD
+        ) external pure returns (bool);
+    }
+
+    interface IAggregatorVerifier {
+        function verifyAggregate(bytes32 root, uint64 count, bytes32[] digests)
+            external pure returns (bool);
+
+        function verifyEncoded(bytes encoded) external pure returns (bool);
+    }
+}
+
+/// `VerifierStub.computeDigest(meta…, body)`.
+pub fn compute_digest(meta: &ProofMeta, body: &[u8]) -> Vec<u8> {
+    IVerifierStub::computeDigestCall {
+        backendIdHash: meta.backend_id_hash,
+        profileIdHash: meta.profile_id_hash,
+        pubioHash: meta.pubio_hash,
+        bodyLen: meta.body_len,
+        body: body.to_vec().into(),
+    }
+    .abi_encode()
+}
+
+/// `VerifierStub.verifyDigest(meta…, body, expected)`.
+pub fn verify_digest(meta: &ProofMeta, body: &[u8], expected: &[u8; 32]) -> Vec<u8> {
+    IVerifierStub::verifyDigestCall {
+        backendIdHash: meta.backend_id_hash,
+        profileIdHash: meta.profile_id_hash,
+        pubioHash: meta.pubio_hash,
+        bodyLen: meta.body_len,
+        body: body.to_vec().into(),
+        expectedD: B256::from(*expected),
+    }
+    .abi_encode()
+}
+
+/// `AggregatorVerifier.verifyAggregate(root, digests.len(), digests)`.
+pub fn verify_aggregate(root: &[u8; 32], digests: &[[u8; 32]]) -> Vec<u8> {
+    IAggregatorVerifier::verifyAggregateCall {
+        root: B256::from(*root),
+        count: digests.len() as u64,
+        digests: digests.iter().copied().map(B256::from).collect(),
+    }
+    .abi_encode()
+}
+
+/// `AggregatorVerifier.verifyEncoded(encoded)`, for the ABI-encoded
+/// aggregate `zkd evm-digest --aggregate` writes.
+pub fn verify_encoded(encoded: &[u8]) -> Vec<u8> {
+    IAggregatorVerifier::verifyEncodedCall {
+        encoded: encoded.to_vec().into(),
+    }
+    .abi_encode()
+}
+
+/// Return data of `computeDigest`.
+pub fn decode_digest_return(data: &[u8]) -> Result<[u8; 32]> {
+    let digest = <B256 as SolValue>::abi_decode(data).map_err(|e| anyhow!(e))?;
+    Ok(digest.0)
+}
+
+/// Return data of the `verify*` calls.
+pub fn decode_bool_return(data: &[u8]) -> Result<bool> {
+    <bool as SolValue>::abi_decode(data).map_err(|e| anyhow!(e))
+}
@@ -0,0 +1,85 @@
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::ProofMeta;
+
+/// The EVM's `keccak256`.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut h = Keccak::v256();
+    h.update(data);
+    let mut out = [0u8; 32];
+    h.finalize(&mut out);
+    out
+}
+
+/// `keccak256(abi.encode(EvmDigestInput{backendIdHash, profileIdHash,
+/// pubioHash, bodyLen, body}))`, as recomputed by the Solidity verifier.
+///
+/// The ABI encoding is streamed into the hasher word by word, so `body` is
+/// never copied (proof bodies may be large or memory-mapped).
+pub fn digest_d(meta: &ProofMeta, body: &[u8]) -> [u8; 32] {
+    let mut h = Keccak::v256();
+    // Outer offset of the (dynamic) struct, then its five head words; the
+    // `bytes` tail starts right after them.
+    for word in [
+        32,
+        meta.backend_id_hash,
+        meta.profile_id_hash,
+        meta.pubio_hash,
+        meta.body_len,
+        5 * 32,
+        body.len() as u64,
+    ] {
+        h.update(&abi_word(word));
+    }
+    h.update(body);
+    let pad = (32 - body.len() % 32) % 32;
+    h.update(&[0u8; 32][..pad]);
+    let mut out = [0u8; 32];
+    h.finalize(&mut out);
+    out
+}
+
+fn abi_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+#[cfg(all(test, feature = "alloy"))]
+mod tests {
+    use super::*;
+    use alloy_sol_types::{sol, SolValue};
+
+    sol! {
+        struct EvmDigestInput {
+            uint64 backendIdHash;
+            uint64 profileIdHash;
+            uint64 pubioHash;
+            uint64 bodyLen;
+            bytes body;
+        }
+    }
+
+    #[test]
+    fn streamed_digest_matches_abi_encode() {
+        for len in [0usize, 1, 8, 31, 32, 33, 40, 1000] {
+            let body: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let meta = ProofMeta {
+                backend_id_hash: 0x0102_0304_0506_0708,
+                profile_id_hash: u64::MAX,
+                pubio_hash: 7,
+                body_len: len as u64,
+                config_hash: 0xdead,
+            };
+            let encoded = EvmDigestInput {
+                backendIdHash: meta.backend_id_hash,
+                profileIdHash: meta.profile_id_hash,
+                pubioHash: meta.pubio_hash,
+                bodyLen: meta.body_len,
+                body: body.clone().into(),
+            }
+            .abi_encode();
+            assert_eq!(digest_d(&meta, &body), keccak256(&encoded), "len {len}");
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! EVM glue for zkd proofs, usable without the prover.
+//!
+//! Indexers, relayers and bots that only need to recompute a proof's EVM
+//! digest `D`, (de)serialize the ABI payloads or build verifier calldata can
+//! depend on this crate alone. `zkprov-corelib` delegates its `evm::digest`
+//! and `evm::abi` modules here, so both always agree.
+//!
+//! * [`digest`]: `keccak256` and `D` over a [`ProofMeta`] and proof body.
+//!   Needs only `tiny-keccak`.
+//! * [`abi`] and [`calldata`] (feature `alloy`, on by default): typed
+//!   `alloy-sol-types` structs for the metadata, body and public IO payloads,
+//!   and calldata for `examples/evm_verifier/contracts`.
+
+pub mod digest;
+
+#[cfg(feature = "alloy")]
+pub mod abi;
+#[cfg(feature = "alloy")]
+pub mod calldata;
+
+pub use digest::{digest_d, keccak256};
+
+/// The proof header fields the EVM side sees: the four words `D` binds plus
+/// `config_hash`, which travels in the ABI metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ProofMeta {
+    pub backend_id_hash: u64,
+    pub profile_id_hash: u64,
+    pub pubio_hash: u64,
+    pub body_len: u64,
+    pub config_hash: u64,
+}
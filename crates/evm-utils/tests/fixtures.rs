@@ -0,0 +1,63 @@
+#![cfg(feature = "alloy")]
+
+use std::fs;
+use std::path::PathBuf;
+
+use zkd_evm_utils::{abi, calldata, digest_d, keccak256};
+
+fn testdata(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../examples/evm_verifier/testdata")
+        .join(name);
+    fs::read(&path).unwrap_or_else(|e| panic!("reading {}: {e}", path.display()))
+}
+
+#[test]
+fn digest_matches_evm_verifier_fixtures() {
+    let meta_abi = testdata("meta.abi");
+    let meta = abi::decode_meta(&meta_abi).unwrap();
+    assert_eq!(abi::encode_meta(&meta), meta_abi);
+
+    let body = testdata("body.bin");
+    assert_eq!(abi::decode_body(&testdata("body.abi")).unwrap(), body);
+
+    let expected = String::from_utf8(testdata("digest.hex")).unwrap();
+    assert_eq!(hex::encode(digest_d(&meta, &body)), expected.trim());
+}
+
+#[test]
+fn calldata_uses_contract_selectors() {
+    let meta = abi::decode_meta(&testdata("meta.abi")).unwrap();
+    let body = testdata("body.bin");
+    let d = digest_d(&meta, &body);
+    let selector = |sig: &str| keccak256(sig.as_bytes())[..4].to_vec();
+
+    let call = calldata::verify_digest(&meta, &body, &d);
+    assert_eq!(
+        call[..4],
+        selector("verifyDigest(uint64,uint64,uint64,uint64,bytes,bytes32)")
+    );
+    // expectedD is the last head word; the body tail follows it.
+    assert_eq!(&call[4 + 5 * 32..4 + 6 * 32], &d);
+
+    let call = calldata::compute_digest(&meta, &body);
+    assert_eq!(
+        call[..4],
+        selector("computeDigest(uint64,uint64,uint64,uint64,bytes)")
+    );
+
+    let call = calldata::verify_aggregate(&[7; 32], &[d, d]);
+    assert_eq!(
+        call[..4],
+        selector("verifyAggregate(bytes32,uint64,bytes32[])")
+    );
+
+    let encoded = testdata("aggregate.abi");
+    let call = calldata::verify_encoded(&encoded);
+    assert_eq!(call[..4], selector("verifyEncoded(bytes)"));
+
+    assert_eq!(calldata::decode_digest_return(&d).unwrap(), d);
+    let mut word = [0u8; 32];
+    word[31] = 1;
+    assert!(calldata::decode_bool_return(&word).unwrap());
+}
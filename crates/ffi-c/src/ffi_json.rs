@@ -1,11 +1,10 @@
 use std::ffi::CString;
 
 use serde::Serialize;
-use serde_json::{json, Map, Value};
+use serde_json::{Map, Value};
+use zkprov_ffi_types::{Empty, RESERVED_FIELDS};
 
-use crate::error::{ErrorCode, ZKP_OK};
-
-const RESERVED_FIELDS: &[&str] = &["ok", "code", "msg"];
+use crate::error::ErrorCode;
 
 pub struct Envelope {
     map: Map<String, Value>,
@@ -23,6 +22,11 @@ impl Envelope {
         }
     }
 
+    /// Envelope for a typed `zkprov_ffi_types` envelope.
+    pub fn typed<T: Serialize>(envelope: &zkprov_ffi_types::Envelope<T>) -> Self {
+        Self::from_value(serde_json::to_value(envelope).expect("failed to serialize JSON envelope"))
+    }
+
     pub fn into_string(self) -> String {
         serde_json::to_string(&Value::Object(self.map)).expect("failed to serialize JSON envelope")
     }
@@ -33,19 +37,11 @@ impl Envelope {
 }
 
 pub fn ok() -> Envelope {
-    Envelope::from_value(json!({
-        "ok": true,
-        "code": ZKP_OK,
-        "msg": "OK",
-    }))
+    Envelope::typed(&zkprov_ffi_types::Envelope::ok(Empty {}))
 }
 
 pub fn err(msg_code: ErrorCode, msg: impl Into<String>) -> Envelope {
-    Envelope::from_value(json!({
-        "ok": false,
-        "code": msg_code.code(),
-        "msg": msg.into(),
-    }))
+    Envelope::typed(&zkprov_ffi_types::Envelope::err(msg_code, msg, Empty {}))
 }
 
 pub fn with_field<T>(mut envelope: Envelope, key: impl Into<String>, value: T) -> Envelope
@@ -71,6 +67,7 @@ mod tests {
     fn ok_envelope_roundtrips() {
         let json = ok().into_string();
         let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema"], Value::from("zkp-ffi/1"));
         assert_eq!(value["ok"], Value::Bool(true));
         assert_eq!(value["code"], Value::from(0));
         assert_eq!(value["msg"], Value::from("OK"));
@@ -1,18 +1,23 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::{c_char, c_void, CStr};
+use std::path::Path;
 use std::ptr;
 use std::slice;
-use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 
-use anyhow::Error as AnyhowError;
 use serde::Serialize;
-use zkprov_backend_native::{native_prove, native_verify};
-use zkprov_corelib::backend::BackendInfo;
+use zkprov_bundles::{BlindingSource, BlindingTracker, PrivacyError};
+use zkprov_corelib::air::parser::parse_air_file;
+use zkprov_corelib::backend::{BackendInfo, CommitmentSupport, PublicInputs};
+use zkprov_corelib::buildinfo;
 use zkprov_corelib::config::Config;
-use zkprov_corelib::errors::{CapabilityError, RegistryError};
+use zkprov_corelib::errors::RegistryError;
 use zkprov_corelib::evm::digest::digest_D;
+use zkprov_corelib::evm::pubio::PubIoEncoding;
 use zkprov_corelib::profile::load_all_profiles;
-use zkprov_corelib::proof::ProofHeader;
+use zkprov_corelib::proof::{split_proof, ProofView};
 use zkprov_corelib::{registry, validate::validate_config};
 
 mod error;
@@ -23,6 +28,7 @@ pub use error::{
     ZKP_ERR_PROOF_CORRUPT, ZKP_ERR_VERIFY_FAIL, ZKP_OK,
 };
 pub use ffi_json::{err, ok, with_field, Envelope};
+use zkprov_ffi_types::{ProveMeta, VerifyMeta, VersionMeta, VersionReport};
 
 #[derive(Debug, Clone, Copy)]
 struct Allocation {
@@ -32,21 +38,107 @@ struct Allocation {
 
 type FfiResult<T> = Result<T, ErrorCode>;
 
-static ALLOCATIONS: OnceLock<Mutex<HashMap<usize, Allocation>>> = OnceLock::new();
-static INIT_RESULT: OnceLock<Result<(), ErrorCode>> = OnceLock::new();
+/// ABI revision, mirrored by `ZKP_ABI_VERSION` in `zkprov.h`. Symbols added
+/// after the first revision note the version that introduced them; bindings
+/// probe for them before use.
+pub const ZKP_ABI_VERSION: u32 = 2;
+
+/// Opaque arena handle handed to C callers.
+#[repr(C)]
+pub struct ZkpArena {
+    _private: [u8; 0],
+}
+
+/// Buffers allocated while an arena is open on its thread. They bypass
+/// `ALLOCATIONS` and are released together by `zkp_arena_free`; `None` once
+/// the arena is freed.
+type Arena = Arc<Mutex<Option<Vec<Vec<u8>>>>>;
 
-fn allocations() -> &'static Mutex<HashMap<usize, Allocation>> {
-    ALLOCATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+static NEXT_ARENA_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Every open arena by id. The buffers live here rather than with the thread,
+/// so they outlive a thread that exits without freeing its arena, and any
+/// thread may free an arena or a buffer in it.
+static OPEN_ARENAS: Mutex<BTreeMap<u64, Arena>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    /// Arenas opened on this thread; the last one still open receives new
+    /// allocations. Each has its own lock, so adopting a buffer does not
+    /// contend with other threads.
+    static ARENAS: RefCell<Vec<Arena>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Move `vec` into the innermost arena this thread opened and nobody has
+/// freed yet, if any, returning its pointer.
+fn arena_adopt(vec: Vec<u8>) -> Result<*mut u8, Vec<u8>> {
+    ARENAS.with(|arenas| {
+        let mut arenas = arenas.borrow_mut();
+        let mut vec = vec;
+        while let Some(arena) = arenas.last() {
+            if let Some(buffers) = arena.lock().ok().as_mut().and_then(|b| b.as_mut()) {
+                let ptr = vec.as_mut_ptr();
+                buffers.push(vec);
+                return Ok(ptr);
+            }
+            // Freed, possibly from another thread.
+            arenas.pop();
+        }
+        Err(vec)
+    })
+}
+
+/// Release `ptr` from whichever open arena owns it, if any.
+fn arena_release(ptr: *mut u8) {
+    let Ok(open) = OPEN_ARENAS.lock() else {
+        return;
+    };
+    for arena in open.values() {
+        let Ok(mut guard) = arena.lock() else {
+            continue;
+        };
+        if let Some(buffers) = guard.as_mut() {
+            if let Some(idx) = buffers.iter().position(|b| b.as_ptr() == ptr.cast_const()) {
+                buffers.swap_remove(idx);
+                return;
+            }
+        }
+    }
+}
+
+/// Number of independently locked shards in the allocation tracker. Threads
+/// allocating or freeing different buffers almost always hit different
+/// shards, so the tracker does not serialize multi-threaded hosts.
+const ALLOCATION_SHARDS: usize = 64;
+
+type AllocationShard = Mutex<HashMap<usize, Allocation>>;
+
+static ALLOCATIONS: OnceLock<[AllocationShard; ALLOCATION_SHARDS]> = OnceLock::new();
+/// Outcome of the one-time runtime setup; racing first calls block until the
+/// winner finishes, so no entry point ever sees a half-initialized registry.
+static INIT_RESULT: OnceLock<Result<(), ErrorCode>> = OnceLock::new();
+/// Serializes `zkp_blind_track`: opening a blinding log may compact it, and
+/// the check-then-append is not atomic, so two threads of this process must
+/// never work on a log at the same time.
+static BLIND_LOG: Mutex<()> = Mutex::new(());
+
+/// The shard tracking `ptr`: a Fibonacci hash of the address, so buffers
+/// handed out back to back (same alignment, nearby addresses) spread out.
+fn allocation_shard(ptr: usize) -> &'static AllocationShard {
+    let shards = ALLOCATIONS.get_or_init(|| std::array::from_fn(|_| Mutex::default()));
+    let mixed = (ptr as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    &shards[(mixed >> (64 - ALLOCATION_SHARDS.trailing_zeros())) as usize]
 }
 
 fn store_allocation(ptr: *mut u8, len: usize, cap: usize) -> FfiResult<()> {
-    let mut guard = allocations().lock().map_err(|_| ErrorCode::Internal)?;
+    let mut guard = allocation_shard(ptr as usize)
+        .lock()
+        .map_err(|_| ErrorCode::Internal)?;
     guard.insert(ptr as usize, Allocation { len, cap });
     Ok(())
 }
 
 fn take_allocation(ptr: *mut u8) -> Option<Allocation> {
-    allocations()
+    allocation_shard(ptr as usize)
         .lock()
         .ok()
         .and_then(|mut guard| guard.remove(&(ptr as usize)))
@@ -60,6 +152,8 @@ fn release_allocation(ptr: *mut u8) {
         unsafe {
             Vec::from_raw_parts(ptr, alloc.len, alloc.cap);
         }
+    } else {
+        arena_release(ptr);
     }
 }
 
@@ -67,18 +161,17 @@ fn alloc_bytes(len: usize) -> FfiResult<*mut u8> {
     if len == 0 {
         return Ok(ptr::null_mut());
     }
-    let mut buf = vec![0u8; len];
-    let ptr = buf.as_mut_ptr();
-    let cap = buf.capacity();
-    store_allocation(ptr, len, cap)?;
-    std::mem::forget(buf);
-    Ok(ptr)
+    leak_vec(vec![0u8; len])
 }
 
-fn leak_vec(mut vec: Vec<u8>) -> FfiResult<*mut u8> {
+fn leak_vec(vec: Vec<u8>) -> FfiResult<*mut u8> {
     if vec.is_empty() {
         return Ok(ptr::null_mut());
     }
+    let mut vec = match arena_adopt(vec) {
+        Ok(ptr) => return Ok(ptr),
+        Err(vec) => vec,
+    };
     let len = vec.len();
     let cap = vec.capacity();
     let ptr = vec.as_mut_ptr();
@@ -105,7 +198,7 @@ fn init_runtime() -> FfiResult<()> {
     let result = INIT_RESULT.get_or_init(|| {
         if let Err(err) = zkprov_backend_native::register_native_backend() {
             if !matches!(err, RegistryError::DuplicateBackend(_)) {
-                return Err(map_registry_error(&err));
+                return Err(ErrorCode::from(&err));
             }
         }
         registry::ensure_builtins_registered();
@@ -129,77 +222,99 @@ fn read_cstring(ptr: *const c_char) -> FfiResult<String> {
     }
 }
 
-fn ensure_output_ptr<T>(out: *mut *mut T) -> FfiResult<()> {
-    if out.is_null() {
+/// Reads a NUL-terminated UTF-16 string. Unpaired surrogates are rejected
+/// rather than replaced so the conversion stays lossless.
+fn read_wstring(ptr: *const u16) -> FfiResult<String> {
+    if ptr.is_null() {
         return Err(ErrorCode::InvalidArg);
     }
-    unsafe {
-        *out = ptr::null_mut();
-    }
-    Ok(())
-}
-
-fn ensure_output_scalar<T: Default>(out: *mut T) -> FfiResult<()> {
-    if out.is_null() {
+    let units = unsafe {
+        let mut len = 0usize;
+        while *ptr.add(len) != 0 {
+            len += 1;
+        }
+        slice::from_raw_parts(ptr, len)
+    };
+    if units.is_empty() {
         return Err(ErrorCode::InvalidArg);
     }
-    unsafe {
-        *out = T::default();
-    }
-    Ok(())
+    String::from_utf16(units).map_err(|_| ErrorCode::InvalidArg)
 }
 
-fn map_capability_error(err: &CapabilityError) -> ErrorCode {
-    match err {
-        CapabilityError::ProfileNotFound(_) => ErrorCode::Profile,
-        CapabilityError::Mismatch(_) => ErrorCode::Backend,
-        CapabilityError::FieldUnsupported { .. }
-        | CapabilityError::HashUnsupported { .. }
-        | CapabilityError::FriArityUnsupported { .. }
-        | CapabilityError::RecursionUnavailable { .. } => ErrorCode::Backend,
-    }
+/// String arguments shared by the narrow and wide prove/verify entry points.
+struct Request {
+    backend: String,
+    field: String,
+    hash: String,
+    fri_arity: u32,
+    profile: String,
+    air: String,
+    pub_inputs: String,
+    pubio_encoding: PubIoEncoding,
 }
 
-fn map_registry_error(err: &RegistryError) -> ErrorCode {
-    match err {
-        RegistryError::DuplicateBackend(_) => ErrorCode::Internal,
-        RegistryError::BackendNotFound(_) => ErrorCode::Backend,
+impl Request {
+    #[allow(clippy::too_many_arguments)]
+    fn read<P>(
+        read: fn(P) -> FfiResult<String>,
+        backend_id: P,
+        field: P,
+        hash_id: P,
+        fri_arity: u32,
+        profile_id: P,
+        air_path: P,
+        public_inputs_json: P,
+    ) -> FfiResult<Self> {
+        Ok(Self {
+            backend: read(backend_id)?,
+            field: read(field)?,
+            hash: read(hash_id)?,
+            fri_arity,
+            profile: read(profile_id)?,
+            air: read(air_path)?,
+            pub_inputs: read(public_inputs_json)?,
+            pubio_encoding: PubIoEncoding::Json,
+        })
     }
-}
 
-fn map_prove_error(err: &AnyhowError) -> ErrorCode {
-    if let Some(cap) = err.downcast_ref::<CapabilityError>() {
-        return map_capability_error(cap);
+    fn config(&self) -> Config {
+        Config::new(
+            self.backend.clone(),
+            self.field.clone(),
+            self.hash.clone(),
+            self.fri_arity,
+            false,
+            self.profile.clone(),
+        )
+        .with_pubio_encoding(self.pubio_encoding)
     }
-    if let Some(reg) = err.downcast_ref::<RegistryError>() {
-        return map_registry_error(reg);
+
+    fn with_pubio_encoding(mut self, encoding: *const c_char) -> FfiResult<Self> {
+        self.pubio_encoding = read_cstring(encoding)?
+            .parse()
+            .map_err(|_| ErrorCode::InvalidArg)?;
+        Ok(self)
     }
-    ErrorCode::Internal
 }
 
-fn is_proof_corrupt_message(msg: &str) -> bool {
-    const CORRUPT_MARKERS: &[&str] = &[
-        "too short",
-        "bad magic",
-        "unsupported proof version",
-        "body length mismatch",
-    ];
-    CORRUPT_MARKERS.iter().any(|needle| msg.contains(needle))
+fn ensure_output_ptr<T>(out: *mut *mut T) -> FfiResult<()> {
+    if out.is_null() {
+        return Err(ErrorCode::InvalidArg);
+    }
+    unsafe {
+        *out = ptr::null_mut();
+    }
+    Ok(())
 }
 
-fn map_verify_error(err: &AnyhowError) -> ErrorCode {
-    if let Some(cap) = err.downcast_ref::<CapabilityError>() {
-        return map_capability_error(cap);
-    }
-    if let Some(reg) = err.downcast_ref::<RegistryError>() {
-        return map_registry_error(reg);
+fn ensure_output_scalar<T: Default>(out: *mut T) -> FfiResult<()> {
+    if out.is_null() {
+        return Err(ErrorCode::InvalidArg);
     }
-    let msg = err.to_string();
-    if is_proof_corrupt_message(&msg) {
-        ErrorCode::ProofCorrupt
-    } else {
-        ErrorCode::VerifyFail
+    unsafe {
+        *out = T::default();
     }
+    Ok(())
 }
 
 fn to_i32(result: FfiResult<()>) -> i32 {
@@ -229,19 +344,15 @@ fn version_string() -> &'static str {
     VERSION.get_or_init(|| env!("CARGO_PKG_VERSION").to_owned())
 }
 
-fn git_commit_hash() -> Option<&'static str> {
-    option_env!("ZKD_GIT_SHA")
-        .or(option_env!("VERGEN_GIT_SHA"))
-        .or(option_env!("GIT_COMMIT_HASH"))
+fn version_meta() -> VersionMeta {
+    VersionMeta {
+        version: version_string().to_owned(),
+        commit: buildinfo::git_commit().map(str::to_owned),
+    }
 }
 
-fn with_version(envelope: Envelope) -> Envelope {
-    let envelope = with_field(envelope, "version", version_string());
-    if let Some(commit) = git_commit_hash() {
-        with_field(envelope, "commit", commit)
-    } else {
-        envelope
-    }
+fn typed_json<T: Serialize>(body: T) -> String {
+    zkprov_ffi_types::Envelope::ok(body).to_json()
 }
 
 #[no_mangle]
@@ -270,6 +381,99 @@ pub unsafe extern "C" fn zkp_list_backends(out_json: *mut *mut c_char) -> i32 {
     })())
 }
 
+/// Writes the whole registry (backends with capabilities, aliases) as JSON.
+///
+/// # Safety
+///
+/// - `out_json` must point to valid, writable memory where a pointer to a newly
+///   allocated, null-terminated string can be stored.
+/// - The caller is responsible for freeing the returned string with
+///   [`zkp_free_string`](crate::zkp_free_string).
+#[no_mangle]
+pub unsafe extern "C" fn zkp_registry_json(out_json: *mut *mut c_char) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_json)?;
+        init_runtime()?;
+        let json = serialize_json(&registry::snapshot())?;
+        let ptr = alloc_cstring(&json)?;
+        unsafe {
+            *out_json = ptr;
+        }
+        Ok(())
+    })())
+}
+
+/// Writes the ids of backends matching a JSON `BackendQuery` (e.g.
+/// `{"hash":"poseidon2","recursion":true}`) as a JSON array.
+///
+/// # Safety
+///
+/// - `query_json` must be a valid, null-terminated UTF-8 string.
+/// - `out_json` must point to valid, writable memory where a pointer to a newly
+///   allocated, null-terminated string can be stored.
+/// - The caller is responsible for freeing the returned string with
+///   [`zkp_free_string`](crate::zkp_free_string).
+#[no_mangle]
+pub unsafe extern "C" fn zkp_find_backends(
+    query_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_json)?;
+        let query: registry::BackendQuery =
+            serde_json::from_str(&read_cstring(query_json)?).map_err(|_| ErrorCode::InvalidArg)?;
+        init_runtime()?;
+        let ids = registry::find_backends(|caps| query.matches(caps));
+        let json = serialize_json(&ids)?;
+        let ptr = alloc_cstring(&json)?;
+        unsafe {
+            *out_json = ptr;
+        }
+        Ok(())
+    })())
+}
+
+/// `zkp_list_commitments` payload.
+#[derive(Serialize)]
+struct CommitmentsJson {
+    backend: &'static str,
+    commitments: Vec<CommitmentSupport>,
+}
+
+/// Writes the commitment kinds a backend supports, with their curves and
+/// hashes, as JSON (`{"backend":..,"commitments":[..]}`). Available from
+/// [`ZKP_ABI_VERSION`] 2.
+///
+/// # Safety
+///
+/// - `backend_id` must be a valid, null-terminated UTF-8 string.
+/// - `out_json` must point to valid, writable memory where a pointer to a newly
+///   allocated, null-terminated string can be stored.
+/// - The caller is responsible for freeing the returned string with
+///   [`zkp_free_string`](crate::zkp_free_string).
+#[no_mangle]
+pub unsafe extern "C" fn zkp_list_commitments(
+    backend_id: *const c_char,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_json)?;
+        let backend_id = read_cstring(backend_id)?;
+        init_runtime()?;
+        let backend = registry::resolve_backend_id(&backend_id).map_err(|_| ErrorCode::Backend)?;
+        let caps = registry::get_backend_capabilities(backend).map_err(|_| ErrorCode::Backend)?;
+        let json = serialize_json(&CommitmentsJson {
+            backend,
+            commitments: caps.commitments(),
+        })?;
+        let ptr = alloc_cstring(&json)?;
+        unsafe {
+            *out_json = ptr;
+        }
+        Ok(())
+    })())
+}
+
 /// # Safety
 ///
 /// - `out_json` must point to valid, writable memory where a pointer to a newly
@@ -301,8 +505,10 @@ pub unsafe extern "C" fn zkp_list_profiles(out_json: *mut *mut c_char) -> i32 {
 pub unsafe extern "C" fn zkp_version(out_json: *mut *mut c_char) -> i32 {
     to_i32((|| {
         ensure_output_ptr(out_json)?;
-        let envelope = with_version(ok());
-        let json = envelope.into_string();
+        let json = typed_json(VersionReport {
+            version: version_meta(),
+            build: buildinfo::build_info(),
+        });
         let ptr = alloc_cstring(&json)?;
         unsafe {
             *out_json = ptr;
@@ -340,48 +546,97 @@ pub unsafe extern "C" fn zkp_prove(
         ensure_output_ptr(out_json_meta)?;
         init_runtime()?;
 
-        let backend = read_cstring(backend_id)?;
-        let field = read_cstring(field)?;
-        let hash = read_cstring(hash_id)?;
-        let profile = read_cstring(profile_id)?;
-        let air = read_cstring(air_path)?;
-        let pub_inputs = read_cstring(public_inputs_json)?;
-
-        let config = Config::new(backend, field, hash, fri_arity, false, profile);
-        validate_config(&config).map_err(|e| map_capability_error(&e))?;
-
-        let proof = native_prove(&config, &pub_inputs, &air).map_err(|e| map_prove_error(&e))?;
-        let proof_len = proof.len();
-        let proof_len_u64 = u64::try_from(proof_len).map_err(|_| ErrorCode::Internal)?;
-        if proof_len < 40 {
-            return Err(ErrorCode::Internal);
-        }
-        let header = ProofHeader::decode(&proof[0..40]).map_err(|_| ErrorCode::Internal)?;
-        let body = &proof[40..];
-        let digest = digest_D(&header, body);
-        let digest_hex = hex_encode(&digest);
-
-        let meta_envelope = with_version(with_field(
-            with_field(ok(), "digest", digest_hex),
-            "proof_len",
-            proof_len_u64,
-        ));
-        let meta_json = meta_envelope.into_string();
-        let meta_ptr = alloc_cstring(&meta_json)?;
-
-        let proof_ptr = leak_vec(proof).inspect_err(|_| {
-            release_allocation(meta_ptr as *mut u8);
-        })?;
+        let request = Request::read(
+            read_cstring,
+            backend_id,
+            field,
+            hash_id,
+            fri_arity,
+            profile_id,
+            air_path,
+            public_inputs_json,
+        )?;
+        prove_request(request, out_proof, out_proof_len, out_json_meta)
+    })())
+}
 
-        unsafe {
-            *out_proof = proof_ptr;
-            *out_proof_len = proof_len_u64;
-            *out_json_meta = meta_ptr;
-        }
-        Ok(())
+/// # Safety
+///
+/// Same contract as [`zkp_prove`], except that the string arguments are
+/// NUL-terminated UTF-16 (`wchar_t` on Windows) instead of UTF-8.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn zkp_prove_w(
+    backend_id: *const u16,
+    field: *const u16,
+    hash_id: *const u16,
+    fri_arity: u32,
+    profile_id: *const u16,
+    air_path: *const u16,
+    public_inputs_json: *const u16,
+    out_proof: *mut *mut u8,
+    out_proof_len: *mut u64,
+    out_json_meta: *mut *mut c_char,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_proof)?;
+        ensure_output_scalar(out_proof_len)?;
+        ensure_output_ptr(out_json_meta)?;
+        init_runtime()?;
+
+        let request = Request::read(
+            read_wstring,
+            backend_id,
+            field,
+            hash_id,
+            fri_arity,
+            profile_id,
+            air_path,
+            public_inputs_json,
+        )?;
+        prove_request(request, out_proof, out_proof_len, out_json_meta)
     })())
 }
 
+fn prove_request(
+    request: Request,
+    out_proof: *mut *mut u8,
+    out_proof_len: *mut u64,
+    out_json_meta: *mut *mut c_char,
+) -> FfiResult<()> {
+    let config = request.config();
+    validate_config(&config).map_err(|e| ErrorCode::from(&e))?;
+
+    let ir = parse_air_file(Path::new(&request.air)).map_err(|_| ErrorCode::Internal)?;
+    let inputs = PublicInputs::from_json(request.pub_inputs).map_err(|e| ErrorCode::from(&e))?;
+    let proof = zkprov_corelib::prove(&config, &ir, &inputs)
+        .map_err(|e| ErrorCode::from(&e))?
+        .to_bytes();
+    let proof_len = proof.len();
+    let proof_len_u64 = u64::try_from(proof_len).map_err(|_| ErrorCode::Internal)?;
+    let (header, body) = split_proof(&proof).map_err(|_| ErrorCode::Internal)?;
+    let digest = digest_D(&header, body);
+    let digest_hex = hex_encode(&digest);
+
+    let meta_json = typed_json(ProveMeta {
+        digest: digest_hex,
+        proof_len: proof_len_u64,
+        version: version_meta(),
+    });
+    let meta_ptr = alloc_cstring(&meta_json)?;
+
+    let proof_ptr = leak_vec(proof).inspect_err(|_| {
+        release_allocation(meta_ptr as *mut u8);
+    })?;
+
+    unsafe {
+        *out_proof = proof_ptr;
+        *out_proof_len = proof_len_u64;
+        *out_json_meta = meta_ptr;
+    }
+    Ok(())
+}
+
 /// # Safety
 ///
 /// - All pointer arguments must be valid for reads of a null-terminated string
@@ -409,56 +664,247 @@ pub unsafe extern "C" fn zkp_verify(
         ensure_output_ptr(out_json_meta)?;
         init_runtime()?;
 
-        let backend = read_cstring(backend_id)?;
-        let field = read_cstring(field)?;
-        let hash = read_cstring(hash_id)?;
-        let profile = read_cstring(profile_id)?;
-        let air = read_cstring(air_path)?;
-        let pub_inputs = read_cstring(public_inputs_json)?;
+        let request = Request::read(
+            read_cstring,
+            backend_id,
+            field,
+            hash_id,
+            fri_arity,
+            profile_id,
+            air_path,
+            public_inputs_json,
+        )?;
+        verify_request(request, proof_ptr, proof_len, out_json_meta)
+    })())
+}
 
-        let proof_len_usize = usize::try_from(proof_len).map_err(|_| ErrorCode::InvalidArg)?;
-        if proof_len_usize == 0 {
-            return Err(ErrorCode::ProofCorrupt);
-        }
-        if proof_ptr.is_null() {
-            return Err(ErrorCode::InvalidArg);
-        }
-        let proof = unsafe { slice::from_raw_parts(proof_ptr, proof_len_usize) };
+/// # Safety
+///
+/// Same contract as [`zkp_verify`], except that the string arguments are
+/// NUL-terminated UTF-16 (`wchar_t` on Windows) instead of UTF-8.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn zkp_verify_w(
+    backend_id: *const u16,
+    field: *const u16,
+    hash_id: *const u16,
+    fri_arity: u32,
+    profile_id: *const u16,
+    air_path: *const u16,
+    public_inputs_json: *const u16,
+    proof_ptr: *const u8,
+    proof_len: u64,
+    out_json_meta: *mut *mut c_char,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_json_meta)?;
+        init_runtime()?;
 
-        if proof.len() < 40 {
-            return Err(ErrorCode::ProofCorrupt);
-        }
-        let header = ProofHeader::decode(&proof[0..40]).map_err(|_| ErrorCode::ProofCorrupt)?;
-        let body = &proof[40..];
-        if u64::try_from(body.len()).map_err(|_| ErrorCode::Internal)? != header.body_len {
-            return Err(ErrorCode::ProofCorrupt);
-        }
-        let digest = digest_D(&header, body);
-        let digest_hex = hex_encode(&digest);
+        let request = Request::read(
+            read_wstring,
+            backend_id,
+            field,
+            hash_id,
+            fri_arity,
+            profile_id,
+            air_path,
+            public_inputs_json,
+        )?;
+        verify_request(request, proof_ptr, proof_len, out_json_meta)
+    })())
+}
 
-        let config = Config::new(backend, field, hash, fri_arity, false, profile);
-        validate_config(&config).map_err(|e| map_capability_error(&e))?;
+/// # Safety
+///
+/// Same contract as [`zkp_prove`]; `pubio_encoding` must additionally be a
+/// valid null-terminated string, `"json"` or `"typed"`.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn zkp_prove_with_io_encoding(
+    backend_id: *const c_char,
+    field: *const c_char,
+    hash_id: *const c_char,
+    fri_arity: u32,
+    profile_id: *const c_char,
+    air_path: *const c_char,
+    public_inputs_json: *const c_char,
+    pubio_encoding: *const c_char,
+    out_proof: *mut *mut u8,
+    out_proof_len: *mut u64,
+    out_json_meta: *mut *mut c_char,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_proof)?;
+        ensure_output_scalar(out_proof_len)?;
+        ensure_output_ptr(out_json_meta)?;
+        init_runtime()?;
 
-        match native_verify(&config, &pub_inputs, &air, proof) {
-            Ok(true) => {}
-            Ok(false) => return Err(ErrorCode::VerifyFail),
-            Err(err) => return Err(map_verify_error(&err)),
-        }
+        let request = Request::read(
+            read_cstring,
+            backend_id,
+            field,
+            hash_id,
+            fri_arity,
+            profile_id,
+            air_path,
+            public_inputs_json,
+        )?
+        .with_pubio_encoding(pubio_encoding)?;
+        prove_request(request, out_proof, out_proof_len, out_json_meta)
+    })())
+}
+
+/// # Safety
+///
+/// Same contract as [`zkp_verify`]; `pubio_encoding` must additionally be a
+/// valid null-terminated string, `"json"` or `"typed"`.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn zkp_verify_with_io_encoding(
+    backend_id: *const c_char,
+    field: *const c_char,
+    hash_id: *const c_char,
+    fri_arity: u32,
+    profile_id: *const c_char,
+    air_path: *const c_char,
+    public_inputs_json: *const c_char,
+    pubio_encoding: *const c_char,
+    proof_ptr: *const u8,
+    proof_len: u64,
+    out_json_meta: *mut *mut c_char,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_json_meta)?;
+        init_runtime()?;
+
+        let request = Request::read(
+            read_cstring,
+            backend_id,
+            field,
+            hash_id,
+            fri_arity,
+            profile_id,
+            air_path,
+            public_inputs_json,
+        )?
+        .with_pubio_encoding(pubio_encoding)?;
+        verify_request(request, proof_ptr, proof_len, out_json_meta)
+    })())
+}
 
-        let meta_envelope = with_version(with_field(
-            with_field(ok(), "verified", true),
-            "digest",
-            digest_hex,
-        ));
-        let meta_json = meta_envelope.into_string();
-        let meta_ptr = alloc_cstring(&meta_json)?;
+fn verify_request(
+    request: Request,
+    proof_ptr: *const u8,
+    proof_len: u64,
+    out_json_meta: *mut *mut c_char,
+) -> FfiResult<()> {
+    let proof_len_usize = usize::try_from(proof_len).map_err(|_| ErrorCode::InvalidArg)?;
+    if proof_len_usize == 0 {
+        return Err(ErrorCode::ProofCorrupt);
+    }
+    if proof_ptr.is_null() {
+        return Err(ErrorCode::InvalidArg);
+    }
+    // Zero-copy: the caller's buffer is only borrowed, never copied, for the
+    // whole verification (header decode, digest and backend verify).
+    let proof = unsafe { slice::from_raw_parts(proof_ptr, proof_len_usize) };
+    let proof = ProofView::from_bytes(proof).map_err(|_| ErrorCode::ProofCorrupt)?;
+    let digest = digest_D(&proof.header, proof.body);
+    let digest_hex = hex_encode(&digest);
+
+    let config = request.config();
+    validate_config(&config).map_err(|e| ErrorCode::from(&e))?;
+
+    let ir = parse_air_file(Path::new(&request.air)).map_err(|_| ErrorCode::VerifyFail)?;
+    let inputs = PublicInputs::from_json(request.pub_inputs).map_err(|_| ErrorCode::InvalidArg)?;
+    zkprov_corelib::verify(&config, &ir, &inputs, &proof).map_err(|e| ErrorCode::from(&e))?;
+
+    let meta_json = typed_json(VerifyMeta {
+        verified: true,
+        digest: digest_hex,
+        version: version_meta(),
+    });
+    let meta_ptr = alloc_cstring(&meta_json)?;
+    unsafe {
+        *out_json_meta = meta_ptr;
+    }
+    Ok(())
+}
+
+/// # Safety
+///
+/// - When `master_len` is non-zero, `master_ptr` must reference at least
+///   `master_len` readable bytes; `label` must then be a valid null-terminated
+///   string. Both are ignored when `master_ptr` is null (OS CSPRNG).
+/// - `out_blind` and `out_blind_len` must be valid, writable pointers. The
+///   caller releases the blind with [`zkp_free`](crate::zkp_free).
+#[no_mangle]
+pub unsafe extern "C" fn zkp_blind_new(
+    label: *const c_char,
+    master_ptr: *const u8,
+    master_len: u64,
+    out_blind: *mut *mut u8,
+    out_blind_len: *mut u64,
+) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_blind)?;
+        ensure_output_scalar(out_blind_len)?;
+
+        let (source, label) = if master_ptr.is_null() {
+            (BlindingSource::os(), String::new())
+        } else {
+            let len = usize::try_from(master_len).map_err(|_| ErrorCode::InvalidArg)?;
+            let master = unsafe { slice::from_raw_parts(master_ptr, len) };
+            let source = BlindingSource::from_master(master).map_err(|e| map_privacy_error(&e))?;
+            (source, read_cstring(label)?)
+        };
+        let blind = source.blind(&label).map_err(|e| map_privacy_error(&e))?;
+        let blind_ptr = leak_vec(blind.to_vec())?;
         unsafe {
-            *out_json_meta = meta_ptr;
+            *out_blind = blind_ptr;
+            *out_blind_len = blind.len() as u64;
         }
         Ok(())
     })())
 }
 
+/// Record `blind` in the persistent blinding log at `log_path` (created if
+/// missing). Returns `ZKP_ERR_INVALID_ARG` when the blind was already
+/// recorded, by this or an earlier session, and `ZKP_ERR_INTERNAL` when the
+/// log cannot be read or written.
+///
+/// # Safety
+///
+/// - `log_path` must be a valid null-terminated string.
+/// - `blind_ptr` must reference at least `blind_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zkp_blind_track(
+    log_path: *const c_char,
+    blind_ptr: *const u8,
+    blind_len: u64,
+) -> i32 {
+    to_i32((|| {
+        let path = read_cstring(log_path)?;
+        let len = usize::try_from(blind_len).map_err(|_| ErrorCode::InvalidArg)?;
+        if blind_ptr.is_null() || len == 0 {
+            return Err(ErrorCode::InvalidArg);
+        }
+        let blind = unsafe { slice::from_raw_parts(blind_ptr, len) };
+        let _log = BLIND_LOG.lock().unwrap_or_else(|e| e.into_inner());
+        let mut tracker = BlindingTracker::persistent(&path).map_err(|e| map_privacy_error(&e))?;
+        tracker
+            .note_and_check(blind, true)
+            .map_err(|e| map_privacy_error(&e))
+    })())
+}
+
+fn map_privacy_error(err: &PrivacyError) -> ErrorCode {
+    match err {
+        PrivacyError::WeakMasterSecret | PrivacyError::BlindingReuse => ErrorCode::InvalidArg,
+        _ => ErrorCode::Internal,
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn zkp_alloc(nbytes: u64) -> *mut c_void {
     match usize::try_from(nbytes) {
@@ -478,6 +924,58 @@ pub extern "C" fn zkp_free(ptr: *mut c_void) {
     release_allocation(ptr as *mut u8);
 }
 
+/// Open an arena on the calling thread. Until it is freed, every buffer the
+/// runtime hands out on this thread (proofs, JSON strings, `zkp_alloc`) is
+/// owned by the arena and released in one step by `zkp_arena_free`.
+/// `zkp_free` still releases a single arena buffer early.
+///
+/// # Safety
+///
+/// - `out_arena` must point to valid, writable memory where the arena handle
+///   can be stored.
+/// - The handle must be released with [`zkp_arena_free`], from any thread;
+///   the arena and its buffers outlive the opening thread until then.
+#[no_mangle]
+pub unsafe extern "C" fn zkp_arena_new(out_arena: *mut *mut ZkpArena) -> i32 {
+    to_i32((|| {
+        ensure_output_ptr(out_arena)?;
+        let id = NEXT_ARENA_ID.fetch_add(1, Ordering::Relaxed);
+        let arena: Arena = Arc::new(Mutex::new(Some(Vec::new())));
+        OPEN_ARENAS
+            .lock()
+            .map_err(|_| ErrorCode::Internal)?
+            .insert(id, arena.clone());
+        ARENAS.with(|arenas| arenas.borrow_mut().push(arena));
+        unsafe {
+            *out_arena = id as usize as *mut ZkpArena;
+        }
+        Ok(())
+    })())
+}
+
+/// Release every buffer owned by `arena` and close it. Any thread may free
+/// any arena; the opening thread's later allocations then go to its next
+/// open arena, or are tracked individually. Unknown or already-freed handles
+/// return `ZKP_ERR_INVALID_ARG`.
+#[no_mangle]
+pub extern "C" fn zkp_arena_free(arena: *mut ZkpArena) -> i32 {
+    let id = arena as usize as u64;
+    let Ok(mut open) = OPEN_ARENAS.lock() else {
+        return ZKP_ERR_INTERNAL;
+    };
+    match open.remove(&id) {
+        Some(arena) => {
+            // Dropping the buffers releases them; the opening thread sees
+            // `None` and stops routing allocations here.
+            if let Ok(mut buffers) = arena.lock() {
+                buffers.take();
+            }
+            ZKP_OK
+        }
+        None => ZKP_ERR_INVALID_ARG,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -485,6 +983,7 @@ mod tests {
     use std::ffi::{CStr, CString};
     use std::path::PathBuf;
     use std::ptr;
+    use zkprov_corelib::proof::HEADER_LEN;
 
     fn parse_cstring(cstr: CString) -> Value {
         let json = cstr
@@ -544,6 +1043,12 @@ mod tests {
         let value: Value = serde_json::from_str(version_json).unwrap();
         assert!(value["ok"].as_bool().unwrap());
         assert_eq!(value["version"], Value::from(env!("CARGO_PKG_VERSION")));
+        let build: buildinfo::BuildInfo = serde_json::from_value(value["build"].clone()).unwrap();
+        assert_eq!(build, buildinfo::build_info());
+        let typed: zkprov_ffi_types::Envelope<VersionReport> =
+            serde_json::from_str(version_json).unwrap();
+        assert_eq!(typed.schema, zkprov_ffi_types::SCHEMA);
+        assert!(!build.target.is_empty() && build.rustc.starts_with("rustc"));
         zkp_free(version_ptr.cast());
     }
 
@@ -604,7 +1109,7 @@ mod tests {
             )
         };
         assert_eq!(status, ZKP_OK);
-        assert!(proof_len >= 40);
+        assert!(proof_len >= HEADER_LEN as u64);
         assert!(!proof_ptr.is_null());
         assert!(!prove_meta_ptr.is_null());
 
@@ -619,6 +1124,9 @@ mod tests {
             prove_meta_json["version"],
             Value::from(env!("CARGO_PKG_VERSION"))
         );
+        let typed: zkprov_ffi_types::Envelope<ProveMeta> =
+            serde_json::from_str(prove_meta).unwrap();
+        assert_eq!(typed.schema, zkprov_ffi_types::SCHEMA);
 
         let mut verify_meta_ptr: *mut c_char = ptr::null_mut();
         let status = unsafe {
@@ -648,12 +1156,534 @@ mod tests {
             verify_meta_json["version"],
             Value::from(env!("CARGO_PKG_VERSION"))
         );
+        let typed: zkprov_ffi_types::Envelope<VerifyMeta> =
+            serde_json::from_str(verify_meta).unwrap();
+        assert_eq!(typed.schema, zkprov_ffi_types::SCHEMA);
+        assert!(typed.body.verified);
+
+        // The header binds the FRI arity, so the same proof fails under 4.
+        let mut rebound_meta_ptr: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            zkp_verify(
+                backend.as_ptr(),
+                field.as_ptr(),
+                hash.as_ptr(),
+                4,
+                profile.as_ptr(),
+                air.as_ptr(),
+                inputs.as_ptr(),
+                proof_ptr as *const u8,
+                proof_len,
+                &mut rebound_meta_ptr,
+            )
+        };
+        assert_eq!(status, ZKP_ERR_VERIFY_FAIL);
+        assert!(rebound_meta_ptr.is_null());
 
         zkp_free(prove_meta_ptr.cast());
         zkp_free(verify_meta_ptr.cast());
         zkp_free(proof_ptr.cast());
     }
 
+    /// Records the size of every allocation made by a thread inside
+    /// [`track_allocations`]; other test threads are not counted.
+    struct TrackingAlloc;
+
+    thread_local! {
+        /// `(total bytes, largest single allocation)` while tracking.
+        static TRACKED: std::cell::Cell<Option<(usize, usize)>> =
+            const { std::cell::Cell::new(None) };
+    }
+
+    fn record_allocation(size: usize) {
+        let _ = TRACKED.try_with(|t| {
+            if let Some((total, largest)) = t.get() {
+                t.set(Some((total + size, largest.max(size))));
+            }
+        });
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for TrackingAlloc {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            std::alloc::System.alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+        unsafe fn realloc(
+            &self,
+            ptr: *mut u8,
+            layout: std::alloc::Layout,
+            new_size: usize,
+        ) -> *mut u8 {
+            record_allocation(new_size);
+            std::alloc::System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOC: TrackingAlloc = TrackingAlloc;
+
+    /// Run `f`, returning the total and largest allocation it made.
+    fn track_allocations<R>(f: impl FnOnce() -> R) -> (R, usize, usize) {
+        TRACKED.with(|t| t.set(Some((0, 0))));
+        let out = f();
+        let (total, largest) = TRACKED.with(|t| t.take()).unwrap();
+        (out, total, largest)
+    }
+
+    #[test]
+    fn verify_borrows_proof_buffer_without_copying() {
+        assert_eq!(zkp_init(), ZKP_OK);
+        let backend = CString::new("native@0.0").unwrap();
+        let field = CString::new("Prime254").unwrap();
+        let hash = CString::new("blake3").unwrap();
+        let profile = CString::new("balanced").unwrap();
+        let air = toy_air_path();
+        let inputs = CString::new("{\"a\":1}").unwrap();
+
+        let mut proof_ptr: *mut u8 = ptr::null_mut();
+        let mut proof_len: u64 = 0;
+        let mut meta_ptr: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            zkp_prove(
+                backend.as_ptr(),
+                field.as_ptr(),
+                hash.as_ptr(),
+                2,
+                profile.as_ptr(),
+                air.as_ptr(),
+                inputs.as_ptr(),
+                &mut proof_ptr,
+                &mut proof_len,
+                &mut meta_ptr,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        let mut proof = unsafe { slice::from_raw_parts(proof_ptr, proof_len as usize) }.to_vec();
+        zkp_free(proof_ptr.cast());
+        zkp_free(meta_ptr.cast());
+
+        let verify = |proof: &[u8]| {
+            let mut meta_ptr: *mut c_char = ptr::null_mut();
+            let (status, total, largest) = track_allocations(|| unsafe {
+                zkp_verify(
+                    backend.as_ptr(),
+                    field.as_ptr(),
+                    hash.as_ptr(),
+                    2,
+                    profile.as_ptr(),
+                    air.as_ptr(),
+                    inputs.as_ptr(),
+                    proof.as_ptr(),
+                    proof.len() as u64,
+                    &mut meta_ptr,
+                )
+            });
+            zkp_free(meta_ptr.cast());
+            (status, total, largest)
+        };
+        let (status, small_total, _) = verify(&proof);
+        assert_eq!(status, ZKP_OK);
+
+        // Grow the body to 4 MiB (header kept consistent): the backend rejects
+        // it, but only after decoding, digesting and dispatching the buffer,
+        // none of which may allocate in proportion to its size.
+        let body_len = 4usize << 20;
+        proof.resize(HEADER_LEN + body_len, 0xab);
+        proof[40..48].copy_from_slice(&(body_len as u64).to_le_bytes());
+        let (status, total, largest) = verify(&proof);
+        assert_eq!(status, ZKP_ERR_VERIFY_FAIL);
+        assert!(largest < 64 * 1024, "largest allocation {largest} bytes");
+        assert!(
+            total < small_total + 64 * 1024,
+            "verify allocated {total} bytes for a {body_len}-byte body ({small_total} for the small proof)"
+        );
+    }
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    #[test]
+    fn wide_entry_points_match_utf8_semantics() {
+        assert_eq!(zkp_init(), ZKP_OK);
+        let dir = tempfile::tempdir().unwrap();
+        let air_path = dir.path().join("證明-é.air");
+        std::fs::copy(workspace_root().join("examples/air/toy.air"), &air_path).unwrap();
+        let air_str = air_path.to_str().unwrap();
+        let inputs_str = "{\"note\":\"héllo 🌍\"}";
+
+        let (backend, field, hash, profile) = ("native@0.0", "Prime254", "blake3", "balanced");
+        let (w_backend, w_field, w_hash, w_profile) =
+            (wide(backend), wide(field), wide(hash), wide(profile));
+        let (w_air, w_inputs) = (wide(air_str), wide(inputs_str));
+
+        let mut wide_proof: *mut u8 = ptr::null_mut();
+        let mut wide_len: u64 = 0;
+        let mut meta: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            zkp_prove_w(
+                w_backend.as_ptr(),
+                w_field.as_ptr(),
+                w_hash.as_ptr(),
+                2,
+                w_profile.as_ptr(),
+                w_air.as_ptr(),
+                w_inputs.as_ptr(),
+                &mut wide_proof,
+                &mut wide_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        zkp_free(meta.cast());
+
+        let c = |s: &str| CString::new(s).unwrap();
+        let (c_backend, c_field, c_hash, c_profile) = (c(backend), c(field), c(hash), c(profile));
+        let (c_air, c_inputs) = (c(air_str), c(inputs_str));
+        let mut narrow_proof: *mut u8 = ptr::null_mut();
+        let mut narrow_len: u64 = 0;
+        let status = unsafe {
+            zkp_prove(
+                c_backend.as_ptr(),
+                c_field.as_ptr(),
+                c_hash.as_ptr(),
+                2,
+                c_profile.as_ptr(),
+                c_air.as_ptr(),
+                c_inputs.as_ptr(),
+                &mut narrow_proof,
+                &mut narrow_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        zkp_free(meta.cast());
+        let (wide_bytes, narrow_bytes) = unsafe {
+            (
+                slice::from_raw_parts(wide_proof, wide_len as usize),
+                slice::from_raw_parts(narrow_proof, narrow_len as usize),
+            )
+        };
+        assert_eq!(wide_bytes, narrow_bytes);
+
+        // A proof made through the UTF-8 API verifies through the UTF-16 one.
+        let status = unsafe {
+            zkp_verify_w(
+                w_backend.as_ptr(),
+                w_field.as_ptr(),
+                w_hash.as_ptr(),
+                2,
+                w_profile.as_ptr(),
+                w_air.as_ptr(),
+                w_inputs.as_ptr(),
+                narrow_proof,
+                narrow_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        zkp_free(meta.cast());
+
+        // Different inputs must fail the same way in both encodings.
+        let (other_c, other_w) = (c("{}"), wide("{}"));
+        let narrow_status = unsafe {
+            zkp_verify(
+                c_backend.as_ptr(),
+                c_field.as_ptr(),
+                c_hash.as_ptr(),
+                2,
+                c_profile.as_ptr(),
+                c_air.as_ptr(),
+                other_c.as_ptr(),
+                wide_proof,
+                wide_len,
+                &mut meta,
+            )
+        };
+        let wide_status = unsafe {
+            zkp_verify_w(
+                w_backend.as_ptr(),
+                w_field.as_ptr(),
+                w_hash.as_ptr(),
+                2,
+                w_profile.as_ptr(),
+                w_air.as_ptr(),
+                other_w.as_ptr(),
+                wide_proof,
+                wide_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(narrow_status, ZKP_ERR_VERIFY_FAIL);
+        assert_eq!(wide_status, narrow_status);
+        assert!(meta.is_null());
+
+        zkp_free(wide_proof.cast());
+        zkp_free(narrow_proof.cast());
+    }
+
+    #[test]
+    fn wide_strings_reject_lone_surrogates_and_empty_input() {
+        assert_eq!(read_wstring(wide("héllo 🌍").as_ptr()).unwrap(), "héllo 🌍");
+        assert_eq!(
+            read_wstring([0x0061, 0xD800, 0x0062, 0].as_ptr()),
+            Err(ErrorCode::InvalidArg)
+        );
+        assert_eq!(read_wstring([0u16].as_ptr()), Err(ErrorCode::InvalidArg));
+        assert_eq!(read_wstring(ptr::null()), Err(ErrorCode::InvalidArg));
+    }
+
+    #[test]
+    fn blind_new_draws_random_or_derived_blinds() {
+        let draw = |label: Option<&CString>, master: &[u8]| {
+            let mut blind: *mut u8 = ptr::null_mut();
+            let mut len: u64 = 0;
+            let status = unsafe {
+                zkp_blind_new(
+                    label.map_or(ptr::null(), |l| l.as_ptr()),
+                    if master.is_empty() {
+                        ptr::null()
+                    } else {
+                        master.as_ptr()
+                    },
+                    master.len() as u64,
+                    &mut blind,
+                    &mut len,
+                )
+            };
+            if status != ZKP_OK {
+                return Err(status);
+            }
+            let bytes = unsafe { slice::from_raw_parts(blind, len as usize) }.to_vec();
+            zkp_free(blind.cast());
+            Ok(bytes)
+        };
+
+        let a = draw(None, &[]).unwrap();
+        let b = draw(None, &[]).unwrap();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, b);
+
+        let master = [7u8; 32];
+        let label = CString::new("orders/1").unwrap();
+        let other = CString::new("orders/2").unwrap();
+        let d1 = draw(Some(&label), &master).unwrap();
+        assert_eq!(d1, draw(Some(&label), &master).unwrap());
+        assert_ne!(d1, draw(Some(&other), &master).unwrap());
+        assert_eq!(draw(Some(&label), &master[..16]), Err(ZKP_ERR_INVALID_ARG));
+    }
+
+    #[test]
+    fn blind_track_rejects_reuse_across_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = CString::new(dir.path().join("blinds.log").to_str().unwrap()).unwrap();
+        let track = |blind: &[u8]| unsafe {
+            zkp_blind_track(log.as_ptr(), blind.as_ptr(), blind.len() as u64)
+        };
+        assert_eq!(track(&[1u8; 32]), ZKP_OK);
+        assert_eq!(track(&[2u8; 32]), ZKP_OK);
+        assert_eq!(track(&[1u8; 32]), ZKP_ERR_INVALID_ARG);
+        assert_eq!(track(&[]), ZKP_ERR_INVALID_ARG);
+
+        let foreign = dir.path().join("notes.txt");
+        std::fs::write(&foreign, b"not a log").unwrap();
+        let foreign = CString::new(foreign.to_str().unwrap()).unwrap();
+        let status = unsafe { zkp_blind_track(foreign.as_ptr(), [3u8; 32].as_ptr(), 32) };
+        assert_eq!(status, ZKP_ERR_INTERNAL);
+    }
+
+    #[test]
+    fn registry_introspection_exports_snapshot_and_queries() {
+        let take = |ptr: *mut c_char| {
+            let value: Value =
+                serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+            zkp_free(ptr.cast());
+            value
+        };
+
+        let mut out: *mut c_char = ptr::null_mut();
+        assert_eq!(unsafe { zkp_registry_json(&mut out) }, ZKP_OK);
+        let snapshot = take(out);
+        let native = &snapshot["backends"][0];
+        assert_eq!(native["id"], "native@0.0");
+        assert_eq!(native["placeholder"], false);
+        assert_eq!(native["capabilities"]["recursion"], "none");
+        assert_eq!(snapshot["aliases"]["native@latest"], "native@0.0");
+
+        let find = |query: &str| {
+            let query = CString::new(query).unwrap();
+            let mut out: *mut c_char = ptr::null_mut();
+            match unsafe { zkp_find_backends(query.as_ptr(), &mut out) } {
+                ZKP_OK => Ok(take(out)),
+                status => Err(status),
+            }
+        };
+        assert_eq!(
+            find(r#"{"hash":"poseidon2","fri_arity":4}"#),
+            Ok(serde_json::json!(["native@0.0"]))
+        );
+        assert_eq!(find(r#"{"recursion":true}"#), Ok(serde_json::json!([])));
+        assert_eq!(find(r#"{"hashes":"blake3"}"#), Err(ZKP_ERR_INVALID_ARG));
+        assert_eq!(find("not json"), Err(ZKP_ERR_INVALID_ARG));
+    }
+
+    #[test]
+    fn list_commitments_reports_kinds_per_backend() {
+        let list = |backend: &str| {
+            let backend = CString::new(backend).unwrap();
+            let mut out: *mut c_char = ptr::null_mut();
+            let status = unsafe { zkp_list_commitments(backend.as_ptr(), &mut out) };
+            if status != ZKP_OK {
+                assert!(out.is_null());
+                return Err(status);
+            }
+            let value: Value =
+                serde_json::from_str(unsafe { CStr::from_ptr(out) }.to_str().unwrap()).unwrap();
+            zkp_free(out.cast());
+            Ok(value)
+        };
+
+        let native = list("native@latest").unwrap();
+        assert_eq!(native["backend"], "native@0.0");
+        let kinds = native["commitments"].as_array().unwrap();
+        let names: Vec<&str> = kinds.iter().map(|k| k["kind"].as_str().unwrap()).collect();
+        assert_eq!(names, ["pedersen", "poseidon_commit", "keccak_commit"]);
+        assert_eq!(kinds[0]["curves"], serde_json::json!(["placeholder"]));
+        assert_eq!(kinds[2]["hashes"], serde_json::json!(["keccak256"]));
+
+        assert_eq!(list("nope@1.0"), Err(ZKP_ERR_BACKEND));
+        assert_eq!(
+            unsafe { zkp_list_commitments(ptr::null(), &mut ptr::null_mut()) },
+            ZKP_ERR_INVALID_ARG
+        );
+    }
+
+    #[test]
+    fn io_encoding_switch_selects_pubio_binding() {
+        assert_eq!(zkp_init(), ZKP_OK);
+        let path = workspace_root()
+            .join("examples")
+            .join("air")
+            .join("commit_demo.air");
+        let c = |s: &str| CString::new(s).unwrap();
+        let (backend, field, hash, profile) =
+            (c("native@0.0"), c("Prime254"), c("blake3"), c("balanced"));
+        let air = c(path.to_str().unwrap());
+        let inputs = c(r#"{"x":1,"y":2,"acc":"0xbeef","digest":7}"#);
+        let reformatted = c(r#"{"digest":7,"acc":"beef","y":2,"x":1}"#);
+        let (typed, json, bogus) = (c("typed"), c("json"), c("cbor"));
+
+        let mut proof_ptr: *mut u8 = ptr::null_mut();
+        let mut proof_len: u64 = 0;
+        let mut meta: *mut c_char = ptr::null_mut();
+        let status = unsafe {
+            zkp_prove_with_io_encoding(
+                backend.as_ptr(),
+                field.as_ptr(),
+                hash.as_ptr(),
+                2,
+                profile.as_ptr(),
+                air.as_ptr(),
+                inputs.as_ptr(),
+                typed.as_ptr(),
+                &mut proof_ptr,
+                &mut proof_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        zkp_free(meta.cast());
+
+        let verify = |inputs: &CString, encoding: &CString| {
+            let mut meta: *mut c_char = ptr::null_mut();
+            let status = unsafe {
+                zkp_verify_with_io_encoding(
+                    backend.as_ptr(),
+                    field.as_ptr(),
+                    hash.as_ptr(),
+                    2,
+                    profile.as_ptr(),
+                    air.as_ptr(),
+                    inputs.as_ptr(),
+                    encoding.as_ptr(),
+                    proof_ptr,
+                    proof_len,
+                    &mut meta,
+                )
+            };
+            zkp_free(meta.cast());
+            status
+        };
+        assert_eq!(verify(&reformatted, &typed), ZKP_OK);
+        assert_eq!(verify(&inputs, &json), ZKP_ERR_VERIFY_FAIL);
+        assert_eq!(verify(&inputs, &bogus), ZKP_ERR_INVALID_ARG);
+        zkp_free(proof_ptr.cast());
+    }
+
+    fn is_tracked(ptr: *mut c_void) -> bool {
+        let addr = ptr as usize;
+        allocation_shard(addr).lock().unwrap().contains_key(&addr)
+    }
+
+    #[test]
+    fn concurrent_prove_and_free_is_consistent() {
+        const THREADS: usize = 32;
+        const ROUNDS: usize = 8;
+        let digests: Vec<Vec<String>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..THREADS)
+                .map(|t| {
+                    scope.spawn(move || {
+                        let backend = CString::new("native@0.0").unwrap();
+                        let field = CString::new("Prime254").unwrap();
+                        let hash = CString::new("blake3").unwrap();
+                        let profile = CString::new("balanced").unwrap();
+                        let air = toy_air_path();
+                        let inputs = CString::new("{\"a\":1,\"b\":[2,3]}").unwrap();
+                        let mut digests = Vec::with_capacity(ROUNDS);
+                        for round in 0..ROUNDS {
+                            let scratch = zkp_alloc((16 + t + round) as u64);
+                            let mut proof_ptr: *mut u8 = ptr::null_mut();
+                            let mut proof_len: u64 = 0;
+                            let mut meta_ptr: *mut c_char = ptr::null_mut();
+                            let status = unsafe {
+                                zkp_prove(
+                                    backend.as_ptr(),
+                                    field.as_ptr(),
+                                    hash.as_ptr(),
+                                    2,
+                                    profile.as_ptr(),
+                                    air.as_ptr(),
+                                    inputs.as_ptr(),
+                                    &mut proof_ptr,
+                                    &mut proof_len,
+                                    &mut meta_ptr,
+                                )
+                            };
+                            assert_eq!(status, ZKP_OK);
+                            let meta: Value = serde_json::from_str(
+                                unsafe { CStr::from_ptr(meta_ptr) }.to_str().unwrap(),
+                            )
+                            .unwrap();
+                            digests.push(meta["digest"].as_str().unwrap().to_string());
+                            // Every live buffer stays tracked until its own free.
+                            for p in [scratch, proof_ptr.cast(), meta_ptr.cast()] {
+                                assert!(is_tracked(p));
+                                zkp_free(p);
+                            }
+                        }
+                        digests
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+        let first = &digests[0][0];
+        assert!(digests.iter().flatten().all(|d| d == first));
+    }
+
     #[test]
     fn zkp_free_is_idempotent() {
         let ptr = zkp_alloc(64);
@@ -661,4 +1691,96 @@ mod tests {
         zkp_free(ptr);
         zkp_free(ptr);
     }
+
+    /// Buffers owned by the open arena `arena`.
+    fn arena_buffers(arena: *mut ZkpArena) -> Option<usize> {
+        let open = OPEN_ARENAS.lock().unwrap();
+        let arena = open.get(&(arena as usize as u64))?;
+        let len = arena.lock().unwrap().as_ref().map(Vec::len);
+        len
+    }
+
+    #[test]
+    fn arena_owns_allocations_until_freed() {
+        let mut arena: *mut ZkpArena = ptr::null_mut();
+        assert_eq!(unsafe { zkp_arena_new(&mut arena) }, ZKP_OK);
+        assert!(!arena.is_null());
+
+        let buf = zkp_alloc(32);
+        let mut version_ptr: *mut c_char = ptr::null_mut();
+        assert_eq!(unsafe { zkp_version(&mut version_ptr) }, ZKP_OK);
+        assert!(!buf.is_null() && !version_ptr.is_null());
+        // Arena buffers bypass the global tracker.
+        assert!(!is_tracked(buf));
+        assert!(!is_tracked(version_ptr.cast()));
+        assert_eq!(arena_buffers(arena), Some(2));
+        // zkp_free releases a single arena buffer early.
+        zkp_free(version_ptr.cast());
+        assert_eq!(arena_buffers(arena), Some(1));
+
+        assert_eq!(zkp_arena_free(arena), ZKP_OK);
+        assert_eq!(zkp_arena_free(arena), ZKP_ERR_INVALID_ARG);
+        assert_eq!(arena_buffers(arena), None);
+
+        // With no arena active, allocations are tracked individually again.
+        let tracked = zkp_alloc(8);
+        assert!(take_allocation(tracked.cast()).is_some());
+    }
+
+    #[test]
+    fn nested_arenas_route_to_innermost() {
+        let mut outer: *mut ZkpArena = ptr::null_mut();
+        let mut inner: *mut ZkpArena = ptr::null_mut();
+        assert_eq!(unsafe { zkp_arena_new(&mut outer) }, ZKP_OK);
+        let _a = zkp_alloc(16);
+        assert_eq!(unsafe { zkp_arena_new(&mut inner) }, ZKP_OK);
+        let _b = zkp_alloc(16);
+        assert_eq!(arena_buffers(outer), Some(1));
+        assert_eq!(arena_buffers(inner), Some(1));
+        // Freeing out of order is allowed.
+        assert_eq!(zkp_arena_free(outer), ZKP_OK);
+        assert_eq!(zkp_arena_free(inner), ZKP_OK);
+        assert_eq!(
+            unsafe { zkp_arena_new(ptr::null_mut()) },
+            ZKP_ERR_INVALID_ARG
+        );
+    }
+
+    #[test]
+    fn arena_outlives_its_thread_and_frees_from_another() {
+        // The opening thread exits without freeing: its buffer stays valid.
+        let (arena, buf) = std::thread::spawn(|| {
+            let mut arena: *mut ZkpArena = ptr::null_mut();
+            assert_eq!(unsafe { zkp_arena_new(&mut arena) }, ZKP_OK);
+            let buf = zkp_alloc(4).cast::<u8>();
+            unsafe { ptr::write_bytes(buf, 0xab, 4) };
+            (arena as usize, buf as usize)
+        })
+        .join()
+        .unwrap();
+        let arena = arena as *mut ZkpArena;
+        assert_eq!(arena_buffers(arena), Some(1));
+        let bytes = unsafe { slice::from_raw_parts(buf as *const u8, 4) };
+        assert_eq!(bytes, &[0xab; 4]);
+        assert_eq!(zkp_arena_free(arena), ZKP_OK);
+    }
+
+    #[test]
+    fn arena_freed_elsewhere_stops_receiving_allocations() {
+        let mut arena: *mut ZkpArena = ptr::null_mut();
+        assert_eq!(unsafe { zkp_arena_new(&mut arena) }, ZKP_OK);
+        let _owned = zkp_alloc(8);
+        let handle = arena as usize;
+        let code = std::thread::spawn(move || zkp_arena_free(handle as *mut ZkpArena))
+            .join()
+            .unwrap();
+        assert_eq!(code, ZKP_OK);
+        assert_eq!(arena_buffers(arena), None);
+
+        // This thread's next allocation is tracked individually, not lost.
+        let tracked = zkp_alloc(8);
+        assert!(is_tracked(tracked));
+        zkp_free(tracked);
+        assert!(ARENAS.with(|arenas| arenas.borrow().is_empty()));
+    }
 }
@@ -30,8 +30,65 @@ type VerifyFn = unsafe extern "C" fn(
     u64,
     *mut *mut c_char,
 ) -> i32;
+type ProveWFn = unsafe extern "C" fn(
+    *const u16,
+    *const u16,
+    *const u16,
+    u32,
+    *const u16,
+    *const u16,
+    *const u16,
+    *mut *mut u8,
+    *mut u64,
+    *mut *mut c_char,
+) -> i32;
+type VerifyWFn = unsafe extern "C" fn(
+    *const u16,
+    *const u16,
+    *const u16,
+    u32,
+    *const u16,
+    *const u16,
+    *const u16,
+    *const u8,
+    u64,
+    *mut *mut c_char,
+) -> i32;
+type ProveIoFn = unsafe extern "C" fn(
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    u32,
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    *mut *mut u8,
+    *mut u64,
+    *mut *mut c_char,
+) -> i32;
+type VerifyIoFn = unsafe extern "C" fn(
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    u32,
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    *const c_char,
+    *const u8,
+    u64,
+    *mut *mut c_char,
+) -> i32;
+type BlindNewFn =
+    unsafe extern "C" fn(*const c_char, *const u8, u64, *mut *mut u8, *mut u64) -> i32;
+type BlindTrackFn = unsafe extern "C" fn(*const c_char, *const u8, u64) -> i32;
+type FindBackendsFn = unsafe extern "C" fn(*const c_char, *mut *mut c_char) -> i32;
+type ListCommitmentsFn = unsafe extern "C" fn(*const c_char, *mut *mut c_char) -> i32;
 type AllocFn = unsafe extern "C" fn(u64) -> *mut c_void;
 type FreeFn = unsafe extern "C" fn(*mut c_void);
+type ArenaNewFn = unsafe extern "C" fn(*mut *mut c_void) -> i32;
+type ArenaFreeFn = unsafe extern "C" fn(*mut c_void) -> i32;
 
 fn workspace_root() -> PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -87,6 +144,12 @@ fn exports_expected_symbols() {
         lib.get::<InitFn>(b"zkp_init\0").expect("zkp_init missing");
         lib.get::<ListFn>(b"zkp_list_backends\0")
             .expect("zkp_list_backends missing");
+        lib.get::<ListFn>(b"zkp_registry_json\0")
+            .expect("zkp_registry_json missing");
+        lib.get::<FindBackendsFn>(b"zkp_find_backends\0")
+            .expect("zkp_find_backends missing");
+        lib.get::<ListCommitmentsFn>(b"zkp_list_commitments\0")
+            .expect("zkp_list_commitments missing");
         lib.get::<ListFn>(b"zkp_list_profiles\0")
             .expect("zkp_list_profiles missing");
         lib.get::<ListFn>(b"zkp_version\0")
@@ -95,9 +158,25 @@ fn exports_expected_symbols() {
             .expect("zkp_prove missing");
         lib.get::<VerifyFn>(b"zkp_verify\0")
             .expect("zkp_verify missing");
+        lib.get::<ProveWFn>(b"zkp_prove_w\0")
+            .expect("zkp_prove_w missing");
+        lib.get::<VerifyWFn>(b"zkp_verify_w\0")
+            .expect("zkp_verify_w missing");
+        lib.get::<ProveIoFn>(b"zkp_prove_with_io_encoding\0")
+            .expect("zkp_prove_with_io_encoding missing");
+        lib.get::<VerifyIoFn>(b"zkp_verify_with_io_encoding\0")
+            .expect("zkp_verify_with_io_encoding missing");
+        lib.get::<BlindNewFn>(b"zkp_blind_new\0")
+            .expect("zkp_blind_new missing");
+        lib.get::<BlindTrackFn>(b"zkp_blind_track\0")
+            .expect("zkp_blind_track missing");
         lib.get::<AllocFn>(b"zkp_alloc\0")
             .expect("zkp_alloc missing");
         lib.get::<FreeFn>(b"zkp_free\0").expect("zkp_free missing");
+        lib.get::<ArenaNewFn>(b"zkp_arena_new\0")
+            .expect("zkp_arena_new missing");
+        lib.get::<ArenaFreeFn>(b"zkp_arena_free\0")
+            .expect("zkp_arena_free missing");
     }
 }
 
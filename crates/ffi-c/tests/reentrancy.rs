@@ -0,0 +1,197 @@
+//! N threads × M prove/verify/list cycles through the C entry points, all
+//! starting at once so the first calls also race the runtime setup.
+//!
+//! CI runs this file under ThreadSanitizer, which fails on any data race:
+//!
+//! ```text
+//! RUSTFLAGS=-Zsanitizer=thread cargo +nightly test -Zbuild-std \
+//!     --target x86_64-unknown-linux-gnu -p zkprov-ffi-c --test reentrancy
+//! ```
+
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::Path;
+use std::ptr;
+use std::sync::Barrier;
+
+use serde_json::Value;
+use zkprov::*;
+
+const THREADS: usize = 8;
+const CYCLES: usize = 4;
+
+/// Take ownership of a JSON string returned by the FFI.
+fn take_json(ptr: *mut c_char) -> Value {
+    assert!(!ptr.is_null());
+    let json = serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+    zkp_free(ptr.cast());
+    json
+}
+
+fn list(f: unsafe extern "C" fn(*mut *mut c_char) -> i32) -> Value {
+    let mut out = ptr::null_mut();
+    assert_eq!(unsafe { f(&mut out) }, ZKP_OK);
+    take_json(out)
+}
+
+struct Args {
+    backend: CString,
+    field: CString,
+    hash: CString,
+    profile: CString,
+    air: CString,
+    inputs: CString,
+}
+
+impl Args {
+    fn new() -> Self {
+        let air = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/air/toy.air");
+        Self {
+            backend: CString::new("native@0.0").unwrap(),
+            field: CString::new("Prime254").unwrap(),
+            hash: CString::new("blake3").unwrap(),
+            profile: CString::new("balanced").unwrap(),
+            air: CString::new(air.to_str().unwrap()).unwrap(),
+            inputs: CString::new(r#"{"a":1,"b":[2,3]}"#).unwrap(),
+        }
+    }
+
+    /// Prove, returning the proof buffer (still owned by the caller) and the
+    /// digest from the metadata.
+    fn prove(&self) -> (*mut u8, u64, String) {
+        let mut proof = ptr::null_mut();
+        let mut proof_len = 0u64;
+        let mut meta = ptr::null_mut();
+        let status = unsafe {
+            zkp_prove(
+                self.backend.as_ptr(),
+                self.field.as_ptr(),
+                self.hash.as_ptr(),
+                2,
+                self.profile.as_ptr(),
+                self.air.as_ptr(),
+                self.inputs.as_ptr(),
+                &mut proof,
+                &mut proof_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        let digest = take_json(meta)["digest"].as_str().unwrap().to_string();
+        (proof, proof_len, digest)
+    }
+
+    fn verify(&self, proof: *const u8, proof_len: u64) -> String {
+        let mut meta = ptr::null_mut();
+        let status = unsafe {
+            zkp_verify(
+                self.backend.as_ptr(),
+                self.field.as_ptr(),
+                self.hash.as_ptr(),
+                2,
+                self.profile.as_ptr(),
+                self.air.as_ptr(),
+                self.inputs.as_ptr(),
+                proof,
+                proof_len,
+                &mut meta,
+            )
+        };
+        assert_eq!(status, ZKP_OK);
+        take_json(meta)["digest"].as_str().unwrap().to_string()
+    }
+}
+
+#[test]
+fn concurrent_prove_verify_list_cycles_agree() {
+    let start = Barrier::new(THREADS);
+    let results: Vec<(Vec<String>, Vec<usize>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let start = &start;
+                scope.spawn(move || {
+                    let args = Args::new();
+                    start.wait();
+                    // Half the threads act like a Rust host touching the
+                    // registry directly while the others initialize the FFI.
+                    if t % 2 == 0 {
+                        zkprov_corelib::registry::ensure_builtins_registered();
+                    } else {
+                        assert_eq!(zkp_init(), ZKP_OK);
+                    }
+
+                    let mut digests = Vec::new();
+                    let mut handed_off = Vec::new();
+                    for cycle in 0..CYCLES {
+                        let backends = list(zkp_list_backends);
+                        assert!(backends
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .any(|b| b["id"] == "native@0.0"));
+                        let profiles = list(zkp_list_profiles);
+                        assert!(profiles
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .any(|p| p["id"] == "balanced"));
+                        let version = list(zkp_version);
+                        assert_eq!(version["version"], env!("CARGO_PKG_VERSION"));
+
+                        let (proof, proof_len, digest) = args.prove();
+                        assert_eq!(args.verify(proof, proof_len), digest);
+                        digests.push(digest);
+                        if cycle % 2 == 0 {
+                            zkp_free(proof.cast());
+                        } else {
+                            // Freed by the main thread below.
+                            handed_off.push(proof as usize);
+                        }
+
+                        // Arena-owned buffers on this thread only.
+                        let mut arena = ptr::null_mut();
+                        assert_eq!(unsafe { zkp_arena_new(&mut arena) }, ZKP_OK);
+                        let (proof, proof_len, digest) = args.prove();
+                        assert_eq!(args.verify(proof, proof_len), digest);
+                        assert_eq!(zkp_arena_free(arena), ZKP_OK);
+                    }
+                    (digests, handed_off)
+                })
+            })
+            .collect();
+        workers.into_iter().map(|w| w.join().unwrap()).collect()
+    });
+
+    let first = results[0].0[0].clone();
+    for (digests, handed_off) in results {
+        assert_eq!(digests.len(), CYCLES);
+        assert!(digests.iter().all(|d| *d == first));
+        for proof in handed_off {
+            zkp_free(proof as *mut c_void);
+        }
+    }
+}
+
+#[test]
+fn racing_blind_tracks_record_a_blind_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = CString::new(dir.path().join("blinds.log").to_str().unwrap()).unwrap();
+    let blind = [0x5au8; 32];
+    let start = Barrier::new(THREADS);
+
+    let statuses: Vec<i32> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                scope.spawn(|| {
+                    start.wait();
+                    unsafe { zkp_blind_track(log.as_ptr(), blind.as_ptr(), blind.len() as u64) }
+                })
+            })
+            .collect();
+        workers.into_iter().map(|w| w.join().unwrap()).collect()
+    });
+
+    assert_eq!(statuses.iter().filter(|&&s| s == ZKP_OK).count(), 1);
+    assert!(statuses
+        .iter()
+        .all(|&s| s == ZKP_OK || s == ZKP_ERR_INVALID_ARG));
+}
@@ -0,0 +1,131 @@
+use zkprov_corelib::errors::{CapabilityError, ProveError, RegistryError, VerifyError};
+
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorCode {
+    Ok = 0,
+    InvalidArg = 1,
+    Backend = 2,
+    Profile = 3,
+    ProofCorrupt = 4,
+    VerifyFail = 5,
+    Internal = 6,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 7] = [
+        ErrorCode::Ok,
+        ErrorCode::InvalidArg,
+        ErrorCode::Backend,
+        ErrorCode::Profile,
+        ErrorCode::ProofCorrupt,
+        ErrorCode::VerifyFail,
+        ErrorCode::Internal,
+    ];
+
+    #[inline]
+    pub const fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Snake-case name, e.g. `proof_corrupt` for `ZKP_ERR_PROOF_CORRUPT`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::Ok => "ok",
+            ErrorCode::InvalidArg => "invalid_arg",
+            ErrorCode::Backend => "backend",
+            ErrorCode::Profile => "profile",
+            ErrorCode::ProofCorrupt => "proof_corrupt",
+            ErrorCode::VerifyFail => "verify_fail",
+            ErrorCode::Internal => "internal",
+        }
+    }
+
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.code() == code)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == name)
+    }
+}
+
+impl From<ErrorCode> for i32 {
+    fn from(code: ErrorCode) -> Self {
+        code.code()
+    }
+}
+
+pub const ZKP_OK: i32 = ErrorCode::Ok.code();
+pub const ZKP_ERR_INVALID_ARG: i32 = ErrorCode::InvalidArg.code();
+pub const ZKP_ERR_BACKEND: i32 = ErrorCode::Backend.code();
+pub const ZKP_ERR_PROFILE: i32 = ErrorCode::Profile.code();
+pub const ZKP_ERR_PROOF_CORRUPT: i32 = ErrorCode::ProofCorrupt.code();
+pub const ZKP_ERR_VERIFY_FAIL: i32 = ErrorCode::VerifyFail.code();
+pub const ZKP_ERR_INTERNAL: i32 = ErrorCode::Internal.code();
+
+impl From<&CapabilityError> for ErrorCode {
+    fn from(err: &CapabilityError) -> Self {
+        match err {
+            CapabilityError::ProfileNotFound(_)
+            | CapabilityError::ProfileOverrideInvalid { .. }
+            | CapabilityError::ProfileUnsigned(_) => ErrorCode::Profile,
+            CapabilityError::Mismatch(_) => ErrorCode::Backend,
+            CapabilityError::OptionsInvalid { .. }
+            | CapabilityError::AirHintConflict { .. }
+            | CapabilityError::AirHintMissing { .. } => ErrorCode::InvalidArg,
+            CapabilityError::FieldUnsupported { .. }
+            | CapabilityError::HashUnsupported { .. }
+            | CapabilityError::FriArityUnsupported { .. }
+            | CapabilityError::RecursionUnavailable { .. }
+            | CapabilityError::TargetUnsupported { .. }
+            | CapabilityError::RequirementUnmet { .. } => ErrorCode::Backend,
+        }
+    }
+}
+
+impl From<&RegistryError> for ErrorCode {
+    fn from(err: &RegistryError) -> Self {
+        match err {
+            RegistryError::DuplicateBackend(_)
+            | RegistryError::AliasConflict { .. }
+            | RegistryError::InvalidSunset(_) => ErrorCode::Internal,
+            RegistryError::BackendNotFound(_) | RegistryError::NoMatchingBackend(_) => {
+                ErrorCode::Backend
+            }
+        }
+    }
+}
+
+impl From<&ProveError> for ErrorCode {
+    fn from(err: &ProveError) -> Self {
+        match err {
+            ProveError::Capability(cap) => cap.into(),
+            ProveError::Registry(reg) => reg.into(),
+            ProveError::Unsupported { .. } => ErrorCode::Backend,
+            ProveError::InvalidInput(_)
+            | ProveError::Unsatisfied { .. }
+            | ProveError::BoundaryUnsatisfied { .. }
+            | ProveError::MemoryBudget { .. } => ErrorCode::InvalidArg,
+            ProveError::TimedOut { .. } | ProveError::Backend(_) => ErrorCode::Internal,
+        }
+    }
+}
+
+impl From<&VerifyError> for ErrorCode {
+    fn from(err: &VerifyError) -> Self {
+        match err {
+            VerifyError::Capability(cap) => cap.into(),
+            VerifyError::Registry(reg) => reg.into(),
+            VerifyError::Unsupported { .. } => ErrorCode::Backend,
+            VerifyError::Malformed(_)
+            | VerifyError::BodyLength { .. }
+            | VerifyError::Segment { .. } => ErrorCode::ProofCorrupt,
+            VerifyError::HeaderBinding { .. }
+            | VerifyError::RootMismatch { .. }
+            | VerifyError::Rejected(_)
+            | VerifyError::Policy(_)
+            | VerifyError::Backend(_) => ErrorCode::VerifyFail,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! JSON shapes shared by every surface that reports results as JSON: the C
+//! ABI (`zkp_*` meta/envelope strings), `zkd ... --json`, and any service
+//! wrapping them.
+//!
+//! Every document is an [`Envelope`]: `schema`, `ok`, `code` and `msg`,
+//! followed by the payload fields, e.g.
+//!
+//! ```json
+//! {"schema":"zkp-ffi/1","ok":true,"code":0,"msg":"OK","verified":true,"digest":"0x..."}
+//! ```
+//!
+//! `schema` changes only on incompatible edits (removed or retyped fields);
+//! adding a payload field keeps `zkp-ffi/1`, so readers must ignore unknown
+//! fields.
+
+use serde::{Deserialize, Serialize};
+use zkprov_corelib::buildinfo::BuildInfo;
+
+mod error;
+
+pub use error::{
+    ErrorCode, ZKP_ERR_BACKEND, ZKP_ERR_INTERNAL, ZKP_ERR_INVALID_ARG, ZKP_ERR_PROFILE,
+    ZKP_ERR_PROOF_CORRUPT, ZKP_ERR_VERIFY_FAIL, ZKP_OK,
+};
+
+/// Schema id stamped on every envelope.
+pub const SCHEMA: &str = "zkp-ffi/1";
+
+/// Envelope keys payloads must not reuse.
+pub const RESERVED_FIELDS: &[&str] = &["schema", "ok", "code", "msg"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope<T = Empty> {
+    pub schema: String,
+    pub ok: bool,
+    /// [`ErrorCode`] as its integer value (`0` on success).
+    pub code: i32,
+    pub msg: String,
+    #[serde(flatten)]
+    pub body: T,
+}
+
+impl<T> Envelope<T> {
+    pub fn ok(body: T) -> Self {
+        Self {
+            schema: SCHEMA.to_string(),
+            ok: true,
+            code: ErrorCode::Ok.code(),
+            msg: "OK".to_string(),
+            body,
+        }
+    }
+
+    pub fn err(code: ErrorCode, msg: impl Into<String>, body: T) -> Self {
+        Self {
+            schema: SCHEMA.to_string(),
+            ok: false,
+            code: code.code(),
+            msg: msg.into(),
+            body,
+        }
+    }
+}
+
+impl<T: Serialize> Envelope<T> {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("FFI envelopes serialize")
+    }
+}
+
+/// Payload of envelopes that carry only the status fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Empty {}
+
+/// Library version, flattened into prove/verify/version payloads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionMeta {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// `zkp_version` and `zkd version --json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionReport {
+    #[serde(flatten)]
+    pub version: VersionMeta,
+    pub build: BuildInfo,
+}
+
+/// `zkp_prove` metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProveMeta {
+    /// EVM digest `D`, `0x`-prefixed hex.
+    pub digest: String,
+    pub proof_len: u64,
+    #[serde(flatten)]
+    pub version: VersionMeta,
+}
+
+/// `zkp_verify` metadata and `zkd verify --json` on success.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyMeta {
+    pub verified: bool,
+    /// EVM digest `D`, `0x`-prefixed hex.
+    pub digest: String,
+    #[serde(flatten)]
+    pub version: VersionMeta,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn envelope_flattens_payload_after_status_fields() {
+        let env = Envelope::ok(VerifyMeta {
+            verified: true,
+            digest: "0x01".to_string(),
+            version: VersionMeta {
+                version: "0.1.0".to_string(),
+                commit: None,
+            },
+        });
+        let json = env.to_json();
+        assert!(
+            json.starts_with(r#"{"schema":"zkp-ffi/1","ok":true,"code":0,"msg":"OK","#),
+            "{json}"
+        );
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["verified"], true);
+        assert!(value.get("commit").is_none());
+        assert_eq!(
+            serde_json::from_str::<Envelope<VerifyMeta>>(&json).unwrap(),
+            env
+        );
+
+        let err = Envelope::err(ErrorCode::VerifyFail, "root mismatch", Empty {});
+        let value: Value = serde_json::from_str(&err.to_json()).unwrap();
+        assert_eq!(value["code"], 5);
+        assert_eq!(value.as_object().unwrap().len(), RESERVED_FIELDS.len());
+    }
+}
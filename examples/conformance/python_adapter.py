@@ -0,0 +1,47 @@
+"""`zkd conformance` adapter for the Python binding.
+
+    zkd conformance -- python3 examples/conformance/python_adapter.py
+
+Reads one request JSON object on stdin and prints one reply on stdout.
+"""
+
+from __future__ import annotations
+
+import json
+import re
+import sys
+
+import zkprov
+
+_ERR = re.compile(r"^\[ZKProv err (-?\d+)\]")
+
+
+def main() -> int:
+    req = json.load(sys.stdin)
+    cfg = dict(
+        backend_id=req["backend"],
+        field=req["field"],
+        hash_id=req["hash"],
+        fri_arity=req["fri_arity"],
+        profile_id=req["profile"],
+        air_path=req["air"],
+        public_inputs_json=req["inputs"],
+    )
+    try:
+        if req["op"] == "prove":
+            proof, _ = zkprov.prove(**cfg)
+            reply = {"code": 0, "proof": proof.hex()}
+        else:
+            zkprov.verify(proof=bytes.fromhex(req["proof"]), **cfg)
+            reply = {"code": 0}
+    except RuntimeError as e:
+        m = _ERR.match(str(e))
+        if not m:
+            raise
+        reply = {"code": int(m.group(1)), "msg": str(e)}
+    print(json.dumps(reply))
+    return 0
+
+
+if __name__ == "__main__":
+    raise SystemExit(main())
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+/// Verifies a batch of proof digests (D) against an aggregate Merkle root.
+/// Tree rules match `zkprov_corelib::evm::aggregate`:
+///   leaf = keccak256(0x00 || D), node = keccak256(0x01 || left || right),
+///   an odd trailing node is carried up unchanged.
+contract AggregatorVerifier {
+    struct EvmAggregate {
+        bytes32 root;
+        uint64 count;
+        bytes32[] leaves;
+    }
+
+    function leafHash(bytes32 digest) public pure returns (bytes32) {
+        return keccak256(abi.encodePacked(bytes1(0x00), digest));
+    }
+
+    function computeRoot(bytes32[] memory digests) public pure returns (bytes32) {
+        require(digests.length > 0, "empty aggregate");
+        uint256 n = digests.length;
+        bytes32[] memory level = new bytes32[](n);
+        for (uint256 i = 0; i < n; i++) {
+            level[i] = leafHash(digests[i]);
+        }
+        while (n > 1) {
+            uint256 next = (n + 1) / 2;
+            for (uint256 i = 0; i < next; i++) {
+                uint256 l = 2 * i;
+                if (l + 1 < n) {
+                    level[i] = keccak256(abi.encodePacked(bytes1(0x01), level[l], level[l + 1]));
+                } else {
+                    level[i] = level[l];
+                }
+            }
+            n = next;
+        }
+        return level[0];
+    }
+
+    function verifyAggregate(bytes32 root, uint64 count, bytes32[] calldata digests)
+        public
+        pure
+        returns (bool)
+    {
+        return digests.length == count && computeRoot(digests) == root;
+    }
+
+    /// Accepts the ABI-encoded `EvmAggregate` produced by `zkd evm-digest --aggregate`.
+    function verifyEncoded(bytes calldata encoded) external pure returns (bool) {
+        EvmAggregate memory agg = abi.decode(encoded, (EvmAggregate));
+        return agg.leaves.length == agg.count && computeRoot(agg.leaves) == agg.root;
+    }
+}
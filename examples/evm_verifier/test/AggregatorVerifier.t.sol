@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.24;
+
+import "../contracts/AggregatorVerifier.sol";
+
+interface Vm {
+    function readFile(string calldata path) external view returns (string memory);
+    function readFileBinary(string calldata path) external view returns (bytes memory);
+    function parseJson(string calldata json, string calldata key) external pure returns (bytes memory);
+}
+
+contract AggregatorVerifierTest {
+    // hevm cheatcodes address
+    Vm private constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    AggregatorVerifier private verifier = new AggregatorVerifier();
+
+    function testRootMatchesFixture() public {
+        string memory fixture = vm.readFile("testdata/aggregate.json");
+        bytes32 root = abi.decode(vm.parseJson(fixture, ".root"), (bytes32));
+        uint64 count = abi.decode(vm.parseJson(fixture, ".count"), (uint64));
+        bytes32[] memory leaves = abi.decode(vm.parseJson(fixture, ".leaves"), (bytes32[]));
+
+        require(verifier.computeRoot(leaves) == root, "root mismatch");
+        require(verifier.verifyAggregate(root, count, leaves), "aggregate rejected");
+
+        leaves[0] = bytes32(uint256(leaves[0]) ^ 1);
+        require(!verifier.verifyAggregate(root, count, leaves), "tampered leaf accepted");
+    }
+
+    function testRustEncodedAggregateVerifies() public {
+        bytes memory encoded = vm.readFileBinary("testdata/aggregate.abi");
+        require(verifier.verifyEncoded(encoded), "encoded aggregate rejected");
+    }
+}
@@ -0,0 +1,81 @@
+#pragma once
+
+#include <stdint.h>
+
+/*
+ * Backend plugin ABI. zkd loads every shared library in the directories
+ * listed in ZKD_BACKEND_PATH (separated like PATH) and calls its
+ * zkd_register_backend entry point once. See crates/backends/plugin-example
+ * for a complete plugin.
+ *
+ * Thread-safety: prove, verify and free_buf may be called from any number of
+ * threads at once. None of them may unwind or longjmp across the boundary.
+ */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Newest ABI revision described by this header. */
+#define ZKD_BACKEND_ABI_VERSION 1
+
+/* Status codes. */
+#define ZKD_BACKEND_OK 0
+#define ZKD_BACKEND_ERR_INVALID_INPUT 1
+#define ZKD_BACKEND_ERR_UNSUPPORTED 2
+#define ZKD_BACKEND_ERR_REJECTED 3
+#define ZKD_BACKEND_ERR_INTERNAL 4
+#define ZKD_BACKEND_ERR_ABI 5
+
+/*
+ * Bytes allocated by the plugin: a proof body on success, a UTF-8 error
+ * message otherwise. The host copies them and hands the buffer back to
+ * free_buf. A plugin with nothing to say leaves ptr NULL.
+ */
+typedef struct zkd_buf {
+    uint8_t *ptr;
+    uint64_t len;
+} zkd_buf;
+
+/*
+ * Function table of ABI revision 1. The table and both strings must stay
+ * valid while the library is loaded.
+ *
+ * Requests are UTF-8 JSON objects:
+ *   {"abi_version": 1, "backend_id": "...", "air": {...}, "profile": {...},
+ *    "hash": "<hash id of the run>", "inputs": <canonical public inputs>}
+ * The host writes and checks the proof header; plugins handle the body only.
+ */
+typedef struct zkd_backend_v1 {
+    /* Revision this table implements, within the host's range. */
+    uint32_t abi_version;
+    /* "name@version", NUL-terminated. */
+    const char *id;
+    /*
+     * Capabilities as a NUL-terminated JSON object: fields, hashes,
+     * fri_arities, recursion, lookups, curves, pedersen and optionally
+     * max_rows and nondeterminism (see `zkd backend-ls --json`).
+     */
+    const char *capabilities_json;
+    /* ZKD_BACKEND_OK with the proof body in *out, or an error code. */
+    int32_t (*prove)(const uint8_t *request, uint64_t request_len, zkd_buf *out);
+    /*
+     * ZKD_BACKEND_OK when body proves the request, ZKD_BACKEND_ERR_REJECTED
+     * when it does not, or another error code.
+     */
+    int32_t (*verify)(const uint8_t *request, uint64_t request_len,
+                      const uint8_t *body, uint64_t body_len, zkd_buf *out);
+    void (*free_buf)(zkd_buf buf);
+} zkd_backend_v1;
+
+/**
+ * Entry point every plugin exports. The host speaks ABI revisions
+ * host_min..=host_max; the plugin stores a table for one of them in *out and
+ * returns ZKD_BACKEND_OK, or returns ZKD_BACKEND_ERR_ABI when it speaks none.
+ */
+int32_t zkd_register_backend(uint32_t host_min, uint32_t host_max,
+                             const zkd_backend_v1 **out);
+
+#ifdef __cplusplus
+}
+#endif
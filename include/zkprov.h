@@ -9,15 +9,22 @@
  *   - macOS ships libzkprov as a dynamic library (libzkprov.dylib).
  *   - Android distributions embed the .so inside the application package.
  *
- * Thread-safety: the exported functions are re-entrant, but the prover runtime
- * maintains global trackers that are not synchronized. Callers must
- * synchronize access if those trackers are shared across threads.
+ * Thread-safety: every exported function is re-entrant and may be called
+ * from any number of threads at once, including before zkp_init returns on
+ * another thread. Buffers and arenas may be freed on any thread.
  */
 
 #ifdef __cplusplus
 extern "C" {
 #endif
 
+/*
+ * ABI revision. Symbols added after revision 1 say which revision introduced
+ * them; code built against an older header, or loading an older library,
+ * should test for them (#if / dlsym) before use.
+ */
+#define ZKP_ABI_VERSION 2
+
 /* Error codes (see task 0.9.B). */
 #define ZKP_OK 0
 #define ZKP_ERR_INVALID_ARG 1
@@ -27,6 +34,9 @@ extern "C" {
 #define ZKP_ERR_VERIFY_FAIL 5
 #define ZKP_ERR_INTERNAL 6
 
+/* Opaque arena handle (see zkp_arena_new). */
+typedef struct zkp_arena zkp_arena;
+
 /**
  * Initialize the prover runtime. This function is idempotent and does not
  * allocate memory on success. Returns ZKP_OK on success or an error code on
@@ -44,6 +54,36 @@ int32_t zkp_init(void);
  */
 int32_t zkp_list_backends(char **out_json);
 
+/**
+ * Retrieve the whole backend registry as JSON: `backends` (id, aliases,
+ * deprecation, full `capabilities`, `placeholder`) and `aliases`
+ * (alias -> target). Ownership of *out_json is as for zkp_list_backends.
+ */
+int32_t zkp_registry_json(char **out_json);
+
+/**
+ * Retrieve the ids of backends matching a capability query, as a JSON array.
+ *
+ * `query_json` is an object with any of `field`, `hash`, `fri_arity`,
+ * `recursion` (bool), `lookups`, `pedersen`, `curve`, `min_rows`; unset keys
+ * match anything and unknown keys are rejected with ZKP_ERR_INVALID_ARG.
+ * Ownership of *out_json is as for zkp_list_backends.
+ */
+int32_t zkp_find_backends(const char *query_json, char **out_json);
+
+#if ZKP_ABI_VERSION >= 2
+/**
+ * Retrieve the commitment schemes a backend supports (since ABI 2).
+ *
+ * *out_json receives `{"backend": id, "commitments": [...]}` where each entry
+ * has `kind` ("pedersen", "poseidon_commit", "keccak_commit"), `curves` and
+ * `hashes`, derived from the backend's capabilities. backend_id may be an
+ * alias; unknown backends return ZKP_ERR_BACKEND. Ownership of *out_json is
+ * as for zkp_list_backends.
+ */
+int32_t zkp_list_commitments(const char *backend_id, char **out_json);
+#endif
+
 /**
  * Retrieve a JSON description of the available proving profiles.
  *
@@ -58,8 +98,9 @@ int32_t zkp_list_profiles(char **out_json);
  * Retrieve the semantic version of the prover runtime.
  *
  * On success, *out_json receives a heap-allocated, NUL-terminated UTF-8 JSON
- * string (envelope with `version` and optional metadata). The caller must free
- * any non-NULL value via zkp_free. On failure, *out_json is set to NULL.
+ * string (envelope with `version`, optional `commit`, and a `build` object
+ * carrying git commit, target, cargo profile, rustc version and features).
+ * The caller must free any non-NULL value via zkp_free. On failure, *out_json is set to NULL.
  */
 int32_t zkp_version(char **out_json);
 
@@ -94,7 +135,9 @@ int32_t zkp_prove(
  * Verify a proof previously produced by zkp_prove.
  *
  * Parameters and ownership rules mirror zkp_prove. The proof_ptr/proof_len pair
- * must reference caller-owned proof bytes. On success, *out_json_meta receives a
+ * must reference caller-owned proof bytes. Zero-copy: the buffer is only read
+ * in place for the duration of the call (it may be a read-only memory-mapped
+ * file) and nothing proportional to proof_len is allocated. On success, *out_json_meta receives a
  * heap-allocated, NUL-terminated UTF-8 string that the caller must free with
  * zkp_free when finished. On failure, *out_json_meta is set to NULL.
  */
@@ -111,6 +154,111 @@ int32_t zkp_verify(
     char **out_json_meta
 );
 
+/**
+ * zkp_prove / zkp_verify with an explicit public IO encoding.
+ *
+ * pubio_encoding selects which bytes the proof header's pubio_hash binds:
+ *   - "json"  (v1): the public_inputs_json text verbatim (same as zkp_prove)
+ *   - "typed" (v2): abi.encode of the AIR's declared [[public_inputs]], so
+ *                   key order and formatting of the JSON no longer matter
+ * Any other value returns ZKP_ERR_INVALID_ARG. Everything else mirrors
+ * zkp_prove / zkp_verify.
+ */
+int32_t zkp_prove_with_io_encoding(
+    const char *backend_id,
+    const char *field,
+    const char *hash_id,
+    uint32_t fri_arity,
+    const char *profile_id,
+    const char *air_path,
+    const char *public_inputs_json,
+    const char *pubio_encoding,
+    uint8_t **out_proof,
+    uint64_t *out_proof_len,
+    char **out_json_meta
+);
+
+int32_t zkp_verify_with_io_encoding(
+    const char *backend_id,
+    const char *field,
+    const char *hash_id,
+    uint32_t fri_arity,
+    const char *profile_id,
+    const char *air_path,
+    const char *public_inputs_json,
+    const char *pubio_encoding,
+    const uint8_t *proof_ptr,
+    uint64_t proof_len,
+    char **out_json_meta
+);
+
+/**
+ * UTF-16 variants of zkp_prove and zkp_verify for hosts whose native string
+ * type is UTF-16 (.NET, Win32 wchar_t). String arguments must be non-empty,
+ * NUL-terminated UTF-16 sequences; unpaired surrogates are rejected with
+ * ZKP_ERR_INVALID_ARG. All other parameters, return codes, and ownership
+ * rules are identical to the UTF-8 entry points, and the JSON written to
+ * *out_json_meta is still UTF-8.
+ */
+int32_t zkp_prove_w(
+    const uint16_t *backend_id,
+    const uint16_t *field,
+    const uint16_t *hash_id,
+    uint32_t fri_arity,
+    const uint16_t *profile_id,
+    const uint16_t *air_path,
+    const uint16_t *public_inputs_json,
+    uint8_t **out_proof,
+    uint64_t *out_proof_len,
+    char **out_json_meta
+);
+
+int32_t zkp_verify_w(
+    const uint16_t *backend_id,
+    const uint16_t *field,
+    const uint16_t *hash_id,
+    uint32_t fri_arity,
+    const uint16_t *profile_id,
+    const uint16_t *air_path,
+    const uint16_t *public_inputs_json,
+    const uint8_t *proof_ptr,
+    uint64_t proof_len,
+    char **out_json_meta
+);
+
+/**
+ * Generate a 32-byte Pedersen blinding factor.
+ *
+ * When master_ptr is NULL the blind comes from the OS CSPRNG and label is
+ * ignored. Otherwise the blind is derived with HKDF-SHA256 from the
+ * master_len-byte master secret (at least 32 bytes) and the non-empty,
+ * NUL-terminated UTF-8 label, so the same label always yields the same blind.
+ * On success, *out_blind receives a heap-allocated buffer the caller must
+ * release with zkp_free, and *out_blind_len receives its length.
+ */
+int32_t zkp_blind_new(
+    const char *label,
+    const uint8_t *master_ptr,
+    uint64_t master_len,
+    uint8_t **out_blind,
+    uint64_t *out_blind_len
+);
+
+/**
+ * Record a blinding factor in the append-only blinding log at log_path
+ * (created if missing) so it cannot be used again, even by a later process.
+ * The log stores BLAKE3 fingerprints, never the blinds themselves. Returns
+ * ZKP_ERR_INVALID_ARG when the blind is already recorded and
+ * ZKP_ERR_INTERNAL when the log cannot be read or written. Calls within one
+ * process are serialized, so when threads race to record the same blind
+ * exactly one of them succeeds.
+ */
+int32_t zkp_blind_track(
+    const char *log_path,
+    const uint8_t *blind_ptr,
+    uint64_t blind_len
+);
+
 /**
  * Allocate a buffer owned by the prover runtime. Callers must eventually
  * release any non-NULL pointer returned from this function with zkp_free.
@@ -125,6 +273,25 @@ void *zkp_alloc(uint64_t nbytes);
  */
 void zkp_free(void *ptr);
 
+/**
+ * Open an allocation arena on the calling thread and store its handle in
+ * *out_arena. While the arena is open, every buffer the runtime returns on
+ * this thread (proofs, JSON strings, zkp_alloc) is owned by the arena instead
+ * of being tracked individually; zkp_free still releases one of them early.
+ * Arenas nest: new allocations go to the most recently opened arena. Only
+ * the opening thread allocates into an arena, but the arena and its buffers
+ * stay valid after that thread exits, until zkp_arena_free.
+ */
+int32_t zkp_arena_new(zkp_arena **out_arena);
+
+/**
+ * Release every buffer owned by the arena and close it. May be called on any
+ * thread; the opening thread's later allocations go to its next open arena,
+ * or are tracked individually. Returns ZKP_ERR_INVALID_ARG for unknown or
+ * already-freed handles.
+ */
+int32_t zkp_arena_free(zkp_arena *arena);
+
 #ifdef __cplusplus
 }
 #endif
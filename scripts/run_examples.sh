@@ -7,10 +7,10 @@ TARGET_ROOT="${CARGO_TARGET_DIR:-${ROOT_DIR}/target}"
 OS_NAME="$(uname -s)"
 
 case "$OS_NAME" in
-  Linux*)    SHARED_NAME="libzkprov.so"; STATIC_NAME="libzkprov.a"; LIB_ENV="LD_LIBRARY_PATH"; EXE_SUFFIX="";;
-  Darwin*)   SHARED_NAME="libzkprov.dylib"; STATIC_NAME="libzkprov.a"; LIB_ENV="DYLD_LIBRARY_PATH"; EXE_SUFFIX="";;
+  Linux*)    SHARED_NAME="libzkprov.so"; STATIC_NAME="libzkprov.a";;
+  Darwin*)   SHARED_NAME="libzkprov.dylib"; STATIC_NAME="libzkprov.a";;
   MINGW*|MSYS*|CYGWIN*|Windows_NT)
-             SHARED_NAME="zkprov.dll"; STATIC_NAME="zkprov.lib"; LIB_ENV="PATH"; EXE_SUFFIX=".exe";;
+             SHARED_NAME="zkprov.dll"; STATIC_NAME="zkprov.lib";;
   *) echo "Unsupported platform: $OS_NAME" >&2; exit 1;;
 esac
 
@@ -115,71 +115,14 @@ setup_python_env() {
   _PY_ENV_READY=1
 }
 
-run_c_example() {
-  build_rust_core
-  local shared_lib
-  shared_lib="$(resolve_artifact "$SHARED_NAME")" || return 1
-  local release_dir
-  release_dir="$(dirname "$shared_lib")"
-  local build_dir="$TARGET_ROOT/examples"
-  mkdir -p "$build_dir"
-  local exe_path="$build_dir/roundtrip_c${EXE_SUFFIX}"
-
-  if have_cmd clang; then
-    info "Compiling C harness with clang."
-    local -a clang_args=(
-      "-I$ROOT_DIR/include"
-      "$ROOT_DIR/examples/c/roundtrip.c"
-      "-L$release_dir"
-      -lzkprov
-    )
-    local rpath_flag=""
-    local -a extra_ldflags
-    extra_ldflags=()
-    case "$OS_NAME" in
-      Linux*)
-        rpath_flag="-Wl,-rpath,$release_dir"
-        ;;
-      Darwin*)
-        rpath_flag="-Wl,-rpath,$release_dir"
-        ;;
-      MINGW*|MSYS*|CYGWIN*|Windows_NT)
-        extra_ldflags=(-lws2_32 -luserenv -lntdll)
-        ;;
-      *)
-        rpath_flag=""
-        ;;
-    esac
-    if [[ -n "$rpath_flag" ]]; then
-      clang_args+=("$rpath_flag")
-    fi
-    if [[ ${#extra_ldflags[@]} -gt 0 ]]; then
-      clang_args+=("${extra_ldflags[@]}")
-    fi
-    clang_args+=(-o "$exe_path")
-    clang "${clang_args[@]}"
-  elif have_cmd cl; then
-    info "Compiling C harness with cl."
-    local src_win include_win out_win lib_dir_win
-    src_win="$(cygpath -w "$ROOT_DIR/examples/c/roundtrip.c")"
-    include_win="$(cygpath -w "$ROOT_DIR/include")"
-    out_win="$(cygpath -w "$exe_path")"
-    lib_dir_win="$(cygpath -w "$release_dir")"
-    cmd.exe /C "cl /nologo /I\"$include_win\" \"$src_win\" /Fe:\"$out_win\" /link /LIBPATH:\"$lib_dir_win\" zkprov.lib ws2_32.lib userenv.lib ntdll.lib" >/dev/null
-  else
-    echo "Neither clang nor cl compiler is available for C example." >&2
-    return 1
-  fi
+# C, Flutter and EVM examples are implemented by `zkd examples run`, which does
+# not depend on bash or clang and also runs on Windows.
+run_zkd_example() {
+  "$CARGO_BIN" run -q -p zkd -- examples run --root "$ROOT_DIR" "$1"
+}
 
-  info "Running C harness."
-  case "$LIB_ENV" in
-    PATH)
-      env PATH="$release_dir:$PATH" "$exe_path"
-      ;;
-    *)
-      env "$LIB_ENV=$release_dir" "$exe_path"
-      ;;
-  esac
+run_c_example() {
+  run_zkd_example c
 }
 
 run_node_example() {
@@ -209,24 +152,11 @@ run_python_example() {
 }
 
 run_flutter_example() {
-  build_rust_core
-  if ! have_cmd flutter; then
-    echo "Flutter SDK is required for the Flutter example." >&2
-    return 1
-  fi
-  local shared_lib
-  shared_lib="$(resolve_artifact "$SHARED_NAME")" || return 1
-  local example_dir="$ROOT_DIR/examples/flutter_app"
-  info "Resolving Flutter dependencies."
-  (
-    cd "$example_dir"
-    flutter --suppress-analytics pub get
-  )
-  info "Building Flutter APK (release)."
-  (
-    cd "$example_dir"
-    ZKPROV_LIBRARY_PATH="$shared_lib" flutter --suppress-analytics build apk
-  )
+  run_zkd_example flutter
+}
+
+run_evm_example() {
+  run_zkd_example evm
 }
 
 run_wasm_example() {
@@ -278,6 +208,7 @@ Known examples:
   node      - Node.js bindings roundtrip
   python    - Python bindings roundtrip
   flutter   - Flutter demo application
+  evm       - EVM verifier fixtures (and forge test when available)
   wasm      - WebAssembly smoke test
 USAGE
 }
@@ -287,7 +218,7 @@ if [[ ${1:-} == "-h" || ${1:-} == "--help" ]]; then
   exit 0
 fi
 
-AVAILABLE_EXAMPLES=(c node python flutter wasm)
+AVAILABLE_EXAMPLES=(c node python flutter evm wasm)
 REQUESTED_EXAMPLES=("$@")
 if [[ ${#REQUESTED_EXAMPLES[@]} -eq 0 ]]; then
   REQUESTED_EXAMPLES=("${AVAILABLE_EXAMPLES[@]}")
@@ -308,6 +239,9 @@ for example in "${REQUESTED_EXAMPLES[@]}"; do
     flutter)
       execute_example "$example" "Flutter demo" run_flutter_example
       ;;
+    evm)
+      execute_example "$example" "EVM fixtures" run_evm_example
+      ;;
     wasm)
       execute_example "$example" "WASM smoke" run_wasm_example
       ;;
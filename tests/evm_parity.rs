@@ -46,6 +46,8 @@ fn digest_matches_manual_encoding() {
         profile_id_hash: 0x2222,
         pubio_hash: 0x3333,
         body_len: 3,
+        config_hash: 0x4444,
+        flags: 0,
     };
     let body = vec![0xde, 0xad, 0xbe];
     let digest = digest_D(&header, &body);
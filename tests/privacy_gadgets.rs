@@ -1,5 +1,6 @@
 use zkprov_bundles::{
-    AddUnderCommit, BlindingTracker, PedersenCommit, PedersenCtx, PrivacyError, RangeCheck,
+    AddUnderCommit, BlindingSource, BlindingTracker, CommitEquality, PedersenCommit, PedersenCtx,
+    PrivacyError, RangeCheck,
 };
 use zkprov_corelib::air::AirProgram;
 use zkprov_corelib::air_bindings::Bindings;
@@ -79,6 +80,34 @@ fn positive_add_under_commit() {
     let (_csum, _r12) = AddUnderCommit::run(&ctx, &mut tracker, m1, r1, m2, r2).unwrap();
 }
 
+#[test]
+fn positive_commit_equality_across_blinds_and_hashes() {
+    let (ctx, mut tracker) = ctx_and_tracker();
+    let air: AirProgram = toml::from_str(&toy_air_text(false)).unwrap();
+    let mut b = Bindings::from_air(&air);
+    b.hash_id_for_commitments = Some("keccak256".into());
+    let keccak = PedersenCtx::from_bindings(&b).unwrap();
+
+    let (ca, cb) =
+        CommitEquality::run(&ctx, &keccak, &mut tracker, b"balance=42", b"ra", b"rb").unwrap();
+    assert_ne!(ca, cb);
+    CommitEquality::verify(&ctx, &ca, b"ra", &keccak, &cb, b"rb", b"balance=42").unwrap();
+}
+
+#[test]
+fn negative_commit_equality_different_messages() {
+    let (ctx, mut tracker) = ctx_and_tracker();
+    let ca = ctx.commit(&mut tracker, b"balance=42", b"ra").unwrap();
+    let cb = ctx.commit(&mut tracker, b"balance=43", b"rb").unwrap();
+    let err =
+        CommitEquality::verify(&ctx, &ca, b"ra", &ctx, &cb, b"rb", b"balance=42").unwrap_err();
+    assert_eq!(err, PrivacyError::CommitmentMismatch);
+    // A wrong blind is a mismatch too.
+    let err =
+        CommitEquality::verify(&ctx, &ca, b"rx", &ctx, &ca, b"ra", b"balance=42").unwrap_err();
+    assert_eq!(err, PrivacyError::CommitmentMismatch);
+}
+
 #[test]
 fn negative_blinding_reuse_policy() {
     // Emulate a program that forbids reuse by flipping policy in bindings.
@@ -94,3 +123,32 @@ fn negative_blinding_reuse_policy() {
     let err = ctx.commit(&mut tracker, b"B", b"R").unwrap_err();
     assert_eq!(err, PrivacyError::BlindingReuse);
 }
+
+#[test]
+fn blinding_source_derives_stable_blinds_and_tracks_reuse() {
+    let (ctx, mut tracker) = ctx_and_tracker();
+    assert_eq!(
+        BlindingSource::from_master(&[1u8; 16]).unwrap_err(),
+        PrivacyError::WeakMasterSecret
+    );
+    let source = BlindingSource::from_master(&[1u8; 32]).unwrap();
+    assert_eq!(
+        source.blind("orders/1").unwrap(),
+        source.blind("orders/1").unwrap()
+    );
+
+    let (c, blind) = ctx
+        .commit_with_source(&mut tracker, &source, "orders/1", b"A")
+        .unwrap();
+    assert!(ctx.open(b"A", &blind, &c.cx, &c.cy).unwrap());
+    // Same label in the same session yields the same blind, which is a reuse.
+    let err = ctx
+        .commit_with_source(&mut tracker, &source, "orders/1", b"B")
+        .unwrap_err();
+    assert_eq!(err, PrivacyError::BlindingReuse);
+    ctx.commit_with_source(&mut tracker, &source, "orders/2", b"B")
+        .unwrap();
+
+    let os = BlindingSource::os();
+    assert_ne!(os.blind("").unwrap(), os.blind("").unwrap());
+}
@@ -1,6 +1,8 @@
 use zkprov_corelib::{
-    air::AirProgram,
+    air::{parser::parse_air_str, AirProgram},
     air_bindings::Bindings,
+    backend::PublicInputs,
+    config::Config,
     validation::{ReportMeta, ValidationErrorCode, ValidationReport, Validator},
     zkprov_bundles::{BlindingTracker, PedersenCtx},
 };
@@ -124,6 +126,41 @@ fn range_overflow_sets_code() {
     );
 }
 
+#[test]
+fn commit_equality_sets_code() {
+    let air: AirProgram = toml::from_str(TOY_AIR).expect("toy AIR parses");
+    let mut bindings = Bindings::from_air(&air);
+    bindings.commitments.no_r_reuse = Some(false);
+
+    let ped_ctx = PedersenCtx::from_bindings(&bindings).expect("pedersen ctx");
+    let mut tracker = BlindingTracker::new();
+    let a = ped_ctx.commit(&mut tracker, b"100", b"ra").unwrap();
+    let b = ped_ctx.commit(&mut tracker, b"100", b"rb").unwrap();
+    let other = ped_ctx.commit(&mut tracker, b"101", b"rc").unwrap();
+
+    let mut validator = Validator::new(&bindings);
+    validator.check_commit_equality(b"100", &a, b"ra", &b, b"rb");
+    let report = validator.finalize();
+    assert!(report.ok, "{:?}", report.errors);
+    assert_eq!(report.checks[0].name, "commit_equality");
+
+    let mut validator = Validator::new(&bindings);
+    validator.check_commit_equality(b"100", &a, b"ra", &other, b"rc");
+    let report = validator.finalize();
+    assert!(!report.ok);
+    assert!(!report.commit_passed);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(
+        report.errors[0].code,
+        ValidationErrorCode::CommitmentMismatch
+    );
+    assert_eq!(report.errors[0].context["code_num"], 1008);
+    assert_eq!(
+        report.errors[0].context["operation"],
+        "check_commit_equality"
+    );
+}
+
 #[test]
 fn curve_not_allowed_sets_code() {
     let air: AirProgram = toml::from_str(TOY_AIR).expect("toy AIR parses");
@@ -163,3 +200,109 @@ fn pedersen_disabled_sets_code() {
         ValidationErrorCode::PedersenNotEnabled
     );
 }
+
+#[test]
+fn header_binding_mismatches_get_specific_codes() {
+    zkprov_backend_native::ensure_native_registered();
+    let ir = parse_air_str(TOY_AIR).expect("toy AIR parses");
+    let air: AirProgram = toml::from_str(TOY_AIR).expect("toy AIR parses");
+    let bindings = Bindings::from_air(&air);
+    let config = Config::new("native@latest", "Prime254", "blake3", 2, false, "balanced");
+    let inputs = r#"{"n":5}"#;
+    let proof = zkprov_corelib::prove(&config, &ir, &PublicInputs::from_json(inputs).unwrap())
+        .expect("prove");
+
+    let codes = |config: &Config, inputs: &str| {
+        let mut validator = Validator::new(&bindings);
+        validator.check_header_bindings(&proof.header, config, &ir, inputs);
+        validator
+            .finalize()
+            .errors
+            .into_iter()
+            .map(|e| e.code)
+            .collect::<Vec<_>>()
+    };
+    assert!(codes(&config, inputs).is_empty());
+    assert_eq!(
+        codes(&config, r#"{"n":6}"#),
+        [ValidationErrorCode::PubIoMismatch]
+    );
+    assert_eq!(
+        codes(&config, "not json"),
+        [ValidationErrorCode::InvalidPublicInputs]
+    );
+    let other = Config::new("native@0.0", "Prime254", "blake3", 4, false, "dev-fast");
+    assert_eq!(
+        codes(&other, inputs),
+        [
+            ValidationErrorCode::ProfileIdMismatch,
+            ValidationErrorCode::ConfigHashMismatch
+        ]
+    );
+
+    let mut header = proof.header.clone();
+    header.backend_id_hash ^= 1;
+    let mut validator = Validator::new(&bindings);
+    validator.check_header_bindings(&header, &config, &ir, inputs);
+    let report = validator.finalize();
+    assert!(!report.ok && report.commit_passed);
+    assert_eq!(
+        report.errors[0].code,
+        ValidationErrorCode::BackendIdMismatch
+    );
+    assert_eq!(report.errors[0].context["field"], "backend_id_hash");
+    assert_eq!(report.errors[0].context["backend_id"], "native@0.0");
+}
+
+#[test]
+fn error_codes_are_stable_and_messages_come_from_the_catalog() {
+    use std::collections::HashSet;
+    use zkprov_corelib::validation::catalog::{render, CATALOG};
+    use zkprov_corelib::validation::ValidationError;
+
+    let ids: HashSet<_> = CATALOG.iter().map(|e| e.id).collect();
+    let nums: HashSet<_> = CATALOG.iter().map(|e| e.num).collect();
+    assert_eq!(ids.len(), CATALOG.len());
+    assert_eq!(nums.len(), CATALOG.len());
+    assert_eq!(ValidationErrorCode::BlindingReuse.id(), "blinding_reuse");
+    assert_eq!(ValidationErrorCode::PubIoMismatch.num(), 1103);
+
+    let ctx = serde_json::json!({"field": "pubio_hash", "bits": 8});
+    assert_eq!(
+        render("{field} / {bits} / {missing}", &ctx),
+        "pubio_hash / 8 / {missing}"
+    );
+
+    let err = ValidationError::from_catalog(
+        ValidationErrorCode::InvalidPublicInputs,
+        serde_json::json!({"reason": "not json"}),
+    );
+    assert_eq!(
+        err.msg,
+        "public inputs cannot be bound to the AIR: not json"
+    );
+    assert_eq!(err.context["code"], "invalid_public_inputs");
+    assert_eq!(err.context["code_num"], 1105);
+    let err = ValidationError::new(
+        ValidationErrorCode::BlindingReuse,
+        "custom",
+        serde_json::Value::Null,
+    );
+    assert_eq!(err.context["code"], "blinding_reuse");
+
+    // Validator findings carry the code in their JSON context.
+    let air: AirProgram = toml::from_str(TOY_AIR).expect("toy AIR parses");
+    let mut validator = Validator::new(&Bindings::from_air(&air));
+    validator.config_mut().pedersen_enabled = false;
+    validator.check_commit_point(b"hello", b"blind");
+    let json: serde_json::Value =
+        serde_json::from_str(&validator.finalize().to_json().unwrap()).unwrap();
+    let error = &json["errors"][0];
+    assert_eq!(error["code"], "PedersenNotEnabled");
+    assert_eq!(error["context"]["code"], "pedersen_not_enabled");
+    assert_eq!(error["context"]["code_num"], 1006);
+    assert_eq!(
+        error["msg"],
+        ValidationErrorCode::PedersenNotEnabled.template()
+    );
+}